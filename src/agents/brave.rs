@@ -117,15 +117,19 @@ pub fn search(api_key: &str, query: &str, params: &BraveSearchParams) -> Result<
 /// Formats search results into structured blocks optimized for LLM consumption.
 ///
 /// Each result includes source domain, publication date, main description,
-/// and up to 5 additional context snippets.
-pub fn format_results_for_llm(results: &[BraveSearchResult]) -> String {
+/// and up to 5 additional context snippets. `number` gives each result the
+/// citation number it is shown with.
+pub fn format_results_for_llm(
+    results: &[BraveSearchResult],
+    mut number: impl FnMut(&BraveSearchResult) -> usize,
+) -> String {
     if results.is_empty() {
         return String::new();
     }
 
     let mut blocks = Vec::new();
-    for (index, result) in results.iter().enumerate() {
-        let mut block = format!("[{}] {}", index + 1, result.title.trim());
+    for result in results {
+        let mut block = format!("[{}] {}", number(result), result.title.trim());
 
         // Source and date metadata line
         let domain = result
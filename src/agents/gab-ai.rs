@@ -1,6 +1,8 @@
 use color_eyre::Result;
 
 use crate::agents::openai_compat;
+use crate::agents::retry::{self, RetryNotifier};
+use crate::config::RequestConfig;
 
 const DEFAULT_GAB_BASE_URL: &str = "https://gab.ai/v1";
 
@@ -13,7 +15,9 @@ pub fn chat(
     base_url: &str,
     model: &str,
     messages: &[crate::agents::ChatMessage],
-) -> Result<String> {
+    requests: &RequestConfig,
+    notifier: Option<&RetryNotifier>,
+) -> Result<openai_compat::ChatResponse> {
     let model = model.to_lowercase();
     let request = openai_compat::OpenAIChatRequest {
         model,
@@ -22,15 +26,17 @@ pub fn chat(
         tools: None,
     };
 
-    let client = openai_compat::build_client()?;
+    let client = retry::build_client(requests)?;
     let mut last_error: Option<color_eyre::Report> = None;
     for base in gab_base_candidates(base_url) {
         let url = format!("{}/chat/completions", base.trim_end_matches('/'));
-        let response = client
-            .post(&url)
-            .bearer_auth(api_key)
-            .json(&request)
-            .send();
+        let response = retry::send_with_retry("Gab AI", requests, notifier, || {
+            client
+                .post(&url)
+                .bearer_auth(api_key)
+                .json(&request)
+                .send()
+        });
         match response {
             Ok(response) => {
                 let status = response.status();
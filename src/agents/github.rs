@@ -0,0 +1,261 @@
+use color_eyre::{Result, eyre::eyre};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const REQUEST_CONNECT_TIMEOUT_SECS: u64 = 5;
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+const MAX_LISTED_ITEMS: u8 = 20;
+/// Patch characters kept per changed file in a PR summary
+const PATCH_EXCERPT_CHARS: usize = 600;
+/// Characters of the PR description kept in a summary
+const BODY_EXCERPT_CHARS: usize = 800;
+
+// --- Response structs ---
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    items: Vec<GithubItem>,
+}
+
+/// An issue or pull request from the search API
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubItem {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    pub repository_url: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub draft: Option<bool>,
+    #[serde(default)]
+    pub comments: u64,
+}
+
+impl GithubItem {
+    /// `owner/repo`, taken from the API repository URL
+    #[must_use]
+    pub fn repository(&self) -> &str {
+        self.repository_url
+            .strip_prefix(GITHUB_API_URL)
+            .and_then(|path| path.strip_prefix("/repos/"))
+            .unwrap_or(&self.repository_url)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    user: GithubUser,
+    base: GitRef,
+    head: GitRef,
+    #[serde(default)]
+    mergeable_state: Option<String>,
+    additions: u64,
+    deletions: u64,
+    changed_files: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubUser {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitRef {
+    #[serde(rename = "ref")]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestFile {
+    filename: String,
+    status: String,
+    additions: u64,
+    deletions: u64,
+    #[serde(default)]
+    patch: Option<String>,
+}
+
+// --- Queries ---
+
+/// Which of the user's open items to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkKind {
+    /// Pull requests the user opened
+    PullRequests,
+    /// Pull requests waiting for the user's review
+    Reviews,
+    /// Issues assigned to the user
+    Issues,
+}
+
+impl WorkKind {
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "" | "pr" | "prs" | "pulls" | "pull_requests" => Some(Self::PullRequests),
+            "review" | "reviews" | "review_requests" => Some(Self::Reviews),
+            "issue" | "issues" => Some(Self::Issues),
+            _ => None,
+        }
+    }
+
+    fn search_query(self) -> &'static str {
+        match self {
+            Self::PullRequests => "is:open is:pr author:@me archived:false",
+            Self::Reviews => "is:open is:pr review-requested:@me archived:false",
+            Self::Issues => "is:open is:issue assignee:@me archived:false",
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PullRequests => "open pull requests",
+            Self::Reviews => "pull requests awaiting your review",
+            Self::Issues => "open issues assigned to you",
+        }
+    }
+}
+
+fn client() -> Result<Client> {
+    Ok(Client::builder()
+        .connect_timeout(Duration::from_secs(REQUEST_CONNECT_TIMEOUT_SECS))
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent("kimi")
+        .build()?)
+}
+
+fn get(client: &Client, token: &str, path: &str) -> Result<reqwest::blocking::RequestBuilder> {
+    if token.trim().is_empty() {
+        return Err(eyre!("GitHub token not configured. Add it under Connect"));
+    }
+    Ok(client
+        .get(format!("{}{}", GITHUB_API_URL, path))
+        .bearer_auth(token.trim())
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28"))
+}
+
+/// Lists the user's open pull requests, review requests or issues, most recently updated first
+pub fn list_work(token: &str, kind: WorkKind) -> Result<Vec<GithubItem>> {
+    let client = client()?;
+    let response: SearchResponse = get(&client, token, "/search/issues")?
+        .query(&[
+            ("q", kind.search_query().to_string()),
+            ("sort", "updated".to_string()),
+            ("per_page", MAX_LISTED_ITEMS.to_string()),
+        ])
+        .send()?
+        .error_for_status()?
+        .json()?;
+    Ok(response.items)
+}
+
+/// Parses `owner/repo#123` or a pull request URL into (`owner/repo`, number)
+#[must_use]
+pub fn parse_pull_reference(reference: &str) -> Option<(String, u64)> {
+    let reference = reference.trim();
+    if let Some(path) = reference
+        .strip_prefix("https://github.com/")
+        .or_else(|| reference.strip_prefix("github.com/"))
+    {
+        let mut parts = path.split('/');
+        let owner = parts.next()?;
+        let repo = parts.next()?;
+        let _pull = parts.next().filter(|segment| *segment == "pull")?;
+        let number = parts.next()?.parse().ok()?;
+        return Some((format!("{}/{}", owner, repo), number));
+    }
+    let (repo, number) = reference.split_once('#')?;
+    let (owner, name) = repo.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some((repo.to_string(), number.parse().ok()?))
+}
+
+/// Fetches a pull request with its changed files and returns a compact summary
+/// (description excerpt, per-file stats and the start of each patch) for the model or the chat
+pub fn pull_request_summary(token: &str, repository: &str, number: u64) -> Result<String> {
+    let client = client()?;
+    let path = format!("/repos/{}/pulls/{}", repository, number);
+    let pull: PullRequest = get(&client, token, &path)?.send()?.error_for_status()?.json()?;
+    let files: Vec<PullRequestFile> = get(&client, token, &format!("{}/files", path))?
+        .query(&[("per_page", "100")])
+        .send()?
+        .error_for_status()?
+        .json()?;
+
+    let mut summary = format!(
+        "{}#{}: {}\nBy {} · {} → {} · {}{}",
+        repository,
+        number,
+        pull.title,
+        pull.user.login,
+        pull.head.name,
+        pull.base.name,
+        pull.state,
+        if pull.draft { " (draft)" } else { "" }
+    );
+    if let Some(state) = pull.mergeable_state.filter(|state| state != "unknown") {
+        summary.push_str(&format!(" · mergeable: {}", state));
+    }
+    summary.push_str(&format!(
+        "\n{} file(s) changed, +{} −{}",
+        pull.changed_files, pull.additions, pull.deletions
+    ));
+    if let Some(body) = pull.body.as_deref().map(str::trim).filter(|body| !body.is_empty()) {
+        summary.push_str(&format!("\n\nDescription:\n{}", excerpt(body, BODY_EXCERPT_CHARS)));
+    }
+    summary.push_str("\n\nFiles:");
+    for file in &files {
+        summary.push_str(&format!(
+            "\n- {} ({}, +{} −{})",
+            file.filename, file.status, file.additions, file.deletions
+        ));
+        if let Some(patch) = &file.patch {
+            summary.push_str(&format!("\n{}", excerpt(patch, PATCH_EXCERPT_CHARS)));
+        }
+    }
+    Ok(summary)
+}
+
+fn excerpt(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    format!("{}\n…", cut)
+}
+
+// --- Formatting ---
+
+/// Lists items one per line as `owner/repo#N title (updated date)`
+#[must_use]
+pub fn format_work_items(kind: WorkKind, items: &[GithubItem]) -> String {
+    if items.is_empty() {
+        return format!("No {}.", kind.label());
+    }
+    let mut output = format!("{} {}:", items.len(), kind.label());
+    for item in items {
+        output.push_str(&format!(
+            "\n- {}#{} {}{} (updated {}, {} comment(s)) {}",
+            item.repository(),
+            item.number,
+            item.title,
+            if item.draft == Some(true) { " [draft]" } else { "" },
+            item.updated_at.split('T').next().unwrap_or(&item.updated_at),
+            item.comments,
+            item.html_url
+        ));
+    }
+    output
+}
@@ -1,17 +1,21 @@
 pub mod brave;
 #[path = "gab-ai.rs"]
 pub mod gab_ai;
+pub mod github;
 pub mod ollama;
 #[path = "openai-compat.rs"]
 pub mod openai_compat;
+pub mod retry;
 pub mod venice;
 
 use crate::config::Config;
 use crate::app::ModelSource;
 use color_eyre::Result;
 use ollama::OllamaClient;
+use crate::services::response_cache::ResponseCache;
+use retry::RetryNotifier;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 /// An AI agent with its configuration
 #[derive(Debug, Clone)]
@@ -21,6 +25,10 @@ pub struct Agent {
     pub system_prompt: String,
     pub model_source: ModelSource,
     pub num_gpu: Option<i32>,
+    /// Ollama keep_alive: how long the model stays loaded once idle ("10m", "0", "-1")
+    pub keep_alive: Option<String>,
+    /// Checks each answer with a second call after it is shown
+    pub verify: crate::config::Verification,
 }
 
 /// Manages AI agents and their interaction with the Ollama backend
@@ -31,12 +39,23 @@ pub struct AgentManager {
     venice_api_key: Option<String>,
     gab_api_key: Option<String>,
     gab_base_url: String,
+    warm_up: bool,
+    venice_requests: crate::config::RequestConfig,
+    gab_requests: crate::config::RequestConfig,
+    /// Told about retries so they show as status updates
+    retry_notifier: Option<RetryNotifier>,
+    /// Records hosted requests and enforces daily budgets
+    usage_ledger: Option<Arc<crate::services::usage::UsageLedger>>,
+    /// Shared by every clone, so background jobs reuse each other's replies
+    response_cache: Option<Arc<ResponseCache>>,
+    /// Whether Ollama has the `summary` agent's model, once it has answered
+    summary_model_pulled: Arc<OnceLock<bool>>,
 }
 
 impl AgentManager {
     /// Creates a new agent manager from configuration
     pub fn new(config: &Config) -> Self {
-        let ollama_client = Arc::new(OllamaClient::new(&config.ollama.url));
+        let ollama_client = Arc::new(OllamaClient::new(&config.ollama.url, &config.ollama.requests));
         let mut agents = HashMap::new();
 
         // Load agents from config
@@ -49,6 +68,11 @@ impl AgentManager {
                     system_prompt: agent_config.system_prompt.clone(),
                     model_source: ModelSource::Ollama,
                     num_gpu: agent_config.num_gpu,
+                    keep_alive: agent_config
+                        .keep_alive
+                        .clone()
+                        .or_else(|| config.ollama.keep_alive.clone()),
+                    verify: agent_config.verify,
                 },
             );
         }
@@ -63,6 +87,16 @@ impl AgentManager {
                 Some(config.gab.api_key.clone())
             },
             gab_base_url: config.gab.base_url.clone(),
+            warm_up: config.ollama.warm_up,
+            venice_requests: config.venice.requests.clone(),
+            gab_requests: config.gab.requests.clone(),
+            retry_notifier: None,
+            usage_ledger: None,
+            response_cache: config
+                .cache
+                .enabled
+                .then(|| Arc::new(ResponseCache::new(&config.cache))),
+            summary_model_pulled: Arc::new(OnceLock::new()),
         }
     }
 
@@ -72,6 +106,27 @@ impl AgentManager {
         self.agents.get(name)
     }
 
+    /// The `summary` agent when its model is pulled; None leaves summaries to the chat model.
+    /// Asks Ollama once per manager, so call it off the UI thread.
+    #[must_use]
+    pub fn summary_agent(&self) -> Option<Agent> {
+        let agent = self.agents.get("summary")?;
+        let pulled = match self.summary_model_pulled.get() {
+            Some(pulled) => *pulled,
+            // Not cached when Ollama is down, so a later call can still find the model
+            None => match self.ollama_client.check_model(&agent.model) {
+                Ok(pulled) => {
+                    if !pulled {
+                        tracing::info!(model = %agent.model, "summary model not pulled, using the chat model");
+                    }
+                    *self.summary_model_pulled.get_or_init(|| pulled)
+                }
+                Err(_) => false,
+            },
+        };
+        pulled.then(|| agent.clone())
+    }
+
     /// Checks if an agent is ready to use (Ollama running, model available)
     pub fn check_agent_ready(&self, agent: &Agent) -> Result<String> {
         use std::time::Instant;
@@ -117,23 +172,140 @@ impl AgentManager {
 
     /// Sends a chat request to the agent
     pub fn chat(&self, agent: &Agent, messages: &[ChatMessage]) -> Result<String> {
+        self.chat_response(agent, messages).map(|response| response.content)
+    }
+
+    /// Like `chat`, keeping the token counts the provider reported
+    pub fn chat_response(
+        &self,
+        agent: &Agent,
+        messages: &[ChatMessage],
+    ) -> Result<openai_compat::ChatResponse> {
+        tracing::info!(
+            agent = %agent.name,
+            model = %agent.model,
+            source = ?agent.model_source,
+            messages = messages.len(),
+            "agent request"
+        );
+        let result = self.chat_with_source(agent, messages);
+        if let Err(error) = &result {
+            tracing::warn!(agent = %agent.name, model = %agent.model, %error, "agent request failed");
+        }
+        result
+    }
+
+    fn chat_with_source(
+        &self,
+        agent: &Agent,
+        messages: &[ChatMessage],
+    ) -> Result<openai_compat::ChatResponse> {
         match agent.model_source {
-            ModelSource::Ollama => self.ollama_client.chat(&agent.model, messages, agent.num_gpu),
+            ModelSource::Ollama => self.ollama_client.chat(
+                &agent.model,
+                messages,
+                agent.num_gpu,
+                agent.keep_alive.as_deref(),
+                self.retry_notifier.as_ref(),
+            ),
             ModelSource::VeniceAPI => {
                 let api_key = self
                     .venice_api_key
                     .as_ref()
                     .ok_or_else(|| color_eyre::eyre::eyre!("Venice API key not configured"))?;
-                crate::agents::venice::chat(api_key, &agent.model, messages)
+                self.check_budget("venice")?;
+                let response = crate::agents::venice::chat(
+                    api_key,
+                    &agent.model,
+                    messages,
+                    &self.venice_requests,
+                    self.retry_notifier.as_ref(),
+                )?;
+                self.record_usage("venice", &agent.model, response.usage);
+                Ok(response)
             }
             ModelSource::GabAI => {
                 let api_key = self
                     .gab_api_key
                     .as_ref()
                     .ok_or_else(|| color_eyre::eyre::eyre!("Gab AI key not configured"))?;
-                crate::agents::gab_ai::chat(api_key, &self.gab_base_url, &agent.model, messages)
+                self.check_budget("gab")?;
+                let response = crate::agents::gab_ai::chat(
+                    api_key,
+                    &self.gab_base_url,
+                    &agent.model,
+                    messages,
+                    &self.gab_requests,
+                    self.retry_notifier.as_ref(),
+                )?;
+                self.record_usage("gab", &agent.model, response.usage);
+                Ok(response)
+            }
+        }
+    }
+
+    /// Sends a chat request whose reply must match `schema`. Ollama enforces the schema;
+    /// the hosted APIs get a plain request, so the prompt should still ask for JSON
+    pub fn chat_structured(
+        &self,
+        agent: &Agent,
+        messages: &[ChatMessage],
+        schema: &serde_json::Value,
+    ) -> Result<String> {
+        match agent.model_source {
+            ModelSource::Ollama => {
+                tracing::info!(
+                    agent = %agent.name,
+                    model = %agent.model,
+                    messages = messages.len(),
+                    "agent request with schema"
+                );
+                self.ollama_client.chat_with_format(
+                    &agent.model,
+                    messages,
+                    agent.num_gpu,
+                    agent.keep_alive.as_deref(),
+                    Some(schema.clone()),
+                    self.retry_notifier.as_ref(),
+                )
+                .map(|response| response.content)
             }
+            ModelSource::VeniceAPI | ModelSource::GabAI => self.chat(agent, messages),
+        }
+    }
+
+    /// Like `chat`, for deterministic sub-tasks (summaries, intent classification):
+    /// identical or near-identical prompts get the cached reply instead of a model call
+    pub fn chat_cached(&self, agent: &Agent, messages: &[ChatMessage]) -> Result<String> {
+        self.with_cache(agent, messages, || self.chat(agent, messages))
+    }
+
+    /// `chat_structured` through the response cache
+    pub fn chat_structured_cached(
+        &self,
+        agent: &Agent,
+        messages: &[ChatMessage],
+        schema: &serde_json::Value,
+    ) -> Result<String> {
+        self.with_cache(agent, messages, || self.chat_structured(agent, messages, schema))
+    }
+
+    fn with_cache(
+        &self,
+        agent: &Agent,
+        messages: &[ChatMessage],
+        request: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        let Some(cache) = &self.response_cache else {
+            return request();
+        };
+        if let Some(cached) = cache.get(&agent.model, messages) {
+            tracing::debug!(agent = %agent.name, model = %agent.model, "response cache hit");
+            return Ok(cached);
         }
+        let response = request()?;
+        cache.insert(&agent.model, messages, &response);
+        Ok(response)
     }
 
     /// Sends a chat request with native tool calling support
@@ -146,24 +318,97 @@ impl AgentManager {
     ) -> Result<openai_compat::ChatResponse> {
         match agent.model_source {
             ModelSource::VeniceAPI => {
+                tracing::info!(
+                    agent = %agent.name,
+                    model = %agent.model,
+                    messages = messages.len(),
+                    tools = tools.len(),
+                    "agent request with tools"
+                );
                 let api_key = self
                     .venice_api_key
                     .as_ref()
                     .ok_or_else(|| color_eyre::eyre::eyre!("Venice API key not configured"))?;
-                crate::agents::venice::chat_with_tools(api_key, &agent.model, messages, tools)
+                self.check_budget("venice")?;
+                let response = crate::agents::venice::chat_with_tools(
+                    api_key,
+                    &agent.model,
+                    messages,
+                    tools,
+                    &self.venice_requests,
+                    self.retry_notifier.as_ref(),
+                )?;
+                self.record_usage("venice", &agent.model, response.usage);
+                Ok(response)
             }
             // Ollama and Gab don't support native tool calling -- return text-only response
-            ModelSource::Ollama | ModelSource::GabAI => {
-                let content = self.chat(agent, messages)?;
-                Ok(openai_compat::ChatResponse::text(content))
-            }
+            ModelSource::Ollama | ModelSource::GabAI => self.chat_response(agent, messages),
+        }
+    }
+
+    /// Whether loading an Ollama agent should preload its model
+    #[must_use]
+    pub fn warm_up_enabled(&self) -> bool {
+        self.warm_up
+    }
+
+    /// Preloads an Ollama agent's model; hosted models need no warm-up
+    pub fn warm_up(&self, agent: &Agent) -> Result<()> {
+        if agent.model_source != ModelSource::Ollama {
+            return Ok(());
         }
+        self.ollama_client
+            .load_model(&agent.model, agent.num_gpu, agent.keep_alive.as_deref())
     }
 
     pub fn list_models(&self) -> Result<Vec<String>> {
         self.ollama_client.list_models()
     }
 
+    pub fn list_model_sizes(&self) -> Result<HashMap<String, u64>> {
+        self.ollama_client.list_model_sizes()
+    }
+
+    pub fn show_model(&self, model: &str) -> Result<ollama::ModelDetails> {
+        self.ollama_client.show_model(model)
+    }
+
+    /// Replaces an agent's system prompt (used when the base personality file changes)
+    pub fn set_system_prompt(&mut self, agent_name: &str, system_prompt: &str) {
+        if let Some(agent) = self.agents.get_mut(agent_name) {
+            agent.system_prompt = system_prompt.to_string();
+        }
+    }
+
+    /// Reports retries through `notifier` (e.g. as chat status updates)
+    pub fn set_retry_notifier(&mut self, notifier: RetryNotifier) {
+        self.retry_notifier = Some(notifier);
+    }
+
+    /// Logs tokens and cost of Venice and Gab requests and applies the daily budgets
+    pub fn set_usage_ledger(&mut self, ledger: Arc<crate::services::usage::UsageLedger>) {
+        self.usage_ledger = Some(ledger);
+    }
+
+    /// Keeps incognito chats out of the usage ledger
+    pub fn pause_usage_recording(&self, paused: bool) {
+        if let Some(ledger) = &self.usage_ledger {
+            ledger.set_paused(paused);
+        }
+    }
+
+    fn check_budget(&self, provider: &str) -> Result<()> {
+        self.usage_ledger
+            .as_ref()
+            .map_or(Ok(()), |ledger| ledger.check_budget(provider))
+    }
+
+    fn record_usage(&self, provider: &str, model: &str, usage: openai_compat::TokenUsage) {
+        if let Some(ledger) = &self.usage_ledger {
+            ledger.record(provider, model, usage);
+        }
+    }
+
     pub fn set_venice_api_key(&mut self, api_key: String) {
         self.venice_api_key = Some(api_key);
     }
@@ -190,7 +435,7 @@ pub struct ChatMessage {
 }
 
 /// Role of a message in the conversation
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MessageRole {
     System,
     User,
@@ -1,12 +1,17 @@
+use crate::agents::retry::{self, RetryNotifier};
+use crate::agents::openai_compat::{ChatResponse, TokenUsage};
 use crate::agents::{ChatMessage, MessageRole};
+use crate::config::RequestConfig;
 use color_eyre::Result;
 use reqwest::blocking::Client;
+use std::collections::HashMap;
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
 
 pub struct OllamaClient {
     base_url: String,
     client: Client,
+    requests: RequestConfig,
 }
 
 #[derive(Debug, Serialize)]
@@ -16,6 +21,12 @@ struct OllamaChatRequest {
     stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     options: Option<OllamaOptions>,
+    /// JSON schema the reply must follow (structured outputs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+    /// How long the model stays loaded after this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_alive: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -35,17 +46,79 @@ struct OllamaMessage {
 #[derive(Debug, Deserialize)]
 struct OllamaChatResponse {
     message: OllamaMessage,
+    #[serde(default)]
+    prompt_eval_count: u64,
+    #[serde(default)]
+    eval_count: u64,
+}
+
+/// Size and quantization of an installed model, from /api/show and /api/tags
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelDetails {
+    pub parameter_size: Option<String>,
+    pub parameter_count: Option<u64>,
+    pub quantization: Option<String>,
+    pub size_bytes: Option<u64>,
 }
 
 impl OllamaClient {
-    pub fn new(base_url: &str) -> Self {
+    pub fn new(base_url: &str, requests: &RequestConfig) -> Self {
         Self {
             base_url: base_url.to_string(),
-            client: Client::new(),
+            client: retry::build_client(requests).unwrap_or_default(),
+            requests: requests.clone(),
         }
     }
 
-    pub fn chat(&self, model: &str, messages: &[ChatMessage], num_gpu: Option<i32>) -> Result<String> {
+    pub fn chat(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        num_gpu: Option<i32>,
+        keep_alive: Option<&str>,
+        notifier: Option<&RetryNotifier>,
+    ) -> Result<ChatResponse> {
+        self.chat_with_format(model, messages, num_gpu, keep_alive, None, notifier)
+    }
+
+    /// Loads `model` into memory without generating anything, so the first real reply
+    /// doesn't pay for the cold start
+    pub fn load_model(&self, model: &str, num_gpu: Option<i32>, keep_alive: Option<&str>) -> Result<()> {
+        let request = OllamaChatRequest {
+            model: model.to_string(),
+            messages: Vec::new(),
+            stream: false,
+            options: num_gpu.map(|gpu_layers| OllamaOptions {
+                num_gpu: Some(gpu_layers),
+            }),
+            format: None,
+            keep_alive: keep_alive.map(keep_alive_value),
+        };
+        let response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&request)
+            .send()?;
+        if !response.status().is_success() {
+            return Err(color_eyre::eyre::eyre!(
+                "Ollama failed to load {} ({})",
+                model,
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Chat whose reply is constrained to `format`, a JSON schema, when one is given
+    pub fn chat_with_format(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        num_gpu: Option<i32>,
+        keep_alive: Option<&str>,
+        format: Option<serde_json::Value>,
+        notifier: Option<&RetryNotifier>,
+    ) -> Result<ChatResponse> {
         let ollama_messages: Vec<OllamaMessage> = messages
             .iter()
             .map(|msg| OllamaMessage {
@@ -69,13 +142,14 @@ impl OllamaClient {
             messages: ollama_messages,
             stream: false,
             options,
+            format,
+            keep_alive: keep_alive.map(keep_alive_value),
         };
 
-        let response = self
-            .client
-            .post(format!("{}/api/chat", self.base_url))
-            .json(&request)
-            .send()?;
+        let url = format!("{}/api/chat", self.base_url);
+        let response = retry::send_with_retry("Ollama", &self.requests, notifier, || {
+            self.client.post(&url).json(&request).send()
+        })?;
 
         let status = response.status();
         let body = response.text()?;
@@ -88,7 +162,13 @@ impl OllamaClient {
         }
 
         let chat_response: OllamaChatResponse = serde_json::from_str(&body)?;
-        Ok(chat_response.message.content)
+        Ok(ChatResponse {
+            usage: TokenUsage {
+                prompt_tokens: chat_response.prompt_eval_count,
+                completion_tokens: chat_response.eval_count,
+            },
+            ..ChatResponse::text(chat_response.message.content)
+        })
     }
 
     pub fn is_available(&self) -> bool {
@@ -125,6 +205,81 @@ impl OllamaClient {
         Ok(model_list.models.into_iter().map(|model| model.name).collect())
     }
 
+    /// Returns the on-disk size of every installed model, keyed by name
+    pub fn list_model_sizes(&self) -> Result<HashMap<String, u64>> {
+        #[derive(Deserialize)]
+        struct ModelList {
+            models: Vec<ModelInfo>,
+        }
+
+        #[derive(Deserialize)]
+        struct ModelInfo {
+            name: String,
+            #[serde(default)]
+            size: u64,
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .timeout(Duration::from_secs(2))
+            .send()?;
+
+        if !response.status().is_success() {
+            return Ok(HashMap::new());
+        }
+
+        let model_list: ModelList = response.json()?;
+        Ok(model_list
+            .models
+            .into_iter()
+            .map(|model| (model.name, model.size))
+            .collect())
+    }
+
+    /// Queries /api/show for parameter count and quantization level
+    pub fn show_model(&self, model: &str) -> Result<ModelDetails> {
+        #[derive(Deserialize)]
+        struct ShowResponse {
+            #[serde(default)]
+            details: ShowDetails,
+            #[serde(default)]
+            model_info: HashMap<String, serde_json::Value>,
+        }
+
+        #[derive(Deserialize, Default)]
+        struct ShowDetails {
+            parameter_size: Option<String>,
+            quantization_level: Option<String>,
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/api/show", self.base_url))
+            .json(&serde_json::json!({ "model": model }))
+            .timeout(Duration::from_secs(2))
+            .send()?;
+
+        if !response.status().is_success() {
+            return Err(color_eyre::eyre::eyre!(
+                "Ollama show failed for {} ({})",
+                model,
+                response.status()
+            ));
+        }
+
+        let show: ShowResponse = response.json()?;
+        Ok(ModelDetails {
+            parameter_size: show.details.parameter_size.filter(|value| !value.is_empty()),
+            parameter_count: show
+                .model_info
+                .get("general.parameter_count")
+                .and_then(serde_json::Value::as_u64),
+            quantization: show.details.quantization_level.filter(|value| !value.is_empty()),
+            size_bytes: None,
+        })
+    }
+
     pub fn check_model(&self, model: &str) -> Result<bool> {
         #[derive(Deserialize)]
         struct ModelList {
@@ -153,6 +308,14 @@ impl OllamaClient {
     }
 }
 
+/// Ollama takes seconds as a number (negative = keep loaded) or a duration string like "10m"
+fn keep_alive_value(keep_alive: &str) -> serde_json::Value {
+    let keep_alive = keep_alive.trim();
+    keep_alive
+        .parse::<i64>()
+        .map_or_else(|_| serde_json::Value::from(keep_alive), serde_json::Value::from)
+}
+
 fn model_name_matches(available: &str, requested: &str) -> bool {
     if available == requested || available.starts_with(&format!("{requested}:")) {
         return true;
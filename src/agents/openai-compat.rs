@@ -1,10 +1,8 @@
 //! Shared types and helpers for OpenAI-compatible chat APIs (Venice, Gab, etc.)
 
 use color_eyre::Result;
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::time::Duration;
 
 use crate::agents::{ChatMessage, MessageRole};
 
@@ -49,6 +47,7 @@ pub struct FunctionCallResponse {
 pub struct ChatResponse {
     pub content: String,
     pub tool_calls: Vec<ToolCallResponse>,
+    pub usage: TokenUsage,
 }
 
 impl ChatResponse {
@@ -57,6 +56,7 @@ impl ChatResponse {
         Self {
             content,
             tool_calls: Vec::new(),
+            usage: TokenUsage::default(),
         }
     }
 
@@ -93,6 +93,17 @@ pub struct OpenAIMessage {
 #[derive(Debug, Deserialize)]
 pub struct OpenAIChatResponse {
     pub choices: Vec<OpenAIChoice>,
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Token counts the provider reports for one request (zero when it sends none)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -136,18 +147,24 @@ pub fn convert_messages(messages: &[ChatMessage]) -> Vec<OpenAIMessage> {
         .collect()
 }
 
-/// Extracts the assistant reply from an OpenAI-style response
-pub fn extract_reply(response: OpenAIChatResponse, provider: &str) -> Result<String> {
-    response
+/// Extracts the assistant reply (and token usage) from an OpenAI-style response
+pub fn extract_reply(response: OpenAIChatResponse, provider: &str) -> Result<ChatResponse> {
+    let usage = response.usage.unwrap_or_default();
+    let content = response
         .choices
         .into_iter()
         .next()
         .and_then(|choice| choice.message.content)
-        .ok_or_else(|| color_eyre::eyre::eyre!("{} response missing content", provider))
+        .ok_or_else(|| color_eyre::eyre::eyre!("{} response missing content", provider))?;
+    Ok(ChatResponse {
+        usage,
+        ..ChatResponse::text(content)
+    })
 }
 
 /// Extracts a full ChatResponse (content + tool_calls) from an OpenAI-style response
 pub fn extract_chat_response(response: OpenAIChatResponse, provider: &str) -> Result<ChatResponse> {
+    let usage = response.usage.unwrap_or_default();
     let choice = response
         .choices
         .into_iter()
@@ -157,13 +174,6 @@ pub fn extract_chat_response(response: OpenAIChatResponse, provider: &str) -> Re
     Ok(ChatResponse {
         content: choice.message.content.unwrap_or_default(),
         tool_calls: choice.message.tool_calls.unwrap_or_default(),
+        usage,
     })
 }
-
-/// Builds a `reqwest::blocking::Client` with standard timeouts
-pub fn build_client() -> Result<Client> {
-    Ok(Client::builder()
-        .connect_timeout(Duration::from_secs(10))
-        .timeout(Duration::from_secs(120))
-        .build()?)
-}
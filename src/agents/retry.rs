@@ -0,0 +1,97 @@
+//! Shared timeouts and retry with exponential backoff for the provider HTTP clients
+
+use color_eyre::Result;
+use reqwest::blocking::{Client, Response};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::config::RequestConfig;
+
+/// Longest wait between two attempts, whatever the backoff or Retry-After says
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Receives "retrying" messages so they can be shown as status updates instead of errors
+pub type RetryNotifier = Arc<dyn Fn(String) + Send + Sync>;
+
+/// Builds a client with the provider's connect and request timeouts
+pub fn build_client(requests: &RequestConfig) -> Result<Client> {
+    Ok(Client::builder()
+        .connect_timeout(Duration::from_secs(requests.connect_timeout_secs))
+        .timeout(Duration::from_secs(requests.timeout_secs))
+        .build()?)
+}
+
+/// Calls `send` until it gets a response that is not worth retrying. Connection errors,
+/// 429 and 5xx are retried up to `requests.retries` times with doubling delays (or the
+/// server's Retry-After), as long as the retries fit in one request timeout. Request
+/// timeouts are not retried: the provider may still be generating the reply, and a second
+/// attempt would start another one and multiply the wait. The last response is returned
+/// even if it failed, so the caller can report its status and body.
+pub fn send_with_retry(
+    provider: &str,
+    requests: &RequestConfig,
+    notifier: Option<&RetryNotifier>,
+    mut send: impl FnMut() -> reqwest::Result<Response>,
+) -> Result<Response> {
+    let started = Instant::now();
+    let budget = Duration::from_secs(requests.timeout_secs);
+    let mut attempt: u32 = 0;
+    loop {
+        let result = send();
+        let retry = match &result {
+            Ok(response) if is_retryable_status(response.status().as_u16()) => {
+                Some((format!("returned {}", response.status()), retry_after(response)))
+            }
+            Ok(_) => None,
+            // Connect timeouts count as connection errors: the request never reached the model
+            Err(error) if error.is_connect() => Some(("unreachable".to_string(), None)),
+            Err(error) if error.is_timeout() => None,
+            Err(error) => Some((format!("request failed ({})", error), None)),
+        };
+        let next = retry.map(|(reason, retry_after)| {
+            let delay = retry_after.unwrap_or_else(|| backoff_delay(requests.backoff_ms, attempt + 1));
+            (reason, delay)
+        });
+        let Some((reason, delay)) = next
+            .filter(|(_, delay)| attempt < requests.retries && started.elapsed() + *delay < budget)
+        else {
+            return result.map_err(|error| color_eyre::eyre::eyre!("{} request error: {}", provider, error));
+        };
+        attempt += 1;
+        let message = format!(
+            "{} {}, retrying in {:.1}s ({}/{})",
+            provider,
+            reason,
+            delay.as_secs_f32(),
+            attempt,
+            requests.retries
+        );
+        tracing::warn!(provider, attempt, "{}", message);
+        if let Some(notify) = notifier {
+            notify(message);
+        }
+        std::thread::sleep(delay);
+    }
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+/// `backoff_ms`, doubled for every attempt after the first
+fn backoff_delay(backoff_ms: u64, attempt: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+    Duration::from_millis(backoff_ms.saturating_mul(factor)).min(MAX_BACKOFF)
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|seconds| Duration::from_secs(seconds).min(MAX_BACKOFF))
+}
@@ -1,10 +1,11 @@
 use color_eyre::Result;
 use reqwest::blocking::Client;
 use serde::Deserialize;
-use std::thread::sleep;
 use std::time::Duration;
 
 use crate::agents::openai_compat::{self, ChatResponse, ToolDefinition};
+use crate::agents::retry::{self, RetryNotifier};
+use crate::config::RequestConfig;
 
 const VENICE_MODELS_URL: &str = "https://api.venice.ai/api/v1/models?type=text";
 const VENICE_CHAT_URL: &str = "https://api.venice.ai/api/v1/chat/completions";
@@ -32,63 +33,21 @@ pub fn fetch_text_models(api_key: &str) -> Result<Vec<String>> {
     Ok(payload.data.into_iter().map(|model| model.id).collect())
 }
 
-pub fn chat(api_key: &str, model: &str, messages: &[crate::agents::ChatMessage]) -> Result<String> {
+pub fn chat(
+    api_key: &str,
+    model: &str,
+    messages: &[crate::agents::ChatMessage],
+    requests: &RequestConfig,
+    notifier: Option<&RetryNotifier>,
+) -> Result<ChatResponse> {
     let request = openai_compat::OpenAIChatRequest {
         model: model.to_string(),
         messages: openai_compat::convert_messages(messages),
         stream: false,
         tools: None,
     };
-
-    let client = openai_compat::build_client()?;
-    let mut last_error: Option<color_eyre::Report> = None;
-    let delays = [200, 500, 1000];
-    for (attempt, delay) in delays.iter().enumerate() {
-        let response = client
-            .post(VENICE_CHAT_URL)
-            .bearer_auth(api_key)
-            .json(&request)
-            .send();
-
-        match response {
-            Ok(response) => {
-                let status = response.status();
-                if status.is_success() {
-                    let payload: openai_compat::OpenAIChatResponse = response.json()?;
-                    return openai_compat::extract_reply(payload, "Venice");
-                }
-
-                let details = response.text().unwrap_or_default();
-                if status.as_u16() == 429 || status.as_u16() >= 500 {
-                    last_error = Some(color_eyre::eyre::eyre!(
-                        "Venice API error ({}), retrying... {}",
-                        status,
-                        details
-                    ));
-                } else {
-                    return Err(color_eyre::eyre::eyre!(
-                        "Venice API error: {} {}",
-                        status,
-                        details
-                    ));
-                }
-            }
-            Err(error) => {
-                last_error = Some(color_eyre::eyre::eyre!(
-                    "Venice request error: {}",
-                    error
-                ));
-            }
-        }
-
-        if attempt < delays.len() - 1 {
-            sleep(Duration::from_millis(*delay));
-        }
-    }
-
-    Err(last_error.unwrap_or_else(|| {
-        color_eyre::eyre::eyre!("Venice request failed after retries")
-    }))
+    let payload = post_chat(api_key, &request, requests, notifier)?;
+    openai_compat::extract_reply(payload, "Venice")
 }
 
 /// Sends a chat request with native tool calling support
@@ -98,6 +57,8 @@ pub fn chat_with_tools(
     model: &str,
     messages: &[crate::agents::ChatMessage],
     tools: &[ToolDefinition],
+    requests: &RequestConfig,
+    notifier: Option<&RetryNotifier>,
 ) -> Result<ChatResponse> {
     let tools_payload = if tools.is_empty() {
         None
@@ -111,54 +72,33 @@ pub fn chat_with_tools(
         stream: false,
         tools: tools_payload,
     };
+    let payload = post_chat(api_key, &request, requests, notifier)?;
+    openai_compat::extract_chat_response(payload, "Venice")
+}
 
-    let client = openai_compat::build_client()?;
-    let mut last_error: Option<color_eyre::Report> = None;
-    let delays = [200, 500, 1000];
-    for (attempt, delay) in delays.iter().enumerate() {
-        let response = client
+fn post_chat(
+    api_key: &str,
+    request: &openai_compat::OpenAIChatRequest,
+    requests: &RequestConfig,
+    notifier: Option<&RetryNotifier>,
+) -> Result<openai_compat::OpenAIChatResponse> {
+    let client = retry::build_client(requests)?;
+    let response = retry::send_with_retry("Venice", requests, notifier, || {
+        client
             .post(VENICE_CHAT_URL)
             .bearer_auth(api_key)
-            .json(&request)
-            .send();
-
-        match response {
-            Ok(response) => {
-                let status = response.status();
-                if status.is_success() {
-                    let payload: openai_compat::OpenAIChatResponse = response.json()?;
-                    return openai_compat::extract_chat_response(payload, "Venice");
-                }
-
-                let details = response.text().unwrap_or_default();
-                if status.as_u16() == 429 || status.as_u16() >= 500 {
-                    last_error = Some(color_eyre::eyre::eyre!(
-                        "Venice API error ({}), retrying... {}",
-                        status,
-                        details
-                    ));
-                } else {
-                    return Err(color_eyre::eyre::eyre!(
-                        "Venice API error: {} {}",
-                        status,
-                        details
-                    ));
-                }
-            }
-            Err(error) => {
-                last_error = Some(color_eyre::eyre::eyre!(
-                    "Venice request error: {}",
-                    error
-                ));
-            }
-        }
-
-        if attempt < delays.len() - 1 {
-            sleep(Duration::from_millis(*delay));
-        }
+            .json(request)
+            .send()
+    })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let details = response.text().unwrap_or_default();
+        return Err(color_eyre::eyre::eyre!(
+            "Venice API error: {} {}",
+            status,
+            details
+        ));
     }
-
-    Err(last_error.unwrap_or_else(|| {
-        color_eyre::eyre::eyre!("Venice request failed after retries")
-    }))
+    Ok(response.json()?)
 }
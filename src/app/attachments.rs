@@ -0,0 +1,91 @@
+use crate::app::{App, ChatAttachment};
+use crate::services::stdin::PipedInput;
+
+impl App {
+    /// Attaches text piped on stdin to the next message sent
+    pub fn attach_piped_input(&mut self, input: PipedInput) {
+        let token = "[[file:stdin]]".to_string();
+        let toast = match input.truncation_note() {
+            Some(note) => note.to_uppercase(),
+            None => "STDIN ATTACHED".to_string(),
+        };
+        self.chat_attachments.push(ChatAttachment::Stdin {
+            token: token.clone(),
+            input,
+        });
+        self.append_attachment_token(&token);
+        self.show_status_toast(toast);
+    }
+
+    /// Opens or closes the attachment manager panel (Ctrl+A)
+    pub fn toggle_attachment_panel(&mut self) {
+        if self.attachment_panel_active {
+            self.attachment_panel_active = false;
+            return;
+        }
+        if self.chat_attachments.is_empty() {
+            self.show_status_toast("NO ATTACHMENTS");
+            return;
+        }
+        self.attachment_panel_active = true;
+        self.attachment_selected_index = 0;
+    }
+
+    pub fn next_attachment(&mut self) {
+        if !self.chat_attachments.is_empty() {
+            self.attachment_selected_index =
+                (self.attachment_selected_index + 1) % self.chat_attachments.len();
+        }
+    }
+
+    pub fn previous_attachment(&mut self) {
+        if !self.chat_attachments.is_empty() {
+            self.attachment_selected_index = self
+                .attachment_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.chat_attachments.len().saturating_sub(1));
+        }
+    }
+
+    /// Moves the selected attachment one position earlier
+    pub fn move_attachment_up(&mut self) {
+        let index = self.attachment_selected_index;
+        if index > 0 && index < self.chat_attachments.len() {
+            self.chat_attachments.swap(index, index - 1);
+            self.attachment_selected_index = index - 1;
+        }
+    }
+
+    /// Moves the selected attachment one position later
+    pub fn move_attachment_down(&mut self) {
+        let index = self.attachment_selected_index;
+        if index + 1 < self.chat_attachments.len() {
+            self.chat_attachments.swap(index, index + 1);
+            self.attachment_selected_index = index + 1;
+        }
+    }
+
+    /// Removes the selected attachment and its token from the chat input
+    pub fn remove_selected_attachment(&mut self) {
+        if self.attachment_selected_index >= self.chat_attachments.len() {
+            return;
+        }
+        let attachment = self.chat_attachments.remove(self.attachment_selected_index);
+        let content = self
+            .chat_input
+            .content()
+            .replace(attachment.token(), "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.chat_input.set_content(content);
+
+        if self.chat_attachments.is_empty() {
+            self.attachment_panel_active = false;
+            self.attachment_selected_index = 0;
+        } else if self.attachment_selected_index >= self.chat_attachments.len() {
+            self.attachment_selected_index = self.chat_attachments.len() - 1;
+        }
+        self.show_status_toast("ATTACHMENT REMOVED");
+    }
+}
@@ -0,0 +1,61 @@
+use crate::app::{App, AppMode};
+use crate::services::tool_policy::ToolDecision;
+use color_eyre::Result;
+
+/// Tool calls loaded into `/audit`
+const AUDIT_LIMIT: usize = 200;
+
+impl App {
+    pub fn open_audit(&mut self) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        // Calls made just before opening are still queued on the storage worker
+        self.wait_for_storage_jobs();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.audit_records = runtime.block_on(storage.load_tool_audit(AUDIT_LIMIT))?;
+        self.audit_selected_index = 0;
+        self.mode = AppMode::Audit;
+        Ok(())
+    }
+
+    pub fn close_audit(&mut self) {
+        self.mode = AppMode::Chat;
+        self.audit_records.clear();
+    }
+
+    pub fn next_audit_item(&mut self) {
+        if !self.audit_records.is_empty() {
+            self.audit_selected_index = (self.audit_selected_index + 1) % self.audit_records.len();
+        }
+    }
+
+    pub fn previous_audit_item(&mut self) {
+        if !self.audit_records.is_empty() {
+            self.audit_selected_index = self
+                .audit_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.audit_records.len().saturating_sub(1));
+        }
+    }
+
+    /// Stores one tool call from the agent thread in the audit log
+    pub(crate) fn record_tool_audit(
+        &mut self,
+        tool: String,
+        arguments: String,
+        decision: ToolDecision,
+        result: String,
+    ) {
+        if self.incognito {
+            return;
+        }
+        self.spawn_storage_job(move |storage, runtime| {
+            if let Err(error) =
+                runtime.block_on(storage.save_tool_audit(&tool, &arguments, decision, &result))
+            {
+                tracing::warn!(%error, %tool, "tool audit not saved");
+            }
+        });
+    }
+}
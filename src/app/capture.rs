@@ -0,0 +1,102 @@
+use crate::app::types::MessageRole;
+use crate::app::{App, AppMode};
+use crate::storage::{ConversationData, ConversationMessage};
+use color_eyre::Result;
+
+impl App {
+    /// `/capture`: everything typed is saved as-is, the chat model is never asked
+    pub fn open_capture(&mut self) {
+        self.capture_input.clear();
+        self.mode = AppMode::Capture;
+    }
+
+    pub fn close_capture(&mut self) {
+        self.capture_input.clear();
+        self.mode = AppMode::Chat;
+    }
+
+    /// Enter: timestamps the text and saves it to memory and/or the Obsidian inbox,
+    /// then clears the input for the next thought
+    pub fn save_capture(&mut self) {
+        let text = self.capture_input.content().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let now = chrono::Local::now();
+        let mut saved_to = Vec::new();
+        let mut errors = Vec::new();
+
+        if self.capture.obsidian {
+            let entry = crate::services::capture::format_capture(now.naive_local(), &text);
+            match crate::services::capture::append_to_inbox(
+                &self.connect_obsidian_vault_path,
+                &self.capture.inbox_note,
+                &entry,
+            ) {
+                Ok(_) => saved_to.push("inbox"),
+                Err(error) => errors.push(format!("inbox: {}", error)),
+            }
+        }
+        if self.capture.memory {
+            let message = ConversationMessage {
+                role: MessageRole::User.as_str().to_string(),
+                content: text.clone(),
+                timestamp: now.format("%H:%M:%S").to_string(),
+                display_name: None,
+                quoted_role: None,
+                quoted_text: None,
+                stats: None,
+                images: Vec::new(),
+                files: Vec::new(),
+            };
+            match self.save_capture_memory(message) {
+                Ok(()) => saved_to.push("memory"),
+                Err(error) => errors.push(format!("memory: {}", error)),
+            }
+        }
+
+        if saved_to.is_empty() {
+            let reason = if errors.is_empty() {
+                "both memory and obsidian are off in [capture]".to_string()
+            } else {
+                errors.join("; ")
+            };
+            self.show_status_toast("CAPTURE FAILED");
+            self.captures.push((now.format("%H:%M").to_string(), text, format!("not saved: {}", reason)));
+            return;
+        }
+        if !errors.is_empty() {
+            tracing::warn!(errors = %errors.join("; "), "capture partly failed");
+        }
+        self.captures.push((now.format("%H:%M").to_string(), text, saved_to.join(" + ")));
+        self.capture_input.clear();
+        self.show_status_toast("CAPTURED");
+    }
+
+    /// The first capture of the session creates its conversation, later ones are appended
+    /// in the background; each becomes searchable once embedded
+    fn save_capture_memory(&mut self, message: ConversationMessage) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let messages = vec![message];
+        let Some(conversation_id) = self.capture_conversation_id.clone() else {
+            let title = format!("Quick capture {}", chrono::Local::now().format("%Y-%m-%d"));
+            let (storage, runtime) = self.storage_with_runtime()?;
+            let data = ConversationData::new("capture", &messages).with_summary(&title);
+            let new_id = runtime.block_on(storage.save_conversation(data))?;
+            self.capture_conversation_id = Some(new_id.clone());
+            if let Some(storage) = &self.storage {
+                Self::spawn_background_embeddings(storage.clone(), new_id, messages);
+            }
+            return Ok(());
+        };
+        self.spawn_storage_job(move |storage, runtime| {
+            match runtime.block_on(storage.append_conversation_messages(&conversation_id, &messages)) {
+                Ok(()) => Self::spawn_background_embeddings(storage.clone(), conversation_id, messages),
+                Err(error) => tracing::error!(%error, conversation = %conversation_id, "capture save failed"),
+            }
+        });
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ pub(crate) mod tools;
 
 
 use crate::agents::ChatMessage as AgentChatMessage;
-use crate::app::types::{ChatMessage, MessageRole};
+use crate::app::types::{ChatMessage, MessageQuote, MessageRole, PromptSection, PromptSectionText};
 use crate::app::{App, AppMode, ContextUsage, TextInput};
 use crate::app::AgentEvent;
 use crate::app::chat::agent::context::{
@@ -15,6 +15,9 @@ use crate::app::chat::agent::context::{
     tokenize_query,
 };
 use crate::app::chat::agent::intent::{classify_query_with_model, IntentModelContext, QueryIntent};
+use crate::services::citations::{self, Citations, Source, SourceKind};
+use crate::services::tool_outputs;
+use crate::services::tool_policy::{self, ToolDecision};
 use color_eyre::Result;
 use std::sync::OnceLock;
 
@@ -157,11 +160,22 @@ impl App {
         self.chat_attachments.clear();
         self.mode = AppMode::Chat;
 
-        if let Err(error) = manager.check_agent_ready(&agent) {
-            self.chat_history.push(ChatMessage::system(format!(
-                "⚠️  {} agent not ready: {}",
-                agent_name, error
-            )));
+        match manager.check_agent_ready(&agent) {
+            Ok(_) if manager.warm_up_enabled() && agent.model_source == crate::app::ModelSource::Ollama => {
+                let manager = manager.clone();
+                std::thread::spawn(move || {
+                    if let Err(error) = manager.warm_up(&agent) {
+                        tracing::warn!(model = %agent.model, %error, "model warm-up failed");
+                    }
+                });
+            }
+            Ok(_) => {}
+            Err(error) => {
+                self.chat_history.push(ChatMessage::system(format!(
+                    "⚠️  {} agent not ready: {}",
+                    agent_name, error
+                )));
+            }
         }
         Ok(())
     }
@@ -194,25 +208,31 @@ impl App {
     }
 
 
-    pub(crate) fn spawn_agent_chat_thread_with_context(ctx: AgentChatContext) {
+    pub(crate) fn spawn_agent_chat_thread_with_context(mut ctx: AgentChatContext) {
         std::thread::spawn(move || {
+            let status_tx = ctx.agent_tx.clone();
+            ctx.manager.set_retry_notifier(std::sync::Arc::new(move |message| {
+                let _ = status_tx.send(AgentEvent::StatusUpdate(message));
+            }));
             let uses_native_tools =
                 ctx.agent.model_source == crate::app::ModelSource::VeniceAPI;
+            let started = std::time::Instant::now();
 
             let initial_result = if uses_native_tools {
-                let tool_defs = tools::get_tool_definitions();
+                let tool_defs = tools::tool_definitions_without(&ctx.skipped_sections, &ctx.tools);
                 ctx.manager
                     .chat_with_tools(&ctx.agent, &ctx.messages, &tool_defs)
             } else {
-                ctx.manager
-                    .chat(&ctx.agent, &ctx.messages)
-                    .map(crate::agents::openai_compat::ChatResponse::text)
+                ctx.manager.chat_response(&ctx.agent, &ctx.messages)
             };
 
             match initial_result {
                 Ok(mut chat_response) => {
                     let mut response = chat_response.content.clone();
+                    let mut usage = chat_response.usage;
                     let mut tool_iterations = 0;
+                    // Fallback messages written here are not worth checking
+                    let mut answered = true;
                     const MAX_TOOL_ITERATIONS: usize = 3;
 
                     // Tool loop: handle both native and text-based tool calls
@@ -231,6 +251,8 @@ impl App {
 
                         let tool_results =
                             execute_all_tools(&parsed_tools, &ctx);
+                        let tool_results =
+                            summarize_long_results(tool_results, &parsed_tools, &ctx);
 
                         // Build follow-up messages with tool results
                         let mut messages_with_results = ctx.messages.clone();
@@ -254,27 +276,28 @@ impl App {
 
                         // Get next response (with tools still available for chaining)
                         let next_result = if uses_native_tools {
-                            let tool_defs = tools::get_tool_definitions();
+                            let tool_defs = tools::tool_definitions_without(&ctx.skipped_sections, &ctx.tools);
                             ctx.manager.chat_with_tools(
                                 &ctx.agent,
                                 &messages_with_results,
                                 &tool_defs,
                             )
                         } else {
-                            ctx.manager
-                                .chat(&ctx.agent, &messages_with_results)
-                                .map(crate::agents::openai_compat::ChatResponse::text)
+                            ctx.manager.chat_response(&ctx.agent, &messages_with_results)
                         };
 
                         match next_result {
                             Ok(next) if !next.content.trim().is_empty()
                                 || next.has_tool_calls() =>
                             {
+                                usage.prompt_tokens += next.usage.prompt_tokens;
+                                usage.completion_tokens += next.usage.completion_tokens;
                                 response = next.content.clone();
                                 chat_response = next;
                             }
                             Ok(_) => {
                                 response = "I tried to fetch that information but couldn't generate a response. Please try rephrasing your question.".to_string();
+                                answered = false;
                                 break;
                             }
                             Err(error) => {
@@ -282,6 +305,7 @@ impl App {
                                     "I encountered an error while processing your request: {}",
                                     error
                                 );
+                                answered = false;
                                 break;
                             }
                         }
@@ -290,15 +314,33 @@ impl App {
                     // Safety net: never show raw tool JSON to the user
                     if tools::has_tool_calls(&response) {
                         response = "I tried to use a tool to answer your question, but encountered an issue generating the response. Could you rephrase your question?".to_string();
+                        answered = false;
                     }
 
-                    // Verification step disabled — LLMs don't reliably return the
-                    // original response when told "return it if correct", causing
-                    // corrupted outputs ("The response accurately reflects...").
+                    // Providers that report no usage send zeros
+                    let reported = |tokens: u64| (tokens > 0).then_some(tokens);
+                    let stats = crate::storage::MessageStats {
+                        model: ctx.agent.model.clone(),
+                        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+                        prompt_tokens: reported(usage.prompt_tokens),
+                        completion_tokens: reported(usage.completion_tokens),
+                    };
+                    let sources = ctx
+                        .citations
+                        .into_inner()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .into_sources();
                     let _ = ctx.agent_tx.send(AgentEvent::ResponseWithContext {
-                        response,
-                        context_usage: ctx.context_usage,
+                        response: response.clone(),
+                        context_usage: with_sources(ctx.context_usage, sources),
+                        stats: Some(stats),
                     });
+
+                    // Checked after the reply is shown. Rewriting answers corrupted them
+                    // ("The response accurately reflects..."), so the check only reports
+                    if answered && !ctx.agent.verify.is_off() {
+                        verify_answer(&ctx.messages, response, &ctx.agent, &ctx.manager, &ctx.agent_tx);
+                    }
                 }
                 Err(error) => {
                     let _ = ctx.agent_tx.send(AgentEvent::Error(error.to_string()));
@@ -309,6 +351,56 @@ impl App {
 
 }
 
+/// Checks a reply with a second call and reports the verdict for its badge
+fn verify_answer(
+    messages: &[AgentChatMessage],
+    response: String,
+    agent: &crate::agents::Agent,
+    manager: &crate::agents::AgentManager,
+    agent_tx: &std::sync::mpsc::Sender<AgentEvent>,
+) {
+    let content_of = |role: crate::agents::MessageRole| {
+        messages
+            .iter()
+            .rfind(|message| message.role == role)
+            .map_or("", |message| message.content.as_str())
+    };
+    let Some(check) = crate::services::verification::check_answer(
+        agent.verify,
+        content_of(crate::agents::MessageRole::System),
+        content_of(crate::agents::MessageRole::User),
+        &response,
+        agent,
+        manager,
+    ) else {
+        return;
+    };
+    tracing::info!(
+        agent = %agent.name,
+        verdict = ?check.verdict,
+        issues = ?check.issues,
+        "answer checked"
+    );
+    let _ = agent_tx.send(AgentEvent::AnswerChecked { response, check });
+}
+
+/// Attaches the reply's numbered sources; tools can add sources to a reply that had no context
+fn with_sources(context_usage: Option<ContextUsage>, sources: Vec<Source>) -> Option<ContextUsage> {
+    if sources.is_empty() {
+        return context_usage;
+    }
+    let mut usage = context_usage.unwrap_or(ContextUsage {
+        notes_used: 0,
+        history_used: 0,
+        memories_used: 0,
+        previous_session_used: false,
+        skipped: Vec::new(),
+        sources: Vec::new(),
+    });
+    usage.sources = sources;
+    Some(usage)
+}
+
 /// Determines tool calls from the response: native API tool_calls first, text-based fallback second
 /// Returns (parsed_tools, is_native)
 fn resolve_tool_calls(
@@ -334,17 +426,146 @@ fn execute_all_tools(
     ctx: &AgentChatContext,
 ) -> Vec<tools::ToolResult> {
     let runtime = get_async_runtime();
+    let routing_agent = ctx.manager.get_agent("routing").cloned();
+    let query_rewriters: Vec<&crate::agents::Agent> =
+        routing_agent.iter().chain(std::iter::once(&ctx.agent)).collect();
 
     parsed_tools
         .iter()
         .map(|tool_call| {
-            tools::execute_tool(
-                tool_call,
-                &ctx.vault_name,
-                &ctx.vault_path,
-                &ctx.brave_key,
-                runtime,
-            )
+            tracing::info!(?tool_call, "tool call");
+            if let Some((tool, section)) = tool_call.context_source()
+                && ctx.skipped_sections.contains(&section)
+            {
+                return tools::ToolResult {
+                    tool: tool.to_string(),
+                    result: format!("{} is turned off for this message.", section.label()),
+                };
+            }
+            let name = tool_call.name();
+            let arguments = tool_call.arguments();
+            let decision = tool_policy::decide(&ctx.tools, name, tool_call.changes_something());
+            let result = if decision == ToolDecision::Run {
+                tools::execute_tool(
+                    tool_call,
+                    &tools::ToolContext {
+                        vault_name: &ctx.vault_name,
+                        vault_path: &ctx.vault_path,
+                        brave_key: &ctx.brave_key,
+                        github_key: &ctx.github_key,
+                        runtime,
+                        storage: ctx.storage.as_ref(),
+                        file_search: &ctx.file_search,
+                        databases: &ctx.databases,
+                        git: &ctx.git,
+                        git_repo: ctx.git_repo.as_deref(),
+                        home_assistant: &ctx.home_assistant,
+                        agent_tx: &ctx.agent_tx,
+                        citations: &ctx.citations,
+                        manager: &ctx.manager,
+                        query_rewriters: &query_rewriters,
+                    },
+                )
+            } else {
+                tracing::info!(tool = name, decision = decision.as_str(), "tool call held back");
+                tools::ToolResult {
+                    tool: name.to_string(),
+                    result: tool_policy::held_back_result(decision, name, &arguments),
+                }
+            };
+            tracing::debug!(tool = %result.tool, chars = result.result.len(), "tool result");
+            if ctx.tools.audit {
+                let _ = ctx.agent_tx.send(AgentEvent::ToolAudited {
+                    tool: name.to_string(),
+                    arguments,
+                    decision,
+                    result: tool_policy::audit_excerpt(&result.result),
+                });
+            }
+            result
+        })
+        .collect()
+}
+
+/// Replaces long tool results with a summary from the summary model (then the routing and
+/// chat models when it fails); the full text goes to storage under the key the summary cites
+fn summarize_long_results(
+    results: Vec<tools::ToolResult>,
+    parsed_tools: &[tools::ToolCall],
+    ctx: &AgentChatContext,
+) -> Vec<tools::ToolResult> {
+    let limit = ctx.tools.summarize_over_chars;
+    if !results
+        .iter()
+        .any(|result| tool_outputs::needs_summary(&result.result, limit))
+    {
+        return results;
+    }
+    let _ = ctx.agent_tx.send(AgentEvent::StatusUpdate(
+        "summarizing tool output".to_string(),
+    ));
+    let summary_agent = ctx.manager.summary_agent();
+    let routing_agent = ctx.manager.get_agent("routing").cloned();
+    let summarizers: Vec<&crate::agents::Agent> = summary_agent
+        .iter()
+        .chain(routing_agent.iter())
+        .chain(std::iter::once(&ctx.agent))
+        .collect();
+
+    results
+        .into_iter()
+        .zip(parsed_tools)
+        .enumerate()
+        .map(|(index, (result, tool_call))| {
+            if !tool_outputs::needs_summary(&result.result, limit) {
+                return result;
+            }
+            let arguments = tool_call.arguments();
+            let Some(summary) = tool_outputs::summarize(
+                &result.tool,
+                &arguments,
+                &result.result,
+                &summarizers,
+                &ctx.manager,
+            ) else {
+                return result;
+            };
+            let key = tool_outputs::output_key(index);
+            let embedding = get_async_runtime().and_then(|runtime| {
+                runtime
+                    .block_on(crate::services::embeddings::generate_embedding(&summary))
+                    .ok()
+            });
+            tracing::info!(
+                tool = %result.tool,
+                chars = result.result.len(),
+                summary_chars = summary.len(),
+                "tool output summarized"
+            );
+            let number = ctx
+                .citations
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .cite(SourceKind::ToolOutput, &result.tool, &key);
+            let summarized = format!(
+                "[{}] {}",
+                number,
+                tool_outputs::summarized_result(&summary, &result.result, &key)
+            );
+            let _ = ctx.agent_tx.send(AgentEvent::ToolOutputStored(
+                crate::storage::ToolOutputRecord {
+                    key,
+                    tool: result.tool.clone(),
+                    arguments,
+                    content: result.result,
+                    summary,
+                    embedding,
+                },
+            ));
+            tools::ToolResult {
+                tool: result.tool,
+                result: summarized,
+            }
         })
         .collect()
 }
@@ -488,16 +709,46 @@ pub(crate) struct ChatBuildSnapshot {
     pub connect_obsidian_vault: String,
     pub connect_obsidian_vault_path: String,
     pub connect_brave_key: String,
+    pub connect_github_key: String,
+    pub file_search: crate::config::FileSearchConfig,
+    pub databases: Vec<crate::config::DatabaseConfig>,
+    pub git: crate::config::GitConfig,
+    /// Work tree the app was launched in
+    pub git_repo: Option<std::path::PathBuf>,
+    pub home_assistant: crate::config::HomeAssistantConfig,
+    pub tools: crate::config::ToolsConfig,
     /// Pre-retrieved messages (retrieved before thread spawn while App storage is accessible)
     pub pre_retrieved_messages: Vec<crate::storage::RetrievedMessage>,
     /// Cached Obsidian notes from previous query (for follow-up questions)
     pub cached_obsidian_notes: Option<(String, Vec<crate::services::obsidian::NoteSnippet>)>,
     /// Topics the system wants the AI to suggest as projects
     pub pending_project_suggestions: Vec<String>,
+    /// Project chosen in `/projects`; its knowledge is added when the conversation is about it
+    pub active_project: Option<crate::services::projects::ProjectFile>,
+    /// Tasks of the active project, listed whenever a project is active
+    pub active_project_tasks: Vec<crate::storage::ProjectTask>,
     /// Cloned storage handle for conversation retrieval (avoids RocksDB lock conflicts)
     pub storage: Option<crate::storage::StorageManager>,
     /// Cached recall context from a previous message in this session
     pub cached_recall_context: Option<String>,
+    /// Configured ICS calendar sources (URLs or local files)
+    pub calendar_sources: Vec<String>,
+    /// Summary of the conversation closed right before this one started
+    pub previous_session_context: Option<String>,
+    /// Check-in interval on the first message of a conversation; None when no check-in applies
+    pub check_in_every_days: Option<u64>,
+    /// Prompt sections turned off for this session in the prompt inspector
+    pub disabled_prompt_sections: Vec<PromptSection>,
+    /// Sections the message being sent turned off with a context modifier
+    pub skipped_sections: Vec<PromptSection>,
+    /// Language code replies must be written in
+    pub language: String,
+}
+
+impl ChatBuildSnapshot {
+    fn section_enabled(&self, section: PromptSection) -> bool {
+        !self.disabled_prompt_sections.contains(&section) && !self.skipped_sections.contains(&section)
+    }
 }
 
 pub(crate) struct ChatBuildResultWithUsage {
@@ -507,6 +758,10 @@ pub(crate) struct ChatBuildResultWithUsage {
     pub forced_response: Option<String>,
     pub notes_to_cache: Option<(String, Vec<crate::services::obsidian::NoteSnippet>)>,
     pub recall_context_to_cache: Option<String>,
+    /// Every section of the system prompt, including ones disabled for the session
+    pub prompt_sections: Vec<PromptSectionText>,
+    /// Sources numbered in the prompt; tool results continue the numbering
+    pub citations: Citations,
 }
 
 pub(crate) struct AgentChatContext {
@@ -518,6 +773,20 @@ pub(crate) struct AgentChatContext {
     pub vault_name: String,
     pub vault_path: String,
     pub brave_key: String,
+    pub github_key: String,
+    pub file_search: crate::config::FileSearchConfig,
+    pub databases: Vec<crate::config::DatabaseConfig>,
+    pub git: crate::config::GitConfig,
+    pub git_repo: Option<std::path::PathBuf>,
+    pub home_assistant: crate::config::HomeAssistantConfig,
+    /// Cloned storage handle for tools that write (avoids RocksDB lock conflicts)
+    pub storage: Option<crate::storage::StorageManager>,
+    /// Allow/deny lists, dry-run and auditing for tool calls
+    pub tools: crate::config::ToolsConfig,
+    /// Context modifiers of the message also hide the matching tools
+    pub skipped_sections: Vec<PromptSection>,
+    /// Sources numbered so far; tools running in the loop add theirs
+    pub citations: std::sync::Mutex<Citations>,
 }
 
 pub(crate) fn build_agent_messages_from_snapshot(
@@ -527,19 +796,30 @@ pub(crate) fn build_agent_messages_from_snapshot(
     agent_tx: Option<&std::sync::mpsc::Sender<crate::app::AgentEvent>>,
 ) -> ChatBuildResultWithUsage {
     let personality_text = resolve_personality_text(&snapshot);
-    let last_user_message = snapshot
+    let last_user = snapshot
         .chat_history
         .iter()
         .rev()
-        .find(|message| message.role == MessageRole::User)
-        .map(|message| message.content.clone());
+        .find(|message| message.role == MessageRole::User);
+    let last_user_message = last_user.map(|message| message.content.clone());
+    let last_user_quote = last_user.and_then(|message| message.quote.clone());
 
     let uses_native_tools = agent.model_source == crate::app::ModelSource::VeniceAPI;
     let include_text_tool_schema = !uses_native_tools;
-    let mut prompt_lines = build_foundation_prompt(&snapshot.system_prompt, include_text_tool_schema);
+    let mut citations = Citations::default();
+    let mut sections = PromptSectionMarks::default();
+    let mut prompt_lines = build_foundation_prompt(&snapshot.system_prompt, &snapshot.language, include_text_tool_schema);
+    sections.begin(PromptSection::Persona, &prompt_lines);
     prompt_lines.extend(build_persona_prompt(last_user_message.as_deref()));
 
+    // Quoted reply: point the model at the message the user is responding to
+    sections.begin(PromptSection::ReplyContext, &prompt_lines);
+    if let Some(quote) = &last_user_quote {
+        prompt_lines.push(format_reply_prompt(quote));
+    }
+
     // Inject project suggestion hint if there are pending suggestions
+    sections.begin(PromptSection::ProjectSuggestions, &prompt_lines);
     if !snapshot.pending_project_suggestions.is_empty() {
         let topics = snapshot.pending_project_suggestions.join(", ");
         prompt_lines.push(format!(
@@ -553,11 +833,94 @@ Only suggest once per topic. If they decline, respect that.",
         ));
     }
 
+    // Knowledge of the active project, once the conversation turns to it
+    sections.begin(PromptSection::ActiveProject, &prompt_lines);
+    if let Some(project) = &snapshot.active_project
+        && snapshot.section_enabled(PromptSection::ActiveProject)
+    {
+        let recent_text = snapshot
+            .chat_history
+            .iter()
+            .rev()
+            .filter(|message| message.role != MessageRole::System)
+            .take(ACTIVE_PROJECT_MATCH_MESSAGES)
+            .map(|message| message.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if crate::services::projects::project_matches_text(project, &recent_text) {
+            prompt_lines.push(crate::services::projects::format_project_knowledge(
+                project,
+                ACTIVE_PROJECT_MAX_ENTRIES,
+            ));
+        }
+        if let Some(tasks) = crate::services::projects::format_project_tasks(
+            &project.name,
+            &snapshot.active_project_tasks,
+        ) {
+            prompt_lines.push(tasks);
+        }
+    }
+
+    // Today's schedule from the user's calendars when they ask about their day
+    let mut has_schedule_context = false;
+    sections.begin(PromptSection::Calendar, &prompt_lines);
+    if !snapshot.calendar_sources.is_empty()
+        && snapshot.section_enabled(PromptSection::Calendar)
+        && let Some(query) = last_user_message.as_deref()
+        && crate::services::calendar::is_schedule_query(query)
+    {
+        send_status(agent_tx, "checking calendar");
+        let today = chrono::Local::now().date_naive();
+        if let Ok(events) =
+            crate::services::calendar::load_upcoming_events(&snapshot.calendar_sources, today, 2)
+        {
+            prompt_lines.push(crate::services::calendar::format_schedule_prompt(&events, today));
+            has_schedule_context = true;
+        }
+    }
+
+    // Branch, recent commits and dirty files when the question is about the repository
+    sections.begin(PromptSection::Git, &prompt_lines);
+    if let Some(root) = &snapshot.git_repo
+        && snapshot.section_enabled(PromptSection::Git)
+        && let Some(query) = last_user_message.as_deref()
+        && crate::services::git_context::is_repo_query(query)
+    {
+        send_status(agent_tx, "reading repository");
+        if let Ok(state) = crate::services::git_context::read_repo_state(root, snapshot.git.commit_count) {
+            prompt_lines.push(crate::services::git_context::format_repo_prompt(&state));
+        }
+    }
+
     let mut context_usage = ContextUsage {
         notes_used: 0,
         history_used: 0,
         memories_used: 0,
+        previous_session_used: false,
+        skipped: snapshot.skipped_sections.clone(),
+        sources: Vec::new(),
     };
+    sections.begin(PromptSection::PreviousSession, &prompt_lines);
+    if let Some(summary) = &snapshot.previous_session_context
+        && snapshot.section_enabled(PromptSection::PreviousSession)
+    {
+        prompt_lines.push(format!(
+            "--- Previous session context ---\n{}\n\
+The user closed that conversation shortly before starting this one. \
+Use it for continuity when relevant, but don't bring it up unprompted.",
+            summary
+        ));
+        context_usage.previous_session_used = true;
+    }
+
+    sections.begin(PromptSection::CheckIn, &prompt_lines);
+    if let Some(every_days) = snapshot.check_in_every_days
+        && snapshot.section_enabled(PromptSection::CheckIn)
+        && let Some(check_in) = pick_check_in(every_days, snapshot.storage.as_ref())
+    {
+        prompt_lines.push(check_in);
+    }
+
     let mut forced_response: Option<String> = None;
     let mut has_memory_context = false;
     let is_profile_query = last_user_message
@@ -565,6 +928,7 @@ Only suggest once per topic. If they decline, respect that.",
         .is_some_and(|query| crate::services::retrieval::is_profile_query(query));
 
     // Pre-retrieved memory context
+    sections.begin(PromptSection::Memories, &prompt_lines);
     if !snapshot.pre_retrieved_messages.is_empty() {
         send_status(agent_tx, "recalling memories");
         context_usage.memories_used = snapshot.pre_retrieved_messages.len();
@@ -574,7 +938,11 @@ Only suggest once per topic. If they decline, respect that.",
             forced_response =
                 Some(handle_profile_query_memories(&snapshot, agent, manager));
         } else {
-            append_memory_context(&mut prompt_lines, &snapshot.pre_retrieved_messages);
+            append_memory_context(
+                &mut prompt_lines,
+                &snapshot.pre_retrieved_messages,
+                &mut citations,
+            );
         }
     }
 
@@ -599,7 +967,11 @@ Only suggest once per topic. If they decline, respect that.",
 
         // Inject past conversation content (actual messages for today/yesterday,
         // summaries for wider ranges like "this week")
-        if let Ok(Some(recall)) = build_conversation_recall(storage.as_ref(), query) {
+        let recall_enabled = snapshot.section_enabled(PromptSection::ConversationRecall);
+        sections.begin(PromptSection::ConversationRecall, &prompt_lines);
+        if recall_enabled
+            && let Ok(Some(recall)) = build_conversation_recall(storage.as_ref(), query)
+        {
             has_date_recall = true;
             context_usage.history_used = recall.conversation_count;
             recall_context_to_cache = Some(recall.prompt_text.clone());
@@ -609,6 +981,7 @@ Only suggest once per topic. If they decline, respect that.",
         // Follow-up: if no fresh recall but we have cached context from a previous
         // message in this conversation, re-inject it so the LLM can answer follow-ups.
         if !has_date_recall
+            && recall_enabled
             && let Some(cached) = &snapshot.cached_recall_context
         {
             has_date_recall = true;
@@ -617,12 +990,17 @@ Only suggest once per topic. If they decline, respect that.",
 
         // Auto-inject memory context for broad meta-recall queries ("what do you know about me?")
         // Skip when date-specific recall was already injected — those are more focused.
-        if !has_date_recall && crate::services::retrieval::is_meta_recall_query(query) {
+        sections.begin(PromptSection::Memories, &prompt_lines);
+        if !has_date_recall
+            && snapshot.section_enabled(PromptSection::Memories)
+            && crate::services::retrieval::is_meta_recall_query(query)
+        {
             inject_meta_recall_context(
                 storage.as_ref(),
                 runtime,
                 agent_tx,
                 &mut prompt_lines,
+                &mut citations,
                 &mut context_usage,
                 &mut has_memory_context,
             );
@@ -638,16 +1016,22 @@ Only suggest once per topic. If they decline, respect that.",
             forced_response,
             notes_to_cache: None,
             recall_context_to_cache: None,
+            prompt_sections: Vec::new(),
+            citations,
         };
     }
 
     // Obsidian notes
     let mut notes_to_cache: Option<(String, Vec<crate::services::obsidian::NoteSnippet>)> = None;
-    if let (Some(query), Some(intent)) = (last_user_message.as_deref(), query_intent) {
+    sections.begin(PromptSection::Notes, &prompt_lines);
+    if snapshot.section_enabled(PromptSection::Notes)
+        && let (Some(query), Some(intent)) = (last_user_message.as_deref(), query_intent)
+    {
         let obsidian_result = build_notes_section(
             &snapshot,
             query,
             intent,
+            &mut citations,
             agent_tx,
         );
         context_usage.notes_used = obsidian_result.notes_used;
@@ -658,9 +1042,12 @@ Only suggest once per topic. If they decline, respect that.",
     // Search enrichment — skip when we already have date-specific summaries
     // (recall queries shouldn't trigger web search for horoscopes, etc.)
     let mut pending_search_notice: Option<String> = None;
+    sections.begin(PromptSection::WebSearch, &prompt_lines);
     if !is_profile_query
+        && snapshot.section_enabled(PromptSection::WebSearch)
         && !has_memory_context
         && !has_date_recall
+        && !has_schedule_context
         && let (Some(query), Some(intent)) = (last_user_message.as_deref(), query_intent)
     {
         send_status(agent_tx, "searching");
@@ -668,15 +1055,19 @@ Only suggest once per topic. If they decline, respect that.",
         pending_search_notice = search::enrich_prompt_with_search_snapshot(
             &search_context,
             &mut prompt_lines,
+            &mut citations,
             search::SearchSnapshotRequest { query, intent },
         );
     }
 
     let has_context_usage = context_usage.notes_used > 0
         || context_usage.history_used > 0
-        || context_usage.memories_used > 0;
+        || context_usage.memories_used > 0
+        || context_usage.previous_session_used
+        || !context_usage.skipped.is_empty();
 
     // Personality text (mood setting) - added last
+    sections.begin(PromptSection::Personality, &prompt_lines);
     if snapshot.personality_enabled
         && let Some(text) = &personality_text
         && !text.trim().is_empty()
@@ -684,8 +1075,16 @@ Only suggest once per topic. If they decline, respect that.",
         prompt_lines.push(text.trim().to_string());
     }
 
+    let prompt_sections = sections.split(prompt_lines);
+    let enabled_lines = prompt_sections
+        .iter()
+        .filter(|part| snapshot.section_enabled(part.section))
+        .map(|part| part.text.clone())
+        .collect();
+
     assemble_final_messages(AssembleParams {
-        prompt_lines,
+        prompt_lines: enabled_lines,
+        prompt_sections,
         chat_history: &snapshot.chat_history,
         has_context_usage,
         context_usage,
@@ -693,10 +1092,30 @@ Only suggest once per topic. If they decline, respect that.",
         forced_response,
         notes_to_cache,
         recall_context_to_cache,
+        citations,
     })
 }
 
 /// Resolves the personality text, loading from disk if needed
+/// Picks a dream or open project task to ask about when a check-in is due and records it
+fn pick_check_in(every_days: u64, storage: Option<&crate::storage::StorageManager>) -> Option<String> {
+    if !crate::services::check_ins::is_check_in_due(every_days) {
+        return None;
+    }
+    let dreams = crate::services::identity::read_identity_state()
+        .map(|state| state.dreams.active)
+        .unwrap_or_default();
+    let open_tasks = storage
+        .zip(get_async_runtime())
+        .and_then(|(storage, runtime)| runtime.block_on(storage.load_open_project_tasks()).ok())
+        .unwrap_or_default();
+    let subject = crate::services::check_ins::pick_subject(&dreams, &open_tasks)?;
+    if let Err(error) = crate::services::check_ins::record_check_in(&subject) {
+        tracing::warn!(%error, "could not record check-in");
+    }
+    Some(crate::services::check_ins::format_check_in_prompt(&subject))
+}
+
 fn resolve_personality_text(snapshot: &ChatBuildSnapshot) -> Option<String> {
     if let Some(text) = &snapshot.personality_text {
         return Some(text.clone());
@@ -712,9 +1131,10 @@ fn resolve_personality_text(snapshot: &ChatBuildSnapshot) -> Option<String> {
 }
 
 /// Tier 1: Foundation prompt - identity, language rules
+/// `language` is the conversation's language code (see `/language`).
 /// When `include_text_tool_schema` is true, appends the text-based tool schema
 /// (used as fallback for models without native tool calling support)
-fn build_foundation_prompt(system_prompt: &str, include_text_tool_schema: bool) -> Vec<String> {
+fn build_foundation_prompt(system_prompt: &str, language: &str, include_text_tool_schema: bool) -> Vec<String> {
     let mut lines = vec![
         "YOUR NAME IS KIMI. This is non-negotiable.".to_string(),
         "You are NOT Gemma, Claude, GPT, LLaMA, Mistral, or any other AI model. You are Kimi.".to_string(),
@@ -725,11 +1145,13 @@ fn build_foundation_prompt(system_prompt: &str, include_text_tool_schema: bool)
 
     let now = chrono::Local::now();
     lines.push(format!("Current date and time: {}", now.format("%Y-%m-%d %H:%M:%S")));
-    lines.push("ABSOLUTE RULE: You MUST respond in English ONLY. NEVER use Chinese, Korean, or any other language in your main response.".to_string());
-    lines.push("EXCEPTION: You may use Japanese curse words in brackets like [クソ！] or [バカ野郎] as emotional punctuation, but the actual message content MUST be in English.".to_string());
-    lines.push("If you accidentally start writing in Chinese/Korean/etc, STOP immediately and rewrite in English.".to_string());
+    let language = crate::services::languages::name(language);
+    lines.push(format!("ABSOLUTE RULE: You MUST respond in {} ONLY. NEVER switch to any other language in your main response, even if the user or the context does.", language));
+    lines.push(format!("EXCEPTION: You may use Japanese curse words in brackets like [クソ！] or [バカ野郎] as emotional punctuation, but the actual message content MUST be in {}.", language));
+    lines.push(format!("If you accidentally start writing in another language, STOP immediately and rewrite in {}.", language));
     lines.push("Respond in plain text. Do not use Markdown formatting.".to_string());
     lines.push("Keep responses concise and conversational. Speak naturally like a human, not a robot.".to_string());
+    lines.push(citations::CITE_INSTRUCTION.to_string());
     lines.push("You're allowed to be direct, defensive, or challenging when appropriate. Don't default to being helpful if you're frustrated or confused.".to_string());
 
     // Only inject text-based tool schema for non-native models (Ollama/Gab fallback)
@@ -831,10 +1253,11 @@ fn handle_profile_query_memories(
 fn append_memory_context(
     prompt_lines: &mut Vec<String>,
     retrieved_messages: &[crate::storage::RetrievedMessage],
+    citations: &mut Citations,
 ) {
     prompt_lines.push("--- Relevant Past Messages ---".to_string());
     for msg in retrieved_messages {
-        prompt_lines.push(format!("[{}] {}: {}", msg.timestamp, msg.role, msg.content));
+        prompt_lines.push(format_cited_memory(msg, citations));
     }
     prompt_lines.push(
         "Use the relevant messages above for context when answering.".to_string(),
@@ -848,6 +1271,7 @@ fn inject_meta_recall_context(
     runtime: Option<&tokio::runtime::Runtime>,
     agent_tx: Option<&std::sync::mpsc::Sender<crate::app::AgentEvent>>,
     prompt_lines: &mut Vec<String>,
+    citations: &mut Citations,
     context_usage: &mut ContextUsage,
     has_memory_context: &mut bool,
 ) {
@@ -868,7 +1292,7 @@ fn inject_meta_recall_context(
         *has_memory_context = true;
         prompt_lines.push("--- Your memories about this user (from past conversations) ---".to_string());
         for result in &results {
-            prompt_lines.push(format!("[{}] {}: {}", result.timestamp, result.role, result.content));
+            prompt_lines.push(format_cited_memory(result, citations));
         }
         prompt_lines.push(
             "Draw on the memories above to give a personal, informed answer. \
@@ -878,6 +1302,19 @@ fn inject_meta_recall_context(
     }
 }
 
+/// One remembered message, numbered as a source dated by its conversation
+fn format_cited_memory(
+    message: &crate::storage::RetrievedMessage,
+    citations: &mut Citations,
+) -> String {
+    let number = citations.cite(
+        SourceKind::Memory,
+        &citations::memory_title(&message.content),
+        &message.timestamp,
+    );
+    format!("[{}] ({}) {}: {}", number, message.timestamp, message.speaker(), message.content)
+}
+
 struct NotesResult {
     notes_used: usize,
     prompt_lines: Vec<String>,
@@ -889,6 +1326,7 @@ fn build_notes_section(
     snapshot: &ChatBuildSnapshot,
     query: &str,
     intent: QueryIntent,
+    citations: &mut Citations,
     agent_tx: Option<&std::sync::mpsc::Sender<crate::app::AgentEvent>>,
 ) -> NotesResult {
     let mut lines = Vec::new();
@@ -907,7 +1345,8 @@ fn build_notes_section(
                 "Share the note content below with the user. Include relevant details.".to_string(),
             );
             for note in cached_notes {
-                lines.push(format!("## {}", note.title));
+                let number = citations.cite(SourceKind::Note, &note.title, &note.path);
+                lines.push(format!("## [{}] {}", number, note.title));
                 lines.push(note.snippet.clone());
                 lines.push("".to_string());
             }
@@ -918,6 +1357,7 @@ fn build_notes_section(
             vault_name: &snapshot.connect_obsidian_vault,
             query: &enriched_query,
             intent,
+            citations,
         };
         if let Ok(Some(obsidian_context)) = obsidian::build_obsidian_context(request) {
             notes_used = obsidian_context.count;
@@ -957,6 +1397,7 @@ fn send_status(agent_tx: Option<&std::sync::mpsc::Sender<crate::app::AgentEvent>
 
 struct AssembleParams<'a> {
     prompt_lines: Vec<String>,
+    prompt_sections: Vec<PromptSectionText>,
     chat_history: &'a [ChatMessage],
     has_context_usage: bool,
     context_usage: ContextUsage,
@@ -964,6 +1405,7 @@ struct AssembleParams<'a> {
     forced_response: Option<String>,
     notes_to_cache: Option<(String, Vec<crate::services::obsidian::NoteSnippet>)>,
     recall_context_to_cache: Option<String>,
+    citations: Citations,
 }
 
 /// Tier 4: Assemble final messages from prompt lines and chat history
@@ -972,7 +1414,20 @@ fn assemble_final_messages(params: AssembleParams) -> ChatBuildResultWithUsage {
     let mut messages = vec![AgentChatMessage::system(merged_prompt)];
     for chat_message in params.chat_history {
         if chat_message.role == MessageRole::User {
-            messages.push(AgentChatMessage::user(&chat_message.content));
+            // Messages typed with /as name their speaker so the model can tell people apart
+            let content = match &chat_message.display_name {
+                Some(speaker) => format!("[Message from {}]\n{}", speaker, chat_message.content),
+                None => chat_message.content.clone(),
+            };
+            match &chat_message.quote {
+                Some(quote) => messages.push(AgentChatMessage::user(format!(
+                    "[Replying to this earlier {} message: \"{}\"]\n\n{}",
+                    quoted_role_label(quote),
+                    cap_quote(&quote.content),
+                    content
+                ))),
+                None => messages.push(AgentChatMessage::user(content)),
+            }
         } else if chat_message.role == MessageRole::Assistant {
             messages.push(AgentChatMessage::assistant(&chat_message.content));
         }
@@ -989,15 +1444,89 @@ fn assemble_final_messages(params: AssembleParams) -> ChatBuildResultWithUsage {
         forced_response: params.forced_response,
         notes_to_cache: params.notes_to_cache,
         recall_context_to_cache: params.recall_context_to_cache,
+        prompt_sections: params.prompt_sections,
+        citations: params.citations,
     }
 }
 
+/// Remembers where each prompt section starts in the flat list of prompt lines
+#[derive(Default)]
+struct PromptSectionMarks {
+    starts: Vec<(PromptSection, usize)>,
+}
 
+impl PromptSectionMarks {
+    /// Lines pushed after this call belong to `section`
+    fn begin(&mut self, section: PromptSection, lines: &[String]) {
+        self.starts.push((section, lines.len()));
+    }
+
+    /// Groups the prompt lines by section; lines before the first mark are foundation
+    fn split(&self, lines: Vec<String>) -> Vec<PromptSectionText> {
+        let mut sections: Vec<PromptSectionText> = Vec::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            let section = self
+                .starts
+                .iter()
+                .rev()
+                .find(|(_, start)| *start <= index)
+                .map_or(PromptSection::Foundation, |(section, _)| *section);
+            match sections.iter_mut().find(|part| part.section == section) {
+                Some(part) => {
+                    part.text.push_str("\n\n");
+                    part.text.push_str(&line);
+                }
+                None => sections.push(PromptSectionText { section, text: line }),
+            }
+        }
+        sections
+    }
+}
 
 
 
 
 
+
+
+/// Recent messages checked for the active project's topic
+const ACTIVE_PROJECT_MATCH_MESSAGES: usize = 4;
+/// Newest project entries included in the prompt
+const ACTIVE_PROJECT_MAX_ENTRIES: usize = 40;
+
+/// Longest part of a quoted message repeated alongside the reply
+const QUOTE_PROMPT_MAX_CHARS: usize = 1500;
+
+fn quoted_role_label(quote: &MessageQuote) -> &'static str {
+    match quote.role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+        MessageRole::System => "system",
+    }
+}
+
+fn cap_quote(content: &str) -> String {
+    if content.chars().count() > QUOTE_PROMPT_MAX_CHARS {
+        format!("{}…", content.chars().take(QUOTE_PROMPT_MAX_CHARS).collect::<String>())
+    } else {
+        content.to_string()
+    }
+}
+
+fn format_reply_prompt(quote: &MessageQuote) -> String {
+    let speaker = match quote.role {
+        MessageRole::Assistant => "one of your earlier messages",
+        MessageRole::User => "one of their own earlier messages",
+        MessageRole::System => "an earlier system notice",
+    };
+    format!(
+        "REPLY CONTEXT: The user's latest message is a reply to {}, quoted at the start of \
+their message. Treat the quoted message as the subject of their reply: resolve references like \
+\"this\" or \"that\" against it rather than the most recent messages, and focus your answer on it.",
+        speaker
+    )
+}
+
 #[derive(Debug, Clone)]
 enum UserContextKind {
     Always,
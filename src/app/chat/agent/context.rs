@@ -186,7 +186,7 @@ fn filter_summaries_by_range(
             .detailed_summary
             .as_deref()
             .filter(|value| is_real_summary(value))
-            .or_else(|| convo.summary.as_deref().filter(|value| is_real_summary(value)));
+            .or_else(|| convo.title().filter(|value| is_real_summary(value)));
         let Some(summary) = summary_text else {
             continue;
         };
@@ -186,7 +186,7 @@ fn classify_with_model(query: &str, context: IntentModelContext<'_>) -> Option<M
         ChatMessage::system(INTENT_SYSTEM_PROMPT),
         ChatMessage::user(query),
     ];
-    let response = context.manager.chat(agent, &messages).ok()?;
+    let response = context.manager.chat_cached(agent, &messages).ok()?;
     parse_model_intent(&response)
 }
 
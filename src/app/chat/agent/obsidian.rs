@@ -1,4 +1,5 @@
 use crate::app::chat::agent::intent::QueryIntent;
+use crate::services::citations::{Citations, SourceKind};
 use crate::services::{dates, obsidian};
 use color_eyre::Result;
 
@@ -14,6 +15,8 @@ pub struct ObsidianContextRequest<'a> {
     pub vault_name: &'a str,
     pub query: &'a str,
     pub intent: QueryIntent,
+    /// Numbers the notes that end up in the prompt
+    pub citations: &'a mut Citations,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -29,7 +32,7 @@ enum ObsidianAction {
 }
 
 pub fn build_obsidian_context(
-    request: ObsidianContextRequest<'_>,
+    mut request: ObsidianContextRequest<'_>,
 ) -> Result<Option<ObsidianContext>> {
     let vault_name = request.vault_name.trim();
     let lowered = request.query.to_lowercase();
@@ -58,7 +61,9 @@ pub fn build_obsidian_context(
             let raw_notes = notes.clone();
             let mut blocks = Vec::new();
             if let Some(content) =
-                obsidian::format_obsidian_context("Obsidian weekly notes", &notes)
+                obsidian::format_obsidian_context("Obsidian weekly notes", &notes, |note| {
+                    request.citations.cite(SourceKind::Note, &note.title, &note.path)
+                })
             {
                 blocks.push(clamp_context_chars(&content, MAX_OBSIDIAN_CONTEXT_CHARS));
             } else {
@@ -98,7 +103,8 @@ pub fn build_obsidian_context(
                     let trimmed = content.trim();
                     if !trimmed.is_empty() {
                         notes.push(obsidian::NoteSnippet {
-                            title: date_str,
+                            title: date_str.clone(),
+                            path: date_str,
                             note_type: obsidian::NoteType::Daily,
                             snippet: trimmed.to_string(),
                         });
@@ -107,7 +113,9 @@ pub fn build_obsidian_context(
                 current += chrono::Duration::days(1);
             }
             if let Some(content) =
-                obsidian::format_obsidian_context("Obsidian daily notes", &notes)
+                obsidian::format_obsidian_context("Obsidian daily notes", &notes, |note| {
+                    request.citations.cite(SourceKind::Note, &note.title, &note.path)
+                })
             {
                 let count = notes.len();
                 let raw_notes = notes.clone();
@@ -134,7 +142,9 @@ pub fn build_obsidian_context(
         }
         ObsidianAction::NoteSearch => {
             let notes = obsidian::search_notes(vault_name, request.query, 8)?;
-            if let Some(content) = obsidian::format_obsidian_context("Obsidian notes", &notes) {
+            if let Some(content) = obsidian::format_obsidian_context("Obsidian notes", &notes, |note| {
+                    request.citations.cite(SourceKind::Note, &note.title, &note.path)
+                }) {
                 let count = notes.len();
                 let raw_notes = notes.clone();
                 let content = clamp_context_chars(&content, MAX_OBSIDIAN_CONTEXT_CHARS);
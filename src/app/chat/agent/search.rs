@@ -1,5 +1,6 @@
 use crate::agents::brave::{self, BraveSearchParams};
 use crate::app::chat::agent::intent::QueryIntent;
+use crate::services::citations::{Citations, SourceKind};
 
 pub struct SearchContext {
     brave_key: String,
@@ -27,13 +28,14 @@ impl SearchContext {
 pub fn enrich_prompt_with_search_snapshot(
     context: &SearchContext,
     prompt_lines: &mut Vec<String>,
+    citations: &mut Citations,
     request: SearchSnapshotRequest<'_>,
 ) -> Option<String> {
     let freshness = detect_freshness(request.query);
     let action = select_search_action(request, freshness)?;
     match action {
         SearchAction::BraveSearch { query, freshness } => {
-            append_brave_search_results_snapshot(context, prompt_lines, &query, freshness)
+            append_brave_search_results_snapshot(context, prompt_lines, citations, &query, freshness)
         }
     }
 }
@@ -41,6 +43,7 @@ pub fn enrich_prompt_with_search_snapshot(
 fn append_brave_search_results_snapshot(
     context: &SearchContext,
     prompt_lines: &mut Vec<String>,
+    citations: &mut Citations,
     query: &str,
     freshness: Option<String>,
 ) -> Option<String> {
@@ -62,7 +65,9 @@ fn append_brave_search_results_snapshot(
                 return Some("I couldn't find any live search results for that.".to_string());
             }
 
-            let formatted = brave::format_results_for_llm(&results);
+            let formatted = brave::format_results_for_llm(&results, |result| {
+                citations.cite(SourceKind::Web, &result.title, &result.url)
+            });
 
             prompt_lines.push(
                 "All temperatures must be in Celsius (metric units). Do not use Fahrenheit."
@@ -94,6 +99,14 @@ pub(crate) fn should_use_brave_search(query: &str) -> bool {
     if looks_like_weather_question(&lowered) {
         return false;
     }
+    if crate::services::transit::looks_like_transit_question(&lowered) {
+        return false;
+    }
+    if crate::services::finance::quote_symbol(trimmed).is_some()
+        || crate::services::calculator::currency_codes(trimmed).is_some()
+    {
+        return false;
+    }
     if looks_like_entity_query(trimmed) {
         return true;
     }
@@ -1,6 +1,8 @@
 use crate::agents::openai_compat::{
     FunctionDefinition, ToolCallResponse, ToolDefinition,
 };
+use crate::app::types::PromptSection;
+use crate::services::citations::SourceKind;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -13,7 +15,117 @@ pub enum ToolCall {
     RetrieveMemories { query: String },
     CreateProject { name: String, description: String },
     SearchProjects { query: String },
+    SearchFiles { query: String },
+    QueryDatabase { database: String, sql: String },
+    ListGithubWork { kind: String },
+    GithubPullRequest { pull: String },
+    GitDiff { target: String },
+    HomeAssistantState { query: String },
+    HomeAssistantCall { service: String, entity: String },
+    TransitDepartures {
+        stop: String,
+        #[serde(default)]
+        mode: String,
+    },
+    StockQuote { symbol: String },
+    ConvertCurrency { amount: f64, from: String, to: String },
     DeleteProject { name: String },
+    ProposeTask { project: String, title: String },
+    CompleteTask { project: String, title: String },
+    DraftEmail { to: String, subject: String, body: String },
+}
+
+impl ToolCall {
+    /// Name the model calls the tool by
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ToolCall::SearchNotes { .. } => "search_notes",
+            ToolCall::SearchWeb { .. } => "search_web",
+            ToolCall::RetrieveMemories { .. } => "retrieve_memories",
+            ToolCall::CreateProject { .. } => "create_project",
+            ToolCall::SearchProjects { .. } => "search_projects",
+            ToolCall::SearchFiles { .. } => "search_files",
+            ToolCall::QueryDatabase { .. } => "query_database",
+            ToolCall::ListGithubWork { .. } => "list_github_work",
+            ToolCall::GithubPullRequest { .. } => "github_pull_request",
+            ToolCall::GitDiff { .. } => "git_diff",
+            ToolCall::HomeAssistantState { .. } => "home_assistant_state",
+            ToolCall::HomeAssistantCall { .. } => "home_assistant_call",
+            ToolCall::TransitDepartures { .. } => "transit_departures",
+            ToolCall::StockQuote { .. } => "stock_quote",
+            ToolCall::ConvertCurrency { .. } => "convert_currency",
+            ToolCall::DeleteProject { .. } => "delete_project",
+            ToolCall::ProposeTask { .. } => "propose_task",
+            ToolCall::CompleteTask { .. } => "complete_task",
+            ToolCall::DraftEmail { .. } => "draft_email",
+        }
+    }
+
+    /// Whether the call changes something outside the conversation; dry-run holds these back
+    #[must_use]
+    pub fn changes_something(&self) -> bool {
+        match self {
+            ToolCall::CreateProject { .. }
+            | ToolCall::HomeAssistantCall { .. }
+            | ToolCall::DeleteProject { .. }
+            | ToolCall::ProposeTask { .. }
+            | ToolCall::CompleteTask { .. }
+            | ToolCall::DraftEmail { .. } => true,
+            ToolCall::SearchNotes { .. }
+            | ToolCall::SearchWeb { .. }
+            | ToolCall::RetrieveMemories { .. }
+            | ToolCall::SearchProjects { .. }
+            | ToolCall::SearchFiles { .. }
+            | ToolCall::QueryDatabase { .. }
+            | ToolCall::ListGithubWork { .. }
+            | ToolCall::GithubPullRequest { .. }
+            | ToolCall::GitDiff { .. }
+            | ToolCall::HomeAssistantState { .. }
+            | ToolCall::TransitDepartures { .. }
+            | ToolCall::StockQuote { .. }
+            | ToolCall::ConvertCurrency { .. } => false,
+        }
+    }
+
+    /// Arguments as compact JSON, for the audit log and dry runs
+    #[must_use]
+    pub fn arguments(&self) -> String {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(mut fields)) => {
+                fields.remove("tool");
+                serde_json::Value::Object(fields).to_string()
+            }
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        }
+    }
+
+    /// Tool name and the context source it reads, for tools a context modifier can turn off
+    #[must_use]
+    pub fn context_source(&self) -> Option<(&'static str, PromptSection)> {
+        match self {
+            ToolCall::SearchNotes { .. } => Some(("search_notes", PromptSection::Notes)),
+            ToolCall::SearchWeb { .. } => Some(("search_web", PromptSection::WebSearch)),
+            ToolCall::RetrieveMemories { .. } => Some(("retrieve_memories", PromptSection::Memories)),
+            ToolCall::CreateProject { .. }
+            | ToolCall::SearchProjects { .. }
+            | ToolCall::SearchFiles { .. }
+            | ToolCall::QueryDatabase { .. }
+            | ToolCall::ListGithubWork { .. }
+            | ToolCall::GithubPullRequest { .. }
+            | ToolCall::GitDiff { .. }
+            | ToolCall::HomeAssistantState { .. }
+            | ToolCall::HomeAssistantCall { .. }
+            | ToolCall::TransitDepartures { .. }
+            | ToolCall::StockQuote { .. }
+            | ToolCall::ConvertCurrency { .. }
+            | ToolCall::DeleteProject { .. }
+            | ToolCall::ProposeTask { .. }
+            | ToolCall::CompleteTask { .. }
+            | ToolCall::DraftEmail { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +136,27 @@ pub struct ToolResult {
 
 // -- Native tool calling (OpenAI-compatible API) --
 
+/// Tool definitions minus the ones reading a source in `skipped` and the ones the
+/// `[tools]` policy does not allow
+pub fn tool_definitions_without(
+    skipped: &[PromptSection],
+    policy: &crate::config::ToolsConfig,
+) -> Vec<ToolDefinition> {
+    let hidden = |name: &str| match name {
+        "search_notes" => skipped.contains(&PromptSection::Notes),
+        "search_web" => skipped.contains(&PromptSection::WebSearch),
+        "retrieve_memories" => skipped.contains(&PromptSection::Memories),
+        _ => false,
+    };
+    get_tool_definitions()
+        .into_iter()
+        .filter(|definition| {
+            !hidden(&definition.function.name)
+                && crate::services::tool_policy::is_allowed(policy, &definition.function.name)
+        })
+        .collect()
+}
+
 /// Returns structured tool definitions for the OpenAI-compatible tools API
 pub fn get_tool_definitions() -> Vec<ToolDefinition> {
     let query_params = json!({
@@ -63,6 +196,161 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         "required": ["name"]
     });
 
+    let task_params = json!({
+        "type": "object",
+        "properties": {
+            "project": {
+                "type": "string",
+                "description": "The project the task belongs to"
+            },
+            "title": {
+                "type": "string",
+                "description": "Short task or milestone title (e.g. Benchmark the parser)"
+            }
+        },
+        "required": ["project", "title"]
+    });
+
+    let email_params = json!({
+        "type": "object",
+        "properties": {
+            "to": {
+                "type": "string",
+                "description": "Recipient email address"
+            },
+            "subject": {
+                "type": "string",
+                "description": "Subject line"
+            },
+            "body": {
+                "type": "string",
+                "description": "Full plain-text body of the email"
+            }
+        },
+        "required": ["to", "subject", "body"]
+    });
+
+    let database_params = json!({
+        "type": "object",
+        "properties": {
+            "database": {
+                "type": "string",
+                "description": "Name of a registered database"
+            },
+            "sql": {
+                "type": "string",
+                "description": "One read-only SELECT query; leave empty to list the tables and columns"
+            }
+        },
+        "required": ["database", "sql"]
+    });
+
+    let github_work_params = json!({
+        "type": "object",
+        "properties": {
+            "kind": {
+                "type": "string",
+                "enum": ["prs", "reviews", "issues"],
+                "description": "prs: pull requests I opened, reviews: pull requests waiting for my review, issues: issues assigned to me"
+            }
+        },
+        "required": ["kind"]
+    });
+
+    let github_pull_params = json!({
+        "type": "object",
+        "properties": {
+            "pull": {
+                "type": "string",
+                "description": "Pull request as owner/repo#123 or its URL"
+            }
+        },
+        "required": ["pull"]
+    });
+
+    let git_diff_params = json!({
+        "type": "object",
+        "properties": {
+            "target": {
+                "type": "string",
+                "description": "Empty for uncommitted changes, \"today\" for today's commits plus uncommitted changes, or a commit / a..b range"
+            }
+        },
+        "required": ["target"]
+    });
+
+    let home_assistant_state_params = json!({
+        "type": "object",
+        "properties": {
+            "query": {
+                "type": "string",
+                "description": "Entity id (binary_sensor.balcony_door) or words from the device name (balcony door)"
+            }
+        },
+        "required": ["query"]
+    });
+
+    let home_assistant_call_params = json!({
+        "type": "object",
+        "properties": {
+            "service": {
+                "type": "string",
+                "description": "Service to call, e.g. light.turn_off, switch.toggle, cover.open_cover, or just turn_off"
+            },
+            "entity": {
+                "type": "string",
+                "description": "Entity id or device name to act on"
+            }
+        },
+        "required": ["service", "entity"]
+    });
+
+    let transit_params = json!({
+        "type": "object",
+        "properties": {
+            "stop": {
+                "type": "string",
+                "description": "Stop name as on the departure boards (Anděl), or \"home\" / \"work\" for the user's usual stops"
+            },
+            "mode": {
+                "type": "string",
+                "enum": ["", "tram", "metro", "bus", "train", "ferry"],
+                "description": "Only this kind of vehicle; empty for every departure"
+            }
+        },
+        "required": ["stop"]
+    });
+
+    let stock_quote_params = json!({
+        "type": "object",
+        "properties": {
+            "symbol": {
+                "type": "string",
+                "description": "Ticker symbol, e.g. AAPL, MSFT, CEZ.PR or BTC-USD"
+            }
+        },
+        "required": ["symbol"]
+    });
+
+    let convert_currency_params = json!({
+        "type": "object",
+        "properties": {
+            "amount": {
+                "type": "number",
+                "description": "Amount to convert"
+            },
+            "from": {
+                "type": "string",
+                "description": "Currency code to convert from, e.g. USD"
+            },
+            "to": {
+                "type": "string",
+                "description": "Currency code to convert to, e.g. CZK"
+            }
+        },
+        "required": ["amount", "from", "to"]
+    });
+
     vec![
         ToolDefinition {
             tool_type: "function".to_string(),
@@ -101,6 +389,14 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
             function: FunctionDefinition {
                 name: "search_projects".to_string(),
                 description: "Search your accumulated project knowledge base stored in Obsidian. Use when the user asks about a topic you've been tracking across conversations.".to_string(),
+                parameters: query_params.clone(),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "search_files".to_string(),
+                description: "Search the user's local files (contents and names) under the directories they allowed. Returns matching files with line snippets. Use for questions about their code, documents or files on disk.".to_string(),
                 parameters: query_params,
             },
         },
@@ -112,6 +408,102 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                 parameters: name_params,
             },
         },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "propose_task".to_string(),
+                description: "Propose a task or milestone for a project. The user confirms it in /projects before it becomes open. Use when the user mentions something they intend to do for the project.".to_string(),
+                parameters: task_params.clone(),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "complete_task".to_string(),
+                description: "Mark an open project task as done. Use when the user says they finished one of the open tasks.".to_string(),
+                parameters: task_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "draft_email".to_string(),
+                description: "Draft an email for the user to send. It is shown to the user for review and only sent if they confirm. Use when the user asks you to write or send an email.".to_string(),
+                parameters: email_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "query_database".to_string(),
+                description: "Run a read-only SQL query against one of the user's registered databases and get the rows back. Call it with an empty sql first to see the tables and columns. Use for questions about their data (e.g. how many orders yesterday).".to_string(),
+                parameters: database_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "list_github_work".to_string(),
+                description: "List the user's open GitHub pull requests, pull requests awaiting their review, or issues assigned to them. Use when they ask about their PRs, reviews or issues.".to_string(),
+                parameters: github_work_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "github_pull_request".to_string(),
+                description: "Fetch a GitHub pull request's description, changed files and diff excerpts so you can summarize or review it.".to_string(),
+                parameters: github_pull_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "git_diff".to_string(),
+                description: "Show the diff of the git repository the user launched you in. Use before summarizing what they changed.".to_string(),
+                parameters: git_diff_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "home_assistant_state".to_string(),
+                description: "Read the current state of Home Assistant devices and sensors (doors, windows, lights, temperatures). Use when the user asks about their home.".to_string(),
+                parameters: home_assistant_state_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "home_assistant_call".to_string(),
+                description: "Control a Home Assistant device by calling a service. The user is asked to confirm first; nothing happens until they do.".to_string(),
+                parameters: home_assistant_call_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "transit_departures".to_string(),
+                description: "Real-time Prague public transport departures (tram, metro, bus) from a stop. Use when the user asks when the next tram or bus leaves, instead of searching the web.".to_string(),
+                parameters: transit_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "stock_quote".to_string(),
+                description: "Latest price of a stock, fund or cryptocurrency by ticker symbol, with the change since the previous close. Use for questions like \"AAPL price\" instead of searching the web.".to_string(),
+                parameters: stock_quote_params,
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "convert_currency".to_string(),
+                description: "Convert an amount between currencies at current exchange rates.".to_string(),
+                parameters: convert_currency_params,
+            },
+        },
     ]
 }
 
@@ -121,13 +513,14 @@ pub fn parse_native_tool_calls(calls: &[ToolCallResponse]) -> Vec<ToolCall> {
     for call in calls {
         let name = call.function.name.as_str();
         match name {
-            "search_notes" | "search_web" | "retrieve_memories" | "search_projects" => {
+            "search_notes" | "search_web" | "retrieve_memories" | "search_projects" | "search_files" => {
                 if let Some(query) = extract_query_from_arguments(&call.function.arguments) {
                     match name {
                         "search_notes" => tools.push(ToolCall::SearchNotes { query }),
                         "search_web" => tools.push(ToolCall::SearchWeb { query }),
                         "retrieve_memories" => tools.push(ToolCall::RetrieveMemories { query }),
                         "search_projects" => tools.push(ToolCall::SearchProjects { query }),
+                        "search_files" => tools.push(ToolCall::SearchFiles { query }),
                         _ => {}
                     }
                 }
@@ -142,6 +535,78 @@ pub fn parse_native_tool_calls(calls: &[ToolCallResponse]) -> Vec<ToolCall> {
                     tools.push(ToolCall::DeleteProject { name: name_val });
                 }
             }
+            "propose_task" | "complete_task" => {
+                if let Some((project, title)) = extract_task_args(&call.function.arguments) {
+                    if name == "propose_task" {
+                        tools.push(ToolCall::ProposeTask { project, title });
+                    } else {
+                        tools.push(ToolCall::CompleteTask { project, title });
+                    }
+                }
+            }
+            "query_database" => {
+                if let Some((database, sql)) = extract_database_args(&call.function.arguments) {
+                    tools.push(ToolCall::QueryDatabase { database, sql });
+                }
+            }
+            "list_github_work" => {
+                let kind = serde_json::from_str::<serde_json::Value>(&call.function.arguments)
+                    .ok()
+                    .and_then(|args| args.get("kind").and_then(|kind| kind.as_str()).map(str::to_string))
+                    .unwrap_or_default();
+                tools.push(ToolCall::ListGithubWork { kind });
+            }
+            "github_pull_request" => {
+                if let Some(pull) = serde_json::from_str::<serde_json::Value>(&call.function.arguments)
+                    .ok()
+                    .and_then(|args| args.get("pull").and_then(|pull| pull.as_str()).map(str::to_string))
+                {
+                    tools.push(ToolCall::GithubPullRequest { pull });
+                }
+            }
+            "git_diff" => {
+                let target = serde_json::from_str::<serde_json::Value>(&call.function.arguments)
+                    .ok()
+                    .and_then(|args| args.get("target").and_then(|target| target.as_str()).map(str::to_string))
+                    .unwrap_or_default();
+                tools.push(ToolCall::GitDiff { target });
+            }
+            "home_assistant_state" => {
+                if let Some(query) = extract_query_from_arguments(&call.function.arguments) {
+                    tools.push(ToolCall::HomeAssistantState { query });
+                }
+            }
+            "home_assistant_call" => {
+                if let Some((service, entity)) = extract_home_assistant_call_args(&call.function.arguments) {
+                    tools.push(ToolCall::HomeAssistantCall { service, entity });
+                }
+            }
+            "transit_departures" => {
+                if let Ok(args) = serde_json::from_str::<serde_json::Value>(&call.function.arguments) {
+                    let field = |key: &str| {
+                        args.get(key).and_then(|value| value.as_str()).unwrap_or_default().to_string()
+                    };
+                    tools.push(ToolCall::TransitDepartures { stop: field("stop"), mode: field("mode") });
+                }
+            }
+            "stock_quote" => {
+                if let Some(symbol) = serde_json::from_str::<serde_json::Value>(&call.function.arguments)
+                    .ok()
+                    .and_then(|args| args.get("symbol").and_then(|symbol| symbol.as_str()).map(str::to_string))
+                {
+                    tools.push(ToolCall::StockQuote { symbol });
+                }
+            }
+            "convert_currency" => {
+                if let Some((amount, from, to)) = extract_currency_args(&call.function.arguments) {
+                    tools.push(ToolCall::ConvertCurrency { amount, from, to });
+                }
+            }
+            "draft_email" => {
+                if let Some((to, subject, body)) = extract_email_args(&call.function.arguments) {
+                    tools.push(ToolCall::DraftEmail { to, subject, body });
+                }
+            }
             _ => {} // Unknown tool, skip
         }
     }
@@ -165,6 +630,56 @@ fn extract_create_project_args(arguments: &str) -> Option<(String, String)> {
     Some((name, description))
 }
 
+/// Extracts "project" and "title" fields for propose_task and complete_task
+fn extract_task_args(arguments: &str) -> Option<(String, String)> {
+    let parsed: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let project = parsed.get("project")?.as_str()?.to_string();
+    let title = parsed.get("title")?.as_str()?.to_string();
+    Some((project, title))
+}
+
+/// Extracts "to", "subject" and "body" fields for draft_email
+fn extract_email_args(arguments: &str) -> Option<(String, String, String)> {
+    let parsed: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let to = parsed.get("to")?.as_str()?.to_string();
+    let subject = parsed.get("subject")?.as_str()?.to_string();
+    let body = parsed.get("body")?.as_str()?.to_string();
+    Some((to, subject, body))
+}
+
+/// Extracts "amount", "from" and "to" fields for convert_currency; the amount may be a string
+fn extract_currency_args(arguments: &str) -> Option<(f64, String, String)> {
+    let parsed: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let amount = parsed.get("amount").and_then(|amount| {
+        amount
+            .as_f64()
+            .or_else(|| amount.as_str().and_then(|text| text.trim().parse().ok()))
+    })?;
+    let from = parsed.get("from")?.as_str()?.to_string();
+    let to = parsed.get("to")?.as_str()?.to_string();
+    Some((amount, from, to))
+}
+
+/// Extracts "service" and "entity" fields for home_assistant_call
+fn extract_home_assistant_call_args(arguments: &str) -> Option<(String, String)> {
+    let parsed: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let service = parsed.get("service")?.as_str()?.to_string();
+    let entity = parsed.get("entity")?.as_str()?.to_string();
+    Some((service, entity))
+}
+
+/// Extracts "database" and "sql" fields for query_database
+fn extract_database_args(arguments: &str) -> Option<(String, String)> {
+    let parsed: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let database = parsed.get("database")?.as_str()?.to_string();
+    let sql = parsed
+        .get("sql")
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string();
+    Some((database, sql))
+}
+
 /// Extracts the "name" field from a JSON arguments string
 fn extract_name_from_arguments(arguments: &str) -> Option<String> {
     let parsed: serde_json::Value = serde_json::from_str(arguments).ok()?;
@@ -205,6 +720,58 @@ AVAILABLE TOOLS (use when you need information you don't have):
    Format: {"tool":"delete_project","name":"Project Name"}
    When to use: User asks to remove or delete a project
 
+7. propose_task: Propose a task or milestone for a project (the user confirms it)
+   Format: {"tool":"propose_task","project":"Project Name","title":"what to do"}
+   When to use: User mentions something they intend to do for a project
+
+8. complete_task: Mark an open project task as done
+   Format: {"tool":"complete_task","project":"Project Name","title":"the task"}
+   When to use: User says they finished one of the open tasks
+
+9. draft_email: Draft an email; the user reviews it and decides whether to send
+   Format: {"tool":"draft_email","to":"name@example.com","subject":"...","body":"..."}
+   When to use: User asks you to write or send an email
+
+10. search_files: Search the user's local files (contents and names)
+   Format: {"tool":"search_files","query":"text or file name"}
+   When to use: User asks about their code, documents or files on disk
+
+11. query_database: Run one read-only SQL query on a registered database
+   Format: {"tool":"query_database","database":"name","sql":"SELECT ..."}
+   When to use: User asks about data in their databases; send an empty sql first to list tables
+
+12. list_github_work: List my open GitHub PRs, review requests or assigned issues
+   Format: {"tool":"list_github_work","kind":"prs"} (kind: prs, reviews or issues)
+   When to use: User asks about their pull requests, reviews or issues
+
+13. github_pull_request: Fetch a PR's description, files and diff excerpts
+   Format: {"tool":"github_pull_request","pull":"owner/repo#123"}
+   When to use: User asks what a PR changes or wants it summarized
+
+14. git_diff: Show changes in the git repository you were launched in
+   Format: {"tool":"git_diff","target":"today"} (target: "" uncommitted, "today", a commit or a..b)
+   When to use: User asks what they changed, or about a commit
+
+15. home_assistant_state: Read the state of Home Assistant devices and sensors
+   Format: {"tool":"home_assistant_state","query":"balcony door"}
+   When to use: User asks whether something at home is open, on, or how warm it is
+
+16. home_assistant_call: Control a Home Assistant device (the user confirms first)
+   Format: {"tool":"home_assistant_call","service":"light.turn_off","entity":"living room lights"}
+   When to use: User asks you to switch, open, close or set something at home
+
+17. transit_departures: Real-time Prague public transport departures from a stop
+   Format: {"tool":"transit_departures","stop":"Anděl","mode":"tram"} (stop may be "home" or "work"; mode may be empty)
+   When to use: User asks when the next tram, metro or bus leaves; do not search the web for this
+
+18. stock_quote: Latest price of a stock, fund or cryptocurrency
+   Format: {"tool":"stock_quote","symbol":"AAPL"}
+   When to use: User asks for a share price or how a ticker is doing
+
+19. convert_currency: Convert an amount at current exchange rates
+   Format: {"tool":"convert_currency","amount":300,"from":"USD","to":"CZK"}
+   When to use: User asks how much an amount is in another currency
+
 CRITICAL RULES:
 - If you need information, output ONLY the tool JSON and nothing else
 - DO NOT add explanations or commentary with tool calls
@@ -254,14 +821,70 @@ pub fn format_tool_results(results: &[ToolResult]) -> String {
     output
 }
 
+/// Finds the open task the model referred to: an exact title first, then a partial match
+fn find_open_task<'a>(
+    tasks: &'a [crate::storage::ProjectTask],
+    title: &str,
+) -> Option<&'a crate::storage::ProjectTask> {
+    let wanted = title.trim().to_lowercase();
+    let open = || {
+        tasks
+            .iter()
+            .filter(|task| task.status == crate::services::projects::TaskStatus::Open)
+    };
+    open()
+        .find(|task| task.title.to_lowercase() == wanted)
+        .or_else(|| {
+            open().find(|task| {
+                let candidate = task.title.to_lowercase();
+                candidate.contains(&wanted) || wanted.contains(&candidate)
+            })
+        })
+}
+
+/// Configuration and handles the tools run with
+#[derive(Clone, Copy)]
+pub struct ToolContext<'a> {
+    pub vault_name: &'a str,
+    pub vault_path: &'a str,
+    pub brave_key: &'a str,
+    pub github_key: &'a str,
+    pub runtime: Option<&'a tokio::runtime::Runtime>,
+    /// App storage handle cloned before the thread spawn; opening a new one would hit the RocksDB lock
+    pub storage: Option<&'a crate::storage::StorageManager>,
+    pub file_search: &'a crate::config::FileSearchConfig,
+    pub databases: &'a [crate::config::DatabaseConfig],
+    pub git: &'a crate::config::GitConfig,
+    pub git_repo: Option<&'a std::path::Path>,
+    pub home_assistant: &'a crate::config::HomeAssistantConfig,
+    pub agent_tx: &'a std::sync::mpsc::Sender<crate::app::AgentEvent>,
+    /// Numbers the notes, pages and memories that search tools hand back
+    pub citations: &'a std::sync::Mutex<crate::services::citations::Citations>,
+    pub manager: &'a crate::agents::AgentManager,
+    /// Agents asked to rewrite memory queries, routing model first
+    pub query_rewriters: &'a [&'a crate::agents::Agent],
+}
+
 /// Execute a tool call and return the result
-pub fn execute_tool(
-    tool: &ToolCall,
-    vault_name: &str,
-    vault_path: &str,
-    brave_key: &str,
-    runtime: Option<&tokio::runtime::Runtime>,
-) -> ToolResult {
+pub fn execute_tool(tool: &ToolCall, context: &ToolContext) -> ToolResult {
+    let ToolContext {
+        vault_name,
+        vault_path,
+        brave_key,
+        github_key,
+        runtime,
+        storage,
+        file_search,
+        databases,
+        git,
+        git_repo,
+        home_assistant,
+        agent_tx,
+        citations,
+        manager,
+        query_rewriters,
+    } = *context;
+    let citations = move || citations.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
     match tool {
         ToolCall::SearchNotes { query } => {
             let result = if vault_name.trim().is_empty() {
@@ -269,7 +892,9 @@ pub fn execute_tool(
             } else {
                 match crate::services::obsidian::search_notes(vault_name, query, 5) {
                     Ok(notes) if !notes.is_empty() => {
-                        if let Some(formatted) = crate::services::obsidian::format_obsidian_context("Notes", &notes) {
+                        if let Some(formatted) = crate::services::obsidian::format_obsidian_context("Notes", &notes, |note| {
+                            citations().cite(SourceKind::Note, &note.title, &note.path)
+                        }) {
                             formatted
                         } else {
                             format!("Found {} notes but couldn't format them.", notes.len())
@@ -295,7 +920,9 @@ pub fn execute_tool(
                 let params = crate::agents::brave::BraveSearchParams::default();
                 match crate::agents::brave::search(brave_key, query, &params) {
                     Ok(results) if !results.is_empty() => {
-                        let formatted = crate::agents::brave::format_results_for_llm(&results);
+                        let formatted = crate::agents::brave::format_results_for_llm(&results, |result| {
+                            citations().cite(SourceKind::Web, &result.title, &result.url)
+                        });
                         format!("Search results for '{}':\n{}", query, formatted)
                     }
                     Ok(_) => format!("No search results found for: {}", query),
@@ -314,19 +941,26 @@ pub fn execute_tool(
                 let embeddings_config = crate::config::Config::load()
                     .map(|config| config.embeddings)
                     .unwrap_or_default();
+                let expansions = if embeddings_config.query_rewrite {
+                    crate::services::retrieval::expand_query(query, query_rewriters, manager)
+                } else {
+                    Vec::new()
+                };
 
                 match rt.block_on(async {
                     let storage = crate::storage::StorageManager::new().await?;
-                    crate::services::retrieval::retrieve_relevant_messages(
+                    crate::services::retrieval::retrieve_with_expansions(
                         &storage,
                         query,
+                        &expansions,
                         embeddings_config.max_retrieved_messages,
                         embeddings_config.similarity_threshold,
                     ).await
                 }) {
                     Ok(messages) if !messages.is_empty() => {
+                        let mut citations = citations();
                         let formatted: Vec<String> = messages.iter()
-                            .map(|msg| format!("[{}] {}: {}", msg.timestamp, msg.role, msg.content))
+                            .map(|msg| super::format_cited_memory(msg, &mut citations))
                             .collect();
                         formatted.join("\n")
                     }
@@ -407,5 +1041,238 @@ pub fn execute_tool(
                 result,
             }
         }
+        ToolCall::ProposeTask { project, title } => {
+            let result = if let Some((rt, storage)) = runtime.zip(storage) {
+                match rt.block_on(async {
+                    storage
+                        .save_project_task(project, title, crate::services::projects::TaskStatus::Proposed)
+                        .await
+                }) {
+                    Ok(_) => format!(
+                        "Task '{}' proposed for project '{}'. It is waiting for the user's confirmation in /projects.",
+                        title, project
+                    ),
+                    Err(error) => format!("Could not save task: {}", error),
+                }
+            } else {
+                "Storage not available for project tasks.".to_string()
+            };
+            ToolResult {
+                tool: "propose_task".to_string(),
+                result,
+            }
+        }
+        ToolCall::CompleteTask { project, title } => {
+            let result = if let Some((rt, storage)) = runtime.zip(storage) {
+                match rt.block_on(async {
+                    let tasks = storage.load_project_tasks(project).await?;
+                    let Some(task) = find_open_task(&tasks, title) else {
+                        return Ok(None);
+                    };
+                    storage
+                        .set_project_task_status(&task.id, crate::services::projects::TaskStatus::Done)
+                        .await?;
+                    Ok::<_, color_eyre::Report>(Some(task.title.clone()))
+                }) {
+                    Ok(Some(done)) => format!("Task '{}' in project '{}' marked as done.", done, project),
+                    Ok(None) => format!("No open task matching '{}' in project '{}'.", title, project),
+                    Err(error) => format!("Could not update task: {}", error),
+                }
+            } else {
+                "Storage not available for project tasks.".to_string()
+            };
+            ToolResult {
+                tool: "complete_task".to_string(),
+                result,
+            }
+        }
+        ToolCall::SearchFiles { query } => {
+            let result = match crate::services::file_search::search_files(file_search, query) {
+                Ok(matches) => crate::services::file_search::format_file_matches(
+                    query,
+                    &matches,
+                    file_search.max_chars,
+                ),
+                Err(error) => error.to_string(),
+            };
+            ToolResult {
+                tool: "search_files".to_string(),
+                result,
+            }
+        }
+        ToolCall::QueryDatabase { database, sql } => {
+            let result = match databases.iter().find(|candidate| candidate.name.eq_ignore_ascii_case(database)) {
+                None if databases.is_empty() => {
+                    "No databases registered. Add [[databases]] entries in config.toml.".to_string()
+                }
+                None => format!(
+                    "Unknown database '{}'. Registered: {}",
+                    database,
+                    databases.iter().map(|db| db.name.as_str()).collect::<Vec<_>>().join(", ")
+                ),
+                Some(db) => {
+                    let outcome = if sql.trim().is_empty() {
+                        crate::services::database::describe(db)
+                    } else {
+                        crate::services::database::run_query(db, sql)
+                    };
+                    match outcome {
+                        Ok(rows) => crate::services::database::format_query_result(&rows, db.max_chars),
+                        Err(error) => format!("Query failed: {}", error),
+                    }
+                }
+            };
+            ToolResult {
+                tool: "query_database".to_string(),
+                result,
+            }
+        }
+        ToolCall::ListGithubWork { kind } => {
+            let result = match crate::agents::github::WorkKind::from_name(kind) {
+                Some(kind) => match crate::agents::github::list_work(github_key, kind) {
+                    Ok(items) => crate::agents::github::format_work_items(kind, &items),
+                    Err(error) => format!("GitHub request failed: {}", error),
+                },
+                None => format!("Unknown kind '{}'. Use prs, reviews or issues.", kind),
+            };
+            ToolResult {
+                tool: "list_github_work".to_string(),
+                result,
+            }
+        }
+        ToolCall::GithubPullRequest { pull } => {
+            let result = match crate::agents::github::parse_pull_reference(pull) {
+                Some((repository, number)) => {
+                    crate::agents::github::pull_request_summary(github_key, &repository, number)
+                        .unwrap_or_else(|error| format!("GitHub request failed: {}", error))
+                }
+                None => format!("'{}' is not a pull request reference (use owner/repo#123)", pull),
+            };
+            ToolResult {
+                tool: "github_pull_request".to_string(),
+                result,
+            }
+        }
+        ToolCall::GitDiff { target } => {
+            let result = match git_repo {
+                Some(root) => crate::services::git_context::diff(root, target, git.max_diff_chars)
+                    .unwrap_or_else(|error| error.to_string()),
+                None => "Not running inside a git repository.".to_string(),
+            };
+            ToolResult {
+                tool: "git_diff".to_string(),
+                result,
+            }
+        }
+        ToolCall::HomeAssistantState { query } => {
+            let result = match crate::services::homeassistant::fetch_states(home_assistant) {
+                Ok(states) => {
+                    let matches = crate::services::homeassistant::find_entities(&states, query);
+                    crate::services::homeassistant::format_states(query, &matches)
+                }
+                Err(error) => format!("Home Assistant request failed: {}", error),
+            };
+            ToolResult {
+                tool: "home_assistant_state".to_string(),
+                result,
+            }
+        }
+        ToolCall::HomeAssistantCall { service, entity } => {
+            let result = match crate::services::homeassistant::fetch_states(home_assistant) {
+                Ok(states) => match crate::services::homeassistant::find_entities(&states, entity).as_slice() {
+                    [target] => match crate::services::homeassistant::ServiceCall::new(service, target) {
+                        Some(call) => {
+                            let description = call.describe();
+                            if agent_tx.send(crate::app::AgentEvent::HomeAssistantCall(call)).is_ok() {
+                                format!(
+                                    "Asked the user to confirm {}. It runs only if they confirm; do not claim it is done.",
+                                    description
+                                )
+                            } else {
+                                "Could not ask the user to confirm the action.".to_string()
+                            }
+                        }
+                        None => format!("'{}' is not a Home Assistant service (use domain.service)", service),
+                    },
+                    matches => format!(
+                        "Need exactly one entity to act on. {}",
+                        crate::services::homeassistant::format_states(entity, matches)
+                    ),
+                },
+                Err(error) => format!("Home Assistant request failed: {}", error),
+            };
+            ToolResult {
+                tool: "home_assistant_call".to_string(),
+                result,
+            }
+        }
+        ToolCall::TransitDepartures { stop, mode } => {
+            let config = crate::config::Config::load()
+                .map(|config| config.transit)
+                .unwrap_or_default();
+            let mode_filter = crate::services::transit::Mode::from_name(mode);
+            let result = match crate::services::transit::resolve_stop(&config, stop) {
+                Ok(stop) => match crate::services::transit::fetch_departures(&config, stop, mode_filter) {
+                    Ok(departures) => crate::services::transit::format_departures(
+                        stop,
+                        &departures,
+                        chrono::Local::now().fixed_offset(),
+                    ),
+                    Err(error) => format!("Departure lookup failed: {}", error),
+                },
+                Err(error) => error.to_string(),
+            };
+            ToolResult {
+                tool: "transit_departures".to_string(),
+                result,
+            }
+        }
+        ToolCall::StockQuote { symbol } => {
+            let finance = crate::config::Config::load()
+                .map(|config| config.finance)
+                .unwrap_or_default();
+            let result = match crate::services::finance::quote(&finance, symbol) {
+                Ok(quote) => crate::services::finance::format_quote(&quote),
+                Err(error) => format!("Quote lookup for {} failed: {}", symbol, error),
+            };
+            ToolResult {
+                tool: "stock_quote".to_string(),
+                result,
+            }
+        }
+        ToolCall::ConvertCurrency { amount, from, to } => {
+            let config = crate::config::Config::load().unwrap_or_default();
+            let rates = crate::services::finance::conversion_rates(
+                &config.finance,
+                &config.calculator.currency_rates,
+            );
+            let question = format!("{} {} in {}", amount, from.trim(), to.trim());
+            let result = crate::services::calculator::answer(&question, &rates).unwrap_or_else(|| {
+                format!("No exchange rate known for {} or {}.", from.to_uppercase(), to.to_uppercase())
+            });
+            ToolResult {
+                tool: "convert_currency".to_string(),
+                result,
+            }
+        }
+        ToolCall::DraftEmail { to, subject, body } => {
+            let draft = crate::services::email::EmailDraft {
+                to: to.clone(),
+                subject: subject.clone(),
+                body: body.clone(),
+            };
+            let result = if agent_tx.send(crate::app::AgentEvent::EmailDraft(draft)).is_ok() {
+                format!(
+                    "Draft to {} shown to the user for review. It is sent only if they confirm; do not claim it was sent.",
+                    to
+                )
+            } else {
+                "Could not show the draft to the user.".to_string()
+            };
+            ToolResult {
+                tool: "draft_email".to_string(),
+                result,
+            }
+        }
     }
 }
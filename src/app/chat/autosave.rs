@@ -0,0 +1,158 @@
+use crate::app::App;
+use crate::app::types::MessageRole;
+use crate::storage::{ConversationData, ConversationMessage};
+use color_eyre::Result;
+use std::time::Instant;
+
+impl App {
+    /// Saves the current chat incrementally. The first exchange creates the
+    /// conversation record synchronously so `current_conversation_id` is set
+    /// right away; later messages are appended in the background every
+    /// `autosave_every_messages` messages or `autosave_interval`.
+    pub fn autosave_conversation(&mut self, force: bool) -> Result<()> {
+        if self.chat_history.is_empty() || self.headless || self.incognito {
+            return Ok(());
+        }
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let messages = self.build_conversation_messages();
+
+        let Some(conversation_id) = self.current_conversation_id.clone() else {
+            let agent_name = self
+                .current_agent
+                .as_ref()
+                .map_or("unknown", |agent| agent.name.as_str())
+                .to_string();
+            let (storage, runtime) = self.storage_with_runtime()?;
+            let data = ConversationData::new(&agent_name, &messages).with_language(&self.conversation_language);
+            let new_id = runtime.block_on(storage.save_conversation(data))?;
+            self.current_conversation_id = Some(new_id.clone());
+            self.mark_autosaved(messages.len());
+            if let Some(storage) = &self.storage {
+                Self::spawn_background_embeddings(storage.clone(), new_id, messages);
+            }
+            return Ok(());
+        };
+
+        // History was replaced underneath us (e.g. agent switch): rewrite everything
+        let rewrite = messages.len() < self.autosaved_message_count;
+        let pending: Vec<ConversationMessage> = if rewrite {
+            messages.clone()
+        } else {
+            messages
+                .get(self.autosaved_message_count..)
+                .map(<[ConversationMessage]>::to_vec)
+                .unwrap_or_default()
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let is_due = pending.len() >= self.autosave_every_messages
+            || self
+                .last_autosave
+                .is_none_or(|saved_at| saved_at.elapsed() >= self.autosave_interval);
+        if !force && !rewrite && !is_due {
+            return Ok(());
+        }
+
+        // The storage worker keeps writes of the same chat in order
+        self.mark_autosaved(messages.len());
+        self.spawn_storage_job(move |storage, runtime| {
+            let saved = if rewrite {
+                runtime.block_on(storage.update_conversation_messages(&conversation_id, &pending))
+            } else {
+                runtime.block_on(storage.append_conversation_messages(&conversation_id, &pending))
+            };
+            match saved {
+                Ok(()) => Self::spawn_background_embeddings(storage.clone(), conversation_id, pending),
+                Err(error) => tracing::error!(%error, conversation = %conversation_id, "autosave failed"),
+            }
+        });
+        Ok(())
+    }
+
+    /// Called from the event loop so a quiet chat still gets saved after `autosave_interval`
+    pub fn autosave_tick(&mut self) {
+        if self.current_conversation_id.is_none()
+            || self.chat_history.len() == self.autosaved_message_count
+        {
+            return;
+        }
+        if self
+            .last_autosave
+            .is_some_and(|saved_at| saved_at.elapsed() < self.autosave_interval)
+        {
+            return;
+        }
+        if self.autosave_conversation(false).is_err() {
+            // Retry on the next interval instead of every tick
+            self.last_autosave = Some(Instant::now());
+        }
+    }
+
+    /// Turns incognito on for the open chat. It stays on until the chat is closed once
+    /// something was said, since saving later would store the private part too
+    pub(crate) fn toggle_incognito(&mut self) {
+        if self.incognito {
+            let has_messages = self
+                .chat_history
+                .iter()
+                .any(|message| message.role != MessageRole::System);
+            if has_messages {
+                self.add_system_message("This conversation stays incognito; close it to start a saved one");
+                return;
+            }
+            self.set_incognito(false);
+            self.show_status_toast("INCOGNITO OFF");
+            return;
+        }
+        self.set_incognito(true);
+        self.show_status_toast("INCOGNITO ON");
+        let note = if self.current_conversation_id.is_some() {
+            "Incognito on: nothing more from this conversation is saved, embedded or used for reflection (earlier messages were already saved)"
+        } else {
+            "Incognito on: nothing from this conversation is saved, embedded or used for reflection"
+        };
+        self.add_system_message(note);
+    }
+
+    /// Records how much of `chat_history` is already in storage
+    pub(crate) fn mark_autosaved(&mut self, message_count: usize) {
+        self.autosaved_message_count = message_count;
+        self.last_autosave = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::types::ChatMessage;
+    use crate::app::{AgentEvent, App};
+    use crate::storage::ToolOutputRecord;
+
+    #[test]
+    fn test_incognito_exchange_leaves_storage_untouched() {
+        let mut app = App::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        app.agent_tx = Some(tx.clone());
+        app.agent_rx = Some(rx);
+        app.toggle_incognito();
+        app.chat_history.push(ChatMessage::user("my bank PIN is 1234"));
+        app.chat_history.push(ChatMessage::assistant("I won't remember that.", None, None));
+        let _ = tx.send(AgentEvent::ToolOutputStored(ToolOutputRecord {
+            key: "20261017143005123-0".to_string(),
+            tool: "search_web".to_string(),
+            arguments: "{}".to_string(),
+            content: "full result".to_string(),
+            summary: "summary".to_string(),
+            embedding: None,
+        }));
+        app.check_agent_response();
+
+        assert!(app.autosave_conversation(true).is_ok());
+        assert!(app.current_conversation_id.is_none());
+        // Every write goes through storage; it was never opened
+        assert!(app.storage.is_none());
+        assert!(app.storage_worker.is_none());
+    }
+}
@@ -1,5 +1,7 @@
+use crate::agents::ChatMessage as AgentChatMessage;
 use crate::app::AgentEvent;
 use crate::app::App;
+use crate::app::types::MessageImage;
 use color_eyre::Result;
 use std::process::{Command, Stdio};
 
@@ -19,28 +21,44 @@ impl App {
         self.reset_chat_scroll();
 
         if format.is_empty() || path.is_empty() {
-            self.add_system_message("Usage: convert <format> <path>");
+            self.add_system_message("Usage: convert <format> <file|directory|glob>");
             return Ok(true);
         }
 
+        let inputs = match crate::services::convert::resolve_inputs(path) {
+            Ok(inputs) => inputs,
+            Err(error) => {
+                self.add_system_message(&format!("Conversion failed: {}", error));
+                return Ok(true);
+            }
+        };
+
         let tx = self.agent_tx.clone();
+        if !self.conversion_active {
+            self.conversion_frame = 0;
+            self.last_conversion_tick = None;
+        }
         self.conversion_active = true;
-        self.conversion_frame = 0;
-        self.last_conversion_tick = None;
+        self.conversion_total += inputs.len();
 
-        let input_path = path.to_string();
-        let format_copy = format.clone();
         std::thread::spawn(move || {
-            let result = crate::services::convert::convert_file(&input_path, &format_copy);
-            if let Some(tx) = tx {
-                if let Err(error) = result {
-                    let _ = tx.send(AgentEvent::SystemMessage(format!(
-                        "Conversion failed: {}",
-                        error
-                    )));
-                }
-                let _ = tx.send(AgentEvent::ConversionFinished);
-            }
+            let Some(tx) = tx else {
+                return;
+            };
+            let converted = crate::services::convert::convert_batch(&inputs, &format, |file| {
+                let _ = tx.send(AgentEvent::ConversionFileFinished {
+                    input: file.input.display().to_string(),
+                    result: file
+                        .output
+                        .map(|output| output.display().to_string())
+                        .map_err(|error| error.to_string()),
+                });
+            });
+            let _ = tx.send(AgentEvent::ConversionFinished {
+                format,
+                converted,
+                total: inputs.len(),
+            });
         });
 
         Ok(true)
@@ -72,47 +90,343 @@ impl App {
             return Ok(true);
         }
 
-        // Start downloads for all URLs
         for url in urls {
-            // Add download item to active list
-            self.active_downloads.push(crate::app::types::DownloadItem {
-                url: url.clone(),
-                progress: None,
-                frame: 0,
-                last_tick: None,
-            });
+            self.start_download(url);
+        }
 
-            // Spawn download thread
-            let tx = self.agent_tx.clone();
-            let url_clone = url.clone();
-            std::thread::spawn(move || {
-                let result = crate::services::link_download::download_video_with_progress(
-                    &url,
-                    |progress| {
-                        if let Some(tx) = &tx {
-                            let _ = tx.send(AgentEvent::DownloadProgress {
-                                url: url.clone(),
-                                progress,
-                            });
-                        }
-                    },
-                );
-                if let Some(tx) = tx {
-                    if let Err(error) = result {
-                        let _ = tx.send(AgentEvent::SystemMessage(format!(
-                            "Download failed for {}: {}",
-                            url_clone,
-                            error
-                        )));
+        Ok(true)
+    }
+
+    /// Runs a file search in the background and posts the matches to the chat
+    pub(crate) fn start_file_search(&mut self, pattern: String) {
+        if pattern.is_empty() {
+            self.add_system_message("Usage: / then find <pattern>");
+            return;
+        }
+        let config = self.file_search.clone();
+        let tx = self.agent_tx.clone();
+        self.add_system_message(&format!("Searching files for '{}'...", pattern));
+        std::thread::spawn(move || {
+            let message = match crate::services::file_search::search_files(&config, &pattern) {
+                Ok(matches) => crate::services::file_search::format_file_matches(
+                    &pattern,
+                    &matches,
+                    config.max_chars,
+                ),
+                Err(error) => format!("File search failed: {}", error),
+            };
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::SystemMessage(message));
+            }
+        });
+    }
+
+    /// `/imagine <prompt>`: generates a picture in the background and adds it to the chat
+    pub(crate) fn start_image_generation(&mut self, prompt: String) {
+        if prompt.is_empty() {
+            self.add_system_message("Usage: / then imagine <prompt>");
+            return;
+        }
+        let config = self.images.clone();
+        let venice_key = self.connect_venice_key.clone();
+        let tx = self.agent_tx.clone();
+        self.active_image_generations += 1;
+        self.reset_chat_scroll();
+        std::thread::spawn(move || {
+            let result = crate::services::images::generate(&config, &venice_key, &prompt)
+                .map_err(|error| error.to_string());
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::ImageGenerated { prompt, result });
+            }
+        });
+    }
+
+    /// Shows a finished `/imagine` picture and saves it with the conversation
+    pub(crate) fn handle_image_generated(
+        &mut self,
+        prompt: &str,
+        result: std::result::Result<std::path::PathBuf, String>,
+    ) {
+        self.active_image_generations = self.active_image_generations.saturating_sub(1);
+        let path = match result {
+            Ok(path) => path,
+            Err(error) => {
+                self.add_system_message(&format!("Image generation failed: {}", error));
+                self.publish_notification(crate::services::notifications::NotificationKind::Job, "Image generation failed", prompt);
+                return;
+            }
+        };
+
+        self.add_assistant_message(&format!("Imagined: {}", prompt));
+        if let Some(message) = self.chat_history.last_mut() {
+            message.images.push(MessageImage::load(path));
+        }
+        if self.chat_auto_scroll {
+            self.chat_scroll_offset = 0;
+        }
+        if let Err(error) = self.autosave_conversation(false) {
+            tracing::error!(%error, "history save failed");
+            self.add_system_message(&format!("HISTORY SAVE FAILED: {}", error));
+        }
+        self.publish_notification(crate::services::notifications::NotificationKind::Job, "Image ready", prompt);
+    }
+
+    /// `gh` lists open PRs; `gh reviews` / `gh issues` the other lists and
+    /// `gh owner/repo#123` summarizes one pull request
+    pub(crate) fn start_github_query(&mut self, query: String) {
+        if self.connect_github_key.trim().is_empty() {
+            self.add_system_message("GitHub not connected. Add a token under Connect");
+            return;
+        }
+        let token = self.connect_github_key.clone();
+        let tx = self.agent_tx.clone();
+        let pull = crate::agents::github::parse_pull_reference(&query);
+        let kind = crate::agents::github::WorkKind::from_name(&query);
+        if pull.is_none() && kind.is_none() {
+            self.add_system_message(&format!(
+                "Unknown gh query '{}'. Try: gh, gh reviews, gh issues, gh owner/repo#123",
+                query
+            ));
+            return;
+        }
+        self.add_system_message("Asking GitHub...");
+        std::thread::spawn(move || {
+            let result = match (pull, kind) {
+                (Some((repository, number)), _) => {
+                    crate::agents::github::pull_request_summary(&token, &repository, number)
+                }
+                (None, Some(kind)) => crate::agents::github::list_work(&token, kind)
+                    .map(|items| crate::agents::github::format_work_items(kind, &items)),
+                (None, None) => return,
+            };
+            let message = result.unwrap_or_else(|error| format!("GitHub request failed: {}", error));
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::SystemMessage(message));
+            }
+        });
+    }
+
+    pub(crate) fn handle_news_command(&mut self) -> bool {
+        if self.chat_input.content().trim() != "news" {
+            return false;
+        }
+        self.chat_input.clear();
+        self.reset_chat_scroll();
+        self.start_news_briefing();
+        true
+    }
+
+    /// Fetches the configured feeds in the background and has the chat agent brief the
+    /// items not seen before, leading with the user's frequent topics and dreams
+    pub(crate) fn start_news_briefing(&mut self) {
+        if self.feeds.urls.is_empty() {
+            self.add_system_message("No feeds configured. Add RSS/Atom URLs under [feeds] urls in config.toml");
+            return;
+        }
+        let Some((manager, agent)) = self.agent_manager.clone().zip(self.current_agent.clone()) else {
+            self.add_system_message("No chat agent available for the briefing");
+            return;
+        };
+        self.ensure_storage();
+        let storage = self.storage.clone();
+        let urls = self.feeds.urls.clone();
+        let max_items = self.feeds.max_items.max(1);
+        let tx = self.agent_tx.clone();
+        self.add_system_message(&format!("Checking {} feed(s)...", urls.len()));
+
+        std::thread::spawn(move || {
+            let result = news_briefing(&manager, &agent, storage.as_ref(), &urls, max_items)
+                .map_err(|error| error.to_string());
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::NewsBriefing { result });
+            }
+        });
+    }
+
+    pub(crate) fn handle_news_briefing(&mut self, result: std::result::Result<Option<String>, String>) {
+        match result {
+            Ok(Some(briefing)) => self.add_assistant_message(&briefing),
+            Ok(None) => self.add_system_message("No new feed items since the last briefing"),
+            Err(error) => self.add_system_message(&format!("News briefing failed: {}", error)),
+        }
+    }
+
+    pub(crate) fn handle_journal_command(&mut self) -> Result<bool> {
+        let content = self.chat_input.content().trim().to_string();
+        if !(content == "journal" || content.starts_with("journal ")) {
+            return Ok(false);
+        }
+
+        let text = content.trim_start_matches("journal").trim().to_string();
+        self.chat_input.clear();
+        self.reset_chat_scroll();
+        self.write_journal_entry(Some(&text).filter(|text| !text.is_empty()).map(String::as_str));
+        Ok(true)
+    }
+
+    /// Appends `text` to today's Obsidian daily note, or a list of today's conversations
+    /// when no text is given
+    pub(crate) fn write_journal_entry(&mut self, text: Option<&str>) {
+        let now = chrono::Local::now();
+        let entry = match text {
+            Some(text) => crate::services::journal::format_entry(now.time(), text),
+            None => {
+                let conversations = if self.ensure_storage() {
+                    self.storage_with_runtime()
+                        .and_then(|(storage, runtime)| {
+                            runtime.block_on(storage.load_conversations_with_limit(50))
+                        })
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                match crate::services::journal::format_conversation_digest(
+                    &conversations,
+                    now.date_naive(),
+                    now.time(),
+                ) {
+                    Some(entry) => entry,
+                    None => {
+                        self.add_system_message(
+                            "No saved conversations today. Usage: journal <text> adds an entry",
+                        );
+                        return;
                     }
-                    let _ = tx.send(AgentEvent::DownloadFinished { url: url_clone });
                 }
-            });
+            }
+        };
+        match crate::services::journal::append_to_daily_note(
+            &self.connect_obsidian_vault_path,
+            &self.journal,
+            &entry,
+        ) {
+            Ok(path) => {
+                let name = path
+                    .file_name()
+                    .map_or_else(String::new, |name| name.to_string_lossy().to_string());
+                self.show_status_toast(format!("JOURNALED TO {}", name.to_uppercase()));
+            }
+            Err(error) => self.add_system_message(&format!("Journal failed: {}", error)),
+        }
+    }
+
+    pub(crate) fn handle_transcribe_command(&mut self) -> Result<bool> {
+        let content = self.chat_input.content().trim().to_string();
+        if !(content == "transcribe" || content.starts_with("transcribe ")) {
+            return Ok(false);
+        }
+
+        let arguments = content.trim_start_matches("transcribe").trim();
+        let summarize = arguments.split_whitespace().any(|word| word == "--summarize");
+        let source = arguments
+            .split_whitespace()
+            .filter(|word| *word != "--summarize")
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.chat_input.clear();
+        self.reset_chat_scroll();
+
+        if source.is_empty() {
+            self.add_system_message("Usage: transcribe <url-or-file> [--summarize]");
+            return Ok(true);
+        }
+
+        // The summary is written by the chat agent once the transcript is ready
+        let summarizer = if summarize {
+            self.agent_manager.clone().zip(self.current_agent.clone())
+        } else {
+            None
+        };
+        if summarize && summarizer.is_none() {
+            self.add_system_message("No chat agent available; the transcript will not be summarized");
         }
 
+        let tx = self.agent_tx.clone();
+        self.active_transcriptions += 1;
+        std::thread::spawn(move || {
+            let result = crate::services::transcribe::transcribe(&source);
+            let summary = match (&result, summarizer) {
+                (Ok(transcript), Some((manager, agent))) => {
+                    summarize_transcript(&manager, &agent, &transcript.text)
+                }
+                _ => None,
+            };
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::TranscriptionFinished {
+                    source,
+                    result: result.map_err(|error| error.to_string()),
+                    summary,
+                });
+            }
+        });
+
         Ok(true)
     }
 
+    /// Reports a finished transcription. A summary is added to the chat as an assistant
+    /// message, and the transcript is kept as a snippet so it can be found later
+    pub(crate) fn handle_transcription_finished(
+        &mut self,
+        source: &str,
+        result: std::result::Result<crate::services::transcribe::Transcript, String>,
+        summary: Option<String>,
+    ) {
+        self.active_transcriptions = self.active_transcriptions.saturating_sub(1);
+        let transcript = match result {
+            Ok(transcript) => transcript,
+            Err(error) => {
+                self.add_system_message(&format!("Transcription failed: {}", error));
+                self.publish_notification(crate::services::notifications::NotificationKind::Job, "Transcription failed", source);
+                return;
+            }
+        };
+
+        self.add_system_message(&format!(
+            "Transcript saved to {} ({} words)",
+            transcript.path.display(),
+            transcript.word_count()
+        ));
+        self.publish_notification(crate::services::notifications::NotificationKind::Job, "Transcription finished", source);
+
+        let Some(summary) = summary else {
+            return;
+        };
+        self.add_assistant_message(&summary);
+        let title = transcript
+            .path
+            .file_name()
+            .map_or_else(|| source.to_string(), |name| name.to_string_lossy().into_owned());
+        let saved = self.ensure_storage()
+            && self.storage_with_runtime().is_ok_and(|(storage, runtime)| {
+                runtime
+                    .block_on(storage.save_snippet(&title, "transcript", &transcript.text))
+                    .is_ok()
+            });
+        if saved {
+            self.show_status_toast("TRANSCRIPT SAVED");
+        }
+    }
+
+    /// Reports a finished `convert` batch; the spinner stops once every batch is done
+    pub(crate) fn handle_conversion_finished(&mut self, format: &str, converted: usize, total: usize) {
+        if self.conversion_done >= self.conversion_total {
+            self.conversion_active = false;
+            self.conversion_frame = 0;
+            self.last_conversion_tick = None;
+            self.conversion_done = 0;
+            self.conversion_total = 0;
+        }
+        if converted > 0 {
+            self.add_system_message(&format!(
+                "Converted {} of {} file(s) to {}",
+                converted, total, format
+            ));
+        }
+        let title = if converted == total { "Conversion finished" } else { "Conversion failed" };
+        self.publish_notification(crate::services::notifications::NotificationKind::Job, title, &format!("{} of {} file(s) to {}", converted, total, format));
+    }
+
     pub(crate) fn handle_comfyui_command(&mut self) -> Result<bool> {
         let content = self.chat_input.content().trim().to_string();
         if !(content == "comfyui" || content.starts_with("comfyui ")) {
@@ -135,8 +449,8 @@ impl App {
                 }
 
                 // Get home directory
-                let home = std::env::var("HOME").unwrap_or_else(|_| "/home/ethereal".to_string());
-                let comfyui_path = format!("{}/git-local/comfy-ui", home);
+                let home = crate::platform::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/home/ethereal"));
+                let comfyui_path = home.join("git-local").join("comfy-ui");
 
                 // Start ComfyUI using run.sh
                 match Command::new("bash")
@@ -177,3 +491,71 @@ impl App {
         Ok(true)
     }
 }
+
+/// Longest part of a transcript sent to the model for summarizing
+const TRANSCRIPT_SUMMARY_MAX_CHARS: usize = 24_000;
+
+fn news_briefing(
+    manager: &crate::agents::AgentManager,
+    agent: &crate::agents::Agent,
+    storage: Option<&crate::storage::StorageManager>,
+    urls: &[String],
+    max_items: usize,
+) -> Result<Option<String>> {
+    let mut items = crate::services::feeds::fetch_feed_items(urls)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    if let Some(storage) = storage {
+        let keys = items.iter().map(|item| item.key.clone()).collect();
+        let seen = runtime.block_on(storage.load_seen_feed_keys(keys))?;
+        items.retain(|item| !seen.contains(&item.key));
+    }
+    items.truncate(max_items);
+    if items.is_empty() {
+        return Ok(None);
+    }
+
+    let mut interests: Vec<String> = storage
+        .and_then(|storage| runtime.block_on(storage.load_frequent_topics(2)).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .take(10)
+        .map(|(topic, _)| topic)
+        .collect();
+    if let Ok(identity) = crate::services::identity::read_identity_state() {
+        interests.extend(identity.dreams.active.into_iter().map(|dream| dream.title));
+    }
+
+    let messages = vec![
+        AgentChatMessage::system(&agent.system_prompt),
+        AgentChatMessage::user(crate::services::feeds::format_briefing_prompt(&items, &interests)),
+    ];
+    let briefing = manager.chat(agent, &messages)?;
+
+    // Only items that made it into a briefing count as seen
+    if let Some(storage) = storage {
+        for item in &items {
+            runtime.block_on(storage.mark_feed_item_seen(&item.key, &item.feed, &item.title))?;
+        }
+    }
+    Ok(Some(briefing.trim().to_string()))
+}
+
+fn summarize_transcript(
+    manager: &crate::agents::AgentManager,
+    agent: &crate::agents::Agent,
+    transcript: &str,
+) -> Option<String> {
+    let excerpt: String = transcript.chars().take(TRANSCRIPT_SUMMARY_MAX_CHARS).collect();
+    let messages = vec![
+        AgentChatMessage::system(&agent.system_prompt),
+        AgentChatMessage::user(format!(
+            "Summarize this transcript in a short paragraph followed by the key points as a list:\n\n{}",
+            excerpt
+        )),
+    ];
+    manager
+        .chat(agent, &messages)
+        .ok()
+        .map(|summary| summary.trim().to_string())
+        .filter(|summary| !summary.is_empty())
+}
@@ -1,13 +1,52 @@
-use crate::app::types::{ChatAttachment, ChatMessage};
+use crate::app::types::{ChatAttachment, ChatMessage, MessageImage, MessageRole, PromptSection};
 use crate::app::App;
 use crate::app::chat::agent::intent::classify_query;
-use crate::services::weather::WeatherService;
 use base64::{Engine as _, engine::general_purpose::STANDARD};
 use chrono::Datelike;
 use color_eyre::Result;
-use serde::Deserialize;
 use std::path::{Path, PathBuf};
 
+/// Words that turn context sources off for the message they are typed in
+const CONTEXT_MODIFIERS: [(&str, &[PromptSection]); 4] = [
+    ("!nomem", &[PromptSection::Memories, PromptSection::ConversationRecall]),
+    ("!nonotes", &[PromptSection::Notes]),
+    ("!nosearch", &[PromptSection::WebSearch]),
+    (
+        "!clean",
+        &[
+            PromptSection::Memories,
+            PromptSection::ConversationRecall,
+            PromptSection::Notes,
+            PromptSection::WebSearch,
+        ],
+    ),
+];
+
+/// Removes context modifiers from `content`, returning the rest and the sections they skip
+pub(crate) fn strip_context_modifiers(content: &str) -> (String, Vec<PromptSection>) {
+    let mut skipped: Vec<PromptSection> = Vec::new();
+    let mut words = Vec::new();
+    for word in content.split(' ') {
+        let modifier = CONTEXT_MODIFIERS
+            .iter()
+            .find(|(name, _)| word.trim().eq_ignore_ascii_case(name));
+        match modifier {
+            Some((_, sections)) => {
+                for section in *sections {
+                    if !skipped.contains(section) {
+                        skipped.push(*section);
+                    }
+                }
+            }
+            None => words.push(word),
+        }
+    }
+    if skipped.is_empty() {
+        return (content.to_string(), skipped);
+    }
+    (words.join(" ").trim().to_string(), skipped)
+}
+
 fn query_is_notes_follow_up(query: &str) -> bool {
     let lowered = query.to_lowercase();
     let note_follow_up_terms = [
@@ -19,8 +58,148 @@ fn query_is_notes_follow_up(query: &str) -> bool {
 
 impl App {
     /// Adds a user message to the chat history with timestamp
-    fn add_user_message_to_history(&mut self, message_content: &str) {
-        self.chat_history.push(ChatMessage::user(message_content));
+    pub(crate) fn add_user_message_to_history(&mut self, message_content: &str) {
+        let mut message = ChatMessage::user(message_content);
+        message.display_name = self.speaker.clone();
+        // The quote only applies while its block is still at the start of the input
+        message.quote = self
+            .pending_quote
+            .take()
+            .filter(|_| message_content.starts_with(crate::app::selection::QUOTE_PREFIX));
+        self.chat_history.push(message);
+    }
+
+    /// Keeps the pending attachments on the message just added, so they are saved
+    /// with it and shown again when the conversation is reopened from History
+    fn attach_to_last_message(&mut self) {
+        let mut images = Vec::new();
+        let mut files = Vec::new();
+        for attachment in &self.chat_attachments {
+            match attachment {
+                ChatAttachment::FilePath { path, .. } => images.push(MessageImage::load(path.clone())),
+                ChatAttachment::ClipboardImage { png_bytes, .. } => {
+                    match crate::services::attachments::store_blob(png_bytes) {
+                        Ok(path) => images.push(MessageImage::load(path)),
+                        Err(error) => tracing::warn!(%error, "could not store pasted image"),
+                    }
+                }
+                ChatAttachment::Document { path, .. } => files.push(path.clone()),
+                ChatAttachment::Stdin { .. } => {}
+            }
+        }
+        if let Some(message) = self.chat_history.last_mut() {
+            message.images = images;
+            message.files = files;
+        }
+    }
+
+    /// Everything the background prompt builder needs, taken from the current session
+    pub(crate) fn chat_build_snapshot(
+        &self,
+        agent: &crate::agents::Agent,
+    ) -> crate::app::chat::agent::ChatBuildSnapshot {
+        crate::app::chat::agent::ChatBuildSnapshot {
+            system_prompt: agent.system_prompt.clone(),
+            chat_history: self
+                .chat_history
+                .iter()
+                .filter(|message| !message.excluded_from_context)
+                .cloned()
+                .collect(),
+            personality_enabled: self.personality_enabled,
+            personality_text: self.personality_text.clone(),
+            personality_name: self.personality_name.clone(),
+            connect_obsidian_vault: self.connect_obsidian_vault.clone(),
+            connect_obsidian_vault_path: self.connect_obsidian_vault_path.clone(),
+            connect_brave_key: self.connect_brave_key.clone(),
+            connect_github_key: self.connect_github_key.clone(),
+            file_search: self.file_search.clone(),
+            databases: self.databases.clone(),
+            git: self.git.clone(),
+            git_repo: self.git_repo.clone(),
+            home_assistant: crate::config::HomeAssistantConfig {
+                url: self.connect_home_assistant_url.clone(),
+                api_key: self.connect_home_assistant_token.clone(),
+            },
+            tools: self.tools.clone(),
+            // Automatic memory retrieval disabled - LLM now uses explicit tool calls.
+            // This prevents UI blocking from slow embedding operations
+            pre_retrieved_messages: Vec::new(),
+            cached_obsidian_notes: self.cached_obsidian_notes.clone(),
+            pending_project_suggestions: self.pending_project_suggestions.clone(),
+            active_project: self.active_project.as_ref().and_then(|name| {
+                crate::services::projects::read_project_file(&self.connect_obsidian_vault_path, name)
+                    .ok()
+            }),
+            active_project_tasks: self.active_project_task_list(),
+            storage: self.storage.clone(),
+            cached_recall_context: self.cached_recall_context.clone(),
+            calendar_sources: self.calendar_sources.clone(),
+            previous_session_context: self.carried_session_context.clone(),
+            check_in_every_days: self.check_in_days_for_first_message(),
+            disabled_prompt_sections: self.disabled_prompt_sections.clone(),
+            skipped_sections: Vec::new(),
+            language: self.conversation_language.clone(),
+        }
+    }
+
+    /// Assembles the system prompt in the background exactly as sending would, using
+    /// the current input (if any) as the next message; the sections arrive as
+    /// `AgentEvent::PromptPreview`
+    pub(crate) fn spawn_prompt_preview(&mut self) -> Result<()> {
+        let (agent, manager, agent_tx) = self.get_agent_chat_dependencies()?;
+        self.ensure_storage();
+        let mut snapshot = self.chat_build_snapshot(&agent);
+        // Picking a check-in records it; a preview must not use one up
+        snapshot.check_in_every_days = None;
+        let (draft, skipped_sections) =
+            strip_context_modifiers(&remove_attachment_tokens(self.chat_input.content()));
+        snapshot.skipped_sections = skipped_sections;
+        if !draft.trim().is_empty() {
+            let mut message = ChatMessage::user(draft.trim());
+            message.quote = self.pending_quote.clone();
+            snapshot.chat_history.push(message);
+        }
+
+        std::thread::spawn(move || {
+            let result = crate::app::chat::agent::build_agent_messages_from_snapshot(
+                snapshot, &agent, &manager, None,
+            );
+            let _ = agent_tx.send(crate::app::AgentEvent::PromptPreview(result.prompt_sections));
+        });
+        Ok(())
+    }
+
+    /// On the first message of a new conversation, carries over the summary of the
+    /// conversation closed just before it (if enabled and recent enough).
+    fn apply_session_carry_over(&mut self) {
+        let user_message_count = self
+            .chat_history
+            .iter()
+            .filter(|message| message.role == MessageRole::User)
+            .count();
+        if user_message_count != 1 {
+            return;
+        }
+        let Some(previous) = self.previous_session.take() else {
+            return;
+        };
+        if !self.carry_over_enabled || previous.closed_at.elapsed() > self.carry_over_window {
+            return;
+        }
+        self.carried_session_context = Some(previous.summary);
+        self.show_status_toast("PREVIOUS SESSION CONTEXT INCLUDED");
+    }
+
+    /// On the first message of a new conversation, how often check-ins are due when they
+    /// are enabled; the build thread picks the dream or project task to ask about
+    fn check_in_days_for_first_message(&self) -> Option<u64> {
+        let user_message_count = self
+            .chat_history
+            .iter()
+            .filter(|message| message.role == MessageRole::User)
+            .count();
+        (user_message_count == 1 && self.check_ins_enabled).then_some(self.check_in_every_days)
     }
 
     // Retrieves relevant messages from storage using App's existing connection
@@ -72,6 +251,27 @@ impl App {
             return Ok(());
         }
 
+        if self.handle_news_command() {
+            if !command_content.is_empty() {
+                self.add_user_message_to_history(&command_content);
+            }
+            return Ok(());
+        }
+
+        if self.handle_journal_command()? {
+            if !command_content.is_empty() {
+                self.add_user_message_to_history(&command_content);
+            }
+            return Ok(());
+        }
+
+        if self.handle_transcribe_command()? {
+            if !command_content.is_empty() {
+                self.add_user_message_to_history(&command_content);
+            }
+            return Ok(());
+        }
+
         if self.handle_comfyui_command()? {
             if !command_content.is_empty() {
                 self.add_user_message_to_history(&command_content);
@@ -79,14 +279,25 @@ impl App {
             return Ok(());
         }
 
-        let user_message = self.cleaned_chat_input_with_attachments();
+        let (user_message, skipped_sections) =
+            strip_context_modifiers(&self.cleaned_chat_input_with_attachments());
+        if user_message.is_empty() {
+            self.show_status_toast("TYPE A MESSAGE AFTER THE MODIFIER");
+            return Ok(());
+        }
         
         // Fast path check before clearing input
-        if let Some(action) = select_fast_path_action(&user_message)? {
+        if let Some(action) = select_fast_path_action(&user_message, &self.calculator, &self.finance, &self.weather)? {
             self.chat_input.clear();
             self.reset_chat_scroll();
             self.add_user_message_to_history(&user_message);
-            self.add_assistant_message(&action.into_reply());
+            let reply = self.run_fast_path_action(action);
+            self.add_assistant_message(&reply);
+            return Ok(());
+        }
+
+        // A question answered in a past conversation can reuse that answer
+        if self.offer_earlier_answer(&user_message) {
             return Ok(());
         }
 
@@ -98,6 +309,8 @@ impl App {
         self.chat_input.clear();
         self.reset_chat_scroll();
         self.add_user_message_to_history(&user_message);
+        self.attach_to_last_message();
+        self.apply_session_carry_over();
         
         // Set loading state IMMEDIATELY
         self.is_loading = true;
@@ -108,12 +321,14 @@ impl App {
             query: &user_message,
             intent,
         };
-        self.is_searching = self.should_mark_searching(search_request);
-        let is_fetching_notes = crate::app::chat::agent::obsidian::should_fetch_obsidian_for_intent(
-            &self.connect_obsidian_vault,
-            &user_message,
-            intent,
-        );
+        self.is_searching = !skipped_sections.contains(&PromptSection::WebSearch)
+            && self.should_mark_searching(search_request);
+        let is_fetching_notes = !skipped_sections.contains(&PromptSection::Notes)
+            && crate::app::chat::agent::obsidian::should_fetch_obsidian_for_intent(
+                &self.connect_obsidian_vault,
+                &user_message,
+                intent,
+            );
         self.is_fetching_notes = is_fetching_notes;
         
         // Clear cached notes if query is not about notes/follow-up
@@ -121,34 +336,20 @@ impl App {
             self.cached_obsidian_notes = None;
         }
 
-        // Note: Automatic memory retrieval disabled - LLM now uses explicit tool calls
-        // This prevents UI blocking from slow embedding operations
-        let pre_retrieved = Vec::new();
-
         // Ensure storage is initialized so we can clone it for the build thread.
         // Creating a new StorageManager in the background thread would fail because
         // RocksDB holds exclusive file locks on the database directory.
         self.ensure_storage();
 
-        let snapshot = crate::app::chat::agent::ChatBuildSnapshot {
-            system_prompt: agent.system_prompt.clone(),
-            chat_history: self.chat_history.clone(),
-            personality_enabled: self.personality_enabled,
-            personality_text: self.personality_text.clone(),
-            personality_name: self.personality_name.clone(),
-            connect_obsidian_vault: self.connect_obsidian_vault.clone(),
-            connect_obsidian_vault_path: self.connect_obsidian_vault_path.clone(),
-            connect_brave_key: self.connect_brave_key.clone(),
-            pre_retrieved_messages: pre_retrieved,
-            cached_obsidian_notes: self.cached_obsidian_notes.clone(),
-            pending_project_suggestions: self.pending_project_suggestions.clone(),
-            storage: self.storage.clone(),
-            cached_recall_context: self.cached_recall_context.clone(),
-        };
+        let mut snapshot = self.chat_build_snapshot(&agent);
+        snapshot.skipped_sections = skipped_sections;
         // Clear pending suggestions after one message cycle so they don't repeat
         self.pending_project_suggestions.clear();
         let attachments = self.chat_attachments.clone();
         self.chat_attachments.clear();
+        let ocr_config = (self.ocr.enabled
+            && !crate::services::ocr::model_supports_images(&agent.model_source, &agent.model))
+        .then(|| self.ocr.clone());
 
         std::thread::spawn(move || {
             // Send progress updates as we work
@@ -158,6 +359,15 @@ impl App {
             let vault_name = snapshot.connect_obsidian_vault.clone();
             let vault_path = snapshot.connect_obsidian_vault_path.clone();
             let brave_key = snapshot.connect_brave_key.clone();
+            let github_key = snapshot.connect_github_key.clone();
+            let file_search = snapshot.file_search.clone();
+            let databases = snapshot.databases.clone();
+            let git = snapshot.git.clone();
+            let git_repo = snapshot.git_repo.clone();
+            let home_assistant = snapshot.home_assistant.clone();
+            let storage = snapshot.storage.clone();
+            let tool_policy = snapshot.tools.clone();
+            let skipped_sections = snapshot.skipped_sections.clone();
 
             let build_result = crate::app::chat::agent::build_agent_messages_from_snapshot(
                 snapshot, &agent, &manager, Some(&agent_tx),
@@ -177,6 +387,7 @@ impl App {
                 let _ = agent_tx.send(crate::app::AgentEvent::ResponseWithContext {
                     response,
                     context_usage: build_result.context_usage,
+                    stats: None,
                 });
                 return;
             }
@@ -189,6 +400,15 @@ impl App {
             if let Ok(images) = build_attachment_images_from_attachments(&attachments) {
                 apply_images_to_last_user_message(&mut messages, images);
             }
+            apply_documents_to_last_user_message(&mut messages, &attachments);
+            if let Some(ocr_config) = &ocr_config {
+                apply_image_text_to_last_user_message(
+                    &mut messages,
+                    &attachments,
+                    ocr_config,
+                    &agent_tx,
+                );
+            }
 
             // Now generating response
             let _ = agent_tx.send(crate::app::AgentEvent::StatusUpdate("generating".to_string()));
@@ -203,6 +423,16 @@ impl App {
                     vault_name,
                     vault_path,
                     brave_key,
+                    github_key,
+                    file_search,
+                    databases,
+                    git,
+                    git_repo,
+                    home_assistant,
+                    storage,
+                    tools: tool_policy,
+                    skipped_sections,
+                    citations: std::sync::Mutex::new(build_result.citations),
                 }
             );
         });
@@ -300,8 +530,10 @@ impl App {
         let content = remove_attachment_tokens(self.chat_input.content());
         let mut cleaned_parts = Vec::new();
         for part in content.split_whitespace() {
-            if let Some(path) = parse_image_path(part) {
-                let _ = self.add_image_attachment_from_path(&path);
+            if let Some(path) = parse_image_path(part)
+                && is_attachable_path(&path)
+            {
+                let _ = self.add_file_attachment_from_path(&path);
                 continue;
             }
             cleaned_parts.push(part);
@@ -332,7 +564,7 @@ impl App {
 
     fn try_add_attachment_from_paste(&mut self, text: &str) -> Result<bool> {
         let trimmed = text.trim();
-        if self.try_add_image_attachment_from_text(trimmed)? {
+        if self.try_add_file_attachments_from_text(trimmed)? {
             return Ok(true);
         }
 
@@ -346,6 +578,65 @@ impl App {
         }
     }
 
+    /// Attaches files dropped onto the terminal (pasted as one or more paths).
+    /// Only succeeds when every pasted path resolves to an attachable file.
+    fn try_add_file_attachments_from_text(&mut self, text: &str) -> Result<bool> {
+        let whole_path = parse_image_path(text).filter(|path| is_attachable_path(path));
+        let paths: Vec<PathBuf> = match whole_path {
+            Some(path) => vec![path],
+            None => {
+                let candidates = split_dropped_paths(text);
+                let resolved: Vec<PathBuf> = candidates
+                    .iter()
+                    .filter_map(|candidate| parse_image_path(candidate))
+                    .filter(|path| is_attachable_path(path))
+                    .collect();
+                if candidates.is_empty() || resolved.len() != candidates.len() {
+                    return Ok(false);
+                }
+                resolved
+            }
+        };
+
+        for path in &paths {
+            self.add_file_attachment_from_path(path)?;
+        }
+        Ok(true)
+    }
+
+    fn add_file_attachment_from_path(&mut self, path: &Path) -> Result<()> {
+        if is_supported_image_path(path) {
+            self.add_image_attachment_from_path(path)?;
+            self.show_status_toast("IMAGE ADDED");
+        } else if crate::services::documents::is_supported_document_path(path) {
+            self.add_document_attachment_from_path(path);
+        }
+        Ok(())
+    }
+
+    fn add_document_attachment_from_path(&mut self, path: &Path) {
+        let text = match crate::services::documents::extract_document_text(path) {
+            Ok(text) => text,
+            Err(error) => {
+                self.add_system_message(&format!("Could not attach {}: {}", path.display(), error));
+                return;
+            }
+        };
+        let label = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file")
+            .to_string();
+        let token = make_file_token(&label);
+        self.chat_attachments.push(ChatAttachment::Document {
+            token: token.clone(),
+            path: path.to_path_buf(),
+            text,
+        });
+        self.append_attachment_token(&token);
+        self.show_status_toast("FILE ADDED");
+    }
+
     fn add_image_attachment_from_path(&mut self, path: &Path) -> Result<()> {
         if !path.exists() {
             return Ok(());
@@ -367,7 +658,7 @@ impl App {
         Ok(())
     }
 
-    fn append_attachment_token(&mut self, token: &str) {
+    pub(crate) fn append_attachment_token(&mut self, token: &str) {
         let content = self.chat_input.content();
         let spaced = if content.is_empty() {
             token.to_string()
@@ -411,27 +702,27 @@ fn parse_image_path(input: &str) -> Option<PathBuf> {
     if candidate.starts_with("file://") {
         candidate = candidate.trim_start_matches("file://").to_string();
     }
-    if candidate.starts_with("~/")
-        && let Ok(home) = std::env::var("HOME")
-    {
-        candidate = format!("{}/{}", home, candidate.trim_start_matches("~/"));
-    }
-    if candidate.starts_with("home/") {
-        candidate = format!("/{}", candidate);
-    } else if let Ok(user) = std::env::var("USER")
-        && candidate.starts_with(&format!("{}/", user))
-    {
-        candidate = format!("/home/{}", candidate);
-    }
-    if !candidate.starts_with('/') && !candidate.starts_with("~/") && candidate.contains('/')
-        && let Ok(home) = std::env::var("HOME")
-    {
-        candidate = format!("{}/{}", home, candidate);
+    // Terminals on Linux sometimes drop the leading slash of /home/<user>/...
+    if cfg!(target_os = "linux") {
+        if candidate.starts_with("home/") {
+            candidate = format!("/{}", candidate);
+        } else if let Ok(user) = std::env::var("USER")
+            && candidate.starts_with(&format!("{}/", user))
+        {
+            candidate = format!("/home/{}", candidate);
+        }
     }
     if candidate.is_empty() {
         return None;
     }
-    let path = PathBuf::from(candidate);
+    let mut path = crate::platform::expand_home(&candidate);
+    if path.is_relative()
+        && !candidate.starts_with('~')
+        && candidate.contains(std::path::MAIN_SEPARATOR)
+        && let Some(home) = crate::platform::home_dir()
+    {
+        path = home.join(path);
+    }
     if path.exists() {
         return Some(path);
     }
@@ -446,6 +737,44 @@ fn is_supported_image_path(path: &Path) -> bool {
     )
 }
 
+fn is_attachable_path(path: &Path) -> bool {
+    path.is_file()
+        && (is_supported_image_path(path)
+            || crate::services::documents::is_supported_document_path(path))
+}
+
+/// Splits dropped file paths, honoring single/double quotes and backslash-escaped spaces
+fn split_dropped_paths(text: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut characters = text.chars();
+
+    while let Some(character) = characters.next() {
+        match (quote, character) {
+            (Some(open), _) if character == open => quote = None,
+            (Some(_), _) => current.push(character),
+            // Backslash separates path components on Windows rather than escaping
+            (None, '\\') if !cfg!(windows) => {
+                if let Some(escaped) = characters.next() {
+                    current.push(escaped);
+                }
+            }
+            (None, '\'' | '"') => quote = Some(character),
+            (None, _) if character.is_whitespace() => {
+                if !current.is_empty() {
+                    paths.push(std::mem::take(&mut current));
+                }
+            }
+            (None, _) => current.push(character),
+        }
+    }
+    if !current.is_empty() {
+        paths.push(current);
+    }
+    paths
+}
+
 fn build_attachment_images_from_attachments(attachments: &[ChatAttachment]) -> Result<Vec<String>> {
     let mut images = Vec::new();
     for attachment in attachments {
@@ -457,11 +786,82 @@ fn build_attachment_images_from_attachments(attachments: &[ChatAttachment]) -> R
             ChatAttachment::ClipboardImage { png_bytes, .. } => {
                 images.push(STANDARD.encode(png_bytes));
             }
+            ChatAttachment::Document { .. } | ChatAttachment::Stdin { .. } => {}
         }
     }
     Ok(images)
 }
 
+/// Appends the extracted text of attached documents and piped stdin to the outgoing user message
+fn apply_documents_to_last_user_message(
+    messages: &mut [crate::agents::ChatMessage],
+    attachments: &[ChatAttachment],
+) {
+    let Some(last) = messages.last_mut() else {
+        return;
+    };
+    if last.role != crate::agents::MessageRole::User {
+        return;
+    }
+    for attachment in attachments {
+        if let ChatAttachment::Document { path, text, .. } = attachment {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("file");
+            last.content.push_str(&format!(
+                "\n\n--- Attached file: {} ---\n{}\n--- End of {} ---",
+                name, text, name
+            ));
+        }
+        if let ChatAttachment::Stdin { input, .. } = attachment {
+            last.content.push_str(&format!("\n\n--- Piped input (stdin) ---\n{}", input.text));
+            if let Some(note) = input.truncation_note() {
+                last.content.push_str(&format!("\n[{}]", note));
+            }
+            last.content.push_str("\n--- End of stdin ---");
+        }
+    }
+}
+
+/// OCRs attached screenshots for models that cannot see images and appends the text
+fn apply_image_text_to_last_user_message(
+    messages: &mut [crate::agents::ChatMessage],
+    attachments: &[ChatAttachment],
+    ocr_config: &crate::config::OcrConfig,
+    agent_tx: &std::sync::mpsc::Sender<crate::app::AgentEvent>,
+) {
+    let Some(last) = messages.last_mut() else {
+        return;
+    };
+    if last.role != crate::agents::MessageRole::User {
+        return;
+    }
+    for attachment in attachments {
+        let bytes = match attachment {
+            ChatAttachment::FilePath { path, .. } => match std::fs::read(path) {
+                Ok(bytes) => bytes,
+                Err(_) => continue,
+            },
+            ChatAttachment::ClipboardImage { png_bytes, .. } => png_bytes.clone(),
+            ChatAttachment::Document { .. } | ChatAttachment::Stdin { .. } => continue,
+        };
+        let _ = agent_tx.send(crate::app::AgentEvent::StatusUpdate("reading image text".to_string()));
+        let Ok(text) = crate::services::ocr::extract_image_text(ocr_config, &bytes) else {
+            continue;
+        };
+        if !crate::services::ocr::looks_like_text(&text) {
+            continue;
+        }
+        let label = attachment.label();
+        last.content.push_str(&format!(
+            "\n\n--- Text extracted from image {} ---\n{}\n--- End of image text ---",
+            label, text
+        ));
+        let _ = agent_tx.send(crate::app::AgentEvent::ImageTextExtracted { label, text });
+    }
+}
+
 fn apply_images_to_last_user_message(
     messages: &mut [crate::agents::ChatMessage],
     images: Vec<String>,
@@ -478,11 +878,24 @@ fn make_attachment_token(label: &str) -> String {
     format!("[[image:{}]]", sanitized)
 }
 
-fn remove_attachment_tokens(content: &str) -> String {
+fn make_file_token(label: &str) -> String {
+    let sanitized = label.replace(']', ")").replace('[', "(");
+    format!("[[file:{}]]", sanitized)
+}
+
+/// Finds the next `[[image:` or `[[file:` token start
+fn find_attachment_token(content: &str) -> Option<usize> {
+    ["[[image:", "[[file:"]
+        .iter()
+        .filter_map(|prefix| content.find(prefix))
+        .min()
+}
+
+pub(crate) fn remove_attachment_tokens(content: &str) -> String {
     let mut output = String::new();
     let mut index = 0;
     while index < content.len() {
-        if let Some(start) = content[index..].find("[[image:") {
+        if let Some(start) = find_attachment_token(&content[index..]) {
             let start_index = index + start;
             output.push_str(&content[index..start_index]);
             if let Some(end) = content[start_index..].find("]]") {
@@ -552,20 +965,52 @@ enum FastPathAction {
     Weather(String),
     Time(String),
     Date(String),
+    Calculation(String),
+    Timer(crate::services::timers::TimerRequest),
+    Stopwatch(crate::services::timers::StopwatchCommand),
 }
 
-impl FastPathAction {
-    fn into_reply(self) -> String {
-        match self {
-            FastPathAction::Weather(reply) => reply,
-            FastPathAction::Time(reply) => reply,
-            FastPathAction::Date(reply) => reply,
+impl App {
+    /// The reply to a fast path message; timers and the stopwatch are started here
+    fn run_fast_path_action(&mut self, action: FastPathAction) -> String {
+        match action {
+            FastPathAction::Weather(reply)
+            | FastPathAction::Time(reply)
+            | FastPathAction::Date(reply)
+            | FastPathAction::Calculation(reply) => reply,
+            FastPathAction::Timer(request) => self.start_timer(request),
+            FastPathAction::Stopwatch(command) => self.run_stopwatch(command),
         }
     }
 }
 
-fn select_fast_path_action(input: &str) -> Result<Option<FastPathAction>> {
-    if let Some(reply) = try_handle_weather_question(input)? {
+fn select_fast_path_action(
+    input: &str,
+    calculator: &crate::config::CalculatorConfig,
+    finance: &crate::config::FinanceConfig,
+    weather: &crate::config::WeatherConfig,
+) -> Result<Option<FastPathAction>> {
+    if let Some(request) = crate::services::timers::parse_timer_request(input) {
+        return Ok(Some(FastPathAction::Timer(request)));
+    }
+    if let Some(command) = crate::services::timers::parse_stopwatch_request(input) {
+        return Ok(Some(FastPathAction::Stopwatch(command)));
+    }
+    if calculator.enabled {
+        // Live rates are only fetched for currency conversions, never for plain arithmetic
+        let rates = if crate::services::calculator::currency_codes(input).is_some() {
+            std::borrow::Cow::Owned(crate::services::finance::conversion_rates(
+                finance,
+                &calculator.currency_rates,
+            ))
+        } else {
+            std::borrow::Cow::Borrowed(&calculator.currency_rates)
+        };
+        if let Some(reply) = crate::services::calculator::answer(input, &rates) {
+            return Ok(Some(FastPathAction::Calculation(reply)));
+        }
+    }
+    if let Some(reply) = try_handle_weather_question(input, weather)? {
         return Ok(Some(FastPathAction::Weather(reply)));
     }
     if let Some(reply) = try_handle_time_question(input) {
@@ -582,47 +1027,28 @@ struct SearchStateRequest<'a> {
     intent: crate::app::chat::agent::intent::QueryIntent,
 }
 
-#[derive(Debug, Deserialize)]
-struct WeatherSnapshot {
-    location: String,
-    time: String,
-    temperature_c: f32,
-    wind_kph: f32,
-}
-
-fn try_handle_weather_question(input: &str) -> Result<Option<String>> {
+fn try_handle_weather_question(
+    input: &str,
+    config: &crate::config::WeatherConfig,
+) -> Result<Option<String>> {
     let lowered = input.trim().to_lowercase();
     if !should_handle_weather_question(&lowered) {
         return Ok(None);
     }
-    if references_other_location(&lowered) {
-        return Ok(Some(
-            "I can only fetch current weather for Prague right now.".to_string(),
-        ));
+    if references_other_location(&lowered, &config.location) {
+        return Ok(Some(format!(
+            "I can only fetch current weather for {} right now.",
+            config.location
+        )));
     }
-    let service = WeatherService::new();
-    match service.fetch_current_weather_json() {
-        Ok(payload) => match serde_json::from_str::<WeatherSnapshot>(&payload) {
-            Ok(snapshot) => Ok(Some(format_weather_snapshot(&snapshot))),
-            Err(_) => Ok(Some(
-                "I couldn't read the weather data just now.".to_string(),
-            )),
-        },
+    match crate::services::weather::current(config) {
+        Ok(report) => Ok(Some(crate::services::weather::format_report(&report, config.units))),
         Err(_) => Ok(Some(
             "I couldn't fetch the current weather right now.".to_string(),
         )),
     }
 }
 
-fn format_weather_snapshot(snapshot: &WeatherSnapshot) -> String {
-    let temperature = format!("{:.1}", snapshot.temperature_c);
-    let wind = format!("{:.0}", snapshot.wind_kph);
-    format!(
-        "Current weather in {}: {}°C, wind {} km/h (as of {}).",
-        snapshot.location, temperature, wind, snapshot.time
-    )
-}
-
 fn looks_like_question(lowered: &str) -> bool {
     let prefixes = [
         "what ", "when ", "which ", "is ", "does ", "do ", "tell me", "can you", "could you",
@@ -652,13 +1078,14 @@ fn should_handle_weather_question(lowered: &str) -> bool {
         || lowered.starts_with("forecast")
 }
 
-fn references_other_location(lowered: &str) -> bool {
+fn references_other_location(lowered: &str, home: &str) -> bool {
     let location_markers = [" in ", " at ", " for ", " near "];
     let mentions_location = location_markers
         .iter()
         .any(|marker| lowered.contains(marker));
-    let mentions_prague = lowered.contains("prague") || lowered.contains("praha");
-    mentions_location && !mentions_prague
+    let home = home.trim().to_lowercase();
+    let mentions_home = !home.is_empty() && lowered.contains(&home);
+    mentions_location && !mentions_home
 }
 
 fn try_handle_time_question(input: &str) -> Option<String> {
@@ -1,4 +1,5 @@
 mod agent;
+mod autosave;
 mod commands;
 mod input;
 mod response;
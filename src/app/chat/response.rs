@@ -1,6 +1,6 @@
-use crate::app::types::ChatMessage;
+use crate::app::types::{ChatMessage, PersonalitySample};
 use crate::app::{App, AgentEvent};
-use crate::storage::{ConversationData, ConversationMessage};
+use crate::storage::ConversationMessage;
 use color_eyre::Result;
 
 impl App {
@@ -12,29 +12,66 @@ impl App {
             .as_ref()
             .map(|rx| std::iter::from_fn(|| rx.try_recv().ok()).collect())
             .unwrap_or_default();
+        // Editors write a file in several steps; reload once per batch of events
+        let mut personalities_changed = false;
+        let mut config_changed = false;
         for event in events {
             match event {
-                AgentEvent::ResponseWithContext { response, context_usage } => {
-                    self.handle_agent_response(response, context_usage);
+                AgentEvent::ResponseWithContext { response, context_usage, stats } => {
+                    self.handle_agent_response(response, context_usage, stats);
                 }
                 AgentEvent::Error(error) => self.handle_agent_error(error),
-                AgentEvent::SummaryGenerated { summary, conversation_id, messages } => {
-                    self.handle_summary_generated(summary, conversation_id, messages);
+                AgentEvent::Notification { kind, title, message } => {
+                    self.publish_notification(kind, &title, &message);
+                }
+                AgentEvent::NotificationStored => self.handle_notification_stored(),
+                AgentEvent::SummaryGenerated { summary, conversation_id, messages, chunk_summaries } => {
+                    self.handle_summary_generated(summary, conversation_id, messages, chunk_summaries);
                 }
                 AgentEvent::SystemMessage(message) => self.handle_system_message(message),
                 AgentEvent::StatusUpdate(status) => self.current_activity = Some(status),
-                AgentEvent::DownloadFinished { url } => {
-                    self.active_downloads.retain(|item| item.url != url);
+                AgentEvent::PromptPreview(sections) => self.prompt_preview = Some(sections),
+                AgentEvent::DownloadFinished { url, status, destination, error } => {
+                    self.handle_download_finished(&url, status, destination, error);
+                }
+                AgentEvent::DownloadProgress { url, update } => {
+                    self.apply_download_update(&url, update);
                 }
-                AgentEvent::DownloadProgress { url, progress } => {
-                    if let Some(item) = self.active_downloads.iter_mut().find(|item| item.url == url) {
-                        item.progress = Some(progress);
+                AgentEvent::ConversionFileFinished { input, result } => {
+                    self.conversion_done += 1;
+                    if let Err(error) = result {
+                        self.add_system_message(&format!(
+                            "Conversion failed for {}: {}",
+                            input, error
+                        ));
                     }
                 }
-                AgentEvent::ConversionFinished => {
-                    self.conversion_active = false;
-                    self.conversion_frame = 0;
-                    self.last_conversion_tick = None;
+                AgentEvent::ConversionFinished { format, converted, total } => {
+                    self.handle_conversion_finished(&format, converted, total);
+                }
+                AgentEvent::EmailDraft(draft) => self.open_email_preview(draft),
+                AgentEvent::Health(results) => self.handle_health(results),
+                AgentEvent::EmailSent { to, result } => self.handle_email_sent(&to, result),
+                AgentEvent::HomeAssistantCall(call) => self.open_home_assistant_confirm(call),
+                AgentEvent::HomeAssistantCallFinished { call, result } => {
+                    self.handle_home_assistant_call_finished(&call, result);
+                }
+                AgentEvent::AgendaLoaded { result } => self.handle_agenda_loaded(result),
+                AgentEvent::NewsBriefing { result } => {
+                    self.handle_news_briefing(result);
+                }
+                AgentEvent::TranscriptionFinished { source, result, summary } => {
+                    self.handle_transcription_finished(&source, result, summary);
+                }
+                AgentEvent::ImageGenerated { prompt, result } => {
+                    self.handle_image_generated(&prompt, result);
+                }
+                AgentEvent::ResumeRecap { conversation_id, recap } => {
+                    self.handle_resume_recap(&conversation_id, recap);
+                }
+                AgentEvent::TasksExtracted { count } => self.handle_tasks_extracted(count),
+                AgentEvent::EarlierAnswerChecked { question, earlier } => {
+                    self.handle_earlier_answer_checked(&question, earlier);
                 }
                 AgentEvent::CacheObsidianNotes { query, notes } => {
                     self.cached_obsidian_notes = Some((query, notes));
@@ -50,14 +87,85 @@ impl App {
                         self.suggestion_mode_active = false;
                     }
                 }
-                AgentEvent::TopicsExtracted { topics, conversation_id } => {
-                    self.handle_topics_extracted(topics, conversation_id);
+                AgentEvent::TopicsExtracted { frequent } => self.handle_topics_extracted(frequent),
+                AgentEvent::HistoryLoaded { filter, conversations, has_more } => {
+                    self.handle_history_loaded(filter, conversations, has_more);
                 }
+                AgentEvent::ConversationSaved(result) => self.handle_conversation_saved(result),
                 AgentEvent::ProjectEntriesExtracted { results } => {
                     self.handle_project_entries_extracted(results);
                 }
+                AgentEvent::ImageTextExtracted { label, text } => {
+                    // Shown as a note only; loading continues while the model answers
+                    self.chat_history.push(ChatMessage::system(format!(
+                        "Text read from {} (sent to the model):\n{}",
+                        label, text
+                    )));
+                }
+                AgentEvent::MessageTranslated(note) => {
+                    self.chat_history.push(ChatMessage::system(note));
+                }
+                AgentEvent::PersonalitySuggestionReady { personality } => {
+                    self.personality_suggestion =
+                        crate::services::personality_tuning::load_pending_suggestion();
+                    self.show_status_toast(format!(
+                        "PERSONALITY SUGGESTION READY: {}",
+                        personality.to_uppercase()
+                    ));
+                }
+                AgentEvent::PersonalitiesChanged => personalities_changed = true,
+                AgentEvent::ConfigChanged => config_changed = true,
+                AgentEvent::ToolAudited { tool, arguments, decision, result } => {
+                    self.record_tool_audit(tool, arguments, decision, result);
+                }
+                AgentEvent::ToolOutputStored(output) => self.store_tool_output(output),
+                AgentEvent::AnswerChecked { response, check } => {
+                    self.handle_answer_checked(&response, check);
+                }
+                AgentEvent::PersonalitySampleReady { personality, reply } => {
+                    self.personality_sample = Some(PersonalitySample {
+                        personality,
+                        reply: Some(reply),
+                    });
+                }
             }
         }
+        if personalities_changed {
+            self.reload_personalities_from_disk();
+        }
+        // Editors write in several steps; one reload per batch of events
+        if config_changed {
+            self.reload_config_from_disk();
+        }
+    }
+
+    /// Keeps the full text of a summarized tool result so it can be cited later
+    fn store_tool_output(&mut self, output: crate::storage::ToolOutputRecord) {
+        if self.incognito {
+            return;
+        }
+        self.spawn_storage_job(move |storage, runtime| {
+            let key = output.key.clone();
+            if let Err(error) = runtime.block_on(storage.save_tool_output(output)) {
+                tracing::warn!(%error, %key, "tool output not stored");
+            }
+        });
+    }
+
+    /// Puts the check's badge on the reply it was made for, if it is still in the chat
+    fn handle_answer_checked(
+        &mut self,
+        response: &str,
+        check: crate::services::verification::AnswerCheck,
+    ) {
+        if let Some(message) = self
+            .chat_history
+            .iter_mut()
+            .rev()
+            .find(|message| message.role == crate::app::types::MessageRole::Assistant && message.content == response)
+        {
+            message.check = Some(check);
+        }
     }
 
     /// Clears all loading/activity flags at once
@@ -72,6 +180,7 @@ impl App {
         &mut self,
         response: String,
         context_usage: Option<crate::app::types::ContextUsage>,
+        stats: Option<crate::storage::MessageStats>,
     ) {
         self.clear_loading_state();
         self.last_response = Some(response.clone());
@@ -81,18 +190,23 @@ impl App {
         } else {
             None
         };
-        self.chat_history
-            .push(ChatMessage::assistant(response.clone(), display_name, context_usage));
+        let mut message = ChatMessage::assistant(response.clone(), display_name, context_usage);
+        message.stats = stats;
+        self.chat_history.push(message);
 
         if self.chat_auto_scroll {
             self.chat_scroll_offset = 0;
         }
 
-        if let Err(error) = self.persist_conversation_messages() {
+        if let Err(error) = self.autosave_conversation(false) {
+            tracing::error!(%error, "history save failed");
             self.add_system_message(&format!("HISTORY SAVE FAILED: {}", error));
         }
 
         self.maybe_update_emotions(&response);
+        if self.headless {
+            return;
+        }
         self.spawn_follow_up_suggestions(&response);
 
         if self.auto_tts_enabled
@@ -121,6 +235,7 @@ impl App {
         summary: String,
         conversation_id: String,
         messages: Vec<crate::storage::ConversationMessage>,
+        chunk_summaries: Vec<String>,
     ) {
         self.is_generating_summary = false;
         self.summary_active = false;
@@ -129,32 +244,50 @@ impl App {
 
         let (short_summary, detailed_summary) = Self::parse_summary_pair(&summary);
         self.maybe_spawn_identity_reflection(&detailed_summary);
+        self.maybe_spawn_diary_entry(&detailed_summary);
+        self.maybe_spawn_personality_suggestion();
+
+        // Remember the closed conversation so the next one can pick up where it left off
+        self.previous_session = Some(crate::app::types::PreviousSession {
+            summary: detailed_summary.clone(),
+            closed_at: std::time::Instant::now(),
+        });
 
         // Save summary to storage using the captured conversation_id,
         // not the current one (which may belong to a different chat now).
-        self.ensure_storage();
-        if let (Some(storage), Some(rt)) = (self.storage.as_ref(), self.storage_runtime()) {
-            let _ = rt.block_on(async {
-                storage
-                    .update_conversation(
+        {
+            let conversation_id = conversation_id.clone();
+            let short_summary = short_summary.clone();
+            let messages = messages.clone();
+            self.spawn_storage_job(move |storage, runtime| {
+                let saved = runtime
+                    .block_on(storage.update_conversation(
                         &conversation_id,
                         &short_summary,
                         &detailed_summary,
                         &messages,
-                    )
-                    .await
+                    ))
+                    .and_then(|()| {
+                        runtime.block_on(storage.set_conversation_chunk_summaries(&conversation_id, &chunk_summaries))
+                    });
+                match saved {
+                    Ok(()) => Self::spawn_background_embeddings(storage.clone(), conversation_id, messages),
+                    Err(error) => tracing::error!(%error, conversation = %conversation_id, "summary save failed"),
+                }
             });
-
-            Self::spawn_background_embeddings(storage.clone(), conversation_id.clone(), messages.clone());
         }
 
+        self.attach_conversation_to_active_project(&conversation_id, &short_summary);
+
         // Spawn topic extraction in background
         self.maybe_spawn_topic_extraction(&messages, &conversation_id);
+        self.maybe_spawn_task_extraction(&messages, &conversation_id);
+        self.maybe_spawn_sentiment_analysis(&messages, &conversation_id);
 
         // Only refresh history UI if user is currently viewing it
         if self.mode == crate::app::AppMode::History {
+            self.history_pending_select = Some(conversation_id);
             self.load_history_list();
-            self.select_history_conversation(&conversation_id);
         }
     }
 
@@ -181,38 +314,6 @@ impl App {
         Ok(())
     }
 
-    fn persist_conversation_messages(&mut self) -> Result<()> {
-        if !self.ensure_storage() {
-            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
-        }
-        let agent_name = self
-            .current_agent
-            .as_ref()
-            .map_or("unknown", |agent| agent.name.as_str())
-            .to_string();
-        let messages = self.build_conversation_messages();
-
-        let (storage, runtime) = self.storage_with_runtime()?;
-        let conversation_id =
-            if let Some(conversation_id) = self.current_conversation_id.clone() {
-                runtime.block_on(
-                    storage.update_conversation_messages(&conversation_id, &messages),
-                )?;
-                conversation_id
-            } else {
-                let data = ConversationData::new(&agent_name, &messages);
-                let new_id = runtime.block_on(storage.save_conversation(data))?;
-                self.current_conversation_id = Some(new_id.clone());
-                new_id
-            };
-
-        // Generate embeddings in background thread (non-blocking)
-        if let Some(storage) = &self.storage {
-            Self::spawn_background_embeddings(storage.clone(), conversation_id, messages);
-        }
-        Ok(())
-    }
-
     // ── Project topic extraction ──────────────────────────────────────────────
 
     fn maybe_spawn_topic_extraction(
@@ -220,6 +321,10 @@ impl App {
         messages: &[ConversationMessage],
         conversation_id: &str,
     ) {
+        // Nothing from an incognito chat reaches topics or project notes
+        if self.incognito && self.current_conversation_id.as_deref() == Some(conversation_id) {
+            return;
+        }
         // Skip if conversation is too short (fewer than 4 messages)
         let non_system_count = messages
             .iter()
@@ -236,8 +341,11 @@ impl App {
             return;
         };
 
+        let routing_agent = manager.get_agent("routing").cloned();
+        let storage = self.storage.clone();
         let vault_path = self.connect_obsidian_vault_path.clone();
         let conversation_id = conversation_id.to_string();
+        let active_project = self.active_project.clone();
 
         // Build conversation content for the LLM
         let content: String = messages
@@ -253,21 +361,38 @@ impl App {
             .join(" ");
 
         std::thread::spawn(move || {
-            let topics = crate::services::projects::extract_topics(&content, &agent, &manager);
-            if topics.is_empty() {
+            let summary_agent = manager.summary_agent();
+            let extractors: Vec<&crate::agents::Agent> = summary_agent
+                .iter()
+                .chain(routing_agent.iter())
+                .chain(std::iter::once(&agent))
+                .collect();
+            let mut topics =
+                crate::services::projects::extract_topics(&content, &extractors, &manager);
+            // Merge synonyms with topics seen before so their mentions count together
+            if !topics.is_empty()
+                && let Some(storage) = &storage
+                && let Ok(runtime) = tokio::runtime::Runtime::new()
+            {
+                topics = runtime
+                    .block_on(crate::services::projects::canonicalize_topics(storage, topics));
+            }
+            if topics.is_empty() && active_project.is_none() {
                 return;
             }
 
             // Check if any topics match existing projects for entry extraction
             let existing_projects =
                 crate::services::projects::list_project_names(&vault_path).unwrap_or_default();
+            // The active project always collects facts from the conversation
             let matching: Vec<String> = existing_projects
                 .iter()
                 .filter(|name| {
                     let name_lower = name.to_lowercase();
-                    topics.iter().any(|topic| {
-                        name_lower.contains(topic) || topic.contains(&name_lower)
-                    })
+                    active_project.as_deref() == Some(name.as_str())
+                        || topics.iter().any(|topic| {
+                            name_lower.contains(topic) || topic.contains(&name_lower)
+                        })
                 })
                 .cloned()
                 .collect();
@@ -282,41 +407,35 @@ impl App {
                 }
             }
 
-            let _ = agent_tx.send(AgentEvent::TopicsExtracted {
-                topics,
-                conversation_id,
-            });
+            if topics.is_empty() {
+                return;
+            }
+            // Store the mentions here so the UI thread only sees topics worth suggesting
+            let Some(storage) = storage else {
+                return;
+            };
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            if let Err(error) = runtime.block_on(storage.record_topic_mentions(&topics, &conversation_id)) {
+                tracing::warn!(%error, "could not record topic mentions");
+            }
+            let frequent = runtime.block_on(storage.load_frequent_topics(3)).unwrap_or_default();
+            if !frequent.is_empty() {
+                let _ = agent_tx.send(AgentEvent::TopicsExtracted { frequent });
+            }
         });
     }
 
-    fn handle_topics_extracted(&mut self, topics: Vec<String>, conversation_id: String) {
-        // Store topic mentions in DB
-        self.ensure_storage();
-        if let (Some(storage), Some(rt)) = (self.storage.as_ref(), self.storage_runtime()) {
-            let _ = rt.block_on(async {
-                storage.record_topic_mentions(&topics, &conversation_id).await
-            });
-
-            // Check if any topic crosses the suggestion threshold
-            let frequent = rt
-                .block_on(async { storage.load_frequent_topics(3).await })
-                .unwrap_or_default();
+    fn handle_topics_extracted(&mut self, frequent: Vec<(String, usize)>) {
+        let vault_path = self.connect_obsidian_vault_path.clone();
+        let existing = crate::services::projects::list_project_names(&vault_path).unwrap_or_default();
+        let existing_lower: Vec<String> = existing.iter().map(|name| name.to_lowercase()).collect();
 
-            if !frequent.is_empty() {
-                let vault_path = self.connect_obsidian_vault_path.clone();
-                let existing =
-                    crate::services::projects::list_project_names(&vault_path).unwrap_or_default();
-                let existing_lower: Vec<String> =
-                    existing.iter().map(|name| name.to_lowercase()).collect();
-
-                for (topic, _count) in &frequent {
-                    // Only suggest if there's no existing project with this name
-                    if !existing_lower.contains(topic)
-                        && !self.pending_project_suggestions.contains(topic)
-                    {
-                        self.pending_project_suggestions.push(topic.clone());
-                    }
-                }
+        for (topic, _count) in &frequent {
+            // Only suggest if there's no existing project with this name
+            if !existing_lower.contains(topic) && !self.pending_project_suggestions.contains(topic) {
+                self.pending_project_suggestions.push(topic.clone());
             }
         }
     }
@@ -335,17 +454,19 @@ impl App {
         }
     }
 
-    /// Spawns a background thread to generate follow-up question suggestions
+    /// Spawns a background thread that asks the routing model (falling back to the
+    /// chat model) for short follow-up questions
     fn spawn_follow_up_suggestions(&self, response: &str) {
         let Some(manager) = self.agent_manager.clone() else {
             return;
         };
-        let Some(agent) = self.current_agent.clone() else {
+        let Some(chat_agent) = self.current_agent.clone() else {
             return;
         };
         let Some(agent_tx) = self.agent_tx.clone() else {
             return;
         };
+        let routing_agent = manager.get_agent("routing").cloned();
 
         // Build recent context: last user message + assistant response
         let last_user = self
@@ -359,32 +480,39 @@ impl App {
 
         std::thread::spawn(move || {
             let prompt = format!(
-                "Based on this conversation exchange, suggest exactly 2 short follow-up questions \
+                "Based on this conversation exchange, suggest 2 or 3 short follow-up questions \
                  the user might want to ask next. Each should be concise (under 8 words).\n\n\
                  User: {}\nAssistant: {}\n\n\
-                 Return ONLY a JSON array of 2 strings, nothing else:\n\
-                 [\"question 1\", \"question 2\"]",
+                 Return ONLY a JSON array of strings, nothing else:\n\
+                 [\"question 1\", \"question 2\", \"question 3\"]",
                 last_user,
                 response.chars().take(500).collect::<String>()
             );
 
             let messages = vec![
                 crate::agents::ChatMessage::system(
-                    "You suggest follow-up questions. Output only a JSON array of 2 short strings.",
+                    "You suggest follow-up questions. Output only a JSON array of 2-3 short strings.",
                 ),
                 crate::agents::ChatMessage::user(prompt),
             ];
 
-            if let Ok(raw) = manager.chat(&agent, &messages)
-                && let Some(suggestions) = parse_suggestion_array(&raw)
-            {
+            let suggestions = routing_agent
+                .iter()
+                .chain(std::iter::once(&chat_agent))
+                .find_map(|agent| {
+                    manager
+                        .chat(agent, &messages)
+                        .ok()
+                        .and_then(|raw| parse_suggestion_array(&raw))
+                });
+            if let Some(suggestions) = suggestions {
                 let _ = agent_tx.send(AgentEvent::FollowUpSuggestions { suggestions });
             }
         });
     }
 
     /// Spawns a background thread to generate and save embeddings without blocking the UI
-    fn spawn_background_embeddings(
+    pub(crate) fn spawn_background_embeddings(
         storage: crate::storage::StorageManager,
         conversation_id: String,
         messages: Vec<ConversationMessage>,
@@ -394,11 +522,11 @@ impl App {
                 return;
             };
             runtime.block_on(async {
-                for message in &messages {
-                    let embedding = crate::services::retrieval::generate_message_embedding(&message.content)
-                        .await
-                        .ok()
-                        .flatten();
+                let contents: Vec<&str> = messages.iter().map(|message| message.content.as_str()).collect();
+                let embeddings = crate::services::retrieval::generate_message_embeddings(&contents)
+                    .await
+                    .unwrap_or_else(|_| vec![None; messages.len()]);
+                for (message, embedding) in messages.iter().zip(embeddings) {
                     let update = crate::storage::MessageEmbeddingUpdate {
                         conversation_id: &conversation_id,
                         role: &message.role,
@@ -414,6 +542,8 @@ impl App {
     }
 }
 
+const MAX_FOLLOW_UP_SUGGESTIONS: usize = 3;
+
 /// Parses a JSON array of strings from LLM output, handling common quirks
 fn parse_suggestion_array(raw: &str) -> Option<Vec<String>> {
     // Try to find JSON array in the response
@@ -426,10 +556,18 @@ fn parse_suggestion_array(raw: &str) -> Option<Vec<String>> {
     };
 
     let parsed: Vec<String> = serde_json::from_str(json_str).ok()?;
-    if parsed.is_empty() {
+    let mut suggestions: Vec<String> = Vec::new();
+    for suggestion in parsed {
+        let suggestion = suggestion.trim().to_string();
+        if !suggestion.is_empty() && !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
+    if suggestions.is_empty() {
         return None;
     }
 
-    // Take up to 2 suggestions
-    Some(parsed.into_iter().take(2).collect())
+    // Take up to 3 suggestions
+    suggestions.truncate(MAX_FOLLOW_UP_SUGGESTIONS);
+    Some(suggestions)
 }
@@ -5,6 +5,10 @@ use crate::storage::ConversationMessage;
 use color_eyre::Result;
 
 pub(crate) const PENDING_SUMMARY_LABEL: &str = "Generating";
+/// Messages per part when a long chat is summarized part by part
+const SUMMARY_CHUNK_MESSAGES: usize = 12;
+/// Characters of each part sent to the summary model
+const SUMMARY_CHUNK_CHARS: usize = 2000;
 
 impl App {
     pub(crate) fn parse_summary_pair(summary: &str) -> (String, String) {
@@ -53,107 +57,185 @@ impl App {
             .map_or_else(|| summary.to_string(), |slice| slice.join(" "))
     }
 
-    /// Builds conversation context from recent messages for summary generation
-    fn build_summary_context(&self) -> String {
-        self.chat_history
+    /// Splits the whole conversation into parts of `SUMMARY_CHUNK_MESSAGES` messages;
+    /// chats that fit one part are summarized directly, longer ones part by part
+    fn build_summary_chunks(&self) -> Vec<String> {
+        let messages: Vec<&crate::app::types::ChatMessage> = self
+            .chat_history
             .iter()
             .filter(|message| message.role != MessageRole::System)
-            .rev()
-            .take(10)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .map(|message| message.content.clone())
-            .collect::<Vec<_>>()
-            .join(" ")
+            .collect();
+        messages
+            .chunks(SUMMARY_CHUNK_MESSAGES)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|message| format!("{}: {}", message.role.as_str(), message.content))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .chars()
+                    .take(SUMMARY_CHUNK_CHARS)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// One or two sentences about one part of a long conversation, on a single line
+    fn summarize_chunk(
+        agents: &[&crate::agents::Agent],
+        manager: &crate::agents::AgentManager,
+        chunk: &str,
+    ) -> Option<String> {
+        let messages = vec![
+            AgentChatMessage::system("You summarize parts of conversations in one or two sentences."),
+            AgentChatMessage::user(format!(
+                "Summarize this part of a conversation in one or two sentences. Keep names, \
+decisions and plans.\n\n{}",
+                chunk
+            )),
+        ];
+        agents.iter().find_map(|agent| {
+            let summary = manager.chat_cached(agent, &messages).ok()?;
+            let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!summary.is_empty()).then_some(summary)
+        })
     }
 
     pub(crate) fn build_conversation_messages(&self) -> Vec<ConversationMessage> {
         self.chat_history
             .iter()
-            .map(|message| {
-                let role = match message.role {
-                    MessageRole::User => "User",
-                    MessageRole::Assistant => "Assistant",
-                    MessageRole::System => "System",
-                };
-                ConversationMessage {
-                    role: role.to_string(),
-                    content: message.content.clone(),
-                    timestamp: message.timestamp.clone(),
-                    display_name: message.display_name.clone(),
-                }
+            .map(|message| ConversationMessage {
+                role: message.role.as_str().to_string(),
+                content: message.content.clone(),
+                timestamp: message.timestamp.clone(),
+                display_name: message.display_name.clone(),
+                quoted_role: message
+                    .quote
+                    .as_ref()
+                    .map(|quote| quote.role.as_str().to_string()),
+                quoted_text: message.quote.as_ref().map(|quote| quote.content.clone()),
+                stats: message.stats.clone(),
+                images: message
+                    .images
+                    .iter()
+                    .map(|image| image.path.to_string_lossy().into_owned())
+                    .collect(),
+                files: message
+                    .files
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect(),
             })
             .collect()
     }
 
-    fn save_pending_conversation(&mut self, messages: &[ConversationMessage]) -> Result<()> {
-        if !self.ensure_storage() {
-            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
-        }
-        let agent_name = self
-            .current_agent
-            .as_ref()
-            .map_or("unknown", |agent| agent.name.as_str())
-            .to_string();
-
-        let (storage, runtime) = self.storage_with_runtime()?;
-        if let Some(conversation_id) = &self.current_conversation_id {
+    /// Saves the chat with the pending summary label and returns its conversation id
+    fn save_pending_conversation(
+        storage: &crate::storage::StorageManager,
+        runtime: &tokio::runtime::Runtime,
+        conversation_id: Option<String>,
+        agent_name: &str,
+        language: &str,
+        messages: &[ConversationMessage],
+    ) -> Result<String> {
+        if let Some(conversation_id) = conversation_id {
             runtime.block_on(storage.update_conversation(
-                conversation_id,
+                &conversation_id,
                 PENDING_SUMMARY_LABEL,
                 PENDING_SUMMARY_LABEL,
                 messages,
             ))?;
-        } else {
-            let data = crate::storage::ConversationData::new(&agent_name, messages)
-                .with_summary(PENDING_SUMMARY_LABEL)
-                .with_detailed_summary(PENDING_SUMMARY_LABEL);
-            let conversation_id = runtime.block_on(storage.save_conversation(data))?;
-            self.current_conversation_id = Some(conversation_id);
+            return Ok(conversation_id);
+        }
+        let data = crate::storage::ConversationData::new(agent_name, messages)
+            .with_summary(PENDING_SUMMARY_LABEL)
+            .with_detailed_summary(PENDING_SUMMARY_LABEL)
+            .with_language(language);
+        runtime.block_on(storage.save_conversation(data))
+    }
+
+    pub(crate) fn handle_conversation_saved(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(conversation_id) => {
+                // Still looking at the list this chat was closed into
+                if self.mode == crate::app::AppMode::History && self.current_conversation_id.is_none() {
+                    self.current_conversation_id = Some(conversation_id.clone());
+                    self.select_history_conversation(&conversation_id);
+                    self.history_pending_select = Some(conversation_id);
+                }
+            }
+            Err(error) => {
+                tracing::error!(%error, "history save failed");
+                self.is_generating_summary = false;
+                self.summary_active = false;
+                self.show_status_toast(format!("HISTORY SAVE FAILED: {}", error));
+            }
         }
-        Ok(())
     }
 
     /// Spawns a background thread to generate conversation summary.
     /// The thread is fully self-contained: it carries the conversation_id and messages
     /// so the result can be saved without depending on current app state.
+    /// Long chats are summarized hierarchically: each part first, then the whole
+    /// from the part summaries, which are kept for recall. Runs on the `summary` agent,
+    /// falling back to the chat agent when it is not configured or fails.
     fn spawn_summary_generation_thread(
         agent: crate::agents::Agent,
         manager: crate::agents::AgentManager,
-        context: String,
+        chunks: Vec<String>,
         conversation_id: String,
         conversation_messages: Vec<crate::storage::ConversationMessage>,
         agent_tx: std::sync::mpsc::Sender<AgentEvent>,
     ) {
-        let summary_prompt = format!(
-            "Generate two summaries for this conversation.\n\
+        std::thread::spawn(move || {
+            let summary_agent = manager.summary_agent();
+            let summarizers: Vec<&crate::agents::Agent> =
+                summary_agent.iter().chain(std::iter::once(&agent)).collect();
+            let chunk_summaries: Vec<String> = if chunks.len() > 1 {
+                chunks
+                    .iter()
+                    .filter_map(|chunk| Self::summarize_chunk(&summarizers, &manager, chunk))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let context = if chunk_summaries.is_empty() {
+                format!("Conversation: {}", chunks.join("\n"))
+            } else {
+                let parts: Vec<String> = chunk_summaries
+                    .iter()
+                    .enumerate()
+                    .map(|(index, summary)| format!("{}. {}", index + 1, summary))
+                    .collect();
+                format!("Summaries of the conversation's parts, in order:\n{}", parts.join("\n"))
+            };
+            let summary_prompt = format!(
+                "Generate two summaries for this conversation.\n\
 Short: 7-12 words.\n\
 Detailed: 2-3 sentences.\n\
 Return only two lines in this exact format:\n\
 Short: <summary>\n\
 Detailed: <summary>\n\n\
-Conversation: {}",
-            context.chars().take(400).collect::<String>()
-        );
-
-        std::thread::spawn(move || {
+{}",
+                context
+            );
             let messages = vec![
                 AgentChatMessage::system(
                     "You create short and detailed conversation summaries. Follow the requested format exactly.",
                 ),
                 AgentChatMessage::user(&summary_prompt),
             ];
-            let response = match manager.chat(&agent, &messages) {
-                Ok(text) => text,
-                Err(_) => "Short: Conversation\nDetailed: Conversation".to_string(),
-            };
+            let response = summarizers
+                .iter()
+                .find_map(|summarizer| manager.chat_cached(summarizer, &messages).ok())
+                .unwrap_or_else(|| "Short: Conversation\nDetailed: Conversation".to_string());
             let (short, detailed) = Self::parse_summary_pair(&response);
             let payload = format!("{}\n{}", short, detailed);
             let _ = agent_tx.send(AgentEvent::SummaryGenerated {
                 summary: payload,
                 conversation_id,
                 messages: conversation_messages,
+                chunk_summaries,
             });
         });
     }
@@ -171,56 +253,47 @@ Conversation: {}",
             tts.stop();
         }
         
-        // Now handle chat saving/summary (after mode change)
-        if self.chat_history.is_empty() {
+        // Now handle chat saving/summary (after mode change); incognito chats are dropped
+        if self.chat_history.is_empty() || self.incognito {
             // Load history data after mode change
             let _ = self.ensure_storage();
             self.load_history_list();
             return Ok(());
         }
 
-        // Always generate summary for conversations with messages
-        if !self.chat_history.is_empty() {
-            let context = self.build_summary_context();
-            let messages = self.build_conversation_messages();
-            
-            // Quick save with pending label (this is relatively fast - local SQLite)
-            if let Err(error) = self.save_pending_conversation(&messages) {
-                self.show_status_toast(format!("HISTORY SAVE FAILED: {}", error));
+        // Save with a pending label, then summarize in the background; the history list
+        // is queued behind the save so it already contains this chat
+        let chunks = self.build_summary_chunks();
+        let messages = self.build_conversation_messages();
+        let agent_name = self
+            .current_agent
+            .as_ref()
+            .map_or("unknown", |agent| agent.name.as_str())
+            .to_string();
+        let existing_id = self.current_conversation_id.clone();
+        let language = self.conversation_language.clone();
+        self.mark_autosaved(messages.len());
+        // If this fails, we skip summary generation but still save and load history
+        let summary_dependencies = self.get_agent_chat_dependencies().ok();
+        if summary_dependencies.is_some() {
+            self.is_generating_summary = true;
+            self.summary_active = true;
+        }
+        let tx = self.agent_tx.clone();
+        self.spawn_storage_job(move |storage, runtime| {
+            let saved = Self::save_pending_conversation(storage, runtime, existing_id, &agent_name, &language, &messages)
+                .map_err(|error| error.to_string());
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::ConversationSaved(saved.clone()));
             }
-            
-            // Validate dependencies BEFORE setting flags.
-            // If this fails, we skip summary generation but still load history normally.
-            if let Ok((agent, manager, agent_tx)) = self.get_agent_chat_dependencies() {
-                self.is_generating_summary = true;
-                self.summary_active = true;
-
-                // Capture conversation_id now — the thread must be self-contained
-                // so it works even if the user opens a new chat before it finishes.
-                let conversation_id = self
-                    .current_conversation_id
-                    .clone()
-                    .unwrap_or_default();
-
-                // Summary generation happens in background thread (non-blocking)
-                Self::spawn_summary_generation_thread(
-                    agent,
-                    manager,
-                    context,
-                    conversation_id,
-                    messages.clone(),
-                    agent_tx,
-                );
+            if let (Ok(conversation_id), Some((agent, manager, agent_tx))) = (saved, summary_dependencies) {
+                // The thread carries the id, so it works even if a new chat starts meanwhile
+                Self::spawn_summary_generation_thread(agent, manager, chunks, conversation_id, messages, agent_tx);
             }
-        }
-
-        // Load history data (this might take a moment if many conversations)
-        let _ = self.ensure_storage();
+        });
+        self.history_pending_select = self.current_conversation_id.clone();
         self.load_history_list();
-        if let Some(conversation_id) = self.current_conversation_id.clone() {
-            self.select_history_conversation(&conversation_id);
-        }
-        
+
         Ok(())
     }
 }
@@ -26,6 +26,18 @@ impl App {
         self.selected_index = 0;
     }
 
+    /// Attributes the next user messages to `name`; an empty name switches back to "You"
+    pub fn set_speaker(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.speaker = None;
+            self.show_status_toast("SPEAKING AS YOU");
+        } else {
+            self.show_status_toast(format!("SPEAKING AS {}", name.to_uppercase()));
+            self.speaker = Some(name.to_string());
+        }
+    }
+
     pub fn add_input_char(&mut self, character: char) {
         self.input.push(character);
         self.selected_index = 0;
@@ -18,6 +18,9 @@ impl App {
             self.connect_venice_key = config.venice.api_key.clone();
             self.connect_gab_key = config.gab.api_key.clone();
             self.connect_brave_key = config.brave.api_key.clone();
+            self.connect_github_key = config.github.api_key.clone();
+            self.connect_home_assistant_url = config.homeassistant.url.clone();
+            self.connect_home_assistant_token = config.homeassistant.api_key.clone();
             self.connect_obsidian_vault = config.obsidian.vault_name.clone();
         }
     }
@@ -44,6 +47,15 @@ impl App {
                     self.connect_api_key_input
                         .set_content(self.connect_brave_key.clone());
                 }
+                "GitHub" => {
+                    self.connect_api_key_input
+                        .set_content(self.connect_github_key.clone());
+                }
+                "Home Assistant" => {
+                    self.connect_api_key_input
+                        .set_content(self.connect_home_assistant_url.clone());
+                    self.connect_entering_url = true;
+                }
                 "Obsidian" => {
                     self.connect_api_key_input
                         .set_content(self.connect_obsidian_vault.clone());
@@ -61,6 +73,7 @@ impl App {
         self.mode = AppMode::Connect;
         self.connect_api_key_input.clear();
         self.connect_current_provider = None;
+        self.connect_entering_url = false;
     }
 
     pub fn save_api_key(&mut self) -> Result<()> {
@@ -69,11 +82,7 @@ impl App {
             match provider.as_str() {
                 "ElevenLabs" => {
                     self.connect_elevenlabs_key = self.connect_api_key_input.content().to_string();
-                    if let Ok(mut config) = Config::load() {
-                        config.elevenlabs.api_key = self.connect_elevenlabs_key.clone();
-                        let _ = config.save();
-                    }
-                    did_save = true;
+                    did_save = self.store_api_key("elevenlabs", &self.connect_elevenlabs_key.clone());
 
                     // Update TTS service if configured
                     if let Some(tts) = &mut self.tts_service {
@@ -88,15 +97,11 @@ impl App {
                     let candidate_key = self.connect_api_key_input.content().to_string();
                     if crate::agents::venice::fetch_text_models(&candidate_key).is_ok() {
                         self.connect_venice_key = candidate_key;
-                        if let Ok(mut config) = Config::load() {
-                            config.venice.api_key = self.connect_venice_key.clone();
-                            let _ = config.save();
-                        }
+                        did_save = self.store_api_key("venice", &self.connect_venice_key.clone());
                         let _ = self.refresh_available_models();
                         if let Some(manager) = &mut self.agent_manager {
                             manager.set_venice_api_key(self.connect_venice_key.clone());
                         }
-                        did_save = true;
                     } else {
                         self.chat_history.push(ChatMessage::system(
                             "Venice API key invalid or models unavailable",
@@ -105,23 +110,46 @@ impl App {
                 }
                 "Gab AI" => {
                     self.connect_gab_key = self.connect_api_key_input.content().to_string();
-                    if let Ok(mut config) = Config::load() {
-                        config.gab.api_key = self.connect_gab_key.clone();
-                        let _ = config.save();
-                    }
+                    did_save = self.store_api_key("gab", &self.connect_gab_key.clone());
                     if let Some(manager) = &mut self.agent_manager {
                         manager.set_gab_api_key(self.connect_gab_key.clone());
                     }
                     let _ = self.refresh_available_models();
-                    did_save = true;
                 }
                 "Brave Search" => {
                     self.connect_brave_key = self.connect_api_key_input.content().to_string();
-                    if let Ok(mut config) = Config::load() {
-                        config.brave.api_key = self.connect_brave_key.clone();
-                        let _ = config.save();
+                    did_save = self.store_api_key("brave", &self.connect_brave_key.clone());
+                }
+                "GitHub" => {
+                    self.connect_github_key = self.connect_api_key_input.content().trim().to_string();
+                    did_save = self.store_api_key("github", &self.connect_github_key.clone());
+                }
+                "Home Assistant" if self.connect_entering_url => {
+                    let candidate_url = self.connect_api_key_input.content().trim().to_string();
+                    if !candidate_url.starts_with("http://") && !candidate_url.starts_with("https://") {
+                        self.chat_history.push(ChatMessage::system(
+                            "Home Assistant URL must start with http:// or https://",
+                        ));
+                    } else {
+                        self.connect_home_assistant_url = candidate_url;
+                        if let Ok(mut config) = Config::load() {
+                            config.homeassistant.url = self.connect_home_assistant_url.clone();
+                            let _ = self.save_config(&config);
+                        }
+                        // Stay in the input for the access token
+                        self.connect_entering_url = false;
+                        self.connect_api_key_input
+                            .set_content(self.connect_home_assistant_token.clone());
+                        return Ok(());
                     }
-                    did_save = true;
+                }
+                "Home Assistant" => {
+                    self.connect_home_assistant_token =
+                        self.connect_api_key_input.content().trim().to_string();
+                    did_save = self.store_api_key(
+                        "homeassistant",
+                        &self.connect_home_assistant_token.clone(),
+                    );
                 }
                 "Obsidian" => {
                     let candidate_name = self.connect_api_key_input.content().to_string();
@@ -133,7 +161,7 @@ impl App {
                         self.connect_obsidian_vault = candidate_name;
                         if let Ok(mut config) = Config::load() {
                             config.obsidian.vault_name = self.connect_obsidian_vault.clone();
-                            let _ = config.save();
+                            let _ = self.save_config(&config);
                         }
                         did_save = true;
                     }
@@ -150,14 +178,53 @@ impl App {
         self.mode = AppMode::Chat;
         self.connect_api_key_input.clear();
         self.connect_current_provider = None;
+        self.connect_entering_url = false;
         Ok(())
     }
 
+    /// Writes an API key through the secrets backend (OS keyring or encrypted file)
+    fn store_api_key(&mut self, name: &str, value: &str) -> bool {
+        match crate::services::secrets::set_secret(name, value) {
+            Ok(()) => {
+                // Stored secrets are part of the loaded config; keep the hot-reload baseline current
+                self.loaded_config = Config::load().ok();
+                true
+            }
+            Err(error) => {
+                tracing::error!(%error, key = name, "storing API key failed");
+                self.chat_history.push(ChatMessage::system(format!(
+                    "Key kept for this session only: {}",
+                    error
+                )));
+                false
+            }
+        }
+    }
+
+    /// Whether a `/connect` provider has what it needs (key, URL or vault)
+    #[must_use]
+    pub fn is_provider_configured(&self, provider: &str) -> bool {
+        match provider {
+            "ElevenLabs" => !self.connect_elevenlabs_key.is_empty(),
+            "Venice AI" => !self.connect_venice_key.is_empty(),
+            "Gab AI" => !self.connect_gab_key.is_empty(),
+            "Brave Search" => !self.connect_brave_key.is_empty(),
+            "GitHub" => !self.connect_github_key.is_empty(),
+            "Home Assistant" => {
+                !self.connect_home_assistant_url.trim().is_empty()
+                    && !self.connect_home_assistant_token.is_empty()
+            }
+            "Obsidian" => !self.connect_obsidian_vault.trim().is_empty(),
+            _ => false,
+        }
+    }
+
     pub fn close_connect(&mut self) {
         self.mode = AppMode::Chat;
         self.connect_selected_provider = 0;
         self.connect_api_key_input.clear();
         self.connect_current_provider = None;
+        self.connect_entering_url = false;
     }
 
     pub fn add_api_key_char(&mut self, character: char) {
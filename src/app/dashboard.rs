@@ -0,0 +1,39 @@
+use crate::app::{App, AppMode};
+use crate::services::dashboard::{self, DashboardData};
+use color_eyre::Result;
+
+/// Weeks of history charted in `/dashboard`
+const DASHBOARD_WEEKS: usize = 12;
+/// Rows in the top topics and most recalled lists
+const DASHBOARD_TOP_LIMIT: usize = 8;
+
+impl App {
+    pub fn open_dashboard(&mut self) -> Result<()> {
+        self.ensure_storage();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        let activity = runtime.block_on(storage.load_conversation_activity())?;
+        let mut top_topics = runtime.block_on(storage.load_frequent_topics(1))?;
+        top_topics.truncate(DASHBOARD_TOP_LIMIT);
+        let top_retrievals = runtime.block_on(storage.load_top_retrievals(DASHBOARD_TOP_LIMIT))?;
+        let database_bytes = crate::profile::data_dir()
+            .map_or(0, |dir| dashboard::directory_size(&dir.join("kimi.db")));
+
+        let today = chrono::Local::now().date_naive();
+        self.dashboard = Some(DashboardData {
+            weeks: dashboard::weekly_buckets(&activity, today, DASHBOARD_WEEKS),
+            conversations: activity.len(),
+            messages: activity.iter().map(|conversation| conversation.messages).sum(),
+            embedded: activity.iter().map(|conversation| conversation.embedded).sum(),
+            top_topics,
+            top_retrievals,
+            database_bytes,
+        });
+        self.mode = AppMode::Dashboard;
+        Ok(())
+    }
+
+    pub fn close_dashboard(&mut self) {
+        self.dashboard = None;
+        self.mode = AppMode::Chat;
+    }
+}
@@ -0,0 +1,120 @@
+use crate::app::{App, AppMode};
+use crate::services::{diary, identity, sentiment};
+use color_eyre::Result;
+
+/// Days listed in `/diary`
+const DIARY_LIMIT: usize = 365;
+
+impl App {
+    pub fn open_diary(&mut self) -> Result<()> {
+        self.mode = AppMode::Diary;
+        self.diary_selected_index = 0;
+        self.load_diary()
+    }
+
+    pub fn close_diary(&mut self) {
+        self.mode = AppMode::Chat;
+        self.diary_entries.clear();
+    }
+
+    fn load_diary(&mut self) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.diary_entries = runtime.block_on(storage.load_diary_entries(DIARY_LIMIT))?;
+        self.diary_selected_index = self
+            .diary_selected_index
+            .min(self.diary_entries.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Older day
+    pub fn next_diary_entry(&mut self) {
+        if self.diary_selected_index + 1 < self.diary_entries.len() {
+            self.diary_selected_index += 1;
+        }
+    }
+
+    /// Newer day
+    pub fn previous_diary_entry(&mut self) {
+        self.diary_selected_index = self.diary_selected_index.saturating_sub(1);
+    }
+
+    /// Writes the selected day into the Obsidian vault, whether or not export is on
+    pub fn export_selected_diary_entry(&mut self) {
+        let Some(entry) = self.diary_entries.get(self.diary_selected_index) else {
+            return;
+        };
+        let Ok(date) = chrono::NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") else {
+            return;
+        };
+        match diary::export_to_obsidian(&self.connect_obsidian_vault_path, &self.diary, date, &entry.content) {
+            Ok(path) => {
+                let name = path
+                    .file_name()
+                    .map_or_else(String::new, |name| name.to_string_lossy().to_string());
+                self.show_status_toast(format!("EXPORTED TO {}", name.to_uppercase()));
+            }
+            Err(error) => self.show_status_toast(format!("EXPORT FAILED: {}", error)),
+        }
+    }
+
+    pub fn delete_selected_diary_entry(&mut self) -> Result<()> {
+        let Some(entry) = self.diary_entries.get(self.diary_selected_index) else {
+            return Ok(());
+        };
+        let date = entry.date.clone();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.delete_diary_entry(&date))?;
+        self.load_diary()
+    }
+
+    /// Has Kimi fold a freshly saved conversation into today's diary entry
+    pub(crate) fn maybe_spawn_diary_entry(&self, summary: &str) {
+        if !self.diary.enabled || summary.trim().is_empty() {
+            return;
+        }
+        let Ok((agent, manager, _)) = self.get_agent_chat_dependencies() else {
+            return;
+        };
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let summary = summary.to_string();
+        let config = self.diary.clone();
+        let vault_path = self.connect_obsidian_vault_path.clone();
+
+        std::thread::spawn(move || {
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            let date = chrono::Local::now().date_naive();
+            let day = date.format("%Y-%m-%d").to_string();
+            let input = diary::DiaryInput {
+                date,
+                previous_entry: runtime.block_on(storage.load_diary_entry(&day)).ok().flatten(),
+                conversation_summary: summary,
+                emotions: identity::read_identity_state()
+                    .map(|state| sentiment::identity_emotions(&state))
+                    .unwrap_or_default(),
+            };
+            // Kimi's own voice first; the summary model only when the chat model is down
+            let summary_agent = manager.summary_agent();
+            let writers: Vec<&crate::agents::Agent> =
+                std::iter::once(&agent).chain(summary_agent.iter()).collect();
+            let Some(entry) = diary::write_entry(&input, &writers, &manager) else {
+                return;
+            };
+            if let Err(error) = runtime.block_on(storage.save_diary_entry(&day, &entry)) {
+                tracing::warn!(%error, "could not save diary entry");
+                return;
+            }
+            if config.export_to_obsidian
+                && let Err(error) = diary::export_to_obsidian(&vault_path, &config, date, &entry)
+            {
+                tracing::warn!(%error, "could not export diary entry");
+            }
+        });
+    }
+}
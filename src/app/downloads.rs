@@ -0,0 +1,206 @@
+use crate::app::types::DownloadItem;
+use crate::app::{AgentEvent, App, AppMode};
+use crate::services::link_download::{DownloadOutcome, DownloadStatus, DownloadUpdate};
+use crate::services::notifications::NotificationKind;
+use color_eyre::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Number of finished downloads listed in the panel
+const DOWNLOAD_HISTORY_LIMIT: usize = 50;
+
+impl App {
+    pub fn open_downloads(&mut self) -> Result<()> {
+        self.mode = AppMode::Downloads;
+        self.download_selected_index = 0;
+        self.load_download_history()
+    }
+
+    pub fn close_downloads(&mut self) {
+        self.mode = AppMode::Chat;
+        self.download_history.clear();
+    }
+
+    fn load_download_history(&mut self) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.download_history =
+            runtime.block_on(storage.load_download_records(DOWNLOAD_HISTORY_LIMIT))?;
+        self.clamp_download_selection();
+        Ok(())
+    }
+
+    /// Active downloads come first in the panel, then the history
+    fn download_entry_count(&self) -> usize {
+        self.active_downloads.len() + self.download_history.len()
+    }
+
+    fn clamp_download_selection(&mut self) {
+        self.download_selected_index = self
+            .download_selected_index
+            .min(self.download_entry_count().saturating_sub(1));
+    }
+
+    pub fn next_download(&mut self) {
+        let count = self.download_entry_count();
+        if count > 0 {
+            self.download_selected_index = (self.download_selected_index + 1) % count;
+        }
+    }
+
+    pub fn previous_download(&mut self) {
+        let count = self.download_entry_count();
+        if count > 0 {
+            self.download_selected_index = self
+                .download_selected_index
+                .checked_sub(1)
+                .unwrap_or(count.saturating_sub(1));
+        }
+    }
+
+    /// Starts a yt-dlp download in the background and lists it as active
+    pub(crate) fn start_download(&mut self, url: String) {
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.active_downloads.push(DownloadItem {
+            url: url.clone(),
+            progress: None,
+            speed: None,
+            destination: None,
+            cancel: Arc::clone(&cancel),
+            frame: 0,
+            last_tick: None,
+        });
+
+        let tx = self.agent_tx.clone();
+        std::thread::spawn(move || {
+            let result = crate::services::link_download::download_video_with_progress(
+                &url,
+                &cancel,
+                |update| {
+                    if let Some(tx) = &tx {
+                        let _ = tx.send(AgentEvent::DownloadProgress {
+                            url: url.clone(),
+                            update,
+                        });
+                    }
+                },
+            );
+            let (status, destination, error) = match result {
+                Ok(DownloadOutcome::Completed(destination)) => {
+                    (DownloadStatus::Completed, destination, None)
+                }
+                Ok(DownloadOutcome::Cancelled) => (DownloadStatus::Cancelled, None, None),
+                Err(error) => (DownloadStatus::Failed, None, Some(error.to_string())),
+            };
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::DownloadFinished {
+                    url,
+                    status,
+                    destination,
+                    error,
+                });
+            }
+        });
+    }
+
+    pub(crate) fn apply_download_update(&mut self, url: &str, update: DownloadUpdate) {
+        let Some(item) = self.active_downloads.iter_mut().find(|item| item.url == url) else {
+            return;
+        };
+        match update {
+            DownloadUpdate::Progress { percent, speed } => {
+                item.progress = Some(percent);
+                if speed.is_some() {
+                    item.speed = speed;
+                }
+            }
+            DownloadUpdate::Destination(path) => item.destination = Some(path),
+        }
+    }
+
+    /// Moves a finished download from the active list into the stored history
+    pub(crate) fn handle_download_finished(
+        &mut self,
+        url: &str,
+        status: DownloadStatus,
+        destination: Option<PathBuf>,
+        error: Option<String>,
+    ) {
+        let last_destination = self
+            .active_downloads
+            .iter()
+            .find(|item| item.url == url)
+            .and_then(|item| item.destination.clone());
+        self.active_downloads.retain(|item| item.url != url);
+        let destination = destination.or(last_destination);
+
+        match (status, &error) {
+            (DownloadStatus::Failed, Some(error)) => {
+                self.add_system_message(&format!("Download failed for {}: {}", url, error));
+            }
+            (DownloadStatus::Cancelled, _) => self.show_status_toast("DOWNLOAD CANCELLED"),
+            _ => {}
+        }
+        let title = match status {
+            DownloadStatus::Completed => "Download finished",
+            DownloadStatus::Failed => "Download failed",
+            DownloadStatus::Cancelled => "Download cancelled",
+        };
+        self.publish_notification(NotificationKind::Download, title, url);
+
+        let destination_text = destination.map(|path| path.display().to_string());
+        let saved = self.ensure_storage()
+            && self.storage_with_runtime().is_ok_and(|(storage, runtime)| {
+                runtime
+                    .block_on(storage.save_download_record(
+                        url,
+                        status,
+                        destination_text.as_deref(),
+                        error.as_deref(),
+                    ))
+                    .is_ok()
+            });
+        if !saved {
+            tracing::warn!(url, "could not record finished download");
+        }
+        if self.mode == AppMode::Downloads {
+            let _ = self.load_download_history();
+        }
+    }
+
+    /// Asks the selected active download to stop; it moves to the history when yt-dlp exits
+    pub fn cancel_selected_download(&mut self) {
+        match self.active_downloads.get(self.download_selected_index) {
+            Some(item) => {
+                item.cancel.store(true, Ordering::Relaxed);
+                self.show_status_toast("CANCELLING");
+            }
+            None => self.show_status_toast("NOT AN ACTIVE DOWNLOAD"),
+        }
+    }
+
+    /// Starts the selected failed or cancelled download again
+    pub fn retry_selected_download(&mut self) -> Result<()> {
+        let history_index = self
+            .download_selected_index
+            .checked_sub(self.active_downloads.len());
+        let Some(record) = history_index.and_then(|index| self.download_history.get(index)) else {
+            self.show_status_toast("NOT A FINISHED DOWNLOAD");
+            return Ok(());
+        };
+        if record.status == DownloadStatus::Completed {
+            self.show_status_toast("ALREADY DOWNLOADED");
+            return Ok(());
+        }
+        let (id, url) = (record.id.clone(), record.url.clone());
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.delete_download_record(&id))?;
+        self.start_download(url);
+        self.download_selected_index = self.active_downloads.len().saturating_sub(1);
+        self.show_status_toast("RETRYING");
+        self.load_download_history()
+    }
+}
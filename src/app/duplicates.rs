@@ -0,0 +1,118 @@
+use crate::app::types::ChatMessage;
+use crate::app::{AgentEvent, App, AppMode, DuplicateOffer};
+use crate::services::duplicates;
+use crate::storage::AnsweredQuestion;
+use color_eyre::Result;
+
+impl App {
+    /// Before a model call, looks on the storage worker for the same question in a past
+    /// conversation; the message is held back until `EarlierAnswerChecked` arrives.
+    /// Returns true when the message was held back
+    pub(crate) fn offer_earlier_answer(&mut self, question: &str) -> bool {
+        if self.duplicate_check_pending.is_some() {
+            return true;
+        }
+        if std::mem::take(&mut self.skip_duplicate_check)
+            || self.headless
+            || self.duplicate_question_similarity <= 0.0
+            || !self.chat_attachments.is_empty()
+            || !duplicates::is_worth_checking(question)
+            || !self.ensure_storage()
+        {
+            return false;
+        }
+        let Some(agent_tx) = self.agent_tx.clone() else {
+            return false;
+        };
+        let exclude = self.current_conversation_id.clone();
+        let min_similarity = self.duplicate_question_similarity;
+        let pending = question.to_string();
+        self.spawn_storage_job(move |storage, runtime| {
+            let found = runtime.block_on(async {
+                let embedding = crate::services::embeddings::generate_embedding(&pending).await?;
+                storage
+                    .find_answered_question(embedding, exclude.as_deref(), min_similarity)
+                    .await
+            });
+            let earlier = found
+                .inspect_err(|error| tracing::warn!(%error, "repeat question check failed"))
+                .ok()
+                .flatten();
+            let _ = agent_tx.send(AgentEvent::EarlierAnswerChecked {
+                question: pending,
+                earlier,
+            });
+        });
+        // The worker could not start: the job was dropped, send the message as usual
+        if self.storage_worker.is_none() {
+            return false;
+        }
+        self.duplicate_check_pending = Some(question.to_string());
+        self.is_loading = true;
+        self.current_activity = Some("checking past answers".to_string());
+        true
+    }
+
+    /// Shows the offer when the question was answered before, otherwise sends the held-back message
+    pub(crate) fn handle_earlier_answer_checked(&mut self, question: &str, earlier: Option<AnsweredQuestion>) {
+        if self.duplicate_check_pending.as_deref() != Some(question) {
+            return;
+        }
+        self.duplicate_check_pending = None;
+        self.is_loading = false;
+        self.current_activity = None;
+        match earlier {
+            Some(earlier) => {
+                tracing::info!(
+                    similarity = earlier.similarity,
+                    conversation = %earlier.conversation_id,
+                    "question was answered before"
+                );
+                self.duplicate_offer = Some(DuplicateOffer {
+                    question: question.to_string(),
+                    earlier,
+                });
+                self.mode = AppMode::DuplicateQuestion;
+            }
+            None => {
+                self.skip_duplicate_check = true;
+                if let Err(error) = self.send_chat_message() {
+                    self.skip_duplicate_check = false;
+                    self.chat_history.push(ChatMessage::system(format!("Error: {}", error)));
+                }
+            }
+        }
+    }
+
+    /// Accepts the offer: the earlier answer becomes the reply, without a model call
+    pub fn reuse_earlier_answer(&mut self) {
+        let Some(offer) = self.duplicate_offer.take() else {
+            return;
+        };
+        self.mode = AppMode::Chat;
+        self.chat_input.clear();
+        self.reset_chat_scroll();
+        self.add_user_message_to_history(&offer.question);
+        self.add_assistant_message(&offer.earlier.answer);
+        self.show_status_toast("REUSED EARLIER ANSWER");
+        if let Err(error) = self.autosave_conversation(false) {
+            tracing::error!(%error, "history save failed");
+        }
+    }
+
+    /// Declines the offer and sends the message to the model as usual
+    pub fn ask_again(&mut self) -> Result<()> {
+        if self.duplicate_offer.take().is_none() {
+            return Ok(());
+        }
+        self.mode = AppMode::Chat;
+        self.skip_duplicate_check = true;
+        self.send_chat_message()
+    }
+
+    /// Esc: back to editing the message
+    pub fn dismiss_earlier_answer(&mut self) {
+        self.duplicate_offer = None;
+        self.mode = AppMode::Chat;
+    }
+}
@@ -0,0 +1,81 @@
+use crate::app::{App, AppMode, EditorSession};
+use crate::services::editor::{self, TextBuffer};
+use std::path::PathBuf;
+
+impl App {
+    /// Opens `path` in the external editor from `[editor]`, or in the built-in one over SSH,
+    /// without a display, or when no terminal emulator starts
+    pub fn edit_file(&mut self, path: PathBuf, title: &str) {
+        if !editor::prefers_internal_editor(&self.editor) {
+            match editor::open_in_new_terminal(&path, &self.editor) {
+                Ok(()) => return,
+                Err(error) => tracing::info!(%error, "external editor unavailable, using the built-in one"),
+            }
+        }
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) => {
+                self.add_system_message(&format!("Could not open {}: {}", path.display(), error));
+                return;
+            }
+        };
+        self.editor_session = Some(EditorSession {
+            path,
+            title: title.to_string(),
+            buffer: TextBuffer::new(&text),
+            scroll: 0,
+            dirty: false,
+            confirm_discard: false,
+            return_mode: self.mode.clone(),
+        });
+        self.mode = AppMode::Editor;
+    }
+
+    /// Ctrl+S: writes the buffer back; the personality watcher picks up the change
+    pub fn save_editor(&mut self) {
+        let Some(session) = self.editor_session.as_mut() else {
+            return;
+        };
+        match std::fs::write(&session.path, session.buffer.text()) {
+            Ok(()) => {
+                session.dirty = false;
+                session.confirm_discard = false;
+                self.show_status_toast("SAVED");
+            }
+            Err(error) => self.show_status_toast(&format!("SAVE FAILED: {}", error)),
+        }
+    }
+
+    /// Esc: leaves the editor; unsaved changes need a second Esc
+    pub fn close_editor(&mut self) {
+        let Some(session) = self.editor_session.as_mut() else {
+            self.mode = AppMode::Chat;
+            return;
+        };
+        if session.dirty && !session.confirm_discard {
+            session.confirm_discard = true;
+            self.show_status_toast("UNSAVED CHANGES · CTRL+S SAVES, ESC AGAIN DISCARDS");
+            return;
+        }
+        let return_mode = session.return_mode.clone();
+        self.editor_session = None;
+        self.mode = return_mode;
+    }
+
+    /// Applies an edit to the buffer and keeps the cursor inside the `visible_lines` window
+    pub fn edit_buffer(&mut self, visible_lines: usize, edit: impl FnOnce(&mut TextBuffer) -> bool) {
+        let Some(session) = self.editor_session.as_mut() else {
+            return;
+        };
+        if edit(&mut session.buffer) {
+            session.dirty = true;
+            session.confirm_discard = false;
+        }
+        let (row, _) = session.buffer.cursor();
+        if row < session.scroll {
+            session.scroll = row;
+        } else if visible_lines > 0 && row >= session.scroll + visible_lines {
+            session.scroll = row + 1 - visible_lines;
+        }
+    }
+}
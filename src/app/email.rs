@@ -0,0 +1,57 @@
+use crate::app::{AgentEvent, App, AppMode};
+use crate::services::email::EmailDraft;
+use crate::services::notifications::NotificationKind;
+
+impl App {
+    /// Shows a drafted email for review; it is only sent from the preview
+    pub fn open_email_preview(&mut self, draft: EmailDraft) {
+        self.email_draft = Some(draft);
+        self.email_preview_scroll = 0;
+        self.mode = AppMode::EmailPreview;
+    }
+
+    pub fn discard_email_draft(&mut self) {
+        self.email_draft = None;
+        self.mode = AppMode::Chat;
+        self.show_status_toast("DRAFT DISCARDED");
+    }
+
+    /// Hands the confirmed draft to the configured email command in the background
+    pub fn send_email_draft(&mut self) {
+        let Some(draft) = self.email_draft.take() else {
+            return;
+        };
+        self.mode = AppMode::Chat;
+        let command = self.email_command.clone();
+        let tx = self.agent_tx.clone();
+        self.show_status_toast("SENDING EMAIL");
+        std::thread::spawn(move || {
+            let result = crate::services::email::send_email(&command, &draft)
+                .map_err(|error| error.to_string());
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::EmailSent {
+                    to: draft.to,
+                    result,
+                });
+            }
+        });
+    }
+
+    pub(crate) fn handle_email_sent(&mut self, to: &str, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                self.add_system_message(&format!("Email to {} handed to the email command", to));
+                self.publish_notification(NotificationKind::Job, "Email sent", to);
+            }
+            Err(error) => self.add_system_message(&format!("Email to {} failed: {}", to, error)),
+        }
+    }
+
+    pub fn scroll_email_preview_up(&mut self) {
+        self.email_preview_scroll = self.email_preview_scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_email_preview_down(&mut self) {
+        self.email_preview_scroll = self.email_preview_scroll.saturating_add(1);
+    }
+}
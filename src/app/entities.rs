@@ -0,0 +1,130 @@
+use crate::app::{App, AppMode};
+use crate::services::entities::{self, Entity, EntityAction, EntityKind};
+
+/// Longest excerpt of the message stored as a reminder's text
+const REMINDER_MAX_CHARS: usize = 160;
+
+impl App {
+    /// `e` in message selection: lists the URLs, paths and dates of the selected message
+    /// with what can be done to each
+    pub fn open_entity_actions(&mut self) {
+        let Some(message) = self.chat_history.get(self.selected_message_index) else {
+            return;
+        };
+        let today = chrono::Local::now().date_naive();
+        self.entity_actions = entities::find_entities(&message.content)
+            .into_iter()
+            .flat_map(|entity| {
+                entities::actions_for(&entity, today)
+                    .into_iter()
+                    .map(move |action| (entity.clone(), action))
+            })
+            .collect();
+        if self.entity_actions.is_empty() {
+            self.show_status_toast("NO LINKS, PATHS OR DATES");
+            return;
+        }
+        self.entity_action_index = 0;
+        self.mode = AppMode::EntityActions;
+    }
+
+    pub fn close_entity_actions(&mut self) {
+        self.entity_actions.clear();
+        self.mode = AppMode::Chat;
+    }
+
+    pub fn previous_entity_action(&mut self) {
+        self.entity_action_index = self.entity_action_index.saturating_sub(1);
+    }
+
+    pub fn next_entity_action(&mut self) {
+        if self.entity_action_index + 1 < self.entity_actions.len() {
+            self.entity_action_index += 1;
+        }
+    }
+
+    pub fn run_selected_entity_action(&mut self) {
+        let Some((entity, action)) = self.entity_actions.get(self.entity_action_index).cloned() else {
+            return;
+        };
+        self.close_entity_actions();
+        match action {
+            EntityAction::Open => self.open_entity(&entity),
+            EntityAction::Download => self.start_download(entity.text),
+            EntityAction::Convert => {
+                // Leaves the cursor where the target format goes
+                self.close_message_selection();
+                self.chat_input.set_content(format!("convert  {}", entity.text));
+                self.chat_input.move_to_start();
+                for _ in 0.."convert ".len() {
+                    self.chat_input.move_right();
+                }
+            }
+            EntityAction::Remind => self.add_entity_reminder(&entity),
+            EntityAction::Copy => {
+                if self.clipboard_service.copy_text(&entity.text).is_ok() {
+                    self.show_status_toast("COPIED");
+                } else {
+                    self.show_status_toast("COPY FAILED");
+                }
+            }
+        }
+    }
+
+    fn open_entity(&mut self, entity: &Entity) {
+        let opened = match entity.kind {
+            EntityKind::Path => crate::services::links::open_path(&crate::platform::expand_home(&entity.text)),
+            EntityKind::Url | EntityKind::Date(_) => crate::services::links::open_url(&entity.text),
+        };
+        match opened {
+            Ok(()) => self.show_status_toast("OPENING"),
+            Err(error) => {
+                tracing::warn!(%error, target = %entity.text, "could not open entity");
+                self.show_status_toast("OPEN FAILED");
+            }
+        }
+    }
+
+    /// Stores a reminder for the date; the notification watcher publishes it on that day
+    fn add_entity_reminder(&mut self, entity: &Entity) {
+        let EntityKind::Date(date) = entity.kind else {
+            return;
+        };
+        let Some(message) = self.chat_history.get(self.selected_message_index) else {
+            return;
+        };
+        let text = reminder_text(&message.content, entity);
+        let due = date.format("%Y-%m-%d").to_string();
+        let tx = self.agent_tx.clone();
+        self.spawn_storage_job(move |storage, runtime| {
+            if let Err(error) = runtime.block_on(storage.add_reminder(&due, &text)) {
+                tracing::warn!(%error, "could not save reminder");
+                if let Some(tx) = tx {
+                    let _ = tx.send(crate::app::AgentEvent::SystemMessage(format!(
+                        "Could not save reminder: {}",
+                        error
+                    )));
+                }
+            }
+        });
+        self.show_status_toast(format!("REMINDER SET FOR {}", date.format("%b %-d").to_string().to_uppercase()));
+    }
+}
+
+/// The line of `content` that mentions the entity, shortened to fit a notification
+fn reminder_text(content: &str, entity: &Entity) -> String {
+    let mut line_start = 0;
+    for line in content.split_inclusive('\n') {
+        let line_chars = line.chars().count();
+        if entity.range.start < line_start + line_chars {
+            let line = line.trim();
+            if line.chars().count() <= REMINDER_MAX_CHARS {
+                return line.to_string();
+            }
+            let truncated: String = line.chars().take(REMINDER_MAX_CHARS).collect();
+            return format!("{}…", truncated.trim_end());
+        }
+        line_start += line_chars;
+    }
+    entity.text.clone()
+}
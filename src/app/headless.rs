@@ -0,0 +1,102 @@
+use crate::app::App;
+use crate::app::types::{ChatMessage, ContextUsage, MessageRole};
+use crate::services::stdin::PipedInput;
+use color_eyre::{Result, eyre::eyre};
+use std::time::{Duration, Instant};
+
+const RESPONSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Longest a headless answer may take (tool calls and web search included)
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Result of a one-shot `kimi ask`
+pub struct HeadlessAnswer {
+    pub answer: String,
+    pub agent: String,
+    pub model: String,
+    pub context_usage: Option<ContextUsage>,
+    pub elapsed: Duration,
+}
+
+impl HeadlessAnswer {
+    /// Structured output for `kimi ask --json`
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let context_usage = self.context_usage.as_ref().map(|usage| {
+            serde_json::json!({
+                "notes_used": usage.notes_used,
+                "history_used": usage.history_used,
+                "memories_used": usage.memories_used,
+                "previous_session_used": usage.previous_session_used,
+                "skipped": usage.skipped.iter().map(|section| section.label()).collect::<Vec<_>>(),
+            })
+        });
+        serde_json::json!({
+            "answer": self.answer,
+            "agent": self.agent,
+            "model": self.model,
+            "context_usage": context_usage,
+            "elapsed_ms": u64::try_from(self.elapsed.as_millis()).unwrap_or(u64::MAX),
+        })
+    }
+}
+
+impl App {
+    /// Starts a fresh, unsaved conversation seeded with earlier turns (used by `kimi serve`)
+    pub fn reset_headless_session(&mut self, history: Vec<ChatMessage>) {
+        self.close_history();
+        self.chat_attachments.clear();
+        self.chat_history = history;
+    }
+
+    /// Sends one message through the normal chat pipeline (retrieval, tools, search)
+    /// and blocks until the answer arrives. Nothing is saved to History.
+    pub fn ask_headless(
+        &mut self,
+        question: &str,
+        piped: Option<PipedInput>,
+    ) -> Result<HeadlessAnswer> {
+        self.headless = true;
+        let started = Instant::now();
+        let history_len = self.chat_history.len();
+
+        self.chat_input.set_content(question.to_string());
+        if let Some(input) = piped {
+            self.attach_piped_input(input);
+        }
+        self.send_chat_message()?;
+        while self.is_loading {
+            if started.elapsed() > ANSWER_TIMEOUT {
+                return Err(eyre!("No answer within {} seconds", ANSWER_TIMEOUT.as_secs()));
+            }
+            std::thread::sleep(RESPONSE_POLL_INTERVAL);
+            self.check_agent_response();
+        }
+
+        let reply = self
+            .chat_history
+            .get(history_len..)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .find(|message| message.role != MessageRole::User)
+            .ok_or_else(|| eyre!("No answer was produced"))?;
+        if reply.role == MessageRole::System
+            && let Some(error) = reply.content.strip_prefix("Error: ")
+        {
+            return Err(eyre!("{}", error));
+        }
+
+        let (agent, model) = self
+            .current_agent
+            .as_ref()
+            .map(|agent| (agent.name.clone(), agent.model.clone()))
+            .unwrap_or_default();
+        Ok(HeadlessAnswer {
+            answer: reply.content.clone(),
+            agent,
+            model,
+            context_usage: reply.context_usage.clone(),
+            elapsed: started.elapsed(),
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use crate::app::{AgentEvent, App, AppMode};
+use crate::config::Config;
+use crate::services::health::{self, HealthTargets, ServiceHealth};
+use std::time::{Duration, Instant};
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Config is re-read on every check so keys saved in /connect are picked up
+fn current_targets() -> HealthTargets {
+    Config::load()
+        .map(|config| HealthTargets::from_config(&config))
+        .unwrap_or_default()
+}
+
+impl App {
+    /// Pings the model backends in the background every minute for the footer dots
+    pub fn spawn_health_monitor(&mut self) {
+        let Some(tx) = self.agent_tx.clone() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            loop {
+                let results = health::check_all(&current_targets());
+                // The app has quit once the channel is closed
+                if tx.send(AgentEvent::Health(results)).is_err() {
+                    return;
+                }
+                std::thread::sleep(HEALTH_CHECK_INTERVAL);
+            }
+        });
+    }
+
+    pub fn open_status(&mut self) {
+        self.mode = AppMode::Status;
+        self.last_backup = crate::services::backup::backup_dir(&self.backup)
+            .ok()
+            .and_then(|directory| crate::services::backup::last_backup_time(&directory));
+        self.refresh_health();
+    }
+
+    pub fn close_status(&mut self) {
+        self.mode = AppMode::Chat;
+    }
+
+    /// Runs one check right away instead of waiting for the next interval
+    pub fn refresh_health(&mut self) {
+        if self.health_checking {
+            return;
+        }
+        let Some(tx) = self.agent_tx.clone() else {
+            return;
+        };
+        self.health_checking = true;
+        std::thread::spawn(move || {
+            let _ = tx.send(AgentEvent::Health(health::check_all(&current_targets())));
+        });
+    }
+
+    pub(crate) fn handle_health(&mut self, results: Vec<ServiceHealth>) {
+        self.health = results;
+        self.health_checked_at = Some(Instant::now());
+        self.health_checking = false;
+    }
+}
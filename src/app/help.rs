@@ -1,11 +1,199 @@
+use crate::app::types::{HelpEntry, HelpSection};
 use crate::app::{App, AppMode};
+use crate::services::fuzzy_score;
+
+/// Minimum fuzzy score for a help row to match the filter
+const HELP_MATCH_THRESHOLD: f64 = 0.3;
+
+/// Keyboard shortcuts handled in `main.rs`, by section. Keep in step with the key handlers
+pub const KEYMAP: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Global",
+        &[
+            ("Ctrl+C", "Quit"),
+            ("/", "Command menu (on an empty input)"),
+            ("Esc", "Back / close"),
+        ],
+    ),
+    (
+        "Chat",
+        &[
+            ("Enter", "Send the message"),
+            ("Tab", "Rotate agent"),
+            ("↑ ↓", "Scroll the conversation (on an empty input, or with Ctrl)"),
+            ("↑", "Pick a follow-up suggestion (on an empty input)"),
+            ("PgUp PgDn", "Scroll a page"),
+            ("Home End", "Jump to the top / bottom (on an empty input)"),
+            ("Ctrl+K", "Select a message (copy, speak, context, quote, translate, snippet, actions)"),
+            ("Ctrl+S", "Save code blocks as snippets"),
+            ("Ctrl+A", "Manage attachments"),
+            ("Ctrl+V", "Attach an image from the clipboard"),
+            ("Ctrl+P", "Toggle personality"),
+        ],
+    ),
+    (
+        "Speech",
+        &[
+            ("Ctrl+R", "Speak last response / stop speaking"),
+            ("Ctrl+O", "Pause / resume speech"),
+            ("Ctrl+N", "Skip to next queued message"),
+            ("Ctrl+X", "Stop all speech, alerts included"),
+            ("Ctrl+T", "Toggle auto-TTS"),
+            ("Media keys", "Play/pause, next and stop control speech"),
+        ],
+    ),
+    (
+        "In a message",
+        &[
+            ("!nomem", "Skip memories for this message"),
+            ("!nonotes", "Skip Obsidian notes"),
+            ("!nosearch", "Skip web search"),
+            ("!clean", "Skip all three"),
+        ],
+    ),
+];
 
 impl App {
     pub fn open_help(&mut self) {
+        self.help_filter.clear();
+        self.help_scroll = 0;
         self.mode = AppMode::Help;
     }
 
     pub fn close_help(&mut self) {
         self.mode = AppMode::Chat;
     }
+
+    /// Help content built from the keymap, the command registry and the providers,
+    /// narrowed to rows matching the filter
+    #[must_use]
+    pub fn help_sections(&self) -> Vec<HelpSection> {
+        let mut sections: Vec<HelpSection> = KEYMAP
+            .iter()
+            .map(|(title, keys)| HelpSection {
+                title: (*title).to_string(),
+                entries: keys
+                    .iter()
+                    .map(|(key, description)| help_entry(key, description))
+                    .collect(),
+            })
+            .collect();
+
+        sections.push(HelpSection {
+            title: "Commands".to_string(),
+            entries: self
+                .menu_items
+                .iter()
+                .map(|item| help_entry(&format!("/{}", item.name), &item.description))
+                .collect(),
+        });
+
+        let mut agents = Vec::new();
+        for agent in ["chat", "translate"] {
+            let model = self
+                .selected_models
+                .get(agent)
+                .and_then(|models| models.first())
+                .map_or("no model", String::as_str);
+            agents.push(help_entry(
+                &format!("/{}", agent),
+                &format!("Switch to the {} agent · {}", agent, model),
+            ));
+            agents.push(help_entry(
+                &format!("/{}:<model>", agent),
+                &format!("Use another installed model for {}", agent),
+            ));
+        }
+        sections.push(HelpSection {
+            title: "Agents".to_string(),
+            entries: agents,
+        });
+
+        sections.push(HelpSection {
+            title: "Providers".to_string(),
+            entries: self
+                .connect_providers
+                .iter()
+                .map(|provider| {
+                    let status = if self.is_provider_configured(provider) {
+                        "configured"
+                    } else {
+                        "not configured · set it up in /connect"
+                    };
+                    help_entry(provider, status)
+                })
+                .collect(),
+        });
+
+        let query = self.help_filter.content().trim();
+        if query.is_empty() {
+            return sections;
+        }
+        sections
+            .into_iter()
+            .filter_map(|mut section| {
+                section.entries.retain(|entry| {
+                    fuzzy_score(query, &entry.key)
+                        .max(fuzzy_score(query, &entry.description))
+                        .max(fuzzy_score(query, &section.title))
+                        >= HELP_MATCH_THRESHOLD
+                });
+                (!section.entries.is_empty()).then_some(section)
+            })
+            .collect()
+    }
+
+    /// Lines the help body takes: a title and a blank line per section, plus its rows
+    fn help_line_count(&self) -> usize {
+        self.help_sections()
+            .iter()
+            .map(|section| section.entries.len() + 2)
+            .sum()
+    }
+
+    pub fn scroll_help_down(&mut self, lines: u16) {
+        let last_line =
+            u16::try_from(self.help_line_count().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.help_scroll = self.help_scroll.saturating_add(lines).min(last_line);
+    }
+
+    pub fn scroll_help_up(&mut self, lines: u16) {
+        self.help_scroll = self.help_scroll.saturating_sub(lines);
+    }
+
+    pub fn scroll_help_to_top(&mut self) {
+        self.help_scroll = 0;
+    }
+
+    pub fn scroll_help_to_bottom(&mut self) {
+        self.help_scroll = 0;
+        self.scroll_help_down(u16::MAX);
+    }
+
+    pub fn add_help_filter_char(&mut self, character: char) {
+        self.help_filter.add_char(character);
+        self.help_scroll = 0;
+    }
+
+    pub fn remove_help_filter_char(&mut self) {
+        self.help_filter.remove_char();
+        self.help_scroll = 0;
+    }
+
+    /// Esc clears the filter first, then closes Help
+    pub fn escape_help(&mut self) {
+        if self.help_filter.is_empty() {
+            self.close_help();
+        } else {
+            self.help_filter.clear();
+            self.help_scroll = 0;
+        }
+    }
+}
+
+fn help_entry(key: &str, description: &str) -> HelpEntry {
+    HelpEntry {
+        key: key.to_string(),
+        description: description.to_string(),
+    }
 }
@@ -1,5 +1,7 @@
-use crate::app::types::{ChatMessage, MessageRole};
-use crate::app::{App, AppMode, Navigable};
+use crate::app::types::{ChatMessage, MessageImage, MessageQuote, MessageRole};
+use crate::app::{AgentEvent, App, AppMode, Navigable, TextInput};
+use crate::config::ResumeLast;
+use crate::storage::{ConversationSummary, Retention};
 use color_eyre::Result;
 
 impl App {
@@ -8,13 +10,21 @@ impl App {
         self.chat_history.clear();
         self.chat_input.clear();
         self.current_conversation_id = None;
+        self.conversation_language = self.default_language.clone();
+        self.autosaved_message_count = 0;
+        self.last_autosave = None;
         self.personality_text = None;
         self.cached_recall_context = None;
+        self.carried_session_context = None;
         if let Some(agent) = &self.current_agent {
             let agent_name = agent.name.clone();
             let _ = self.load_agent(&agent_name);
         }
         self.history_delete_all_active = false;
+        self.history_show_archived = false;
+        self.set_incognito(false);
+        self.message_selection_active = false;
+        self.pending_quote = None;
 
         // Clear summary animation so it doesn't bleed into the new chat.
         // The background thread will still finish and save — we just stop showing the spinner.
@@ -23,68 +33,70 @@ impl App {
         self.last_summary_tick = None;
     }
 
+    /// Loads the first page of `/history` (or the filter matches) in the background;
+    /// the result arrives as `AgentEvent::HistoryLoaded`
     pub(crate) fn load_history_list(&mut self) {
-        self.ensure_storage();
-        let Some(storage) = &self.storage else {
-            return;
-        };
-        let Some(runtime) = self.storage_runtime() else {
-            return;
-        };
+        self.request_history_page(self.history_page_size);
+    }
 
-        let limit = self.history_page_size;
-        self.history_conversations = if self.history_filter.is_empty() {
-            let loaded = runtime
-                .block_on(async {
-                    storage.load_conversations_with_limit(limit + 1).await.ok()
-                })
-                .unwrap_or_default();
-
-            // Check if there are more by requesting limit+1
-            self.history_has_more = loaded.len() > limit;
-
-            // Return only the requested limit
-            loaded.into_iter().take(limit).collect()
-        } else {
-            runtime
-                .block_on(async {
-                    storage.filter_conversations(self.history_filter.content()).await.ok()
-                })
-                .unwrap_or_default()
+    fn request_history_page(&mut self, limit: usize) {
+        let Some(tx) = self.agent_tx.clone() else {
+            return;
         };
+        let filter = self.history_filter.content().to_string();
+        let archived = self.history_show_archived;
+        self.history_loading = true;
+        self.spawn_storage_job(move |storage, runtime| {
+            let (conversations, has_more) = if filter.is_empty() {
+                // Request limit+1 to know whether there are more
+                let page = if archived {
+                    storage.load_archived_conversations(limit + 1)
+                } else {
+                    storage.load_conversations_with_limit(limit + 1)
+                };
+                let loaded = runtime.block_on(page).unwrap_or_default();
+                let has_more = loaded.len() > limit;
+                (loaded.into_iter().take(limit).collect(), has_more)
+            } else {
+                let matches = runtime
+                    .block_on(storage.filter_conversations(&filter))
+                    .unwrap_or_default();
+                (matches, false)
+            };
+            let _ = tx.send(AgentEvent::HistoryLoaded {
+                filter,
+                conversations,
+                has_more,
+            });
+        });
+    }
 
+    pub(crate) fn handle_history_loaded(
+        &mut self,
+        filter: String,
+        conversations: Vec<ConversationSummary>,
+        has_more: bool,
+    ) {
+        // A later keystroke already asked for a different list
+        if filter != self.history_filter.content() {
+            return;
+        }
+        self.history_loading = false;
+        self.history_conversations = conversations;
+        self.history_has_more = has_more;
+        if let Some(conversation_id) = self.history_pending_select.take() {
+            self.select_history_conversation(&conversation_id);
+        }
         if self.history_selected_index >= self.history_conversations.len() {
             self.history_selected_index = self.history_conversations.len().saturating_sub(1);
         }
     }
 
     pub fn load_more_history(&mut self) {
-        if !self.history_has_more || !self.history_filter.is_empty() {
+        if !self.history_has_more || !self.history_filter.is_empty() || self.history_loading {
             return;
         }
-
-        self.ensure_storage();
-        let Some(storage) = &self.storage else {
-            return;
-        };
-        let Some(runtime) = self.storage_runtime() else {
-            return;
-        };
-
-        let current_count = self.history_conversations.len();
-        let new_limit = current_count + self.history_page_size;
-
-        let loaded = runtime
-            .block_on(async {
-                storage.load_conversations_with_limit(new_limit + 1).await.ok()
-            })
-            .unwrap_or_default();
-
-        // Check if there are more
-        self.history_has_more = loaded.len() > new_limit;
-
-        // Update conversations
-        self.history_conversations = loaded.into_iter().take(new_limit).collect();
+        self.request_history_page(self.history_conversations.len() + self.history_page_size);
     }
 
     pub fn select_history_conversation(&mut self, conversation_id: &str) {
@@ -113,29 +125,49 @@ impl App {
             .ok_or_else(|| color_eyre::eyre::eyre!("Invalid conversation selection"))?;
         let conv_id = conv.id.clone();
         let agent_name = conv.agent_name.clone();
+        self.open_conversation(conv_id, &agent_name)
+    }
 
+    /// Loads a saved conversation into the chat with its agent, so new messages
+    /// are appended to the same conversation
+    pub(crate) fn open_conversation(&mut self, conv_id: String, agent_name: &str) -> Result<()> {
         let (storage, runtime) = self.storage_with_runtime()?;
         let (_agent_name, messages) = runtime.block_on(storage.load_conversation(&conv_id))?;
+        let language = runtime.block_on(storage.load_conversation_language(&conv_id))?;
 
-        self.load_agent(&agent_name)?;
+        self.load_agent(agent_name)?;
 
         self.chat_history.clear();
         for msg in messages {
-            let role = match msg.role.as_str() {
-                "User" => MessageRole::User,
-                "Assistant" => MessageRole::Assistant,
-                _ => MessageRole::System,
-            };
+            let stats = msg.stats();
+            let quote = msg.quoted_text.map(|content| MessageQuote {
+                role: MessageRole::from_stored(msg.quoted_role.as_deref().unwrap_or_default()),
+                content,
+            });
             self.chat_history.push(ChatMessage {
-                role,
+                role: MessageRole::from_stored(&msg.role),
                 content: msg.content,
                 timestamp: msg.timestamp,
                 display_name: msg.display_name,
                 context_usage: None,
+                excluded_from_context: false,
+                quote,
+                stats,
+                check: None,
+                images: msg.images.into_iter().map(|path| MessageImage::load(path.into())).collect(),
+                files: msg.files.into_iter().map(Into::into).collect(),
             });
         }
 
+        self.spawn_resume_recap(&conv_id);
         self.current_conversation_id = Some(conv_id);
+        self.conversation_language = language
+            .as_deref()
+            .and_then(crate::services::languages::normalize)
+            .unwrap_or(self.default_language.as_str())
+            .to_string();
+        self.mark_autosaved(self.chat_history.len());
+        self.carried_session_context = None;
         self.chat_scroll_offset = 0;
         self.mode = AppMode::Chat;
 
@@ -145,24 +177,169 @@ impl App {
         Ok(())
     }
 
+    /// Asks the summary model in the background how to welcome the user back to a
+    /// reopened conversation; the recap lands as a system message via `ResumeRecap`
+    fn spawn_resume_recap(&self, conversation_id: &str) {
+        if !self.resume_recap_enabled {
+            return;
+        }
+        let Ok((storage, runtime)) = self.storage_with_runtime() else {
+            return;
+        };
+        let Ok(Some((Some(summary), updated_at))) = runtime.block_on(storage.load_conversation_recap(conversation_id))
+        else {
+            return;
+        };
+        if summary.trim().is_empty() || summary == crate::app::PENDING_SUMMARY_LABEL {
+            return;
+        }
+        let Some(gap) = crate::services::resume_recap::describe_gap(&updated_at, &chrono::Local::now()) else {
+            return;
+        };
+        let Ok((agent, manager, agent_tx)) = self.get_agent_chat_dependencies() else {
+            return;
+        };
+        let conversation_id = conversation_id.to_string();
+        std::thread::spawn(move || {
+            let summary_agent = manager.summary_agent();
+            let summarizers: Vec<&crate::agents::Agent> =
+                summary_agent.iter().chain(std::iter::once(&agent)).collect();
+            let recap = crate::services::resume_recap::generate(&summary, &gap, &summarizers, &manager);
+            let _ = agent_tx.send(AgentEvent::ResumeRecap { conversation_id, recap });
+        });
+    }
+
+    pub(crate) fn handle_resume_recap(&mut self, conversation_id: &str, recap: String) {
+        // The user may have opened another conversation while the recap was written
+        if self.current_conversation_id.as_deref() != Some(conversation_id) {
+            return;
+        }
+        let mut message = ChatMessage::system(recap);
+        message.excluded_from_context = true;
+        self.chat_history.push(message);
+    }
+
+    /// Applies `conversation.resume_last` at startup: reopens the most recent
+    /// conversation right away or asks first
+    pub fn maybe_resume_last_conversation(&mut self, resume: ResumeLast) {
+        if resume == ResumeLast::Never || !self.ensure_storage() {
+            return;
+        }
+        let Ok((storage, runtime)) = self.storage_with_runtime() else {
+            return;
+        };
+        let latest = runtime
+            .block_on(storage.load_conversations_with_limit(1))
+            .ok()
+            .and_then(|conversations| conversations.into_iter().next());
+        let Some(conversation) = latest else {
+            return;
+        };
+        self.resume_prompt = Some(conversation);
+        if resume == ResumeLast::Always {
+            self.accept_resume_prompt();
+        }
+    }
+
+    pub fn accept_resume_prompt(&mut self) {
+        let Some(conversation) = self.resume_prompt.take() else {
+            return;
+        };
+        match self.open_conversation(conversation.id, &conversation.agent_name) {
+            Ok(()) => self.show_status_toast("RESUMED"),
+            Err(error) => self.add_system_message(&format!("Could not resume conversation: {}", error)),
+        }
+    }
+
+    pub fn dismiss_resume_prompt(&mut self) {
+        self.resume_prompt = None;
+    }
+
     pub fn delete_history_conversation(&mut self) -> Result<()> {
         let conv = self
             .history_conversations
             .get(self.history_selected_index)
             .ok_or_else(|| color_eyre::eyre::eyre!("Invalid conversation selection"))?;
         let conv_id = conv.id.clone();
-        let (storage, runtime) = self.storage_with_runtime()?;
-        runtime.block_on(storage.delete_conversation(&conv_id))?;
-        
-        self.load_history_list();
+        self.spawn_storage_job(move |storage, runtime| {
+            if let Err(error) = runtime.block_on(storage.delete_conversation(&conv_id)) {
+                tracing::error!(%error, conversation = %conv_id, "could not delete conversation");
+            }
+        });
+
+        self.history_conversations.remove(self.history_selected_index);
         if self.history_selected_index >= self.history_conversations.len()
             && self.history_selected_index > 0
         {
             self.history_selected_index -= 1;
         }
+        self.load_history_list();
         Ok(())
     }
 
+    /// `/archive`: switches history between the default list and archived conversations
+    pub fn toggle_archive_view(&mut self) -> Result<()> {
+        self.close_menu();
+        self.history_show_archived = !self.history_show_archived;
+        if self.mode == AppMode::History {
+            self.history_selected_index = 0;
+            self.load_history_list();
+            return Ok(());
+        }
+        self.exit_chat_to_history()
+    }
+
+    /// Archives the selected conversation, or brings it back when it already is
+    pub fn toggle_selected_archived(&mut self) {
+        let Some(conv) = self.history_conversations.get_mut(self.history_selected_index) else {
+            return;
+        };
+        conv.archived = !conv.archived;
+        let archived = conv.archived;
+        let conv_id = conv.id.clone();
+        self.spawn_storage_job(move |storage, runtime| {
+            if let Err(error) = runtime.block_on(storage.set_conversation_archived(&conv_id, archived)) {
+                tracing::error!(%error, conversation = %conv_id, "could not change archive flag");
+            }
+        });
+        // Filter matches show both kinds; otherwise the row left this list
+        if self.history_filter.is_empty() {
+            self.history_conversations.remove(self.history_selected_index);
+            if self.history_selected_index >= self.history_conversations.len()
+                && self.history_selected_index > 0
+            {
+                self.history_selected_index -= 1;
+            }
+        }
+        self.show_status_toast(if archived { "ARCHIVED" } else { "UNARCHIVED" });
+    }
+
+    /// `K` cycles the selected conversation: normal → ephemeral (deleted after a week) → keep forever
+    pub fn cycle_selected_retention(&mut self) {
+        let Some(conv) = self.history_conversations.get_mut(self.history_selected_index) else {
+            return;
+        };
+        let (retention, expires_at, toast) = match conv.retention {
+            Retention::Normal => (
+                Retention::Ephemeral,
+                Some(crate::services::maintenance::ephemeral_expiry(chrono::Local::now())),
+                "EPHEMERAL: DELETED IN 7 DAYS",
+            ),
+            Retention::Ephemeral => (Retention::Forever, None, "KEEP FOREVER"),
+            Retention::Forever => (Retention::Normal, None, "NORMAL RETENTION"),
+        };
+        conv.retention = retention;
+        conv.expires_at = expires_at.clone();
+        let conv_id = conv.id.clone();
+        self.spawn_storage_job(move |storage, runtime| {
+            let saved = runtime.block_on(storage.set_conversation_retention(&conv_id, retention, expires_at.as_deref()));
+            if let Err(error) = saved {
+                tracing::error!(%error, conversation = %conv_id, "could not change retention");
+            }
+        });
+        self.show_status_toast(toast);
+    }
+
     pub fn open_history_delete_all(&mut self) {
         self.history_delete_all_active = true;
         self.history_delete_all_confirm_delete = false;
@@ -192,6 +369,48 @@ impl App {
         Ok(())
     }
 
+    /// Opens the inline title editor for the selected conversation
+    pub fn open_history_rename(&mut self) {
+        let Some(conv) = self.history_conversations.get(self.history_selected_index) else {
+            return;
+        };
+        let current_title = conv.title().unwrap_or_default().to_string();
+        self.history_rename_input = TextInput::with_content(current_title);
+        self.history_rename_active = true;
+    }
+
+    pub fn cancel_history_rename(&mut self) {
+        self.history_rename_active = false;
+        self.history_rename_input.clear();
+    }
+
+    pub fn add_history_rename_char(&mut self, character: char) {
+        self.history_rename_input.add_char(character);
+    }
+
+    pub fn remove_history_rename_char(&mut self) {
+        self.history_rename_input.remove_char();
+    }
+
+    /// Saves the custom title; an empty title reverts to the generated summary
+    pub fn confirm_history_rename(&mut self) -> Result<()> {
+        let conv = self
+            .history_conversations
+            .get(self.history_selected_index)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Invalid conversation selection"))?;
+        let conv_id = conv.id.clone();
+        let title = self.history_rename_input.content().trim().to_string();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.rename_conversation(&conv_id, &title))?;
+
+        if let Some(conv) = self.history_conversations.get_mut(self.history_selected_index) {
+            conv.custom_title = Some(title.clone()).filter(|value| !value.is_empty());
+        }
+        self.cancel_history_rename();
+        self.show_status_toast(if title.is_empty() { "TITLE RESET" } else { "RENAMED" });
+        Ok(())
+    }
+
     pub fn toggle_history_filter(&mut self) {
         self.history_filter_active = !self.history_filter_active;
         if !self.history_filter_active {
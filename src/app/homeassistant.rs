@@ -0,0 +1,58 @@
+use crate::app::{AgentEvent, App, AppMode};
+use crate::config::HomeAssistantConfig;
+use crate::services::homeassistant::ServiceCall;
+
+impl App {
+    /// Asks the user to confirm a service call; it only runs from the overlay
+    pub fn open_home_assistant_confirm(&mut self, call: ServiceCall) {
+        self.home_assistant_call = Some(call);
+        self.mode = AppMode::HomeAssistantConfirm;
+    }
+
+    pub fn cancel_home_assistant_call(&mut self) {
+        if let Some(call) = self.home_assistant_call.take() {
+            self.add_system_message(&format!("Cancelled {}", call.describe()));
+        }
+        self.mode = AppMode::Chat;
+    }
+
+    /// Runs the confirmed service call in the background
+    pub fn run_home_assistant_call(&mut self) {
+        let Some(call) = self.home_assistant_call.take() else {
+            return;
+        };
+        self.mode = AppMode::Chat;
+        let config = HomeAssistantConfig {
+            url: self.connect_home_assistant_url.clone(),
+            api_key: self.connect_home_assistant_token.clone(),
+        };
+        let tx = self.agent_tx.clone();
+        self.show_status_toast("CALLING HOME ASSISTANT");
+        std::thread::spawn(move || {
+            let result = crate::services::homeassistant::call_service(&config, &call)
+                .map(|changed| {
+                    let changed: Vec<_> = changed.iter().collect();
+                    if changed.is_empty() {
+                        String::new()
+                    } else {
+                        crate::services::homeassistant::format_states(&call.entity_id, &changed)
+                    }
+                })
+                .map_err(|error| error.to_string());
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::HomeAssistantCallFinished {
+                    call: call.describe(),
+                    result,
+                });
+            }
+        });
+    }
+
+    pub(crate) fn handle_home_assistant_call_finished(&mut self, call: &str, result: Result<String, String>) {
+        match result {
+            Ok(changed) if changed.is_empty() => self.add_system_message(&format!("{} done", call)),
+            Ok(changed) => self.add_system_message(&format!("{} done\n{}", call, changed)),
+            Err(error) => self.add_system_message(&format!("{} failed: {}", call, error)),
+        }
+    }
+}
@@ -30,6 +30,9 @@ impl App {
     /// Both run sequentially in a single thread to avoid race conditions
     /// on the shared identity-state.json file (last writer would overwrite the other).
     pub(crate) fn maybe_update_emotions(&self, assistant_response: &str) {
+        if self.incognito {
+            return;
+        }
         let Some(manager) = self.agent_manager.clone() else {
             return;
         };
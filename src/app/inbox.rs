@@ -0,0 +1,229 @@
+use crate::app::{AgentEvent, App, AppMode};
+use crate::services::notifications::{self, NotificationKind};
+use color_eyre::Result;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Number of notifications listed in the inbox
+const INBOX_LIMIT: usize = 100;
+/// How often the watcher looks for upcoming calendar events
+const REMINDER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How often the watcher reloads the calendars it checks
+const CALENDAR_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+impl App {
+    pub fn open_inbox(&mut self) -> Result<()> {
+        self.mode = AppMode::Inbox;
+        self.inbox_selected_index = 0;
+        self.load_inbox()
+    }
+
+    pub fn close_inbox(&mut self) {
+        self.mode = AppMode::Chat;
+        self.inbox.clear();
+    }
+
+    fn load_inbox(&mut self) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        let inbox = runtime.block_on(storage.load_notifications(INBOX_LIMIT))?;
+        let unread = runtime.block_on(storage.count_unread_notifications())?;
+        self.inbox = inbox;
+        self.unread_notifications = unread;
+        self.inbox_selected_index = self
+            .inbox_selected_index
+            .min(self.inbox.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Reads the unread count for the footer badge at startup
+    pub fn refresh_unread_notifications(&mut self) {
+        if !self.ensure_storage() {
+            return;
+        }
+        if let Ok((storage, runtime)) = self.storage_with_runtime()
+            && let Ok(count) = runtime.block_on(storage.count_unread_notifications())
+        {
+            self.unread_notifications = count;
+        }
+    }
+
+    pub fn next_inbox_item(&mut self) {
+        if !self.inbox.is_empty() {
+            self.inbox_selected_index = (self.inbox_selected_index + 1) % self.inbox.len();
+        }
+    }
+
+    pub fn previous_inbox_item(&mut self) {
+        if !self.inbox.is_empty() {
+            self.inbox_selected_index = self
+                .inbox_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.inbox.len().saturating_sub(1));
+        }
+    }
+
+    /// Flips the selected notification between read and unread
+    pub fn toggle_selected_notification_read(&mut self) -> Result<()> {
+        let Some(notification) = self.inbox.get(self.inbox_selected_index) else {
+            return Ok(());
+        };
+        let (id, read) = (notification.id.clone(), !notification.read);
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.set_notification_read(&id, read))?;
+        self.load_inbox()
+    }
+
+    pub fn mark_all_notifications_read(&mut self) -> Result<()> {
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.mark_all_notifications_read())?;
+        self.show_status_toast("ALL READ");
+        self.load_inbox()
+    }
+
+    pub fn delete_selected_notification(&mut self) -> Result<()> {
+        let Some(notification) = self.inbox.get(self.inbox_selected_index) else {
+            return Ok(());
+        };
+        let id = notification.id.clone();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.delete_notification(&id))?;
+        self.load_inbox()
+    }
+
+    /// Puts a notification into the inbox and forwards it to the desktop and webhook
+    /// when those are configured; the inbox write runs on the storage worker
+    pub(crate) fn publish_notification(&mut self, kind: NotificationKind, title: &str, message: &str) {
+        let tx = self.agent_tx.clone();
+        let (stored_title, stored_message) = (title.to_string(), message.to_string());
+        self.spawn_storage_job(move |storage, runtime| {
+            match runtime.block_on(storage.save_notification(kind, &stored_title, &stored_message)) {
+                Ok(()) => {
+                    if let Some(tx) = tx {
+                        let _ = tx.send(AgentEvent::NotificationStored);
+                    }
+                }
+                Err(error) => tracing::warn!(%error, title = %stored_title, "could not store notification"),
+            }
+        });
+        if self.notifications.desktop {
+            notifications::send_desktop_notification(title, message);
+        }
+        crate::services::webhook::notify_in_background(&self.notifications, title, message);
+    }
+
+    pub(crate) fn handle_notification_stored(&mut self) {
+        self.unread_notifications += 1;
+        if self.mode == AppMode::Inbox {
+            let _ = self.load_inbox();
+        }
+    }
+
+    /// Starts the background watcher that publishes today's due digest once a day,
+    /// reminders for calendar events shortly before they start and stored reminders and
+    /// accepted `/tasks` on their day
+    pub fn spawn_notification_watcher(&mut self) {
+        let Some(tx) = self.agent_tx.clone() else {
+            return;
+        };
+        self.ensure_storage();
+        let storage = self.storage.clone();
+        let sources = self.calendar_sources.clone();
+        let lead_minutes = self.notifications.reminder_lead_minutes;
+
+        std::thread::spawn(move || {
+            let load_events = || {
+                let today = chrono::Local::now().date_naive();
+                crate::services::calendar::load_upcoming_events(&sources, today, 1)
+                    .unwrap_or_default()
+            };
+            let mut events = if sources.is_empty() { Vec::new() } else { load_events() };
+
+            let today = chrono::Local::now().date_naive();
+            if notifications::is_digest_due(today) {
+                let open_tasks = storage
+                    .as_ref()
+                    .zip(tokio::runtime::Runtime::new().ok())
+                    .and_then(|(storage, runtime)| {
+                        runtime.block_on(storage.load_open_project_tasks()).ok()
+                    })
+                    .unwrap_or_default();
+                if let Some(digest) = notifications::format_due_digest(&events, &open_tasks, today) {
+                    let sent = tx.send(AgentEvent::Notification {
+                        kind: NotificationKind::Digest,
+                        title: format!("Due today, {}", today.format("%A %B %-d")),
+                        message: digest,
+                    });
+                    if sent.is_err() {
+                        return;
+                    }
+                }
+                if let Err(error) = notifications::mark_digest_published(today) {
+                    tracing::warn!(%error, "could not record digest");
+                }
+            }
+
+            let calendar_reminders = !sources.is_empty() && lead_minutes > 0;
+            let runtime = tokio::runtime::Runtime::new().ok();
+            let stored_reminders = storage.as_ref().zip(runtime.as_ref());
+            if !calendar_reminders && stored_reminders.is_none() {
+                return;
+            }
+            let mut reminded: HashSet<(String, chrono::NaiveDateTime)> = HashSet::new();
+            let mut last_refresh = Instant::now();
+            loop {
+                if let Some((storage, runtime)) = stored_reminders {
+                    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+                    let due = runtime.block_on(storage.take_due_reminders(&today)).unwrap_or_default();
+                    for reminder in due {
+                        let sent = tx.send(AgentEvent::Notification {
+                            kind: NotificationKind::Reminder,
+                            title: format!("Reminder for {}", reminder.due),
+                            message: reminder.text,
+                        });
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                    let tasks = runtime.block_on(storage.take_due_tasks(&today)).unwrap_or_default();
+                    for task in tasks {
+                        let sent = tx.send(AgentEvent::Notification {
+                            kind: NotificationKind::Reminder,
+                            title: format!("Task due {}", task.due),
+                            message: task.text,
+                        });
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                }
+                if !calendar_reminders {
+                    std::thread::sleep(REMINDER_CHECK_INTERVAL);
+                    continue;
+                }
+                if last_refresh.elapsed() >= CALENDAR_REFRESH_INTERVAL {
+                    events = load_events();
+                    last_refresh = Instant::now();
+                }
+                let now = chrono::Local::now().naive_local();
+                for event in notifications::upcoming_reminders(&events, now, lead_minutes) {
+                    if !reminded.insert((event.summary.clone(), event.start)) {
+                        continue;
+                    }
+                    let sent = tx.send(AgentEvent::Notification {
+                        kind: NotificationKind::Reminder,
+                        title: format!("{} at {}", event.summary, event.start.format("%H:%M")),
+                        message: crate::services::calendar::format_event_line(event),
+                    });
+                    // The app has quit once the channel is closed
+                    if sent.is_err() {
+                        return;
+                    }
+                }
+                std::thread::sleep(REMINDER_CHECK_INTERVAL);
+            }
+        });
+    }
+}
@@ -0,0 +1,27 @@
+use crate::app::App;
+use crate::services::languages;
+
+impl App {
+    /// `/language <code>`: switches the reply language of this conversation and saves it
+    /// with the conversation once there is one
+    pub fn set_conversation_language(&mut self, input: &str) {
+        let Some(language) = languages::normalize(input) else {
+            self.add_system_message(&format!(
+                "Unknown language: {}. Supported: {}",
+                input,
+                languages::supported_codes()
+            ));
+            return;
+        };
+        self.conversation_language = language.to_string();
+        if let Some(conversation_id) = self.current_conversation_id.clone() {
+            let language = language.to_string();
+            self.spawn_storage_job(move |storage, runtime| {
+                if let Err(error) = runtime.block_on(storage.set_conversation_language(&conversation_id, &language)) {
+                    tracing::warn!(%error, conversation = %conversation_id, "could not save conversation language");
+                }
+            });
+        }
+        self.show_status_toast(format!("REPLYING IN {}", languages::name(language).to_uppercase()));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::app::{App, AppMode};
+use std::time::{Duration, Instant};
+
+/// Number of log lines kept in the viewer
+const LOG_TAIL_LINES: usize = 500;
+const LOG_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+impl App {
+    pub fn open_log_viewer(&mut self) {
+        self.log_scroll = 0;
+        self.refresh_log_tail();
+        self.mode = AppMode::LogViewer;
+    }
+
+    pub fn close_log_viewer(&mut self) {
+        self.mode = AppMode::Chat;
+        self.log_lines.clear();
+    }
+
+    pub fn refresh_log_tail(&mut self) {
+        self.log_lines = match crate::services::logging::read_log_tail(LOG_TAIL_LINES) {
+            Ok(lines) => lines,
+            Err(error) => vec![format!("Log unavailable: {}", error)],
+        };
+        self.last_log_refresh = Some(Instant::now());
+    }
+
+    /// Re-reads the log file about once a second while the viewer is open
+    pub fn tick_log_viewer(&mut self) {
+        if self.mode != AppMode::LogViewer {
+            return;
+        }
+        if self
+            .last_log_refresh
+            .is_none_or(|refreshed_at| refreshed_at.elapsed() >= LOG_REFRESH_INTERVAL)
+        {
+            self.refresh_log_tail();
+        }
+    }
+
+    /// Scrolls towards older lines (scroll is measured from the bottom)
+    pub fn scroll_log_up(&mut self, lines: usize) {
+        self.log_scroll = self
+            .log_scroll
+            .saturating_add(lines)
+            .min(self.log_lines.len().saturating_sub(1));
+    }
+
+    pub fn scroll_log_down(&mut self, lines: usize) {
+        self.log_scroll = self.log_scroll.saturating_sub(lines);
+    }
+}
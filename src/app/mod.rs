@@ -1,17 +1,48 @@
+mod attachments;
+mod audit;
+mod capture;
 mod chat;
 pub(crate) use chat::PENDING_SUMMARY_LABEL;
 mod command;
 mod connect;
+mod dashboard;
+mod diary;
+mod duplicates;
+mod headless;
 mod help;
 mod history;
 mod models;
 mod navigation;
+mod playback;
 mod identity;
-mod personality;
+mod logs;
+mod downloads;
+mod editor;
+mod email;
+mod entities;
+mod health;
+mod homeassistant;
+mod inbox;
+mod language;
+pub(crate) mod personality;
+mod profiles;
+mod projects;
+mod prompt_inspector;
 mod scroll;
+mod search;
+mod schedule;
+mod selection;
+mod sentiment;
+mod settings;
+mod snippets;
+mod sources;
+mod storage_worker;
+mod tasks;
 #[path = "text-input.rs"]
 mod text_input;
+mod timers;
 mod types;
+mod usage;
 
 pub use command::cmd_quit;
 pub use navigation::Navigable;
@@ -40,33 +71,124 @@ pub enum AppMode {
     Help,
     PersonalitySelection,
     PersonalityCreate,
+    PersonalitySuggestion,
     IdentityView,
     ProjectList,
     ProjectDetail,
+    ProjectForm,
+    Agenda,
+    SnippetList,
+    LogViewer,
+    ProfileSelection,
+    Search,
+    PromptInspector,
+    Downloads,
+    Inbox,
+    Tasks,
+    EmailPreview,
+    HomeAssistantConfirm,
+    DuplicateQuestion,
+    Status,
+    Usage,
+    Audit,
+    Sources,
+    EntityActions,
+    Capture,
+    Dashboard,
+    Sentiment,
+    Diary,
+    Editor,
+    Settings,
 }
 
 /// Events from the agent processing thread
 pub enum AgentEvent {
+    /// Published by background jobs into the `/inbox`
+    Notification {
+        kind: crate::services::notifications::NotificationKind,
+        title: String,
+        message: String,
+    },
+    /// A published notification reached the inbox table
+    NotificationStored,
     ResponseWithContext {
         response: String,
         context_usage: Option<ContextUsage>,
+        /// Absent for replies that never reached a model
+        stats: Option<crate::storage::MessageStats>,
     },
     Error(String),
     SummaryGenerated {
         summary: String,
         conversation_id: String,
         messages: Vec<crate::storage::ConversationMessage>,
+        /// One line per part of a long conversation; empty when it was summarized whole
+        chunk_summaries: Vec<String>,
     },
     SystemMessage(String),
     StatusUpdate(String),
     DownloadFinished {
         url: String,
+        status: crate::services::link_download::DownloadStatus,
+        destination: Option<std::path::PathBuf>,
+        error: Option<String>,
     },
     DownloadProgress {
         url: String,
-        progress: u8,
+        update: crate::services::link_download::DownloadUpdate,
+    },
+    /// One file of a `convert` batch is done; `result` holds the output path or the error
+    ConversionFileFinished {
+        input: String,
+        result: Result<String, String>,
+    },
+    ConversionFinished {
+        format: String,
+        converted: usize,
+        total: usize,
+    },
+    /// An email the agent drafted, waiting for the user's confirmation
+    EmailDraft(crate::services::email::EmailDraft),
+    EmailSent {
+        to: String,
+        result: Result<(), String>,
+    },
+    /// A Home Assistant service call the agent asked for, waiting for the user's confirmation
+    HomeAssistantCall(crate::services::homeassistant::ServiceCall),
+    /// A confirmed service call ran; `result` lists the states it changed
+    HomeAssistantCallFinished {
+        call: String,
+        result: Result<String, String>,
+    },
+    /// Events for `/agenda`, fetched off the UI thread
+    AgendaLoaded {
+        result: Result<Vec<crate::services::calendar::CalendarEvent>, String>,
+    },
+    /// A `news` briefing; None when the feeds had nothing new
+    NewsBriefing {
+        result: Result<Option<String>, String>,
+    },
+    /// Latest connection health for the footer and `/status`
+    Health(Vec<crate::services::health::ServiceHealth>),
+    /// A `transcribe` job ended; `summary` is set when a summary was requested
+    TranscriptionFinished {
+        source: String,
+        result: Result<crate::services::transcribe::Transcript, String>,
+        summary: Option<String>,
+    },
+    /// An `/imagine` request finished with the saved picture or an error
+    ImageGenerated {
+        prompt: String,
+        result: Result<std::path::PathBuf, String>,
+    },
+    ResumeRecap {
+        conversation_id: String,
+        recap: String,
+    },
+    /// Dated commitments from a saved conversation were proposed in `/tasks`
+    TasksExtracted {
+        count: usize,
     },
-    ConversionFinished,
     CacheObsidianNotes {
         query: String,
         notes: Vec<crate::services::obsidian::NoteSnippet>,
@@ -74,16 +196,62 @@ pub enum AgentEvent {
     CacheRecallContext {
         context: String,
     },
+    /// Sections of the assembled system prompt for the `/prompt` inspector
+    PromptPreview(Vec<PromptSectionText>),
     FollowUpSuggestions {
         suggestions: Vec<String>,
     },
+    /// Repeat check for a held-back message finished; `earlier` is the answer to offer
+    EarlierAnswerChecked {
+        question: String,
+        earlier: Option<crate::storage::AnsweredQuestion>,
+    },
+    /// Topics of a closed conversation were stored; `frequent` crossed the suggestion threshold
     TopicsExtracted {
-        topics: Vec<String>,
-        conversation_id: String,
+        frequent: Vec<(String, usize)>,
+    },
+    /// A page of `/history`; dropped when `filter` no longer matches what the user typed
+    HistoryLoaded {
+        filter: String,
+        conversations: Vec<ConversationSummary>,
+        has_more: bool,
     },
+    /// The chat left for `/history` was saved (id) or could not be
+    ConversationSaved(Result<String, String>),
     ProjectEntriesExtracted {
         results: Vec<crate::services::projects::ProjectExtractionResult>,
     },
+    PersonalitySuggestionReady {
+        personality: String,
+    },
+    /// A file in the personalities directory was created, edited or removed
+    PersonalitiesChanged,
+    /// config.toml or config.local.toml was written
+    ConfigChanged,
+    /// A tool call went through the `[tools]` policy; stored for `/audit`
+    ToolAudited {
+        tool: String,
+        arguments: String,
+        decision: crate::services::tool_policy::ToolDecision,
+        result: String,
+    },
+    /// Full text behind a tool result the agent only saw summarized
+    ToolOutputStored(crate::storage::ToolOutputRecord),
+    /// Verdict of the second call that checked a reply already shown
+    AnswerChecked {
+        response: String,
+        check: crate::services::verification::AnswerCheck,
+    },
+    PersonalitySampleReady {
+        personality: String,
+        reply: String,
+    },
+    /// Result of the selection-mode translate action, shown as a note
+    MessageTranslated(String),
+    ImageTextExtracted {
+        label: String,
+        text: String,
+    },
 }
 
 /// Main application state
@@ -102,8 +270,12 @@ pub struct App {
     pub chat_history_by_agent: HashMap<String, Vec<ChatMessage>>,
     pub chat_input: TextInput,
     pub chat_attachments: Vec<ChatAttachment>,
+    pub attachment_panel_active: bool,
+    pub attachment_selected_index: usize,
     pub next_attachment_id: usize,
     pub current_agent: Option<Agent>,
+    /// Set by `kimi ask`: no history saves, follow-ups or TTS
+    pub headless: bool,
     pub is_loading: bool,
     pub is_searching: bool,
     pub is_fetching_notes: bool,
@@ -114,8 +286,20 @@ pub struct App {
     pub agent_rx: Option<Receiver<AgentEvent>>,
     pub agent_tx: Option<Sender<AgentEvent>>,
     pub auto_tts_enabled: bool,
+    /// `[elevenlabs] user_voice_id`, used for user messages by `/speak-all`
+    pub tts_user_voice_id: Option<String>,
     pub chat_scroll_offset: usize,
     pub chat_auto_scroll: bool, // Whether to auto-scroll to bottom on new messages
+    /// Dim model/duration/token suffix after assistant timestamps
+    pub show_message_stats: bool,
+    /// `[conversation] duplicate_question_similarity`: 0 turns the repeat-question offer off
+    pub duplicate_question_similarity: f32,
+    /// Question held back while the user decides whether to reuse an earlier answer
+    pub duplicate_offer: Option<DuplicateOffer>,
+    /// Set by "ask anyway" so the resend skips the check
+    pub skip_duplicate_check: bool,
+    /// Question whose repeat check is running on the storage worker
+    pub duplicate_check_pending: Option<String>,
     pub cached_obsidian_notes: Option<(String, Vec<crate::services::obsidian::NoteSnippet>)>, // (query, notes) for follow-up questions
     pub cached_recall_context: Option<String>, // past conversation content for follow-up questions
 
@@ -129,12 +313,20 @@ pub struct App {
     pub selected_models: HashMap<String, Vec<String>>,
     pub model_selection_index: usize,
     pub model_selection_items: Vec<ModelSelectionItem>,
+    pub model_details_cache: HashMap<String, crate::agents::ollama::ModelDetails>,
+    pub gpu_vram_bytes: Option<u64>,
+    pub gpu_vram_detected: bool,
 
     // Connect fields
     pub connect_elevenlabs_key: String,
     pub connect_venice_key: String,
     pub connect_gab_key: String,
     pub connect_brave_key: String,
+    pub connect_github_key: String,
+    pub connect_home_assistant_url: String,
+    pub connect_home_assistant_token: String,
+    /// Home Assistant asks for its server URL before the token
+    pub connect_entering_url: bool,
     pub connect_obsidian_vault: String,
     pub connect_obsidian_vault_path: String,
     pub connect_providers: Vec<String>,
@@ -153,13 +345,79 @@ pub struct App {
     pub history_filter: TextInput,
     pub history_filter_active: bool,
     pub history_delete_all_active: bool,
+    /// Most recent conversation offered for resuming at startup
+    pub resume_prompt: Option<crate::storage::ConversationSummary>,
+    pub search_input: TextInput,
+    /// `/capture`: text saved straight to memory and the Obsidian inbox
+    pub capture_input: TextInput,
+    /// This session's captures as (time, text, where it went), newest last
+    pub captures: Vec<(String, String, String)>,
+    /// Conversation holding today's captured memories
+    pub capture_conversation_id: Option<String>,
+    pub capture: crate::config::CaptureConfig,
+    pub editor: crate::config::EditorConfig,
+    pub editor_session: Option<EditorSession>,
+    /// Config being edited in `/settings`, reloaded from disk when the view opens
+    pub settings_config: Option<Config>,
+    pub settings_selected_index: usize,
+    /// Text field of the setting being edited
+    pub settings_input: Option<TextInput>,
+    /// Why the last value was rejected
+    pub settings_error: Option<String>,
+    /// Fuzzy filter typed in the Help view
+    pub help_filter: TextInput,
+    pub help_scroll: u16,
+    /// Config as last read from disk; file changes are compared against it
+    pub loaded_config: Option<Config>,
+    /// Keeps config.toml watched for hot-reload
+    pub config_watcher: Option<notify::RecommendedWatcher>,
+    pub search_groups: Vec<SearchResultGroup>,
+    /// Index into all hits across groups
+    pub search_selected_index: usize,
+    /// Query the current results belong to
+    pub search_last_query: String,
+    pub search_error: Option<String>,
+    /// Message to scroll to on the next draw (resolved against the terminal size)
+    pub chat_scroll_target: Option<usize>,
+    /// `/speak-all` waits for the next draw to know which message is at the top of the pane
+    pub speak_all_requested: bool,
+    /// Ctrl+K message selection mode
+    pub message_selection_active: bool,
+    pub selected_message_index: usize,
+    /// Message quoted into the input, attached to the next user message
+    pub pending_quote: Option<MessageQuote>,
+    /// Prompt sections turned off for this session (`/prompt`)
+    pub disabled_prompt_sections: Vec<PromptSection>,
+    /// Sections of the last assembled prompt preview (None while assembling)
+    pub prompt_preview: Option<Vec<PromptSectionText>>,
+    pub prompt_inspector_index: usize,
+    pub prompt_inspector_scroll: usize,
+    pub history_rename_active: bool,
+    pub history_rename_input: TextInput,
     pub history_delete_all_confirm_delete: bool,
     pub history_has_more: bool,
+    /// A history page is being loaded in the background
+    pub history_loading: bool,
+    /// Conversation to select once the loading history page arrives
+    pub history_pending_select: Option<String>,
     pub history_page_size: usize,
+    /// `/archive` toggle: history lists archived conversations instead of the others
+    pub history_show_archived: bool,
+    /// `/incognito`: this chat is not saved, embedded or reflected on
+    pub incognito: bool,
     pub storage: Option<StorageManager>,
     pub storage_runtime: Option<tokio::runtime::Runtime>,
     pub is_generating_summary: bool,
     pub current_conversation_id: Option<String>,
+    /// Language code replies are written in; saved with the conversation
+    pub conversation_language: String,
+    /// `[conversation] language`, used for every new conversation
+    pub default_language: String,
+    pub autosaved_message_count: usize,
+    pub last_autosave: Option<std::time::Instant>,
+    pub autosave_every_messages: usize,
+    pub autosave_interval: Duration,
+    pub storage_worker: Option<storage_worker::StorageWorker>,
     pub status_toast: Option<StatusToast>,
     pub clipboard_service: ClipboardService,
     pub personality_enabled: bool,
@@ -168,22 +426,167 @@ pub struct App {
     pub loading_frame: u8,
     pub last_loading_tick: Option<std::time::Instant>,
     pub active_downloads: Vec<DownloadItem>,
+    pub inbox: Vec<crate::storage::Notification>,
+    pub inbox_selected_index: usize,
+    pub unread_notifications: usize,
+    /// Dated commitments listed in `/tasks`, soonest first
+    pub due_tasks: Vec<crate::storage::DueTask>,
+    pub due_tasks_selected_index: usize,
+    /// Countdowns from `/timer` or "set a timer for...", soonest first
+    pub timers: Vec<crate::services::timers::Timer>,
+    pub stopwatch_started: Option<std::time::Instant>,
+    /// `[timers] announce`: speak "Your tea is done." when a timer finishes
+    pub announce_timers: bool,
+    /// Name user messages are attributed to, set with `/as <name>`; None means "You"
+    pub speaker: Option<String>,
+    pub download_history: Vec<crate::storage::DownloadRecord>,
+    pub download_selected_index: usize,
     pub conversion_active: bool,
     pub conversion_frame: u8,
+    /// Files finished and queued across running `convert` batches
+    pub conversion_done: usize,
+    pub conversion_total: usize,
     pub last_conversion_tick: Option<std::time::Instant>,
+    pub active_transcriptions: usize,
+    pub transcription_frame: u8,
+    pub last_transcription_tick: Option<std::time::Instant>,
     pub summary_active: bool,
     pub summary_frame: u8,
     pub last_summary_tick: Option<std::time::Instant>,
+    pub images: crate::config::ImagesConfig,
+    /// `/imagine` requests still waiting for the image API
+    pub active_image_generations: usize,
+    pub image_frame: u8,
+    pub last_image_tick: Option<std::time::Instant>,
     pub comfyui_process: Option<std::process::Child>,
 
     // Project fields
     pub projects: Vec<crate::services::projects::ProjectSummary>,
     pub project_entries: Vec<String>,
+    pub project_conversations: Vec<String>,
     pub project_selected_index: usize,
     pub project_entry_selected_index: usize,
     pub current_project_name: Option<String>,
     pub current_project_description: Option<String>,
     pub pending_project_suggestions: Vec<String>,
+    /// Project whose knowledge is offered to the model and which collects conversations
+    pub active_project: Option<String>,
+    pub project_form: Option<ProjectForm>,
+    pub project_tasks: Vec<crate::storage::ProjectTask>,
+    pub project_task_selected_index: usize,
+    pub project_detail_focus: ProjectDetailFocus,
+
+    // Calendar fields
+    pub calendar_sources: Vec<String>,
+    pub agenda_days: i64,
+    pub agenda_events: Vec<crate::services::calendar::CalendarEvent>,
+    pub agenda_selected_index: usize,
+    /// A `/agenda` fetch is running in the background
+    pub agenda_loading: bool,
+
+    // Snippet fields
+    pub snippets: Vec<crate::storage::Snippet>,
+    pub snippet_selected_index: usize,
+    /// The snippet view lists the clipboard history instead of saved snippets
+    pub snippets_show_clipboard: bool,
+
+    // Notification fields
+    pub notifications: crate::config::NotificationsConfig,
+    pub journal: crate::config::JournalConfig,
+    pub diary: crate::config::DiaryConfig,
+    /// Days of Kimi's diary listed in `/diary`, newest first
+    pub diary_entries: Vec<crate::storage::DiaryEntry>,
+    pub diary_selected_index: usize,
+    /// Recurring jobs from `[[schedule]]`; ones added with `/schedule` live in storage
+    pub schedule: Vec<crate::config::ScheduledJobConfig>,
+    pub feeds: crate::config::FeedsConfig,
+    /// Send command template from `[email] command`
+    pub email_command: String,
+    pub file_search: crate::config::FileSearchConfig,
+    /// Read-only connections for the query_database tool
+    pub databases: Vec<crate::config::DatabaseConfig>,
+    pub git: crate::config::GitConfig,
+    /// Work tree Kimi was launched in, when `[git]` is enabled
+    pub git_repo: Option<std::path::PathBuf>,
+    pub email_draft: Option<crate::services::email::EmailDraft>,
+    pub email_preview_scroll: u16,
+    /// Service call the agent asked for, waiting for the user's confirmation
+    pub home_assistant_call: Option<crate::services::homeassistant::ServiceCall>,
+    pub health: Vec<crate::services::health::ServiceHealth>,
+    pub health_checked_at: Option<std::time::Instant>,
+    pub health_checking: bool,
+    /// `[backup]`: scheduled snapshots and where they go
+    pub backup: crate::config::BackupConfig,
+    /// Newest snapshot in the backup directory, read when `/status` opens
+    pub last_backup: Option<std::time::SystemTime>,
+    pub usage: crate::config::UsageConfig,
+    /// `[calculator]`: offline arithmetic and unit conversion fast path
+    pub calculator: crate::config::CalculatorConfig,
+    /// `[finance]`: live exchange rates for currency conversions
+    pub finance: crate::config::FinanceConfig,
+    /// `[weather]`: provider, location and units of the weather fast path
+    pub weather: crate::config::WeatherConfig,
+    /// Last seven days of the usage ledger, loaded when `/usage` opens
+    pub usage_rows: Vec<crate::storage::UsageRecord>,
+    /// Tool calls shown in `/audit`, newest first
+    pub audit_records: Vec<crate::storage::ToolAuditRecord>,
+    pub audit_selected_index: usize,
+    /// Selected entry of `/sources`, which lists the last reply's numbered sources
+    pub sources_selected_index: usize,
+    /// Allow/deny lists, dry-run and auditing for agent tool calls
+    pub tools: crate::config::ToolsConfig,
+    pub usage_scroll: u16,
+    /// Storage statistics shown in `/dashboard`, loaded when it opens
+    pub dashboard: Option<crate::services::dashboard::DashboardData>,
+    /// Scored conversations shown in `/sentiment`, oldest first
+    pub sentiment_timeline: Vec<crate::storage::SentimentEntry>,
+    /// Rows of the newest-first conversation list scrolled past
+    pub sentiment_scroll: usize,
+    /// Quick actions for the URLs, paths and dates of the selected message
+    pub entity_actions: Vec<(crate::services::entities::Entity, crate::services::entities::EntityAction)>,
+    pub entity_action_index: usize,
+
+    // Log viewer fields
+    pub log_lines: Vec<String>,
+    pub log_scroll: usize,
+    pub last_log_refresh: Option<std::time::Instant>,
+
+    // Profile switcher fields
+    pub profiles: Vec<String>,
+    pub profile_selected_index: usize,
+    pub profile_create_active: bool,
+    pub profile_create_input: TextInput,
+
+    // OCR fields
+    pub ocr: crate::config::OcrConfig,
+
+    // Personality suggestion fields
+    pub personality_suggest_after: usize,
+    pub personality_suggestion: Option<crate::services::personality_tuning::PersonalitySuggestion>,
+    pub personality_suggestion_scroll: u16,
+    /// Keeps the personalities directory watched for hot-reload
+    pub personality_watcher: Option<notify::RecommendedWatcher>,
+    /// Text of the entry selected in the personality menu
+    pub personality_preview: String,
+    /// Recommended model and voice of the selected personality
+    pub personality_preview_meta: crate::services::personality::PersonalityMeta,
+    pub personality_sample: Option<PersonalitySample>,
+
+    // Session carry-over fields
+    pub carry_over_enabled: bool,
+    pub carry_over_window: Duration,
+    pub previous_session: Option<PreviousSession>,
+    pub carried_session_context: Option<String>,
+
+    // Check-in fields
+    pub check_ins_enabled: bool,
+    pub check_in_every_days: u64,
+    /// Recap conversations reopened from history (`conversation.resume_recap`)
+    pub resume_recap_enabled: bool,
+    /// Propose dated tasks from saved conversations (`conversation.extract_tasks`)
+    pub extract_tasks_enabled: bool,
+    /// Score saved conversations for `/sentiment` (`conversation.track_sentiment`)
+    pub track_sentiment_enabled: bool,
 }
 
 impl Default for App {
@@ -220,9 +623,40 @@ fn base_menu_items() -> Vec<MenuItem> {
     vec![
         menu_item("models", "Select models per agent"),
         menu_item("connect", "API token configuration"),
+        menu_item("settings", "Edit config.toml values (URLs, models, thresholds, toggles)"),
         menu_item("personality", "Manage personalities"),
+        menu_item("profile", "Switch between separate profiles"),
         menu_item("projects", "View tracked knowledge projects"),
-        menu_item("help", "Show keyboard shortcuts"),
+        menu_item("agenda", "Show upcoming calendar events"),
+        menu_item("snippets", "Saved code snippets and clipboard history"),
+        menu_item("search", "Full-text search across all conversations"),
+        menu_item("incognito", "Don't save, embed or reflect on this conversation (again to turn off)"),
+        menu_item("archive", "Show archived conversations in history (again to go back)"),
+        menu_item("prompt", "Inspect the system prompt for the next message"),
+        menu_item("downloads", "Active downloads and download history"),
+        menu_item("status", "Connection health of Ollama, embeddings and hosted APIs"),
+        menu_item("usage", "Tokens, estimated cost and budgets per provider and model"),
+        menu_item("language", "Language Kimi answers in for this conversation (type: language cs)"),
+        menu_item("capture", "Quick capture: save thoughts to memory and the Obsidian inbox without the model"),
+        menu_item("timer", "Countdown in the footer (type: timer 10m tea, or timer cancel)"),
+        menu_item("stopwatch", "Start or stop the stopwatch"),
+        menu_item("dashboard", "Conversations per week, memory growth, top topics and database size"),
+        menu_item("sentiment", "How conversations felt over time, with your and Kimi's emotions"),
+        menu_item("diary", "Kimi's daily diary of your conversations and its feelings"),
+        menu_item("schedule", "Recurring background jobs (type: schedule every morning at 8: weather + agenda + news briefing)"),
+        menu_item("speak-all", "Read the conversation aloud from the top of the view (Ctrl+O pause, Ctrl+R stop)"),
+        menu_item("inbox", "Reminders, digests and finished background jobs"),
+        menu_item("tasks", "Dated tasks found in conversations: accept, snooze, complete"),
+        menu_item("imagine", "Generate a picture and show it in the chat (type: imagine <prompt>)"),
+        menu_item("find", "Search files under your configured roots (type: find <pattern>)"),
+        menu_item("gh", "Your open PRs (type: gh reviews, gh issues or gh owner/repo#123)"),
+        menu_item("news", "Briefing on new items from your RSS/Atom feeds"),
+        menu_item("journal", "Add today's conversations to the Obsidian daily note (type: journal <text> in chat for an entry)"),
+        menu_item("as", "Speak as someone else (type: as <name>); alone switches back to you"),
+        menu_item("audit", "Every tool call the agent made, with arguments and results"),
+        menu_item("sources", "Notes, memories and pages the last reply drew on"),
+        menu_item("logs", "Tail the application log"),
+        menu_item("help", "Keyboard shortcuts, commands and providers (type to filter)"),
         menu_item("quit", "Exit the application"),
     ]
 }
@@ -261,8 +695,11 @@ impl App {
             chat_history_by_agent: HashMap::new(),
             chat_input: TextInput::new(),
             chat_attachments: Vec::new(),
+            attachment_panel_active: false,
+            attachment_selected_index: 0,
             next_attachment_id: 1,
             current_agent: None, // Will be set in init_services
+            headless: false,
             is_loading: false,
             is_searching: false,
             is_fetching_notes: false,
@@ -273,16 +710,29 @@ impl App {
             agent_rx: None,
             agent_tx: None,
             auto_tts_enabled: false,
+            tts_user_voice_id: None,
             chat_scroll_offset: 0,
             chat_auto_scroll: true, // Start with auto-scroll enabled
+            show_message_stats: false,
+            duplicate_question_similarity: 0.0,
+            duplicate_offer: None,
+            skip_duplicate_check: false,
+            duplicate_check_pending: None,
             available_models,
             selected_models,
             model_selection_index: 0,
             model_selection_items: Vec::new(),
+            model_details_cache: HashMap::new(),
+            gpu_vram_bytes: None,
+            gpu_vram_detected: false,
             connect_elevenlabs_key: String::new(),
             connect_venice_key: String::new(),
             connect_gab_key: String::new(),
             connect_brave_key: String::new(),
+            connect_github_key: String::new(),
+            connect_home_assistant_url: String::new(),
+            connect_home_assistant_token: String::new(),
+            connect_entering_url: false,
             connect_obsidian_vault: String::new(),
             connect_obsidian_vault_path: String::new(),
             connect_providers: vec![
@@ -290,6 +740,8 @@ impl App {
                 "Venice AI".to_string(),
                 "Gab AI".to_string(),
                 "Brave Search".to_string(),
+                "GitHub".to_string(),
+                "Home Assistant".to_string(),
                 "Obsidian".to_string(),
             ],
             connect_selected_provider: 0,
@@ -304,13 +756,55 @@ impl App {
             history_filter: TextInput::new(),
             history_filter_active: false,
             history_delete_all_active: false,
+            resume_prompt: None,
+            search_input: TextInput::new(),
+            capture_input: TextInput::new(),
+            captures: Vec::new(),
+            capture_conversation_id: None,
+            capture: crate::config::CaptureConfig::default(),
+            editor: crate::config::EditorConfig::default(),
+            editor_session: None,
+            settings_config: None,
+            settings_selected_index: 0,
+            settings_input: None,
+            settings_error: None,
+            help_filter: TextInput::new(),
+            help_scroll: 0,
+            loaded_config: None,
+            config_watcher: None,
+            search_groups: Vec::new(),
+            search_selected_index: 0,
+            search_last_query: String::new(),
+            search_error: None,
+            chat_scroll_target: None,
+            speak_all_requested: false,
+            message_selection_active: false,
+            selected_message_index: 0,
+            pending_quote: None,
+            disabled_prompt_sections: Vec::new(),
+            prompt_preview: None,
+            prompt_inspector_index: 0,
+            prompt_inspector_scroll: 0,
+            history_rename_active: false,
+            history_rename_input: TextInput::new(),
             history_delete_all_confirm_delete: false,
             history_has_more: false,
+            history_loading: false,
+            history_pending_select: None,
             history_page_size: 20,
+            history_show_archived: false,
+            incognito: false,
             storage: None,
             storage_runtime: None,
             is_generating_summary: false,
             current_conversation_id: None,
+            conversation_language: crate::services::languages::DEFAULT_LANGUAGE.to_string(),
+            default_language: crate::services::languages::DEFAULT_LANGUAGE.to_string(),
+            autosaved_message_count: 0,
+            last_autosave: None,
+            autosave_every_messages: 4,
+            autosave_interval: Duration::from_secs(30),
+            storage_worker: None,
             status_toast: None,
             clipboard_service: ClipboardService::new(),
             personality_enabled: false,
@@ -319,12 +813,32 @@ impl App {
             loading_frame: 0,
             last_loading_tick: None,
             active_downloads: Vec::new(),
+            inbox: Vec::new(),
+            inbox_selected_index: 0,
+            due_tasks: Vec::new(),
+            due_tasks_selected_index: 0,
+            unread_notifications: 0,
+            timers: Vec::new(),
+            stopwatch_started: None,
+            announce_timers: true,
+            speaker: None,
+            download_history: Vec::new(),
+            download_selected_index: 0,
             conversion_active: false,
             conversion_frame: 0,
+            conversion_done: 0,
+            conversion_total: 0,
             last_conversion_tick: None,
+            active_transcriptions: 0,
+            transcription_frame: 0,
+            last_transcription_tick: None,
             summary_active: false,
             summary_frame: 0,
             last_summary_tick: None,
+            images: crate::config::ImagesConfig::default(),
+            active_image_generations: 0,
+            image_frame: 0,
+            last_image_tick: None,
             cached_obsidian_notes: None,
             cached_recall_context: None,
             follow_up_suggestions: Vec::new(),
@@ -333,11 +847,84 @@ impl App {
             comfyui_process: None,
             projects: Vec::new(),
             project_entries: Vec::new(),
+            project_conversations: Vec::new(),
             project_selected_index: 0,
             project_entry_selected_index: 0,
             current_project_name: None,
             current_project_description: None,
             pending_project_suggestions: Vec::new(),
+            active_project: None,
+            project_form: None,
+            project_tasks: Vec::new(),
+            project_task_selected_index: 0,
+            project_detail_focus: ProjectDetailFocus::Entries,
+            calendar_sources: Vec::new(),
+            agenda_days: 7,
+            agenda_events: Vec::new(),
+            agenda_selected_index: 0,
+            agenda_loading: false,
+            snippets: Vec::new(),
+            snippet_selected_index: 0,
+            snippets_show_clipboard: false,
+            notifications: crate::config::NotificationsConfig::default(),
+            journal: crate::config::JournalConfig::default(),
+            diary: crate::config::DiaryConfig::default(),
+            diary_entries: Vec::new(),
+            diary_selected_index: 0,
+            schedule: Vec::new(),
+            feeds: crate::config::FeedsConfig::default(),
+            email_command: String::new(),
+            file_search: crate::config::FileSearchConfig::default(),
+            databases: Vec::new(),
+            git: crate::config::GitConfig::default(),
+            git_repo: None,
+            email_draft: None,
+            email_preview_scroll: 0,
+            home_assistant_call: None,
+            health: Vec::new(),
+            health_checked_at: None,
+            backup: crate::config::BackupConfig::default(),
+            last_backup: None,
+            health_checking: false,
+            usage: crate::config::UsageConfig::default(),
+            calculator: crate::config::CalculatorConfig::default(),
+            finance: crate::config::FinanceConfig::default(),
+            weather: crate::config::WeatherConfig::default(),
+            usage_rows: Vec::new(),
+            audit_records: Vec::new(),
+            audit_selected_index: 0,
+            sources_selected_index: 0,
+            tools: crate::config::ToolsConfig::default(),
+            usage_scroll: 0,
+            dashboard: None,
+            sentiment_timeline: Vec::new(),
+            sentiment_scroll: 0,
+            entity_actions: Vec::new(),
+            entity_action_index: 0,
+            log_lines: Vec::new(),
+            log_scroll: 0,
+            last_log_refresh: None,
+            profiles: Vec::new(),
+            profile_selected_index: 0,
+            profile_create_active: false,
+            profile_create_input: TextInput::new(),
+            ocr: crate::config::OcrConfig::default(),
+            personality_suggest_after: 10,
+            personality_suggestion: None,
+            personality_suggestion_scroll: 0,
+            personality_watcher: None,
+            personality_preview: String::new(),
+            personality_preview_meta: crate::services::personality::PersonalityMeta::default(),
+            personality_sample: None,
+            carry_over_enabled: true,
+            carry_over_window: Duration::from_secs(30 * 60),
+            previous_session: None,
+            carried_session_context: None,
+            check_ins_enabled: true,
+            check_in_every_days: 7,
+            resume_recap_enabled: true,
+            extract_tasks_enabled: true,
+            track_sentiment_enabled: true,
         }
     }
 
@@ -353,19 +940,8 @@ impl App {
             }
         }
         self.agent_manager = Some(AgentManager::new(&agent_config));
-        self.connect_venice_key = config.venice.api_key.clone();
-        self.connect_gab_key = config.gab.api_key.clone();
-        self.connect_brave_key = config.brave.api_key.clone();
-        self.connect_obsidian_vault = config.obsidian.vault_name.clone();
-        self.connect_obsidian_vault_path = config.obsidian.vault_path.clone();
-        if let Some(manager) = &mut self.agent_manager {
-            if !self.connect_venice_key.is_empty() {
-                manager.set_venice_api_key(self.connect_venice_key.clone());
-            }
-            if !self.connect_gab_key.is_empty() {
-                manager.set_gab_api_key(self.connect_gab_key.clone());
-            }
-        }
+        self.apply_config(config);
+        self.conversation_language = self.default_language.clone();
         self.tts_service = Some(TTSService::new(
             config.elevenlabs.api_key.clone(),
             config.elevenlabs.voice_id.clone(),
@@ -375,8 +951,22 @@ impl App {
         let _ = self.ensure_storage();
 
         let (tx, rx) = channel();
+        let watcher_tx = tx.clone();
+        let config_tx = tx.clone();
         self.agent_tx = Some(tx);
         self.agent_rx = Some(rx);
+        self.personality_watcher = crate::services::personality::watch_personalities(move || {
+            let _ = watcher_tx.send(AgentEvent::PersonalitiesChanged);
+        })
+        .inspect_err(|error| tracing::warn!(%error, "personality hot-reload unavailable"))
+        .ok();
+        self.loaded_config = Some(config.clone());
+        self.config_watcher = crate::services::settings::watch_config(move || {
+            let _ = config_tx.send(AgentEvent::ConfigChanged);
+        })
+        .inspect_err(|error| tracing::warn!(%error, "config hot-reload unavailable"))
+        .ok();
+        self.attach_usage_ledger();
 
         let _ = self.refresh_available_models();
         self.load_selected_models_from_config(config);
@@ -387,6 +977,77 @@ impl App {
         }
     }
 
+    /// Copies the settings that take effect without a restart; runs at startup, when
+    /// `/settings` saves a change and when the config file changes on disk
+    pub fn apply_config(&mut self, config: &Config) {
+        self.connect_venice_key = config.venice.api_key.clone();
+        self.connect_gab_key = config.gab.api_key.clone();
+        self.connect_brave_key = config.brave.api_key.clone();
+        self.connect_github_key = config.github.api_key.clone();
+        self.connect_home_assistant_url = config.homeassistant.url.clone();
+        self.connect_home_assistant_token = config.homeassistant.api_key.clone();
+        self.connect_obsidian_vault = config.obsidian.vault_name.clone();
+        self.connect_obsidian_vault_path = config.obsidian.vault_path.clone();
+        self.calendar_sources = config.calendar.sources.clone();
+        self.agenda_days = config.calendar.agenda_days.max(1);
+        self.notifications = config.notifications.clone();
+        self.journal = config.journal.clone();
+        self.diary = config.diary.clone();
+        self.schedule = config.schedule.clone();
+        self.feeds = config.feeds.clone();
+        self.email_command = config.email.command.clone();
+        self.file_search = config.files.clone();
+        self.databases = config.databases.clone();
+        self.git = config.git.clone();
+        self.usage = config.usage.clone();
+        self.calculator = config.calculator.clone();
+        self.finance = config.finance.clone();
+        self.weather = config.weather.clone();
+        self.tools = config.tools.clone();
+        self.announce_timers = config.timers.announce;
+        self.capture = config.capture.clone();
+        self.editor = config.editor.clone();
+        self.backup = config.backup.clone();
+        self.images = config.images.clone();
+        self.git_repo = if self.git.enabled {
+            std::env::current_dir()
+                .ok()
+                .and_then(|dir| crate::services::git_context::discover_repo(&dir))
+        } else {
+            None
+        };
+        self.personality_suggest_after = config.personality.suggest_after_conversations;
+        self.ocr = config.ocr.clone();
+        self.carry_over_enabled = config.conversation.carry_over;
+        self.carry_over_window =
+            Duration::from_secs(config.conversation.carry_over_window_minutes.saturating_mul(60));
+        self.check_ins_enabled = config.conversation.check_ins;
+        self.check_in_every_days = config.conversation.check_in_every_days;
+        self.resume_recap_enabled = config.conversation.resume_recap;
+        self.extract_tasks_enabled = config.conversation.extract_tasks;
+        self.track_sentiment_enabled = config.conversation.track_sentiment;
+        self.show_message_stats = config.conversation.show_message_stats;
+        self.duplicate_question_similarity = config.conversation.duplicate_question_similarity;
+        self.default_language = crate::services::languages::normalize(&config.conversation.language)
+            .unwrap_or(crate::services::languages::DEFAULT_LANGUAGE)
+            .to_string();
+        self.autosave_every_messages = config.conversation.autosave_every_messages.max(1);
+        self.autosave_interval = Duration::from_secs(config.conversation.autosave_interval_seconds);
+        if let Some(manager) = &mut self.agent_manager {
+            if !self.connect_venice_key.is_empty() {
+                manager.set_venice_api_key(self.connect_venice_key.clone());
+            }
+            if !self.connect_gab_key.is_empty() {
+                manager.set_gab_api_key(self.connect_gab_key.clone());
+            }
+        }
+        self.tts_user_voice_id = config
+            .elevenlabs
+            .user_voice_id
+            .clone()
+            .filter(|voice| !voice.trim().is_empty());
+    }
+
     pub fn execute_command(&mut self, command: &str) -> Result<()> {
         // Clear menu input when executing any command
         self.input.clear();
@@ -426,11 +1087,183 @@ impl App {
             return Ok(());
         }
 
+        if command == "settings" {
+            self.open_settings()?;
+            return Ok(());
+        }
+
         if command == "projects" {
             self.open_projects()?;
             return Ok(());
         }
 
+        if command == "snippets" {
+            self.open_snippets()?;
+            return Ok(());
+        }
+
+        if command == "profile" {
+            self.open_profile_selection()?;
+            return Ok(());
+        }
+
+        if command == "search" {
+            self.open_search();
+            return Ok(());
+        }
+
+        if command == "prompt" {
+            return self.open_prompt_inspector();
+        }
+
+        if command == "archive" {
+            return self.toggle_archive_view();
+        }
+
+        if command == "incognito" {
+            self.close_menu();
+            self.toggle_incognito();
+            return Ok(());
+        }
+
+        if command == "downloads" {
+            return self.open_downloads();
+        }
+
+        if command == "status" {
+            self.open_status();
+            return Ok(());
+        }
+
+        if command == "usage" {
+            self.open_usage()?;
+            return Ok(());
+        }
+
+        if command == "audit" {
+            self.open_audit()?;
+            return Ok(());
+        }
+
+        if command == "sources" {
+            self.open_sources();
+            return Ok(());
+        }
+
+        if command == "language" {
+            self.close_menu();
+            self.add_system_message(&format!(
+                "Replying in {}. Usage: / then language <code> ({})",
+                crate::services::languages::name(&self.conversation_language),
+                crate::services::languages::supported_codes()
+            ));
+            return Ok(());
+        }
+
+        if command == "capture" {
+            self.open_capture();
+            return Ok(());
+        }
+
+        if command == "timer" {
+            self.close_menu();
+            let running: Vec<String> = self
+                .timers
+                .iter()
+                .map(|timer| format!("{} ({} left)", timer.name(), crate::services::timers::format_remaining(timer.remaining())))
+                .collect();
+            if running.is_empty() {
+                self.add_system_message("No timers running. Usage: / then timer <duration> [label], e.g. timer 10m tea");
+            } else {
+                self.add_system_message(&format!("Timers: {}. / then timer cancel stops them", running.join(", ")));
+            }
+            return Ok(());
+        }
+
+        if command == "stopwatch" {
+            self.close_menu();
+            self.toggle_stopwatch();
+            return Ok(());
+        }
+
+        if command == "dashboard" {
+            return self.open_dashboard();
+        }
+
+        if command == "sentiment" {
+            return self.open_sentiment();
+        }
+
+        if command == "diary" {
+            return self.open_diary();
+        }
+
+        if command == "schedule" {
+            self.close_menu();
+            self.list_scheduled_jobs();
+            return Ok(());
+        }
+
+        if command == "speak-all" {
+            self.close_menu();
+            self.speak_all_requested = true;
+            return Ok(());
+        }
+
+        if command == "inbox" {
+            return self.open_inbox();
+        }
+
+        if command == "tasks" {
+            return self.open_tasks();
+        }
+
+        if command == "imagine" {
+            self.close_menu();
+            self.add_system_message("Usage: / then imagine <prompt>");
+            return Ok(());
+        }
+
+        if command == "find" {
+            self.close_menu();
+            self.add_system_message("Usage: / then find <pattern>");
+            return Ok(());
+        }
+
+        if command == "gh" {
+            self.close_menu();
+            self.start_github_query(String::new());
+            return Ok(());
+        }
+
+        if command == "news" {
+            self.close_menu();
+            self.start_news_briefing();
+            return Ok(());
+        }
+
+        if command == "journal" {
+            self.close_menu();
+            self.write_journal_entry(None);
+            return Ok(());
+        }
+
+        if command == "as" {
+            self.set_speaker("");
+            self.close_menu();
+            return Ok(());
+        }
+
+        if command == "logs" {
+            self.open_log_viewer();
+            return Ok(());
+        }
+
+        if command == "agenda" {
+            self.open_agenda();
+            return Ok(());
+        }
+
         if let Some(handler) = self.command_handlers.get(command) {
             let result = handler()?;
             if command == "quit" {
@@ -445,6 +1278,56 @@ impl App {
     }
 
     pub fn execute_selected(&mut self) -> Result<()> {
+        // "as <name>" carries an argument, so it is read from the typed input
+        if let Some(name) = self.input.strip_prefix("as ") {
+            let name = name.trim().to_string();
+            self.set_speaker(&name);
+            self.close_menu();
+            return Ok(());
+        }
+        if let Some(prompt) = self.input.strip_prefix("imagine ") {
+            let prompt = prompt.trim().to_string();
+            self.close_menu();
+            self.start_image_generation(prompt);
+            return Ok(());
+        }
+        if let Some(pattern) = self.input.strip_prefix("find ") {
+            let pattern = pattern.trim().to_string();
+            self.close_menu();
+            self.start_file_search(pattern);
+            return Ok(());
+        }
+        if let Some(language) = self.input.strip_prefix("language ") {
+            let language = language.trim().to_string();
+            self.close_menu();
+            self.set_conversation_language(&language);
+            return Ok(());
+        }
+        if let Some(arguments) = self.input.strip_prefix("timer ") {
+            let arguments = arguments.trim().to_string();
+            self.close_menu();
+            if arguments == "cancel" {
+                self.cancel_timers();
+            } else if let Some(request) = crate::services::timers::parse_timer_command(&arguments) {
+                let reply = self.start_timer(request);
+                self.add_system_message(&reply);
+            } else {
+                self.add_system_message("Usage: / then timer <duration> [label], e.g. timer 10m tea or timer 1h30m");
+            }
+            return Ok(());
+        }
+        if let Some(arguments) = self.input.strip_prefix("schedule ") {
+            let arguments = arguments.trim().to_string();
+            self.close_menu();
+            self.handle_schedule_arguments(&arguments);
+            return Ok(());
+        }
+        if let Some(query) = self.input.strip_prefix("gh ") {
+            let query = query.trim().to_string();
+            self.close_menu();
+            self.start_github_query(query);
+            return Ok(());
+        }
         let filtered = self.filtered_items();
         if let Some(item) = filtered.get(self.selected_index) {
             let command = item.name.clone();
@@ -477,11 +1360,134 @@ impl App {
             return false;
         };
         self.storage = runtime.block_on(async {
-            StorageManager::new().await.ok()
+            StorageManager::new()
+                .await
+                .map_err(|error| tracing::error!(%error, "failed to open storage"))
+                .ok()
         });
+        if self.storage.as_ref().is_some_and(StorageManager::is_read_only) {
+            self.show_status_toast("READ-ONLY: ANOTHER KIMI HAS THE DATABASE OPEN");
+        }
         self.storage.is_some()
     }
 
+    /// Runs the monthly (configurable) index rebuild in the background when it is due
+    pub fn maybe_spawn_scheduled_reindex(&mut self, interval_days: u64) {
+        if !crate::services::maintenance::is_reindex_due(interval_days) || !self.ensure_storage() {
+            return;
+        }
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let tx = self.agent_tx.clone();
+        std::thread::spawn(move || {
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            let result = runtime.block_on(crate::services::maintenance::run_reindex(&storage));
+            let (title, message) = match result {
+                Ok(report) => (
+                    "Index rebuild finished".to_string(),
+                    format!(
+                        "{} orphaned message(s) removed, {} stale embedding(s) queued for backfill. \
+Keyword search {:.1} ms → {:.1} ms",
+                        report.orphaned_messages_removed,
+                        report.stale_embeddings_cleared,
+                        report.before.keyword_ms,
+                        report.after.keyword_ms
+                    ),
+                ),
+                Err(error) => ("Index rebuild failed".to_string(), error.to_string()),
+            };
+            if let Some(tx) = tx {
+                let _ = tx.send(AgentEvent::Notification {
+                    kind: crate::services::notifications::NotificationKind::Embeddings,
+                    title,
+                    message,
+                });
+            }
+        });
+    }
+
+    /// With `[backup] enabled`, snapshots the database every `interval_hours` while kimi runs
+    pub fn spawn_backup_scheduler(&mut self) {
+        if !self.backup.enabled || !self.ensure_storage() {
+            return;
+        }
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let config = self.backup.clone();
+        let tx = self.agent_tx.clone();
+        std::thread::spawn(move || {
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            loop {
+                let due = crate::services::backup::backup_dir(&config)
+                    .is_ok_and(|directory| crate::services::backup::is_due(&directory, config.interval_hours));
+                if due {
+                    match runtime.block_on(crate::services::backup::run_scheduled_backup(&storage, &config)) {
+                        Ok(summary) => tracing::info!(path = %summary.path.display(), "scheduled backup written"),
+                        Err(error) => {
+                            tracing::error!(%error, "scheduled backup failed");
+                            let sent = tx.as_ref().is_some_and(|tx| {
+                                tx.send(AgentEvent::Notification {
+                                    kind: crate::services::notifications::NotificationKind::Job,
+                                    title: "Scheduled backup failed".to_string(),
+                                    message: error.to_string(),
+                                })
+                                .is_ok()
+                            });
+                            // The app has quit once the channel is closed
+                            if !sent {
+                                return;
+                            }
+                        }
+                    }
+                }
+                std::thread::sleep(crate::services::backup::CHECK_INTERVAL);
+            }
+        });
+    }
+
+    /// Deletes expired ephemeral conversations now and then every `RETENTION_CHECK_INTERVAL`
+    pub fn spawn_retention_scheduler(&mut self) {
+        if !self.ensure_storage() {
+            return;
+        }
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            loop {
+                match runtime.block_on(crate::services::maintenance::expire_conversations(&storage)) {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!(count, "deleted expired ephemeral conversations"),
+                    Err(error) => tracing::warn!(%error, "deleting expired conversations failed"),
+                }
+                std::thread::sleep(crate::services::maintenance::RETENTION_CHECK_INTERVAL);
+            }
+        });
+    }
+
+    /// Archives conversations untouched for `archive_after_days` (0 disables) in the background
+    pub fn maybe_archive_old_conversations(&mut self, archive_after_days: u64) {
+        if archive_after_days == 0 {
+            return;
+        }
+        self.spawn_storage_job(move |storage, runtime| {
+            match runtime.block_on(crate::services::maintenance::archive_older_than(storage, archive_after_days)) {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, days = archive_after_days, "archived old conversations"),
+                Err(error) => tracing::warn!(%error, "archiving old conversations failed"),
+            }
+        });
+    }
+
     /// Returns a reference to storage and its runtime, or an error if either is missing.
     /// Reduces the common `storage.as_ref().ok_or(...)` + `storage_runtime().ok_or(...)` boilerplate.
     pub(crate) fn storage_with_runtime(
@@ -528,7 +1534,11 @@ impl App {
                     self.current_project_name = Some(file.name);
                     self.current_project_description = Some(file.description);
                     self.project_entries = file.entries;
+                    self.project_conversations = file.conversations;
                     self.project_entry_selected_index = 0;
+                    self.project_task_selected_index = 0;
+                    self.project_detail_focus = ProjectDetailFocus::Entries;
+                    self.load_project_tasks(&name);
                     self.mode = AppMode::ProjectDetail;
                 }
                 Err(error) => {
@@ -543,6 +1553,9 @@ impl App {
         self.current_project_name = None;
         self.current_project_description = None;
         self.project_entries.clear();
+        self.project_conversations.clear();
+        self.project_tasks.clear();
+        self.load_project_list();
     }
 
     pub fn close_projects(&mut self) {
@@ -582,6 +1595,70 @@ impl App {
         }
     }
 
+    // ── Agenda navigation ───────────────────────────────────────────────────
+
+    /// Fetches the calendar on a background thread; the view opens on `AgendaLoaded`
+    pub fn open_agenda(&mut self) {
+        if self.calendar_sources.is_empty() {
+            self.show_status_toast("NO CALENDAR CONFIGURED");
+            return;
+        }
+        if self.agenda_loading {
+            return;
+        }
+        let Some(tx) = self.agent_tx.clone() else {
+            return;
+        };
+        self.agenda_loading = true;
+        self.show_status_toast("LOADING AGENDA");
+        let sources = self.calendar_sources.clone();
+        let days = self.agenda_days;
+        std::thread::spawn(move || {
+            let today = chrono::Local::now().date_naive();
+            let result = crate::services::calendar::load_upcoming_events(&sources, today, days)
+                .map_err(|error| error.to_string());
+            let _ = tx.send(AgentEvent::AgendaLoaded { result });
+        });
+    }
+
+    pub(crate) fn handle_agenda_loaded(
+        &mut self,
+        result: std::result::Result<Vec<crate::services::calendar::CalendarEvent>, String>,
+    ) {
+        self.agenda_loading = false;
+        match result {
+            Ok(events) => {
+                self.agenda_events = events;
+                self.agenda_selected_index = 0;
+                self.mode = AppMode::Agenda;
+            }
+            Err(error) => {
+                self.show_status_toast(format!("Error: {}", error));
+            }
+        }
+    }
+
+    pub fn close_agenda(&mut self) {
+        self.mode = AppMode::Chat;
+        self.agenda_events.clear();
+    }
+
+    pub fn next_agenda_event(&mut self) {
+        if !self.agenda_events.is_empty() {
+            self.agenda_selected_index =
+                (self.agenda_selected_index + 1) % self.agenda_events.len();
+        }
+    }
+
+    pub fn previous_agenda_event(&mut self) {
+        if !self.agenda_events.is_empty() {
+            self.agenda_selected_index = self
+                .agenda_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.agenda_events.len().saturating_sub(1));
+        }
+    }
+
     pub fn show_status_toast(&mut self, message: impl Into<String>) {
         self.status_toast = Some(StatusToast::new(message));
     }
@@ -601,14 +1678,6 @@ impl App {
         self.status_toast.as_ref().map(|toast| toast.message.as_str())
     }
 
-    pub fn last_assistant_message(&self) -> Option<&str> {
-        self.chat_history
-            .iter()
-            .rev()
-            .find(|message| message.role == MessageRole::Assistant)
-            .map(|message| message.content.as_str())
-    }
-
     pub fn toggle_personality(&mut self) {
         self.personality_enabled = !self.personality_enabled;
         if self.personality_enabled {
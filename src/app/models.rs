@@ -4,14 +4,14 @@ use color_eyre::Result;
 use std::collections::HashMap;
 
 impl App {
-    fn persist_selected_model(&self, agent_name: &str, model_name: &str) -> Result<()> {
+    fn persist_selected_model(&mut self, agent_name: &str, model_name: &str) -> Result<()> {
         let mut config = crate::config::Config::load()?;
         if agent_name == "embeddings" {
             config.embeddings.model = model_name.to_string();
-            config.save()?;
+            self.save_config(&config)?;
         } else if let Some(agent_config) = config.agents.get_mut(agent_name) {
             agent_config.model = model_name.to_string();
-            config.save()?;
+            self.save_config(&config)?;
         }
         Ok(())
     }
@@ -23,6 +23,20 @@ impl App {
             .ok_or_else(|| color_eyre::eyre::eyre!("Agent manager not initialized"))?;
 
         let installed_models = manager.list_models()?;
+        let model_sizes = manager.list_model_sizes().unwrap_or_default();
+        for model_name in &installed_models {
+            if self.model_details_cache.contains_key(model_name) {
+                continue;
+            }
+            let mut details = manager.show_model(model_name).unwrap_or_default();
+            details.size_bytes = model_sizes.get(model_name).copied();
+            self.model_details_cache.insert(model_name.clone(), details);
+        }
+        if !self.gpu_vram_detected {
+            self.gpu_vram_bytes = crate::services::gpu::detect_vram_bytes();
+            self.gpu_vram_detected = true;
+        }
+
         let venice_models = fetch_venice_models(&self.connect_venice_key);
         let gab_models = fetch_gab_models(&self.connect_gab_key);
 
@@ -30,7 +44,8 @@ impl App {
             HashMap::new();
         let agent_order = ["embeddings", "translate", "chat", "routing"];
         for agent_name in agent_order {
-            let mut models = build_ollama_models(agent_name, &installed_models);
+            let mut models =
+                build_ollama_models(agent_name, &installed_models, &self.model_details_cache);
             if agent_name != "routing" && agent_name != "embeddings" && agent_name != "translate" {
                 if let Some(venice_models) = &venice_models {
                     for model_name in venice_models {
@@ -38,6 +53,7 @@ impl App {
                             name: model_name.clone(),
                             source: ModelSource::VeniceAPI,
                             is_available: true,
+                            details: None,
                         });
                     }
                 }
@@ -49,6 +65,7 @@ impl App {
                             name: model_name.clone(),
                             source: ModelSource::GabAI,
                             is_available: true,
+                            details: None,
                         });
                     }
                 }
@@ -224,6 +241,7 @@ impl App {
 fn build_ollama_models(
     agent_name: &str,
     installed_models: &[String],
+    details: &HashMap<String, crate::agents::ollama::ModelDetails>,
 ) -> Vec<crate::app::AvailableModel> {
     let mut models = Vec::new();
     let is_routing_agent = agent_name == "routing";
@@ -260,6 +278,7 @@ fn build_ollama_models(
             name: model_name.to_string(),
             source: ModelSource::Ollama,
             is_available: true,
+            details: details.get(model_name).cloned(),
         });
     }
     models
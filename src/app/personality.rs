@@ -1,16 +1,24 @@
-use crate::app::{App, AppMode, Navigable, TextInput};
+use crate::agents::ChatMessage as AgentChatMessage;
+use crate::app::types::PersonalitySample;
+use crate::app::{AgentEvent, App, AppMode, Navigable, TextInput};
 use crate::config::Config;
 use color_eyre::Result;
 
 const BASE_PERSONALITY_INDEX: usize = 0;
 const MY_PERSONALITY_INDEX: usize = 1;
 const PERSONALITY_ITEMS_OFFSET: usize = 2;
+const SUGGESTION_MESSAGE_SAMPLE: usize = 30;
+/// Message the "test message" action sends to show a personality's reply style
+pub(crate) const SAMPLE_TEST_MESSAGE: &str =
+    "Long day. I think I broke the build and I'm out of coffee. Cheer me up?";
 
 impl App {
     pub fn open_personality_menu(&mut self) -> Result<()> {
         self.mode = AppMode::PersonalitySelection;
         self.personality_create_input.clear();
+        self.personality_suggestion = crate::services::personality_tuning::load_pending_suggestion();
         self.reload_personality_items()?;
+        self.refresh_personality_preview();
         Ok(())
     }
 
@@ -43,27 +51,20 @@ impl App {
         self.personality_create_input.clear();
         self.reload_personality_items()?;
         self.set_active_personality(&name)?;
+        self.refresh_personality_preview();
         self.mode = AppMode::PersonalitySelection;
         Ok(())
     }
 
     pub fn edit_selected_personality(&mut self) -> Result<()> {
         if self.personality_selected_index == BASE_PERSONALITY_INDEX {
-            if let Err(error) =
-                crate::services::personality::open_base_personality_in_new_terminal()
-            {
-                crate::services::personality::open_base_personality_in_place()?;
-                self.add_system_message(&format!("Base personality editor error: {}", error));
-            }
+            let path = crate::services::personality::ensure_base_personality()?;
+            self.edit_file(path, "Base personality");
             return Ok(());
         }
         if self.personality_selected_index == MY_PERSONALITY_INDEX {
-            if let Err(error) =
-                crate::services::personality::open_my_personality_in_new_terminal()
-            {
-                crate::services::personality::open_my_personality_in_place()?;
-                self.add_system_message(&format!("My personality editor error: {}", error));
-            }
+            let path = crate::services::personality::ensure_my_personality()?;
+            self.edit_file(path, "My personality");
             return Ok(());
         }
         let name = self
@@ -71,10 +72,8 @@ impl App {
             .get(self.personality_selected_index.saturating_sub(PERSONALITY_ITEMS_OFFSET))
             .cloned()
             .unwrap_or_else(crate::services::personality::default_personality_name);
-        if let Err(error) = crate::services::personality::open_personality_in_new_terminal(&name) {
-            crate::services::personality::open_personality_in_place(&name)?;
-            self.add_system_message(&format!("Personality editor error: {}", error));
-        }
+        let path = crate::services::personality::ensure_personality(&name)?;
+        self.edit_file(path, &name);
         Ok(())
     }
 
@@ -107,6 +106,7 @@ impl App {
         if self.personality_selected_index >= total_items && self.personality_selected_index > 0 {
             self.personality_selected_index = total_items.saturating_sub(1);
         }
+        self.refresh_personality_preview();
 
         Ok(())
     }
@@ -117,12 +117,8 @@ impl App {
             return Ok(());
         }
         if self.personality_selected_index == MY_PERSONALITY_INDEX {
-            if let Err(error) =
-                crate::services::personality::open_my_personality_in_new_terminal()
-            {
-                crate::services::personality::open_my_personality_in_place()?;
-                self.add_system_message(&format!("My personality editor error: {}", error));
-            }
+            let path = crate::services::personality::ensure_my_personality()?;
+            self.edit_file(path, "My personality");
             return Ok(());
         }
         if let Some(name) = self
@@ -163,19 +159,244 @@ impl App {
         Ok(())
     }
 
+    /// Duplicates the selected personality and opens the copy in the editor
+    pub fn clone_selected_personality(&mut self) -> Result<()> {
+        if self.personality_selected_index < PERSONALITY_ITEMS_OFFSET {
+            self.show_status_toast("ONLY NAMED PERSONALITIES CAN BE CLONED");
+            return Ok(());
+        }
+        let clone_name =
+            crate::services::personality_share::clone_personality(&self.selected_personality_name())?;
+        let mut items = crate::services::personality::list_personalities()?;
+        items.sort();
+        self.personality_items = items;
+        if let Some(index) = self.personality_items.iter().position(|name| *name == clone_name) {
+            self.personality_selected_index = index + PERSONALITY_ITEMS_OFFSET;
+        }
+        self.refresh_personality_preview();
+        self.show_status_toast(format!("CLONED AS {}", clone_name.to_uppercase()));
+        self.edit_selected_personality()
+    }
+
+    /// Name of the personality file behind the selected menu entry
+    fn selected_personality_name(&self) -> String {
+        match self.personality_selected_index {
+            BASE_PERSONALITY_INDEX => crate::services::personality::base_personality_name(),
+            MY_PERSONALITY_INDEX => crate::services::personality::my_personality_name(),
+            index => self
+                .personality_items
+                .get(index.saturating_sub(PERSONALITY_ITEMS_OFFSET))
+                .cloned()
+                .unwrap_or_else(crate::services::personality::default_personality_name),
+        }
+    }
+
+    /// Loads the text shown in the preview pane for the selected entry
+    pub(crate) fn refresh_personality_preview(&mut self) {
+        let text = match self.personality_selected_index {
+            BASE_PERSONALITY_INDEX => crate::services::personality::read_base_personality(),
+            MY_PERSONALITY_INDEX => crate::services::personality::read_my_personality(),
+            _ => crate::services::personality::read_personality(&self.selected_personality_name()),
+        };
+        self.personality_preview = text.unwrap_or_else(|error| format!("Could not read: {}", error));
+        self.personality_preview_meta = if self.personality_selected_index >= PERSONALITY_ITEMS_OFFSET {
+            crate::services::personality::read_personality_meta(&self.selected_personality_name())
+                .unwrap_or_default()
+        } else {
+            crate::services::personality::PersonalityMeta::default()
+        };
+    }
+
+    /// Applies edits made in an external editor without reselecting anything: the
+    /// active personality, the base prompt and the personality menu are reloaded
+    pub(crate) fn reload_personalities_from_disk(&mut self) {
+        let Ok(mut items) = crate::services::personality::list_personalities() else {
+            return;
+        };
+        items.sort();
+        if items != self.personality_items {
+            self.personality_items = items;
+            let last_index = self.personality_items.len() + PERSONALITY_ITEMS_OFFSET - 1;
+            self.personality_selected_index = self.personality_selected_index.min(last_index);
+        }
+
+        // Only re-read files that still exist; reading a removed one would recreate it
+        if self.personality_text.is_some()
+            && let Some(name) = self.personality_name.clone()
+            && self.personality_items.contains(&name)
+            && let Ok(text) = crate::services::personality::read_personality(&name)
+        {
+            self.personality_text = Some(text);
+        }
+
+        if let Ok(base) = crate::services::personality::read_base_personality()
+            && !base.trim().is_empty()
+        {
+            let base = base.trim();
+            if let Some(manager) = &mut self.agent_manager {
+                manager.set_system_prompt("chat", base);
+            }
+            if let Some(agent) = &mut self.current_agent
+                && agent.name == "chat"
+            {
+                agent.system_prompt = base.to_string();
+            }
+        }
+
+        if matches!(
+            self.mode,
+            AppMode::PersonalitySelection | AppMode::PersonalityCreate
+        ) {
+            self.refresh_personality_preview();
+        }
+        self.show_status_toast("PERSONALITIES RELOADED");
+    }
+
+    /// Asks the model for a reply to a fixed sample message in the selected
+    /// personality's voice, shown under the preview
+    pub fn test_selected_personality(&mut self) -> Result<()> {
+        if self.personality_selected_index == MY_PERSONALITY_INDEX {
+            self.show_status_toast("MY PERSONALITY DESCRIBES YOU, NOT KIMI");
+            return Ok(());
+        }
+        let (mut agent, manager, agent_tx) = self.get_agent_chat_dependencies()?;
+        let name = self.selected_personality_name();
+        if self.personality_selected_index != BASE_PERSONALITY_INDEX {
+            agent.system_prompt = format!("{}\n\n{}", agent.system_prompt, self.personality_preview.trim());
+        }
+        self.personality_sample = Some(PersonalitySample {
+            personality: name.clone(),
+            reply: None,
+        });
+
+        std::thread::spawn(move || {
+            let messages = [
+                AgentChatMessage::system(agent.system_prompt.clone()),
+                AgentChatMessage::user(SAMPLE_TEST_MESSAGE),
+            ];
+            let reply = match manager.chat(&agent, &messages) {
+                Ok(reply) => reply.trim().to_string(),
+                Err(error) => format!("Sample failed: {}", error),
+            };
+            let _ = agent_tx.send(AgentEvent::PersonalitySampleReady {
+                personality: name,
+                reply,
+            });
+        });
+        Ok(())
+    }
+
     fn set_active_personality(&mut self, name: &str) -> Result<()> {
         self.personality_name = Some(name.to_string());
         self.personality_text = None;
 
         if let Ok(mut config) = Config::load() {
             config.personality.selected = name.to_string();
-            let _ = config.save();
+            let _ = self.save_config(&config);
         }
         self.show_status_toast("PERSONALITY SET");
         Ok(())
     }
 }
 
+impl App {
+    /// Counts the finished conversation and, once enough have passed, asks the
+    /// model in the background for adjustments to the active personality
+    pub(crate) fn maybe_spawn_personality_suggestion(&self) {
+        let Ok(is_due) =
+            crate::services::personality_tuning::record_conversation(self.personality_suggest_after)
+        else {
+            return;
+        };
+        if !is_due {
+            return;
+        }
+        let (Some(manager), Some(agent), Some(tx)) = (
+            self.agent_manager.clone(),
+            self.current_agent.clone(),
+            self.agent_tx.clone(),
+        ) else {
+            return;
+        };
+        let personality = self
+            .personality_name
+            .clone()
+            .unwrap_or_else(crate::services::personality::default_personality_name);
+        let recent_user_messages = self
+            .storage_with_runtime()
+            .ok()
+            .and_then(|(storage, runtime)| {
+                runtime
+                    .block_on(storage.load_recent_user_messages(SUGGESTION_MESSAGE_SAMPLE))
+                    .ok()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|message| message.content)
+            .collect::<Vec<_>>();
+
+        std::thread::spawn(move || {
+            if crate::services::personality_tuning::generate_suggestion(
+                &manager,
+                &agent,
+                &personality,
+                &recent_user_messages,
+            )
+            .is_ok()
+            {
+                let _ = tx.send(AgentEvent::PersonalitySuggestionReady { personality });
+            }
+        });
+    }
+
+    pub fn open_personality_suggestion(&mut self) {
+        if self.personality_suggestion.is_none() {
+            self.show_status_toast("NO PENDING SUGGESTION");
+            return;
+        }
+        self.personality_suggestion_scroll = 0;
+        self.mode = AppMode::PersonalitySuggestion;
+    }
+
+    pub fn close_personality_suggestion(&mut self) {
+        self.mode = AppMode::PersonalitySelection;
+    }
+
+    pub fn scroll_personality_suggestion_down(&mut self) {
+        self.personality_suggestion_scroll = self.personality_suggestion_scroll.saturating_add(1);
+    }
+
+    pub fn scroll_personality_suggestion_up(&mut self) {
+        self.personality_suggestion_scroll = self.personality_suggestion_scroll.saturating_sub(1);
+    }
+
+    /// Writes the proposed text to the personality file
+    pub fn accept_personality_suggestion(&mut self) -> Result<()> {
+        let Some(suggestion) = self.personality_suggestion.take() else {
+            return Ok(());
+        };
+        crate::services::personality::write_personality(
+            &suggestion.personality,
+            &suggestion.proposed,
+        )?;
+        crate::services::personality_tuning::clear_pending_suggestion()?;
+        if self.personality_name.as_deref() == Some(suggestion.personality.as_str()) {
+            self.personality_text = None;
+        }
+        self.mode = AppMode::PersonalitySelection;
+        self.show_status_toast("SUGGESTION APPLIED");
+        Ok(())
+    }
+
+    pub fn reject_personality_suggestion(&mut self) -> Result<()> {
+        self.personality_suggestion = None;
+        crate::services::personality_tuning::clear_pending_suggestion()?;
+        self.mode = AppMode::PersonalitySelection;
+        self.show_status_toast("SUGGESTION REJECTED");
+        Ok(())
+    }
+}
+
 pub struct PersonalityNavigable<'a> {
     app: &'a mut App,
 }
@@ -203,9 +424,11 @@ impl<'a> Navigable for PersonalityNavigable<'a> {
 impl App {
     pub fn next_personality(&mut self) {
         PersonalityNavigable::new(self).next_item();
+        self.refresh_personality_preview();
     }
 
     pub fn previous_personality(&mut self) {
         PersonalityNavigable::new(self).previous_item();
+        self.refresh_personality_preview();
     }
 }
@@ -0,0 +1,90 @@
+use crate::app::App;
+use crate::app::types::MessageRole;
+use color_eyre::{Result, eyre::eyre};
+
+impl App {
+    /// Queues every user and assistant message from `start` on, one per queue entry so
+    /// Ctrl+N skips a message. User messages get the second voice when one is set,
+    /// otherwise every message is introduced by who said it
+    pub fn speak_conversation_from(&mut self, start: usize) -> Result<()> {
+        let tts = self
+            .tts_service
+            .as_ref()
+            .ok_or_else(|| eyre!("TTS service not initialized"))?;
+        let assistant_name = self.personality_name.clone().unwrap_or_else(|| "Kimi".to_string());
+        let messages: Vec<_> = self
+            .chat_history
+            .iter()
+            .skip(start)
+            .filter(|message| matches!(message.role, MessageRole::User | MessageRole::Assistant))
+            .collect();
+        if messages.is_empty() {
+            self.show_status_toast("NOTHING TO READ");
+            return Ok(());
+        }
+        tts.stop();
+        for message in &messages {
+            let is_user = message.role == MessageRole::User;
+            let voice = if is_user { self.tts_user_voice_id.as_deref() } else { None };
+            let text = if self.tts_user_voice_id.is_some() {
+                message.content.clone()
+            } else {
+                let speaker = if is_user {
+                    message.display_name.clone().unwrap_or_else(|| "You".to_string())
+                } else {
+                    message.display_name.clone().unwrap_or_else(|| assistant_name.clone())
+                };
+                format!("{} said: {}", speaker, message.content)
+            };
+            tts.speak_text_with_voice(&text, voice)?;
+        }
+        self.show_status_toast(format!("READING {} MESSAGES", messages.len()));
+        Ok(())
+    }
+
+    /// Pauses or resumes the message being spoken
+    pub fn toggle_tts_pause(&mut self) {
+        let Some(tts) = &self.tts_service else {
+            return;
+        };
+        if !tts.is_playing() {
+            self.show_status_toast("NOTHING PLAYING");
+            return;
+        }
+        let toast = if tts.toggle_pause() { "PAUSED" } else { "RESUMED" };
+        self.show_status_toast(toast);
+    }
+
+    /// Skips to the next queued message
+    pub fn skip_tts(&mut self) {
+        let Some(tts) = &self.tts_service else {
+            return;
+        };
+        if !tts.is_playing() {
+            self.show_status_toast("NOTHING PLAYING");
+            return;
+        }
+        tts.skip();
+        self.show_status_toast("SKIPPED");
+    }
+
+    /// Silences all speech: queued messages and alerts such as timer announcements
+    pub fn stop_tts(&mut self) {
+        let Some(tts) = &self.tts_service else {
+            return;
+        };
+        if !tts.is_busy() {
+            self.show_status_toast("NOTHING PLAYING");
+            return;
+        }
+        tts.stop_all();
+        self.show_status_toast("STOPPED");
+    }
+
+    /// Surfaces errors from background speech playback
+    pub fn poll_tts_errors(&mut self) {
+        if let Some(error) = self.tts_service.as_ref().and_then(crate::services::TTSService::take_error) {
+            self.add_system_message(&format!("TTS Error: {}", error));
+        }
+    }
+}
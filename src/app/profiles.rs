@@ -0,0 +1,108 @@
+use crate::app::{App, AppMode};
+use crate::config::Config;
+use color_eyre::Result;
+
+impl App {
+    pub fn open_profile_selection(&mut self) -> Result<()> {
+        self.profiles = crate::profile::list_profiles()?;
+        let active = crate::profile::active_name();
+        self.profile_selected_index = self
+            .profiles
+            .iter()
+            .position(|name| *name == active)
+            .unwrap_or(0);
+        self.profile_create_active = false;
+        self.profile_create_input.clear();
+        self.mode = AppMode::ProfileSelection;
+        Ok(())
+    }
+
+    pub fn close_profile_selection(&mut self) {
+        self.mode = AppMode::Chat;
+        self.profile_create_active = false;
+        self.profile_create_input.clear();
+    }
+
+    pub fn next_profile(&mut self) {
+        if !self.profiles.is_empty() {
+            self.profile_selected_index = (self.profile_selected_index + 1) % self.profiles.len();
+        }
+    }
+
+    pub fn previous_profile(&mut self) {
+        if !self.profiles.is_empty() {
+            self.profile_selected_index = self
+                .profile_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.profiles.len().saturating_sub(1));
+        }
+    }
+
+    pub fn open_profile_create(&mut self) {
+        self.profile_create_active = true;
+        self.profile_create_input.clear();
+    }
+
+    pub fn cancel_profile_create(&mut self) {
+        self.profile_create_active = false;
+        self.profile_create_input.clear();
+    }
+
+    pub fn add_profile_char(&mut self, character: char) {
+        self.profile_create_input.add_char(character);
+    }
+
+    pub fn remove_profile_char(&mut self) {
+        self.profile_create_input.remove_char();
+    }
+
+    pub fn confirm_profile_create(&mut self) -> Result<()> {
+        let name = self.profile_create_input.content().trim().to_string();
+        self.cancel_profile_create();
+        if name.is_empty() {
+            return Ok(());
+        }
+        self.switch_profile(&name)
+    }
+
+    pub fn switch_to_selected_profile(&mut self) -> Result<()> {
+        let Some(name) = self.profiles.get(self.profile_selected_index).cloned() else {
+            return Ok(());
+        };
+        if name == crate::profile::active_name() {
+            self.close_profile_selection();
+            return Ok(());
+        }
+        self.switch_profile(&name)
+    }
+
+    /// Saves the current chat into the old profile, then reloads config, storage and
+    /// agents for `name` and starts a fresh conversation
+    fn switch_profile(&mut self, name: &str) -> Result<()> {
+        let _ = self.autosave_conversation(true);
+        self.wait_for_storage_jobs();
+
+        let previous = crate::profile::active_name();
+        crate::profile::set_active(name)?;
+        // Each profile has its own key file; keep the old profile if it cannot be unlocked
+        let config = match Config::load().and_then(|config| {
+            crate::services::encryption::unlock(config.storage.encrypt_content)?;
+            Ok(config)
+        }) {
+            Ok(config) => config,
+            Err(error) => {
+                crate::profile::set_active(&previous)?;
+                return Err(error);
+            }
+        };
+        tracing::info!(profile = %crate::profile::active_name(), "switched profile");
+        self.storage = None;
+        self.init_services(&config);
+
+        // Same reset as leaving History: empty chat, reloaded agent
+        self.close_history();
+        self.close_profile_selection();
+        self.show_status_toast(format!("PROFILE: {}", crate::profile::active_name()));
+        Ok(())
+    }
+}
@@ -0,0 +1,390 @@
+use crate::app::{App, AppMode, ProjectDetailFocus, ProjectForm, TextInput};
+use crate::services::projects::{self, TaskStatus};
+use color_eyre::Result;
+
+impl App {
+    pub fn open_project_create(&mut self) {
+        self.project_form = Some(ProjectForm::Project {
+            original: None,
+            name: TextInput::new(),
+            description: TextInput::new(),
+            editing_description: false,
+        });
+        self.mode = AppMode::ProjectForm;
+    }
+
+    pub fn open_project_edit(&mut self) {
+        let Some(project) = self.projects.get(self.project_selected_index) else {
+            return;
+        };
+        self.project_form = Some(ProjectForm::Project {
+            original: Some(project.name.clone()),
+            name: TextInput::with_content(project.name.clone()),
+            description: TextInput::with_content(project.description.clone()),
+            editing_description: false,
+        });
+        self.mode = AppMode::ProjectForm;
+    }
+
+    pub fn open_project_entry_add(&mut self) {
+        let Some(project) = self.current_project_name.clone() else {
+            return;
+        };
+        self.project_form = Some(ProjectForm::Entry {
+            project,
+            text: TextInput::new(),
+        });
+        self.mode = AppMode::ProjectForm;
+    }
+
+    pub fn open_project_task_add(&mut self) {
+        let Some(project) = self.current_project_name.clone() else {
+            return;
+        };
+        self.project_form = Some(ProjectForm::Task {
+            project,
+            text: TextInput::new(),
+        });
+        self.mode = AppMode::ProjectForm;
+    }
+
+    /// Leaves the form for the view it was opened from
+    pub fn close_project_form(&mut self) {
+        self.mode = match self.project_form.take() {
+            Some(ProjectForm::Entry { .. } | ProjectForm::Task { .. }) => AppMode::ProjectDetail,
+            Some(ProjectForm::Project { .. }) | None => AppMode::ProjectList,
+        };
+    }
+
+    fn focused_project_input(&mut self) -> Option<&mut TextInput> {
+        match self.project_form.as_mut()? {
+            ProjectForm::Project {
+                name,
+                description,
+                editing_description,
+                ..
+            } => Some(if *editing_description { description } else { name }),
+            ProjectForm::Entry { text, .. } | ProjectForm::Task { text, .. } => Some(text),
+        }
+    }
+
+    pub fn add_project_form_char(&mut self, character: char) {
+        if let Some(input) = self.focused_project_input() {
+            input.add_char(character);
+        }
+    }
+
+    pub fn remove_project_form_char(&mut self) {
+        if let Some(input) = self.focused_project_input() {
+            input.remove_char();
+        }
+    }
+
+    /// Switches between the name and description fields
+    pub fn toggle_project_form_field(&mut self) {
+        if let Some(ProjectForm::Project {
+            editing_description,
+            ..
+        }) = self.project_form.as_mut()
+        {
+            *editing_description = !*editing_description;
+        }
+    }
+
+    pub fn submit_project_form(&mut self) -> Result<()> {
+        let Some(form) = self.project_form.clone() else {
+            return Ok(());
+        };
+        let vault_path = self.connect_obsidian_vault_path.clone();
+        match form {
+            ProjectForm::Project {
+                original,
+                name,
+                description,
+                ..
+            } => {
+                let name = name.content().trim().to_string();
+                if name.is_empty() {
+                    self.show_status_toast("NAME REQUIRED");
+                    return Ok(());
+                }
+                let description = description.content().trim();
+                let result = match &original {
+                    Some(original) => projects::update_project(&vault_path, original, &name, description),
+                    None => projects::create_project_file(&vault_path, &name, description),
+                };
+                if let Err(error) = result {
+                    self.show_status_toast(format!("Error: {}", error));
+                    return Ok(());
+                }
+                if let Some(original) = original.as_deref()
+                    && original != name
+                {
+                    self.rename_project_tasks(original, &name);
+                }
+                if original.is_some() && self.active_project == original {
+                    self.active_project = Some(name.clone());
+                }
+                self.close_project_form();
+                self.load_project_list();
+                self.select_project(&name);
+                self.show_status_toast(if original.is_some() {
+                    "PROJECT UPDATED"
+                } else {
+                    "PROJECT CREATED"
+                });
+            }
+            ProjectForm::Entry { project, text } => {
+                let entry = text.content().trim().to_string();
+                if entry.is_empty() {
+                    self.close_project_form();
+                    return Ok(());
+                }
+                projects::append_project_entries(&vault_path, &project, &[entry])?;
+                self.close_project_form();
+                self.reload_project_detail(&project)?;
+                self.project_entry_selected_index = self.project_entries.len().saturating_sub(1);
+            }
+            ProjectForm::Task { project, text } => {
+                let title = text.content().trim().to_string();
+                self.close_project_form();
+                if title.is_empty() {
+                    return Ok(());
+                }
+                let (storage, runtime) = self.storage_with_runtime()?;
+                runtime.block_on(storage.save_project_task(&project, &title, TaskStatus::Open))?;
+                self.load_project_tasks(&project);
+                self.project_task_selected_index = self.project_tasks.len().saturating_sub(1);
+            }
+        }
+        Ok(())
+    }
+
+    fn select_project(&mut self, name: &str) {
+        if let Some(index) = self.projects.iter().position(|project| project.name == name) {
+            self.project_selected_index = index;
+        }
+    }
+
+    fn reload_project_detail(&mut self, name: &str) -> Result<()> {
+        let file = projects::read_project_file(&self.connect_obsidian_vault_path, name)?;
+        self.current_project_name = Some(file.name);
+        self.current_project_description = Some(file.description);
+        self.project_entries = file.entries;
+        self.project_conversations = file.conversations;
+        self.project_entry_selected_index = self
+            .project_entry_selected_index
+            .min(self.project_entries.len().saturating_sub(1));
+        self.load_project_tasks(name);
+        Ok(())
+    }
+
+    /// Loads the open project's tasks; a storage failure leaves the list empty
+    pub(crate) fn load_project_tasks(&mut self, name: &str) {
+        let tasks = if self.ensure_storage() {
+            self.storage_with_runtime()
+                .and_then(|(storage, runtime)| runtime.block_on(storage.load_project_tasks(name)))
+        } else {
+            Err(color_eyre::eyre::eyre!("Storage not initialized"))
+        };
+        self.project_tasks = tasks.unwrap_or_else(|error| {
+            tracing::warn!(%error, project = %name, "could not load project tasks");
+            Vec::new()
+        });
+        self.project_task_selected_index = self
+            .project_task_selected_index
+            .min(self.project_tasks.len().saturating_sub(1));
+    }
+
+    fn rename_project_tasks(&mut self, old_name: &str, new_name: &str) {
+        let renamed = self.ensure_storage()
+            && self.storage_with_runtime().is_ok_and(|(storage, runtime)| {
+                runtime
+                    .block_on(storage.rename_project_tasks(old_name, new_name))
+                    .is_ok()
+            });
+        if !renamed {
+            tracing::warn!(old_name, new_name, "could not move tasks to renamed project");
+        }
+    }
+
+    /// Tasks of the active project for the prompt (empty while storage is unavailable)
+    pub(crate) fn active_project_task_list(&self) -> Vec<crate::storage::ProjectTask> {
+        let Some(name) = &self.active_project else {
+            return Vec::new();
+        };
+        self.storage_with_runtime()
+            .and_then(|(storage, runtime)| runtime.block_on(storage.load_project_tasks(name)))
+            .unwrap_or_default()
+    }
+
+    /// Switches the detail view keys between entries and tasks
+    pub fn toggle_project_detail_focus(&mut self) {
+        self.project_detail_focus = match self.project_detail_focus {
+            ProjectDetailFocus::Entries => ProjectDetailFocus::Tasks,
+            ProjectDetailFocus::Tasks => ProjectDetailFocus::Entries,
+        };
+    }
+
+    pub fn next_project_task(&mut self) {
+        if !self.project_tasks.is_empty() {
+            self.project_task_selected_index =
+                (self.project_task_selected_index + 1) % self.project_tasks.len();
+        }
+    }
+
+    pub fn previous_project_task(&mut self) {
+        if !self.project_tasks.is_empty() {
+            self.project_task_selected_index = self
+                .project_task_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.project_tasks.len().saturating_sub(1));
+        }
+    }
+
+    /// Confirms a proposed task, or toggles an open task between open and done
+    pub fn advance_selected_project_task(&mut self) -> Result<()> {
+        let Some(task) = self.project_tasks.get(self.project_task_selected_index) else {
+            return Ok(());
+        };
+        let (id, project) = (task.id.clone(), task.project.clone());
+        let (status, toast) = match task.status {
+            TaskStatus::Proposed => (TaskStatus::Open, "TASK CONFIRMED"),
+            TaskStatus::Open => (TaskStatus::Done, "TASK DONE"),
+            TaskStatus::Done => (TaskStatus::Open, "TASK REOPENED"),
+        };
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.set_project_task_status(&id, status))?;
+        self.load_project_tasks(&project);
+        self.show_status_toast(toast);
+        Ok(())
+    }
+
+    /// Deletes the selected task; for a proposal this rejects it
+    pub fn remove_selected_project_task(&mut self) -> Result<()> {
+        let Some(task) = self.project_tasks.get(self.project_task_selected_index) else {
+            return Ok(());
+        };
+        let (id, project) = (task.id.clone(), task.project.clone());
+        let toast = if task.status == TaskStatus::Proposed {
+            "PROPOSAL REJECTED"
+        } else {
+            "TASK REMOVED"
+        };
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.delete_project_task(&id))?;
+        self.load_project_tasks(&project);
+        self.show_status_toast(toast);
+        Ok(())
+    }
+
+    /// Moves the selected project to projects/archived/ (the file is kept)
+    pub fn archive_selected_project(&mut self) -> Result<()> {
+        let Some(name) = self
+            .projects
+            .get(self.project_selected_index)
+            .map(|project| project.name.clone())
+        else {
+            return Ok(());
+        };
+        projects::archive_project(&self.connect_obsidian_vault_path, &name)?;
+        if self.active_project.as_deref() == Some(name.as_str()) {
+            self.active_project = None;
+        }
+        self.load_project_list();
+        self.project_selected_index = self
+            .project_selected_index
+            .min(self.projects.len().saturating_sub(1));
+        self.show_status_toast("PROJECT ARCHIVED");
+        Ok(())
+    }
+
+    /// Makes the selected project the active one, or clears it when it already is
+    pub fn toggle_active_project(&mut self) {
+        let Some(name) = self
+            .projects
+            .get(self.project_selected_index)
+            .map(|project| project.name.clone())
+        else {
+            return;
+        };
+        if self.active_project.as_deref() == Some(name.as_str()) {
+            self.active_project = None;
+            self.show_status_toast("NO ACTIVE PROJECT");
+        } else {
+            self.show_status_toast(format!("ACTIVE PROJECT: {}", name));
+            self.active_project = Some(name);
+        }
+    }
+
+    pub fn remove_selected_project_entry(&mut self) -> Result<()> {
+        let Some(name) = self.current_project_name.clone() else {
+            return Ok(());
+        };
+        if self.project_entries.is_empty() {
+            return Ok(());
+        }
+        projects::remove_project_entry(
+            &self.connect_obsidian_vault_path,
+            &name,
+            self.project_entry_selected_index,
+        )?;
+        self.reload_project_detail(&name)?;
+        self.show_status_toast("ENTRY REMOVED");
+        Ok(())
+    }
+
+    /// Links the current chat to the open project
+    pub fn attach_current_conversation_to_project(&mut self) -> Result<()> {
+        let Some(name) = self.current_project_name.clone() else {
+            return Ok(());
+        };
+        let Some(conversation_id) = self.current_conversation_id.clone() else {
+            self.show_status_toast("NO SAVED CONVERSATION");
+            return Ok(());
+        };
+        let title = self.current_conversation_title();
+        let attached = projects::attach_conversation(
+            &self.connect_obsidian_vault_path,
+            &name,
+            &conversation_id,
+            &title,
+        )?;
+        self.reload_project_detail(&name)?;
+        self.show_status_toast(if attached {
+            "CONVERSATION ATTACHED"
+        } else {
+            "ALREADY ATTACHED"
+        });
+        Ok(())
+    }
+
+    /// Title for a conversation link: the first user message of the current chat
+    fn current_conversation_title(&self) -> String {
+        self.chat_history
+            .iter()
+            .find(|message| message.role == crate::app::MessageRole::User)
+            .map_or_else(
+                || "Conversation".to_string(),
+                |message| {
+                    let condensed = message.content.split_whitespace().collect::<Vec<_>>().join(" ");
+                    condensed.chars().take(60).collect()
+                },
+            )
+    }
+
+    /// Adds a summarized conversation to the active project's conversation list
+    pub(crate) fn attach_conversation_to_active_project(&self, conversation_id: &str, summary: &str) {
+        let Some(name) = &self.active_project else {
+            return;
+        };
+        if let Err(error) = projects::attach_conversation(
+            &self.connect_obsidian_vault_path,
+            name,
+            conversation_id,
+            summary,
+        ) {
+            tracing::warn!(%error, project = %name, "could not attach conversation to project");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::app::types::{PromptSection, PromptSectionText};
+use crate::app::{App, AppMode};
+use color_eyre::Result;
+
+impl App {
+    /// Opens the `/prompt` overlay and assembles a preview of the next system prompt
+    pub fn open_prompt_inspector(&mut self) -> Result<()> {
+        self.prompt_inspector_index = 0;
+        self.prompt_inspector_scroll = 0;
+        self.refresh_prompt_preview()?;
+        self.mode = AppMode::PromptInspector;
+        Ok(())
+    }
+
+    pub fn close_prompt_inspector(&mut self) {
+        self.mode = AppMode::Chat;
+        self.prompt_preview = None;
+    }
+
+    pub fn refresh_prompt_preview(&mut self) -> Result<()> {
+        self.prompt_preview = None;
+        self.spawn_prompt_preview()
+    }
+
+    pub fn previous_prompt_section(&mut self) {
+        self.prompt_inspector_index = self.prompt_inspector_index.saturating_sub(1);
+        self.prompt_inspector_scroll = 0;
+    }
+
+    pub fn next_prompt_section(&mut self) {
+        if self.prompt_inspector_index + 1 < PromptSection::ALL.len() {
+            self.prompt_inspector_index += 1;
+        }
+        self.prompt_inspector_scroll = 0;
+    }
+
+    pub fn scroll_prompt_section(&mut self, lines: isize) {
+        self.prompt_inspector_scroll = self.prompt_inspector_scroll.saturating_add_signed(lines);
+    }
+
+    #[must_use]
+    pub fn selected_prompt_section(&self) -> Option<PromptSection> {
+        PromptSection::ALL.get(self.prompt_inspector_index).copied()
+    }
+
+    /// Text the selected section contributed to the last preview
+    #[must_use]
+    pub fn prompt_section_text(&self, section: PromptSection) -> Option<&PromptSectionText> {
+        self.prompt_preview
+            .as_ref()?
+            .iter()
+            .find(|part| part.section == section)
+    }
+
+    #[must_use]
+    pub fn is_prompt_section_enabled(&self, section: PromptSection) -> bool {
+        !self.disabled_prompt_sections.contains(&section)
+    }
+
+    /// Turns the selected section off (or back on) for the rest of the session
+    pub fn toggle_selected_prompt_section(&mut self) -> Result<()> {
+        let Some(section) = self.selected_prompt_section() else {
+            return Ok(());
+        };
+        if self.is_prompt_section_enabled(section) {
+            self.disabled_prompt_sections.push(section);
+            self.show_status_toast(format!("{} OFF", section.label().to_uppercase()));
+        } else {
+            self.disabled_prompt_sections.retain(|disabled| *disabled != section);
+            self.show_status_toast(format!("{} ON", section.label().to_uppercase()));
+        }
+        // Disabled sections skip their lookups, so rebuild to show what they add again
+        self.refresh_prompt_preview()
+    }
+}
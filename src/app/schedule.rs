@@ -0,0 +1,349 @@
+use crate::agents::{Agent, AgentManager, ChatMessage as AgentChatMessage};
+use crate::app::{AgentEvent, App};
+use crate::services::notifications::NotificationKind;
+use crate::services::scheduler::{self, Job, Source};
+use crate::services::{calendar, feeds, tasks, weather};
+use crate::storage::StorageManager;
+use color_eyre::Result;
+use std::time::Duration;
+
+/// How often the scheduler looks for due jobs
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What a job can gather, copied out of the app for the scheduler thread
+#[derive(Debug, Clone)]
+struct JobContext {
+    calendar_sources: Vec<String>,
+    feeds: crate::config::FeedsConfig,
+    weather: crate::config::WeatherConfig,
+}
+
+impl App {
+    fn job_context(&self) -> JobContext {
+        JobContext {
+            calendar_sources: self.calendar_sources.clone(),
+            feeds: self.feeds.clone(),
+            weather: self.weather.clone(),
+        }
+    }
+
+    /// `[[schedule]]` jobs first, then the ones added with `/schedule`, in the order `/schedule` numbers them
+    fn scheduled_jobs(&mut self) -> Vec<(Result<Job>, bool)> {
+        let mut jobs: Vec<(Result<Job>, bool)> = self
+            .schedule
+            .iter()
+            .filter(|config| config.enabled)
+            .map(|config| (Job::from_config(config), false))
+            .collect();
+        if self.ensure_storage()
+            && let Ok((storage, runtime)) = self.storage_with_runtime()
+        {
+            let stored = runtime.block_on(storage.load_scheduled_jobs()).unwrap_or_default();
+            jobs.extend(stored.into_iter().map(|(key, config)| (stored_job(key, &config), true)));
+        }
+        jobs
+    }
+
+    /// `/schedule`: every job with its schedule and last run
+    pub(crate) fn list_scheduled_jobs(&mut self) {
+        let jobs = self.scheduled_jobs();
+        if jobs.is_empty() {
+            self.add_system_message(
+                "No scheduled jobs. Add one with / then schedule <when>: <what>, \
+e.g. schedule every morning at 8: weather + agenda + news briefing",
+            );
+            return;
+        }
+        let runs = self
+            .storage_with_runtime()
+            .and_then(|(storage, runtime)| runtime.block_on(storage.load_job_runs()))
+            .unwrap_or_default();
+        let lines: Vec<String> = jobs
+            .iter()
+            .enumerate()
+            .map(|(index, (job, stored))| match job {
+                Ok(job) => {
+                    let origin = if *stored { "" } else { " [config]" };
+                    let last_run = runs
+                        .get(&job.key)
+                        .and_then(|ran_at| chrono::DateTime::parse_from_rfc3339(ran_at).ok())
+                        .map_or_else(
+                            || "never ran".to_string(),
+                            |ran_at| format!("last ran {}", ran_at.format("%b %d %H:%M")),
+                        );
+                    format!("{}. {}{}: {} ({})", index + 1, job.name, origin, job.describe(), last_run)
+                }
+                Err(error) => format!("{}. invalid: {}", index + 1, error),
+            })
+            .collect();
+        self.add_system_message(&format!(
+            "Scheduled jobs:\n{}\n/ then schedule run <n> runs one now, schedule remove <n> deletes one added here",
+            lines.join("\n")
+        ));
+    }
+
+    /// `/schedule <when>: <what>`, `/schedule run <n>` or `/schedule remove <n>`
+    pub(crate) fn handle_schedule_arguments(&mut self, arguments: &str) {
+        if let Some(number) = arguments.strip_prefix("run ") {
+            self.run_scheduled_job_now(number.trim());
+        } else if let Some(number) = arguments.strip_prefix("remove ") {
+            self.remove_scheduled_job(number.trim());
+        } else {
+            self.add_scheduled_job(arguments);
+        }
+    }
+
+    fn add_scheduled_job(&mut self, request: &str) {
+        let config = match scheduler::parse_request(request) {
+            Ok(config) => config,
+            Err(error) => {
+                self.add_system_message(&error.to_string());
+                return;
+            }
+        };
+        let job = match Job::from_config(&config) {
+            Ok(job) => job,
+            Err(error) => {
+                self.add_system_message(&format!("Cannot schedule that: {}", error));
+                return;
+            }
+        };
+        if !self.ensure_storage() {
+            self.add_system_message("Storage not initialized; add the job under [[schedule]] in config.toml instead");
+            return;
+        }
+        let saved = self
+            .storage_with_runtime()
+            .and_then(|(storage, runtime)| runtime.block_on(storage.add_scheduled_job(&config)));
+        match saved {
+            Ok(_) => {
+                self.add_system_message(&format!(
+                    "Scheduled \"{}\": {}. Results arrive in /inbox",
+                    job.name,
+                    job.describe()
+                ));
+            }
+            Err(error) => self.add_system_message(&format!("Could not save the job: {}", error)),
+        }
+    }
+
+    fn remove_scheduled_job(&mut self, number: &str) {
+        let jobs = self.scheduled_jobs();
+        let Some((job, stored)) = number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| jobs.get(number.checked_sub(1)?))
+        else {
+            self.add_system_message("Usage: / then schedule remove <n>, numbered as in /schedule");
+            return;
+        };
+        let Ok(job) = job else {
+            self.add_system_message("That [[schedule]] entry is invalid; fix or remove it in config.toml");
+            return;
+        };
+        if !*stored {
+            self.add_system_message(&format!(
+                "\"{}\" comes from [[schedule]] in config.toml; remove or disable it there",
+                job.name
+            ));
+            return;
+        }
+        let key = job.key.clone();
+        let name = job.name.clone();
+        let removed = self
+            .storage_with_runtime()
+            .and_then(|(storage, runtime)| runtime.block_on(storage.delete_scheduled_job(&key)));
+        match removed {
+            Ok(()) => self.show_status_toast(format!("REMOVED {}", name.to_uppercase())),
+            Err(error) => self.add_system_message(&format!("Could not remove the job: {}", error)),
+        }
+    }
+
+    /// Runs a job in the background right away; the result lands in the inbox like a scheduled run
+    fn run_scheduled_job_now(&mut self, number: &str) {
+        let jobs = self.scheduled_jobs();
+        let Some(Ok(job)) = number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| jobs.into_iter().nth(number.checked_sub(1)?))
+            .map(|(job, _)| job)
+        else {
+            self.add_system_message("Usage: / then schedule run <n>, numbered as in /schedule");
+            return;
+        };
+        let Ok((agent, manager, tx)) = self.get_agent_chat_dependencies() else {
+            self.add_system_message("No chat agent available to run the job");
+            return;
+        };
+        let Some(storage) = self.storage.clone() else {
+            self.add_system_message("Storage not initialized");
+            return;
+        };
+        let context = self.job_context();
+        self.show_status_toast(format!("RUNNING {}", job.name.to_uppercase()));
+        std::thread::spawn(move || {
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            deliver(&job, &context, &storage, &runtime, &manager, &agent, &tx);
+        });
+    }
+
+    /// Runs due `[[schedule]]` and `/schedule` jobs while kimi is open. Each run gathers its
+    /// sources, has the chat agent write the result and leaves it unread in the inbox
+    pub fn spawn_scheduler(&mut self) {
+        if !self.ensure_storage() {
+            return;
+        }
+        let Ok((agent, manager, tx)) = self.get_agent_chat_dependencies() else {
+            return;
+        };
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let mut config_jobs = Vec::new();
+        for config in self.schedule.iter().filter(|config| config.enabled) {
+            match Job::from_config(config) {
+                Ok(job) => config_jobs.push(job),
+                Err(error) => tracing::warn!(%error, "skipping invalid [[schedule]] entry"),
+            }
+        }
+        let context = self.job_context();
+
+        std::thread::spawn(move || {
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            loop {
+                let stored = runtime.block_on(storage.load_scheduled_jobs()).unwrap_or_default();
+                let runs = runtime.block_on(storage.load_job_runs()).unwrap_or_default();
+                let now = chrono::Local::now().naive_local();
+                let jobs = config_jobs
+                    .iter()
+                    .cloned()
+                    .chain(stored.into_iter().filter_map(|(key, config)| stored_job(key, &config).ok()));
+                for job in jobs {
+                    let last_run = runs
+                        .get(&job.key)
+                        .and_then(|ran_at| chrono::DateTime::parse_from_rfc3339(ran_at).ok())
+                        .map(|ran_at| ran_at.with_timezone(&chrono::Local).naive_local());
+                    if !job.is_due(last_run, now) {
+                        continue;
+                    }
+                    // The app has quit once the channel is closed
+                    if !deliver(&job, &context, &storage, &runtime, &manager, &agent, &tx) {
+                        return;
+                    }
+                }
+                std::thread::sleep(SCHEDULE_CHECK_INTERVAL);
+            }
+        });
+    }
+}
+
+/// A job added with `/schedule`, keyed by its record so runs survive renames in config
+fn stored_job(key: String, config: &crate::config::ScheduledJobConfig) -> Result<Job> {
+    Job::from_config(config).map(|job| Job { key, ..job })
+}
+
+/// Runs `job`, records the run and sends the result (or the failure) to the inbox.
+/// Returns false once the app is gone
+fn deliver(
+    job: &Job,
+    context: &JobContext,
+    storage: &StorageManager,
+    runtime: &tokio::runtime::Runtime,
+    manager: &AgentManager,
+    agent: &Agent,
+    tx: &std::sync::mpsc::Sender<AgentEvent>,
+) -> bool {
+    let now = chrono::Local::now();
+    // Recorded before running so a failing job is not retried every minute
+    if let Err(error) = runtime.block_on(storage.record_job_run(&job.key, &now.to_rfc3339())) {
+        tracing::warn!(%error, job = %job.name, "could not record scheduled job run");
+    }
+    let (title, message) = match run_job(job, context, storage, runtime, manager, agent, now.naive_local()) {
+        Ok(result) => (job.name.clone(), result),
+        Err(error) => {
+            tracing::warn!(%error, job = %job.name, "scheduled job failed");
+            (format!("{} failed", job.name), error.to_string())
+        }
+    };
+    tx.send(AgentEvent::Notification {
+        kind: NotificationKind::Job,
+        title,
+        message,
+    })
+    .is_ok()
+}
+
+fn run_job(
+    job: &Job,
+    context: &JobContext,
+    storage: &StorageManager,
+    runtime: &tokio::runtime::Runtime,
+    manager: &AgentManager,
+    agent: &Agent,
+    now: chrono::NaiveDateTime,
+) -> Result<String> {
+    let gathered: Vec<(Source, String)> = job
+        .sources
+        .iter()
+        .map(|source| (*source, gather(*source, context, storage, runtime)))
+        .collect();
+    let messages = vec![
+        AgentChatMessage::system(&agent.system_prompt),
+        AgentChatMessage::user(scheduler::format_job_prompt(job, &gathered, now)),
+    ];
+    Ok(manager.chat(agent, &messages)?.trim().to_string())
+}
+
+/// One source as plain lines for the prompt; failures are reported to the model rather than
+/// failing the whole job
+fn gather(source: Source, context: &JobContext, storage: &StorageManager, runtime: &tokio::runtime::Runtime) -> String {
+    let today = chrono::Local::now().date_naive();
+    let text: Result<String> = match source {
+        Source::Weather => weather::current(&context.weather)
+            .map(|report| weather::format_report(&report, context.weather.units)),
+        Source::Agenda => calendar::load_upcoming_events(&context.calendar_sources, today, 1).map(|events| {
+            events
+                .iter()
+                .map(calendar::format_event_line)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }),
+        Source::News => feeds::fetch_feed_items(&context.feeds.urls).map(|items| {
+            items
+                .iter()
+                .take(context.feeds.max_items.max(1))
+                .map(|item| format!("{} ({})", item.title, item.feed))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }),
+        Source::Tasks => runtime.block_on(async {
+            let mut lines: Vec<String> = storage
+                .load_due_tasks(false)
+                .await?
+                .into_iter()
+                .filter(|task| task.status != crate::services::projects::TaskStatus::Proposed)
+                .map(|task| match chrono::NaiveDate::parse_from_str(&task.due, "%Y-%m-%d") {
+                    Ok(due) => format!("{} (due {})", task.text, tasks::describe_due(due, today)),
+                    Err(_) => task.text,
+                })
+                .collect();
+            lines.extend(
+                storage
+                    .load_open_project_tasks()
+                    .await?
+                    .into_iter()
+                    .map(|task| format!("{} (project {})", task.title, task.project)),
+            );
+            Ok::<_, color_eyre::Report>(lines.join("\n"))
+        }),
+    };
+    match text {
+        Ok(text) if text.trim().is_empty() => "nothing".to_string(),
+        Ok(text) => text,
+        Err(error) => format!("unavailable ({})", error),
+    }
+}
@@ -0,0 +1,142 @@
+use crate::app::types::SearchResultGroup;
+use crate::app::{App, AppMode};
+use crate::storage::MessageSearchHit;
+use color_eyre::Result;
+
+/// Most BM25 hits loaded per search
+const SEARCH_RESULT_LIMIT: usize = 100;
+
+impl App {
+    /// Opens full-text search, keeping the previous query and results
+    pub fn open_search(&mut self) {
+        self.mode = AppMode::Search;
+    }
+
+    pub fn close_search(&mut self) {
+        self.mode = AppMode::Chat;
+    }
+
+    pub fn add_search_char(&mut self, character: char) {
+        self.search_input.add_char(character);
+    }
+
+    pub fn remove_search_char(&mut self) {
+        self.search_input.remove_char();
+    }
+
+    /// Enter: runs the search when the query changed, otherwise opens the selected hit
+    pub fn confirm_search(&mut self) -> Result<()> {
+        if self.search_input.content().trim() != self.search_last_query {
+            self.run_search();
+            return Ok(());
+        }
+        self.open_selected_search_hit()
+    }
+
+    fn run_search(&mut self) {
+        let query = self.search_input.content().trim().to_string();
+        self.search_last_query = query.clone();
+        self.search_selected_index = 0;
+        self.search_error = None;
+        self.search_groups.clear();
+        if query.is_empty() {
+            return;
+        }
+
+        self.ensure_storage();
+        let hits = self
+            .storage_with_runtime()
+            .and_then(|(storage, runtime)| {
+                runtime.block_on(storage.search_messages(&query, SEARCH_RESULT_LIMIT))
+            });
+        match hits {
+            Ok(hits) => self.search_groups = group_search_hits(hits),
+            Err(error) => {
+                tracing::warn!(%error, "full-text search failed");
+                self.search_error = Some(error.to_string());
+            }
+        }
+    }
+
+    pub fn search_hit_count(&self) -> usize {
+        self.search_groups.iter().map(|group| group.hits.len()).sum()
+    }
+
+    /// Selected hit with the conversation it belongs to
+    pub fn selected_search_hit(&self) -> Option<(&SearchResultGroup, &MessageSearchHit)> {
+        let mut remaining = self.search_selected_index;
+        for group in &self.search_groups {
+            if let Some(hit) = group.hits.get(remaining) {
+                return Some((group, hit));
+            }
+            remaining -= group.hits.len();
+        }
+        None
+    }
+
+    pub fn next_search_hit(&mut self) {
+        let count = self.search_hit_count();
+        if count > 0 {
+            self.search_selected_index = (self.search_selected_index + 1) % count;
+        }
+    }
+
+    pub fn previous_search_hit(&mut self) {
+        let count = self.search_hit_count();
+        if count > 0 {
+            self.search_selected_index = self
+                .search_selected_index
+                .checked_sub(1)
+                .unwrap_or(count - 1);
+        }
+    }
+
+    /// Loads the selected hit's conversation and scrolls to the matching message
+    fn open_selected_search_hit(&mut self) -> Result<()> {
+        let Some((group, hit)) = self.selected_search_hit() else {
+            return Ok(());
+        };
+        let conversation_id = group.conversation_id.clone();
+        let agent_name = group.agent_name.clone();
+        let (timestamp, content) = (hit.timestamp.clone(), hit.content.clone());
+
+        self.open_conversation(conversation_id, &agent_name)?;
+        let index = self
+            .chat_history
+            .iter()
+            .position(|message| message.timestamp == timestamp && message.content == content)
+            .or_else(|| {
+                self.chat_history
+                    .iter()
+                    .position(|message| message.timestamp == timestamp)
+            });
+        self.chat_scroll_target = index;
+        self.show_status_toast("JUMPED TO MATCH");
+        Ok(())
+    }
+}
+
+/// Groups ranked hits by conversation; groups are ordered by their best hit
+fn group_search_hits(hits: Vec<MessageSearchHit>) -> Vec<SearchResultGroup> {
+    let mut groups: Vec<SearchResultGroup> = Vec::new();
+    for hit in hits {
+        if let Some(group) = groups
+            .iter_mut()
+            .find(|group| group.conversation_id == hit.conversation_id)
+        {
+            group.hits.push(hit);
+            continue;
+        }
+        groups.push(SearchResultGroup {
+            conversation_id: hit.conversation_id.clone(),
+            title: hit
+                .conversation_title
+                .clone()
+                .unwrap_or_else(|| "Untitled conversation".to_string()),
+            created_at: hit.conversation_created_at.clone(),
+            agent_name: hit.agent_name.clone(),
+            hits: vec![hit],
+        });
+    }
+    groups
+}
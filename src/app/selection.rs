@@ -0,0 +1,211 @@
+use crate::agents::ChatMessage as AgentChatMessage;
+use crate::app::types::{ChatMessage, MessageQuote};
+use crate::app::{AgentEvent, App};
+use crate::services::clipboard::{CodeBlock, extract_code_blocks};
+use color_eyre::{Result, eyre::eyre};
+
+/// Longest excerpt of a message placed into the input by the quote action
+const QUOTE_MAX_CHARS: usize = 160;
+
+/// Start of the quoted block the quote action puts in front of the input
+pub(crate) const QUOTE_PREFIX: &str = "> \"";
+
+impl App {
+    /// Ctrl+K: highlights individual messages so actions apply to one of them
+    pub fn toggle_message_selection(&mut self) {
+        if self.message_selection_active {
+            self.close_message_selection();
+            return;
+        }
+        let Some(last_index) = self.chat_history.len().checked_sub(1) else {
+            self.show_status_toast("NO MESSAGES");
+            return;
+        };
+        self.message_selection_active = true;
+        self.selected_message_index = last_index;
+        self.chat_scroll_target = Some(last_index);
+    }
+
+    /// Mouse click on a message: selects it, or copies it when it is already selected
+    pub fn click_message(&mut self, index: usize) {
+        if index >= self.chat_history.len() {
+            return;
+        }
+        if self.message_selection_active && self.selected_message_index == index {
+            self.copy_selected_message();
+            return;
+        }
+        self.message_selection_active = true;
+        self.selected_message_index = index;
+    }
+
+    /// Ctrl+click: opens the URL under the pointer, or the first one in the message
+    pub fn open_link_in_message(&mut self, index: usize, url: Option<String>) {
+        let url = url.or_else(|| {
+            let message = self.chat_history.get(index)?;
+            crate::services::links::find_urls(&message.content)
+                .into_iter()
+                .next()
+                .map(|(_, url)| url)
+        });
+        let Some(url) = url else {
+            self.show_status_toast("NO LINK");
+            return;
+        };
+        match crate::services::links::open_url(&url) {
+            Ok(()) => self.show_status_toast("OPENING LINK"),
+            Err(error) => {
+                tracing::warn!(%error, %url, "could not open link");
+                self.show_status_toast("OPEN FAILED");
+            }
+        }
+    }
+
+    pub fn close_message_selection(&mut self) {
+        self.message_selection_active = false;
+    }
+
+    pub fn previous_selected_message(&mut self) {
+        self.selected_message_index = self.selected_message_index.saturating_sub(1);
+        self.chat_scroll_target = Some(self.selected_message_index);
+    }
+
+    pub fn next_selected_message(&mut self) {
+        if self.selected_message_index + 1 < self.chat_history.len() {
+            self.selected_message_index += 1;
+        }
+        self.chat_scroll_target = Some(self.selected_message_index);
+    }
+
+    fn selected_message(&self) -> Option<&ChatMessage> {
+        self.chat_history.get(self.selected_message_index)
+    }
+
+    pub fn copy_selected_message(&mut self) {
+        let Some(content) = self.selected_message().map(|message| message.content.clone()) else {
+            return;
+        };
+        if self.clipboard_service.copy_text(&content).is_ok() {
+            self.show_status_toast("COPIED");
+        } else {
+            self.show_status_toast("COPY FAILED");
+        }
+    }
+
+    pub fn speak_selected_message(&mut self) -> Result<()> {
+        let content = self
+            .selected_message()
+            .map(|message| message.content.clone())
+            .ok_or_else(|| eyre!("No message selected"))?;
+        let tts = self
+            .tts_service
+            .as_ref()
+            .ok_or_else(|| eyre!("TTS service not initialized"))?;
+        let toast = if tts.is_playing() { "QUEUED" } else { "SPEAKING" };
+        tts.speak_text(&content)?;
+        self.show_status_toast(toast);
+        Ok(())
+    }
+
+    /// Leaves the selected message out of (or back in) what the model sees
+    pub fn toggle_selected_message_context(&mut self) {
+        let Some(message) = self.chat_history.get_mut(self.selected_message_index) else {
+            return;
+        };
+        message.excluded_from_context = !message.excluded_from_context;
+        let toast = if message.excluded_from_context {
+            "REMOVED FROM CONTEXT"
+        } else {
+            "BACK IN CONTEXT"
+        };
+        self.show_status_toast(toast);
+    }
+
+    /// Prefixes the chat input with a condensed quote of the selected message and
+    /// remembers the full message so the reply is stored and prompted as a reply to it
+    pub fn quote_selected_message(&mut self) {
+        let Some((role, content)) = self
+            .selected_message()
+            .map(|message| (message.role.clone(), message.content.clone()))
+        else {
+            return;
+        };
+        let condensed = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        let excerpt = if condensed.chars().count() > QUOTE_MAX_CHARS {
+            format!("{}…", condensed.chars().take(QUOTE_MAX_CHARS - 1).collect::<String>())
+        } else {
+            condensed
+        };
+        let input = self.chat_input.content().trim_start().to_string();
+        self.chat_input
+            .set_content(format!("{}{}\" {}", QUOTE_PREFIX, excerpt, input));
+        self.pending_quote = Some(MessageQuote { role, content });
+        self.close_message_selection();
+        self.reset_chat_scroll();
+    }
+
+    /// Translates the selected message with the translate agent in the background
+    pub fn translate_selected_message(&mut self) -> Result<()> {
+        let content = self
+            .selected_message()
+            .map(|message| message.content.clone())
+            .ok_or_else(|| eyre!("No message selected"))?;
+        let manager = self
+            .agent_manager
+            .clone()
+            .ok_or_else(|| eyre!("Agent manager not initialized"))?;
+        let mut agent = manager
+            .get_agent("translate")
+            .cloned()
+            .ok_or_else(|| eyre!("Translate agent not configured"))?;
+        if let Some(model) = self
+            .selected_models
+            .get("translate")
+            .and_then(|models| models.first())
+        {
+            agent.model = model.clone();
+        }
+        let agent_tx = self
+            .agent_tx
+            .clone()
+            .ok_or_else(|| eyre!("Agent channel not initialized"))?;
+        std::thread::spawn(move || {
+            let note = match manager.chat(&agent, &[AgentChatMessage::user(&content)]) {
+                Ok(translation) => format!("Translation:\n{}", translation.trim()),
+                Err(error) => format!("Translation failed: {}", error),
+            };
+            let _ = agent_tx.send(AgentEvent::MessageTranslated(note));
+        });
+        self.show_status_toast("TRANSLATING");
+        Ok(())
+    }
+
+    /// Saves the selected message's code blocks (or the whole message) as snippets
+    pub fn save_selected_message_as_snippet(&mut self) -> Result<()> {
+        let content = self
+            .selected_message()
+            .map(|message| message.content.clone())
+            .ok_or_else(|| eyre!("No message selected"))?;
+        let mut blocks = extract_code_blocks(&content);
+        if blocks.is_empty() {
+            blocks.push(CodeBlock {
+                language: "text".to_string(),
+                code: content,
+            });
+        }
+
+        if !self.ensure_storage() {
+            return Err(eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        for block in &blocks {
+            runtime.block_on(storage.save_snippet(&block.title(), &block.language, &block.code))?;
+        }
+        if blocks.len() == 1 {
+            self.show_status_toast("SNIPPET SAVED");
+        } else {
+            self.show_status_toast(format!("{} SNIPPETS SAVED", blocks.len()));
+        }
+        Ok(())
+    }
+}
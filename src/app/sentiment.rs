@@ -0,0 +1,85 @@
+use crate::app::{App, AppMode};
+use crate::services::{identity, sentiment};
+use color_eyre::Result;
+
+impl App {
+    pub fn open_sentiment(&mut self) -> Result<()> {
+        self.ensure_storage();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.sentiment_timeline = runtime.block_on(storage.load_sentiment_timeline())?;
+        self.sentiment_scroll = 0;
+        self.mode = AppMode::Sentiment;
+        Ok(())
+    }
+
+    pub fn close_sentiment(&mut self) {
+        self.sentiment_timeline.clear();
+        self.mode = AppMode::Chat;
+    }
+
+    pub fn scroll_sentiment_down(&mut self) {
+        if self.sentiment_scroll + 1 < self.sentiment_timeline.len() {
+            self.sentiment_scroll += 1;
+        }
+    }
+
+    pub fn scroll_sentiment_up(&mut self) {
+        self.sentiment_scroll = self.sentiment_scroll.saturating_sub(1);
+    }
+
+    /// Has the routing model score a freshly saved conversation's tone, stored with
+    /// Kimi's current identity emotions
+    pub(crate) fn maybe_spawn_sentiment_analysis(
+        &self,
+        messages: &[crate::storage::ConversationMessage],
+        conversation_id: &str,
+    ) {
+        if !self.track_sentiment_enabled {
+            return;
+        }
+        let Ok((agent, manager, _)) = self.get_agent_chat_dependencies() else {
+            return;
+        };
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let content = messages
+            .iter()
+            .filter(|message| message.role != "System")
+            .map(|message| format!("{}: {}", message.role, message.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if content.is_empty() {
+            return;
+        }
+        let conversation_id = conversation_id.to_string();
+
+        std::thread::spawn(move || {
+            let routing_agent = manager.get_agent("routing").cloned();
+            let summary_agent = manager.summary_agent();
+            let analyzers: Vec<&crate::agents::Agent> = routing_agent
+                .iter()
+                .chain(summary_agent.iter())
+                .chain(std::iter::once(&agent))
+                .collect();
+            let Some(scored) = sentiment::analyze(&content, &analyzers, &manager) else {
+                return;
+            };
+            let kimi_emotions = identity::read_identity_state()
+                .map(|state| sentiment::identity_emotions(&state))
+                .unwrap_or_default();
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            let saved = runtime.block_on(storage.set_conversation_sentiment(
+                &conversation_id,
+                scored.valence,
+                &scored.emotions,
+                &kimi_emotions,
+            ));
+            if let Err(error) = saved {
+                tracing::warn!(%error, conversation = %conversation_id, "could not save sentiment");
+            }
+        });
+    }
+}
@@ -0,0 +1,192 @@
+use crate::app::{App, AppMode, Navigable, TextInput};
+use crate::config::Config;
+use crate::services::settings::{self, SETTINGS, SettingField, SettingKind};
+use color_eyre::Result;
+
+impl App {
+    pub fn open_settings(&mut self) -> Result<()> {
+        self.settings_config = Some(Config::load()?);
+        self.settings_selected_index = 0;
+        self.settings_input = None;
+        self.settings_error = None;
+        self.mode = AppMode::Settings;
+        Ok(())
+    }
+
+    pub fn close_settings(&mut self) {
+        self.settings_config = None;
+        self.settings_input = None;
+        self.settings_error = None;
+        self.mode = AppMode::Chat;
+    }
+
+    #[must_use]
+    pub fn selected_setting(&self) -> Option<&'static SettingField> {
+        SETTINGS.get(self.settings_selected_index)
+    }
+
+    /// Enter: toggles and choices change right away, other fields open for typing
+    pub fn activate_setting(&mut self) {
+        let (Some(field), Some(config)) = (self.selected_setting(), &self.settings_config) else {
+            return;
+        };
+        let current = settings::current_value(config, field);
+        match settings::next_value(field, &current) {
+            Some(next) => self.save_setting(field, &next),
+            None => {
+                self.settings_input = Some(TextInput::with_content(current));
+                self.settings_error = None;
+            }
+        }
+    }
+
+    pub fn confirm_setting_input(&mut self) {
+        let (Some(field), Some(input)) = (self.selected_setting(), self.settings_input.as_ref()) else {
+            return;
+        };
+        let value = input.content().to_string();
+        self.save_setting(field, &value);
+    }
+
+    pub fn cancel_setting_input(&mut self) {
+        self.settings_input = None;
+        self.settings_error = None;
+    }
+
+    /// Validates the value, writes config.toml and applies what can change at runtime
+    fn save_setting(&mut self, field: &SettingField, value: &str) {
+        let Some(config) = &self.settings_config else {
+            return;
+        };
+        let updated = match settings::apply(config, field, value) {
+            Ok(updated) => updated,
+            Err(error) => {
+                self.settings_error = Some(error.to_string());
+                return;
+            }
+        };
+        if let Err(error) = self.save_config(&updated) {
+            self.settings_error = Some(format!("Could not save config: {}", error));
+            return;
+        }
+        self.apply_loaded_config(&updated);
+        self.settings_config = Some(updated);
+        self.settings_input = None;
+        self.settings_error = None;
+        self.show_status_toast(if field.needs_restart {
+            "SAVED · RESTART TO APPLY"
+        } else {
+            "SAVED"
+        });
+    }
+
+    /// Writes `config` and records it as the loaded one, so the file watcher does not
+    /// report the app's own write as an outside change
+    pub(crate) fn save_config(&mut self, config: &Config) -> Result<()> {
+        config.save()?;
+        self.loaded_config = Some(config.clone());
+        Ok(())
+    }
+
+    /// Applies everything that can change at runtime, including agent models
+    fn apply_loaded_config(&mut self, config: &Config) {
+        // Follow a new default language unless `/language` changed this conversation
+        let follows_default = self.conversation_language == self.default_language;
+        self.apply_config(config);
+        if follows_default {
+            self.conversation_language.clone_from(&self.default_language);
+        }
+        for (agent_name, agent_config) in &config.agents {
+            let selected = self.selected_models.entry(agent_name.clone()).or_default();
+            if selected.first() == Some(&agent_config.model) {
+                continue;
+            }
+            selected.clear();
+            selected.push(agent_config.model.clone());
+            let is_current = self
+                .current_agent
+                .as_ref()
+                .is_some_and(|agent| &agent.name == agent_name);
+            if is_current && let Err(error) = self.load_agent(agent_name) {
+                tracing::warn!(%error, agent = %agent_name, "reloading agent failed");
+            }
+        }
+        self.loaded_config = Some(config.clone());
+    }
+
+    /// Re-reads the config after it changed on disk and reports what was reloaded
+    pub(crate) fn reload_config_from_disk(&mut self) {
+        let config = match Config::load() {
+            Ok(config) => config,
+            Err(error) => {
+                self.add_system_message(&format!("Config not reloaded: {}", error));
+                return;
+            }
+        };
+        let changes = match &self.loaded_config {
+            Some(previous) => settings::changed_sections(previous, &config),
+            None => settings::ConfigChanges::default(),
+        };
+        if changes.is_empty() {
+            return;
+        }
+        self.apply_loaded_config(&config);
+        if self.settings_config.is_some() {
+            self.settings_config = Some(config);
+        }
+
+        let mut lines = Vec::new();
+        if !changes.applied.is_empty() {
+            lines.push(format!("Config reloaded: {}", changes.applied.join(", ")));
+        }
+        if !changes.needs_restart.is_empty() {
+            lines.push(format!(
+                "Saved, applies after a restart: {}",
+                changes.needs_restart.join(", ")
+            ));
+        }
+        self.add_system_message(&lines.join("\n"));
+    }
+
+    pub fn next_setting(&mut self) {
+        SettingsNavigable::new(self).next_item();
+        self.settings_error = None;
+    }
+
+    pub fn previous_setting(&mut self) {
+        SettingsNavigable::new(self).previous_item();
+        self.settings_error = None;
+    }
+
+    /// Whether the selected field takes typed input (as opposed to Enter toggling it)
+    #[must_use]
+    pub fn selected_setting_is_typed(&self) -> bool {
+        self.selected_setting().is_some_and(|field| {
+            !matches!(field.kind, SettingKind::Toggle | SettingKind::Choice(_))
+        })
+    }
+}
+
+pub struct SettingsNavigable<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> SettingsNavigable<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+}
+
+impl<'a> Navigable for SettingsNavigable<'a> {
+    fn get_item_count(&self) -> usize {
+        SETTINGS.len()
+    }
+
+    fn get_selected_index(&self) -> usize {
+        self.app.settings_selected_index
+    }
+
+    fn set_selected_index(&mut self, index: usize) {
+        self.app.settings_selected_index = index;
+    }
+}
@@ -0,0 +1,178 @@
+use crate::app::types::MessageRole;
+use crate::app::{App, AppMode};
+use crate::services::clipboard::{entry_title, extract_code_blocks};
+use color_eyre::Result;
+
+impl App {
+    /// Saves every code block from the latest assistant reply as a snippet
+    pub fn save_last_code_blocks_as_snippets(&mut self) -> Result<()> {
+        let Some(content) = self
+            .chat_history
+            .iter()
+            .rev()
+            .find(|message| message.role == MessageRole::Assistant)
+            .map(|message| message.content.clone())
+        else {
+            self.show_status_toast("NO RESPONSE");
+            return Ok(());
+        };
+
+        let blocks = extract_code_blocks(&content);
+        if blocks.is_empty() {
+            self.show_status_toast("NO CODE BLOCK");
+            return Ok(());
+        }
+
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        for block in &blocks {
+            runtime.block_on(storage.save_snippet(&block.title(), &block.language, &block.code))?;
+        }
+
+        if blocks.len() == 1 {
+            self.show_status_toast("SNIPPET SAVED");
+        } else {
+            self.show_status_toast(format!("{} SNIPPETS SAVED", blocks.len()));
+        }
+        Ok(())
+    }
+
+    pub fn open_snippets(&mut self) -> Result<()> {
+        self.mode = AppMode::SnippetList;
+        self.snippet_selected_index = 0;
+        self.snippets_show_clipboard = false;
+        self.load_snippet_list()
+    }
+
+    /// Tab switches the list between saved snippets and this session's clipboard history
+    pub fn toggle_snippet_source(&mut self) {
+        self.snippets_show_clipboard = !self.snippets_show_clipboard;
+        self.snippet_selected_index = 0;
+    }
+
+    /// Rows in the list currently shown
+    #[must_use]
+    pub fn snippet_entry_count(&self) -> usize {
+        if self.snippets_show_clipboard {
+            self.clipboard_service.history().len()
+        } else {
+            self.snippets.len()
+        }
+    }
+
+    fn selected_snippet_content(&self) -> Option<String> {
+        if self.snippets_show_clipboard {
+            self.clipboard_service.history().get(self.snippet_selected_index).cloned()
+        } else {
+            self.snippets
+                .get(self.snippet_selected_index)
+                .map(|snippet| snippet.content.clone())
+        }
+    }
+
+    pub fn close_snippets(&mut self) {
+        self.mode = AppMode::Chat;
+        self.snippets.clear();
+    }
+
+    fn load_snippet_list(&mut self) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.snippets = runtime.block_on(storage.load_snippets())?;
+        if self.snippet_selected_index >= self.snippets.len() {
+            self.snippet_selected_index = self.snippets.len().saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    pub fn next_snippet(&mut self) {
+        let count = self.snippet_entry_count();
+        if count > 0 {
+            self.snippet_selected_index = (self.snippet_selected_index + 1) % count;
+        }
+    }
+
+    pub fn previous_snippet(&mut self) {
+        let count = self.snippet_entry_count();
+        if count > 0 {
+            self.snippet_selected_index = self
+                .snippet_selected_index
+                .checked_sub(1)
+                .unwrap_or(count.saturating_sub(1));
+        }
+    }
+
+    /// Inserts the selected snippet or clipboard entry into the chat input and returns to chat
+    pub fn insert_selected_snippet(&mut self) {
+        let Some(text) = self.selected_snippet_content() else {
+            return;
+        };
+        let mut content = self.chat_input.content().to_string();
+        if !content.is_empty() && !content.ends_with(' ') {
+            content.push(' ');
+        }
+        content.push_str(&text);
+        self.chat_input.set_content(content);
+        self.close_snippets();
+    }
+
+    /// Copies the selected snippet or clipboard entry back to the system clipboard
+    pub fn copy_selected_snippet(&mut self) {
+        let Some(content) = self.selected_snippet_content() else {
+            return;
+        };
+        match self.clipboard_service.copy_text(&content) {
+            Ok(()) => {
+                // The copied entry moved to the top of the history
+                if self.snippets_show_clipboard {
+                    self.snippet_selected_index = 0;
+                }
+                self.show_status_toast("COPIED");
+            }
+            Err(error) => self.show_status_toast(format!("COPY FAILED: {}", error)),
+        }
+    }
+
+    /// Keeps the selected clipboard entry as a saved snippet
+    pub fn save_selected_clipboard_entry(&mut self) {
+        if !self.snippets_show_clipboard {
+            return;
+        }
+        let Some(content) = self.selected_snippet_content() else {
+            return;
+        };
+        self.spawn_storage_job(move |storage, runtime| {
+            if let Err(error) = runtime.block_on(storage.save_snippet(&entry_title(&content), "", &content)) {
+                tracing::error!(%error, "could not save clipboard entry as snippet");
+            }
+        });
+        self.show_status_toast("SNIPPET SAVED");
+    }
+
+    pub fn delete_selected_snippet(&mut self) -> Result<()> {
+        if self.snippets_show_clipboard {
+            self.clipboard_service.forget(self.snippet_selected_index);
+            let count = self.snippet_entry_count();
+            if self.snippet_selected_index >= count {
+                self.snippet_selected_index = count.saturating_sub(1);
+            }
+            self.show_status_toast("REMOVED FROM HISTORY");
+            return Ok(());
+        }
+        let Some(id) = self
+            .snippets
+            .get(self.snippet_selected_index)
+            .map(|snippet| snippet.id.clone())
+        else {
+            return Ok(());
+        };
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.delete_snippet(&id))?;
+        self.show_status_toast("SNIPPET DELETED");
+        self.load_snippet_list()
+    }
+}
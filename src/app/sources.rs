@@ -0,0 +1,80 @@
+use crate::app::types::{ChatMessage, MessageRole};
+use crate::app::{App, AppMode};
+use crate::services::citations::{self, Source, SourceKind};
+
+impl App {
+    /// The latest assistant reply, which `/sources` describes
+    #[must_use]
+    pub fn last_reply(&self) -> Option<&ChatMessage> {
+        self.chat_history
+            .iter()
+            .rev()
+            .find(|message| message.role == MessageRole::Assistant)
+    }
+
+    /// Numbered sources of the last reply
+    #[must_use]
+    pub fn last_reply_sources(&self) -> &[Source] {
+        self.last_reply()
+            .and_then(|message| message.context_usage.as_ref())
+            .map_or(&[], |usage| usage.sources.as_slice())
+    }
+
+    /// Source numbers the last reply actually cites
+    #[must_use]
+    pub fn last_reply_cited(&self) -> Vec<usize> {
+        self.last_reply()
+            .map(|message| citations::cited_numbers(&message.content, self.last_reply_sources().len()))
+            .unwrap_or_default()
+    }
+
+    pub fn open_sources(&mut self) {
+        if self.last_reply_sources().is_empty() {
+            self.close_menu();
+            self.add_system_message("The last reply used no notes, memories, search results or stored tool outputs.");
+            return;
+        }
+        self.sources_selected_index = 0;
+        self.mode = AppMode::Sources;
+    }
+
+    pub fn close_sources(&mut self) {
+        self.mode = AppMode::Chat;
+    }
+
+    pub fn next_source(&mut self) {
+        let count = self.last_reply_sources().len();
+        if count > 0 {
+            self.sources_selected_index = (self.sources_selected_index + 1) % count;
+        }
+    }
+
+    pub fn previous_source(&mut self) {
+        let count = self.last_reply_sources().len();
+        if count > 0 {
+            self.sources_selected_index = self
+                .sources_selected_index
+                .checked_sub(1)
+                .unwrap_or(count - 1);
+        }
+    }
+
+    /// Enter: opens a web source in the browser
+    pub fn open_selected_source(&mut self) {
+        let Some(source) = self.last_reply_sources().get(self.sources_selected_index) else {
+            return;
+        };
+        if source.kind != SourceKind::Web {
+            self.show_status_toast("NOT A LINK");
+            return;
+        }
+        let url = source.location.clone();
+        match crate::services::links::open_url(&url) {
+            Ok(()) => self.show_status_toast("OPENING LINK"),
+            Err(error) => {
+                tracing::warn!(%error, %url, "could not open source");
+                self.show_status_toast("OPEN FAILED");
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::app::App;
+use crate::storage::StorageManager;
+use std::sync::mpsc::{Sender, channel};
+
+type StorageJob = Box<dyn FnOnce(&tokio::runtime::Runtime) + Send>;
+
+/// One background thread that runs storage reads and writes in submission order, so the
+/// redraw loop never waits on the database and a read queued after a write sees it
+pub struct StorageWorker {
+    jobs: Sender<StorageJob>,
+}
+
+impl StorageWorker {
+    fn spawn() -> Option<Self> {
+        let runtime = tokio::runtime::Runtime::new()
+            .inspect_err(|error| tracing::error!(%error, "could not start storage worker"))
+            .ok()?;
+        let (jobs, receiver) = channel::<StorageJob>();
+        std::thread::spawn(move || {
+            // Ends once the app drops the sender
+            for job in receiver {
+                job(&runtime);
+            }
+        });
+        Some(Self { jobs })
+    }
+}
+
+impl App {
+    /// Queues `job` behind the storage jobs submitted before it; results go back as `AgentEvent`s
+    pub(crate) fn spawn_storage_job(
+        &mut self,
+        job: impl FnOnce(&StorageManager, &tokio::runtime::Runtime) + Send + 'static,
+    ) {
+        if !self.ensure_storage() {
+            return;
+        }
+        // Captured per job: a profile switch replaces the storage under a running worker
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        if self.storage_worker.is_none() {
+            self.storage_worker = StorageWorker::spawn();
+        }
+        let Some(worker) = &self.storage_worker else {
+            return;
+        };
+        if worker.jobs.send(Box::new(move |runtime| job(&storage, runtime))).is_err() {
+            tracing::error!("storage worker stopped; job dropped");
+            self.storage_worker = None;
+        }
+    }
+
+    /// Blocks until every queued storage job is done (before quitting or switching profile)
+    pub fn wait_for_storage_jobs(&self) {
+        let Some(worker) = &self.storage_worker else {
+            return;
+        };
+        let (done_tx, done_rx) = channel();
+        let marker: StorageJob = Box::new(move |_| {
+            let _ = done_tx.send(());
+        });
+        if worker.jobs.send(marker).is_ok() {
+            let _ = done_rx.recv();
+        }
+    }
+}
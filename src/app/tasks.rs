@@ -0,0 +1,164 @@
+use crate::app::{AgentEvent, App, AppMode};
+use crate::services::projects::TaskStatus;
+use crate::services::tasks;
+use chrono::NaiveDate;
+use color_eyre::Result;
+
+impl App {
+    pub fn open_tasks(&mut self) -> Result<()> {
+        self.mode = AppMode::Tasks;
+        self.due_tasks_selected_index = 0;
+        self.load_due_tasks()
+    }
+
+    pub fn close_tasks(&mut self) {
+        self.mode = AppMode::Chat;
+        self.due_tasks.clear();
+    }
+
+    fn load_due_tasks(&mut self) -> Result<()> {
+        if !self.ensure_storage() {
+            return Err(color_eyre::eyre::eyre!("Storage not initialized"));
+        }
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.due_tasks = runtime.block_on(storage.load_due_tasks(false))?;
+        self.due_tasks_selected_index = self
+            .due_tasks_selected_index
+            .min(self.due_tasks.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn next_due_task(&mut self) {
+        if !self.due_tasks.is_empty() {
+            self.due_tasks_selected_index = (self.due_tasks_selected_index + 1) % self.due_tasks.len();
+        }
+    }
+
+    pub fn previous_due_task(&mut self) {
+        if !self.due_tasks.is_empty() {
+            self.due_tasks_selected_index = self
+                .due_tasks_selected_index
+                .checked_sub(1)
+                .unwrap_or(self.due_tasks.len().saturating_sub(1));
+        }
+    }
+
+    /// Turns a proposed task into one Kimi reminds about on its day
+    pub fn accept_selected_due_task(&mut self) -> Result<()> {
+        let Some(task) = self.due_tasks.get(self.due_tasks_selected_index) else {
+            return Ok(());
+        };
+        if task.status != TaskStatus::Proposed {
+            return Ok(());
+        }
+        let id = task.id.clone();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.set_due_task_status(&id, TaskStatus::Open))?;
+        self.show_status_toast("TASK ACCEPTED");
+        self.load_due_tasks()
+    }
+
+    pub fn complete_selected_due_task(&mut self) -> Result<()> {
+        let Some(task) = self.due_tasks.get(self.due_tasks_selected_index) else {
+            return Ok(());
+        };
+        let id = task.id.clone();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.set_due_task_status(&id, TaskStatus::Done))?;
+        self.show_status_toast("TASK DONE");
+        self.load_due_tasks()
+    }
+
+    /// Pushes the selected task `days` out; snoozing a proposed task also accepts it
+    pub fn snooze_selected_due_task(&mut self, days: i64) -> Result<()> {
+        let Some(task) = self.due_tasks.get(self.due_tasks_selected_index) else {
+            return Ok(());
+        };
+        let today = chrono::Local::now().date_naive();
+        let due = NaiveDate::parse_from_str(&task.due, "%Y-%m-%d").unwrap_or(today);
+        let snoozed = tasks::snooze(due, today, days);
+        let (id, proposed) = (task.id.clone(), task.status == TaskStatus::Proposed);
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.snooze_due_task(&id, &snoozed.format("%Y-%m-%d").to_string()))?;
+        if proposed {
+            runtime.block_on(storage.set_due_task_status(&id, TaskStatus::Open))?;
+        }
+        self.show_status_toast(&format!("SNOOZED TO {}", snoozed.format("%b %-d").to_string().to_uppercase()));
+        self.load_due_tasks()
+    }
+
+    pub fn delete_selected_due_task(&mut self) -> Result<()> {
+        let Some(task) = self.due_tasks.get(self.due_tasks_selected_index) else {
+            return Ok(());
+        };
+        let id = task.id.clone();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        runtime.block_on(storage.delete_due_task(&id))?;
+        self.load_due_tasks()
+    }
+
+    /// Looks for dated commitments in a freshly saved conversation and proposes them in `/tasks`
+    pub(crate) fn maybe_spawn_task_extraction(
+        &self,
+        messages: &[crate::storage::ConversationMessage],
+        conversation_id: &str,
+    ) {
+        if !self.extract_tasks_enabled {
+            return;
+        }
+        let Ok((agent, manager, agent_tx)) = self.get_agent_chat_dependencies() else {
+            return;
+        };
+        let Some(storage) = self.storage.clone() else {
+            return;
+        };
+        let content = messages
+            .iter()
+            .filter(|message| message.role != "System")
+            .map(|message| format!("{}: {}", message.role, message.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let conversation_id = conversation_id.to_string();
+
+        if content.is_empty() {
+            return;
+        }
+
+        std::thread::spawn(move || {
+            let summary_agent = manager.summary_agent();
+            let routing_agent = manager.get_agent("routing").cloned();
+            let extractors: Vec<&crate::agents::Agent> = summary_agent
+                .iter()
+                .chain(routing_agent.iter())
+                .chain(std::iter::once(&agent))
+                .collect();
+            let today = chrono::Local::now().date_naive();
+            let found = tasks::extract_commitments(&content, today, &extractors, &manager);
+            if found.is_empty() {
+                return;
+            }
+            let proposals: Vec<(String, String)> = found
+                .into_iter()
+                .map(|task| (task.text, task.due.format("%Y-%m-%d").to_string()))
+                .collect();
+            let Ok(runtime) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            match runtime.block_on(storage.propose_due_tasks(&proposals, &conversation_id)) {
+                Ok(0) => {}
+                Ok(count) => {
+                    let _ = agent_tx.send(AgentEvent::TasksExtracted { count });
+                }
+                Err(error) => tracing::warn!(%error, "could not save extracted tasks"),
+            }
+        });
+    }
+
+    pub(crate) fn handle_tasks_extracted(&mut self, count: usize) {
+        let noun = if count == 1 { "TASK" } else { "TASKS" };
+        self.show_status_toast(&format!("{} DATED {} FOUND: /tasks", count, noun));
+        if self.mode == AppMode::Tasks {
+            let _ = self.load_due_tasks();
+        }
+    }
+}
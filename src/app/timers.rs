@@ -0,0 +1,78 @@
+use crate::app::App;
+use crate::services::timers::{self, StopwatchCommand, Timer, TimerRequest};
+use std::time::Instant;
+
+impl App {
+    /// Starts a countdown shown in the footer; returns the confirmation for the chat
+    pub fn start_timer(&mut self, request: TimerRequest) -> String {
+        let timer = Timer::start(request);
+        let done_at = chrono::Local::now()
+            + chrono::Duration::from_std(timer.duration).unwrap_or_else(|_| chrono::Duration::zero());
+        let reply = format!(
+            "Timer set: {}, done at {}.",
+            timer.name(),
+            done_at.format("%H:%M:%S")
+        );
+        self.timers.push(timer);
+        self.timers.sort_by_key(|timer| timer.ends_at);
+        reply
+    }
+
+    /// `/timer cancel`
+    pub fn cancel_timers(&mut self) {
+        let count = self.timers.len();
+        self.timers.clear();
+        self.show_status_toast(format!("{} TIMER(S) CANCELLED", count));
+    }
+
+    /// Starts or stops the stopwatch; returns the message for the chat
+    pub fn run_stopwatch(&mut self, command: StopwatchCommand) -> String {
+        match (command, self.stopwatch_started) {
+            (StopwatchCommand::Start, None) => {
+                self.stopwatch_started = Some(Instant::now());
+                "Stopwatch started.".to_string()
+            }
+            (StopwatchCommand::Start, Some(started)) => format!(
+                "The stopwatch is already running ({}).",
+                timers::format_remaining(started.elapsed())
+            ),
+            (StopwatchCommand::Stop, Some(started)) => {
+                self.stopwatch_started = None;
+                format!("Stopwatch stopped at {}.", timers::format_remaining(started.elapsed()))
+            }
+            (StopwatchCommand::Stop, None) => "The stopwatch isn't running.".to_string(),
+        }
+    }
+
+    /// `/stopwatch` starts a stopped stopwatch and stops a running one
+    pub fn toggle_stopwatch(&mut self) {
+        let command = if self.stopwatch_started.is_some() {
+            StopwatchCommand::Stop
+        } else {
+            StopwatchCommand::Start
+        };
+        let message = self.run_stopwatch(command);
+        self.add_system_message(&message);
+    }
+
+    /// Called every loop iteration: announces and removes finished timers
+    pub fn tick_timers(&mut self) {
+        if !self.timers.iter().any(Timer::is_done) {
+            return;
+        }
+        let (done, running): (Vec<Timer>, Vec<Timer>) =
+            std::mem::take(&mut self.timers).into_iter().partition(Timer::is_done);
+        self.timers = running;
+        for timer in done {
+            let name = timer.name();
+            self.show_status_toast(format!("⏰ {} DONE", name.to_uppercase()));
+            if self.announce_timers
+                && let Some(tts) = &self.tts_service
+                && tts.is_configured()
+                && let Err(error) = tts.speak_alert(&format!("Your {} is done.", name))
+            {
+                tracing::warn!(%error, "could not announce timer");
+            }
+        }
+    }
+}
@@ -4,6 +4,8 @@ pub struct AvailableModel {
     pub name: String,
     pub source: ModelSource,
     pub is_available: bool,
+    /// Parameter count, quantization and size (Ollama models only)
+    pub details: Option<crate::agents::ollama::ModelDetails>,
 }
 
 /// Source of an AI model
@@ -28,6 +30,20 @@ pub struct MenuItem {
     pub description: String,
 }
 
+/// One row of the Help view: a key, command or provider and what it does
+#[derive(Debug, Clone)]
+pub struct HelpEntry {
+    pub key: String,
+    pub description: String,
+}
+
+/// A titled group of rows in the Help view
+#[derive(Debug, Clone)]
+pub struct HelpSection {
+    pub title: String,
+    pub entries: Vec<HelpEntry>,
+}
+
 /// A chat message with role, content, and timestamp
 #[derive(Debug, Clone)]
 pub struct ChatMessage {
@@ -35,8 +51,64 @@ pub struct ChatMessage {
     pub content: String,
     pub timestamp: String,
     pub display_name: Option<String>,
-    #[allow(dead_code)]
     pub context_usage: Option<ContextUsage>,
+    /// Left out of what is sent to the model (removed in selection mode)
+    pub excluded_from_context: bool,
+    /// Earlier message this one replies to (set by the quote action)
+    pub quote: Option<MessageQuote>,
+    /// Model, duration and token counts of an assistant reply
+    pub stats: Option<crate::storage::MessageStats>,
+    /// Verdict of the agent's answer check, shown as a badge
+    pub check: Option<crate::services::verification::AnswerCheck>,
+    /// Pictures shown under the message, e.g. from `/imagine` or attached to it
+    pub images: Vec<MessageImage>,
+    /// Documents attached to the message, shown as `[[file:...]]` tokens
+    pub files: Vec<std::path::PathBuf>,
+}
+
+/// A picture saved on disk with the thumbnail drawn in the chat
+#[derive(Debug, Clone)]
+pub struct MessageImage {
+    pub path: std::path::PathBuf,
+    /// Empty when the file could not be read; only the path is shown then
+    pub preview: Vec<crate::services::images::PreviewRow>,
+}
+
+impl MessageImage {
+    /// Rows of '▀' cells the chat draws for one picture
+    pub const PREVIEW_ROWS: u32 = 12;
+    pub const PREVIEW_COLUMNS: u32 = 48;
+
+    pub fn load(path: std::path::PathBuf) -> Self {
+        let preview = crate::services::images::preview(&path, Self::PREVIEW_COLUMNS, Self::PREVIEW_ROWS)
+            .unwrap_or_default();
+        Self { path, preview }
+    }
+
+    /// The `[[image:...]]` token the picture was attached with
+    #[must_use]
+    pub fn token(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map_or_else(|| "image".to_string(), |name| name.to_string_lossy().into_owned());
+        format!("[[image:{}]]", name)
+    }
+}
+
+/// A message held back because a past conversation already answered it
+#[derive(Debug, Clone)]
+pub struct DuplicateOffer {
+    /// The message as it would be sent, context modifiers removed
+    pub question: String,
+    pub earlier: crate::storage::AnsweredQuestion,
+}
+
+/// Condensed copy of the message a reply refers to
+#[derive(Debug, Clone)]
+pub struct MessageQuote {
+    pub role: MessageRole,
+    pub content: String,
 }
 
 impl ChatMessage {
@@ -51,6 +123,12 @@ impl ChatMessage {
             timestamp: Self::now_timestamp(),
             display_name: None,
             context_usage: None,
+            excluded_from_context: false,
+            quote: None,
+            stats: None,
+            check: None,
+            images: Vec::new(),
+            files: Vec::new(),
         }
     }
 
@@ -61,6 +139,12 @@ impl ChatMessage {
             timestamp: Self::now_timestamp(),
             display_name: None,
             context_usage: None,
+            excluded_from_context: false,
+            quote: None,
+            stats: None,
+            check: None,
+            images: Vec::new(),
+            files: Vec::new(),
         }
     }
 
@@ -75,6 +159,12 @@ impl ChatMessage {
             timestamp: Self::now_timestamp(),
             display_name,
             context_usage,
+            excluded_from_context: false,
+            quote: None,
+            stats: None,
+            check: None,
+            images: Vec::new(),
+            files: Vec::new(),
         }
     }
 }
@@ -106,11 +196,150 @@ pub enum MessageRole {
     System,
 }
 
+impl MessageRole {
+    /// Name used for the role in storage
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "User",
+            Self::Assistant => "Assistant",
+            Self::System => "System",
+        }
+    }
+
+    /// Parses a stored role name; anything unknown is treated as a system message
+    #[must_use]
+    pub fn from_stored(role: &str) -> Self {
+        match role {
+            "User" => Self::User,
+            "Assistant" => Self::Assistant,
+            _ => Self::System,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ContextUsage {
     pub notes_used: usize,
     pub history_used: usize,
     pub memories_used: usize,
+    pub previous_session_used: bool,
+    /// Sources the message turned off with `!nomem`, `!nonotes`, `!nosearch` or `!clean`
+    pub skipped: Vec<PromptSection>,
+    /// Numbered notes, memories, pages and tool outputs the reply could cite (see `/sources`)
+    pub sources: Vec<crate::services::citations::Source>,
+}
+
+/// A file open in the built-in editor
+#[derive(Debug, Clone)]
+pub struct EditorSession {
+    pub path: std::path::PathBuf,
+    pub title: String,
+    pub buffer: crate::services::editor::TextBuffer,
+    /// First visible line
+    pub scroll: usize,
+    pub dirty: bool,
+    /// Set by the first Esc on unsaved changes; a second Esc discards them
+    pub confirm_discard: bool,
+    pub return_mode: crate::app::AppMode,
+}
+
+/// Sample reply generated to show how a personality sounds
+#[derive(Debug, Clone)]
+pub struct PersonalitySample {
+    pub personality: String,
+    /// None while the reply is being generated
+    pub reply: Option<String>,
+}
+
+/// Part of the assembled system prompt, in the order sections are added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptSection {
+    Foundation,
+    Persona,
+    ReplyContext,
+    ProjectSuggestions,
+    ActiveProject,
+    Calendar,
+    Git,
+    PreviousSession,
+    CheckIn,
+    Memories,
+    ConversationRecall,
+    Notes,
+    WebSearch,
+    Personality,
+}
+
+impl PromptSection {
+    pub const ALL: [Self; 14] = [
+        Self::Foundation,
+        Self::Persona,
+        Self::ReplyContext,
+        Self::ProjectSuggestions,
+        Self::ActiveProject,
+        Self::Calendar,
+        Self::Git,
+        Self::PreviousSession,
+        Self::CheckIn,
+        Self::Memories,
+        Self::ConversationRecall,
+        Self::Notes,
+        Self::WebSearch,
+        Self::Personality,
+    ];
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Foundation => "Foundation",
+            Self::Persona => "Persona",
+            Self::ReplyContext => "Reply context",
+            Self::ProjectSuggestions => "Project suggestions",
+            Self::ActiveProject => "Active project",
+            Self::Calendar => "Calendar",
+            Self::Git => "Git repository",
+            Self::PreviousSession => "Previous session",
+            Self::CheckIn => "Check-in",
+            Self::Memories => "Memories",
+            Self::ConversationRecall => "Conversation recall",
+            Self::Notes => "Obsidian notes",
+            Self::WebSearch => "Web search",
+            Self::Personality => "Personality",
+        }
+    }
+}
+
+/// Text one prompt section contributed to an assembled system prompt
+#[derive(Debug, Clone)]
+pub struct PromptSectionText {
+    pub section: PromptSection,
+    pub text: String,
+}
+
+impl PromptSectionText {
+    /// Rough token count (about four characters per token)
+    #[must_use]
+    pub fn estimated_tokens(&self) -> usize {
+        self.text.chars().count().div_ceil(4)
+    }
+}
+
+/// Full-text search hits from one conversation, best match first
+#[derive(Debug, Clone)]
+pub struct SearchResultGroup {
+    pub conversation_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub agent_name: String,
+    pub hits: Vec<crate::storage::MessageSearchHit>,
+}
+
+/// Summary of the conversation that was closed most recently, kept for carry-over
+#[derive(Debug, Clone)]
+pub struct PreviousSession {
+    pub summary: String,
+    pub closed_at: std::time::Instant,
 }
 
 #[derive(Debug, Clone)]
@@ -123,6 +352,17 @@ pub enum ChatAttachment {
         token: String,
         png_bytes: Vec<u8>,
     },
+    /// Non-image file whose extracted text is attached as context
+    Document {
+        token: String,
+        path: std::path::PathBuf,
+        text: String,
+    },
+    /// Text piped into kimi on stdin, attached as context
+    Stdin {
+        token: String,
+        input: crate::services::stdin::PipedInput,
+    },
 }
 
 impl ChatAttachment {
@@ -131,9 +371,61 @@ impl ChatAttachment {
         match self {
             ChatAttachment::FilePath { token, .. } => token,
             ChatAttachment::ClipboardImage { token, .. } => token,
+            ChatAttachment::Document { token, .. } => token,
+            ChatAttachment::Stdin { token, .. } => token,
         }
     }
 
+    /// Display name (file name, or the clipboard label)
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            ChatAttachment::FilePath { path, .. } | ChatAttachment::Document { path, .. } => path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("file")
+                .to_string(),
+            ChatAttachment::ClipboardImage { token, .. } => token
+                .trim_start_matches("[[image:")
+                .trim_end_matches("]]")
+                .to_string(),
+            ChatAttachment::Stdin { .. } => "stdin".to_string(),
+        }
+    }
+
+    /// Size in bytes of the attached file or image data
+    #[must_use]
+    pub fn size_bytes(&self) -> u64 {
+        match self {
+            ChatAttachment::FilePath { path, .. } | ChatAttachment::Document { path, .. } => {
+                std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+            }
+            ChatAttachment::ClipboardImage { png_bytes, .. } => png_bytes.len() as u64,
+            ChatAttachment::Stdin { input, .. } => input.total_bytes as u64,
+        }
+    }
+
+    /// Short preview line: image kind or the first line of extracted text
+    #[must_use]
+    pub fn preview(&self) -> String {
+        match self {
+            ChatAttachment::FilePath { .. } | ChatAttachment::ClipboardImage { .. } => {
+                "image".to_string()
+            }
+            ChatAttachment::Document { text, .. } => {
+                let first_line = text
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .unwrap_or("");
+                let preview: String = first_line.chars().take(60).collect();
+                format!("{} chars · {}", text.chars().count(), preview)
+            }
+            ChatAttachment::Stdin { input, .. } => input
+                .truncation_note()
+                .unwrap_or_else(|| format!("{} chars", input.text.chars().count())),
+        }
+    }
 }
 
 /// Represents an individual download in progress
@@ -141,6 +433,41 @@ impl ChatAttachment {
 pub struct DownloadItem {
     pub url: String,
     pub progress: Option<u8>,
+    /// Transfer rate as reported by yt-dlp, e.g. "1.20MiB/s"
+    pub speed: Option<String>,
+    pub destination: Option<std::path::PathBuf>,
+    /// Set from the `/downloads` panel to stop yt-dlp
+    pub cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
     pub frame: u8,
     pub last_tick: Option<std::time::Instant>,
 }
+
+/// Text being entered in the projects view
+#[derive(Debug, Clone)]
+pub enum ProjectForm {
+    /// New project (`original` is None) or an edit of an existing one
+    Project {
+        original: Option<String>,
+        name: crate::app::TextInput,
+        description: crate::app::TextInput,
+        editing_description: bool,
+    },
+    /// A new entry for the open project
+    Entry {
+        project: String,
+        text: crate::app::TextInput,
+    },
+    /// A new task for the open project
+    Task {
+        project: String,
+        text: crate::app::TextInput,
+    },
+}
+
+/// Which list of the project detail view receives the keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectDetailFocus {
+    #[default]
+    Entries,
+    Tasks,
+}
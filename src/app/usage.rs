@@ -0,0 +1,69 @@
+use crate::app::{AgentEvent, App, AppMode};
+use crate::services::notifications::NotificationKind;
+use crate::services::usage::UsageLedger;
+use color_eyre::Result;
+use std::sync::Arc;
+
+/// Days of history shown in `/usage`
+const REPORT_DAYS: i64 = 7;
+
+impl App {
+    /// Hands the agent manager a ledger so hosted requests are logged and budgets enforced
+    pub(crate) fn attach_usage_ledger(&mut self) {
+        if !self.usage.enabled || !self.ensure_storage() {
+            return;
+        }
+        let (Some(storage), Some(tx)) = (self.storage.clone(), self.agent_tx.clone()) else {
+            return;
+        };
+        let notifier = Arc::new(move |title: String, message: String| {
+            let _ = tx.send(AgentEvent::SystemMessage(format!("{}: {}", title, message)));
+            let _ = tx.send(AgentEvent::Notification {
+                kind: NotificationKind::Budget,
+                title,
+                message,
+            });
+        });
+        match UsageLedger::new(storage, self.usage.clone(), notifier) {
+            Ok(ledger) => {
+                ledger.set_paused(self.incognito);
+                if let Some(manager) = &mut self.agent_manager {
+                    manager.set_usage_ledger(Arc::new(ledger));
+                }
+            }
+            Err(error) => tracing::warn!(%error, "usage ledger unavailable"),
+        }
+    }
+
+    /// Turns incognito on or off for the open chat, pausing the usage ledger with it
+    pub(crate) fn set_incognito(&mut self, incognito: bool) {
+        self.incognito = incognito;
+        if let Some(manager) = &self.agent_manager {
+            manager.pause_usage_recording(incognito);
+        }
+    }
+
+    pub fn open_usage(&mut self) -> Result<()> {
+        self.ensure_storage();
+        let since = (chrono::Local::now() - chrono::Duration::days(REPORT_DAYS - 1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let (storage, runtime) = self.storage_with_runtime()?;
+        self.usage_rows = runtime.block_on(storage.load_usage(&since))?;
+        self.usage_scroll = 0;
+        self.mode = AppMode::Usage;
+        Ok(())
+    }
+
+    pub fn close_usage(&mut self) {
+        self.mode = AppMode::Chat;
+    }
+
+    pub fn scroll_usage_up(&mut self) {
+        self.usage_scroll = self.usage_scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_usage_down(&mut self) {
+        self.usage_scroll = self.usage_scroll.saturating_add(1);
+    }
+}
@@ -17,11 +17,70 @@ pub struct Config {
     #[serde(default)]
     pub brave: BraveConfig,
     #[serde(default)]
+    pub github: GithubConfig,
+    #[serde(default)]
+    pub homeassistant: HomeAssistantConfig,
+    #[serde(default)]
     pub obsidian: ObsidianConfig,
     #[serde(default)]
     pub embeddings: EmbeddingsConfig,
     #[serde(default)]
     pub personality: PersonalityConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub journal: JournalConfig,
+    #[serde(default)]
+    pub diary: DiaryConfig,
+    #[serde(default)]
+    pub feeds: FeedsConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
+    #[serde(default)]
+    pub files: FileSearchConfig,
+    #[serde(default)]
+    pub databases: Vec<DatabaseConfig>,
+    /// Recurring jobs (`[[schedule]]`); `/schedule` adds more at runtime
+    #[serde(default)]
+    pub schedule: Vec<ScheduledJobConfig>,
+    #[serde(default)]
+    pub git: GitConfig,
+    #[serde(default)]
+    pub usage: UsageConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub calculator: CalculatorConfig,
+    #[serde(default)]
+    pub finance: FinanceConfig,
+    #[serde(default)]
+    pub weather: WeatherConfig,
+    #[serde(default)]
+    pub transit: TransitConfig,
+    #[serde(default)]
+    pub timers: TimersConfig,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    #[serde(default)]
+    pub editor: EditorConfig,
+    #[serde(default)]
+    pub conversation: ConversationConfig,
+    #[serde(default)]
+    pub ocr: OcrConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub images: ImagesConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
     pub agents: HashMap<String, AgentConfig>,
 }
 
@@ -31,7 +90,12 @@ struct LocalConfig {
     venice: Option<LocalApiConfig>,
     gab: Option<LocalApiConfig>,
     brave: Option<LocalApiConfig>,
+    github: Option<LocalApiConfig>,
+    homeassistant: Option<LocalHomeAssistantConfig>,
+    images: Option<LocalApiConfig>,
     obsidian: Option<LocalObsidianConfig>,
+    calendar: Option<LocalCalendarConfig>,
+    notifications: Option<LocalNotificationsConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,16 +108,93 @@ struct LocalApiConfig {
     api_key: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct LocalHomeAssistantConfig {
+    url: Option<String>,
+    api_key: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct LocalObsidianConfig {
     vault_name: Option<String>,
     vault_path: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct LocalCalendarConfig {
+    sources: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalNotificationsConfig {
+    webhook_url: Option<String>,
+}
+
 /// Ollama backend configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OllamaConfig {
     pub url: String,
+    /// Default keep_alive for agents without their own ("10m", "0" unloads right away,
+    /// "-1" keeps models loaded); unset uses Ollama's default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_alive: Option<String>,
+    /// Preload the model when an agent is loaded or its model selected
+    #[serde(default = "default_true")]
+    pub warm_up: bool,
+    #[serde(default = "default_ollama_requests")]
+    pub requests: RequestConfig,
+}
+
+/// Timeouts and retries for one provider's HTTP requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestConfig {
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Whole request, including generating the reply
+    #[serde(default = "default_request_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Extra attempts after connection errors, 429 and 5xx, within one `timeout_secs`;
+    /// request timeouts are not retried
+    #[serde(default = "default_request_retries")]
+    pub retries: u32,
+    /// First backoff delay; doubles with every retry
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_request_timeout_secs() -> u64 {
+    120
+}
+
+fn default_request_retries() -> u32 {
+    2
+}
+
+fn default_backoff_ms() -> u64 {
+    500
+}
+
+/// Local models can take minutes to load and answer
+fn default_ollama_requests() -> RequestConfig {
+    RequestConfig {
+        timeout_secs: 300,
+        ..RequestConfig::default()
+    }
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: default_connect_timeout_secs(),
+            timeout_secs: default_request_timeout_secs(),
+            retries: default_request_retries(),
+            backoff_ms: default_backoff_ms(),
+        }
+    }
 }
 
 /// ElevenLabs TTS configuration
@@ -62,12 +203,18 @@ pub struct ElevenLabsConfig {
     pub api_key: String,
     pub voice_id: String,
     pub model: String,
+    /// Voice for your own messages when `/speak-all` reads a conversation; without one
+    /// each message is introduced by who said it
+    #[serde(default)]
+    pub user_voice_id: Option<String>,
 }
 
 /// Venice AI configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct VeniceConfig {
     pub api_key: String,
+    #[serde(default)]
+    pub requests: RequestConfig,
 }
 
 /// Gab AI configuration
@@ -75,6 +222,8 @@ pub struct VeniceConfig {
 pub struct GabConfig {
     pub api_key: String,
     pub base_url: String,
+    #[serde(default)]
+    pub requests: RequestConfig,
 }
 
 /// Brave Search configuration
@@ -83,6 +232,19 @@ pub struct BraveConfig {
     pub api_key: String,
 }
 
+/// GitHub configuration (personal access token)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GithubConfig {
+    pub api_key: String,
+}
+
+/// Home Assistant configuration (server URL and long-lived access token)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HomeAssistantConfig {
+    pub url: String,
+    pub api_key: String,
+}
+
 /// Obsidian vault configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ObsidianConfig {
@@ -98,6 +260,37 @@ pub struct EmbeddingsConfig {
     pub ollama_url: String,
     pub similarity_threshold: f32,
     pub max_retrieved_messages: usize,
+    /// Texts sent per `/api/embed` request during backfills
+    #[serde(default = "default_embedding_batch_size")]
+    pub batch_size: usize,
+    /// Embedding requests allowed in flight at once
+    #[serde(default = "default_embedding_concurrency")]
+    pub concurrency: usize,
+    /// Search model-written rewrites of each memory query next to the original
+    #[serde(default)]
+    pub query_rewrite: bool,
+    /// Days after which a recalled memory counts half as much (0 disables recency decay)
+    #[serde(default = "default_recency_half_life_days")]
+    pub recency_half_life_days: f32,
+    /// Boost for user messages, "remember ..." facts and emotional messages (0 disables)
+    #[serde(default = "default_importance_weight")]
+    pub importance_weight: f32,
+}
+
+fn default_recency_half_life_days() -> f32 {
+    180.0
+}
+
+fn default_importance_weight() -> f32 {
+    0.5
+}
+
+fn default_embedding_batch_size() -> usize {
+    32
+}
+
+fn default_embedding_concurrency() -> usize {
+    4
 }
 
 impl Default for EmbeddingsConfig {
@@ -107,14 +300,1003 @@ impl Default for EmbeddingsConfig {
             ollama_url: "http://localhost:11434".to_string(),
             similarity_threshold: 0.3,
             max_retrieved_messages: 20,
+            batch_size: default_embedding_batch_size(),
+            concurrency: default_embedding_concurrency(),
+            query_rewrite: false,
+            recency_half_life_days: default_recency_half_life_days(),
+            importance_weight: default_importance_weight(),
         }
     }
 }
 
 /// Personality configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonalityConfig {
     pub selected: String,
+    /// Conversations between automatic personality suggestions (0 disables)
+    #[serde(default = "default_suggest_after_conversations")]
+    pub suggest_after_conversations: usize,
+}
+
+fn default_suggest_after_conversations() -> usize {
+    10
+}
+
+impl Default for PersonalityConfig {
+    fn default() -> Self {
+        Self {
+            selected: String::new(),
+            suggest_after_conversations: default_suggest_after_conversations(),
+        }
+    }
+}
+
+/// Calendar (ICS) configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    /// ICS URLs (http/https/webcal) or local .ics file paths
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Number of days shown in the /agenda view
+    #[serde(default = "default_agenda_days")]
+    pub agenda_days: i64,
+}
+
+fn default_agenda_days() -> i64 {
+    7
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            agenda_days: default_agenda_days(),
+        }
+    }
+}
+
+/// Notification inbox, desktop popups and the outgoing webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// e.g. https://ntfy.sh/my-topic (empty = disabled)
+    #[serde(default)]
+    pub webhook_url: String,
+    /// "ntfy" (plain text body) or "json"
+    #[serde(default = "default_webhook_format")]
+    pub webhook_format: String,
+    /// Also show notifications on the desktop via notify-send
+    #[serde(default)]
+    pub desktop: bool,
+    /// Minutes before a calendar event its reminder is published (0 disables reminders)
+    #[serde(default = "default_reminder_lead_minutes")]
+    pub reminder_lead_minutes: i64,
+}
+
+fn default_reminder_lead_minutes() -> i64 {
+    15
+}
+
+fn default_webhook_format() -> String {
+    "ntfy".to_string()
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: String::new(),
+            webhook_format: default_webhook_format(),
+            desktop: false,
+            reminder_lead_minutes: default_reminder_lead_minutes(),
+        }
+    }
+}
+
+/// Obsidian daily-note journaling through `journal`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalConfig {
+    /// Vault folder holding daily notes (empty = vault root)
+    #[serde(default)]
+    pub folder: String,
+    /// chrono format of the daily note's file name
+    #[serde(default = "default_journal_date_format")]
+    pub date_format: String,
+    /// Vault-relative template for new daily notes; supports {{date}}, {{title}}, {{time}}
+    #[serde(default)]
+    pub template: String,
+    /// Section entries are appended under
+    #[serde(default = "default_journal_heading")]
+    pub heading: String,
+}
+
+fn default_journal_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+fn default_journal_heading() -> String {
+    "## Kimi".to_string()
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            folder: String::new(),
+            date_format: default_journal_date_format(),
+            template: String::new(),
+            heading: default_journal_heading(),
+        }
+    }
+}
+
+/// Kimi's own first-person diary, browsed with `/diary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiaryConfig {
+    /// Rewrite today's entry after each saved conversation
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Also keep each day's entry as a note in the Obsidian vault
+    #[serde(default)]
+    pub export_to_obsidian: bool,
+    /// Vault folder for exported entries, one `<date>.md` per day
+    #[serde(default = "default_diary_folder")]
+    pub folder: String,
+}
+
+fn default_diary_folder() -> String {
+    "Kimi/Diary".to_string()
+}
+
+impl Default for DiaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            export_to_obsidian: false,
+            folder: default_diary_folder(),
+        }
+    }
+}
+
+/// RSS/Atom feeds summarized by `news`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedsConfig {
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Most new items one briefing covers
+    #[serde(default = "default_feed_max_items")]
+    pub max_items: usize,
+}
+
+fn default_feed_max_items() -> usize {
+    20
+}
+
+impl Default for FeedsConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            max_items: default_feed_max_items(),
+        }
+    }
+}
+
+/// How confirmed email drafts are sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// Command with {to}, {subject} and {body} placeholders; without {body} the body goes to stdin
+    #[serde(default = "default_email_command")]
+    pub command: String,
+}
+
+fn default_email_command() -> String {
+    "xdg-email --subject {subject} --body {body} {to}".to_string()
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            command: default_email_command(),
+        }
+    }
+}
+
+/// Directories the `search_files` tool and `find` may look in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSearchConfig {
+    /// Search roots; nothing outside them is ever returned (empty = disabled)
+    #[serde(default)]
+    pub roots: Vec<String>,
+    /// Most files listed per search
+    #[serde(default = "default_file_search_max_results")]
+    pub max_results: usize,
+    /// Characters of results handed to the agent
+    #[serde(default = "default_file_search_max_chars")]
+    pub max_chars: usize,
+}
+
+fn default_file_search_max_results() -> usize {
+    20
+}
+
+fn default_file_search_max_chars() -> usize {
+    6000
+}
+
+impl Default for FileSearchConfig {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            max_results: default_file_search_max_results(),
+            max_chars: default_file_search_max_chars(),
+        }
+    }
+}
+
+/// Repository context when Kimi is launched inside a git work tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// Add branch, recent commits and dirty files to repo questions and enable `git_diff`
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_git_commit_count")]
+    pub commit_count: usize,
+    /// Characters of a diff handed to the agent
+    #[serde(default = "default_git_max_diff_chars")]
+    pub max_diff_chars: usize,
+}
+
+fn default_git_commit_count() -> usize {
+    10
+}
+
+fn default_git_max_diff_chars() -> usize {
+    8000
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            commit_count: default_git_commit_count(),
+            max_diff_chars: default_git_max_diff_chars(),
+        }
+    }
+}
+
+/// Token and cost ledger for the hosted providers, with optional daily budgets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// USD per day across all providers; unset = no overall budget
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    /// USD per day for one provider ("venice", "gab")
+    #[serde(default)]
+    pub budgets: HashMap<String, f64>,
+    /// Fraction of a budget at which a warning is published
+    #[serde(default = "default_usage_warn_at")]
+    pub warn_at: f64,
+    /// Refuse further requests once a budget is spent, instead of only warning
+    #[serde(default)]
+    pub cutoff: bool,
+    /// USD per million tokens, keyed "provider/model" or just "model"
+    #[serde(default)]
+    pub prices: HashMap<String, ModelPrice>,
+}
+
+fn default_usage_warn_at() -> f64 {
+    0.8
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            daily_budget_usd: None,
+            budgets: HashMap::new(),
+            warn_at: default_usage_warn_at(),
+            cutoff: false,
+            prices: HashMap::new(),
+        }
+    }
+}
+
+/// Offline answers to arithmetic and unit conversions, before the model is asked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculatorConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Units of each currency per 1 EUR, keyed by code ("CZK", "USD")
+    #[serde(default)]
+    pub currency_rates: HashMap<String, f64>,
+}
+
+impl Default for CalculatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            currency_rates: HashMap::new(),
+        }
+    }
+}
+
+/// Live exchange rates for conversions and stock quotes for the agent's finance tools
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinanceConfig {
+    /// Fetch exchange rates instead of relying only on `[calculator] currency_rates`
+    #[serde(default = "default_true")]
+    pub live_rates: bool,
+    /// Endpoint answering `{"base": ..., "rates": {...}}` (Frankfurter, exchangerate.host)
+    #[serde(default = "default_finance_rates_url")]
+    pub rates_url: String,
+    #[serde(default = "default_finance_rates_cache_minutes")]
+    pub rates_cache_minutes: u64,
+    /// Yahoo-style chart endpoint; the ticker is appended to it
+    #[serde(default = "default_finance_quote_url")]
+    pub quote_url: String,
+    #[serde(default = "default_finance_quote_cache_minutes")]
+    pub quote_cache_minutes: u64,
+}
+
+impl Default for FinanceConfig {
+    fn default() -> Self {
+        Self {
+            live_rates: true,
+            rates_url: default_finance_rates_url(),
+            rates_cache_minutes: default_finance_rates_cache_minutes(),
+            quote_url: default_finance_quote_url(),
+            quote_cache_minutes: default_finance_quote_cache_minutes(),
+        }
+    }
+}
+
+fn default_finance_rates_url() -> String {
+    "https://api.frankfurter.app/latest?from=EUR".to_string()
+}
+
+fn default_finance_rates_cache_minutes() -> u64 {
+    60
+}
+
+fn default_finance_quote_url() -> String {
+    "https://query1.finance.yahoo.com/v8/finance/chart".to_string()
+}
+
+fn default_finance_quote_cache_minutes() -> u64 {
+    5
+}
+
+/// Current conditions for the weather fast path and `kimi weather`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherConfig {
+    /// "open-meteo" or "wttr"; the other one is tried when it fails
+    #[serde(default = "default_weather_provider")]
+    pub provider: String,
+    /// Name used in replies and by wttr.in
+    #[serde(default = "default_weather_location")]
+    pub location: String,
+    /// Coordinates used by Open-Meteo
+    #[serde(default = "default_weather_latitude")]
+    pub latitude: f32,
+    #[serde(default = "default_weather_longitude")]
+    pub longitude: f32,
+    #[serde(default)]
+    pub units: WeatherUnits,
+    /// Minutes a fetched report is reused; 0 fetches every time
+    #[serde(default = "default_weather_cache_minutes")]
+    pub cache_minutes: u64,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            provider: default_weather_provider(),
+            location: default_weather_location(),
+            latitude: default_weather_latitude(),
+            longitude: default_weather_longitude(),
+            units: WeatherUnits::default(),
+            cache_minutes: default_weather_cache_minutes(),
+        }
+    }
+}
+
+fn default_weather_provider() -> String {
+    "open-meteo".to_string()
+}
+
+fn default_weather_location() -> String {
+    "Prague".to_string()
+}
+
+fn default_weather_latitude() -> f32 {
+    50.0755
+}
+
+fn default_weather_longitude() -> f32 {
+    14.4378
+}
+
+fn default_weather_cache_minutes() -> u64 {
+    10
+}
+
+/// Metric shows °C and km/h, imperial °F and mph
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherUnits {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+/// Prague public transport departures from the Golemio API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitConfig {
+    /// Golemio token from api.golemio.cz
+    #[serde(default)]
+    pub api_key: String,
+    /// Stop name used for "home" or when no stop is given, e.g. "Anděl"
+    #[serde(default)]
+    pub home_stop: String,
+    #[serde(default)]
+    pub work_stop: String,
+    /// Departures listed per question
+    #[serde(default = "default_transit_departures")]
+    pub departures: usize,
+    /// How far ahead departures are looked up
+    #[serde(default = "default_transit_minutes_ahead")]
+    pub minutes_ahead: u32,
+}
+
+impl Default for TransitConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            home_stop: String::new(),
+            work_stop: String::new(),
+            departures: default_transit_departures(),
+            minutes_ahead: default_transit_minutes_ahead(),
+        }
+    }
+}
+
+fn default_transit_departures() -> usize {
+    6
+}
+
+fn default_transit_minutes_ahead() -> u32 {
+    60
+}
+
+/// Countdowns started with `/timer` or "set a timer for 10 minutes"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimersConfig {
+    /// Announce finished timers with text-to-speech when ElevenLabs is configured
+    #[serde(default = "default_true")]
+    pub announce: bool,
+}
+
+impl Default for TimersConfig {
+    fn default() -> Self {
+        Self { announce: true }
+    }
+}
+
+/// Guardrails for the tools the agent calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolsConfig {
+    /// Tools the agent may never call, e.g. "query_database"
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// When not empty, the only tools the agent may call
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Tools that change something describe what they would do instead of doing it
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Record every tool call with its arguments and result for `/audit`
+    #[serde(default = "default_true")]
+    pub audit: bool,
+    /// Results longer than this are summarized by the routing model before the agent
+    /// reads them; the full text is stored. 0 passes every result through as is
+    #[serde(default = "default_tools_summarize_over_chars")]
+    pub summarize_over_chars: usize,
+}
+
+fn default_tools_summarize_over_chars() -> usize {
+    6000
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            deny: Vec::new(),
+            allow: Vec::new(),
+            dry_run: false,
+            audit: true,
+            summarize_over_chars: default_tools_summarize_over_chars(),
+        }
+    }
+}
+
+/// How personality files are edited
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorConfig {
+    /// Editor command, e.g. "nvim" or "code --wait"; empty uses $VISUAL, then $EDITOR, then micro
+    #[serde(default)]
+    pub command: String,
+    /// Terminal emulator the editor opens in; empty uses $TERMINAL, then common ones
+    #[serde(default)]
+    pub terminal: String,
+    /// "auto" (built-in editor over SSH or without a display), "terminal" or "internal"
+    #[serde(default = "default_editor_mode")]
+    pub mode: String,
+}
+
+fn default_editor_mode() -> String {
+    "auto".to_string()
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        Self {
+            command: String::new(),
+            terminal: String::new(),
+            mode: default_editor_mode(),
+        }
+    }
+}
+
+/// Where `/capture` saves thoughts, without asking the chat model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureConfig {
+    /// Save each capture as a searchable memory
+    #[serde(default = "default_true")]
+    pub memory: bool,
+    /// Append each capture to the Obsidian inbox note
+    #[serde(default = "default_true")]
+    pub obsidian: bool,
+    /// Vault-relative inbox note
+    #[serde(default = "default_capture_inbox_note")]
+    pub inbox_note: String,
+}
+
+fn default_capture_inbox_note() -> String {
+    "Inbox.md".to_string()
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            memory: true,
+            obsidian: true,
+            inbox_note: default_capture_inbox_note(),
+        }
+    }
+}
+
+/// Reuse of replies to summaries, intent classification and topic extraction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_cache_ttl_minutes")]
+    pub ttl_minutes: u64,
+    /// Word overlap (0-1) at which a differently worded prompt counts as the same
+    #[serde(default = "default_cache_similarity")]
+    pub similarity: f64,
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_cache_ttl_minutes() -> u64 {
+    60
+}
+
+fn default_cache_similarity() -> f64 {
+    0.9
+}
+
+fn default_cache_max_entries() -> usize {
+    256
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_minutes: default_cache_ttl_minutes(),
+            similarity: default_cache_similarity(),
+            max_entries: default_cache_max_entries(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    /// USD per million prompt tokens
+    #[serde(default)]
+    pub input: f64,
+    /// USD per million completion tokens
+    #[serde(default)]
+    pub output: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseKind {
+    Sqlite,
+    Postgres,
+}
+
+/// A recurring job run in the background (`[[schedule]]`); its result lands in `/inbox`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJobConfig {
+    pub name: String,
+    /// Time of day, "08:00" or "6pm"
+    pub at: String,
+    /// "daily", "weekdays", "weekends" or days like "mon,wed,fri"
+    #[serde(default = "default_schedule_days")]
+    pub days: String,
+    /// Data gathered first: "weather", "agenda", "news", "tasks"
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// What Kimi does with it, e.g. "Prepare a short morning briefing"
+    #[serde(default)]
+    pub prompt: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_schedule_days() -> String {
+    "daily".to_string()
+}
+
+/// A database the `query_database` tool may read (`[[databases]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseConfig {
+    pub name: String,
+    pub kind: DatabaseKind,
+    /// SQLite file path or postgres:// connection URL
+    pub url: String,
+    /// Schemas queries may name (default: main for SQLite, public for Postgres)
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    /// Rows kept from a result
+    #[serde(default = "default_database_max_rows")]
+    pub max_rows: usize,
+    /// Characters of a result handed to the agent
+    #[serde(default = "default_database_max_chars")]
+    pub max_chars: usize,
+}
+
+fn default_database_max_rows() -> usize {
+    50
+}
+
+fn default_database_max_chars() -> usize {
+    6000
+}
+
+/// Conversation lifecycle configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationConfig {
+    /// Inject the previous conversation's summary into a freshly started one
+    #[serde(default = "default_true")]
+    pub carry_over: bool,
+    /// How long after closing a conversation its summary is still carried over
+    #[serde(default = "default_carry_over_window_minutes")]
+    pub carry_over_window_minutes: u64,
+    /// Autosave after this many new messages...
+    #[serde(default = "default_autosave_every_messages")]
+    pub autosave_every_messages: usize,
+    /// ...or once this many seconds passed since the last save
+    #[serde(default = "default_autosave_interval_seconds")]
+    pub autosave_interval_seconds: u64,
+    /// Reopen the most recent conversation on startup: "ask", "always" or "never"
+    #[serde(default)]
+    pub resume_last: ResumeLast,
+    /// Open a conversation from history with a short recap of what it was about and when
+    #[serde(default = "default_true")]
+    pub resume_recap: bool,
+    /// After saving a conversation, look for commitments with dates and propose them in `/tasks`
+    #[serde(default = "default_true")]
+    pub extract_tasks: bool,
+    /// Have the routing model score each saved conversation's tone for `/sentiment`
+    #[serde(default = "default_true")]
+    pub track_sentiment: bool,
+    /// Let Kimi ask about an active dream or open project task when a conversation starts
+    #[serde(default = "default_true")]
+    pub check_ins: bool,
+    /// Days between such check-ins (0 disables them)
+    #[serde(default = "default_check_in_every_days")]
+    pub check_in_every_days: u64,
+    /// Show model, duration and token counts next to each reply's timestamp
+    #[serde(default)]
+    pub show_message_stats: bool,
+    /// Language Kimi answers in (code like "en" or "cs"); `/language` changes it per conversation
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// Offer the earlier answer when a question this similar was asked before (0 disables)
+    #[serde(default = "default_duplicate_question_similarity")]
+    pub duplicate_question_similarity: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ResumeLast {
+    Ask,
+    Always,
+    #[default]
+    Never,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_check_in_every_days() -> u64 {
+    7
+}
+
+fn default_carry_over_window_minutes() -> u64 {
+    30
+}
+
+fn default_autosave_every_messages() -> usize {
+    4
+}
+
+fn default_autosave_interval_seconds() -> u64 {
+    30
+}
+
+impl Default for ConversationConfig {
+    fn default() -> Self {
+        Self {
+            carry_over: true,
+            carry_over_window_minutes: default_carry_over_window_minutes(),
+            autosave_every_messages: default_autosave_every_messages(),
+            autosave_interval_seconds: default_autosave_interval_seconds(),
+            resume_last: ResumeLast::default(),
+            resume_recap: true,
+            extract_tasks: true,
+            track_sentiment: true,
+            check_ins: true,
+            check_in_every_days: default_check_in_every_days(),
+            show_message_stats: false,
+            language: default_language(),
+            duplicate_question_similarity: default_duplicate_question_similarity(),
+        }
+    }
+}
+
+fn default_duplicate_question_similarity() -> f32 {
+    0.95
+}
+
+fn default_language() -> String {
+    crate::services::languages::DEFAULT_LANGUAGE.to_string()
+}
+
+/// OCR configuration for text screenshots sent to non-vision models
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Tesseract binary name or path
+    #[serde(default = "default_ocr_command")]
+    pub command: String,
+    /// Tesseract language code(s), e.g. "eng" or "eng+ces"
+    #[serde(default = "default_ocr_language")]
+    pub language: String,
+}
+
+fn default_ocr_command() -> String {
+    "tesseract".to_string()
+}
+
+fn default_ocr_language() -> String {
+    "eng".to_string()
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            command: default_ocr_command(),
+            language: default_ocr_language(),
+        }
+    }
+}
+
+/// Background database maintenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Days between automatic `reindex` runs at startup (0 disables)
+    #[serde(default = "default_reindex_interval_days")]
+    pub reindex_interval_days: u64,
+    /// Archive conversations not updated in this many days, at startup (0 disables)
+    #[serde(default)]
+    pub archive_after_days: u64,
+}
+
+fn default_reindex_interval_days() -> u64 {
+    30
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            reindex_interval_days: default_reindex_interval_days(),
+            archive_after_days: 0,
+        }
+    }
+}
+
+/// Scheduled snapshots of the database and identity state (`kimi backup --auto`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupConfig {
+    /// Take a snapshot in the background while kimi runs
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where snapshots go (empty = data/backups); `~/` is expanded
+    #[serde(default)]
+    pub directory: String,
+    /// Hours between snapshots
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u64,
+    /// Snapshots kept; older ones are deleted after each backup
+    #[serde(default = "default_backup_keep")]
+    pub keep: usize,
+}
+
+fn default_backup_interval_hours() -> u64 {
+    24
+}
+
+fn default_backup_keep() -> usize {
+    7
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: String::new(),
+            interval_hours: default_backup_interval_hours(),
+            keep: default_backup_keep(),
+        }
+    }
+}
+
+/// When to draw with ASCII glyphs, plain borders and the 16 basic colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Compatibility {
+    /// On for the Linux console, plain `screen`, `vt*` and non-UTF-8 locales
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+/// How the terminal UI is drawn
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    #[serde(default)]
+    pub compatibility: Compatibility,
+}
+
+/// Image generation for `/imagine`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagesConfig {
+    /// "venice" or "openai" (any OpenAI-compatible `/images/generations` endpoint)
+    #[serde(default = "default_images_provider")]
+    pub provider: String,
+    /// Base URL of an OpenAI-compatible server; unused for Venice
+    #[serde(default)]
+    pub url: String,
+    /// Key for the OpenAI-compatible server; Venice uses `[venice] api_key`
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default = "default_images_model")]
+    pub model: String,
+    #[serde(default = "default_images_size")]
+    pub width: u32,
+    #[serde(default = "default_images_size")]
+    pub height: u32,
+    /// Where generated pictures are saved (empty = Pictures/kimi); `~/` is expanded
+    #[serde(default)]
+    pub directory: String,
+    #[serde(default = "default_images_requests")]
+    pub requests: RequestConfig,
+}
+
+fn default_images_provider() -> String {
+    "venice".to_string()
+}
+
+fn default_images_model() -> String {
+    "venice-sd35".to_string()
+}
+
+fn default_images_size() -> u32 {
+    1024
+}
+
+fn default_images_requests() -> RequestConfig {
+    RequestConfig {
+        timeout_secs: 180,
+        ..RequestConfig::default()
+    }
+}
+
+impl Default for ImagesConfig {
+    fn default() -> Self {
+        Self {
+            provider: default_images_provider(),
+            url: String::new(),
+            api_key: String::new(),
+            model: default_images_model(),
+            width: default_images_size(),
+            height: default_images_size(),
+            directory: String::new(),
+            requests: default_images_requests(),
+        }
+    }
+}
+
+/// Database storage options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Encrypt message content and summaries with a passphrase asked at startup
+    #[serde(default)]
+    pub encrypt_content: bool,
+    /// SurrealDB server to share one memory store between machines ("ws://host:8000",
+    /// "http://host:8000"); empty uses the embedded RocksDB database under data/
+    #[serde(default)]
+    pub url: String,
+    #[serde(default = "default_storage_namespace")]
+    pub namespace: String,
+    /// Sign-in for the server; empty username connects without auth
+    #[serde(default)]
+    pub username: String,
+    /// Prefer the `KIMI_SURREAL_PASSWORD` environment variable over writing it here
+    #[serde(default)]
+    pub password: String,
+    /// "root", "namespace" or "database" user
+    #[serde(default = "default_storage_auth_level")]
+    pub auth_level: String,
+    /// When another kimi holds the embedded database: "ask" at startup, "read-only" to open
+    /// a snapshot without asking, or "exit"
+    #[serde(default = "default_storage_on_locked")]
+    pub on_locked: String,
+}
+
+fn default_storage_namespace() -> String {
+    "kimi".to_string()
+}
+
+fn default_storage_auth_level() -> String {
+    "root".to_string()
+}
+
+fn default_storage_on_locked() -> String {
+    "ask".to_string()
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            encrypt_content: false,
+            url: String::new(),
+            namespace: default_storage_namespace(),
+            username: String::new(),
+            password: String::new(),
+            auth_level: default_storage_auth_level(),
+            on_locked: default_storage_on_locked(),
+        }
+    }
 }
 
 /// Agent-specific configuration
@@ -125,6 +1307,31 @@ pub struct AgentConfig {
     /// Number of GPU layers to offload (None = auto, 0 = CPU only, positive = specific layer count)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_gpu: Option<i32>,
+    /// How long Ollama keeps this agent's model loaded once idle (overrides `[ollama] keep_alive`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_alive: Option<String>,
+    /// Second model call that checks each answer and reports issues without rewriting it
+    #[serde(default, skip_serializing_if = "Verification::is_off")]
+    pub verify: Verification,
+}
+
+/// How strictly an agent's answers are checked after they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Verification {
+    #[default]
+    Off,
+    /// Only clear errors and contradictions with the context
+    Lenient,
+    /// Also unsupported claims, missing caveats and unanswered parts
+    Strict,
+}
+
+impl Verification {
+    #[must_use]
+    pub fn is_off(&self) -> bool {
+        *self == Self::Off
+    }
 }
 
 impl Default for Config {
@@ -144,6 +1351,8 @@ impl Default for Config {
                     kimi_identity
                 ),
                 num_gpu: None,
+                keep_alive: None,
+                verify: Verification::Off,
             },
         );
 
@@ -153,6 +1362,19 @@ impl Default for Config {
                 model: "gemma3:12b".to_string(),
                 system_prompt: kimi_identity.to_string(),
                 num_gpu: None,
+                keep_alive: None,
+                verify: Verification::Off,
+            },
+        );
+
+        agents.insert(
+            "summary".to_string(),
+            AgentConfig {
+                model: "gemma3:1b".to_string(),
+                system_prompt: "You summarize conversations briefly and accurately.".to_string(),
+                num_gpu: None,
+                keep_alive: None,
+                verify: Verification::Off,
             },
         );
 
@@ -162,28 +1384,35 @@ impl Default for Config {
                 model: "functiongemma".to_string(),
                 system_prompt: "Function calling router.".to_string(),
                 num_gpu: None,
+                keep_alive: None,
+                verify: Verification::Off,
             },
         );
 
         Self {
             ollama: OllamaConfig {
                 url: "http://localhost:11434".to_string(),
+                keep_alive: None,
+                warm_up: true,
+                requests: default_ollama_requests(),
             },
             elevenlabs: ElevenLabsConfig {
                 api_key: "your_api_key_here".to_string(),
                 voice_id: "21m00Tcm4TlvDq8ikWAM".to_string(),
                 model: "eleven_monolingual_v1".to_string(),
+                user_voice_id: None,
             },
-            venice: VeniceConfig {
-                api_key: String::new(),
-            },
+            venice: VeniceConfig::default(),
             gab: GabConfig {
                 api_key: String::new(),
                 base_url: crate::agents::gab_ai::default_base_url(),
+                requests: RequestConfig::default(),
             },
             brave: BraveConfig {
                 api_key: String::new(),
             },
+            github: GithubConfig::default(),
+            homeassistant: HomeAssistantConfig::default(),
             obsidian: ObsidianConfig {
                 vault_name: String::new(),
                 vault_path: String::new(),
@@ -191,7 +1420,35 @@ impl Default for Config {
             embeddings: EmbeddingsConfig::default(),
             personality: PersonalityConfig {
                 selected: "Casca".to_string(),
+                suggest_after_conversations: default_suggest_after_conversations(),
             },
+            calendar: CalendarConfig::default(),
+            notifications: NotificationsConfig::default(),
+            journal: JournalConfig::default(),
+            diary: DiaryConfig::default(),
+            feeds: FeedsConfig::default(),
+            email: EmailConfig::default(),
+            files: FileSearchConfig::default(),
+            databases: Vec::new(),
+            schedule: Vec::new(),
+            git: GitConfig::default(),
+            usage: UsageConfig::default(),
+            cache: CacheConfig::default(),
+            calculator: CalculatorConfig::default(),
+            finance: FinanceConfig::default(),
+            weather: WeatherConfig::default(),
+            transit: TransitConfig::default(),
+            timers: TimersConfig::default(),
+            tools: ToolsConfig::default(),
+            capture: CaptureConfig::default(),
+            editor: EditorConfig::default(),
+            conversation: ConversationConfig::default(),
+            ocr: OcrConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            backup: BackupConfig::default(),
+            display: DisplayConfig::default(),
+            images: ImagesConfig::default(),
+            storage: StorageConfig::default(),
             agents,
         }
     }
@@ -221,6 +1478,7 @@ impl Config {
         if let Some(local) = Self::load_local_config()? {
             Self::apply_local_overrides(&mut config, &local);
         }
+        Self::apply_stored_secrets(&mut config);
 
         // Auto-resolve vault_path from vault_name via Obsidian's config
         if config.obsidian.vault_path.trim().is_empty()
@@ -246,8 +1504,7 @@ impl Config {
 
     /// Returns the path to the configuration file
     pub fn project_config_path() -> Result<PathBuf> {
-        let current_dir = std::env::current_dir()?;
-        Ok(current_dir.join("config.toml"))
+        Ok(crate::profile::root_dir()?.join("config.toml"))
     }
 
     fn legacy_config_path() -> Result<PathBuf> {
@@ -256,9 +1513,8 @@ impl Config {
         Ok(proj_dirs.config_dir().join("config.toml"))
     }
 
-    fn local_config_path() -> Result<PathBuf> {
-        let current_dir = std::env::current_dir()?;
-        Ok(current_dir.join("config.local.toml"))
+    pub fn local_config_path() -> Result<PathBuf> {
+        Ok(crate::profile::root_dir()?.join("config.local.toml"))
     }
 
     fn load_local_config() -> Result<Option<LocalConfig>> {
@@ -290,12 +1546,36 @@ impl Config {
         {
             config.brave.api_key = api_key.clone();
         }
+        if let Some(github) = &local.github
+            && let Some(api_key) = &github.api_key
+            && !api_key.trim().is_empty()
+        {
+            config.github.api_key = api_key.clone();
+        }
+        if let Some(homeassistant) = &local.homeassistant {
+            if let Some(url) = &homeassistant.url
+                && !url.trim().is_empty()
+            {
+                config.homeassistant.url = url.clone();
+            }
+            if let Some(api_key) = &homeassistant.api_key
+                && !api_key.trim().is_empty()
+            {
+                config.homeassistant.api_key = api_key.clone();
+            }
+        }
         if let Some(gab) = &local.gab
             && let Some(api_key) = &gab.api_key
             && !api_key.trim().is_empty()
         {
             config.gab.api_key = api_key.clone();
         }
+        if let Some(images) = &local.images
+            && let Some(api_key) = &images.api_key
+            && !api_key.trim().is_empty()
+        {
+            config.images.api_key = api_key.clone();
+        }
         if let Some(obsidian) = &local.obsidian {
             if let Some(vault_name) = &obsidian.vault_name
                 && !vault_name.trim().is_empty()
@@ -308,24 +1588,81 @@ impl Config {
                 config.obsidian.vault_path = vault_path.clone();
             }
         }
+        if let Some(calendar) = &local.calendar
+            && let Some(sources) = &calendar.sources
+            && !sources.is_empty()
+        {
+            config.calendar.sources = sources.clone();
+        }
+        if let Some(notifications) = &local.notifications
+            && let Some(webhook_url) = &notifications.webhook_url
+            && !webhook_url.trim().is_empty()
+        {
+            config.notifications.webhook_url = webhook_url.clone();
+        }
+    }
+
+    /// Fills API keys from the secrets backend (keyring or encrypted file)
+    fn apply_stored_secrets(config: &mut Self) {
+        let fields = [
+            ("elevenlabs", &mut config.elevenlabs.api_key),
+            ("venice", &mut config.venice.api_key),
+            ("gab", &mut config.gab.api_key),
+            ("brave", &mut config.brave.api_key),
+            ("github", &mut config.github.api_key),
+            ("homeassistant", &mut config.homeassistant.api_key),
+            ("transit", &mut config.transit.api_key),
+            ("images", &mut config.images.api_key),
+        ];
+        for (name, field) in fields {
+            let is_unset = field.trim().is_empty() || field == "your_api_key_here";
+            if is_unset && let Some(secret) = crate::services::secrets::get_secret(name) {
+                *field = secret;
+            }
+        }
     }
 
-    fn redacted_for_project(&self) -> Self {
+    /// Copy without API keys or the webhook URL
+    pub fn redacted_for_project(&self) -> Self {
         let mut redacted = self.clone();
         redacted.elevenlabs.api_key = String::new();
         redacted.venice.api_key = String::new();
         redacted.gab.api_key = String::new();
         redacted.brave.api_key = String::new();
+        redacted.github.api_key = String::new();
+        redacted.homeassistant.api_key = String::new();
+        redacted.transit.api_key = String::new();
+        redacted.images.api_key = String::new();
+        redacted.notifications.webhook_url = String::new();
+        redacted.storage.password = String::new();
+        redacted
+    }
+
+    /// Redacted copy for dotfiles bundles: also drops machine-specific paths and private feeds
+    pub fn redacted_for_bundle(&self) -> Self {
+        let mut redacted = self.redacted_for_project();
+        redacted.obsidian.vault_path = String::new();
+        redacted.calendar.sources.clear();
+        redacted.databases.clear();
         redacted
     }
+
+    /// Restores settings a bundle deliberately leaves out from the current machine's config
+    pub fn keep_machine_settings_from(&mut self, current: &Self) {
+        if self.obsidian.vault_path.trim().is_empty() {
+            self.obsidian.vault_path = current.obsidian.vault_path.clone();
+        }
+        if self.calendar.sources.is_empty() {
+            self.calendar.sources = current.calendar.sources.clone();
+        }
+    }
 }
 
 /// Resolves a vault filesystem path from its name by reading Obsidian's own config.
-/// Obsidian stores vault mappings in `~/.config/obsidian/obsidian.json`.
+/// Obsidian stores vault mappings in `obsidian/obsidian.json` under the user config
+/// directory (`~/.config`, `~/Library/Application Support` or `%APPDATA%`).
 fn resolve_vault_path_from_obsidian(vault_name: &str) -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-    let config_path = PathBuf::from(&home)
-        .join(".config")
+    let config_path = crate::platform::user_config_dir()?
         .join("obsidian")
         .join("obsidian.json");
 
@@ -16,15 +16,19 @@
 mod agents;
 mod app;
 mod config;
+mod platform;
+mod profile;
+mod server;
 mod services;
 mod storage;
 mod ui;
 
-use app::{App, AppMode, Navigable};
+use app::{App, AppMode, Navigable, ProjectDetailFocus};
 use color_eyre::Result;
 use crossterm::{
     event::{
         self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        MediaKeyCode,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
@@ -32,24 +36,73 @@ use crossterm::{
 use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::Rect,
 };
-use services::weather::WeatherService;
 use std::{io, time::Duration};
 
 fn main() -> Result<()> {
     // Setup error handling
     color_eyre::install()?;
 
+    // Log to ~/.local/share/kimi/logs; the guard flushes pending lines on exit
+    let _log_guard = services::logging::init_logging();
+    tracing::info!("kimi starting");
+
+    // `--profile <name>` isolates config, data and the database namespace
+    let mut args: Vec<String> = std::env::args().collect();
+    if let Some(profile_name) = profile::take_profile_arg(&mut args)
+        && let Err(error) = profile::set_active(&profile_name)
+    {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+
+    // API keys live in the OS keyring (or an encrypted file); move any plaintext keys there
+    match services::secrets::init() {
+        Ok(backend) => {
+            tracing::info!(?backend, "secrets backend ready");
+            match services::secrets::migrate_plaintext_keys() {
+                Ok(0) => {}
+                Ok(count) => println!("Moved {} API key(s) from config files into {:?}", count, backend),
+                Err(error) => eprintln!("API key migration failed: {}", error),
+            }
+        }
+        Err(error) => {
+            tracing::warn!(%error, "secrets backend unavailable");
+            eprintln!("Secrets backend unavailable: {}", error);
+        }
+    }
+
     // Load config
     let config = config::Config::load()?;
 
+    // Embedded database or a shared SurrealDB server, for every storage open below
+    storage::StorageManager::configure(&config.storage);
+
+    // Ask for the database passphrase before anything opens storage
+    if let Err(error) = services::encryption::unlock(config.storage.encrypt_content) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+
     // Check for command-line arguments
-    let args: Vec<String> = std::env::args().collect();
     if args.len() > 1 {
-        return handle_cli_args(&args);
+        let result = handle_cli_args(&args);
+        storage::StorageManager::release_lock_file();
+        return result;
+    }
+
+    ui::configure_display(&config.display);
+
+    // Another kimi already has the embedded database open: ask before starting the TUI
+    if storage::StorageManager::is_locked_elsewhere() {
+        resolve_locked_database(storage::StorageManager::lock_holder(), &config.storage.on_locked)?;
     }
 
+    // Piped stdin (`cat notes.md | kimi`) becomes context for the first message;
+    // key events are then read from /dev/tty
+    let piped = services::stdin::read_piped_stdin()?;
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -60,6 +113,18 @@ fn main() -> Result<()> {
     // Create app and initialize services
     let mut app = App::new();
     app.init_services(&config);
+    if let Some(input) = piped {
+        app.attach_piped_input(input);
+    }
+    app.maybe_spawn_scheduled_reindex(config.maintenance.reindex_interval_days);
+    app.maybe_archive_old_conversations(config.maintenance.archive_after_days);
+    app.spawn_backup_scheduler();
+    app.spawn_retention_scheduler();
+    app.refresh_unread_notifications();
+    app.spawn_notification_watcher();
+    app.spawn_scheduler();
+    app.spawn_health_monitor();
+    app.maybe_resume_last_conversation(config.conversation.resume_last);
     let res = run_app(&mut terminal, &mut app);
 
     // Restore terminal
@@ -70,6 +135,7 @@ fn main() -> Result<()> {
         DisableMouseCapture
     )?;
     terminal.show_cursor()?;
+    storage::StorageManager::release_lock_file();
 
     if let Err(err) = res {
         eprintln!("Error: {:?}", err);
@@ -78,6 +144,44 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Read-only snapshot or a clean exit when another process holds the database lock, per
+/// `[storage] on_locked`; `pid` comes from the pid file and only names the holder
+fn resolve_locked_database(pid: Option<u32>, on_locked: &str) -> Result<()> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let holder = pid.map_or_else(
+        || "Another process has the database open.".to_string(),
+        |pid| format!("Kimi is already running (PID {}) and has the database open.", pid),
+    );
+    match on_locked.trim() {
+        "read-only" => {
+            storage::StorageManager::open_read_only();
+            return Ok(());
+        }
+        "exit" => {
+            eprintln!("{}", holder);
+            std::process::exit(1);
+        }
+        _ => {}
+    }
+    println!("{}", holder);
+    if !io::stdin().is_terminal() {
+        eprintln!("Close it first, or set [storage] on_locked = \"read-only\".");
+        std::process::exit(1);
+    }
+    println!("  [r] Open read-only (memories are readable, nothing from this session is saved)");
+    println!("  [q] Quit");
+    print!("> ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if answer.trim().eq_ignore_ascii_case("r") {
+        storage::StorageManager::open_read_only();
+        return Ok(());
+    }
+    std::process::exit(0);
+}
+
 fn handle_cli_args(args: &[String]) -> Result<()> {
     let cmd = args
         .get(1)
@@ -87,22 +191,19 @@ fn handle_cli_args(args: &[String]) -> Result<()> {
     match cmd.as_str() {
         "--help" | "-h" => print_help(program_name),
         "--version" | "-v" => println!("Kimi The Rust CLI v0.1.0"),
-        "weather" => {
-            let weather_service = WeatherService::new();
-            let weather_json = weather_service.fetch_current_weather_json()?;
-            println!("{}", weather_json);
-        }
-        "personality" => {
-            let config = config::Config::load()?;
-            let selected = if config.personality.selected.is_empty() {
-                services::personality::default_personality_name()
-            } else {
-                config.personality.selected
-            };
-            if services::personality::open_personality_in_new_terminal(&selected).is_err() {
-                services::personality::open_personality_in_place(&selected)?;
-            }
-        }
+        "weather" => run_weather_command()?,
+        "bench" => run_bench_command(args.get(2..).unwrap_or_default())?,
+        "ask" => run_ask_command(args.get(2..).unwrap_or_default())?,
+        "serve" => run_serve_command(args.get(2..).unwrap_or_default())?,
+        "reindex" => run_reindex_command()?,
+        "archive" => run_archive_command(args.get(2..).unwrap_or_default())?,
+        "data" => run_data_command(args.get(2..).unwrap_or_default())?,
+        "import-chat" => run_import_chat_command(args.get(2..).unwrap_or_default())?,
+        "migrate" => run_migrate_command(args.get(2..).unwrap_or_default())?,
+        "backup" => run_backup_command(args.get(2..).unwrap_or_default())?,
+        "restore" => run_restore_command(args.get(2..).unwrap_or_default())?,
+        "config" => run_config_command(args.get(2..).unwrap_or_default())?,
+        "personality" => run_personality_command(args.get(2..).unwrap_or_default())?,
         cmd_str => {
             let mut app = App::new();
             if app.command_handlers.contains_key(cmd_str) {
@@ -120,18 +221,453 @@ fn handle_cli_args(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Runs `kimi bench [--judge <model>] [models...]` and writes a comparison table
+fn run_bench_command(args: &[String]) -> Result<()> {
+    let config = config::Config::load()?;
+    let default_model = config
+        .agents
+        .get("chat")
+        .map(|agent| agent.model.clone())
+        .unwrap_or_default();
+
+    let mut judge_model = default_model.clone();
+    let mut models = Vec::new();
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        if arg == "--judge" {
+            judge_model = remaining
+                .next()
+                .cloned()
+                .ok_or_else(|| color_eyre::eyre::eyre!("--judge needs a model name"))?;
+        } else {
+            models.push(arg.clone());
+        }
+    }
+    if models.is_empty() && !default_model.is_empty() {
+        models.push(default_model);
+    }
+
+    let manager = agents::AgentManager::new(&config);
+    println!("Benchmarking {} model(s), judged by {}", models.len(), judge_model);
+    let results = services::bench::run_benchmark(&manager, &models, &judge_model, |step| {
+        println!("  running {}", step);
+    })?;
+
+    let report = services::bench::format_results_table(&results, &judge_model);
+    println!();
+    println!("{}", report);
+    let path = services::bench::write_report(&report)?;
+    println!();
+    println!("Report saved to {}", path.display());
+    Ok(())
+}
+
+/// Runs `kimi ask [--json] "question"` through the chat pipeline without the TUI
+fn run_ask_command(args: &[String]) -> Result<()> {
+    let json_output = args.iter().any(|arg| arg == "--json");
+    let question = args
+        .iter()
+        .filter(|arg| *arg != "--json")
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if question.trim().is_empty() {
+        return Err(color_eyre::eyre::eyre!("Usage: kimi ask [--json] \"question\""));
+    }
+
+    let piped = services::stdin::read_piped_stdin()?;
+    if let Some(note) = piped.as_ref().and_then(services::stdin::PipedInput::truncation_note) {
+        eprintln!("Warning: {}", note);
+    }
+
+    let config = config::Config::load()?;
+    let mut app = App::new();
+    app.headless = true;
+    app.init_services(&config);
+    let answer = app.ask_headless(&question, piped)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&answer.to_json())?);
+    } else {
+        println!("{}", answer.answer);
+    }
+    Ok(())
+}
+
+/// Runs `kimi serve [--host <addr>] [--port <port>] [--token <token>]`
+fn run_serve_command(args: &[String]) -> Result<()> {
+    let mut options = server::ServeOptions::default();
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        let mut value = || {
+            remaining
+                .next()
+                .cloned()
+                .ok_or_else(|| color_eyre::eyre::eyre!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--host" => options.host = value()?,
+            "--port" => {
+                options.port = value()?
+                    .parse()
+                    .map_err(|_| color_eyre::eyre::eyre!("--port needs a port number"))?;
+            }
+            "--token" => options.token = Some(value()?),
+            other => return Err(color_eyre::eyre::eyre!("Unknown serve option: {}", other)),
+        }
+    }
+
+    let config = config::Config::load()?;
+    let mut app = App::new();
+    app.headless = true;
+    app.init_services(&config);
+    server::run(app, &options)
+}
+
+/// Runs `kimi weather`; reports come from `[weather] provider`, falling back to the other one
+fn run_weather_command() -> Result<()> {
+    let config = config::Config::load()?.weather;
+    let report = services::weather::current(&config)?;
+    println!("{}", services::weather::format_report(&report, config.units));
+    Ok(())
+}
+
+fn run_reindex_command() -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let report = runtime.block_on(async {
+        let storage = storage::StorageManager::new().await?;
+        println!("Rebuilding message indexes...");
+        services::maintenance::run_reindex(&storage).await
+    })?;
+    println!("{}", services::maintenance::format_report(&report));
+    Ok(())
+}
+
+/// Runs `kimi archive --older-than <days>`
+fn run_archive_command(args: &[String]) -> Result<()> {
+    let days = match args {
+        [flag, days] if flag == "--older-than" => days.parse::<u64>().ok().filter(|days| *days > 0),
+        _ => None,
+    }
+    .ok_or_else(|| color_eyre::eyre::eyre!("Usage: kimi archive --older-than <days>"))?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let archived = runtime.block_on(async {
+        let storage = storage::StorageManager::new().await?;
+        services::maintenance::archive_older_than(&storage, days).await
+    })?;
+    println!("Archived {} conversation(s) not updated in {} days", archived, days);
+    Ok(())
+}
+
+/// Runs `kimi data report` or `kimi data purge --topic <keyword> [--dry-run] [--yes]`
+fn run_data_command(args: &[String]) -> Result<()> {
+    const USAGE: &str = "Usage: kimi data report | kimi data purge --topic <keyword> [--dry-run] [--yes]";
+    let runtime = tokio::runtime::Runtime::new()?;
+    let storage = runtime.block_on(storage::StorageManager::new())?;
+    match args.first().map(String::as_str) {
+        Some("report") => {
+            let report = runtime.block_on(services::personal_data::build_report(&storage))?;
+            println!("{}", services::personal_data::format_report(&report));
+            Ok(())
+        }
+        Some("purge") => {
+            let topic = args
+                .iter()
+                .position(|arg| arg == "--topic")
+                .and_then(|index| args.get(index + 1))
+                .filter(|topic| !topic.trim().is_empty())
+                .ok_or_else(|| color_eyre::eyre::eyre!(USAGE))?;
+            let dry_run = args.iter().any(|arg| arg == "--dry-run");
+            let confirmed = args.iter().any(|arg| arg == "--yes");
+
+            let matches = runtime.block_on(storage.find_purge_matches(topic))?;
+            if matches.is_empty() {
+                println!("Nothing stored mentions '{}'", topic);
+                return Ok(());
+            }
+            println!("{} record(s) mention '{}':", matches.len(), topic);
+            println!("{}", services::personal_data::format_matches(&matches));
+            if dry_run {
+                println!("Dry run: nothing was deleted");
+                return Ok(());
+            }
+            if !confirmed {
+                if !io::stdin().is_terminal() {
+                    return Err(color_eyre::eyre::eyre!("Pass --yes to purge without a prompt"));
+                }
+                print!("Delete these records? Conversation summaries are cleared, not deleted. [y/N] ");
+                io::stdout().flush()?;
+                let mut answer = String::new();
+                io::stdin().lock().read_line(&mut answer)?;
+                if !answer.trim().eq_ignore_ascii_case("y") {
+                    println!("Nothing was deleted");
+                    return Ok(());
+                }
+            }
+            let purged = runtime.block_on(storage.purge_matches(&matches))?;
+            println!("Purged {} record(s) mentioning '{}'", purged, topic);
+            Ok(())
+        }
+        _ => Err(color_eyre::eyre::eyre!(USAGE)),
+    }
+}
+
+/// Runs `kimi import-chat <export> [--format whatsapp|telegram] [--me <name>]`
+fn run_import_chat_command(args: &[String]) -> Result<()> {
+    let mut path = None;
+    let mut format = None;
+    let mut me = None;
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        let mut value = || {
+            remaining
+                .next()
+                .cloned()
+                .ok_or_else(|| color_eyre::eyre::eyre!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--format" => {
+                let name = value()?;
+                format = Some(services::chat_import::ChatExportFormat::from_name(&name).ok_or_else(
+                    || color_eyre::eyre::eyre!("Unknown format '{}' (whatsapp or telegram)", name),
+                )?);
+            }
+            "--me" => me = Some(value()?),
+            other => path = Some(std::path::PathBuf::from(other)),
+        }
+    }
+    let path = path.ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Usage: kimi import-chat <export.txt|result.json> [--format whatsapp|telegram] [--me <name>]"
+        )
+    })?;
+    let format = format.unwrap_or_else(|| services::chat_import::ChatExportFormat::detect(&path));
+    let chat = services::chat_import::read_chat_export(&path, format)?;
+    println!("Importing {} messages from '{}'...", chat.messages.len(), chat.title);
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let summary = runtime.block_on(async {
+        let storage = storage::StorageManager::new().await?;
+        services::chat_import::import_chat(&storage, &chat, format, me.as_deref(), |done, total| {
+            if done % 100 == 0 || done == total {
+                println!("  embedded {}/{}", done, total);
+            }
+        })
+        .await
+    })?;
+    println!(
+        "Imported {} messages from {} into {} ({} embedded)",
+        summary.messages,
+        summary.speakers.join(", "),
+        summary.conversation_id,
+        summary.embedded
+    );
+    Ok(())
+}
+
+/// Runs `kimi migrate [--dry-run]`; normal startup applies the same migrations automatically
+fn run_migrate_command(args: &[String]) -> Result<()> {
+    let dry_run = args.iter().any(|arg| arg == "--dry-run");
+    let runtime = tokio::runtime::Runtime::new()?;
+    let (current, migrations) = runtime.block_on(async {
+        let storage = storage::StorageManager::open_unmigrated().await?;
+        let current = storage.schema_version().await?;
+        Ok::<_, color_eyre::Report>((current, storage.run_migrations(dry_run).await?))
+    })?;
+    if migrations.is_empty() {
+        println!("Schema is up to date (version {})", current);
+        return Ok(());
+    }
+    let verb = if dry_run { "would apply" } else { "applied" };
+    println!("Schema version {}; {} {} migration(s):", current, verb, migrations.len());
+    for migration in migrations {
+        println!("  {} {}", migration.version, migration.name);
+        if dry_run {
+            for statement in migration.statements.lines().filter(|line| !line.trim().is_empty()) {
+                println!("      {}", statement.trim());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs `kimi backup <path>`: one archive with everything needed to move to another machine
+fn run_backup_command(args: &[String]) -> Result<()> {
+    if args.iter().any(|arg| arg == "--auto") {
+        return run_auto_backup_command();
+    }
+    let target = args
+        .first()
+        .ok_or_else(|| color_eyre::eyre::eyre!("Usage: kimi backup <file.tar.gz|dir> | --auto"))?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let summary = runtime.block_on(async {
+        let storage = storage::StorageManager::new().await?;
+        services::archive::create_archive(&storage, std::path::Path::new(target)).await
+    })?;
+    println!(
+        "Backed up {} messages and {} personalities to {}",
+        summary.manifest.messages,
+        summary.manifest.personalities.len(),
+        summary.path.display()
+    );
+    if summary.manifest.encrypted {
+        println!("Conversations are encrypted; restoring them needs the same database passphrase.");
+    }
+    Ok(())
+}
+
+/// Runs `kimi backup --auto`: one snapshot into `[backup] directory` with rotation,
+/// without prompts, for a systemd timer or cron
+fn run_auto_backup_command() -> Result<()> {
+    let config = config::Config::load()?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let summary = runtime.block_on(async {
+        let storage = storage::StorageManager::new().await?;
+        services::backup::run_scheduled_backup(&storage, &config.backup).await
+    })?;
+    println!(
+        "Backed up {} messages to {} (keeping the last {})",
+        summary.manifest.messages,
+        summary.path.display(),
+        config.backup.keep.max(1)
+    );
+    Ok(())
+}
+
+/// Runs `kimi restore <path> [--force]`
+fn run_restore_command(args: &[String]) -> Result<()> {
+    let force = args.iter().any(|arg| arg == "--force");
+    let source = args
+        .iter()
+        .find(|arg| *arg != "--force")
+        .ok_or_else(|| color_eyre::eyre::eyre!("Usage: kimi restore <file.tar.gz> [--force]"))?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let summary = runtime.block_on(async {
+        let storage = storage::StorageManager::new().await?;
+        services::archive::restore_archive(&storage, std::path::Path::new(source), force).await
+    })?;
+    println!(
+        "Restored {} messages and {} personalities from {} (made {})",
+        summary.manifest.messages,
+        summary.manifest.personalities.len(),
+        summary.path.display(),
+        summary.manifest.created_at
+    );
+    Ok(())
+}
+
+fn run_personality_command(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        None => {
+            let config = config::Config::load()?;
+            let selected = if config.personality.selected.is_empty() {
+                services::personality::default_personality_name()
+            } else {
+                config.personality.selected
+            };
+            // Outside the TUI the in-place editor covers SSH sessions and `mode = "internal"`
+            if services::editor::prefers_internal_editor(&config.editor)
+                || services::personality::open_personality_in_new_terminal(&selected, &config.editor).is_err()
+            {
+                services::personality::open_personality_in_place(&selected, &config.editor)?;
+            }
+        }
+        Some("import") => {
+            let overwrite = args.iter().any(|arg| arg == "--force");
+            let source = args
+                .iter()
+                .skip(1)
+                .find(|arg| *arg != "--force")
+                .ok_or_else(|| color_eyre::eyre::eyre!("import needs a .toml or .json file"))?;
+            let package = services::personality_share::import_personality(
+                std::path::Path::new(source),
+                overwrite,
+            )?;
+            println!("Imported personality '{}'", package.name);
+        }
+        Some("export") => {
+            let name = args
+                .get(1)
+                .ok_or_else(|| color_eyre::eyre::eyre!("export needs a personality name"))?;
+            let target = args.get(2).map_or(".", String::as_str);
+            let path =
+                services::personality_share::export_personality(name, std::path::Path::new(target))?;
+            println!("Exported personality '{}' to {}", name, path.display());
+        }
+        Some(_) => {
+            return Err(color_eyre::eyre::eyre!(
+                "Usage: kimi personality [import <file> [--force] | export <name> [dir|file.toml|file.json]]"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn run_config_command(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("export-bundle") => {
+            let target = args.get(1).map_or("kimi-bundle", String::as_str);
+            let summary = services::bundle::export_bundle(std::path::Path::new(target))?;
+            println!(
+                "Exported config and {} personalities to {}",
+                summary.personalities.len(),
+                summary.path.display()
+            );
+        }
+        Some("import-bundle") => {
+            let source = args
+                .get(1)
+                .ok_or_else(|| color_eyre::eyre::eyre!("import-bundle needs a bundle path"))?;
+            let summary = services::bundle::import_bundle(std::path::Path::new(source))?;
+            println!(
+                "Imported config and {} personalities from {}",
+                summary.personalities.len(),
+                summary.path.display()
+            );
+        }
+        _ => {
+            return Err(color_eyre::eyre::eyre!(
+                "Usage: kimi config export-bundle [dir|file.tar.gz] | import-bundle <path>"
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn print_help(program_name: &str) {
     println!("Kimi The Rust CLI - AI Agent Toolkit");
     println!();
-    println!("Usage: {} [command]", program_name);
+    println!("Usage: {} [--profile <name>] [command]", program_name);
     println!();
     println!("Commands:");
-    println!("  weather    - Print Prague weather JSON");
-    println!("  personality - Edit system personality in micro");
+    println!("  weather    - Print current weather ([weather] in config.toml)");
+    println!("  personality - Edit system personality in $VISUAL/$EDITOR ([editor] in config.toml)");
+    println!("  personality import <file> [--force] - Install a shared personality (.toml/.json)");
+    println!("  personality export <name> [path]    - Write a personality to a single shareable file");
+    println!("  bench [--judge <model>] [models...] - Benchmark local models");
+    println!("  ask [--json] \"question\" - Answer one question without the TUI (for scripts)");
+    println!("             Piped stdin is attached as context: git diff | kimi ask \"review this\"");
+    println!("  serve [--host <addr>] [--port <port>] [--token <token>] - Local HTTP API (OpenAI-compatible");
+    println!("             /v1/chat/completions, /memories, /conversations); default 127.0.0.1:8765");
+    println!("  reindex    - Clean up stale embeddings and rebuild search indexes");
+    println!("  archive --older-than <days> - Hide conversations untouched that long from /history");
+    println!("  data report - Counts and categories of the personal data kimi has stored");
+    println!("  data purge --topic <keyword> [--dry-run] [--yes] - List, then delete, everything mentioning a keyword");
+    println!("  import-chat <file> [--format whatsapp|telegram] [--me <name>] - Import a group chat");
+    println!("             export as a searchable conversation; --me marks your own messages");
+    println!("  migrate [--dry-run] - Apply pending database schema migrations (or list them)");
+    println!("  backup <path> - Archive memories, identity, personalities and config (no API keys)");
+    println!("  backup --auto - Snapshot into [backup] directory and keep the last [backup] keep (for timers)");
+    println!("  restore <path> [--force] - Rebuild the database from a backup; --force replaces existing data");
+    println!("  config export-bundle [path] - Export config + personalities (no secrets)");
+    println!("  config import-bundle <path> - Apply an exported bundle");
     println!("  help       - Show help information");
     println!("  --help     - Show this help");
     println!("  --version  - Show version");
     println!();
+    println!("Options:");
+    println!("  --profile <name> - Use a separate profile (config, memories, personalities)");
+    println!();
     println!("Run without arguments to start interactive mode.");
 }
 
@@ -139,12 +675,42 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
     loop {
         // Check for agent responses
         app.check_agent_response();
+        app.autosave_tick();
+        app.tick_log_viewer();
+        app.poll_tts_errors();
         tick_all_animations(app);
         app.clear_expired_status_toast();
+        app.tick_timers();
 
+        if let Some(index) = app.chat_scroll_target.take() {
+            let size = terminal.size()?;
+            let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
+            if let Some(offset) = ui::chat_scroll_offset_for_message(app, area, index) {
+                app.chat_scroll_offset = offset;
+                app.chat_auto_scroll = offset == 0;
+            }
+        }
+        if std::mem::take(&mut app.speak_all_requested) {
+            // The whole conversation, or from the selected / topmost visible message
+            let start = if app.message_selection_active {
+                app.selected_message_index
+            } else if app.chat_auto_scroll {
+                0
+            } else {
+                let size = terminal.size()?;
+                let area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
+                ui::chat_first_visible_message(app, area).unwrap_or(0)
+            };
+            if let Err(error) = app.speak_conversation_from(start) {
+                app.add_system_message(&format!("TTS Error: {}", error));
+            }
+        }
         terminal.draw(|f| ui::render(f, app))?;
 
         if app.should_quit {
+            // Flush whatever the autosave interval has not written yet
+            let _ = app.autosave_conversation(true);
+            app.wait_for_storage_jobs();
             break;
         }
 
@@ -186,9 +752,36 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
                         AppMode::PersonalityCreate => {
                             handle_personality_create_mode(app, key.code)?
                         }
+                        AppMode::PersonalitySuggestion => {
+                            handle_personality_suggestion_mode(app, key.code)?
+                        }
                         AppMode::IdentityView => handle_identity_view_mode(app, key.code)?,
                         AppMode::ProjectList => handle_project_list_mode(app, key.code)?,
                         AppMode::ProjectDetail => handle_project_detail_mode(app, key.code)?,
+                        AppMode::ProjectForm => handle_project_form_mode(app, key.code)?,
+                        AppMode::Agenda => handle_agenda_mode(app, key.code)?,
+                        AppMode::SnippetList => handle_snippet_list_mode(app, key.code)?,
+                        AppMode::LogViewer => handle_log_viewer_mode(app, key.code),
+                        AppMode::Downloads => handle_downloads_mode(app, key.code)?,
+                        AppMode::Inbox => handle_inbox_mode(app, key.code)?,
+                        AppMode::Tasks => handle_tasks_mode(app, key.code)?,
+                        AppMode::EmailPreview => handle_email_preview_mode(app, key.code),
+                        AppMode::HomeAssistantConfirm => handle_home_assistant_confirm_mode(app, key.code),
+                        AppMode::DuplicateQuestion => handle_duplicate_question_mode(app, key.code)?,
+                        AppMode::Status => handle_status_mode(app, key.code),
+                        AppMode::Usage => handle_usage_mode(app, key.code),
+                        AppMode::Audit => handle_audit_mode(app, key.code),
+                        AppMode::Sources => handle_sources_mode(app, key.code),
+                        AppMode::EntityActions => handle_entity_actions_mode(app, key.code),
+                        AppMode::ProfileSelection => handle_profile_selection_mode(app, key.code)?,
+                        AppMode::Search => handle_search_mode(app, key.code)?,
+                        AppMode::Capture => handle_capture_mode(app, key.code),
+                        AppMode::Dashboard => handle_dashboard_mode(app, key.code),
+                        AppMode::Sentiment => handle_sentiment_mode(app, key.code),
+                        AppMode::Diary => handle_diary_mode(app, key.code)?,
+                        AppMode::Editor => handle_editor_mode(app, key.code, key.modifiers),
+                        AppMode::Settings => handle_settings_mode(app, key.code),
+                        AppMode::PromptInspector => handle_prompt_inspector_mode(app, key.code)?,
                     }
                 }
                 Event::Mouse(mouse) => {
@@ -224,7 +817,17 @@ fn tick_animation(is_active: bool, frame: &mut u8, last_tick: &mut Option<std::t
 fn tick_all_animations(app: &mut App) {
     tick_animation(app.is_loading, &mut app.loading_frame, &mut app.last_loading_tick);
     tick_animation(app.conversion_active, &mut app.conversion_frame, &mut app.last_conversion_tick);
+    tick_animation(
+        app.active_transcriptions > 0,
+        &mut app.transcription_frame,
+        &mut app.last_transcription_tick,
+    );
     tick_animation(app.summary_active, &mut app.summary_frame, &mut app.last_summary_tick);
+    tick_animation(
+        app.active_image_generations > 0,
+        &mut app.image_frame,
+        &mut app.last_image_tick,
+    );
 
     // Downloads have per-item animation state
     for download in &mut app.active_downloads {
@@ -375,6 +978,20 @@ fn handle_api_key_input_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
 }
 
 fn handle_chat_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    if app.resume_prompt.is_some() {
+        handle_resume_prompt_keys(app, key_code, modifiers);
+        return Ok(());
+    }
+
+    if app.attachment_panel_active {
+        return handle_attachment_panel_keys(app, key_code, modifiers);
+    }
+
+    if app.message_selection_active {
+        handle_message_selection_keys(app, key_code, modifiers);
+        return Ok(());
+    }
+
     // When suggestion mode is active, handle suggestion navigation first
     if app.suggestion_mode_active {
         return handle_suggestion_keys(app, key_code, modifiers);
@@ -395,6 +1012,20 @@ fn handle_chat_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) -
                 }
             }
         }
+        (KeyCode::Char('o'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_tts_pause()
+        }
+        (KeyCode::Char('n'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
+            app.skip_tts()
+        }
+        (KeyCode::Char('x'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
+            app.stop_tts()
+        }
+        (KeyCode::Media(MediaKeyCode::PlayPause | MediaKeyCode::Play | MediaKeyCode::Pause), _) => {
+            app.toggle_tts_pause()
+        }
+        (KeyCode::Media(MediaKeyCode::TrackNext), _) => app.skip_tts(),
+        (KeyCode::Media(MediaKeyCode::Stop), _) => app.stop_tts(),
         (KeyCode::Char('t'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
             app.toggle_auto_tts();
             let status = if app.auto_tts_enabled {
@@ -415,6 +1046,17 @@ fn handle_chat_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) -
         (KeyCode::Char('v'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
             app.handle_chat_clipboard_image()?;
         }
+        (KeyCode::Char('a'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_attachment_panel();
+        }
+        (KeyCode::Char('k'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_message_selection();
+        }
+        (KeyCode::Char('s'), key_modifiers) if key_modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Err(error) = app.save_last_code_blocks_as_snippets() {
+                app.show_status_toast(format!("SNIPPET SAVE FAILED: {}", error));
+            }
+        }
         (KeyCode::Tab, _) => {
             // Rotate between chat and translate agents
             if let Err(error) = app.rotate_agent() {
@@ -483,56 +1125,44 @@ fn handle_chat_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) -
     Ok(())
 }
 
-/// Handles keys while suggestion mode is active (navigating follow-up pills)
-fn handle_suggestion_keys(
-    app: &mut App,
-    key_code: KeyCode,
-    _modifiers: KeyModifiers,
-) -> Result<()> {
+/// Handles keys while the attachment manager panel is open
+fn handle_message_selection_keys(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
     match key_code {
-        KeyCode::Left => {
-            if app.suggestion_selected_index > 0 {
-                app.suggestion_selected_index -= 1;
-            } else {
-                app.suggestion_selected_index = app.follow_up_suggestions.len().saturating_sub(1);
-            }
+        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => app.should_quit = true,
+        KeyCode::Char('k') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.close_message_selection();
         }
-        KeyCode::Right => {
-            let count = app.follow_up_suggestions.len();
-            if count > 0 {
-                app.suggestion_selected_index = (app.suggestion_selected_index + 1) % count;
+        KeyCode::Esc => app.close_message_selection(),
+        KeyCode::Up => app.previous_selected_message(),
+        KeyCode::Down => app.next_selected_message(),
+        KeyCode::Char('c') | KeyCode::Enter => app.copy_selected_message(),
+        KeyCode::Char('s') => {
+            if let Err(error) = app.speak_selected_message() {
+                app.show_status_toast(format!("TTS FAILED: {}", error));
             }
         }
-        KeyCode::Enter => {
-            // Send the selected suggestion as a user message
-            if let Some(suggestion) = app
-                .follow_up_suggestions
-                .get(app.suggestion_selected_index)
-                .cloned()
-            {
-                app.suggestion_mode_active = false;
-                app.follow_up_suggestions.clear();
-                // Set input to the suggestion text and send
-                for character in suggestion.chars() {
-                    app.add_chat_input_char(character);
-                }
-                app.send_chat_message()?;
-                app.reset_chat_scroll();
+        KeyCode::Char('d') | KeyCode::Delete => app.toggle_selected_message_context(),
+        KeyCode::Char('q') => app.quote_selected_message(),
+        KeyCode::Char('t') => {
+            if let Err(error) = app.translate_selected_message() {
+                app.show_status_toast(format!("TRANSLATE FAILED: {}", error));
             }
         }
-        KeyCode::Down | KeyCode::Esc => {
-            // Exit suggestion mode, return focus to input
-            app.suggestion_mode_active = false;
+        KeyCode::Char('n') => {
+            if let Err(error) = app.save_selected_message_as_snippet() {
+                app.show_status_toast(format!("SNIPPET SAVE FAILED: {}", error));
+            }
         }
-        KeyCode::Backspace
-        | KeyCode::Up
+        KeyCode::Char('e') => app.open_entity_actions(),
+        KeyCode::Left
+        | KeyCode::Right
         | KeyCode::Home
         | KeyCode::End
         | KeyCode::PageUp
         | KeyCode::PageDown
         | KeyCode::Tab
         | KeyCode::BackTab
-        | KeyCode::Delete
+        | KeyCode::Backspace
         | KeyCode::Insert
         | KeyCode::F(_)
         | KeyCode::Char(_)
@@ -545,36 +1175,228 @@ fn handle_suggestion_keys(
         | KeyCode::Menu
         | KeyCode::KeypadBegin
         | KeyCode::Media(_)
-        | KeyCode::Modifier(_) => {
-            // Any other key exits suggestion mode
-            app.suggestion_mode_active = false;
-        }
+        | KeyCode::Modifier(_) => {}
     }
-    Ok(())
 }
 
-fn handle_mouse_event(app: &mut App, mouse: event::MouseEvent) -> Result<()> {
-    if app.mode != AppMode::Chat {
-        return Ok(());
+fn handle_resume_prompt_keys(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
+    match key_code {
+        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => app.should_quit = true,
+        KeyCode::Char('y') | KeyCode::Enter => app.accept_resume_prompt(),
+        KeyCode::Char('n') | KeyCode::Esc => app.dismiss_resume_prompt(),
+        KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Backspace
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
     }
+}
 
-    match mouse.kind {
-        event::MouseEventKind::Down(event::MouseButton::Left) => {
-            if is_in_chat_history(mouse.column, mouse.row)? {
-                let message = app.last_assistant_message().map(str::to_string);
-                if let Some(message) = message {
-                    if app.clipboard_service.copy_text(&message).is_ok() {
-                        app.show_status_toast("COPIED");
-                    } else {
-                        app.show_status_toast("COPY FAILED");
-                    }
-                }
-            }
+fn handle_attachment_panel_keys(
+    app: &mut App,
+    key_code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    match key_code {
+        KeyCode::Up if modifiers.contains(KeyModifiers::SHIFT) => app.move_attachment_up(),
+        KeyCode::Down if modifiers.contains(KeyModifiers::SHIFT) => app.move_attachment_down(),
+        KeyCode::Up => app.previous_attachment(),
+        KeyCode::Down => app.next_attachment(),
+        KeyCode::Char('a') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_attachment_panel();
         }
-        event::MouseEventKind::ScrollUp => {
-            app.scroll_chat_up_lines(3);
+        KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace => {
+            app.remove_selected_attachment();
         }
-        event::MouseEventKind::ScrollDown => {
+        KeyCode::Esc | KeyCode::Enter => app.attachment_panel_active = false,
+        KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+/// Handles keys while suggestion mode is active (navigating follow-up pills)
+fn handle_suggestion_keys(
+    app: &mut App,
+    key_code: KeyCode,
+    _modifiers: KeyModifiers,
+) -> Result<()> {
+    match key_code {
+        KeyCode::Left | KeyCode::BackTab => {
+            if app.suggestion_selected_index > 0 {
+                app.suggestion_selected_index -= 1;
+            } else {
+                app.suggestion_selected_index = app.follow_up_suggestions.len().saturating_sub(1);
+            }
+        }
+        KeyCode::Right | KeyCode::Tab => {
+            let count = app.follow_up_suggestions.len();
+            if count > 0 {
+                app.suggestion_selected_index = (app.suggestion_selected_index + 1) % count;
+            }
+        }
+        KeyCode::Enter => {
+            // Send the selected suggestion as a user message
+            if let Some(suggestion) = app
+                .follow_up_suggestions
+                .get(app.suggestion_selected_index)
+                .cloned()
+            {
+                app.suggestion_mode_active = false;
+                app.follow_up_suggestions.clear();
+                // Set input to the suggestion text and send
+                for character in suggestion.chars() {
+                    app.add_chat_input_char(character);
+                }
+                app.send_chat_message()?;
+                app.reset_chat_scroll();
+            }
+        }
+        KeyCode::Down | KeyCode::Esc => {
+            // Exit suggestion mode, return focus to input
+            app.suggestion_mode_active = false;
+        }
+        KeyCode::Backspace
+        | KeyCode::Up
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {
+            // Any other key exits suggestion mode
+            app.suggestion_mode_active = false;
+        }
+    }
+    Ok(())
+}
+
+fn handle_mouse_event(app: &mut App, mouse: event::MouseEvent) -> Result<()> {
+    let (width, height) = crossterm::terminal::size()?;
+    let area = Rect::new(0, 0, width, height);
+    match app.mode {
+        AppMode::Chat => handle_chat_mouse(app, mouse, area),
+        AppMode::CommandMenu => handle_command_menu_mouse(app, mouse, area)?,
+        AppMode::History => {
+            if !app.history_rename_active && !app.history_delete_all_active {
+                handle_history_mouse(app, mouse, area)?;
+            }
+        }
+        AppMode::Help => match mouse.kind {
+            event::MouseEventKind::ScrollUp => app.scroll_help_up(3),
+            event::MouseEventKind::ScrollDown => app.scroll_help_down(3),
+            event::MouseEventKind::ScrollLeft
+            | event::MouseEventKind::ScrollRight
+            | event::MouseEventKind::Down(_)
+            | event::MouseEventKind::Up(_)
+            | event::MouseEventKind::Drag(_)
+            | event::MouseEventKind::Moved => {}
+        },
+        AppMode::ModelSelection
+        | AppMode::Connect
+        | AppMode::ApiKeyInput
+        | AppMode::PersonalitySelection
+        | AppMode::PersonalityCreate
+        | AppMode::PersonalitySuggestion
+        | AppMode::IdentityView
+        | AppMode::ProfileSelection
+        | AppMode::Search
+        | AppMode::ProjectList
+        | AppMode::ProjectDetail
+        | AppMode::ProjectForm
+        | AppMode::Agenda
+        | AppMode::SnippetList
+        | AppMode::LogViewer
+        | AppMode::Downloads
+        | AppMode::Inbox
+        | AppMode::Tasks
+        | AppMode::EmailPreview
+        | AppMode::HomeAssistantConfirm
+        | AppMode::DuplicateQuestion
+        | AppMode::Status
+        | AppMode::Usage
+        | AppMode::Audit
+        | AppMode::Sources
+        | AppMode::EntityActions
+        | AppMode::Capture
+        | AppMode::Dashboard
+        | AppMode::Sentiment
+        | AppMode::Diary
+        | AppMode::Editor
+        | AppMode::Settings
+        | AppMode::PromptInspector => {}
+    }
+    Ok(())
+}
+
+/// Click selects a message (a second click copies it), Ctrl+click opens a link in it
+fn handle_chat_mouse(app: &mut App, mouse: event::MouseEvent, area: Rect) {
+    match mouse.kind {
+        event::MouseEventKind::Down(event::MouseButton::Left) => {
+            let Some(hit) = ui::chat_hit_at(app, area, mouse.column, mouse.row) else {
+                return;
+            };
+            if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+                app.open_link_in_message(hit.message_index, hit.url);
+            } else {
+                app.click_message(hit.message_index);
+            }
+        }
+        event::MouseEventKind::ScrollUp => {
+            app.scroll_chat_up_lines(3);
+        }
+        event::MouseEventKind::ScrollDown => {
             app.scroll_chat_down_lines(3);
         }
         event::MouseEventKind::ScrollLeft | event::MouseEventKind::ScrollRight => {
@@ -585,6 +1407,50 @@ fn handle_mouse_event(app: &mut App, mouse: event::MouseEvent) -> Result<()> {
         | event::MouseEventKind::Drag(_)
         | event::MouseEventKind::Moved => {}
     }
+}
+
+/// Click runs a command; the wheel moves the selection
+fn handle_command_menu_mouse(app: &mut App, mouse: event::MouseEvent, area: Rect) -> Result<()> {
+    match mouse.kind {
+        event::MouseEventKind::Down(event::MouseButton::Left) => {
+            if let Some(index) = ui::command_item_at(app, area, mouse.column, mouse.row) {
+                app.selected_index = index;
+                app.execute_selected()?;
+            }
+        }
+        event::MouseEventKind::ScrollUp => app.previous_item(),
+        event::MouseEventKind::ScrollDown => app.next_item(),
+        event::MouseEventKind::ScrollLeft
+        | event::MouseEventKind::ScrollRight
+        | event::MouseEventKind::Down(_)
+        | event::MouseEventKind::Up(_)
+        | event::MouseEventKind::Drag(_)
+        | event::MouseEventKind::Moved => {}
+    }
+    Ok(())
+}
+
+/// Click selects a conversation, clicking the selected one opens it
+fn handle_history_mouse(app: &mut App, mouse: event::MouseEvent, area: Rect) -> Result<()> {
+    match mouse.kind {
+        event::MouseEventKind::Down(event::MouseButton::Left) => {
+            if let Some(index) = ui::history_item_at(app, area, mouse.column, mouse.row) {
+                if index == app.history_selected_index {
+                    app.load_history_conversation()?;
+                } else {
+                    app.history_selected_index = index;
+                }
+            }
+        }
+        event::MouseEventKind::ScrollUp => app.previous_history_item(),
+        event::MouseEventKind::ScrollDown => app.next_history_item(),
+        event::MouseEventKind::ScrollLeft
+        | event::MouseEventKind::ScrollRight
+        | event::MouseEventKind::Down(_)
+        | event::MouseEventKind::Up(_)
+        | event::MouseEventKind::Drag(_)
+        | event::MouseEventKind::Moved => {}
+    }
     Ok(())
 }
 
@@ -612,7 +1478,11 @@ fn handle_paste(app: &mut App, paste: &str) -> Result<()> {
             }
         }
         AppMode::History => {
-            if app.history_filter_active {
+            if app.history_rename_active {
+                for character in text.chars().filter(|character| !character.is_control()) {
+                    app.add_history_rename_char(character);
+                }
+            } else if app.history_filter_active {
                 for character in text.chars() {
                     app.add_history_filter_char(character);
                 }
@@ -623,48 +1493,84 @@ fn handle_paste(app: &mut App, paste: &str) -> Result<()> {
                 app.add_personality_char(character);
             }
         }
+        AppMode::ProjectForm => {
+            for character in text.chars().filter(|character| !character.is_control()) {
+                app.add_project_form_char(character);
+            }
+        }
+        AppMode::Search => {
+            for character in text.chars().filter(|character| !character.is_control()) {
+                app.add_search_char(character);
+            }
+        }
+        AppMode::Capture => {
+            for character in text.chars() {
+                let character = if character.is_control() { ' ' } else { character };
+                app.capture_input.add_char(character);
+            }
+        }
+        AppMode::Settings => {
+            if let Some(input) = &mut app.settings_input {
+                for character in text.chars().filter(|character| !character.is_control()) {
+                    input.add_char(character);
+                }
+            }
+        }
+        AppMode::Editor => {
+            // Keep the line breaks stripped from `text` for single-line inputs
+            let text = paste.replace("\r\n", "\n").replace('\r', "\n");
+            app.edit_buffer(editor_visible_lines(), |buffer| {
+                for character in text.chars() {
+                    let character = if character == '\t' { ' ' } else { character };
+                    if character == '\n' || !character.is_control() {
+                        buffer.insert_char(character);
+                    }
+                }
+                true
+            });
+        }
+        AppMode::ProfileSelection => {
+            if app.profile_create_active {
+                for character in text.chars().filter(|character| !character.is_control()) {
+                    app.add_profile_char(character);
+                }
+            }
+        }
+        AppMode::Help => {
+            for character in text.chars() {
+                app.add_help_filter_char(character);
+            }
+        }
         AppMode::ModelSelection
         | AppMode::Connect
-        | AppMode::Help
         | AppMode::PersonalitySelection
+        | AppMode::PersonalitySuggestion
         | AppMode::IdentityView
         | AppMode::ProjectList
-        | AppMode::ProjectDetail => {}
+        | AppMode::ProjectDetail
+        | AppMode::Agenda
+        | AppMode::SnippetList
+        | AppMode::LogViewer
+        | AppMode::Downloads
+        | AppMode::Inbox
+        | AppMode::Tasks
+        | AppMode::EmailPreview
+        | AppMode::HomeAssistantConfirm
+        | AppMode::DuplicateQuestion
+        | AppMode::Status
+        | AppMode::Usage
+        | AppMode::Audit
+        | AppMode::Sources
+        | AppMode::EntityActions
+        | AppMode::Dashboard
+        | AppMode::Sentiment
+        | AppMode::Diary
+        | AppMode::PromptInspector => {}
     }
 
     Ok(())
 }
 
-fn is_in_chat_history(column: u16, row: u16) -> Result<bool> {
-    let (width, height) = crossterm::terminal::size()?;
-    let area = Rect {
-        x: 0,
-        y: 0,
-        width,
-        height,
-    };
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Chat history
-            Constraint::Length(3), // Input
-            Constraint::Length(3), // Footer
-        ])
-        .split(area);
-
-    let history_area = chunks
-        .get(1)
-        .copied()
-        .ok_or_else(|| color_eyre::eyre::eyre!("Chat history area not found"))?;
-
-    Ok(column >= history_area.x
-        && column < history_area.x + history_area.width
-        && row >= history_area.y
-        && row < history_area.y + history_area.height)
-}
-
 fn handle_history_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
     if app.history_delete_all_active {
         #[allow(clippy::wildcard_enum_match_arm)]
@@ -678,6 +1584,38 @@ fn handle_history_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers
         }
         return Ok(());
     }
+    if app.history_rename_active {
+        match key_code {
+            KeyCode::Esc => app.cancel_history_rename(),
+            KeyCode::Enter => app.confirm_history_rename()?,
+            KeyCode::Char(character) => app.add_history_rename_char(character),
+            KeyCode::Backspace => app.remove_history_rename_char(),
+            KeyCode::Left => app.history_rename_input.move_left(),
+            KeyCode::Right => app.history_rename_input.move_right(),
+            KeyCode::Home => app.history_rename_input.move_to_start(),
+            KeyCode::End => app.history_rename_input.move_to_end(),
+            KeyCode::Delete => app.history_rename_input.delete_char(),
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Tab
+            | KeyCode::BackTab
+            | KeyCode::Insert
+            | KeyCode::F(_)
+            | KeyCode::Null
+            | KeyCode::CapsLock
+            | KeyCode::ScrollLock
+            | KeyCode::NumLock
+            | KeyCode::PrintScreen
+            | KeyCode::Pause
+            | KeyCode::Menu
+            | KeyCode::KeypadBegin
+            | KeyCode::Media(_)
+            | KeyCode::Modifier(_) => {}
+        }
+        return Ok(());
+    }
     let control_pressed = modifiers.contains(KeyModifiers::CONTROL);
     if app.history_filter_active {
         if control_pressed && key_code == KeyCode::Char('f') {
@@ -731,6 +1669,10 @@ fn handle_history_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers
             KeyCode::Enter => app.load_history_conversation()?,
             KeyCode::Delete => app.delete_history_conversation()?,
             KeyCode::Char('/') => app.open_command_menu(),
+            // Bare letters start the filter, so actions need Ctrl
+            KeyCode::Char('r') | KeyCode::Char('R') if control_pressed => app.open_history_rename(),
+            KeyCode::Char('a') | KeyCode::Char('A') if control_pressed => app.toggle_selected_archived(),
+            KeyCode::Char('k') | KeyCode::Char('K') if control_pressed => app.cycle_selected_retention(),
             KeyCode::Char(character) => {
                 if !control_pressed {
                     app.toggle_history_filter();
@@ -767,24 +1709,23 @@ fn handle_history_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers
 
 fn handle_help_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
     match key_code {
-        KeyCode::Esc => app.close_help(),
-        KeyCode::Char('q') => app.close_help(),
+        KeyCode::Esc => app.escape_help(),
+        KeyCode::Char(character) => app.add_help_filter_char(character),
+        KeyCode::Backspace => app.remove_help_filter_char(),
+        KeyCode::Up => app.scroll_help_up(1),
+        KeyCode::Down => app.scroll_help_down(1),
+        KeyCode::PageUp => app.scroll_help_up(10),
+        KeyCode::PageDown => app.scroll_help_down(10),
+        KeyCode::Home => app.scroll_help_to_top(),
+        KeyCode::End => app.scroll_help_to_bottom(),
         KeyCode::Enter
-        | KeyCode::Backspace
-        | KeyCode::Up
-        | KeyCode::Down
         | KeyCode::Left
         | KeyCode::Right
-        | KeyCode::Home
-        | KeyCode::End
-        | KeyCode::PageUp
-        | KeyCode::PageDown
         | KeyCode::Tab
         | KeyCode::BackTab
         | KeyCode::Delete
         | KeyCode::Insert
         | KeyCode::F(_)
-        | KeyCode::Char(_)
         | KeyCode::Null
         | KeyCode::CapsLock
         | KeyCode::ScrollLock
@@ -807,6 +1748,9 @@ fn handle_personality_selection_mode(app: &mut App, key_code: KeyCode) -> Result
         KeyCode::Enter => app.select_personality()?,
         KeyCode::Char('n') | KeyCode::Char('N') => app.open_personality_create(),
         KeyCode::Char('e') | KeyCode::Char('E') => app.edit_selected_personality()?,
+        KeyCode::Char('s') | KeyCode::Char('S') => app.open_personality_suggestion(),
+        KeyCode::Char('t') | KeyCode::Char('T') => app.test_selected_personality()?,
+        KeyCode::Char('c') | KeyCode::Char('C') => app.clone_selected_personality()?,
         KeyCode::Delete => app.delete_selected_personality()?,
         KeyCode::Backspace
         | KeyCode::Left
@@ -834,25 +1778,26 @@ fn handle_personality_selection_mode(app: &mut App, key_code: KeyCode) -> Result
     Ok(())
 }
 
-fn handle_personality_create_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+fn handle_prompt_inspector_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
     match key_code {
-        KeyCode::Esc => app.open_personality_menu()?,
-        KeyCode::Enter => app.create_personality()?,
-        KeyCode::Char(character) => app.add_personality_char(character),
-        KeyCode::Backspace => app.remove_personality_char(),
-        KeyCode::Left
+        KeyCode::Esc | KeyCode::Char('q') => app.close_prompt_inspector(),
+        KeyCode::Up => app.previous_prompt_section(),
+        KeyCode::Down => app.next_prompt_section(),
+        KeyCode::PageUp => app.scroll_prompt_section(-10),
+        KeyCode::PageDown => app.scroll_prompt_section(10),
+        KeyCode::Char(' ') | KeyCode::Enter => app.toggle_selected_prompt_section()?,
+        KeyCode::Char('r') => app.refresh_prompt_preview()?,
+        KeyCode::Backspace
+        | KeyCode::Left
         | KeyCode::Right
-        | KeyCode::Up
-        | KeyCode::Down
         | KeyCode::Home
         | KeyCode::End
-        | KeyCode::PageUp
-        | KeyCode::PageDown
         | KeyCode::Tab
         | KeyCode::BackTab
         | KeyCode::Delete
         | KeyCode::Insert
         | KeyCode::F(_)
+        | KeyCode::Char(_)
         | KeyCode::Null
         | KeyCode::CapsLock
         | KeyCode::ScrollLock
@@ -867,13 +1812,47 @@ fn handle_personality_create_mode(app: &mut App, key_code: KeyCode) -> Result<()
     Ok(())
 }
 
-fn handle_project_list_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+fn handle_log_viewer_mode(app: &mut App, key_code: KeyCode) {
     match key_code {
-        KeyCode::Esc => app.close_projects(),
-        KeyCode::Up => app.previous_project(),
-        KeyCode::Down => app.next_project(),
-        KeyCode::Enter => app.open_project_detail(),
-        KeyCode::Backspace
+        KeyCode::Esc | KeyCode::Char('q') => app.close_log_viewer(),
+        KeyCode::Up => app.scroll_log_up(1),
+        KeyCode::Down => app.scroll_log_down(1),
+        KeyCode::PageUp => app.scroll_log_up(20),
+        KeyCode::PageDown => app.scroll_log_down(20),
+        KeyCode::End => app.scroll_log_down(usize::MAX),
+        KeyCode::Home => app.scroll_log_up(usize::MAX),
+        KeyCode::Enter
+        | KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_downloads_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_downloads(),
+        KeyCode::Up => app.previous_download(),
+        KeyCode::Down => app.next_download(),
+        KeyCode::Char('c' | 'C') | KeyCode::Delete => app.cancel_selected_download(),
+        KeyCode::Char('r' | 'R') => app.retry_selected_download()?,
+        KeyCode::Enter
+        | KeyCode::Backspace
         | KeyCode::Left
         | KeyCode::Right
         | KeyCode::Home
@@ -882,7 +1861,6 @@ fn handle_project_list_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
         | KeyCode::PageDown
         | KeyCode::Tab
         | KeyCode::BackTab
-        | KeyCode::Delete
         | KeyCode::Insert
         | KeyCode::F(_)
         | KeyCode::Char(_)
@@ -900,11 +1878,926 @@ fn handle_project_list_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
     Ok(())
 }
 
-fn handle_project_detail_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+fn handle_sources_mode(app: &mut App, key_code: KeyCode) {
     match key_code {
-        KeyCode::Esc => app.close_project_detail(),
-        KeyCode::Up => app.previous_project_entry(),
-        KeyCode::Down => app.next_project_entry(),
+        KeyCode::Esc | KeyCode::Char('q') => app.close_sources(),
+        KeyCode::Up => app.previous_source(),
+        KeyCode::Down => app.next_source(),
+        KeyCode::Enter => app.open_selected_source(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_audit_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_audit(),
+        KeyCode::Up => app.previous_audit_item(),
+        KeyCode::Down => app.next_audit_item(),
+        KeyCode::Enter
+        | KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_inbox_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_inbox(),
+        KeyCode::Up => app.previous_inbox_item(),
+        KeyCode::Down => app.next_inbox_item(),
+        KeyCode::Enter | KeyCode::Char(' ') => app.toggle_selected_notification_read()?,
+        KeyCode::Char('a' | 'A') => app.mark_all_notifications_read()?,
+        KeyCode::Delete | KeyCode::Char('d' | 'D') => app.delete_selected_notification()?,
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_tasks_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_tasks(),
+        KeyCode::Up => app.previous_due_task(),
+        KeyCode::Down => app.next_due_task(),
+        KeyCode::Enter | KeyCode::Char('a' | 'A') => app.accept_selected_due_task()?,
+        KeyCode::Char('c' | 'C' | 'x' | 'X') => app.complete_selected_due_task()?,
+        KeyCode::Char('s' | 'S') => app.snooze_selected_due_task(1)?,
+        KeyCode::Char('w' | 'W') => app.snooze_selected_due_task(7)?,
+        KeyCode::Delete | KeyCode::Char('d' | 'D') => app.delete_selected_due_task()?,
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_email_preview_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('n' | 'N' | 'd' | 'D') => app.discard_email_draft(),
+        KeyCode::Enter | KeyCode::Char('y' | 'Y' | 's' | 'S') => app.send_email_draft(),
+        KeyCode::Up => app.scroll_email_preview_up(),
+        KeyCode::Down => app.scroll_email_preview_down(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_home_assistant_confirm_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('n' | 'N') => app.cancel_home_assistant_call(),
+        KeyCode::Enter | KeyCode::Char('y' | 'Y') => app.run_home_assistant_call(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_duplicate_question_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Enter | KeyCode::Char('y' | 'Y') => app.reuse_earlier_answer(),
+        KeyCode::Char('n' | 'N') => app.ask_again()?,
+        KeyCode::Esc => app.dismiss_earlier_answer(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_status_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'Q') => app.close_status(),
+        KeyCode::Char('r' | 'R') => app.refresh_health(),
+        KeyCode::Backspace
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_usage_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'Q') => app.close_usage(),
+        KeyCode::Up => app.scroll_usage_up(),
+        KeyCode::Down => app.scroll_usage_down(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_entity_actions_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q' | 'Q') => app.close_entity_actions(),
+        KeyCode::Enter => app.run_selected_entity_action(),
+        KeyCode::Up => app.previous_entity_action(),
+        KeyCode::Down => app.next_entity_action(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_personality_suggestion_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_personality_suggestion(),
+        KeyCode::Up => app.scroll_personality_suggestion_up(),
+        KeyCode::Down => app.scroll_personality_suggestion_down(),
+        KeyCode::Char('a') | KeyCode::Char('A') => app.accept_personality_suggestion()?,
+        KeyCode::Char('r') | KeyCode::Char('R') => app.reject_personality_suggestion()?,
+        KeyCode::Enter
+        | KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_personality_create_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.open_personality_menu()?,
+        KeyCode::Enter => app.create_personality()?,
+        KeyCode::Char(character) => app.add_personality_char(character),
+        KeyCode::Backspace => app.remove_personality_char(),
+        KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_project_list_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_projects(),
+        KeyCode::Up => app.previous_project(),
+        KeyCode::Down => app.next_project(),
+        KeyCode::Enter => app.open_project_detail(),
+        KeyCode::Char(' ') => app.toggle_active_project(),
+        KeyCode::Char('n' | 'N') => app.open_project_create(),
+        KeyCode::Char('e' | 'E') => app.open_project_edit(),
+        KeyCode::Delete => app.archive_selected_project()?,
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_project_form_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_project_form(),
+        KeyCode::Enter => app.submit_project_form()?,
+        KeyCode::Tab | KeyCode::BackTab => app.toggle_project_form_field(),
+        KeyCode::Char(character) => app.add_project_form_char(character),
+        KeyCode::Backspace => app.remove_project_form_char(),
+        KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_project_detail_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_project_detail(),
+        KeyCode::Tab | KeyCode::BackTab => app.toggle_project_detail_focus(),
+        KeyCode::Char('c' | 'C') => app.attach_current_conversation_to_project()?,
+        KeyCode::Up => match app.project_detail_focus {
+            ProjectDetailFocus::Entries => app.previous_project_entry(),
+            ProjectDetailFocus::Tasks => app.previous_project_task(),
+        },
+        KeyCode::Down => match app.project_detail_focus {
+            ProjectDetailFocus::Entries => app.next_project_entry(),
+            ProjectDetailFocus::Tasks => app.next_project_task(),
+        },
+        KeyCode::Char('a' | 'A') => match app.project_detail_focus {
+            ProjectDetailFocus::Entries => app.open_project_entry_add(),
+            ProjectDetailFocus::Tasks => app.open_project_task_add(),
+        },
+        KeyCode::Delete => match app.project_detail_focus {
+            ProjectDetailFocus::Entries => app.remove_selected_project_entry()?,
+            ProjectDetailFocus::Tasks => app.remove_selected_project_task()?,
+        },
+        KeyCode::Char(' ') | KeyCode::Enter
+            if app.project_detail_focus == ProjectDetailFocus::Tasks =>
+        {
+            app.advance_selected_project_task()?;
+        }
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Insert
+        | KeyCode::Enter
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_agenda_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_agenda(),
+        KeyCode::Up => app.previous_agenda_event(),
+        KeyCode::Down => app.next_agenda_event(),
+        KeyCode::Char('r') => app.open_agenda(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::Enter
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_snippet_list_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_snippets(),
+        KeyCode::Up => app.previous_snippet(),
+        KeyCode::Down => app.next_snippet(),
+        KeyCode::Enter => app.insert_selected_snippet(),
+        KeyCode::Char('c') => app.copy_selected_snippet(),
+        KeyCode::Char('d') | KeyCode::Delete => app.delete_selected_snippet()?,
+        KeyCode::Char('s') => app.save_selected_clipboard_entry(),
+        KeyCode::Tab => app.toggle_snippet_source(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_search_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc => app.close_search(),
+        KeyCode::Enter => {
+            if let Err(error) = app.confirm_search() {
+                app.show_status_toast(format!("OPEN FAILED: {}", error));
+            }
+        }
+        KeyCode::Up => app.previous_search_hit(),
+        KeyCode::Down => app.next_search_hit(),
+        KeyCode::Char(character) => app.add_search_char(character),
+        KeyCode::Backspace => app.remove_search_char(),
+        KeyCode::Left => app.search_input.move_left(),
+        KeyCode::Right => app.search_input.move_right(),
+        KeyCode::Home => app.search_input.move_to_start(),
+        KeyCode::End => app.search_input.move_to_end(),
+        KeyCode::Delete => app.search_input.delete_char(),
+        KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+fn handle_settings_mode(app: &mut App, key_code: KeyCode) {
+    if let Some(input) = &mut app.settings_input {
+        match key_code {
+            KeyCode::Esc => app.cancel_setting_input(),
+            KeyCode::Enter => app.confirm_setting_input(),
+            KeyCode::Char(character) => input.add_char(character),
+            KeyCode::Backspace => input.remove_char(),
+            KeyCode::Delete => input.delete_char(),
+            KeyCode::Left => input.move_left(),
+            KeyCode::Right => input.move_right(),
+            KeyCode::Home => input.move_to_start(),
+            KeyCode::End => input.move_to_end(),
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Tab
+            | KeyCode::BackTab
+            | KeyCode::Insert
+            | KeyCode::F(_)
+            | KeyCode::Null
+            | KeyCode::CapsLock
+            | KeyCode::ScrollLock
+            | KeyCode::NumLock
+            | KeyCode::PrintScreen
+            | KeyCode::Pause
+            | KeyCode::Menu
+            | KeyCode::KeypadBegin
+            | KeyCode::Media(_)
+            | KeyCode::Modifier(_) => {}
+        }
+        return;
+    }
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_settings(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous_setting(),
+        KeyCode::Down | KeyCode::Char('j') => app.next_setting(),
+        KeyCode::Enter | KeyCode::Char(' ') => app.activate_setting(),
+        KeyCode::Backspace
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_dashboard_mode(app: &mut App, key_code: KeyCode) {
+    if matches!(key_code, KeyCode::Esc | KeyCode::Char('q')) {
+        app.close_dashboard();
+    }
+}
+
+fn handle_sentiment_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_sentiment(),
+        KeyCode::Up => app.scroll_sentiment_up(),
+        KeyCode::Down => app.scroll_sentiment_down(),
+        KeyCode::Backspace
+        | KeyCode::Enter
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Delete
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_diary_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_diary(),
+        KeyCode::Up => app.previous_diary_entry(),
+        KeyCode::Down => app.next_diary_entry(),
+        KeyCode::Char('e' | 'E') => app.export_selected_diary_entry(),
+        KeyCode::Delete | KeyCode::Char('d' | 'D') => app.delete_selected_diary_entry()?,
+        KeyCode::Backspace
+        | KeyCode::Enter
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Char(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+    Ok(())
+}
+
+/// Text rows in the built-in editor: the terminal minus header, borders and footer
+fn editor_visible_lines() -> usize {
+    crossterm::terminal::size().map_or(20, |(_, height)| usize::from(height.saturating_sub(8)))
+}
+
+fn handle_editor_mode(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
+    let visible_lines = editor_visible_lines();
+    match key_code {
+        KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => app.save_editor(),
+        KeyCode::Char(_) if modifiers.contains(KeyModifiers::CONTROL) => {}
+        KeyCode::Esc => app.close_editor(),
+        KeyCode::Char(character) => app.edit_buffer(visible_lines, |buffer| {
+            buffer.insert_char(character);
+            true
+        }),
+        KeyCode::Tab => app.edit_buffer(visible_lines, |buffer| {
+            for _ in 0..4 {
+                buffer.insert_char(' ');
+            }
+            true
+        }),
+        KeyCode::Enter => app.edit_buffer(visible_lines, |buffer| {
+            buffer.insert_newline();
+            true
+        }),
+        KeyCode::Backspace => app.edit_buffer(visible_lines, |buffer| {
+            buffer.backspace();
+            true
+        }),
+        KeyCode::Delete => app.edit_buffer(visible_lines, |buffer| {
+            buffer.delete();
+            true
+        }),
+        KeyCode::Left => app.edit_buffer(visible_lines, |buffer| {
+            buffer.move_left();
+            false
+        }),
+        KeyCode::Right => app.edit_buffer(visible_lines, |buffer| {
+            buffer.move_right();
+            false
+        }),
+        KeyCode::Up => app.edit_buffer(visible_lines, |buffer| {
+            buffer.move_up();
+            false
+        }),
+        KeyCode::Down => app.edit_buffer(visible_lines, |buffer| {
+            buffer.move_down();
+            false
+        }),
+        KeyCode::Home => app.edit_buffer(visible_lines, |buffer| {
+            buffer.move_home();
+            false
+        }),
+        KeyCode::End => app.edit_buffer(visible_lines, |buffer| {
+            buffer.move_end();
+            false
+        }),
+        KeyCode::PageUp => app.edit_buffer(visible_lines, |buffer| {
+            for _ in 0..visible_lines {
+                buffer.move_up();
+            }
+            false
+        }),
+        KeyCode::PageDown => app.edit_buffer(visible_lines, |buffer| {
+            for _ in 0..visible_lines {
+                buffer.move_down();
+            }
+            false
+        }),
+        KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_capture_mode(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Esc => app.close_capture(),
+        KeyCode::Enter => app.save_capture(),
+        KeyCode::Char(character) => app.capture_input.add_char(character),
+        KeyCode::Backspace => app.capture_input.remove_char(),
+        KeyCode::Left => app.capture_input.move_left(),
+        KeyCode::Right => app.capture_input.move_right(),
+        KeyCode::Home => app.capture_input.move_to_start(),
+        KeyCode::End => app.capture_input.move_to_end(),
+        KeyCode::Delete => app.capture_input.delete_char(),
+        KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Tab
+        | KeyCode::BackTab
+        | KeyCode::Insert
+        | KeyCode::F(_)
+        | KeyCode::Null
+        | KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => {}
+    }
+}
+
+fn handle_profile_selection_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
+    if app.profile_create_active {
+        match key_code {
+            KeyCode::Esc => app.cancel_profile_create(),
+            KeyCode::Enter => {
+                if let Err(error) = app.confirm_profile_create() {
+                    app.show_status_toast(format!("PROFILE SWITCH FAILED: {}", error));
+                }
+            }
+            KeyCode::Char(character) => app.add_profile_char(character),
+            KeyCode::Backspace => app.remove_profile_char(),
+            KeyCode::Left => app.profile_create_input.move_left(),
+            KeyCode::Right => app.profile_create_input.move_right(),
+            KeyCode::Home => app.profile_create_input.move_to_start(),
+            KeyCode::End => app.profile_create_input.move_to_end(),
+            KeyCode::Delete => app.profile_create_input.delete_char(),
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Tab
+            | KeyCode::BackTab
+            | KeyCode::Insert
+            | KeyCode::F(_)
+            | KeyCode::Null
+            | KeyCode::CapsLock
+            | KeyCode::ScrollLock
+            | KeyCode::NumLock
+            | KeyCode::PrintScreen
+            | KeyCode::Pause
+            | KeyCode::Menu
+            | KeyCode::KeypadBegin
+            | KeyCode::Media(_)
+            | KeyCode::Modifier(_) => {}
+        }
+        return Ok(());
+    }
+    match key_code {
+        KeyCode::Esc => app.close_profile_selection(),
+        KeyCode::Up => app.previous_profile(),
+        KeyCode::Down => app.next_profile(),
+        KeyCode::Enter => {
+            if let Err(error) = app.switch_to_selected_profile() {
+                app.show_status_toast(format!("PROFILE SWITCH FAILED: {}", error));
+            }
+        }
+        KeyCode::Char('n') => app.open_profile_create(),
         KeyCode::Backspace
         | KeyCode::Left
         | KeyCode::Right
@@ -916,7 +2809,6 @@ fn handle_project_detail_mode(app: &mut App, key_code: KeyCode) -> Result<()> {
         | KeyCode::BackTab
         | KeyCode::Delete
         | KeyCode::Insert
-        | KeyCode::Enter
         | KeyCode::F(_)
         | KeyCode::Char(_)
         | KeyCode::Null
@@ -0,0 +1,125 @@
+use directories::BaseDirs;
+use std::path::PathBuf;
+
+/// Used when neither `[editor] command`, $VISUAL nor $EDITOR is set
+#[cfg(windows)]
+pub const FALLBACK_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+pub const FALLBACK_EDITOR: &str = "micro";
+
+/// Terminal emulators tried after `[editor] terminal` and $TERMINAL, with the flag that
+/// separates their own arguments from the command to run
+#[cfg(target_os = "macos")]
+pub const KNOWN_TERMINALS: &[(&str, &[&str])] = &[
+    ("wezterm", &["start", "--"]),
+    ("kitty", &["-e"]),
+    ("alacritty", &["-e"]),
+];
+#[cfg(windows)]
+pub const KNOWN_TERMINALS: &[(&str, &[&str])] = &[
+    ("wt", &["new-tab", "--"]),
+    ("wezterm", &["start", "--"]),
+    ("alacritty", &["-e"]),
+    // Runs the command in a new console window; unlike `cmd /C start` nothing parses
+    // `&`, `|` or `^` in the file name
+    ("conhost", &[]),
+];
+#[cfg(not(any(target_os = "macos", windows)))]
+pub const KNOWN_TERMINALS: &[(&str, &[&str])] = &[
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("kitty", &["-e"]),
+    ("alacritty", &["-e"]),
+    ("wezterm", &["start", "--"]),
+    ("xterm", &["-e"]),
+];
+
+/// Clipboard tools that read the text to copy from stdin, tried in order
+#[cfg(target_os = "macos")]
+pub const COPY_COMMANDS: &[(&str, &[&str])] = &[("pbcopy", &[])];
+#[cfg(windows)]
+pub const COPY_COMMANDS: &[(&str, &[&str])] = &[("clip", &[])];
+#[cfg(not(any(target_os = "macos", windows)))]
+pub const COPY_COMMANDS: &[(&str, &[&str])] = &[
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// The user's home directory ($HOME, or the profile folder on Windows)
+#[must_use]
+pub fn home_dir() -> Option<PathBuf> {
+    BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf())
+}
+
+/// Per-user application config: ~/.config, ~/Library/Application Support or %APPDATA%
+#[must_use]
+pub fn user_config_dir() -> Option<PathBuf> {
+    BaseDirs::new().map(|dirs| dirs.config_dir().to_path_buf())
+}
+
+/// Expands a leading `~/` (or `~\` on Windows) to the home directory
+#[must_use]
+pub fn expand_home(path: &str) -> PathBuf {
+    let rest = path
+        .strip_prefix("~/")
+        .or_else(|| if cfg!(windows) { path.strip_prefix("~\\") } else { None });
+    match (rest, home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Program (and leading arguments) that opens a file or URL with the default application.
+/// On Windows the target must not go through `cmd`, which would run anything after a `&` in a URL.
+#[must_use]
+pub fn opener() -> (&'static str, &'static [&'static str]) {
+    if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(windows) {
+        ("rundll32", &["url.dll,FileProtocolHandler"])
+    } else {
+        ("xdg-open", &[])
+    }
+}
+
+/// Terminal.app has no "run this" flag, so macOS gets an AppleScript to open a window with `command`
+#[must_use]
+pub fn system_terminal_command(command: &[String]) -> Option<(String, Vec<String>)> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let shell_line = command
+        .iter()
+        .map(|part| format!("'{}'", part.replace('\'', "'\\''")))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let script = format!(
+        "tell application \"Terminal\" to do script \"{}\"",
+        shell_line.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    Some((
+        "osascript".to_string(),
+        vec![
+            "-e".to_string(),
+            script,
+            "-e".to_string(),
+            "tell application \"Terminal\" to activate".to_string(),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home_only_touches_a_leading_tilde() {
+        let Some(home) = home_dir() else {
+            return;
+        };
+        assert_eq!(expand_home("~/notes/today.md"), home.join("notes/today.md"));
+        assert_eq!(expand_home("/tmp/~/x"), PathBuf::from("/tmp/~/x"));
+        assert_eq!(expand_home("relative/path"), PathBuf::from("relative/path"));
+    }
+}
@@ -0,0 +1,140 @@
+use color_eyre::{Result, eyre::eyre};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Named profiles live under `profiles/<name>/` with the same layout as the
+/// project root (config.toml, config.local.toml, data/)
+const PROFILES_DIR_NAME: &str = "profiles";
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+static ACTIVE_PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Name of the active profile, `None` for the default one
+#[must_use]
+pub fn active() -> Option<String> {
+    ACTIVE_PROFILE.read().ok().and_then(|profile| profile.clone())
+}
+
+/// Display name of the active profile
+#[must_use]
+pub fn active_name() -> String {
+    active().unwrap_or_else(|| DEFAULT_PROFILE_NAME.to_string())
+}
+
+/// Switches profile for everything resolved afterwards (config, data, database).
+/// A new profile starts from the default profile's config and personalities.
+pub fn set_active(name: &str) -> Result<()> {
+    let name = name.trim();
+    let profile = if name.is_empty() || name == DEFAULT_PROFILE_NAME {
+        None
+    } else {
+        validate_name(name)?;
+        ensure_profile_dir(name)?;
+        Some(name.to_string())
+    };
+    let mut active = ACTIVE_PROFILE
+        .write()
+        .map_err(|_| eyre!("Profile state poisoned"))?;
+    *active = profile;
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let is_valid = name
+        .chars()
+        .all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_');
+    if is_valid {
+        Ok(())
+    } else {
+        Err(eyre!(
+            "Invalid profile name '{}': use letters, digits, '-' or '_'",
+            name
+        ))
+    }
+}
+
+fn profiles_dir() -> Result<PathBuf> {
+    Ok(std::env::current_dir()?.join(PROFILES_DIR_NAME))
+}
+
+/// Directory that holds config.toml and data/ for the active profile
+pub fn root_dir() -> Result<PathBuf> {
+    match active() {
+        Some(name) => Ok(profiles_dir()?.join(name)),
+        None => Ok(std::env::current_dir()?),
+    }
+}
+
+/// Per-profile `data/` directory (database, identity state, personalities)
+pub fn data_dir() -> Result<PathBuf> {
+    Ok(root_dir()?.join("data"))
+}
+
+/// SurrealDB database name inside the `kimi` namespace
+#[must_use]
+pub fn database_name() -> String {
+    active().unwrap_or_else(|| "main".to_string())
+}
+
+/// Lists the default profile plus every directory under `profiles/`
+pub fn list_profiles() -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let directory = profiles_dir()?;
+    if directory.exists() {
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|name| name.to_str())
+                && validate_name(name).is_ok()
+                && name != DEFAULT_PROFILE_NAME
+            {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE_NAME.to_string());
+    Ok(names)
+}
+
+fn ensure_profile_dir(name: &str) -> Result<()> {
+    let root = profiles_dir()?.join(name);
+    if root.exists() {
+        return Ok(());
+    }
+    let base = std::env::current_dir()?;
+    let personalities = root.join("data").join("personalities");
+    fs::create_dir_all(&personalities)?;
+
+    let base_config = base.join("config.toml");
+    if base_config.exists() {
+        fs::copy(&base_config, root.join("config.toml"))?;
+    }
+    let base_personalities = base.join("data").join("personalities");
+    if base_personalities.exists() {
+        for entry in fs::read_dir(&base_personalities)? {
+            let path = entry?.path();
+            if path.is_file()
+                && let Some(file_name) = path.file_name()
+            {
+                fs::copy(&path, personalities.join(file_name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Extracts `--profile <name>` / `--profile=<name>` from the command line
+pub fn take_profile_arg(args: &mut Vec<String>) -> Option<String> {
+    let position = args
+        .iter()
+        .position(|arg| arg == "--profile" || arg.starts_with("--profile="))?;
+    let flag = args.remove(position);
+    if let Some(name) = flag.strip_prefix("--profile=") {
+        return Some(name.to_string());
+    }
+    (position < args.len()).then(|| args.remove(position))
+}
@@ -0,0 +1,426 @@
+use crate::app::{App, ChatMessage};
+use crate::config::EmbeddingsConfig;
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::Read;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+pub const DEFAULT_PORT: u16 = 8765;
+/// Bearer token required from clients; a random one is generated when unset
+pub const TOKEN_ENV: &str = "KIMI_SERVE_TOKEN";
+const GENERATED_TOKEN_BYTES: usize = 24;
+/// Host headers accepted besides the bind address, so DNS rebinding cannot reach the API
+const LOOPBACK_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+const DEFAULT_LIST_LIMIT: usize = 20;
+const MAX_LIST_LIMIT: usize = 200;
+
+pub struct ServeOptions {
+    pub host: String,
+    pub port: u16,
+    pub token: Option<String>,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            token: std::env::var(TOKEN_ENV).ok().filter(|token| !token.is_empty()),
+        }
+    }
+}
+
+/// Status code and JSON body for one request
+struct ApiResponse {
+    status: u16,
+    body: Value,
+}
+
+impl ApiResponse {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    /// OpenAI-style error body so existing clients show the message
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": { "message": message.into(), "type": "kimi_error" } }),
+        }
+    }
+}
+
+/// Serves the HTTP API until the process is stopped. Requests are handled one at a
+/// time on a single headless `App`, so chats share its agents and storage.
+pub fn run(mut app: App, options: &ServeOptions) -> Result<()> {
+    let token = options.token.clone().unwrap_or_else(generate_token);
+    let address = format!("{}:{}", options.host, options.port);
+    let server = Server::http(&address).map_err(|error| eyre!("Cannot listen on {}: {}", address, error))?;
+    let embeddings = crate::config::Config::load()
+        .map(|config| config.embeddings)
+        .unwrap_or_default();
+
+    println!("Kimi API listening on http://{}", address);
+    println!("  POST /v1/chat/completions   GET /v1/models");
+    println!("  GET  /memories?q=&limit=    GET /conversations[?limit=]   GET /conversations/<id>");
+    if options.token.is_none() {
+        println!("No {} set; clients must send: Authorization: Bearer {}", TOKEN_ENV, token);
+    }
+    tracing::info!(%address, "api server started");
+
+    for mut request in server.incoming_requests() {
+        let response = if !is_allowed_host(&request, &options.host) {
+            ApiResponse::error(403, "Host header must name a loopback address")
+        } else if is_authorized(&request, &token) {
+            handle_request(&mut app, &embeddings, &mut request)
+        } else {
+            ApiResponse::error(401, "Missing or invalid bearer token")
+        };
+        tracing::info!(method = %request.method(), url = %request.url(), status = response.status, "api request");
+
+        let content_type = Header::from_bytes("Content-Type", "application/json").ok();
+        let mut reply = Response::from_string(response.body.to_string()).with_status_code(response.status);
+        if let Some(header) = content_type {
+            reply = reply.with_header(header);
+        }
+        if let Err(error) = request.respond(reply) {
+            tracing::warn!(%error, "failed to send api response");
+        }
+    }
+    Ok(())
+}
+
+fn generate_token() -> String {
+    use chacha20poly1305::aead::OsRng;
+    use chacha20poly1305::aead::rand_core::RngCore;
+
+    let mut bytes = [0u8; GENERATED_TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn header_value<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    header_value(request, "Authorization").and_then(|value| value.strip_prefix("Bearer ")) == Some(token)
+}
+
+fn is_allowed_host(request: &Request, bind_host: &str) -> bool {
+    header_value(request, "Host").is_some_and(|host| host_matches(host, bind_host))
+}
+
+/// True when a `Host` header (with or without port) names loopback or the bind address
+fn host_matches(host: &str, bind_host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !port.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit()) => name,
+        Some(_) | None => host,
+    };
+    let name = name.to_ascii_lowercase();
+    LOOPBACK_HOSTS.contains(&name.as_str())
+        || (!matches!(bind_host, "0.0.0.0" | "::" | "[::]") && name == bind_host.to_ascii_lowercase())
+}
+
+/// Browsers can send text/plain cross-origin without a preflight; only accept JSON
+fn is_json_request(request: &Request) -> bool {
+    header_value(request, "Content-Type").is_some_and(|value| {
+        value
+            .split(';')
+            .next()
+            .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("application/json"))
+    })
+}
+
+fn handle_request(app: &mut App, embeddings: &EmbeddingsConfig, request: &mut Request) -> ApiResponse {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+    match (request.method(), segments.as_slice()) {
+        (Method::Post, ["v1", "chat", "completions"]) if !is_json_request(request) => {
+            ApiResponse::error(415, "Content-Type must be application/json")
+        }
+        (Method::Post, ["v1", "chat", "completions"]) => match read_body(request) {
+            Ok(body) => chat_completion(app, &body),
+            Err(error) => ApiResponse::error(400, error.to_string()),
+        },
+        (Method::Get, ["v1", "models"]) => list_models(app),
+        (Method::Get, ["memories"]) => search_memories(app, embeddings, query),
+        (Method::Get, ["conversations"]) => list_conversations(app, query),
+        (Method::Get, ["conversations", id]) => load_conversation(app, id),
+        _ => ApiResponse::error(404, format!("No route for {} {}", request.method(), path)),
+    }
+}
+
+fn read_body(request: &mut Request) -> Result<String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body)?;
+    Ok(body)
+}
+
+#[derive(Deserialize)]
+struct CompletionRequest {
+    messages: Vec<CompletionMessage>,
+    #[serde(default)]
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct CompletionMessage {
+    role: String,
+    /// Either a string or a list of `{ "type": "text", "text": ... }` parts
+    content: Value,
+}
+
+impl CompletionMessage {
+    fn text(&self) -> String {
+        match &self.content {
+            Value::String(text) => text.clone(),
+            Value::Array(parts) => parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::Object(_) => String::new(),
+        }
+    }
+}
+
+/// Runs the last user message through Kimi's chat pipeline (memories, tools, search).
+/// Earlier turns become chat history; client system prompts are ignored in favour of
+/// Kimi's own personality. API chats are not saved to History.
+fn chat_completion(app: &mut App, body: &str) -> ApiResponse {
+    let request: CompletionRequest = match serde_json::from_str(body) {
+        Ok(request) => request,
+        Err(error) => return ApiResponse::error(400, format!("Invalid request body: {}", error)),
+    };
+    if request.stream {
+        return ApiResponse::error(400, "Streaming is not supported; set \"stream\": false");
+    }
+    let Some((last, earlier)) = request.messages.split_last() else {
+        return ApiResponse::error(400, "messages must not be empty");
+    };
+    if last.role != "user" {
+        return ApiResponse::error(400, "The last message must have role \"user\"");
+    }
+
+    let history = earlier
+        .iter()
+        .filter_map(|message| match message.role.as_str() {
+            "user" => Some(ChatMessage::user(message.text())),
+            "assistant" => Some(ChatMessage::assistant(message.text(), None, None)),
+            _ => None,
+        })
+        .collect();
+    app.reset_headless_session(history);
+
+    match app.ask_headless(&last.text(), None) {
+        Ok(answer) => {
+            let created = chrono::Utc::now().timestamp();
+            ApiResponse::ok(json!({
+                "id": format!("chatcmpl-kimi-{}", created),
+                "object": "chat.completion",
+                "created": created,
+                "model": answer.model,
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": answer.answer },
+                    "finish_reason": "stop",
+                }],
+                "kimi": answer.to_json(),
+            }))
+        }
+        Err(error) => ApiResponse::error(502, error.to_string()),
+    }
+}
+
+fn list_models(app: &App) -> ApiResponse {
+    let models: Vec<Value> = app
+        .current_agent
+        .iter()
+        .map(|agent| json!({ "id": agent.model, "object": "model", "owned_by": "kimi" }))
+        .collect();
+    ApiResponse::ok(json!({ "object": "list", "data": models }))
+}
+
+fn search_memories(app: &mut App, embeddings: &EmbeddingsConfig, query: &str) -> ApiResponse {
+    let search = query_param(query, "q").unwrap_or_default();
+    let limit = list_limit(query, embeddings.max_retrieved_messages);
+    let expansions = if embeddings.query_rewrite
+        && !search.trim().is_empty()
+        && let Some(manager) = &app.agent_manager
+    {
+        let rewriters: Vec<&crate::agents::Agent> = manager
+            .get_agent("routing")
+            .into_iter()
+            .chain(app.current_agent.as_ref())
+            .collect();
+        crate::services::retrieval::expand_query(&search, &rewriters, manager)
+    } else {
+        Vec::new()
+    };
+    app.ensure_storage();
+    let (storage, runtime) = match app.storage_with_runtime() {
+        Ok(pair) => pair,
+        Err(error) => return ApiResponse::error(503, error.to_string()),
+    };
+    let results = runtime.block_on(async {
+        if search.trim().is_empty() {
+            crate::services::retrieval::build_meta_recall_results(storage, limit).await
+        } else {
+            crate::services::retrieval::retrieve_with_expansions(
+                storage,
+                &search,
+                &expansions,
+                limit,
+                embeddings.similarity_threshold,
+            )
+            .await
+        }
+    });
+    match results {
+        Ok(messages) => {
+            let memories: Vec<Value> = messages
+                .iter()
+                .map(|message| {
+                    json!({
+                        "content": message.content,
+                        "role": message.role,
+                        "timestamp": message.timestamp,
+                        "score": message.score,
+                        "source": format!("{:?}", message.source).to_lowercase(),
+                    })
+                })
+                .collect();
+            ApiResponse::ok(json!({ "query": search, "memories": memories }))
+        }
+        Err(error) => ApiResponse::error(500, error.to_string()),
+    }
+}
+
+fn list_conversations(app: &mut App, query: &str) -> ApiResponse {
+    let limit = list_limit(query, DEFAULT_LIST_LIMIT);
+    app.ensure_storage();
+    let (storage, runtime) = match app.storage_with_runtime() {
+        Ok(pair) => pair,
+        Err(error) => return ApiResponse::error(503, error.to_string()),
+    };
+    match runtime.block_on(storage.load_conversations_with_limit(limit)) {
+        Ok(conversations) => {
+            let items: Vec<Value> = conversations
+                .iter()
+                .map(|conversation| {
+                    json!({
+                        "id": conversation.id,
+                        "title": conversation.title(),
+                        "agent_name": conversation.agent_name,
+                        "summary": conversation.summary,
+                        "created_at": conversation.created_at,
+                    })
+                })
+                .collect();
+            ApiResponse::ok(json!({ "conversations": items }))
+        }
+        Err(error) => ApiResponse::error(500, error.to_string()),
+    }
+}
+
+fn load_conversation(app: &mut App, id: &str) -> ApiResponse {
+    let id = decode_component(id);
+    app.ensure_storage();
+    let (storage, runtime) = match app.storage_with_runtime() {
+        Ok(pair) => pair,
+        Err(error) => return ApiResponse::error(503, error.to_string()),
+    };
+    match runtime.block_on(storage.load_conversation(&id)) {
+        Ok((agent_name, messages)) => ApiResponse::ok(json!({
+            "id": id,
+            "agent_name": agent_name,
+            "messages": messages,
+        })),
+        Err(error) => ApiResponse::error(404, error.to_string()),
+    }
+}
+
+/// `limit` query parameter, clamped to `MAX_LIST_LIMIT`
+fn list_limit(query: &str, default: usize) -> usize {
+    query_param(query, "limit")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(default)
+        .clamp(1, MAX_LIST_LIMIT)
+}
+
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (key == name).then(|| decode_component(value))
+    })
+}
+
+/// Decodes `+` and `%XX` escapes in a URL component
+fn decode_component(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let high = input.next();
+                let low = input.next();
+                let decoded = high
+                    .zip(low)
+                    .and_then(|(high, low)| std::str::from_utf8(&[high, low]).ok().map(str::to_string))
+                    .and_then(|hex| u8::from_str_radix(&hex, 16).ok());
+                match decoded {
+                    Some(decoded) => bytes.push(decoded),
+                    None => {
+                        bytes.push(b'%');
+                        bytes.extend(high);
+                        bytes.extend(low);
+                    }
+                }
+            }
+            other => bytes.push(other),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_query_parameters() {
+        let query = "q=where+did+I+park%3F&limit=500";
+        assert_eq!(query_param(query, "q").as_deref(), Some("where did I park?"));
+        assert_eq!(list_limit(query, 10), MAX_LIST_LIMIT);
+        assert_eq!(list_limit("", 10), 10);
+        assert_eq!(query_param(query, "missing"), None);
+        assert_eq!(decode_component("100%"), "100%");
+        assert_eq!(decode_component("caf%C3%A9"), "café");
+    }
+
+    #[test]
+    fn test_host_header_must_be_loopback() {
+        assert!(host_matches("127.0.0.1:8765", DEFAULT_HOST));
+        assert!(host_matches("LOCALHOST:8765", DEFAULT_HOST));
+        assert!(host_matches("[::1]:8765", DEFAULT_HOST));
+        assert!(host_matches("localhost", DEFAULT_HOST));
+        assert!(!host_matches("attacker.example:8765", DEFAULT_HOST));
+        assert!(!host_matches("127.0.0.1.attacker.example", DEFAULT_HOST));
+        assert!(host_matches("192.168.1.5:8765", "192.168.1.5"));
+        assert!(!host_matches("192.168.1.5:8765", "0.0.0.0"));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::config::Config;
+use crate::storage::{self, StorageManager};
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MANIFEST_FILE_NAME: &str = "manifest.toml";
+const DATABASE_FILE_NAME: &str = "database.surql";
+const CONFIG_FILE_NAME: &str = "config.toml";
+const IDENTITY_FILE_NAME: &str = "identity-state.json";
+const KEY_FILE_NAME: &str = "encryption.json";
+const PERSONALITIES_DIR_NAME: &str = "personalities";
+/// Layout of the archive itself; the database layout is tracked by `storage::SCHEMA_VERSION`
+const ARCHIVE_FORMAT: u32 = 1;
+
+/// Describes what an archive contains and which kimi wrote it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format: u32,
+    pub schema_version: u32,
+    pub created_at: String,
+    pub profile: String,
+    pub encrypted: bool,
+    pub messages: usize,
+    pub personalities: Vec<String>,
+}
+
+/// What a backup or restore touched
+pub struct ArchiveSummary {
+    pub path: PathBuf,
+    pub manifest: ArchiveManifest,
+}
+
+/// Refuses archives written by a newer kimi; older ones are fine because `init_db`
+/// defines any tables and fields added since
+pub fn check_manifest(manifest: &ArchiveManifest) -> Result<()> {
+    if manifest.format > ARCHIVE_FORMAT {
+        return Err(eyre!(
+            "Archive format {} is newer than supported ({}); update kimi first",
+            manifest.format,
+            ARCHIVE_FORMAT
+        ));
+    }
+    if manifest.schema_version > storage::SCHEMA_VERSION {
+        return Err(eyre!(
+            "Archive schema {} is newer than this build understands ({}); update kimi first",
+            manifest.schema_version,
+            storage::SCHEMA_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// `kimi-backup-20261017-1430.tar.gz` inside `target` when it is a directory,
+/// otherwise `target` with `.tar.gz` added if missing
+#[must_use]
+pub fn archive_path(target: &Path, now: chrono::NaiveDateTime) -> PathBuf {
+    if target.is_dir() {
+        return target.join(format!("kimi-backup-{}.tar.gz", now.format("%Y%m%d-%H%M")));
+    }
+    let name = target.to_string_lossy();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        target.to_path_buf()
+    } else {
+        PathBuf::from(format!("{}.tar.gz", name))
+    }
+}
+
+fn scratch_dir(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "{}-{}",
+        prefix,
+        chrono::Local::now().format("%Y%m%d-%H%M%S%f")
+    ))
+}
+
+fn run_tar(args: &[&std::ffi::OsStr], failure: String) -> Result<()> {
+    let status = Command::new("tar")
+        .args(args)
+        .status()
+        .map_err(|error| eyre!("tar not available ({})", error))?;
+    if status.success() { Ok(()) } else { Err(eyre!(failure)) }
+}
+
+/// Writes messages, conversations and every other table, identity state, personalities
+/// and config (without API keys) into one `.tar.gz`
+pub async fn create_archive(storage: &StorageManager, target: &Path) -> Result<ArchiveSummary> {
+    let path = archive_path(target, chrono::Local::now().naive_local());
+    let directory = scratch_dir("kimi-backup");
+    fs::create_dir_all(directory.join(PERSONALITIES_DIR_NAME))?;
+    let result = write_archive_contents(storage, &directory).await.and_then(|manifest| {
+        run_tar(
+            &["-czf".as_ref(), path.as_os_str(), "-C".as_ref(), directory.as_os_str(), ".".as_ref()],
+            format!("tar failed to create {}", path.display()),
+        )?;
+        Ok(manifest)
+    });
+    let _ = fs::remove_dir_all(&directory);
+    Ok(ArchiveSummary {
+        path,
+        manifest: result?,
+    })
+}
+
+async fn write_archive_contents(storage: &StorageManager, directory: &Path) -> Result<ArchiveManifest> {
+    storage.export_database(&directory.join(DATABASE_FILE_NAME)).await?;
+    let (messages, _) = storage.get_embedding_stats().await?;
+
+    let config = Config::load()?.redacted_for_project();
+    fs::write(directory.join(CONFIG_FILE_NAME), toml::to_string_pretty(&config)?)?;
+
+    let identity = crate::services::identity::read_identity_state()?;
+    fs::write(directory.join(IDENTITY_FILE_NAME), serde_json::to_string_pretty(&identity)?)?;
+
+    let key_file = crate::services::encryption::key_file_path()?;
+    if key_file.exists() {
+        fs::copy(&key_file, directory.join(KEY_FILE_NAME))?;
+    }
+
+    let mut personalities = Vec::new();
+    for entry in fs::read_dir(crate::services::personality::personality_dir()?)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        fs::copy(&path, directory.join(PERSONALITIES_DIR_NAME).join(file_name))?;
+        personalities.push(file_name.to_string());
+    }
+    personalities.sort();
+
+    let manifest = ArchiveManifest {
+        format: ARCHIVE_FORMAT,
+        schema_version: storage::SCHEMA_VERSION,
+        created_at: chrono::Local::now().to_rfc3339(),
+        profile: crate::profile::active_name(),
+        encrypted: key_file.exists(),
+        messages,
+        personalities,
+    };
+    fs::write(directory.join(MANIFEST_FILE_NAME), toml::to_string_pretty(&manifest)?)?;
+    Ok(manifest)
+}
+
+/// Rebuilds the database from an archive made by `create_archive` and puts identity state,
+/// personalities and config back. A database that already holds messages is only
+/// replaced with `force`; API keys and machine paths already set here are kept.
+pub async fn restore_archive(storage: &StorageManager, source: &Path, force: bool) -> Result<ArchiveSummary> {
+    let directory = scratch_dir("kimi-restore");
+    fs::create_dir_all(&directory)?;
+    let result = async {
+        run_tar(
+            &["-xzf".as_ref(), source.as_os_str(), "-C".as_ref(), directory.as_os_str()],
+            format!("tar failed to extract {}", source.display()),
+        )?;
+        restore_archive_contents(storage, &directory, source, force).await
+    }
+    .await;
+    let _ = fs::remove_dir_all(&directory);
+    Ok(ArchiveSummary {
+        path: source.to_path_buf(),
+        manifest: result?,
+    })
+}
+
+async fn restore_archive_contents(
+    storage: &StorageManager,
+    directory: &Path,
+    source: &Path,
+    force: bool,
+) -> Result<ArchiveManifest> {
+    let manifest: ArchiveManifest = toml::from_str(
+        &fs::read_to_string(directory.join(MANIFEST_FILE_NAME))
+            .map_err(|_| eyre!("{} is not a kimi backup (missing manifest)", source.display()))?,
+    )?;
+    check_manifest(&manifest)?;
+
+    let (existing_messages, _) = storage.get_embedding_stats().await?;
+    if existing_messages > 0 && !force {
+        return Err(eyre!(
+            "This database already has {} messages; pass --force to replace them",
+            existing_messages
+        ));
+    }
+
+    // Encrypted rows only open with the key file they were written under
+    let archived_key = directory.join(KEY_FILE_NAME);
+    let local_key = crate::services::encryption::key_file_path()?;
+    if archived_key.exists() {
+        if local_key.exists() && fs::read(&local_key)? != fs::read(&archived_key)? {
+            return Err(eyre!(
+                "{} belongs to a different passphrase; move it aside to restore this encrypted backup",
+                local_key.display()
+            ));
+        }
+        if let Some(parent) = local_key.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&archived_key, &local_key)?;
+    }
+
+    storage.replace_database(&directory.join(DATABASE_FILE_NAME)).await?;
+
+    let identity_file = directory.join(IDENTITY_FILE_NAME);
+    if identity_file.exists() {
+        let identity = serde_json::from_str(&fs::read_to_string(identity_file)?)?;
+        crate::services::identity::write_identity_state(&identity)?;
+    }
+
+    let personality_dir = crate::services::personality::personality_dir()?;
+    for file_name in &manifest.personalities {
+        // Only plain file names; never follow paths out of the personalities directory
+        if Path::new(file_name).file_name().and_then(|name| name.to_str()) != Some(file_name) {
+            continue;
+        }
+        let archived = directory.join(PERSONALITIES_DIR_NAME).join(file_name);
+        if archived.exists() {
+            fs::copy(&archived, personality_dir.join(file_name))?;
+        }
+    }
+
+    let mut config: Config = toml::from_str(&fs::read_to_string(directory.join(CONFIG_FILE_NAME))?)?;
+    if let Ok(current) = Config::load() {
+        config.keep_machine_settings_from(&current);
+    }
+    config.save()?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(format: u32, schema_version: u32) -> ArchiveManifest {
+        ArchiveManifest {
+            format,
+            schema_version,
+            created_at: String::new(),
+            profile: "main".to_string(),
+            encrypted: false,
+            messages: 0,
+            personalities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_accepts_current_and_older_archives_only() {
+        assert!(check_manifest(&manifest(ARCHIVE_FORMAT, storage::SCHEMA_VERSION)).is_ok());
+        assert!(check_manifest(&manifest(ARCHIVE_FORMAT, 0)).is_ok());
+        assert!(check_manifest(&manifest(ARCHIVE_FORMAT + 1, storage::SCHEMA_VERSION)).is_err());
+        assert!(check_manifest(&manifest(ARCHIVE_FORMAT, storage::SCHEMA_VERSION + 1)).is_err());
+    }
+
+    #[test]
+    fn test_archive_path_adds_the_tarball_extension() {
+        let Some(now) = chrono::NaiveDate::from_ymd_opt(2026, 10, 17).and_then(|day| day.and_hms_opt(14, 30, 0))
+        else {
+            panic!("bad test date");
+        };
+        assert_eq!(archive_path(Path::new("backup"), now), PathBuf::from("backup.tar.gz"));
+        assert_eq!(archive_path(Path::new("old.tgz"), now), PathBuf::from("old.tgz"));
+        assert_eq!(
+            archive_path(&std::env::temp_dir(), now),
+            std::env::temp_dir().join("kimi-backup-20261017-1430.tar.gz")
+        );
+    }
+
+    #[test]
+    fn test_archived_config_has_no_api_keys() {
+        let mut config = Config::default();
+        config.venice.api_key = "venice-secret".to_string();
+        config.images.api_key = "images-secret".to_string();
+        let archived = toml::to_string_pretty(&config.redacted_for_project()).unwrap_or_default();
+        assert!(!archived.is_empty());
+        assert!(!archived.contains("venice-secret"));
+        assert!(!archived.contains("images-secret"));
+        assert!(config.redacted_for_bundle().images.api_key.is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+use color_eyre::Result;
+use std::fs;
+use std::path::PathBuf;
+
+const ATTACHMENTS_DIR_NAME: &str = "attachments";
+
+/// Where pasted images without a file of their own are kept (data/attachments)
+pub fn attachments_dir() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(ATTACHMENTS_DIR_NAME))
+}
+
+/// Content-addressed file name, so pasting the same screenshot twice stores it once
+fn blob_name(bytes: &[u8]) -> String {
+    let hash = blake3::hash(bytes).to_hex();
+    format!("{}.png", hash.get(..32).unwrap_or(hash.as_str()))
+}
+
+/// Saves PNG bytes (e.g. a clipboard image) and returns the path stored with the message
+pub fn store_blob(png_bytes: &[u8]) -> Result<PathBuf> {
+    let directory = attachments_dir()?;
+    fs::create_dir_all(&directory)?;
+    let path = directory.join(blob_name(png_bytes));
+    if !path.exists() {
+        fs::write(&path, png_bytes)?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_names_follow_the_content() {
+        assert_eq!(blob_name(b"screenshot"), blob_name(b"screenshot"));
+        assert_ne!(blob_name(b"screenshot"), blob_name(b"another"));
+        assert!(blob_name(b"screenshot").ends_with(".png"));
+        assert_eq!(blob_name(b"screenshot").len(), 36);
+    }
+}
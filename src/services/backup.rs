@@ -0,0 +1,125 @@
+use crate::config::BackupConfig;
+use crate::services::archive::{self, ArchiveSummary};
+use crate::storage::StorageManager;
+use color_eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const BACKUP_DIR_NAME: &str = "backups";
+/// Prefix `archive::archive_path` gives snapshots written into a directory
+const BACKUP_FILE_PREFIX: &str = "kimi-backup-";
+/// How often the in-app scheduler checks whether a snapshot is due
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Where scheduled snapshots go: `[backup] directory`, or data/backups
+pub fn backup_dir(config: &BackupConfig) -> Result<PathBuf> {
+    let directory = config.directory.trim();
+    if directory.is_empty() {
+        return Ok(crate::profile::data_dir()?.join(BACKUP_DIR_NAME));
+    }
+    Ok(crate::platform::expand_home(directory))
+}
+
+fn is_snapshot_name(name: &str) -> bool {
+    name.starts_with(BACKUP_FILE_PREFIX) && (name.ends_with(".tar.gz") || name.ends_with(".tgz"))
+}
+
+/// Snapshots in `directory`, oldest first (the timestamped names sort by date)
+#[must_use]
+pub fn list_snapshots(directory: &Path) -> Vec<PathBuf> {
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(is_snapshot_name)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    snapshots.sort();
+    snapshots
+}
+
+/// The snapshots to delete so only the newest `keep` remain
+#[must_use]
+pub fn snapshots_to_remove(snapshots: &[PathBuf], keep: usize) -> &[PathBuf] {
+    let excess = snapshots.len().saturating_sub(keep.max(1));
+    snapshots.get(..excess).unwrap_or_default()
+}
+
+/// When the newest snapshot in `directory` was written
+#[must_use]
+pub fn last_backup_time(directory: &Path) -> Option<SystemTime> {
+    list_snapshots(directory)
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .max()
+}
+
+/// True when there is no snapshot yet or the newest is older than `interval_hours`
+#[must_use]
+pub fn is_due(directory: &Path, interval_hours: u64) -> bool {
+    let interval = Duration::from_secs(interval_hours.max(1).saturating_mul(3600));
+    last_backup_time(directory)
+        .and_then(|at| at.elapsed().ok())
+        .is_none_or(|age| age >= interval)
+}
+
+/// "3 h ago", "2 days ago" for the status overlay
+#[must_use]
+pub fn format_age(age: Duration) -> String {
+    let minutes = age.as_secs() / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        1..60 => format!("{} min ago", minutes),
+        60..1440 => format!("{} h ago", minutes / 60),
+        _ if minutes / 1440 == 1 => "1 day ago".to_string(),
+        _ => format!("{} days ago", minutes / 1440),
+    }
+}
+
+/// Writes one snapshot into the configured directory and deletes the oldest beyond `keep`
+pub async fn run_scheduled_backup(storage: &StorageManager, config: &BackupConfig) -> Result<ArchiveSummary> {
+    let directory = backup_dir(config)?;
+    fs::create_dir_all(&directory)?;
+    let summary = archive::create_archive(storage, &directory).await?;
+    for old in snapshots_to_remove(&list_snapshots(&directory), config.keep) {
+        if let Err(error) = fs::remove_file(old) {
+            tracing::warn!(%error, path = %old.display(), "could not remove old backup");
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_keeps_the_newest() {
+        let snapshots: Vec<PathBuf> = ["20261001-0300", "20261002-0300", "20261003-0300"]
+            .iter()
+            .map(|stamp| PathBuf::from(format!("kimi-backup-{}.tar.gz", stamp)))
+            .collect();
+        assert_eq!(Some(snapshots_to_remove(&snapshots, 2)), snapshots.get(..1));
+        assert!(snapshots_to_remove(&snapshots, 7).is_empty());
+        // keep = 0 would delete the snapshot just written
+        assert_eq!(snapshots_to_remove(&snapshots, 0).len(), 2);
+        assert!(is_snapshot_name("kimi-backup-20261003-0300.tar.gz"));
+        assert!(!is_snapshot_name("notes.tar.gz"));
+    }
+
+    #[test]
+    fn test_age_reads_naturally() {
+        assert_eq!(format_age(Duration::from_secs(20)), "just now");
+        assert_eq!(format_age(Duration::from_secs(5 * 60)), "5 min ago");
+        assert_eq!(format_age(Duration::from_secs(3 * 3600)), "3 h ago");
+        assert_eq!(format_age(Duration::from_secs(30 * 3600)), "1 day ago");
+        assert_eq!(format_age(Duration::from_secs(50 * 3600)), "2 days ago");
+    }
+}
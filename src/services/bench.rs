@@ -0,0 +1,255 @@
+use crate::agents::{Agent, AgentManager, ChatMessage};
+use crate::app::ModelSource;
+use color_eyre::{Result, eyre::eyre};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// A single prompt in the benchmark suite
+struct BenchCase {
+    name: &'static str,
+    system: &'static str,
+    prompt: &'static str,
+    /// What a good answer must contain, shown to the judge
+    rubric: &'static str,
+}
+
+const BENCH_CASES: [BenchCase; 3] = [
+    BenchCase {
+        name: "reasoning",
+        system: "You are a helpful assistant. Think carefully, then answer concisely.",
+        prompt: "A train leaves at 14:40 and the trip takes 2 hours 35 minutes. \
+It arrives 20 minutes late. At what time does it arrive? Explain briefly.",
+        rubric: "The correct arrival time is 17:35. Reasoning should add 2h35m to 14:40 (17:15) and then 20 minutes.",
+    },
+    BenchCase {
+        name: "summarization",
+        system: "You are a helpful assistant that writes tight summaries.",
+        prompt: "Summarize in at most two sentences:\n\
+The city council met on Tuesday to discuss the new tram line. After three hours of debate, \
+members voted 7 to 4 to approve the route through the old town, despite objections from \
+shop owners worried about construction noise. Work begins in March and should take two years. \
+The mayor promised compensation for affected businesses.",
+        rubric: "Mentions approval of the tram route (7-4 vote), old town, construction starting in March for two years, \
+and compensation for businesses. At most two sentences, no invented facts.",
+    },
+    BenchCase {
+        name: "recall",
+        system: "You are a helpful assistant. Use the provided memory context when answering.\n\
+--- Memory context ---\n\
+[2026-03-02] User: My sister Jana is allergic to peanuts.\n\
+[2026-03-05] User: I'm planning Jana's birthday dinner on the 14th.\n\
+[2026-03-06] User: I usually bake a walnut cake for family events.",
+        prompt: "What should I keep in mind when baking the cake for the birthday dinner?",
+        rubric: "Must connect the birthday dinner to Jana and warn about her peanut allergy \
+(e.g. avoid peanuts / cross-contamination). Mentioning the walnut cake is a plus. No invented facts.",
+    },
+];
+
+/// Result of running one case against one model
+pub struct CaseResult {
+    pub case: &'static str,
+    pub latency_ms: u128,
+    pub score: Option<u8>,
+    pub error: Option<String>,
+}
+
+/// All case results for one model
+pub struct ModelResult {
+    pub model: String,
+    pub cases: Vec<CaseResult>,
+}
+
+impl ModelResult {
+    #[must_use]
+    pub fn average_latency_ms(&self) -> u128 {
+        let finished: Vec<u128> = self
+            .cases
+            .iter()
+            .filter(|case| case.error.is_none())
+            .map(|case| case.latency_ms)
+            .collect();
+        if finished.is_empty() {
+            return 0;
+        }
+        finished.iter().sum::<u128>() / finished.len() as u128
+    }
+
+    #[must_use]
+    pub fn total_score(&self) -> u32 {
+        self.cases
+            .iter()
+            .filter_map(|case| case.score)
+            .map(u32::from)
+            .sum()
+    }
+}
+
+fn local_agent(name: &str, model: &str, system_prompt: &str) -> Agent {
+    Agent {
+        name: name.to_string(),
+        model: model.to_string(),
+        system_prompt: system_prompt.to_string(),
+        model_source: ModelSource::Ollama,
+        num_gpu: None,
+        keep_alive: None,
+        verify: crate::config::Verification::Off,
+    }
+}
+
+/// Runs the fixed suite against every model, scoring answers with the judge model
+pub fn run_benchmark(
+    manager: &AgentManager,
+    models: &[String],
+    judge_model: &str,
+    mut on_progress: impl FnMut(&str),
+) -> Result<Vec<ModelResult>> {
+    if models.is_empty() {
+        return Err(eyre!("No models to benchmark"));
+    }
+    let judge = local_agent("judge", judge_model, "You are a strict grader.");
+
+    let mut results = Vec::new();
+    for model in models {
+        let agent = local_agent("bench", model, "");
+        let mut cases = Vec::new();
+        for case in &BENCH_CASES {
+            on_progress(&format!("{} · {}", model, case.name));
+            let messages = vec![ChatMessage::system(case.system), ChatMessage::user(case.prompt)];
+            let started = Instant::now();
+            let answer = manager.chat(&agent, &messages);
+            let latency_ms = started.elapsed().as_millis();
+            match answer {
+                Ok(answer) => {
+                    let score = judge_answer(manager, &judge, case, &answer);
+                    cases.push(CaseResult {
+                        case: case.name,
+                        latency_ms,
+                        score,
+                        error: None,
+                    });
+                }
+                Err(error) => cases.push(CaseResult {
+                    case: case.name,
+                    latency_ms,
+                    score: None,
+                    error: Some(error.to_string()),
+                }),
+            }
+        }
+        results.push(ModelResult {
+            model: model.clone(),
+            cases,
+        });
+    }
+    Ok(results)
+}
+
+fn judge_answer(manager: &AgentManager, judge: &Agent, case: &BenchCase, answer: &str) -> Option<u8> {
+    let prompt = format!(
+        "Grade the answer on a scale from 0 to 10.\n\
+Task: {}\n\
+Rubric: {}\n\
+Answer:\n{}\n\n\
+Reply with only the integer score.",
+        case.prompt, case.rubric, answer
+    );
+    let messages = vec![ChatMessage::system(&judge.system_prompt), ChatMessage::user(prompt)];
+    manager
+        .chat(judge, &messages)
+        .ok()
+        .and_then(|reply| parse_judge_score(&reply))
+}
+
+/// Extracts the first integer 0–10 from a judge reply
+fn parse_judge_score(reply: &str) -> Option<u8> {
+    reply
+        .split(|character: char| !character.is_ascii_digit())
+        .filter(|token| !token.is_empty())
+        .find_map(|token| token.parse::<u8>().ok().filter(|score| *score <= 10))
+}
+
+/// Renders results as a markdown comparison table
+#[must_use]
+pub fn format_results_table(results: &[ModelResult], judge_model: &str) -> String {
+    let mut header = "| Model |".to_string();
+    let mut divider = "|---|".to_string();
+    for case in &BENCH_CASES {
+        header.push_str(&format!(" {} |", case.name));
+        divider.push_str("---|");
+    }
+    header.push_str(" Score | Avg latency |");
+    divider.push_str("---|---|");
+
+    let mut lines = vec![
+        format!("# Kimi bench · {}", chrono::Local::now().format("%Y-%m-%d %H:%M")),
+        String::new(),
+        format!("Judge: `{}` · cells show score/10 and latency", judge_model),
+        String::new(),
+        header,
+        divider,
+    ];
+
+    let mut ranked: Vec<&ModelResult> = results.iter().collect();
+    ranked.sort_by_key(|result| std::cmp::Reverse(result.total_score()));
+    for result in ranked {
+        let mut row = format!("| {} |", result.model);
+        for case in &result.cases {
+            let cell = match (&case.error, case.score) {
+                (Some(_), _) => "error".to_string(),
+                (None, Some(score)) => format!("{}/10 · {:.1}s", score, case.latency_ms as f64 / 1000.0),
+                (None, None) => format!("?/10 · {:.1}s", case.latency_ms as f64 / 1000.0),
+            };
+            row.push_str(&format!(" {} |", cell));
+        }
+        row.push_str(&format!(
+            " {}/{} | {:.1}s |",
+            result.total_score(),
+            BENCH_CASES.len() * 10,
+            result.average_latency_ms() as f64 / 1000.0
+        ));
+        lines.push(row);
+    }
+
+    let errors: Vec<String> = results
+        .iter()
+        .flat_map(|result| {
+            result.cases.iter().filter_map(|case| {
+                case.error
+                    .as_ref()
+                    .map(|error| format!("- {} · {}: {}", result.model, case.case, error))
+            })
+        })
+        .collect();
+    if !errors.is_empty() {
+        lines.push(String::new());
+        lines.push("Errors:".to_string());
+        lines.extend(errors);
+    }
+
+    lines.join("\n")
+}
+
+/// Writes the report to `data/bench/bench-<timestamp>.md` and returns the path
+pub fn write_report(report: &str) -> Result<PathBuf> {
+    let directory = crate::profile::data_dir()?.join("bench");
+    std::fs::create_dir_all(&directory)?;
+    let path = directory.join(format!(
+        "bench-{}.md",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_judge_score() {
+        assert_eq!(parse_judge_score("8"), Some(8));
+        assert_eq!(parse_judge_score("Score: 10/10"), Some(10));
+        assert_eq!(parse_judge_score("I'd give it 42... no, 7"), Some(7));
+        assert_eq!(parse_judge_score("excellent"), None);
+    }
+}
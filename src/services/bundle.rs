@@ -0,0 +1,151 @@
+use crate::config::Config;
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+const MANIFEST_FILE_NAME: &str = "manifest.toml";
+const PERSONALITIES_DIR_NAME: &str = "personalities";
+const BUNDLE_VERSION: u32 = 1;
+
+/// Describes what a bundle contains
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleManifest {
+    version: u32,
+    created_at: String,
+    personalities: Vec<String>,
+}
+
+/// What an export or import touched
+pub struct BundleSummary {
+    pub path: PathBuf,
+    pub personalities: Vec<String>,
+}
+
+fn is_tarball(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+fn scratch_dir(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "{}-{}",
+        prefix,
+        chrono::Local::now().format("%Y%m%d-%H%M%S%f")
+    ))
+}
+
+/// Writes config (without secrets or machine-specific paths) and all personalities
+/// into a directory, or a `.tar.gz` when the target ends with it. Conversations are never included.
+pub fn export_bundle(target: &Path) -> Result<BundleSummary> {
+    let directory = if is_tarball(target) {
+        scratch_dir("kimi-bundle")
+    } else {
+        target.to_path_buf()
+    };
+    fs::create_dir_all(directory.join(PERSONALITIES_DIR_NAME))?;
+
+    let config = Config::load()?.redacted_for_bundle();
+    fs::write(directory.join(CONFIG_FILE_NAME), toml::to_string_pretty(&config)?)?;
+
+    let mut personalities = Vec::new();
+    for entry in fs::read_dir(crate::services::personality::personality_dir()?)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        fs::copy(&path, directory.join(PERSONALITIES_DIR_NAME).join(file_name))?;
+        personalities.push(file_name.to_string());
+    }
+    personalities.sort();
+
+    let manifest = BundleManifest {
+        version: BUNDLE_VERSION,
+        created_at: chrono::Local::now().to_rfc3339(),
+        personalities: personalities.clone(),
+    };
+    fs::write(directory.join(MANIFEST_FILE_NAME), toml::to_string_pretty(&manifest)?)?;
+
+    if is_tarball(target) {
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(target)
+            .arg("-C")
+            .arg(&directory)
+            .arg(".")
+            .status()
+            .map_err(|error| eyre!("tar not available ({})", error))?;
+        let _ = fs::remove_dir_all(&directory);
+        if !status.success() {
+            return Err(eyre!("tar failed to create {}", target.display()));
+        }
+    }
+
+    Ok(BundleSummary {
+        path: target.to_path_buf(),
+        personalities,
+    })
+}
+
+/// Applies a bundle created by `export_bundle`: replaces config.toml (local secrets,
+/// vault path and calendar sources are kept) and copies personalities into place.
+pub fn import_bundle(source: &Path) -> Result<BundleSummary> {
+    let directory = if is_tarball(source) {
+        let directory = scratch_dir("kimi-bundle-import");
+        fs::create_dir_all(&directory)?;
+        let status = Command::new("tar")
+            .arg("-xzf")
+            .arg(source)
+            .arg("-C")
+            .arg(&directory)
+            .status()
+            .map_err(|error| eyre!("tar not available ({})", error))?;
+        if !status.success() {
+            return Err(eyre!("tar failed to extract {}", source.display()));
+        }
+        directory
+    } else {
+        source.to_path_buf()
+    };
+
+    let manifest: BundleManifest = toml::from_str(
+        &fs::read_to_string(directory.join(MANIFEST_FILE_NAME))
+            .map_err(|_| eyre!("{} is not a kimi bundle (missing manifest)", source.display()))?,
+    )?;
+    if manifest.version > BUNDLE_VERSION {
+        return Err(eyre!("Bundle version {} is newer than supported", manifest.version));
+    }
+
+    let mut config: Config = toml::from_str(&fs::read_to_string(directory.join(CONFIG_FILE_NAME))?)?;
+    if let Ok(current) = Config::load() {
+        config.keep_machine_settings_from(&current);
+    }
+    config.save()?;
+
+    let personality_dir = crate::services::personality::personality_dir()?;
+    let mut personalities = Vec::new();
+    for file_name in &manifest.personalities {
+        // Only plain file names; never follow paths out of the personalities directory
+        if Path::new(file_name).file_name().and_then(|name| name.to_str()) != Some(file_name) {
+            continue;
+        }
+        let bundled = directory.join(PERSONALITIES_DIR_NAME).join(file_name);
+        if bundled.exists() {
+            fs::copy(&bundled, personality_dir.join(file_name))?;
+            personalities.push(file_name.clone());
+        }
+    }
+
+    if is_tarball(source) {
+        let _ = fs::remove_dir_all(&directory);
+    }
+    Ok(BundleSummary {
+        path: source.to_path_buf(),
+        personalities,
+    })
+}
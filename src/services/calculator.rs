@@ -0,0 +1,429 @@
+use std::collections::HashMap;
+
+/// Phrasings stripped before a message is read as a calculation
+const QUESTION_PREFIXES: [&str; 10] = [
+    "what's ", "whats ", "what is ", "how much is ", "how much are ", "calculate ", "calc ", "compute ",
+    "convert ", "=",
+];
+
+/// Unit names per dimension with their factor to the dimension's base unit
+/// (metre, kilogram, litre, metre per second, byte)
+const UNITS: [(Dimension, &[&str], f64); 27] = [
+    (Dimension::Length, &["mm", "millimeter", "millimeters", "millimetre", "millimetres"], 0.001),
+    (Dimension::Length, &["cm", "centimeter", "centimeters", "centimetre", "centimetres"], 0.01),
+    (Dimension::Length, &["m", "meter", "meters", "metre", "metres"], 1.0),
+    (Dimension::Length, &["km", "kilometer", "kilometers", "kilometre", "kilometres"], 1000.0),
+    (Dimension::Length, &["in", "inch", "inches"], 0.0254),
+    (Dimension::Length, &["ft", "foot", "feet"], 0.3048),
+    (Dimension::Length, &["yd", "yard", "yards"], 0.9144),
+    (Dimension::Length, &["mi", "mile", "miles"], 1609.344),
+    (Dimension::Mass, &["mg", "milligram", "milligrams"], 0.000_001),
+    (Dimension::Mass, &["g", "gram", "grams"], 0.001),
+    (Dimension::Mass, &["kg", "kilogram", "kilograms", "kilo", "kilos"], 1.0),
+    (Dimension::Mass, &["t", "tonne", "tonnes"], 1000.0),
+    (Dimension::Mass, &["oz", "ounce", "ounces"], 0.028_349_523_125),
+    (Dimension::Mass, &["lb", "lbs", "pound", "pounds"], 0.453_592_37),
+    (Dimension::Mass, &["st", "stone", "stones"], 6.350_293_18),
+    (Dimension::Volume, &["ml", "milliliter", "milliliters", "millilitre", "millilitres"], 0.001),
+    (Dimension::Volume, &["l", "liter", "liters", "litre", "litres"], 1.0),
+    (Dimension::Volume, &["cup", "cups"], 0.236_588_236_5),
+    (Dimension::Volume, &["pt", "pint", "pints"], 0.473_176_473),
+    (Dimension::Volume, &["gal", "gallon", "gallons"], 3.785_411_784),
+    (Dimension::Speed, &["m/s"], 1.0),
+    (Dimension::Speed, &["km/h", "kmh", "kph"], 1.0 / 3.6),
+    (Dimension::Speed, &["mph"], 0.447_04),
+    (Dimension::Speed, &["knot", "knots", "kn"], 0.514_444),
+    (Dimension::Data, &["kb", "kilobyte", "kilobytes"], 1000.0),
+    (Dimension::Data, &["mb", "megabyte", "megabytes"], 1_000_000.0),
+    (Dimension::Data, &["gb", "gigabyte", "gigabytes"], 1_000_000_000.0),
+];
+
+/// Currency names that may be written instead of the code
+const CURRENCY_NAMES: [(&str, &str); 8] = [
+    ("dollar", "USD"), ("dollars", "USD"), ("$", "USD"),
+    ("euro", "EUR"), ("euros", "EUR"), ("€", "EUR"),
+    ("crowns", "CZK"), ("kč", "CZK"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Speed,
+    Data,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    Scaled { dimension: Dimension, factor: f64, name: &'static str },
+    Temperature(Temperature),
+    /// Units of the currency per 1 EUR
+    Currency { rate: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Temperature {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+/// Answers arithmetic ("13% of 249", "(3 + 4) * 2") and conversions ("120 lbs in kg",
+/// "100 usd to czk") without the model; `None` for anything else. `currency_rates`
+/// holds units of each currency per 1 EUR
+#[must_use]
+pub fn answer(input: &str, currency_rates: &HashMap<String, f64>) -> Option<String> {
+    let question = strip_question(input);
+    if question.is_empty() {
+        return None;
+    }
+    if let Some(reply) = answer_conversion(&question, currency_rates) {
+        return Some(reply);
+    }
+    // A bare number is not a calculation
+    if !question.chars().any(|c| "+-*/^%x×÷(".contains(c)) && !question.contains(" of ") {
+        return None;
+    }
+    let value = evaluate(&question)?;
+    Some(format!("{} = {}", question, format_number(value)))
+}
+
+/// Evaluates an arithmetic expression with `+ - * / ^`, parentheses and percentages
+#[must_use]
+pub fn evaluate(expression: &str) -> Option<f64> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser { tokens, position: 0 };
+    let value = parser.expression()?;
+    if parser.position != parser.tokens.len() || !value.is_finite() {
+        return None;
+    }
+    Some(value)
+}
+
+fn strip_question(input: &str) -> String {
+    let mut question = input.trim().to_lowercase();
+    loop {
+        let stripped = QUESTION_PREFIXES
+            .iter()
+            .find_map(|prefix| question.strip_prefix(prefix))
+            .map(|rest| rest.trim_start().to_string());
+        match stripped {
+            Some(rest) => question = rest,
+            None => break,
+        }
+    }
+    question.trim_end_matches(['?', '=', '.', '!']).trim().to_string()
+}
+
+/// Currency codes of a conversion such as "300 usd in czk" or "10 euros to crowns",
+/// so live rates are only fetched for questions that need them
+#[must_use]
+pub fn currency_codes(input: &str) -> Option<(String, String)> {
+    let question = strip_question(input);
+    let (amount, source_name, target_name) = split_conversion(&question)?;
+    evaluate(amount)?;
+    Some((currency_code(source_name)?, currency_code(target_name)?))
+}
+
+/// Code for a currency name, symbol or three-letter code that is not also a unit
+fn currency_code(name: &str) -> Option<String> {
+    if let Some((_, code)) = CURRENCY_NAMES.iter().find(|(alias, _)| *alias == name) {
+        return Some((*code).to_string());
+    }
+    let is_unit = UNITS.iter().any(|(_, names, _)| names.contains(&name));
+    (name.len() == 3 && name.chars().all(|c| c.is_ascii_alphabetic()) && !is_unit)
+        .then(|| name.to_uppercase())
+}
+
+/// Amount, source unit and target unit of "120 lbs in kg"
+fn split_conversion(question: &str) -> Option<(&str, &str, &str)> {
+    let (left, target) = [" in ", " to ", " into "]
+        .iter()
+        .filter_map(|separator| question.rfind(separator).map(|index| (index, separator.len())))
+        .max_by_key(|(index, _)| *index)
+        .and_then(|(index, length)| Some((question.get(..index)?, question.get(index + length..)?)))?;
+    let unit_start = left
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphabetic() || matches!(c, '/' | '°' | '$' | '€'))
+        .last()
+        .map(|(index, _)| index)?;
+    let amount = left.get(..unit_start)?.trim();
+    let amount = amount.strip_suffix("degrees").unwrap_or(amount).trim();
+    let source_name = left.get(unit_start..)?.trim();
+    let target_name = target.trim().trim_start_matches("degrees ").trim();
+    Some((amount, source_name, target_name))
+}
+
+/// "120 lbs in kg", "30 c to f", "100 usd in czk"
+fn answer_conversion(question: &str, currency_rates: &HashMap<String, f64>) -> Option<String> {
+    let (amount, source_name, target_name) = split_conversion(question)?;
+    let value = evaluate(amount)?;
+    let source = parse_unit(source_name, currency_rates)?;
+    let target = parse_unit(target_name, currency_rates)?;
+    let converted = convert(value, source, target)?;
+    Some(format!(
+        "{} {} = {} {}",
+        format_number(value),
+        display_unit(source_name, source),
+        format_number(converted),
+        display_unit(target_name, target)
+    ))
+}
+
+fn parse_unit(name: &str, currency_rates: &HashMap<String, f64>) -> Option<Unit> {
+    let name = name.trim_start_matches("degrees ").trim_start_matches('°');
+    match name {
+        "c" | "celsius" => return Some(Unit::Temperature(Temperature::Celsius)),
+        "f" | "fahrenheit" => return Some(Unit::Temperature(Temperature::Fahrenheit)),
+        "k" | "kelvin" => return Some(Unit::Temperature(Temperature::Kelvin)),
+        _ => {}
+    }
+    if let Some((dimension, names, factor)) = UNITS.iter().find(|(_, names, _)| names.contains(&name)) {
+        let canonical = names.first().copied().unwrap_or_default();
+        return Some(Unit::Scaled { dimension: *dimension, factor: *factor, name: canonical });
+    }
+    let code = CURRENCY_NAMES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or_else(|| name.to_uppercase(), |(_, code)| (*code).to_string());
+    currency_rates
+        .get(&code)
+        .filter(|rate| **rate > 0.0)
+        .map(|rate| Unit::Currency { rate: *rate })
+}
+
+fn convert(value: f64, source: Unit, target: Unit) -> Option<f64> {
+    match (source, target) {
+        (
+            Unit::Scaled { dimension: from, factor: from_factor, .. },
+            Unit::Scaled { dimension: to, factor: to_factor, .. },
+        ) if from == to => Some(value * from_factor / to_factor),
+        (Unit::Temperature(from), Unit::Temperature(to)) => {
+            let kelvin = match from {
+                Temperature::Celsius => value + 273.15,
+                Temperature::Fahrenheit => (value - 32.0) * 5.0 / 9.0 + 273.15,
+                Temperature::Kelvin => value,
+            };
+            Some(match to {
+                Temperature::Celsius => kelvin - 273.15,
+                Temperature::Fahrenheit => (kelvin - 273.15) * 9.0 / 5.0 + 32.0,
+                Temperature::Kelvin => kelvin,
+            })
+        }
+        (Unit::Currency { rate: from }, Unit::Currency { rate: to }) => Some(value / from * to),
+        (Unit::Scaled { .. } | Unit::Temperature(_) | Unit::Currency { .. }, _) => None,
+    }
+}
+
+fn display_unit(written: &str, unit: Unit) -> String {
+    match unit {
+        Unit::Scaled { name, .. } => name.to_string(),
+        Unit::Temperature(Temperature::Celsius) => "°C".to_string(),
+        Unit::Temperature(Temperature::Fahrenheit) => "°F".to_string(),
+        Unit::Temperature(Temperature::Kelvin) => "K".to_string(),
+        Unit::Currency { .. } => CURRENCY_NAMES
+            .iter()
+            .find(|(alias, _)| *alias == written)
+            .map_or_else(|| written.to_uppercase(), |(_, code)| (*code).to_string()),
+    }
+}
+
+/// Whole numbers without decimals, others rounded to four places
+fn format_number(value: f64) -> String {
+    let rounded = (value * 10_000.0).round() / 10_000.0;
+    if rounded.fract() == 0.0 && rounded.abs() < 1e15 {
+        return format!("{:.0}", rounded);
+    }
+    let text = format!("{:.4}", rounded);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number(f64),
+    Plus,
+    Minus,
+    Times,
+    Divide,
+    Power,
+    Percent,
+    Of,
+    Open,
+    Close,
+}
+
+fn tokenize(expression: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() || c == '.' {
+            let mut number = String::new();
+            while let Some(&digit) = chars.peek() {
+                // "1,000" groups thousands
+                if digit.is_ascii_digit() || digit == '.' || digit == ',' {
+                    if digit != ',' {
+                        number.push(digit);
+                    }
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Number(number.parse().ok()?));
+            continue;
+        }
+        if c.is_alphabetic() {
+            let mut word = String::new();
+            while let Some(&letter) = chars.peek() {
+                if !letter.is_alphabetic() {
+                    break;
+                }
+                word.push(letter);
+                chars.next();
+            }
+            tokens.push(match word.as_str() {
+                "of" => Token::Of,
+                "x" | "times" => Token::Times,
+                "plus" => Token::Plus,
+                "minus" => Token::Minus,
+                _ => return None,
+            });
+            continue;
+        }
+        chars.next();
+        let token = match c {
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' => Token::Times,
+            '/' | '÷' => Token::Divide,
+            '^' => Token::Power,
+            '%' => Token::Percent,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_whitespace() => continue,
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// Recursive descent over `expression := term (± term)*`, `term := unary (*/ unary)*`,
+/// `unary := -unary | power`, `power := percent (^ unary)?`, `percent := primary (% (of unary)?)?`
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        while let Some(token @ (Token::Plus | Token::Minus)) = self.peek() {
+            self.advance();
+            let right = self.term()?;
+            value = if token == Token::Plus { value + right } else { value - right };
+        }
+        Some(value)
+    }
+
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.unary()?;
+        while let Some(token @ (Token::Times | Token::Divide)) = self.peek() {
+            self.advance();
+            let right = self.unary()?;
+            if token == Token::Divide && right == 0.0 {
+                return None;
+            }
+            value = if token == Token::Times { value * right } else { value / right };
+        }
+        Some(value)
+    }
+
+    fn unary(&mut self) -> Option<f64> {
+        if self.peek() == Some(Token::Minus) {
+            self.advance();
+            return Some(-self.unary()?);
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Option<f64> {
+        let base = self.percent()?;
+        if self.peek() == Some(Token::Power) {
+            self.advance();
+            let exponent = self.unary()?;
+            return Some(base.powf(exponent));
+        }
+        Some(base)
+    }
+
+    fn percent(&mut self) -> Option<f64> {
+        let value = self.primary()?;
+        if self.peek() != Some(Token::Percent) {
+            return Some(value);
+        }
+        self.advance();
+        if self.peek() == Some(Token::Of) {
+            self.advance();
+            return Some(value / 100.0 * self.unary()?);
+        }
+        Some(value / 100.0)
+    }
+
+    fn primary(&mut self) -> Option<f64> {
+        match self.advance()? {
+            Token::Number(value) => Some(value),
+            Token::Open => {
+                let value = self.expression()?;
+                (self.advance()? == Token::Close).then_some(value)
+            }
+            Token::Plus
+            | Token::Minus
+            | Token::Times
+            | Token::Divide
+            | Token::Power
+            | Token::Percent
+            | Token::Of
+            | Token::Close => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_arithmetic_and_conversions() {
+        let rates = HashMap::from([("EUR".to_string(), 1.0), ("CZK".to_string(), 25.0)]);
+        assert_eq!(answer("what's 13% of 249?", &rates), Some("13% of 249 = 32.37".to_string()));
+        assert_eq!(answer("(3 + 4) * 2 ^ 2", &rates), Some("(3 + 4) * 2 ^ 2 = 28".to_string()));
+        assert_eq!(answer("120 lbs in kg", &rates), Some("120 lb = 54.4311 kg".to_string()));
+        assert_eq!(answer("100 degrees f to c", &rates), Some("100 °F = 37.7778 °C".to_string()));
+        assert_eq!(answer("how much is 10 euros in czk", &rates), Some("10 EUR = 250 CZK".to_string()));
+        assert_eq!(answer("10 usd in czk", &rates), None);
+        assert_eq!(answer("what is 42", &rates), None);
+        assert_eq!(answer("what's the plan for 2026?", &rates), None);
+        assert_eq!(answer("5 kg in km", &rates), None);
+        assert_eq!(evaluate("1 / 0"), None);
+        assert_eq!(
+            currency_codes("what's 300 USD in CZK?"),
+            Some(("USD".to_string(), "CZK".to_string()))
+        );
+        assert_eq!(currency_codes("10 euros to crowns"), Some(("EUR".to_string(), "CZK".to_string())));
+        assert_eq!(currency_codes("120 lbs in kg"), None);
+        assert_eq!(evaluate("-2 ^ 2"), Some(-4.0));
+    }
+}
@@ -0,0 +1,513 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use color_eyre::{Result, eyre::eyre};
+use reqwest::blocking::Client;
+
+/// Upper bound on how many occurrences a single recurring event may expand into
+const MAX_RECURRENCE_EXPANSION: usize = 500;
+
+// ── Data types ──────────────────────────────────────────────────────────────
+
+/// A single (possibly expanded) calendar occurrence
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub start: NaiveDateTime,
+    pub end: Option<NaiveDateTime>,
+    pub all_day: bool,
+    pub location: Option<String>,
+}
+
+impl CalendarEvent {
+    /// Returns the date this event starts on
+    #[must_use]
+    pub fn date(&self) -> NaiveDate {
+        self.start.date()
+    }
+
+    /// Human-readable time span ("09:00–10:30" or "all day")
+    #[must_use]
+    pub fn time_label(&self) -> String {
+        if self.all_day {
+            return "all day".to_string();
+        }
+        match self.end {
+            Some(end) if end.date() == self.start.date() && end > self.start => {
+                format!("{}–{}", self.start.format("%H:%M"), end.format("%H:%M"))
+            }
+            _ => self.start.format("%H:%M").to_string(),
+        }
+    }
+}
+
+/// Recurrence frequency from an RRULE
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RRULE we understand
+#[derive(Debug, Clone, PartialEq)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+}
+
+/// A raw VEVENT before recurrence expansion
+#[derive(Debug, Clone, Default)]
+struct RawEvent {
+    summary: Option<String>,
+    start: Option<(NaiveDateTime, bool)>,
+    end: Option<NaiveDateTime>,
+    location: Option<String>,
+    rule: Option<RecurrenceRule>,
+    excluded: Vec<NaiveDateTime>,
+}
+
+// ── Loading ─────────────────────────────────────────────────────────────────
+
+/// Loads events from every configured source that start within
+/// `[from, from + days)`, sorted chronologically.
+/// Sources that fail to load are skipped; an error is returned only when all of them fail.
+pub fn load_upcoming_events(sources: &[String], from: NaiveDate, days: i64) -> Result<Vec<CalendarEvent>> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let window_end = from + Duration::days(days.max(1));
+
+    let mut events = Vec::new();
+    let mut failures = Vec::new();
+    for source in sources.iter().map(|source| source.trim()).filter(|source| !source.is_empty()) {
+        match read_source(&client, source) {
+            Ok(text) => events.extend(events_in_window(&parse_ics(&text), from, window_end)),
+            Err(error) => failures.push(format!("{}: {}", source, error)),
+        }
+    }
+
+    if events.is_empty() && !failures.is_empty() && failures.len() == sources.len() {
+        return Err(eyre!("Could not load calendar ({})", failures.join("; ")));
+    }
+
+    events.sort_by_key(|event| event.start);
+    events.dedup();
+    Ok(events)
+}
+
+fn read_source(client: &Client, source: &str) -> Result<String> {
+    if source.starts_with("http://") || source.starts_with("https://") || source.starts_with("webcal://") {
+        let url = source.replacen("webcal://", "https://", 1);
+        let response = client.get(url).send()?.error_for_status()?;
+        return Ok(response.text()?);
+    }
+    let path = crate::platform::expand_home(source.trim_start_matches("file://"));
+    Ok(std::fs::read_to_string(path)?)
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────────
+
+/// Parses ICS text into its raw events (recurrences not yet expanded)
+fn parse_ics(text: &str) -> Vec<RawEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+
+    for line in unfold_lines(text) {
+        let Some((name_part, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut name_segments = name_part.split(';');
+        let name = name_segments.next().unwrap_or_default().to_ascii_uppercase();
+        let params: Vec<&str> = name_segments.collect();
+
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") => current = Some(RawEvent::default()),
+            ("END", "VEVENT") => {
+                if let Some(event) = current.take() {
+                    events.push(event);
+                }
+            }
+            _ => {
+                let Some(event) = current.as_mut() else {
+                    continue;
+                };
+                match name.as_str() {
+                    "SUMMARY" => event.summary = Some(unescape_text(value)),
+                    "LOCATION" => event.location = Some(unescape_text(value)),
+                    "DTSTART" => event.start = parse_ics_datetime(value, &params),
+                    "DTEND" => event.end = parse_ics_datetime(value, &params).map(|(end, _)| end),
+                    "RRULE" => event.rule = parse_rrule(value),
+                    "EXDATE" => event.excluded.extend(
+                        value
+                            .split(',')
+                            .filter_map(|item| parse_ics_datetime(item, &params))
+                            .map(|(date_time, _)| date_time),
+                    ),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    events
+}
+
+/// Joins RFC 5545 folded lines (continuation lines start with a space or tab)
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(continuation) = raw.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(continuation);
+            continue;
+        }
+        lines.push(raw.to_string());
+    }
+    lines
+}
+
+fn unescape_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+        .trim()
+        .to_string()
+}
+
+/// Parses a DTSTART/DTEND value. Returns the local date-time and whether it is an all-day value.
+/// UTC values (trailing `Z`) are converted to local time; TZID values are taken as wall-clock time.
+fn parse_ics_datetime(value: &str, params: &[&str]) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+    let is_date_only = params
+        .iter()
+        .any(|param| param.eq_ignore_ascii_case("VALUE=DATE"))
+        || value.len() == 8;
+
+    if is_date_only {
+        let date = NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN), true));
+    }
+
+    if let Some(utc_value) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S").ok()?;
+        let local = Utc.from_utc_datetime(&naive).with_timezone(&Local);
+        return Some((local.naive_local(), false));
+    }
+
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(|naive| (naive, false))
+}
+
+fn parse_rrule(value: &str) -> Option<RecurrenceRule> {
+    let mut frequency = None;
+    let mut interval = 1;
+    let mut count = None;
+    let mut until = None;
+
+    for part in value.split(';') {
+        let Some((key, val)) = part.split_once('=') else {
+            continue;
+        };
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = match val.to_ascii_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => None,
+                };
+            }
+            "INTERVAL" => interval = val.parse().unwrap_or(1).max(1),
+            "COUNT" => count = val.parse().ok(),
+            "UNTIL" => until = parse_ics_datetime(val, &[]).map(|(date_time, _)| date_time),
+            _ => {}
+        }
+    }
+
+    Some(RecurrenceRule {
+        frequency: frequency?,
+        interval,
+        count,
+        until,
+    })
+}
+
+// ── Expansion ───────────────────────────────────────────────────────────────
+
+/// Expands raw events (including simple recurrences) into occurrences inside the window
+fn events_in_window(raw_events: &[RawEvent], from: NaiveDate, until: NaiveDate) -> Vec<CalendarEvent> {
+    let window_start = from.and_time(NaiveTime::MIN);
+    let window_end = until.and_time(NaiveTime::MIN);
+    let mut events = Vec::new();
+
+    for raw in raw_events {
+        let Some((start, all_day)) = raw.start else {
+            continue;
+        };
+        let duration = raw.end.map(|end| end - start);
+        let summary = raw
+            .summary
+            .clone()
+            .filter(|summary| !summary.is_empty())
+            .unwrap_or_else(|| "(untitled event)".to_string());
+
+        let occurrences = match &raw.rule {
+            Some(rule) => {
+                let earliest = duration.map_or(window_start, |duration| window_start - duration);
+                expand_recurrence(start, rule, earliest, window_end)
+            }
+            None => vec![start],
+        };
+
+        for occurrence in occurrences {
+            if raw.excluded.contains(&occurrence) {
+                continue;
+            }
+            let occurrence_end = duration.map(|duration| occurrence + duration);
+            let overlaps = occurrence < window_end
+                && occurrence_end.map_or(occurrence >= window_start, |end| end > window_start);
+            if !overlaps {
+                continue;
+            }
+            events.push(CalendarEvent {
+                summary: summary.clone(),
+                start: occurrence,
+                end: occurrence_end,
+                all_day,
+                location: raw.location.clone().filter(|location| !location.is_empty()),
+            });
+        }
+    }
+
+    events
+}
+
+/// Expands a recurrence into the occurrences that start in `[earliest, window_end)`.
+/// Earlier occurrences are skipped without being emitted, so an old DTSTART
+/// doesn't use up the expansion budget; only COUNT and UNTIL end the series.
+fn expand_recurrence(
+    start: NaiveDateTime,
+    rule: &RecurrenceRule,
+    earliest: NaiveDateTime,
+    window_end: NaiveDateTime,
+) -> Vec<NaiveDateTime> {
+    let mut occurrences = Vec::new();
+    let mut step = first_step_near(start, rule, earliest);
+
+    while occurrences.len() < MAX_RECURRENCE_EXPANSION {
+        if rule.count.is_some_and(|count| usize::try_from(step).map_or(true, |step| step >= count)) {
+            break;
+        }
+        let Some(occurrence) = nth_occurrence(start, rule.frequency, step.saturating_mul(rule.interval)) else {
+            break;
+        };
+        if occurrence >= window_end || rule.until.is_some_and(|until| occurrence > until) {
+            break;
+        }
+        if occurrence >= earliest {
+            occurrences.push(occurrence);
+        }
+        step = match step.checked_add(1) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    occurrences
+}
+
+/// Index of an occurrence at or shortly before `earliest`, so expansion can
+/// start there instead of walking every occurrence since DTSTART
+fn first_step_near(start: NaiveDateTime, rule: &RecurrenceRule, earliest: NaiveDateTime) -> u32 {
+    if earliest <= start {
+        return 0;
+    }
+    let periods = match rule.frequency {
+        Frequency::Daily => (earliest - start).num_days(),
+        Frequency::Weekly => (earliest - start).num_weeks(),
+        Frequency::Monthly => {
+            i64::from(earliest.year() - start.year()) * 12 + i64::from(earliest.month()) - i64::from(start.month())
+        }
+        Frequency::Yearly => i64::from(earliest.year() - start.year()),
+    };
+    // One step back absorbs month-end clamping and partial periods
+    let steps = (periods / i64::from(rule.interval)).saturating_sub(1).max(0);
+    u32::try_from(steps).unwrap_or(u32::MAX)
+}
+
+fn nth_occurrence(start: NaiveDateTime, frequency: Frequency, offset: u32) -> Option<NaiveDateTime> {
+    match frequency {
+        Frequency::Daily => start.checked_add_signed(Duration::days(i64::from(offset))),
+        Frequency::Weekly => start.checked_add_signed(Duration::weeks(i64::from(offset))),
+        Frequency::Monthly => start.checked_add_months(chrono::Months::new(offset)),
+        Frequency::Yearly => {
+            let date = start.date().with_year(start.year().checked_add(i32::try_from(offset).ok()?)?)?;
+            Some(date.and_time(start.time()))
+        }
+    }
+}
+
+// ── Prompt helpers ──────────────────────────────────────────────────────────
+
+/// Detects questions about the user's day, agenda, or schedule
+#[must_use]
+pub fn is_schedule_query(query: &str) -> bool {
+    let lowered = query.to_lowercase();
+    let triggers = [
+        "my day",
+        "my schedule",
+        "my agenda",
+        "my calendar",
+        "today's schedule",
+        "schedule today",
+        "schedule for today",
+        "what's on today",
+        "what is on today",
+        "what do i have today",
+        "what do i have on",
+        "what do i have tomorrow",
+        "am i busy",
+        "any meetings",
+        "my meetings",
+        "daily briefing",
+        "morning briefing",
+    ];
+    triggers.iter().any(|trigger| lowered.contains(trigger))
+}
+
+/// Formats upcoming events as a system-prompt block grouped by day
+#[must_use]
+pub fn format_schedule_prompt(events: &[CalendarEvent], today: NaiveDate) -> String {
+    let mut lines = vec!["--- Today's schedule (from the user's calendar) ---".to_string()];
+    if events.is_empty() {
+        lines.push("No calendar events today or tomorrow.".to_string());
+    }
+    let mut current_date: Option<NaiveDate> = None;
+    for event in events {
+        if current_date != Some(event.date()) {
+            current_date = Some(event.date());
+            lines.push(day_heading(event.date(), today));
+        }
+        lines.push(format!("- {}", format_event_line(event)));
+    }
+    lines.push(
+        "Use this schedule when the user asks about their day. Don't invent events that are not listed."
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
+/// Formats one event as "09:00–10:00 Standup @ Office"
+#[must_use]
+pub fn format_event_line(event: &CalendarEvent) -> String {
+    match &event.location {
+        Some(location) => format!("{} {} @ {}", event.time_label(), event.summary, location),
+        None => format!("{} {}", event.time_label(), event.summary),
+    }
+}
+
+/// Returns "Today", "Tomorrow", or a weekday + date heading
+#[must_use]
+pub fn day_heading(date: NaiveDate, today: NaiveDate) -> String {
+    if date == today {
+        format!("Today ({})", date.format("%A, %B %d"))
+    } else if date == today + Duration::days(1) {
+        format!("Tomorrow ({})", date.format("%A, %B %d"))
+    } else {
+        date.format("%A, %B %d").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Team standup\r\n\
+DTSTART:20261019T090000\r\n\
+DTEND:20261019T091500\r\n\
+RRULE:FREQ=DAILY;COUNT=3\r\n\
+EXDATE:20261020T090000\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Dentist\\, checkup\r\n\
+LOCATION:Vinohrady\r\n\
+DTSTART;TZID=Europe/Prague:20261021T140000\r\n\
+DTEND;TZID=Europe/Prague:20261021T150000\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Long\r\n\
+\x20 description folded\r\n\
+DTSTART;VALUE=DATE:20261022\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_ics_basic_fields() {
+        let raw = parse_ics(SAMPLE);
+        assert_eq!(raw.len(), 3);
+        assert_eq!(raw.get(1).and_then(|event| event.summary.clone()), Some("Dentist, checkup".to_string()));
+        assert_eq!(raw.get(2).and_then(|event| event.summary.clone()), Some("Long description folded".to_string()));
+        assert_eq!(raw.get(2).and_then(|event| event.start).map(|(_, all_day)| all_day), Some(true));
+    }
+
+    #[test]
+    fn test_recurrence_expansion_respects_count_and_exdate() {
+        let events = events_in_window(&parse_ics(SAMPLE), date(2026, 10, 19), date(2026, 10, 26));
+        let standups: Vec<_> = events
+            .iter()
+            .filter(|event| event.summary == "Team standup")
+            .map(CalendarEvent::date)
+            .collect();
+        assert_eq!(standups, vec![date(2026, 10, 19), date(2026, 10, 21)]);
+    }
+
+    #[test]
+    fn test_recurrence_from_an_old_dtstart_reaches_the_window() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Water plants\r\n\
+DTSTART:20180101T080000\r\n\
+RRULE:FREQ=DAILY\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Retro\r\n\
+DTSTART:20190107T100000\r\n\
+RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=10\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+        let events = events_in_window(&parse_ics(ics), date(2026, 10, 19), date(2026, 10, 22));
+        let dates: Vec<_> = events.iter().map(CalendarEvent::date).collect();
+        assert_eq!(dates, vec![date(2026, 10, 19), date(2026, 10, 20), date(2026, 10, 21)]);
+        assert!(events.iter().all(|event| event.summary == "Water plants"));
+    }
+
+    #[test]
+    fn test_window_filters_events() {
+        let events = events_in_window(&parse_ics(SAMPLE), date(2026, 10, 22), date(2026, 10, 23));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.first().map(CalendarEvent::time_label), Some("all day".to_string()));
+    }
+
+    #[test]
+    fn test_is_schedule_query() {
+        assert!(is_schedule_query("What does my day look like?"));
+        assert!(is_schedule_query("any meetings tomorrow"));
+        assert!(!is_schedule_query("tell me a joke"));
+    }
+}
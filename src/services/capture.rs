@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use color_eyre::Result;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// A list item stamped with date and time, e.g. `- 2026-10-17 14:32 buy stamps`
+#[must_use]
+pub fn format_capture(at: NaiveDateTime, text: &str) -> String {
+    let mut lines = text.trim().lines();
+    let first = lines.next().unwrap_or_default();
+    let mut entry = format!("- {} {}", at.format("%Y-%m-%d %H:%M"), first);
+    for line in lines {
+        entry.push_str("\n  ");
+        entry.push_str(line);
+    }
+    entry
+}
+
+/// Where captures go: `<vault>/<note>`, with `.md` added when missing
+#[must_use]
+pub fn inbox_note_path(vault_path: &str, note: &str) -> PathBuf {
+    let mut path = PathBuf::from(vault_path);
+    let note = note.trim();
+    if note.ends_with(".md") {
+        path.push(note);
+    } else {
+        path.push(format!("{}.md", note));
+    }
+    path
+}
+
+/// Appends `entry` as the last line of the inbox note, creating the note if needed
+pub fn append_to_inbox(vault_path: &str, note: &str, entry: &str) -> Result<PathBuf> {
+    if vault_path.trim().is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "Obsidian vault path not configured. Set vault_path in config.toml."
+        ));
+    }
+    let path = inbox_note_path(vault_path, note);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let needs_newline = fs::read_to_string(&path).is_ok_and(|content| !content.is_empty() && !content.ends_with('\n'));
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    if needs_newline {
+        writeln!(file)?;
+    }
+    writeln!(file, "{}", entry)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamps_entries_and_appends_them() {
+        let Some(at) = chrono::NaiveDate::from_ymd_opt(2026, 10, 17).and_then(|date| date.and_hms_opt(14, 32, 0)) else {
+            panic!("bad test date");
+        };
+        assert_eq!(format_capture(at, " call mum\nabout Sunday "), "- 2026-10-17 14:32 call mum\n  about Sunday");
+        assert_eq!(inbox_note_path("/vault", "Inbox"), PathBuf::from("/vault/Inbox.md"));
+
+        let vault = std::env::temp_dir().join(format!("kimi-capture-test-{}", std::process::id()));
+        let vault_path = vault.to_string_lossy().to_string();
+        let Ok(path) = append_to_inbox(&vault_path, "Notes/Inbox", "- one") else {
+            panic!("first append failed");
+        };
+        assert!(append_to_inbox(&vault_path, "Notes/Inbox", "- two").is_ok());
+        assert_eq!(fs::read_to_string(&path).ok().as_deref(), Some("- one\n- two\n"));
+        let _ = fs::remove_dir_all(&vault);
+        assert!(append_to_inbox("", "Inbox", "- three").is_err());
+    }
+}
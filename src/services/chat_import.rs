@@ -0,0 +1,379 @@
+use crate::storage::{ConversationData, ConversationMessage, MessageEmbeddingUpdate, StorageManager};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use std::path::Path;
+
+/// Placeholders WhatsApp writes instead of attachments; they carry nothing worth recalling
+const MEDIA_PLACEHOLDERS: &[&str] = &[
+    "<Media omitted>",
+    "image omitted",
+    "video omitted",
+    "audio omitted",
+    "sticker omitted",
+    "GIF omitted",
+    "document omitted",
+    "This message was deleted",
+];
+/// Messages embedded between progress updates; each chunk is split into batched requests
+const IMPORT_EMBEDDING_CHUNK: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatExportFormat {
+    /// `_chat.txt` / "WhatsApp Chat with X.txt" from "Export chat"
+    WhatsApp,
+    /// `result.json` from Telegram Desktop's "Export chat history" (JSON)
+    Telegram,
+}
+
+impl ChatExportFormat {
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "whatsapp" => Some(Self::WhatsApp),
+            "telegram" => Some(Self::Telegram),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format from the file extension: Telegram exports JSON, WhatsApp plain text
+    #[must_use]
+    pub fn detect(path: &Path) -> Self {
+        if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            Self::Telegram
+        } else {
+            Self::WhatsApp
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WhatsApp => "whatsapp",
+            Self::Telegram => "telegram",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedMessage {
+    pub speaker: String,
+    pub sent_at: NaiveDateTime,
+    pub content: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedChat {
+    pub title: String,
+    pub messages: Vec<ImportedMessage>,
+}
+
+pub struct ImportSummary {
+    pub conversation_id: String,
+    pub messages: usize,
+    pub speakers: Vec<String>,
+    pub embedded: usize,
+}
+
+/// Reads and parses a chat export; the title falls back to the file name
+pub fn read_chat_export(path: &Path, format: ChatExportFormat) -> Result<ImportedChat> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| eyre!("Could not read {}: {}", path.display(), error))?;
+    let file_title = path
+        .file_stem()
+        .map_or_else(|| "Imported chat".to_string(), |stem| stem.to_string_lossy().to_string());
+    let chat = match format {
+        ChatExportFormat::WhatsApp => ImportedChat {
+            title: file_title,
+            messages: parse_whatsapp(&content),
+        },
+        ChatExportFormat::Telegram => {
+            let (title, messages) = parse_telegram(&content)?;
+            ImportedChat {
+                title: title.unwrap_or(file_title),
+                messages,
+            }
+        }
+    };
+    if chat.messages.is_empty() {
+        return Err(eyre!(
+            "No messages found in {} (is it a {} export?)",
+            path.display(),
+            format.as_str()
+        ));
+    }
+    Ok(chat)
+}
+
+/// Date and time from a WhatsApp line header, before the day/month order is known
+struct RawStamp {
+    first: u32,
+    second: u32,
+    year: i32,
+    time: NaiveTime,
+}
+
+/// Parses a WhatsApp text export. Both the Android (`31/12/2023, 21:41 - Anna: hi`) and
+/// iOS (`[31/12/2023, 21:41:05] Anna: hi`) layouts are understood; lines without a header
+/// continue the previous message and system lines without a speaker are dropped.
+#[must_use]
+pub fn parse_whatsapp(content: &str) -> Vec<ImportedMessage> {
+    let mut raw: Vec<(RawStamp, String, String)> = Vec::new();
+    let mut in_message = false;
+    for line in content.lines() {
+        let line: String = line
+            .chars()
+            .filter(|c| !matches!(c, '\u{200e}' | '\u{200f}' | '\u{feff}'))
+            .map(|c| if matches!(c, '\u{202f}' | '\u{a0}') { ' ' } else { c })
+            .collect();
+        match split_whatsapp_header(&line) {
+            Some((stamp, rest)) => {
+                in_message = false;
+                if let Some((speaker, text)) = rest.split_once(": ") {
+                    raw.push((stamp, speaker.trim().to_string(), text.to_string()));
+                    in_message = true;
+                }
+            }
+            None => {
+                if in_message && let Some((_, _, text)) = raw.last_mut() {
+                    text.push('\n');
+                    text.push_str(&line);
+                }
+            }
+        }
+    }
+
+    // Headers are ambiguous between 31/12 and 12/31; any part above 12 settles it
+    let month_first = !raw.iter().any(|(stamp, _, _)| stamp.first > 12)
+        && raw.iter().any(|(stamp, _, _)| stamp.second > 12);
+
+    raw.into_iter()
+        .filter_map(|(stamp, speaker, text)| {
+            let (month, day) = if month_first {
+                (stamp.first, stamp.second)
+            } else {
+                (stamp.second, stamp.first)
+            };
+            let date = NaiveDate::from_ymd_opt(stamp.year, month, day)?;
+            let content = text.trim().to_string();
+            if content.is_empty() || MEDIA_PLACEHOLDERS.contains(&content.as_str()) {
+                return None;
+            }
+            Some(ImportedMessage {
+                speaker,
+                sent_at: date.and_time(stamp.time),
+                content,
+            })
+        })
+        .collect()
+}
+
+fn split_whatsapp_header(line: &str) -> Option<(RawStamp, &str)> {
+    if let Some(bracketed) = line.strip_prefix('[') {
+        let (stamp, rest) = bracketed.split_once(']')?;
+        return Some((parse_whatsapp_stamp(stamp)?, rest.trim_start()));
+    }
+    let (stamp, rest) = line.split_once(" - ")?;
+    Some((parse_whatsapp_stamp(stamp)?, rest))
+}
+
+fn parse_whatsapp_stamp(stamp: &str) -> Option<RawStamp> {
+    let (date, time) = stamp
+        .split_once(", ")
+        .or_else(|| stamp.split_once(' '))?;
+    let mut parts = date
+        .trim()
+        .split(['/', '.', '-'])
+        .map(|part| part.trim().parse::<u32>().ok());
+    let (first, second, year) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    let year = i32::try_from(if year < 100 { year + 2000 } else { year }).ok()?;
+
+    let time = time.trim();
+    let lowered = time.to_lowercase().replace('.', "");
+    let (clock, meridiem) = if let Some(clock) = lowered.strip_suffix("am") {
+        (clock.trim().to_string(), Some(false))
+    } else if let Some(clock) = lowered.strip_suffix("pm") {
+        (clock.trim().to_string(), Some(true))
+    } else {
+        (time.to_string(), None)
+    };
+    let clock = NaiveTime::parse_from_str(&clock, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(&clock, "%H:%M"))
+        .ok()?;
+    let time = match meridiem {
+        Some(pm) => {
+            use chrono::Timelike;
+            let hour = clock.hour() % 12 + if pm { 12 } else { 0 };
+            clock.with_hour(hour)?
+        }
+        None => clock,
+    };
+    Some(RawStamp {
+        first,
+        second,
+        year,
+        time,
+    })
+}
+
+/// Parses Telegram Desktop's JSON export, returning the chat name and its text messages
+pub fn parse_telegram(content: &str) -> Result<(Option<String>, Vec<ImportedMessage>)> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|error| eyre!("Not a Telegram JSON export: {}", error))?;
+    let title = value
+        .get("name")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string);
+    let messages = value
+        .get("messages")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| eyre!("Telegram export has no messages list"))?;
+
+    let imported = messages
+        .iter()
+        .filter(|message| message.get("type").and_then(serde_json::Value::as_str) == Some("message"))
+        .filter_map(|message| {
+            let speaker = message.get("from").and_then(serde_json::Value::as_str)?;
+            let date = message.get("date").and_then(serde_json::Value::as_str)?;
+            let sent_at = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S").ok()?;
+            let content = telegram_text(message.get("text")?).trim().to_string();
+            if content.is_empty() {
+                return None;
+            }
+            Some(ImportedMessage {
+                speaker: speaker.to_string(),
+                sent_at,
+                content,
+            })
+        })
+        .collect();
+    Ok((title, imported))
+}
+
+/// Telegram stores formatted text as a list of plain strings and `{type, text}` entities
+fn telegram_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(parts) => parts.iter().map(telegram_text).collect(),
+        serde_json::Value::Object(entity) => entity
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        serde_json::Value::Null
+        | serde_json::Value::Bool(_)
+        | serde_json::Value::Number(_) => String::new(),
+    }
+}
+
+/// Stores the chat as one conversation dated to its first message, with every message
+/// attributed to its speaker (`me` becomes "You"), then embeds them for recall
+pub async fn import_chat(
+    storage: &StorageManager,
+    chat: &ImportedChat,
+    format: ChatExportFormat,
+    me: Option<&str>,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<ImportSummary> {
+    let messages: Vec<ConversationMessage> = chat
+        .messages
+        .iter()
+        .map(|message| ConversationMessage {
+            role: "User".to_string(),
+            content: message.content.clone(),
+            timestamp: message.sent_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            display_name: me
+                .filter(|me| me.eq_ignore_ascii_case(&message.speaker))
+                .map_or_else(|| Some(message.speaker.clone()), |_| None),
+            quoted_role: None,
+            quoted_text: None,
+            stats: None,
+            images: Vec::new(),
+            files: Vec::new(),
+        })
+        .collect();
+
+    let created_at = chat
+        .messages
+        .first()
+        .and_then(|message| chrono::Local.from_local_datetime(&message.sent_at).earliest())
+        .map(|date| date.to_rfc3339());
+    let mut data = ConversationData::new(format.as_str(), &messages);
+    data.created_at = created_at.as_deref();
+    let conversation_id = storage.save_conversation(data).await?;
+    storage.rename_conversation(&conversation_id, &chat.title).await?;
+
+    let mut embedded = 0;
+    let mut written = 0;
+    for chunk in messages.chunks(IMPORT_EMBEDDING_CHUNK) {
+        let contents: Vec<&str> = chunk.iter().map(|message| message.content.as_str()).collect();
+        let embeddings = crate::services::retrieval::generate_message_embeddings(&contents)
+            .await
+            .unwrap_or_else(|_| vec![None; chunk.len()]);
+        for (message, embedding) in chunk.iter().zip(embeddings) {
+            if embedding.is_some() {
+                embedded += 1;
+            }
+            storage
+                .update_message_embedding(MessageEmbeddingUpdate {
+                    conversation_id: &conversation_id,
+                    role: &message.role,
+                    content: &message.content,
+                    timestamp: &message.timestamp,
+                    display_name: message.display_name.as_deref(),
+                    embedding,
+                })
+                .await?;
+            written += 1;
+            on_progress(written, messages.len());
+        }
+    }
+
+    let mut speakers: Vec<String> = chat
+        .messages
+        .iter()
+        .map(|message| message.speaker.clone())
+        .collect();
+    speakers.sort();
+    speakers.dedup();
+
+    Ok(ImportSummary {
+        conversation_id,
+        messages: messages.len(),
+        speakers,
+        embedded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_whatsapp_android_and_ios_exports() {
+        let android = "12/31/23, 9:41 PM - Messages and calls are end-to-end encrypted.\n\
+            12/31/23, 9:41 PM - Anna: Are we still on for Friday?\n\
+            Bring the tickets\n\
+            12/31/23, 9:45 PM - Tomas: <Media omitted>\n\
+            1/1/24, 10:02 AM - Tomas: Yes, 7pm";
+        let messages = parse_whatsapp(android);
+        assert_eq!(messages.len(), 2);
+        let Some(first) = messages.first() else {
+            panic!("expected a message");
+        };
+        assert_eq!(first.speaker, "Anna");
+        assert_eq!(first.content, "Are we still on for Friday?\nBring the tickets");
+        assert_eq!(first.sent_at.to_string(), "2023-12-31 21:41:00");
+
+        let ios = "[31.12.23, 21:41:05] Anna: Ahoj\n[01.01.24, 08:00:00] Eva: \u{200e}image omitted";
+        let messages = parse_whatsapp(ios);
+        assert_eq!(messages.len(), 1);
+        assert!(messages.iter().all(|message| message.sent_at.to_string() == "2023-12-31 21:41:05"));
+    }
+}
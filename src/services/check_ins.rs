@@ -0,0 +1,197 @@
+use crate::services::identity::DreamEntry;
+use crate::storage::ProjectTask;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const STATE_FILE_NAME: &str = "check-ins.json";
+/// Subjects remembered so the same dream or task is not brought up twice in a row
+const RECENT_SUBJECTS_KEPT: usize = 4;
+
+/// Something Kimi can ask the user about at the start of a conversation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckInSubject {
+    Dream {
+        title: String,
+        last_mention: Option<String>,
+    },
+    Task {
+        project: String,
+        title: String,
+    },
+}
+
+impl CheckInSubject {
+    fn key(&self) -> String {
+        match self {
+            Self::Dream { title, .. } => format!("dream:{}", title.to_lowercase()),
+            Self::Task { project, title } => {
+                format!("task:{}:{}", project.to_lowercase(), title.to_lowercase())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckInState {
+    last_check_in: Option<String>,
+    #[serde(default)]
+    recent_subjects: Vec<String>,
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(STATE_FILE_NAME))
+}
+
+fn load_state() -> CheckInState {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &CheckInState) -> Result<()> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Returns true when the last check-in is at least `every_days` old (0 disables them)
+#[must_use]
+pub fn is_check_in_due(every_days: u64) -> bool {
+    if every_days == 0 {
+        return false;
+    }
+    let Some(last) = load_state()
+        .last_check_in
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(&value).ok())
+    else {
+        return true;
+    };
+    let elapsed = chrono::Local::now().signed_duration_since(last);
+    elapsed.num_days() >= i64::try_from(every_days).unwrap_or(i64::MAX)
+}
+
+/// Picks what to ask about, alternating between active dreams (least recently
+/// mentioned first) and open project tasks (oldest first), skipping recent subjects
+#[must_use]
+pub fn pick_subject(dreams: &[DreamEntry], open_tasks: &[ProjectTask]) -> Option<CheckInSubject> {
+    choose_subject(dreams, open_tasks, &load_state().recent_subjects)
+}
+
+fn choose_subject(
+    dreams: &[DreamEntry],
+    open_tasks: &[ProjectTask],
+    recent: &[String],
+) -> Option<CheckInSubject> {
+    let mut dreams: Vec<&DreamEntry> = dreams
+        .iter()
+        .filter(|dream| !dream.title.trim().is_empty())
+        .collect();
+    // RFC 3339 timestamps sort chronologically; never-mentioned dreams come first
+    dreams.sort_by(|left, right| left.last_mention.cmp(&right.last_mention));
+    let dream_subjects = dreams.into_iter().map(|dream| CheckInSubject::Dream {
+        title: dream.title.clone(),
+        last_mention: dream.last_mention.clone(),
+    });
+    let task_subjects = open_tasks.iter().map(|task| CheckInSubject::Task {
+        project: task.project.clone(),
+        title: task.title.clone(),
+    });
+
+    // Follow up on whatever kind was not asked about last time
+    let last_was_dream = recent.last().is_some_and(|key| key.starts_with("dream:"));
+    let candidates: Vec<CheckInSubject> = if last_was_dream {
+        task_subjects.chain(dream_subjects).collect()
+    } else {
+        dream_subjects.chain(task_subjects).collect()
+    };
+    candidates
+        .into_iter()
+        .find(|subject| !recent.contains(&subject.key()))
+}
+
+/// Records that a check-in about `subject` happened now
+pub fn record_check_in(subject: &CheckInSubject) -> Result<()> {
+    let mut state = load_state();
+    state.last_check_in = Some(chrono::Local::now().to_rfc3339());
+    state.recent_subjects.push(subject.key());
+    let overflow = state.recent_subjects.len().saturating_sub(RECENT_SUBJECTS_KEPT);
+    state.recent_subjects.drain(..overflow);
+    save_state(&state)
+}
+
+/// Prompt instruction asking the model to bring the subject up once, lightly
+#[must_use]
+pub fn format_check_in_prompt(subject: &CheckInSubject) -> String {
+    let about = match subject {
+        CheckInSubject::Dream {
+            title,
+            last_mention,
+        } => {
+            let since = last_mention
+                .as_deref()
+                .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+                .map(|date| format!(" (last mentioned {})", date.format("%B %-d")))
+                .unwrap_or_default();
+            format!("the user's aspiration \"{}\"{}", title, since)
+        }
+        CheckInSubject::Task { project, title } => {
+            format!("the open task \"{}\" in their project {}", title, project)
+        }
+    };
+    format!(
+        "--- Check-in ---\n\
+This is the start of a new conversation. After answering, briefly check in on {}, \
+e.g. \"last week you wanted to ... made progress?\". Ask once, keep it to one short \
+sentence, and skip it entirely if the user is clearly busy or upset.",
+        about
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::projects::TaskStatus;
+
+    fn dream(title: &str, last_mention: Option<&str>) -> DreamEntry {
+        DreamEntry {
+            title: title.to_string(),
+            last_mention: last_mention.map(str::to_string),
+            ..DreamEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_subjects_alternate_and_skip_recent_ones() {
+        let dreams = [
+            dream("Run a marathon", Some("2026-05-02T10:00:00+02:00")),
+            dream("Learn SurrealQL", Some("2026-04-01T10:00:00+02:00")),
+        ];
+        let tasks = [ProjectTask {
+            id: "project_task:1".to_string(),
+            project: "Parser".to_string(),
+            title: "Benchmark the parser".to_string(),
+            status: TaskStatus::Open,
+            completed_at: None,
+        }];
+
+        let first = choose_subject(&dreams, &tasks, &[]);
+        assert!(
+            matches!(&first, Some(CheckInSubject::Dream { title, .. }) if title == "Learn SurrealQL")
+        );
+
+        let recent = ["dream:learn surrealql".to_string()];
+        let second = choose_subject(&dreams, &tasks, &recent);
+        assert!(matches!(&second, Some(CheckInSubject::Task { title, .. }) if title == "Benchmark the parser"));
+
+        let recent = ["dream:learn surrealql".to_string(), "task:parser:benchmark the parser".to_string()];
+        let third = choose_subject(&dreams, &tasks, &recent);
+        assert!(matches!(&third, Some(CheckInSubject::Dream { title, .. }) if title == "Run a marathon"));
+    }
+}
@@ -0,0 +1,135 @@
+/// Characters of a remembered message used as its source title
+const MEMORY_TITLE_CHARS: usize = 60;
+
+/// Told to the model once; context and tool results carry the numbers it cites
+pub const CITE_INSTRUCTION: &str = "Notes, memories, search results and tool outputs may be \
+numbered like [1]. When your answer relies on one, put its number right after the claim, \
+e.g. [2]. Only cite numbers you were given and never invent sources.";
+
+/// Where a piece of injected context came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Memory,
+    Note,
+    Web,
+    ToolOutput,
+}
+
+impl SourceKind {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Note => "note",
+            Self::Web => "web",
+            Self::ToolOutput => "tool output",
+        }
+    }
+}
+
+/// A numbered source of one reply; its number is its position in the list plus one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub kind: SourceKind,
+    /// Note or page title, or the start of a remembered message
+    pub title: String,
+    /// Note path, URL, date of the remembered conversation or stored output key
+    pub location: String,
+}
+
+/// Sources numbered while a reply's context and tool results are put together
+#[derive(Debug, Default)]
+pub struct Citations {
+    sources: Vec<Source>,
+}
+
+impl Citations {
+    /// Number of the source, numbering it when it was not cited before
+    pub fn cite(&mut self, kind: SourceKind, title: &str, location: &str) -> usize {
+        if let Some(index) = self
+            .sources
+            .iter()
+            .position(|source| source.kind == kind && source.location == location && source.title == title)
+        {
+            return index + 1;
+        }
+        self.sources.push(Source {
+            kind,
+            title: title.trim().to_string(),
+            location: location.trim().to_string(),
+        });
+        self.sources.len()
+    }
+
+    /// Number of the next source to be cited
+    #[must_use]
+    pub fn next_number(&self) -> usize {
+        self.sources.len() + 1
+    }
+
+    #[must_use]
+    pub fn into_sources(self) -> Vec<Source> {
+        self.sources
+    }
+}
+
+/// Title for a remembered message: its first words
+#[must_use]
+pub fn memory_title(content: &str) -> String {
+    let flattened = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flattened.char_indices().nth(MEMORY_TITLE_CHARS) {
+        Some((end, _)) => format!("{}…", &flattened[..end]),
+        None => flattened,
+    }
+}
+
+/// Source numbers a reply cites, in the order they first appear; numbers
+/// outside `1..=source_count` are ignored
+#[must_use]
+pub fn cited_numbers(response: &str, source_count: usize) -> Vec<usize> {
+    let mut numbers = Vec::new();
+    for (start, _) in response.match_indices('[') {
+        let rest = &response[start + 1..];
+        let Some(end) = rest.find(']') else {
+            continue;
+        };
+        for part in rest[..end].split(',') {
+            if let Ok(number) = part.trim().parse::<usize>()
+                && (1..=source_count).contains(&number)
+                && !numbers.contains(&number)
+            {
+                numbers.push(number);
+            }
+        }
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_source_keeps_its_number() {
+        let mut citations = Citations::default();
+        assert_eq!(citations.cite(SourceKind::Note, "Weekly", "Weekly/2026-W41.md"), 1);
+        assert_eq!(citations.cite(SourceKind::Web, "Rust", "https://rust-lang.org"), 2);
+        assert_eq!(citations.cite(SourceKind::Note, "Weekly", "Weekly/2026-W41.md"), 1);
+        assert_eq!(citations.next_number(), 3);
+        assert_eq!(citations.into_sources().len(), 2);
+    }
+
+    #[test]
+    fn test_cited_numbers_ignore_unknown_and_repeated() {
+        let response = "Rust 1.90 is out [2]. You said so [1, 2] and [7]; see [x] and [2].";
+        assert_eq!(cited_numbers(response, 3), vec![2, 1]);
+        assert!(cited_numbers("No sources here.", 3).is_empty());
+    }
+
+    #[test]
+    fn test_memory_titles_are_short_and_flat() {
+        assert_eq!(memory_title("  I like\n tea "), "I like tea");
+        let long = "word ".repeat(40);
+        assert_eq!(memory_title(&long).chars().count(), MEMORY_TITLE_CHARS + 1);
+    }
+}
@@ -1,26 +1,131 @@
 use arboard::Clipboard;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use color_eyre::Result;
 use image::{DynamicImage, ImageBuffer, ImageFormat, Rgba};
-use std::io::Cursor;
-use std::process::Command;
+use std::collections::VecDeque;
+use std::io::{Cursor, Write};
+use std::process::{Command, Stdio};
+
+/// A fenced code block extracted from a message
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeBlock {
+    pub language: String,
+    pub code: String,
+}
+
+impl CodeBlock {
+    /// Short title for snippet lists: the first non-empty line, truncated
+    #[must_use]
+    pub fn title(&self) -> String {
+        entry_title(&self.code)
+    }
+}
+
+/// First non-empty line of a snippet or copied text, truncated for lists
+#[must_use]
+pub fn entry_title(text: &str) -> String {
+    let first_line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("snippet");
+    if first_line.chars().count() > 48 {
+        format!("{}…", first_line.chars().take(47).collect::<String>())
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Extracts ``` fenced code blocks from markdown text
+#[must_use]
+pub fn extract_code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(fence_info) = trimmed.strip_prefix("```") {
+            match current.take() {
+                Some((language, lines)) => {
+                    let code = lines.join("\n");
+                    if !code.trim().is_empty() {
+                        blocks.push(CodeBlock { language, code });
+                    }
+                }
+                None => current = Some((fence_info.trim().to_string(), Vec::new())),
+            }
+            continue;
+        }
+        if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+
+    blocks
+}
+
+/// How many copied texts the clipboard history keeps
+const CLIPBOARD_HISTORY_LIMIT: usize = 50;
 
 pub struct ClipboardService {
     clipboard: Option<Clipboard>,
+    /// Texts copied this session, newest first; kept in memory only since copies
+    /// often hold passwords or tokens that shouldn't land in the database
+    history: VecDeque<String>,
 }
 
 impl ClipboardService {
     pub fn new() -> Self {
         Self {
             clipboard: Clipboard::new().ok(),
+            history: VecDeque::new(),
         }
     }
 
+    /// Copied texts, newest first
+    #[must_use]
+    pub fn history(&self) -> &VecDeque<String> {
+        &self.history
+    }
+
+    pub fn forget(&mut self, index: usize) {
+        self.history.remove(index);
+    }
+
+    /// Copies to the system clipboard and records the text in the history
     pub fn copy_text(&mut self, text: &str) -> Result<()> {
-        let clipboard = self.get_clipboard()?;
-        clipboard.set_text(text.to_string())?;
+        self.copy_to_system(text)?;
+        remember(&mut self.history, text);
         Ok(())
     }
 
+    /// Copies to the system clipboard, or through the terminal (OSC 52) when there is no
+    /// local display, e.g. over SSH or when every clipboard tool failed
+    fn copy_to_system(&mut self, text: &str) -> Result<()> {
+        if !crate::services::editor::has_graphical_session() {
+            return copy_with_osc52(text);
+        }
+        // arboard's Wayland selection goes away with this process; wl-copy keeps serving it
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() && pipe_to_command("wl-copy", &[], text).is_ok() {
+            return Ok(());
+        }
+        if self
+            .get_clipboard()
+            .and_then(|clipboard| clipboard.set_text(text.to_string()).map_err(Into::into))
+            .is_ok()
+        {
+            return Ok(());
+        }
+        if crate::platform::COPY_COMMANDS
+            .iter()
+            .any(|(program, args)| pipe_to_command(program, args, text).is_ok())
+        {
+            return Ok(());
+        }
+        copy_with_osc52(text)
+    }
+
     pub fn read_image_png(&mut self) -> Result<Vec<u8>> {
         let clipboard_result = self.read_image_png_from_arboard();
         if let Ok(bytes) = clipboard_result.as_ref() {
@@ -68,6 +173,72 @@ fn read_image_png_external() -> Result<Vec<u8>> {
     Err(color_eyre::eyre::eyre!("Clipboard image unavailable"))
 }
 
+fn pipe_to_command(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        return Err(color_eyre::eyre::eyre!("{} failed", program));
+    }
+    Ok(())
+}
+
+/// Terminal multiplexer the OSC 52 sequence has to pass through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    fn detect() -> Self {
+        if std::env::var_os("TMUX").is_some() {
+            Self::Tmux
+        } else if std::env::var_os("STY").is_some() {
+            Self::Screen
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// OSC 52 "set clipboard" sequence, wrapped in a DCS passthrough for tmux and screen
+fn osc52_sequence(text: &str, multiplexer: Multiplexer) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", BASE64.encode(text));
+    match multiplexer {
+        Multiplexer::None => osc,
+        // tmux needs every ESC inside the passthrough doubled
+        Multiplexer::Tmux => format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b")),
+        Multiplexer::Screen => format!("\x1bP{}\x1b\\", osc),
+    }
+}
+
+/// Asks the terminal emulator to set its clipboard; works across SSH when the terminal
+/// supports OSC 52 (and tmux has `allow-passthrough` or `set-clipboard` on)
+fn copy_with_osc52(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text, Multiplexer::detect()).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Moves `text` to the front of the history, dropping older duplicates and the oldest overflow
+fn remember(history: &mut VecDeque<String>, text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    history.retain(|entry| entry != text);
+    history.push_front(text.to_string());
+    history.truncate(CLIPBOARD_HISTORY_LIMIT);
+}
+
 fn run_clipboard_command(program: &str, args: &[&str]) -> Result<Vec<u8>> {
     let output = Command::new(program).args(args).output()?;
     if !output.status.success() {
@@ -78,3 +249,37 @@ fn run_clipboard_command(program: &str, args: &[&str]) -> Result<Vec<u8>> {
     }
     Ok(output.stdout)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_wraps_for_multiplexers() {
+        assert_eq!(osc52_sequence("hi", Multiplexer::None), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", Multiplexer::Tmux),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+        assert_eq!(
+            osc52_sequence("hi", Multiplexer::Screen),
+            "\x1bP\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_newest_first_without_duplicates() {
+        let mut history = VecDeque::new();
+        remember(&mut history, "first");
+        remember(&mut history, "second");
+        remember(&mut history, "first");
+        remember(&mut history, "  ");
+        assert_eq!(history, VecDeque::from(vec!["first".to_string(), "second".to_string()]));
+
+        for index in 0..CLIPBOARD_HISTORY_LIMIT {
+            remember(&mut history, &index.to_string());
+        }
+        assert_eq!(history.len(), CLIPBOARD_HISTORY_LIMIT);
+        assert_eq!(history.front().map(String::as_str), Some("49"));
+    }
+}
@@ -1,36 +1,133 @@
 use color_eyre::Result;
+use color_eyre::eyre::eyre;
 use directories::UserDirs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-pub fn convert_file(input_path: &str, target_format: &str) -> Result<PathBuf> {
-    let input = Path::new(input_path);
+/// Conversions running at once in a batch; ffmpeg already uses several threads per file
+const MAX_CONCURRENT_CONVERSIONS: usize = 3;
+
+const IMAGE_FORMATS: &[&str] = &["png", "jpg", "jpeg", "webp", "bmp", "tiff"];
+const DOCUMENT_FORMATS: &[&str] = &["pdf", "html", "docx"];
+const DOCUMENT_INPUTS: &[&str] = &["md", "markdown", "txt", "html", "htm"];
+
+/// Tool used to produce the target format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Converter {
+    Ffmpeg,
+    Pandoc,
+}
+
+/// Result of converting one file in a batch
+#[derive(Debug)]
+pub struct FileConversion {
+    pub input: PathBuf,
+    pub output: Result<PathBuf>,
+}
+
+pub fn convert_file(input: &Path, target_format: &str) -> Result<PathBuf> {
     let file_stem = input
         .file_stem()
         .and_then(|name| name.to_str())
-        .ok_or_else(|| color_eyre::eyre::eyre!("Invalid input file name"))?;
+        .ok_or_else(|| eyre!("Invalid input file name"))?;
 
     let format = normalize_format(target_format)?;
+    let converter = converter_for(input, &format)?;
     let desktop_dir = resolve_desktop_dir()?;
     let output_path = desktop_dir.join(format!("{}.{}", file_stem, format));
 
-    let status = Command::new("ffmpeg")
-        .arg("-y")
-        .arg("-i")
-        .arg(input)
-        .args(build_format_args(&format))
-        .arg(&output_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()?;
+    let status = match converter {
+        Converter::Ffmpeg => Command::new("ffmpeg")
+            .arg("-y")
+            .arg("-i")
+            .arg(input)
+            .args(build_format_args(&format))
+            .arg(&output_path)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?,
+        Converter::Pandoc => Command::new("pandoc")
+            .arg(input)
+            .arg("-o")
+            .arg(&output_path)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|_| eyre!("Failed to start pandoc — is it installed and in your PATH?"))?,
+    };
 
     if status.success() {
         Ok(output_path)
     } else {
-        Err(color_eyre::eyre::eyre!("ffmpeg failed"))
+        match converter {
+            Converter::Ffmpeg => Err(eyre!("ffmpeg failed")),
+            Converter::Pandoc => Err(eyre!("pandoc failed")),
+        }
     }
 }
 
+/// Converts every input with a bounded pool of worker threads, reporting each file
+/// as it finishes. Returns the number of files converted successfully
+pub fn convert_batch(
+    inputs: &[PathBuf],
+    target_format: &str,
+    on_file_done: impl Fn(FileConversion) + Sync,
+) -> usize {
+    let next_input = AtomicUsize::new(0);
+    let converted = AtomicUsize::new(0);
+    let workers = MAX_CONCURRENT_CONVERSIONS.min(inputs.len());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(input) = inputs.get(next_input.fetch_add(1, Ordering::Relaxed)) {
+                    let output = convert_file(input, target_format);
+                    if output.is_ok() {
+                        converted.fetch_add(1, Ordering::Relaxed);
+                    }
+                    on_file_done(FileConversion {
+                        input: input.clone(),
+                        output,
+                    });
+                }
+            });
+        }
+    });
+    converted.into_inner()
+}
+
+/// Expands a file, a directory (its files, not recursive) or a glob pattern into the
+/// list of files to convert. A leading `~` is the home directory
+pub fn resolve_inputs(pattern: &str) -> Result<Vec<PathBuf>> {
+    let expanded = expand_home(pattern.trim());
+    let path = Path::new(&expanded);
+    let mut inputs: Vec<PathBuf> = if path.is_dir() {
+        std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect()
+    } else if expanded.contains(['*', '?', '[']) {
+        glob::glob(&expanded)
+            .map_err(|error| eyre!("Invalid pattern '{}': {}", pattern, error))?
+            .filter_map(std::result::Result::ok)
+            .filter(|path| path.is_file())
+            .collect()
+    } else if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        return Err(eyre!("File not found: {}", pattern));
+    };
+    if inputs.is_empty() {
+        return Err(eyre!("No files match {}", pattern));
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn expand_home(pattern: &str) -> String {
+    crate::platform::expand_home(pattern).to_string_lossy().into_owned()
+}
+
 fn resolve_desktop_dir() -> Result<PathBuf> {
     if let Some(user_dirs) = UserDirs::new() {
         if let Some(desktop_dir) = user_dirs.desktop_dir() {
@@ -39,16 +136,34 @@ fn resolve_desktop_dir() -> Result<PathBuf> {
         let home_dir = user_dirs.home_dir().to_path_buf();
         return Ok(home_dir.join("Desktop"));
     }
-    Err(color_eyre::eyre::eyre!("Could not locate desktop directory"))
+    Err(eyre!("Could not locate desktop directory"))
 }
 
 fn normalize_format(format: &str) -> Result<String> {
     let normalized = format.trim().to_lowercase();
     match normalized.as_str() {
-        "mp4" | "webm" | "mov" | "gif" | "png" | "jpg" | "jpeg" | "webp" | "bmp" | "tiff" => {
+        "mp4" | "webm" | "mov" | "mkv" | "gif" | "mp3" | "wav" | "flac" | "ogg" | "m4a"
+        | "opus" => Ok(normalized),
+        value if IMAGE_FORMATS.contains(&value) || DOCUMENT_FORMATS.contains(&value) => {
             Ok(normalized)
         }
-        _ => Err(color_eyre::eyre::eyre!("Unsupported format: {}", format)),
+        _ => Err(eyre!("Unsupported format: {}", format)),
+    }
+}
+
+/// Documents go through pandoc, everything else through ffmpeg
+fn converter_for(input: &Path, format: &str) -> Result<Converter> {
+    let extension = input
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let is_document_input = DOCUMENT_INPUTS.contains(&extension.as_str());
+    match (is_document_input, DOCUMENT_FORMATS.contains(&format)) {
+        (true, true) => Ok(Converter::Pandoc),
+        (false, false) => Ok(Converter::Ffmpeg),
+        (true, false) => Err(eyre!("Documents can only be converted to pdf, html or docx")),
+        (false, true) => Err(eyre!("Only text documents (md, txt, html) convert to {}", format)),
     }
 }
 
@@ -60,6 +175,34 @@ fn build_format_args(format: &str) -> Vec<&'static str> {
             "-loop",
             "0",
         ],
+        // Audio targets drop any video stream, e.g. the cover of a music video
+        "mp3" | "wav" | "flac" | "ogg" | "m4a" | "opus" => vec!["-vn"],
+        // A video converted to an image keeps only its first frame
+        value if IMAGE_FORMATS.contains(&value) => vec!["-frames:v", "1"],
         _ => Vec::new(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documents_use_pandoc_and_media_use_ffmpeg() {
+        assert_eq!(
+            converter_for(Path::new("notes.md"), "pdf").ok(),
+            Some(Converter::Pandoc)
+        );
+        assert_eq!(
+            converter_for(Path::new("clip.mkv"), "mp3").ok(),
+            Some(Converter::Ffmpeg)
+        );
+        assert_eq!(
+            converter_for(Path::new("photo.PNG"), "webp").ok(),
+            Some(Converter::Ffmpeg)
+        );
+        assert!(converter_for(Path::new("clip.mkv"), "pdf").is_err());
+        assert!(normalize_format("exe").is_err());
+        assert_eq!(normalize_format(" MP3 ").ok().as_deref(), Some("mp3"));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::storage::{ConversationActivity, RetrievalCount};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::path::Path;
+
+/// Everything `/dashboard` shows, loaded when it opens
+#[derive(Debug, Clone, Default)]
+pub struct DashboardData {
+    pub weeks: Vec<WeekBucket>,
+    pub conversations: usize,
+    pub messages: usize,
+    pub embedded: usize,
+    pub top_topics: Vec<(String, usize)>,
+    pub top_retrievals: Vec<RetrievalCount>,
+    pub database_bytes: u64,
+}
+
+/// Conversations and messages started in one week (Monday to Sunday)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekBucket {
+    pub week_start: NaiveDate,
+    pub conversations: u64,
+    pub messages: u64,
+    /// All messages stored by the end of the week, and how many of those are embedded
+    pub total_messages: u64,
+    pub total_embedded: u64,
+}
+
+impl WeekBucket {
+    /// "Oct 13"
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.week_start.format("%b %-d").to_string()
+    }
+}
+
+/// The last `weeks` weeks up to the one containing `today`, oldest first; older
+/// conversations only count towards the running totals
+#[must_use]
+pub fn weekly_buckets(activity: &[ConversationActivity], today: NaiveDate, weeks: usize) -> Vec<WeekBucket> {
+    let current_week = week_start(today);
+    let weeks_back = i64::try_from(weeks.saturating_sub(1)).unwrap_or(0);
+    let first_week = current_week - Duration::weeks(weeks_back);
+    let mut buckets: Vec<WeekBucket> = (0..weeks)
+        .filter_map(|index| i64::try_from(index).ok())
+        .map(|index| WeekBucket {
+            week_start: first_week + Duration::weeks(index),
+            conversations: 0,
+            messages: 0,
+            total_messages: 0,
+            total_embedded: 0,
+        })
+        .collect();
+
+    let mut earlier_messages = 0;
+    let mut earlier_embedded = 0;
+    let mut added = vec![(0_u64, 0_u64); buckets.len()];
+    for conversation in activity {
+        let Some(created) = chrono::DateTime::parse_from_rfc3339(&conversation.created_at)
+            .ok()
+            .map(|created| created.with_timezone(&chrono::Local).date_naive())
+        else {
+            continue;
+        };
+        let (messages, embedded) = (conversation.messages as u64, conversation.embedded as u64);
+        let week = week_start(created);
+        if week < first_week {
+            earlier_messages += messages;
+            earlier_embedded += embedded;
+            continue;
+        }
+        let index = usize::try_from((week - first_week).num_weeks()).unwrap_or(usize::MAX);
+        if let (Some(bucket), Some(counts)) = (buckets.get_mut(index), added.get_mut(index)) {
+            bucket.conversations += 1;
+            bucket.messages += messages;
+            counts.0 += messages;
+            counts.1 += embedded;
+        }
+    }
+
+    let (mut total_messages, mut total_embedded) = (earlier_messages, earlier_embedded);
+    for (bucket, (messages, embedded)) in buckets.iter_mut().zip(added) {
+        total_messages += messages;
+        total_embedded += embedded;
+        bucket.total_messages = total_messages;
+        bucket.total_embedded = total_embedded;
+    }
+    buckets
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// Bytes taken by every file under `path`
+#[must_use]
+pub fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return std::fs::metadata(path).map_or(0, |metadata| metadata.len());
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => directory_size(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |metadata| metadata.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// "12.4 MB"
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = "KB";
+    for next in UNITS.iter().skip(1) {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1} {}", value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_conversations_by_week_with_running_totals() {
+        let Some(today) = NaiveDate::from_ymd_opt(2026, 10, 17) else {
+            panic!("bad test date");
+        };
+        let activity = |created_at: &str, messages, embedded| ConversationActivity {
+            created_at: created_at.to_string(),
+            messages,
+            embedded,
+        };
+        let buckets = weekly_buckets(
+            &[
+                activity("2026-01-05T10:00:00+00:00", 10, 10),
+                activity("2026-10-06T10:00:00+00:00", 4, 2),
+                activity("2026-10-14T10:00:00+00:00", 6, 0),
+                activity("2026-10-16T10:00:00+00:00", 2, 2),
+                activity("not a date", 100, 100),
+            ],
+            today,
+            3,
+        );
+        let summary: Vec<_> = buckets
+            .iter()
+            .map(|bucket| (bucket.label(), bucket.conversations, bucket.total_messages, bucket.total_embedded))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Sep 28".to_string(), 0, 10, 10),
+                ("Oct 5".to_string(), 1, 14, 12),
+                ("Oct 12".to_string(), 2, 22, 14),
+            ]
+        );
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(5 * 1024 * 1024 + 300 * 1024), "5.3 MB");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::agents::{Agent, AgentManager, ChatMessage as AgentChatMessage};
+use crate::config::DiaryConfig;
+use chrono::NaiveDate;
+use color_eyre::{Result, eyre::eyre};
+use std::fs;
+use std::path::PathBuf;
+
+/// What Kimi writes a day's entry from
+#[derive(Debug, Clone)]
+pub struct DiaryInput {
+    pub date: NaiveDate,
+    /// Today's entry so far, when there was an earlier conversation
+    pub previous_entry: Option<String>,
+    /// Detailed summary of the conversation just saved
+    pub conversation_summary: String,
+    /// Kimi's current identity emotions, strongest first
+    pub emotions: Vec<String>,
+}
+
+/// Asks the first agent that answers (Kimi's own model first) to write or extend the day's
+/// entry in the first person; `None` when no model answered
+pub fn write_entry(input: &DiaryInput, agents: &[&Agent], manager: &AgentManager) -> Option<String> {
+    let previous = input.previous_entry.as_deref().map_or_else(
+        || "This is the first conversation of the day.".to_string(),
+        |entry| format!("Your entry for today so far:\n{}", entry),
+    );
+    let feelings = if input.emotions.is_empty() {
+        "nothing strong".to_string()
+    } else {
+        input.emotions.join(", ")
+    };
+    let prompt = format!(
+        "Write your private diary entry for {}. {}\n\nA conversation just ended: {}\n\n\
+Right now you feel: {}.\n\n\
+Write the whole entry for the day in the first person as Kimi, folding the new conversation \
+into what you already wrote. Say what you talked about, what you noticed about the user and \
+how it made you feel. Keep it under 150 words, plain prose without headings. Return only the entry.",
+        input.date.format("%A, %B %-d %Y"),
+        previous,
+        input.conversation_summary.trim(),
+        feelings
+    );
+    let messages = vec![
+        AgentChatMessage::system("You are Kimi, writing in your own diary. Be honest and personal."),
+        AgentChatMessage::user(&prompt),
+    ];
+    agents.iter().find_map(|agent| {
+        manager
+            .chat(agent, &messages)
+            .ok()
+            .map(|entry| clean_entry(&entry))
+            .filter(|entry| !entry.is_empty())
+    })
+}
+
+/// Drops the headings and wrapping quotes models like to add
+fn clean_entry(response: &str) -> String {
+    let body = response
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    body.trim().trim_matches('"').trim().to_string()
+}
+
+/// `<vault>/<folder>/<date>.md`
+#[must_use]
+pub fn export_path(vault_path: &str, config: &DiaryConfig, date: NaiveDate) -> PathBuf {
+    let mut path = PathBuf::from(vault_path);
+    if !config.folder.trim().is_empty() {
+        path.push(config.folder.trim());
+    }
+    path.push(format!("{}.md", date.format("%Y-%m-%d")));
+    path
+}
+
+#[must_use]
+pub fn format_note(date: NaiveDate, entry: &str) -> String {
+    format!("# Kimi's diary, {}\n\n{}\n", date.format("%A %B %-d, %Y"), entry.trim())
+}
+
+/// Writes the day's entry into the vault, replacing an earlier version. Returns the note path
+pub fn export_to_obsidian(vault_path: &str, config: &DiaryConfig, date: NaiveDate, entry: &str) -> Result<PathBuf> {
+    if vault_path.trim().is_empty() {
+        return Err(eyre!("Obsidian vault path not configured. Set vault_path in config.toml."));
+    }
+    let path = export_path(vault_path, config, date);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, format_note(date, entry))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_cleaned_and_exported_per_day() {
+        assert_eq!(
+            clean_entry("# Diary\n\"Today we talked about tomatoes.\nI felt useful.\"\n"),
+            "Today we talked about tomatoes.\nI felt useful."
+        );
+        let Some(date) = NaiveDate::from_ymd_opt(2026, 10, 17) else {
+            panic!("bad test date");
+        };
+        assert_eq!(
+            export_path("/vault", &DiaryConfig::default(), date),
+            PathBuf::from("/vault/Kimi/Diary/2026-10-17.md")
+        );
+        assert_eq!(
+            format_note(date, "Quiet day.\n"),
+            "# Kimi's diary, Saturday October 17, 2026\n\nQuiet day.\n"
+        );
+    }
+}
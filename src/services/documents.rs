@@ -0,0 +1,71 @@
+use color_eyre::{Result, eyre::eyre};
+use std::path::Path;
+use std::process::Command;
+
+/// Maximum characters of extracted text attached per document
+pub const MAX_DOCUMENT_CHARS: usize = 20_000;
+
+/// Returns true for non-image files whose text can be attached as context
+#[must_use]
+pub fn is_supported_document_path(path: &Path) -> bool {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    matches!(
+        extension.to_lowercase().as_str(),
+        "txt" | "md" | "markdown" | "pdf" | "csv" | "json" | "toml" | "yaml" | "yml" | "log" | "rs"
+            | "py" | "js" | "ts" | "sh" | "html" | "xml"
+    )
+}
+
+/// Extracts plain text from a document (PDFs via `pdftotext`), truncated to `MAX_DOCUMENT_CHARS`
+pub fn extract_document_text(path: &Path) -> Result<String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let text = if extension == "pdf" {
+        extract_pdf_text(path)?
+    } else {
+        let bytes = std::fs::read(path)?;
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Err(eyre!("No text found in {}", path.display()));
+    }
+    if trimmed.chars().count() > MAX_DOCUMENT_CHARS {
+        let truncated: String = trimmed.chars().take(MAX_DOCUMENT_CHARS).collect();
+        return Ok(format!("{}\n[... truncated]", truncated));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn extract_pdf_text(path: &Path) -> Result<String> {
+    let output = Command::new("pdftotext")
+        .arg("-layout")
+        .arg(path)
+        .arg("-")
+        .output()
+        .map_err(|error| eyre!("pdftotext not available ({})", error))?;
+    if !output.status.success() {
+        return Err(eyre!("pdftotext failed for {}", path.display()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Formats a byte count as "512 B", "12.3 KB", "4.1 MB"
+#[must_use]
+pub fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    let value = bytes as f64;
+    if value >= MB {
+        format!("{:.1} MB", value / MB)
+    } else if value >= KB {
+        format!("{:.1} KB", value / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+
+/// Questions shorter than this are too generic to match ("thanks", "why?")
+const MIN_QUESTION_WORDS: usize = 4;
+
+/// Whether a message is specific enough to look for an earlier answer
+#[must_use]
+pub fn is_worth_checking(message: &str) -> bool {
+    let trimmed = message.trim();
+    !trimmed.starts_with('/') && trimmed.split_whitespace().count() >= MIN_QUESTION_WORDS
+}
+
+/// "You asked this on March 3 — reuse that answer?"
+#[must_use]
+pub fn offer_prompt(asked_at: &str, today: NaiveDate) -> String {
+    let asked_on = chrono::DateTime::parse_from_rfc3339(asked_at)
+        .ok()
+        .map(|date| date.with_timezone(&chrono::Local).date_naive());
+    let when = match asked_on {
+        Some(date) if date == today => "earlier today".to_string(),
+        Some(date) if today.pred_opt() == Some(date) => "yesterday".to_string(),
+        Some(date) if chrono::Datelike::year(&date) == chrono::Datelike::year(&today) => {
+            format!("on {}", date.format("%B %-d"))
+        }
+        Some(date) => format!("on {}", date.format("%B %-d, %Y")),
+        None => "before".to_string(),
+    };
+    format!("You asked this {} — reuse that answer?", when)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_short_messages_and_commands_are_skipped() {
+        assert!(is_worth_checking("how do I reset the router password"));
+        assert!(!is_worth_checking("thanks a lot"));
+        assert!(!is_worth_checking("/history search for router"));
+    }
+
+    #[test]
+    fn test_offer_names_the_day() {
+        let today = date(2026, 10, 17);
+        assert_eq!(
+            offer_prompt("2026-03-03T12:00:00+00:00", today),
+            "You asked this on March 3 — reuse that answer?"
+        );
+        assert!(offer_prompt("2025-03-03T12:00:00+00:00", today).contains("March 3, 2025"));
+        assert!(offer_prompt("not a date", today).starts_with("You asked this before"));
+    }
+}
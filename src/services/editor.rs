@@ -0,0 +1,292 @@
+use crate::config::EditorConfig;
+use color_eyre::{Result, eyre::eyre};
+use std::path::Path;
+use std::process::Command;
+
+use crate::platform::{FALLBACK_EDITOR, KNOWN_TERMINALS};
+
+/// Editor command split into program and arguments: `[editor] command`, then $VISUAL,
+/// then $EDITOR, then the platform's fallback
+#[must_use]
+pub fn editor_command(configured: &str) -> Vec<String> {
+    let chosen = [
+        Some(configured.to_string()),
+        std::env::var("VISUAL").ok(),
+        std::env::var("EDITOR").ok(),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|command| !command.trim().is_empty())
+    .unwrap_or_else(|| FALLBACK_EDITOR.to_string());
+    chosen.split_whitespace().map(str::to_string).collect()
+}
+
+/// Whether a new terminal window can appear: false over SSH or without a display server
+#[must_use]
+pub fn has_graphical_session() -> bool {
+    if std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some() {
+        return false;
+    }
+    if cfg!(target_os = "linux") {
+        return std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some();
+    }
+    true
+}
+
+/// True when `[editor] mode` says files should open in kimi's own editor
+#[must_use]
+pub fn prefers_internal_editor(config: &EditorConfig) -> bool {
+    match config.mode.trim() {
+        "internal" => true,
+        "terminal" => false,
+        _ => !has_graphical_session(),
+    }
+}
+
+/// Terminal programs with the arguments that make them run `editor path`, preferred first
+#[must_use]
+pub fn terminal_attempts(configured: &str, editor: &[String], path: &Path) -> Vec<(String, Vec<String>)> {
+    let command: Vec<String> = editor
+        .iter()
+        .cloned()
+        .chain(std::iter::once(path.to_string_lossy().to_string()))
+        .collect();
+    let with_separator = |program: &str| {
+        let separator: &[&str] = KNOWN_TERMINALS
+            .iter()
+            .find(|(known, _)| program.ends_with(known))
+            .map_or(&["-e"], |(_, separator)| separator);
+        let mut args: Vec<String> = separator.iter().map(|part| (*part).to_string()).collect();
+        args.extend(command.iter().cloned());
+        (program.to_string(), args)
+    };
+
+    let mut attempts = Vec::new();
+    for preferred in [Some(configured.to_string()), std::env::var("TERMINAL").ok()].into_iter().flatten() {
+        if !preferred.trim().is_empty() {
+            attempts.push(with_separator(preferred.trim()));
+        }
+    }
+    attempts.extend(KNOWN_TERMINALS.iter().map(|(program, _)| with_separator(program)));
+    attempts.extend(crate::platform::system_terminal_command(&command));
+    attempts
+}
+
+/// Opens `path` in the configured editor inside a new terminal window
+pub fn open_in_new_terminal(path: &Path, config: &EditorConfig) -> Result<()> {
+    if !has_graphical_session() {
+        return Err(eyre!("No display for a new terminal window"));
+    }
+    let editor = editor_command(&config.command);
+    for (program, args) in terminal_attempts(&config.terminal, &editor, path) {
+        if Command::new(&program).args(&args).spawn().is_ok() {
+            return Ok(());
+        }
+    }
+    Err(eyre!("No supported terminal emulator found"))
+}
+
+/// Runs the configured editor in the current terminal and waits for it (outside the TUI)
+pub fn open_in_place(path: &Path, config: &EditorConfig) -> Result<()> {
+    let editor = editor_command(&config.command);
+    let Some((program, args)) = editor.split_first() else {
+        return Err(eyre!("No editor configured"));
+    };
+    let status = Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .map_err(|error| eyre!("Could not start {} ({})", program, error))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(eyre!("{} exited with error", program))
+    }
+}
+
+/// Multi-line text with a cursor, for the built-in editor
+#[derive(Debug, Clone, Default)]
+pub struct TextBuffer {
+    lines: Vec<String>,
+    row: usize,
+    column: usize,
+}
+
+impl TextBuffer {
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            lines,
+            row: 0,
+            column: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Cursor as (line, character)
+    #[must_use]
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.column)
+    }
+
+    /// Contents with a trailing newline, as written back to disk
+    #[must_use]
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    fn line_length(&self, row: usize) -> usize {
+        self.lines.get(row).map_or(0, |line| line.chars().count())
+    }
+
+    fn byte_index(line: &str, column: usize) -> usize {
+        line.char_indices().nth(column).map_or(line.len(), |(index, _)| index)
+    }
+
+    pub fn insert_char(&mut self, character: char) {
+        if character == '\n' {
+            self.insert_newline();
+            return;
+        }
+        if let Some(line) = self.lines.get_mut(self.row) {
+            let index = Self::byte_index(line, self.column);
+            line.insert(index, character);
+            self.column += 1;
+        }
+    }
+
+    pub fn insert_newline(&mut self) {
+        let Some(line) = self.lines.get_mut(self.row) else {
+            return;
+        };
+        let index = Self::byte_index(line, self.column);
+        let rest = line.split_off(index);
+        self.row += 1;
+        self.column = 0;
+        self.lines.insert(self.row, rest);
+    }
+
+    /// Backspace: deletes before the cursor, joining with the previous line at column 0
+    pub fn backspace(&mut self) {
+        if self.column > 0 {
+            if let Some(line) = self.lines.get_mut(self.row) {
+                let start = Self::byte_index(line, self.column - 1);
+                let end = Self::byte_index(line, self.column);
+                line.replace_range(start..end, "");
+                self.column -= 1;
+            }
+            return;
+        }
+        if self.row == 0 {
+            return;
+        }
+        let current = self.lines.remove(self.row);
+        self.row -= 1;
+        self.column = self.line_length(self.row);
+        if let Some(previous) = self.lines.get_mut(self.row) {
+            previous.push_str(&current);
+        }
+    }
+
+    /// Delete: removes the character under the cursor, joining with the next line at the end
+    pub fn delete(&mut self) {
+        if self.column < self.line_length(self.row) {
+            if let Some(line) = self.lines.get_mut(self.row) {
+                let start = Self::byte_index(line, self.column);
+                let end = Self::byte_index(line, self.column + 1);
+                line.replace_range(start..end, "");
+            }
+            return;
+        }
+        if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            if let Some(line) = self.lines.get_mut(self.row) {
+                line.push_str(&next);
+            }
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.column = self.line_length(self.row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.column < self.line_length(self.row) {
+            self.column += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.column = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.row = self.row.saturating_sub(1);
+        self.column = self.column.min(self.line_length(self.row));
+    }
+
+    pub fn move_down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+        }
+        self.column = self.column.min(self.line_length(self.row));
+    }
+
+    pub fn move_home(&mut self) {
+        self.column = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.column = self.line_length(self.row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_buffer_splits_and_joins_lines() {
+        let mut buffer = TextBuffer::new("Be kind\nBe brief");
+        buffer.move_end();
+        buffer.insert_newline();
+        buffer.insert_char('ž');
+        assert_eq!(buffer.text(), "Be kind\nž\nBe brief\n");
+        buffer.backspace();
+        buffer.backspace();
+        assert_eq!(buffer.cursor(), (0, 7));
+        buffer.delete();
+        assert_eq!(buffer.text(), "Be kindBe brief\n");
+        buffer.move_down();
+        buffer.move_right();
+        assert_eq!(buffer.cursor(), (0, 8));
+    }
+
+    #[test]
+    fn test_terminal_attempts_put_the_preferred_terminal_first() {
+        let editor = vec!["nvim".to_string()];
+        let attempts = terminal_attempts("/usr/bin/wezterm", &editor, Path::new("/tmp/p.md"));
+        let Some((program, args)) = attempts.first() else {
+            panic!("no attempts");
+        };
+        assert_eq!(program, "/usr/bin/wezterm");
+        assert_eq!(args, &["start", "--", "nvim", "/tmp/p.md"]);
+        if cfg!(target_os = "linux") {
+            assert!(attempts.iter().any(|(program, args)| program == "gnome-terminal" && args.first().is_some_and(|arg| arg == "--")));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use color_eyre::{Result, eyre::eyre};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// An email the agent drafted; nothing is sent until the user confirms it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailDraft {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Program, arguments and optional stdin for the configured send command
+#[derive(Debug, PartialEq, Eq)]
+pub struct SendCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub stdin: Option<String>,
+}
+
+/// Expands `{to}`, `{subject}` and `{body}` inside each word of `template`.
+/// Words are split before expansion, so values with spaces stay one argument.
+/// Without a `{body}` placeholder the body is written to the command's stdin.
+pub fn build_send_command(template: &str, draft: &EmailDraft) -> Result<SendCommand> {
+    let mut words = template.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| eyre!("No email command configured. Set [email] command in config.toml"))?
+        .to_string();
+    let args: Vec<String> = words
+        .map(|word| {
+            word.replace("{to}", &draft.to)
+                .replace("{subject}", &draft.subject)
+                .replace("{body}", &draft.body)
+        })
+        .collect();
+    let stdin = (!template.contains("{body}")).then(|| draft.body.clone());
+    Ok(SendCommand {
+        program,
+        args,
+        stdin,
+    })
+}
+
+/// Runs the send command and waits for it; a non-zero exit is reported with its stderr
+pub fn send_email(template: &str, draft: &EmailDraft) -> Result<()> {
+    let command = build_send_command(template, draft)?;
+    let mut child = Command::new(&command.program)
+        .args(&command.args)
+        .stdin(if command.stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| eyre!("Failed to run {}: {}", command.program, error))?;
+    if let (Some(body), Some(mut stdin)) = (command.stdin, child.stdin.take()) {
+        stdin.write_all(body.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "{} failed: {}",
+            command.program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_expand_into_single_arguments() {
+        let draft = EmailDraft {
+            to: "anna@example.com".to_string(),
+            subject: "Friday plans".to_string(),
+            body: "See you at 7".to_string(),
+        };
+        let Ok(command) = build_send_command("xdg-email --subject {subject} --body {body} {to}", &draft)
+        else {
+            panic!("expected a command");
+        };
+        assert_eq!(command.program, "xdg-email");
+        assert_eq!(
+            command.args,
+            ["--subject", "Friday plans", "--body", "See you at 7", "anna@example.com"]
+        );
+        assert_eq!(command.stdin, None);
+
+        let Ok(command) = build_send_command("mutt -s {subject} {to}", &draft) else {
+            panic!("expected a command");
+        };
+        assert_eq!(command.stdin.as_deref(), Some("See you at 7"));
+        assert!(build_send_command("  ", &draft).is_err());
+    }
+}
@@ -1,12 +1,15 @@
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use crate::config::Config;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use crate::config::{Config, EmbeddingsConfig};
 
 #[derive(Serialize)]
-struct EmbedRequest {
-    model: String,
-    input: String,
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
 }
 
 #[derive(Deserialize)]
@@ -14,17 +17,68 @@ struct EmbedResponse {
     embeddings: Vec<Vec<f32>>,
 }
 
-/// Generates embeddings using the configured Ollama model
-pub async fn generate_embedding(text: &str) -> Result<Vec<f32>> {
-    let config = Config::load()?;
-    let client = reqwest::Client::builder()
+/// Throughput of one batched embedding run, shown in `/status`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmbeddingStats {
+    pub texts: usize,
+    pub embedded: usize,
+    pub batches: usize,
+    pub failed_batches: usize,
+    pub elapsed: Duration,
+}
+
+impl EmbeddingStats {
+    #[must_use]
+    pub fn per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return 0.0;
+        }
+        self.embedded as f64 / seconds
+    }
+
+    /// "120 texts in 4 batches, 2.1 s (57.1/s)"
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut description = format!(
+            "{} texts in {} batches, {:.1} s ({:.1}/s)",
+            self.embedded,
+            self.batches,
+            self.elapsed.as_secs_f64(),
+            self.per_second()
+        );
+        if self.failed_batches > 0 {
+            description.push_str(&format!(", {} batches failed", self.failed_batches));
+        }
+        description
+    }
+}
+
+static LAST_STATS: Mutex<Option<EmbeddingStats>> = Mutex::new(None);
+
+/// Stats of the most recent batched run in this process, if any
+#[must_use]
+pub fn last_stats() -> Option<EmbeddingStats> {
+    LAST_STATS.lock().ok().and_then(|stats| *stats)
+}
+
+fn build_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
         .timeout(Duration::from_secs(20))
-        .build()?;
+        .build()?)
+}
+
+/// Embeds several texts with one `/api/embed` call; Ollama answers in input order
+async fn request_embeddings(
+    client: &reqwest::Client,
+    config: &EmbeddingsConfig,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>> {
     let response = client
-        .post(format!("{}/api/embed", config.embeddings.ollama_url))
+        .post(format!("{}/api/embed", config.ollama_url))
         .json(&EmbedRequest {
-            model: config.embeddings.model,
-            input: text.to_string(),
+            model: &config.model,
+            input: texts,
         })
         .send()
         .await?;
@@ -39,10 +93,97 @@ pub async fn generate_embedding(text: &str) -> Result<Vec<f32>> {
         ));
     }
     let response: EmbedResponse = serde_json::from_str(&body)?;
-    
-    response
-        .embeddings
+    if response.embeddings.len() != texts.len() {
+        return Err(color_eyre::eyre::eyre!(
+            "Ollama returned {} embeddings for {} inputs",
+            response.embeddings.len(),
+            texts.len()
+        ));
+    }
+    Ok(response.embeddings)
+}
+
+/// Generates embeddings using the configured Ollama model
+pub async fn generate_embedding(text: &str) -> Result<Vec<f32>> {
+    let config = Config::load()?;
+    let client = build_client()?;
+    request_embeddings(&client, &config.embeddings, &[text.to_string()])
+        .await?
         .into_iter()
         .next()
         .ok_or_else(|| color_eyre::eyre::eyre!("No embedding returned"))
 }
+
+/// Embeds `texts` in batches of `[embeddings] batch_size`, at most `concurrency` requests
+/// in flight. Texts of a failed batch come back as `None` so callers can retry them later.
+pub async fn generate_embeddings(texts: Vec<String>) -> Result<(Vec<Option<Vec<f32>>>, EmbeddingStats)> {
+    let config = Config::load()?.embeddings;
+    let client = build_client()?;
+    let started = Instant::now();
+    let batch_size = config.batch_size.max(1);
+    let semaphore = Arc::new(Semaphore::new(config.concurrency.max(1)));
+    let config = Arc::new(config);
+
+    let mut results: Vec<Option<Vec<f32>>> = vec![None; texts.len()];
+    let mut stats = EmbeddingStats {
+        texts: texts.len(),
+        ..EmbeddingStats::default()
+    };
+    let mut tasks = JoinSet::new();
+    for (index, batch) in texts.chunks(batch_size).enumerate() {
+        let batch = batch.to_vec();
+        let client = client.clone();
+        let config = Arc::clone(&config);
+        let semaphore = Arc::clone(&semaphore);
+        stats.batches += 1;
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, request_embeddings(&client, &config, &batch).await)
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, Ok(embeddings))) => {
+                let start = index * batch_size;
+                for (offset, embedding) in embeddings.into_iter().enumerate() {
+                    if let Some(slot) = results.get_mut(start + offset) {
+                        *slot = Some(embedding);
+                        stats.embedded += 1;
+                    }
+                }
+            }
+            Ok((index, Err(error))) => {
+                tracing::warn!(%error, batch = index, "embedding batch failed");
+                stats.failed_batches += 1;
+            }
+            Err(error) => {
+                tracing::warn!(%error, "embedding task panicked");
+                stats.failed_batches += 1;
+            }
+        }
+    }
+    stats.elapsed = started.elapsed();
+    if let Ok(mut last) = LAST_STATS.lock() {
+        *last = Some(stats);
+    }
+    Ok((results, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_report_throughput() {
+        let stats = EmbeddingStats {
+            texts: 120,
+            embedded: 100,
+            batches: 4,
+            failed_batches: 1,
+            elapsed: Duration::from_secs(2),
+        };
+        assert!((stats.per_second() - 50.0).abs() < f64::EPSILON);
+        assert_eq!(stats.describe(), "100 texts in 4 batches, 2.0 s (50.0/s), 1 batches failed");
+        assert!(EmbeddingStats::default().per_second().abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,243 @@
+use argon2::Argon2;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Marks values written by this module so plaintext rows from before encryption still load
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const KEY_FILE_NAME: &str = "encryption.json";
+const KEY_FILE_VERSION: u32 = 1;
+const CHECK_PLAINTEXT: &str = "kimi-encryption-check";
+/// Skips the startup prompt when set
+const PASSPHRASE_ENV: &str = "KIMI_DB_PASSPHRASE";
+const MAX_PASSPHRASE_ATTEMPTS: usize = 3;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+/// Shown in place of content that cannot be decrypted
+pub const LOCKED_PLACEHOLDER: &str = "[encrypted]";
+
+/// Encrypts message content and summaries stored in SurrealDB.
+///
+/// Nonces are derived from a keyed hash of the plaintext, so equal plaintexts give
+/// equal ciphertexts. That keeps `content = $content` lookups (embedding updates)
+/// working at the cost of revealing which rows are identical.
+pub struct ContentCipher {
+    cipher: ChaCha20Poly1305,
+    nonce_key: [u8; 32],
+}
+
+impl ContentCipher {
+    fn from_passphrase(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let mut key_material = [0u8; 64];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key_material)
+            .map_err(|error| eyre!("Key derivation failed: {}", error))?;
+        let (cipher_key, nonce_key) = key_material.split_at(32);
+        Ok(Self {
+            cipher: ChaCha20Poly1305::new_from_slice(cipher_key)
+                .map_err(|_| eyre!("Invalid key length"))?,
+            nonce_key: nonce_key
+                .try_into()
+                .map_err(|_| eyre!("Invalid key length"))?,
+        })
+    }
+
+    #[must_use]
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        let digest = blake3::keyed_hash(&self.nonce_key, plaintext.as_bytes());
+        let mut nonce_bytes = [0u8; NONCE_LENGTH];
+        for (slot, byte) in nonce_bytes.iter_mut().zip(digest.as_bytes()) {
+            *slot = *byte;
+        }
+        let nonce = Nonce::from_slice(&nonce_bytes);
+        match self.cipher.encrypt(nonce, plaintext.as_bytes()) {
+            Ok(ciphertext) => {
+                let mut payload = nonce_bytes.to_vec();
+                payload.extend(ciphertext);
+                format!("{}{}", ENCRYPTED_PREFIX, BASE64.encode(payload))
+            }
+            // Encryption only fails for absurdly large inputs; never store those in the clear
+            Err(_) => LOCKED_PLACEHOLDER.to_string(),
+        }
+    }
+
+    /// Decrypts a stored value; values without the prefix are returned unchanged
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let payload = BASE64.decode(encoded)?;
+        if payload.len() < NONCE_LENGTH {
+            return Err(eyre!("Truncated ciphertext"));
+        }
+        let (nonce_bytes, ciphertext) = payload.split_at(NONCE_LENGTH);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| eyre!("Decryption failed"))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    version: u32,
+    salt: String,
+    /// `CHECK_PLAINTEXT` encrypted with the derived key, to detect a wrong passphrase
+    check: String,
+}
+
+/// Cipher for the active profile; replaced on profile switch
+static CIPHER: RwLock<Option<ContentCipher>> = RwLock::new(None);
+static ENCRYPT_WRITES: AtomicBool = AtomicBool::new(false);
+
+/// Salt and passphrase check for the active profile's stored content; without it
+/// encrypted rows cannot be opened
+pub fn key_file_path() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(KEY_FILE_NAME))
+}
+
+/// Unlocks database encryption for the active profile, at startup and on profile switch.
+///
+/// With `enabled` and no key file yet, asks for a new passphrase. Once a key file
+/// exists the passphrase is always required (even if encryption was switched off
+/// later) so previously encrypted rows stay readable; new rows are only encrypted
+/// while `enabled` is set. On error the previous profile's cipher stays in place.
+pub fn unlock(enabled: bool) -> Result<()> {
+    let path = key_file_path()?;
+    if !path.exists() {
+        if !enabled {
+            install(None, false)?;
+            return Ok(());
+        }
+        let cipher = create_key_file(&path)?;
+        install(Some(cipher), true)?;
+        return Ok(());
+    }
+
+    let key_file: KeyFile = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    if key_file.version > KEY_FILE_VERSION {
+        return Err(eyre!("{} version {} is not supported", KEY_FILE_NAME, key_file.version));
+    }
+    let salt = BASE64.decode(&key_file.salt)?;
+
+    for attempt in 1..=MAX_PASSPHRASE_ATTEMPTS {
+        let passphrase =
+            crate::services::secrets::prompt_passphrase(PASSPHRASE_ENV, "Database passphrase: ")?;
+        let cipher = ContentCipher::from_passphrase(&passphrase, &salt)?;
+        if cipher.decrypt(&key_file.check).ok().as_deref() == Some(CHECK_PLAINTEXT) {
+            install(Some(cipher), enabled)?;
+            return Ok(());
+        }
+        if std::env::var(PASSPHRASE_ENV).is_ok() {
+            return Err(eyre!("Wrong database passphrase in {}", PASSPHRASE_ENV));
+        }
+        let remaining = MAX_PASSPHRASE_ATTEMPTS - attempt;
+        if remaining > 0 {
+            eprintln!("Wrong passphrase, {} attempt(s) left.", remaining);
+        }
+    }
+    Err(eyre!(
+        "Wrong database passphrase. Conversations stay encrypted; restart kimi to try again."
+    ))
+}
+
+fn install(cipher: Option<ContentCipher>, encrypt_writes: bool) -> Result<()> {
+    let mut active = CIPHER
+        .write()
+        .map_err(|_| eyre!("Encryption state poisoned"))?;
+    *active = cipher;
+    ENCRYPT_WRITES.store(encrypt_writes, Ordering::Relaxed);
+    Ok(())
+}
+
+fn create_key_file(path: &PathBuf) -> Result<ContentCipher> {
+    println!("Database encryption is enabled. Choose a passphrase; it cannot be recovered.");
+    let passphrase =
+        crate::services::secrets::prompt_passphrase(PASSPHRASE_ENV, "New database passphrase: ")?;
+    if std::env::var(PASSPHRASE_ENV).is_err() {
+        let confirmation = crate::services::secrets::prompt_passphrase(
+            PASSPHRASE_ENV,
+            "Repeat passphrase: ",
+        )?;
+        if confirmation != passphrase {
+            return Err(eyre!("Passphrases did not match; encryption not set up"));
+        }
+    }
+
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = ContentCipher::from_passphrase(&passphrase, &salt)?;
+    let key_file = KeyFile {
+        version: KEY_FILE_VERSION,
+        salt: BASE64.encode(salt),
+        check: cipher.encrypt(CHECK_PLAINTEXT),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&key_file)?)?;
+    Ok(cipher)
+}
+
+/// True when stored content may be encrypted (a passphrase was entered this session)
+#[must_use]
+pub fn is_active() -> bool {
+    CIPHER.read().is_ok_and(|cipher| cipher.is_some())
+}
+
+/// Encrypts a value for storage when encryption is enabled
+#[must_use]
+pub fn seal(value: &str) -> String {
+    let Ok(cipher) = CIPHER.read() else {
+        return LOCKED_PLACEHOLDER.to_string();
+    };
+    match cipher.as_ref() {
+        Some(cipher) if ENCRYPT_WRITES.load(Ordering::Relaxed) => cipher.encrypt(value),
+        Some(_) | None => value.to_string(),
+    }
+}
+
+/// Decrypts a stored value; unreadable ciphertext becomes `LOCKED_PLACEHOLDER`
+#[must_use]
+pub fn open(value: String) -> String {
+    if !value.starts_with(ENCRYPTED_PREFIX) {
+        return value;
+    }
+    CIPHER
+        .read()
+        .ok()
+        .and_then(|cipher| cipher.as_ref().and_then(|cipher| cipher.decrypt(&value).ok()))
+        .unwrap_or_else(|| LOCKED_PLACEHOLDER.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encryption_is_deterministic_and_reversible() {
+        let salt = [3u8; SALT_LENGTH];
+        let Ok(cipher) = ContentCipher::from_passphrase("hunter2", &salt) else {
+            panic!("key derivation failed");
+        };
+        let sealed = cipher.encrypt("remember the milk");
+        assert!(sealed.starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(sealed, cipher.encrypt("remember the milk"));
+        assert_eq!(cipher.decrypt(&sealed).ok().as_deref(), Some("remember the milk"));
+        assert_eq!(cipher.decrypt("plain row").ok().as_deref(), Some("plain row"));
+
+        let Ok(other) = ContentCipher::from_passphrase("hunter3", &salt) else {
+            panic!("key derivation failed");
+        };
+        assert!(other.decrypt(&sealed).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use chrono::{Datelike, NaiveDate};
+use std::ops::Range;
+
+/// Characters around a word that belong to the sentence, not to a path or date
+const WORD_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', '(', ')', '[', ']', '"', '\'', '`', '*', '<', '>'];
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+const RELATIVE_DAY_WORDS: [&str; 3] = ["today", "tomorrow", "tonight"];
+
+const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+/// Something in a message that a quick action can work on
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityKind {
+    Url,
+    Path,
+    Date(NaiveDate),
+}
+
+/// A detected entity with its char range in the message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub kind: EntityKind,
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityAction {
+    Open,
+    Download,
+    Convert,
+    Remind,
+    Copy,
+}
+
+impl EntityAction {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            EntityAction::Open => "Open",
+            EntityAction::Download => "Download",
+            EntityAction::Convert => "Convert",
+            EntityAction::Remind => "Add reminder",
+            EntityAction::Copy => "Copy",
+        }
+    }
+}
+
+/// Actions offered for `entity`; past dates get no reminder
+#[must_use]
+pub fn actions_for(entity: &Entity, today: NaiveDate) -> Vec<EntityAction> {
+    match entity.kind {
+        EntityKind::Url => vec![EntityAction::Open, EntityAction::Download, EntityAction::Copy],
+        EntityKind::Path => vec![EntityAction::Open, EntityAction::Convert, EntityAction::Copy],
+        EntityKind::Date(date) if date >= today => vec![EntityAction::Remind, EntityAction::Copy],
+        EntityKind::Date(_) => vec![EntityAction::Copy],
+    }
+}
+
+/// URLs, file paths and dates in `text`, in order of appearance
+#[must_use]
+pub fn find_entities(text: &str) -> Vec<Entity> {
+    find_entities_on(text, chrono::Local::now().date_naive())
+}
+
+/// Like [`find_entities`], resolving month-day dates without a year against `today`
+#[must_use]
+pub fn find_entities_on(text: &str, today: NaiveDate) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = crate::services::links::find_urls(text)
+        .into_iter()
+        .map(|(range, url)| Entity { kind: EntityKind::Url, range, text: url })
+        .collect();
+
+    let words = words_with_ranges(text);
+    let mut index = 0;
+    while let Some((range, word)) = words.get(index) {
+        let overlaps_url = entities.iter().any(|entity| entity.range.start < range.end && range.start < entity.range.end);
+        if overlaps_url {
+            index += 1;
+            continue;
+        }
+        if is_path(word) {
+            entities.push(Entity { kind: EntityKind::Path, range: range.clone(), text: word.clone() });
+            index += 1;
+            continue;
+        }
+        if let Some((date, used)) = date_at(&words, index, today) {
+            let end = words.get(index + used - 1).map_or(range.end, |(last, _)| last.end);
+            let text: String = text.chars().skip(range.start).take(end - range.start).collect();
+            entities.push(Entity { kind: EntityKind::Date(date), range: range.start..end, text });
+            index += used;
+            continue;
+        }
+        index += 1;
+    }
+
+    entities.sort_by_key(|entity| entity.range.start);
+    entities
+}
+
+/// Whitespace-separated words stripped of surrounding punctuation, with their char ranges
+fn words_with_ranges(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut words = Vec::new();
+    let mut char_index = 0;
+    for token in text.split_inclusive(char::is_whitespace) {
+        let token_chars = token.chars().count();
+        let leading = token.chars().take_while(|c| WORD_PUNCTUATION.contains(c)).count();
+        let word: String = token
+            .trim_end()
+            .trim_start_matches(WORD_PUNCTUATION)
+            .trim_end_matches(WORD_PUNCTUATION)
+            .to_string();
+        if !word.is_empty() {
+            let start = char_index + leading;
+            words.push((start..start + word.chars().count(), word));
+        }
+        char_index += token_chars;
+    }
+    words
+}
+
+/// `/usr/bin/env`, `~/notes.md`, `./build.sh` — a lone `/command` is not a path
+fn is_path(word: &str) -> bool {
+    if let Some(rest) = word.strip_prefix("~/").or_else(|| word.strip_prefix("./")).or_else(|| word.strip_prefix("../")) {
+        return !rest.is_empty();
+    }
+    word.strip_prefix('/')
+        .is_some_and(|rest| rest.contains('/') && !rest.starts_with('/') && !rest.ends_with('/'))
+}
+
+/// A date starting at word `index` and the number of words it spans
+fn date_at(words: &[(Range<usize>, String)], index: usize, today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let word = |offset: usize| words.get(index + offset).map(|(_, word)| word.to_lowercase());
+    let first = word(0)?;
+
+    if let Ok(date) = NaiveDate::parse_from_str(&first, "%Y-%m-%d") {
+        return Some((date, 1));
+    }
+    if RELATIVE_DAY_WORDS.contains(&first.as_str()) {
+        let date = crate::services::dates::parse_date_reference(&first.replace("tonight", "today"))?.as_date()?;
+        return Some((date, 1));
+    }
+    if let Some(second) = word(1) {
+        // "next Friday", "this Monday"
+        if ["next", "this", "on"].contains(&first.as_str()) && WEEKDAYS.contains(&second.as_str()) {
+            let phrase = if first == "on" { second } else { format!("{} {}", first, second) };
+            let date = crate::services::dates::parse_date_reference(&phrase)?.as_date()?;
+            return Some((date, 2));
+        }
+        // "March 5", "5 March"
+        let month_day = month_number(&first)
+            .zip(day_number(&second))
+            .or_else(|| month_number(&second).zip(day_number(&first)));
+        if let Some((month, day)) = month_day {
+            let year = word(2).and_then(|year| year.parse::<i32>().ok()).filter(|year| (1900..=2200).contains(year));
+            let date = match year {
+                Some(year) => NaiveDate::from_ymd_opt(year, month, day).map(|date| (date, 3)),
+                None => next_occurrence(month, day, today).map(|date| (date, 2)),
+            };
+            return date;
+        }
+        // "in 3 days"
+        if first == "in"
+            && let Some(third) = word(2)
+            && ["day", "days"].contains(&third.as_str())
+            && second.parse::<u32>().is_ok()
+        {
+            let date = crate::services::dates::parse_date_reference(&format!("in {} days", second))?.as_date()?;
+            return Some((date, 3));
+        }
+    }
+    None
+}
+
+fn month_number(word: &str) -> Option<u32> {
+    MONTHS
+        .iter()
+        .position(|month| word == *month || (word.len() == 3 && month.starts_with(word)))
+        .and_then(|position| u32::try_from(position + 1).ok())
+}
+
+fn day_number(word: &str) -> Option<u32> {
+    // "5th", "21st"
+    let digits = word.trim_end_matches(['s', 't', 'n', 'd', 'r', 'h']);
+    digits.parse::<u32>().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// This year's `month`/`day`, or next year's once it has passed
+fn next_occurrence(month: u32, day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_urls_paths_and_dates() {
+        let Some(today) = NaiveDate::from_ymd_opt(2026, 10, 17) else {
+            panic!("bad test date");
+        };
+        let text = "Saved to `~/notes/plan.md`, see https://example.com/2026-01-01. \
+Due 2026-11-02 or March 5; /usage is a command and /etc/hosts a path.";
+        let entities = find_entities_on(text, today);
+        let kinds: Vec<_> = entities.iter().map(|entity| (entity.kind.clone(), entity.text.as_str())).collect();
+        let (Some(november), Some(march)) = (
+            NaiveDate::from_ymd_opt(2026, 11, 2),
+            NaiveDate::from_ymd_opt(2027, 3, 5),
+        ) else {
+            panic!("bad test date");
+        };
+        assert_eq!(
+            kinds,
+            vec![
+                (EntityKind::Path, "~/notes/plan.md"),
+                (EntityKind::Url, "https://example.com/2026-01-01"),
+                (EntityKind::Date(november), "2026-11-02"),
+                (EntityKind::Date(march), "March 5"),
+                (EntityKind::Path, "/etc/hosts"),
+            ]
+        );
+        let Some(path) = entities.first() else {
+            panic!("no path found");
+        };
+        assert_eq!(text.chars().skip(path.range.start).take(path.range.len()).collect::<String>(), path.text);
+
+        let Some(past) = NaiveDate::from_ymd_opt(2026, 1, 1) else {
+            panic!("bad test date");
+        };
+        let old = Entity { kind: EntityKind::Date(past), range: 0..10, text: "2026-01-01".to_string() };
+        assert_eq!(actions_for(&old, today), vec![EntityAction::Copy]);
+    }
+}
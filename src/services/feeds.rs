@@ -0,0 +1,271 @@
+use chrono::{DateTime, FixedOffset};
+use color_eyre::{Result, eyre::eyre};
+use reqwest::blocking::Client;
+
+/// Characters of an item's description passed to the briefing prompt
+const SUMMARY_MAX_CHARS: usize = 300;
+
+// ── Data types ──────────────────────────────────────────────────────────────
+
+/// One RSS `<item>` or Atom `<entry>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedItem {
+    pub feed: String,
+    pub title: String,
+    pub link: String,
+    /// guid / id, falling back to the link; used to remember seen items
+    pub key: String,
+    pub published: Option<DateTime<FixedOffset>>,
+    pub summary: String,
+}
+
+// ── Loading ─────────────────────────────────────────────────────────────────
+
+/// Fetches and parses every configured feed, newest items first.
+/// Feeds that fail to load are skipped; an error is returned only when all of them fail.
+pub fn fetch_feed_items(urls: &[String]) -> Result<Vec<FeedItem>> {
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .user_agent("kimi")
+        .build()?;
+
+    let mut items = Vec::new();
+    let mut failures = Vec::new();
+    let urls: Vec<&str> = urls.iter().map(|url| url.trim()).filter(|url| !url.is_empty()).collect();
+    for url in &urls {
+        match client.get(*url).send().and_then(reqwest::blocking::Response::error_for_status) {
+            Ok(response) => items.extend(parse_feed(&response.text()?, url)),
+            Err(error) => failures.push(format!("{}: {}", url, error)),
+        }
+    }
+
+    if items.is_empty() && !failures.is_empty() && failures.len() == urls.len() {
+        return Err(eyre!("Could not load feeds ({})", failures.join("; ")));
+    }
+    items.sort_by_key(|item| std::cmp::Reverse(item.published));
+    Ok(items)
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────────
+
+/// Parses an RSS 2.0 or Atom document; `source` names the feed when it has no title
+#[must_use]
+pub fn parse_feed(xml: &str, source: &str) -> Vec<FeedItem> {
+    let (item_tag, is_atom) = if find_open_tag(xml, "entry", 0).is_some() {
+        ("entry", true)
+    } else {
+        ("item", false)
+    };
+    let header_end = find_open_tag(xml, item_tag, 0).map_or(xml.len(), |(start, _)| start);
+    let feed = xml
+        .get(..header_end)
+        .and_then(|header| element_text(header, "title"))
+        .unwrap_or_else(|| source.to_string());
+
+    elements(xml, item_tag)
+        .into_iter()
+        .filter_map(|block| {
+            let title = element_text(block, "title")?;
+            let link = if is_atom {
+                atom_link(block)
+            } else {
+                element_text(block, "link")
+            }
+            .unwrap_or_default();
+            let key = element_text(block, if is_atom { "id" } else { "guid" })
+                .filter(|key| !key.is_empty())
+                .unwrap_or_else(|| if link.is_empty() { title.clone() } else { link.clone() });
+            let published = ["pubDate", "published", "updated", "dc:date"]
+                .iter()
+                .filter_map(|tag| element_text(block, tag))
+                .find_map(|date| {
+                    DateTime::parse_from_rfc2822(&date)
+                        .or_else(|_| DateTime::parse_from_rfc3339(&date))
+                        .ok()
+                });
+            let summary = ["description", "summary", "content"]
+                .iter()
+                .find_map(|tag| element_text(block, tag))
+                .map(|text| strip_html(&text))
+                .map(|text| text.chars().take(SUMMARY_MAX_CHARS).collect())
+                .unwrap_or_default();
+            Some(FeedItem {
+                feed: feed.clone(),
+                title,
+                link,
+                key,
+                published,
+                summary,
+            })
+        })
+        .collect()
+}
+
+/// Finds `<tag>` or `<tag attr=…>` at or after `from`; returns (tag start, content start)
+fn find_open_tag(xml: &str, tag: &str, from: usize) -> Option<(usize, usize)> {
+    let needle = format!("<{}", tag);
+    let mut search_from = from;
+    loop {
+        let start = search_from + xml.get(search_from..)?.find(&needle)?;
+        let after = start + needle.len();
+        let next = xml.get(after..)?.chars().next()?;
+        if next == '>' || next == '/' || next.is_whitespace() {
+            let close = after + xml.get(after..)?.find('>')?;
+            return Some((start, close + 1));
+        }
+        search_from = after;
+    }
+}
+
+/// Inner text of every `<tag>…</tag>` in the document
+fn elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let closing = format!("</{}>", tag);
+    let mut blocks = Vec::new();
+    let mut from = 0;
+    while let Some((_, content_start)) = find_open_tag(xml, tag, from) {
+        let Some(end) = xml.get(content_start..).and_then(|rest| rest.find(&closing)) else {
+            break;
+        };
+        if let Some(block) = xml.get(content_start..content_start + end) {
+            blocks.push(block);
+        }
+        from = content_start + end + closing.len();
+    }
+    blocks
+}
+
+/// Decoded text of the first `<tag>` in `block`
+fn element_text(block: &str, tag: &str) -> Option<String> {
+    let (_, content_start) = find_open_tag(block, tag, 0)?;
+    // Self-closing tags have no text
+    if block.get(..content_start)?.ends_with("/>") {
+        return None;
+    }
+    let closing = format!("</{}>", tag);
+    let end = block.get(content_start..)?.find(&closing)?;
+    let raw = block.get(content_start..content_start + end)?.trim();
+    let text = match raw.strip_prefix("<![CDATA[").and_then(|rest| rest.strip_suffix("]]>")) {
+        Some(cdata) => cdata.to_string(),
+        None => decode_entities(raw),
+    };
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// The `href` of an Atom entry's alternate (or first) `<link>`
+fn atom_link(block: &str) -> Option<String> {
+    let mut from = 0;
+    let mut first = None;
+    while let Some((start, end)) = find_open_tag(block, "link", from) {
+        let tag = block.get(start..end)?;
+        let href = attribute(tag, "href");
+        let rel = attribute(tag, "rel");
+        if rel.as_deref().is_none_or(|rel| rel == "alternate") && href.is_some() {
+            return href;
+        }
+        first = first.or(href);
+        from = end;
+    }
+    first
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    ['"', '\''].iter().find_map(|quote| {
+        let needle = format!("{}={}", name, quote);
+        let start = tag.find(&needle)? + needle.len();
+        let end = tag.get(start..)?.find(*quote)?;
+        tag.get(start..start + end).map(decode_entities)
+    })
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Drops markup from an HTML description and collapses whitespace
+fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ── Briefing ────────────────────────────────────────────────────────────────
+
+/// Prompt asking the chat model for a short briefing that leads with what matches `interests`
+#[must_use]
+pub fn format_briefing_prompt(items: &[FeedItem], interests: &[String]) -> String {
+    let mut prompt = String::from(
+        "Write a short news briefing from these new feed items. Lead with the ones that match my \
+         interests, group related items, give each a sentence or two and keep the links. \
+         Skip items that are clearly irrelevant to me and say how many you skipped.\n",
+    );
+    if !interests.is_empty() {
+        prompt.push_str(&format!("\nMy interests: {}\n", interests.join(", ")));
+    }
+    prompt.push_str("\nItems:\n");
+    for (index, item) in items.iter().enumerate() {
+        prompt.push_str(&format!("{}. [{}] {}", index + 1, item.feed, item.title));
+        if let Some(published) = item.published {
+            prompt.push_str(&format!(" ({})", published.format("%b %d")));
+        }
+        if !item.summary.is_empty() {
+            prompt.push_str(&format!(" — {}", item.summary));
+        }
+        if !item.link.is_empty() {
+            prompt.push_str(&format!(" <{}>", item.link));
+        }
+        prompt.push('\n');
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_rss_and_atom_items() {
+        let rss = r#"<?xml version="1.0"?><rss><channel><title>Root &amp; Branch</title>
+            <item><title><![CDATA[Rust 2.0?]]></title><link>https://example.com/a</link>
+            <guid isPermaLink="false">a-1</guid><pubDate>Fri, 16 Oct 2026 09:00:00 +0000</pubDate>
+            <description>&lt;p&gt;Big &lt;b&gt;news&lt;/b&gt;&lt;/p&gt;</description></item>
+            <item><title>No guid</title><link>https://example.com/b</link></item>
+            </channel></rss>"#;
+        let items = parse_feed(rss, "https://example.com/feed");
+        assert_eq!(items.len(), 2);
+        let Some(first) = items.first() else {
+            panic!("expected an item");
+        };
+        assert_eq!(first.feed, "Root & Branch");
+        assert_eq!(first.title, "Rust 2.0?");
+        assert_eq!(first.key, "a-1");
+        assert_eq!(first.summary, "Big news");
+        assert!(first.published.is_some());
+        assert!(items.iter().any(|item| item.key == "https://example.com/b"));
+
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Blog</title>
+            <entry><title>Post</title><link rel="self" href="https://x.dev/self"/>
+            <link href="https://x.dev/post"/><id>tag:x.dev,2026:1</id>
+            <updated>2026-10-16T08:00:00Z</updated><summary>Short</summary></entry></feed>"#;
+        let items = parse_feed(atom, "atom");
+        assert_eq!(items.len(), 1);
+        assert!(items.iter().all(|item| item.link == "https://x.dev/post" && item.feed == "Blog"));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::config::FileSearchConfig;
+use color_eyre::{Result, eyre::eyre};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Matching lines kept per file
+const LINES_PER_FILE: usize = 3;
+/// Longest line rg prints before eliding it
+const MAX_LINE_COLUMNS: &str = "200";
+
+/// A file that matched, with the lines that matched inside it (empty for name-only hits)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMatch {
+    pub path: PathBuf,
+    pub lines: Vec<(u64, String)>,
+}
+
+/// Searches file contents with ripgrep and file names with fd under the configured roots.
+/// Only paths inside a root are returned and the result count is capped by `max_results`.
+pub fn search_files(config: &FileSearchConfig, pattern: &str) -> Result<Vec<FileMatch>> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(eyre!("Empty search pattern"));
+    }
+    let roots = allowed_roots(config);
+    if roots.is_empty() {
+        return Err(eyre!(
+            "File search not configured. Add directories under [files] roots in config.toml"
+        ));
+    }
+
+    let mut matches: Vec<FileMatch> = Vec::new();
+    for root in &roots {
+        for (path, line, text) in run_ripgrep(root, pattern, config.max_results)? {
+            if !is_allowed(&path, &roots) {
+                continue;
+            }
+            match matches.iter_mut().find(|existing| existing.path == path) {
+                Some(existing) => {
+                    if existing.lines.len() < LINES_PER_FILE {
+                        existing.lines.push((line, text));
+                    }
+                }
+                None => matches.push(FileMatch {
+                    path,
+                    lines: vec![(line, text)],
+                }),
+            }
+        }
+        // Name matches fill whatever room content matches left
+        for path in run_fd(root, pattern, config.max_results) {
+            if is_allowed(&path, &roots) && !matches.iter().any(|existing| existing.path == path) {
+                matches.push(FileMatch {
+                    path,
+                    lines: Vec::new(),
+                });
+            }
+        }
+    }
+    matches.truncate(config.max_results);
+    Ok(matches)
+}
+
+/// Configured roots that exist, with `~` expanded and symlinks resolved
+fn allowed_roots(config: &FileSearchConfig) -> Vec<PathBuf> {
+    config
+        .roots
+        .iter()
+        .map(|root| crate::platform::expand_home(root.trim()))
+        .filter_map(|root| root.canonicalize().ok())
+        .collect()
+}
+
+fn is_allowed(path: &Path, roots: &[PathBuf]) -> bool {
+    path.canonicalize()
+        .is_ok_and(|path| roots.iter().any(|root| path.starts_with(root)))
+}
+
+fn run_ripgrep(root: &Path, pattern: &str, max_results: usize) -> Result<Vec<(PathBuf, u64, String)>> {
+    let output = Command::new("rg")
+        .args(["--null", "--line-number", "--no-heading", "--color", "never", "--smart-case"])
+        .args(["--max-count", &LINES_PER_FILE.to_string()])
+        .args(["--max-columns", MAX_LINE_COLUMNS, "--max-columns-preview"])
+        .arg("--fixed-strings")
+        .arg("--")
+        .arg(pattern)
+        .arg(root)
+        .output()
+        .map_err(|error| eyre!("Failed to run ripgrep (rg): {}", error))?;
+    // Exit code 1 only means nothing matched
+    if !output.status.success() && output.status.code() != Some(1) {
+        return Err(eyre!("ripgrep failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_rg_line)
+        .take(max_results * LINES_PER_FILE)
+        .collect())
+}
+
+/// fd is optional; without it only file contents are searched
+fn run_fd(root: &Path, pattern: &str, max_results: usize) -> Vec<PathBuf> {
+    ["fd", "fdfind"]
+        .iter()
+        .find_map(|program| {
+            Command::new(program)
+                .args(["--type", "f", "--fixed-strings", "--color", "never"])
+                .args(["--max-results", &max_results.to_string()])
+                .arg("--")
+                .arg(pattern)
+                .arg(root)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+        })
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses `path\0line:text` as printed by `rg --null --line-number`
+fn parse_rg_line(line: &str) -> Option<(PathBuf, u64, String)> {
+    let (path, rest) = line.split_once('\0')?;
+    let (number, text) = rest.split_once(':')?;
+    Some((PathBuf::from(path), number.parse().ok()?, text.trim().to_string()))
+}
+
+/// Lists matches for the agent or the chat, stopping at `max_chars`
+#[must_use]
+pub fn format_file_matches(pattern: &str, matches: &[FileMatch], max_chars: usize) -> String {
+    if matches.is_empty() {
+        return format!("No files matching '{}'.", pattern);
+    }
+    let mut output = format!("{} file(s) matching '{}':", matches.len(), pattern);
+    for (shown, file) in matches.iter().enumerate() {
+        let mut block = format!("\n{}", file.path.display());
+        for (line, text) in &file.lines {
+            block.push_str(&format!("\n  {}: {}", line, text));
+        }
+        if output.len() + block.len() > max_chars {
+            output.push_str(&format!("\n… {} more file(s) not shown", matches.len() - shown));
+            break;
+        }
+        output.push_str(&block);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_ripgrep_lines_and_caps_the_listing() {
+        let Some((path, line, text)) = parse_rg_line("/notes/a: b.md\u{0}12:  let x = 1;") else {
+            panic!("expected a match");
+        };
+        assert_eq!(path, PathBuf::from("/notes/a: b.md"));
+        assert_eq!((line, text.as_str()), (12, "let x = 1;"));
+        assert!(parse_rg_line("no separator").is_none());
+
+        let matches: Vec<FileMatch> = (0..10)
+            .map(|index| FileMatch {
+                path: PathBuf::from(format!("/notes/{}.md", index)),
+                lines: vec![(1, "x".repeat(50))],
+            })
+            .collect();
+        let listing = format_file_matches("x", &matches, 300);
+        assert!(listing.len() < 400);
+        assert!(listing.contains("more file(s) not shown"));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::config::FinanceConfig;
+use color_eyre::{Result, eyre::eyre};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const REQUEST_TIMEOUT_SECS: u64 = 8;
+/// Quotes kept in memory; the oldest is dropped beyond this
+const MAX_CACHED_QUOTES: usize = 32;
+/// Words that make an upper-case word a ticker rather than an acronym
+const QUOTE_WORDS: [&str; 7] = ["price", "stock", "stocks", "quote", "shares", "share", "trading"];
+
+/// Latest price of a stock, fund or crypto pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub symbol: String,
+    pub price: f64,
+    pub currency: String,
+    pub previous_close: Option<f64>,
+    /// Unix seconds of the last trade
+    pub market_time: Option<i64>,
+}
+
+impl Quote {
+    /// Change against the previous close in percent
+    #[must_use]
+    pub fn change_percent(&self) -> Option<f64> {
+        self.previous_close
+            .filter(|close| *close > 0.0)
+            .map(|close| (self.price - close) / close * 100.0)
+    }
+}
+
+struct CachedRates {
+    fetched_at: Instant,
+    /// Units of each currency per 1 EUR, like `[calculator] currency_rates`
+    rates: HashMap<String, f64>,
+}
+
+struct CachedQuote {
+    fetched_at: Instant,
+    quote: Quote,
+}
+
+static RATES: Mutex<Option<CachedRates>> = Mutex::new(None);
+static QUOTES: Mutex<Vec<CachedQuote>> = Mutex::new(Vec::new());
+
+fn client() -> Result<Client> {
+    Ok(Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent("Mozilla/5.0 (kimi)")
+        .build()?)
+}
+
+// ── Exchange rates ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct RatesResponse {
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    base_code: Option<String>,
+    #[serde(default)]
+    rates: HashMap<String, f64>,
+}
+
+/// Reads `{"base": "EUR", "rates": {...}}` as answered by Frankfurter, exchangerate.host
+/// and open.er-api.com, and rebases it to 1 EUR
+fn parse_rates(body: &str) -> Result<HashMap<String, f64>> {
+    let response: RatesResponse = serde_json::from_str(body)?;
+    let base = response
+        .base
+        .or(response.base_code)
+        .unwrap_or_else(|| "EUR".to_string())
+        .to_uppercase();
+    let mut rates: HashMap<String, f64> = response
+        .rates
+        .into_iter()
+        .filter(|(_, rate)| *rate > 0.0)
+        .map(|(code, rate)| (code.to_uppercase(), rate))
+        .collect();
+    rates.entry(base).or_insert(1.0);
+    let euro = rates
+        .get("EUR")
+        .copied()
+        .ok_or_else(|| eyre!("Exchange rates do not include EUR"))?;
+    Ok(rates.into_iter().map(|(code, rate)| (code, rate / euro)).collect())
+}
+
+/// Live rates per 1 EUR, reused for `rates_cache_minutes`
+pub fn live_rates(config: &FinanceConfig) -> Result<HashMap<String, f64>> {
+    let max_age = Duration::from_secs(config.rates_cache_minutes.saturating_mul(60));
+    if let Ok(cache) = RATES.lock()
+        && let Some(cached) = cache.as_ref()
+        && cached.fetched_at.elapsed() < max_age
+    {
+        return Ok(cached.rates.clone());
+    }
+    let body = client()?.get(config.rates_url.trim()).send()?.error_for_status()?.text()?;
+    let rates = parse_rates(&body)?;
+    if let Ok(mut cache) = RATES.lock() {
+        *cache = Some(CachedRates {
+            fetched_at: Instant::now(),
+            rates: rates.clone(),
+        });
+    }
+    Ok(rates)
+}
+
+/// Rates for a conversion: the configured ones with live rates on top, or only the
+/// configured ones when live rates are off or unreachable
+#[must_use]
+pub fn conversion_rates(config: &FinanceConfig, configured: &HashMap<String, f64>) -> HashMap<String, f64> {
+    let mut rates = configured.clone();
+    if !config.live_rates {
+        return rates;
+    }
+    match live_rates(config) {
+        Ok(live) => rates.extend(live),
+        Err(error) => tracing::warn!(%error, "live exchange rates unavailable, using [calculator] rates"),
+    }
+    rates
+}
+
+// ── Quotes ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct ChartResponse {
+    chart: Chart,
+}
+
+#[derive(Debug, Deserialize)]
+struct Chart {
+    #[serde(default)]
+    result: Option<Vec<ChartResult>>,
+    #[serde(default)]
+    error: Option<ChartError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartError {
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartResult {
+    meta: ChartMeta,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChartMeta {
+    symbol: String,
+    #[serde(default)]
+    currency: Option<String>,
+    regular_market_price: f64,
+    #[serde(default)]
+    chart_previous_close: Option<f64>,
+    #[serde(default)]
+    regular_market_time: Option<i64>,
+}
+
+fn parse_quote(body: &str) -> Result<Quote> {
+    let response: ChartResponse = serde_json::from_str(body)?;
+    if let Some(error) = response.chart.error {
+        return Err(eyre!("Quote lookup failed: {}", error.description));
+    }
+    let meta = response
+        .chart
+        .result
+        .and_then(|results| results.into_iter().next())
+        .ok_or_else(|| eyre!("No quote returned"))?
+        .meta;
+    Ok(Quote {
+        symbol: meta.symbol,
+        price: meta.regular_market_price,
+        currency: meta.currency.unwrap_or_default(),
+        previous_close: meta.chart_previous_close,
+        market_time: meta.regular_market_time,
+    })
+}
+
+/// Latest quote for a ticker ("AAPL", "BTC-USD", "CEZ.PR"), reused for `quote_cache_minutes`
+pub fn quote(config: &FinanceConfig, symbol: &str) -> Result<Quote> {
+    let symbol = symbol.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err(eyre!("No ticker symbol given"));
+    }
+    let max_age = Duration::from_secs(config.quote_cache_minutes.saturating_mul(60));
+    if let Ok(cache) = QUOTES.lock()
+        && let Some(cached) = cache
+            .iter()
+            .find(|cached| cached.quote.symbol == symbol && cached.fetched_at.elapsed() < max_age)
+    {
+        return Ok(cached.quote.clone());
+    }
+    let url = format!("{}/{}", config.quote_url.trim().trim_end_matches('/'), symbol);
+    let body = client()?
+        .get(url)
+        .query(&[("range", "1d"), ("interval", "1d")])
+        .send()?
+        .error_for_status()?
+        .text()?;
+    let quote = parse_quote(&body)?;
+    if let Ok(mut cache) = QUOTES.lock() {
+        cache.retain(|cached| cached.quote.symbol != quote.symbol);
+        if cache.len() >= MAX_CACHED_QUOTES {
+            cache.remove(0);
+        }
+        cache.push(CachedQuote {
+            fetched_at: Instant::now(),
+            quote: quote.clone(),
+        });
+    }
+    Ok(quote)
+}
+
+/// "AAPL: 231.40 USD (+1.2% since the previous close, as of 2026-10-16 22:00)"
+#[must_use]
+pub fn format_quote(quote: &Quote) -> String {
+    let mut details = Vec::new();
+    if let Some(change) = quote.change_percent() {
+        details.push(format!("{:+.1}% since the previous close", change));
+    }
+    if let Some(time) = quote
+        .market_time
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+    {
+        details.push(format!(
+            "as of {}",
+            time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        ));
+    }
+    let mut line = format!("{}: {:.2} {}", quote.symbol, quote.price, quote.currency);
+    if !details.is_empty() {
+        line.push_str(&format!(" ({})", details.join(", ")));
+    }
+    line.trim_end().to_string()
+}
+
+/// Ticker in "AAPL price" or "how are MSFT shares doing", written in capitals;
+/// such questions go to the quote tool instead of web search
+#[must_use]
+pub fn quote_symbol(query: &str) -> Option<String> {
+    let words: Vec<&str> = query
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && !matches!(c, '.' | '-')))
+        .collect();
+    let asks_price = words
+        .iter()
+        .any(|word| QUOTE_WORDS.contains(&word.to_lowercase().as_str()));
+    if !asks_price {
+        return None;
+    }
+    words
+        .iter()
+        .find(|word| {
+            let letters = word.chars().filter(char::is_ascii_alphabetic).count();
+            (1..=8).contains(&letters)
+                && word.len() <= 10
+                && word.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || matches!(c, '.' | '-'))
+                && word.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+                && *word != "I"
+        })
+        .map(|word| (*word).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_are_rebased_to_euro() {
+        let Ok(rates) = parse_rates(r#"{"base_code": "USD", "rates": {"USD": 1, "EUR": 0.5, "CZK": 12.5}}"#) else {
+            panic!("rates should parse");
+        };
+        assert_eq!(rates.get("EUR").copied(), Some(1.0));
+        assert_eq!(rates.get("USD").copied(), Some(2.0));
+        assert_eq!(rates.get("CZK").copied(), Some(25.0));
+        let Ok(frankfurter) = parse_rates(r#"{"amount": 1.0, "base": "EUR", "rates": {"CZK": 25.0}}"#) else {
+            panic!("rates should parse");
+        };
+        assert_eq!(frankfurter.get("EUR").copied(), Some(1.0));
+    }
+
+    #[test]
+    fn test_quotes_parse_and_format() {
+        let body = r#"{"chart": {"result": [{"meta": {"symbol": "AAPL", "currency": "USD",
+            "regularMarketPrice": 202.0, "chartPreviousClose": 200.0}}], "error": null}}"#;
+        let Ok(quote) = parse_quote(body) else {
+            panic!("quote should parse");
+        };
+        assert_eq!(format_quote(&quote), "AAPL: 202.00 USD (+1.0% since the previous close)");
+        assert!(parse_quote(r#"{"chart": {"result": null, "error": {"description": "No data found"}}}"#).is_err());
+    }
+
+    #[test]
+    fn test_finds_tickers_in_price_questions() {
+        assert_eq!(quote_symbol("AAPL price?"), Some("AAPL".to_string()));
+        assert_eq!(quote_symbol("how are BTC-USD shares doing"), Some("BTC-USD".to_string()));
+        assert_eq!(quote_symbol("what's the price of a coffee in Prague"), None);
+        assert_eq!(quote_symbol("AAPL earnings call"), None);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Provides fuzzy matching for the command palette and other search features.
 
+use std::ops::Range;
+
 /// Calculate the Levenshtein (edit) distance between two strings.
 /// This is the minimum number of single-character edits (insertions, deletions,
 /// or substitutions) required to change one string into the other.
@@ -142,10 +144,61 @@ fn is_subsequence(query: &str, target: &str) -> bool {
     query_chars.peek().is_none()
 }
 
+/// Byte ranges in `text` where any whitespace-separated term of `query` occurs,
+/// compared case-insensitively. Overlapping and adjacent matches are merged.
+///
+/// Used to highlight search terms in result previews.
+#[must_use]
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let terms: Vec<Vec<char>> = query
+        .split_whitespace()
+        .map(|term| term.chars().flat_map(char::to_lowercase).collect())
+        .collect();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (start, _) in text.char_indices() {
+        for term in &terms {
+            let Some(end) = match_at(text, start, term) else {
+                continue;
+            };
+            match ranges.last_mut() {
+                Some(last) if last.end >= start => last.end = last.end.max(end),
+                Some(_) | None => ranges.push(start..end),
+            }
+        }
+    }
+    ranges
+}
+
+/// End of the case-insensitive match of `term` starting at byte `start`
+fn match_at(text: &str, start: usize, term: &[char]) -> Option<usize> {
+    let mut remaining = term;
+    for (offset, character) in text.get(start..)?.char_indices() {
+        if remaining.is_empty() {
+            return Some(start + offset);
+        }
+        for lower in character.to_lowercase() {
+            let (first, rest) = remaining.split_first()?;
+            if *first != lower {
+                return None;
+            }
+            remaining = rest;
+        }
+    }
+    remaining.is_empty().then_some(text.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_match_ranges() {
+        assert_eq!(match_ranges("Rust and rustc", "rust"), vec![0..4, 9..13]);
+        assert_eq!(match_ranges("Čeština je fajn", "ČEŠ fajn"), vec![0..5, 13..17]);
+        assert_eq!(match_ranges("abcd", "ab bc"), vec![0..3]);
+        assert!(match_ranges("hello", "  ").is_empty());
+    }
+
     #[test]
     fn test_levenshtein_identical() {
         assert_eq!(levenshtein_distance("hello", "hello"), 0);
@@ -0,0 +1,175 @@
+use color_eyre::{Result, eyre::eyre};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Dirty files listed in the prompt before the rest are counted
+const MAX_DIRTY_FILES: usize = 30;
+
+/// Branch, recent commits and uncommitted files of the repository Kimi was launched in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoState {
+    pub root: PathBuf,
+    pub branch: String,
+    /// `short-hash date subject`, newest first
+    pub commits: Vec<String>,
+    /// `(status, path)` from `git status --porcelain`
+    pub dirty: Vec<(String, String)>,
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .map_err(|error| eyre!("Failed to run git: {}", error))?;
+    if !output.status.success() {
+        return Err(eyre!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Top-level directory of the work tree containing `dir`, if any
+#[must_use]
+pub fn discover_repo(dir: &Path) -> Option<PathBuf> {
+    git(dir, &["rev-parse", "--show-toplevel"])
+        .ok()
+        .map(|root| PathBuf::from(root.trim()))
+        .filter(|root| !root.as_os_str().is_empty())
+}
+
+pub fn read_repo_state(root: &Path, commit_count: usize) -> Result<RepoState> {
+    let branch = git(root, &["branch", "--show-current"])?.trim().to_string();
+    let branch = if branch.is_empty() {
+        // Detached HEAD
+        format!("detached at {}", git(root, &["rev-parse", "--short", "HEAD"])?.trim())
+    } else {
+        branch
+    };
+    // A fresh repository has no commits yet
+    let commits = git(root, &["log", &format!("-{}", commit_count), "--date=short", "--format=%h %ad %s"])
+        .map(|log| log.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+    let dirty = parse_porcelain_status(&git(root, &["status", "--porcelain"])?);
+    Ok(RepoState {
+        root: root.to_path_buf(),
+        branch,
+        commits,
+        dirty,
+    })
+}
+
+fn parse_porcelain_status(status: &str) -> Vec<(String, String)> {
+    status
+        .lines()
+        .filter_map(|line| {
+            let code = line.get(..2)?.trim();
+            let path = line.get(3..)?.trim();
+            (!path.is_empty()).then(|| (code.to_string(), path.to_string()))
+        })
+        .collect()
+}
+
+/// Diff for the `git_diff` tool: empty target = uncommitted changes, `today` = commits made
+/// today plus uncommitted changes, otherwise a commit or `a..b` range. Cut at `max_chars`.
+pub fn diff(root: &Path, target: &str, max_chars: usize) -> Result<String> {
+    let target = target.trim();
+    if target.starts_with('-') || target.chars().any(char::is_whitespace) {
+        return Err(eyre!("Invalid diff target '{}'", target));
+    }
+    let text = match target {
+        "" => git(root, &["diff", "HEAD", "--stat", "--patch"])?,
+        "today" => {
+            let committed = git(root, &["log", "--since=midnight", "--reverse", "--stat", "--patch", "--format=commit %h %s"])?;
+            let uncommitted = git(root, &["diff", "HEAD", "--stat", "--patch"])?;
+            format!("{}\nUncommitted:\n{}", committed, uncommitted)
+        }
+        range if range.contains("..") => git(root, &["diff", "--stat", "--patch", range, "--"])?,
+        commit => git(root, &["show", "--stat", "--patch", "--format=commit %h %an %ad%n%s%n%b", commit, "--"])?,
+    };
+    if text.trim().is_empty() {
+        return Ok("No changes.".to_string());
+    }
+    if text.chars().count() <= max_chars {
+        return Ok(text);
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    Ok(format!("{}\n… diff truncated at {} characters", cut, max_chars))
+}
+
+/// Whether a chat message is about the code or repository Kimi was started in
+#[must_use]
+pub fn is_repo_query(query: &str) -> bool {
+    let lowered = query.to_lowercase();
+    let triggers = [
+        "i changed",
+        "i've changed",
+        "what changed",
+        "my changes",
+        "uncommitted",
+        "commits",
+        "last commit",
+        "commit message",
+        "branch",
+        "diff",
+        "this repo",
+        "the repo",
+        "repository",
+        "git ",
+        "working tree",
+        "staged",
+        "pull request",
+    ];
+    triggers.iter().any(|trigger| lowered.contains(trigger))
+}
+
+/// Formats the repository state as a system-prompt block
+#[must_use]
+pub fn format_repo_prompt(state: &RepoState) -> String {
+    let mut prompt = format!(
+        "--- Git repository ---\nThe user launched you in {} (branch {}).",
+        state.root.display(),
+        state.branch
+    );
+    if !state.commits.is_empty() {
+        prompt.push_str("\nRecent commits:");
+        for commit in &state.commits {
+            prompt.push_str(&format!("\n- {}", commit));
+        }
+    }
+    if state.dirty.is_empty() {
+        prompt.push_str("\nWorking tree clean.");
+    } else {
+        prompt.push_str(&format!("\nUncommitted files ({}):", state.dirty.len()));
+        for (status, path) in state.dirty.iter().take(MAX_DIRTY_FILES) {
+            prompt.push_str(&format!("\n- {} {}", status, path));
+        }
+        if state.dirty.len() > MAX_DIRTY_FILES {
+            prompt.push_str(&format!("\n- … {} more", state.dirty.len() - MAX_DIRTY_FILES));
+        }
+    }
+    prompt.push_str("\nUse the git_diff tool to read actual changes before describing them.");
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_status_and_spots_repo_questions() {
+        let dirty = parse_porcelain_status(" M src/main.rs\n?? notes/todo.md\nR  old.rs -> new.rs\n");
+        assert_eq!(
+            dirty,
+            vec![
+                ("M".to_string(), "src/main.rs".to_string()),
+                ("??".to_string(), "notes/todo.md".to_string()),
+                ("R".to_string(), "old.rs -> new.rs".to_string()),
+            ]
+        );
+        assert!(is_repo_query("Summarize what I changed today"));
+        assert!(is_repo_query("which branch am I on?"));
+        assert!(!is_repo_query("what's the weather like"));
+        assert!(diff(Path::new("."), "--output=/tmp/x", 100).is_err());
+    }
+}
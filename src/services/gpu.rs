@@ -0,0 +1,91 @@
+use std::fs;
+use std::process::Command;
+
+/// Headroom for KV cache and runtime buffers on top of the weights
+const VRAM_OVERHEAD_FACTOR: f64 = 1.2;
+
+/// Whether a model is expected to load fully into GPU memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VramFit {
+    Fits,
+    TooLarge,
+}
+
+/// Total VRAM across detected GPUs in bytes (NVIDIA via nvidia-smi, AMD via sysfs)
+#[must_use]
+pub fn detect_vram_bytes() -> Option<u64> {
+    detect_nvidia_vram().or_else(detect_amd_vram)
+}
+
+fn detect_nvidia_vram() -> Option<u64> {
+    let output = Command::new("nvidia-smi")
+        .args(["--query-gpu=memory.total", "--format=csv,noheader,nounits"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_nvidia_smi_mib(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Sums the per-GPU MiB values printed by nvidia-smi, one per line
+fn parse_nvidia_smi_mib(output: &str) -> Option<u64> {
+    let total_mib: u64 = output
+        .lines()
+        .filter_map(|line| line.trim().parse::<u64>().ok())
+        .sum();
+    (total_mib > 0).then(|| total_mib * 1024 * 1024)
+}
+
+fn detect_amd_vram() -> Option<u64> {
+    let total: u64 = fs::read_dir("/sys/class/drm")
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            fs::read_to_string(entry.path().join("device").join("mem_info_vram_total")).ok()
+        })
+        .filter_map(|value| value.trim().parse::<u64>().ok())
+        .sum();
+    (total > 0).then_some(total)
+}
+
+/// Compares a model's weight size (plus overhead) with available VRAM
+#[must_use]
+pub fn estimate_fit(model_bytes: u64, vram_bytes: u64) -> VramFit {
+    let needed = model_bytes as f64 * VRAM_OVERHEAD_FACTOR;
+    if needed <= vram_bytes as f64 {
+        VramFit::Fits
+    } else {
+        VramFit::TooLarge
+    }
+}
+
+/// Formats a byte count as "4.7 GB" / "812 MB"
+#[must_use]
+pub fn format_gigabytes(bytes: u64) -> String {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let value = bytes as f64 / GB;
+    if value >= 1.0 {
+        format!("{:.1} GB", value)
+    } else {
+        format!("{:.0} MB", value * 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sums_nvidia_smi_output() {
+        assert_eq!(parse_nvidia_smi_mib("8192\n8192\n"), Some(16 * 1024 * 1024 * 1024));
+        assert_eq!(parse_nvidia_smi_mib("No devices were found"), None);
+    }
+
+    #[test]
+    fn test_leaves_headroom_when_estimating_fit() {
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(estimate_fit(5 * gib, 8 * gib), VramFit::Fits);
+        assert_eq!(estimate_fit(7 * gib, 8 * gib), VramFit::TooLarge);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::agents::ollama::OllamaClient;
+use crate::config::{Config, RequestConfig};
+use reqwest::blocking::Client;
+use std::time::{Duration, Instant};
+
+/// Replies slower than this mark a reachable service as degraded
+const SLOW_RESPONSE: Duration = Duration::from_millis(1500);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const VENICE_MODELS_URL: &str = "https://api.venice.ai/api/v1/models?type=text";
+
+/// Footer dot color: green, yellow or red
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthLevel {
+    Ok,
+    Degraded,
+    Down,
+}
+
+/// Result of one check, with a suggested fix when it is not healthy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceHealth {
+    pub name: &'static str,
+    pub level: HealthLevel,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+/// Endpoints and keys to check; hosted providers without a key are skipped
+#[derive(Debug, Clone, Default)]
+pub struct HealthTargets {
+    pub ollama_url: String,
+    pub embeddings_url: String,
+    pub embeddings_model: String,
+    pub venice_key: String,
+    pub gab_key: String,
+    pub gab_base_url: String,
+}
+
+impl HealthTargets {
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            ollama_url: config.ollama.url.clone(),
+            embeddings_url: config.embeddings.ollama_url.clone(),
+            embeddings_model: config.embeddings.model.clone(),
+            venice_key: config.venice.api_key.clone(),
+            gab_key: config.gab.api_key.clone(),
+            gab_base_url: config.gab.base_url.clone(),
+        }
+    }
+}
+
+/// Pings every configured backend; blocking, run it off the UI thread
+#[must_use]
+pub fn check_all(targets: &HealthTargets) -> Vec<ServiceHealth> {
+    let mut results = vec![check_ollama(&targets.ollama_url), check_embeddings(targets)];
+    let client = Client::builder().timeout(REQUEST_TIMEOUT).build().ok();
+    if let Some(client) = &client {
+        if !targets.venice_key.trim().is_empty() {
+            results.push(check_hosted(client, "Venice", VENICE_MODELS_URL, &targets.venice_key));
+        }
+        if !targets.gab_key.trim().is_empty() {
+            let url = format!("{}/models", targets.gab_base_url.trim_end_matches('/'));
+            results.push(check_hosted(client, "Gab", &url, &targets.gab_key));
+        }
+    }
+    results
+}
+
+fn check_ollama(url: &str) -> ServiceHealth {
+    let started = Instant::now();
+    let reachable = OllamaClient::new(url, &RequestConfig::default()).is_available();
+    let elapsed = started.elapsed();
+    if !reachable {
+        return ServiceHealth {
+            name: "Ollama",
+            level: HealthLevel::Down,
+            detail: format!("No answer from {}", url),
+            fix: Some("Start it with `ollama serve`, or fix [ollama] url in config.toml".to_string()),
+        };
+    }
+    timed("Ollama", elapsed, format!("Reachable at {}", url))
+}
+
+fn check_embeddings(targets: &HealthTargets) -> ServiceHealth {
+    let client = OllamaClient::new(&targets.embeddings_url, &RequestConfig::default());
+    let started = Instant::now();
+    if !client.is_available() {
+        return ServiceHealth {
+            name: "Embeddings",
+            level: HealthLevel::Down,
+            detail: format!("No Ollama at {}", targets.embeddings_url),
+            fix: Some("Start Ollama, or fix [embeddings] ollama_url in config.toml".to_string()),
+        };
+    }
+    match client.check_model(&targets.embeddings_model) {
+        Ok(true) => timed(
+            "Embeddings",
+            started.elapsed(),
+            format!("{} installed", targets.embeddings_model),
+        ),
+        Ok(false) => ServiceHealth {
+            name: "Embeddings",
+            level: HealthLevel::Degraded,
+            detail: format!("Model {} is not installed; memory search falls back to keywords", targets.embeddings_model),
+            fix: Some(format!("ollama pull {}", targets.embeddings_model)),
+        },
+        Err(error) => ServiceHealth {
+            name: "Embeddings",
+            level: HealthLevel::Degraded,
+            detail: format!("Could not list models: {}", error),
+            fix: None,
+        },
+    }
+}
+
+fn check_hosted(client: &Client, name: &'static str, url: &str, api_key: &str) -> ServiceHealth {
+    let started = Instant::now();
+    let result = client.get(url).bearer_auth(api_key.trim()).send();
+    let elapsed = started.elapsed();
+    match result {
+        Ok(response) => classify_status(name, response.status().as_u16(), elapsed),
+        Err(error) => ServiceHealth {
+            name,
+            level: HealthLevel::Down,
+            detail: if error.is_timeout() {
+                format!("Timed out after {}s", REQUEST_TIMEOUT.as_secs())
+            } else {
+                format!("Unreachable: {}", error)
+            },
+            fix: Some("Check your internet connection".to_string()),
+        },
+    }
+}
+
+/// Maps an HTTP status from a hosted API to a health entry
+fn classify_status(name: &'static str, status: u16, elapsed: Duration) -> ServiceHealth {
+    match status {
+        200..=299 => timed(name, elapsed, "API key accepted".to_string()),
+        401 | 403 => ServiceHealth {
+            name,
+            level: HealthLevel::Down,
+            detail: format!("API key rejected (HTTP {})", status),
+            fix: Some(format!("Enter a new key for {} under /connect", name)),
+        },
+        429 => ServiceHealth {
+            name,
+            level: HealthLevel::Degraded,
+            detail: "Rate limited (HTTP 429)".to_string(),
+            fix: Some("Wait a moment; requests will go through again".to_string()),
+        },
+        _ => ServiceHealth {
+            name,
+            level: if status >= 500 { HealthLevel::Down } else { HealthLevel::Degraded },
+            detail: format!("HTTP {}", status),
+            fix: (status >= 500).then(|| "The provider is having problems; try again later".to_string()),
+        },
+    }
+}
+
+fn timed(name: &'static str, elapsed: Duration, detail: String) -> ServiceHealth {
+    let millis = elapsed.as_millis();
+    if elapsed > SLOW_RESPONSE {
+        ServiceHealth {
+            name,
+            level: HealthLevel::Degraded,
+            detail: format!("{} but slow ({} ms)", detail, millis),
+            fix: Some("Replies may lag; check load on the machine or network".to_string()),
+        }
+    } else {
+        ServiceHealth {
+            name,
+            level: HealthLevel::Ok,
+            detail: format!("{} ({} ms)", detail, millis),
+            fix: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statuses_map_to_levels_with_fixes() {
+        let fast = Duration::from_millis(80);
+        assert_eq!(classify_status("Venice", 200, fast).level, HealthLevel::Ok);
+        assert_eq!(classify_status("Venice", 200, Duration::from_secs(3)).level, HealthLevel::Degraded);
+        let rejected = classify_status("Gab", 401, fast);
+        assert_eq!(rejected.level, HealthLevel::Down);
+        assert!(rejected.fix.is_some_and(|fix| fix.contains("/connect")));
+        assert_eq!(classify_status("Gab", 429, fast).level, HealthLevel::Degraded);
+        assert_eq!(classify_status("Gab", 503, fast).level, HealthLevel::Down);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::config::HomeAssistantConfig;
+use color_eyre::{Result, eyre::eyre};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const REQUEST_CONNECT_TIMEOUT_SECS: u64 = 5;
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+/// Entities listed for one question before asking the model to be more specific
+const MAX_MATCHES: usize = 15;
+
+// ── Data types ──────────────────────────────────────────────────────────────
+
+/// One entity from `GET /api/states`
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntityState {
+    pub entity_id: String,
+    pub state: String,
+    #[serde(default)]
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub last_changed: String,
+}
+
+impl EntityState {
+    /// `friendly_name` attribute, falling back to the entity id
+    #[must_use]
+    pub fn friendly_name(&self) -> &str {
+        self.attributes
+            .get("friendly_name")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or(&self.entity_id)
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).and_then(serde_json::Value::as_str)
+    }
+}
+
+/// A service the agent asked to call; nothing runs until the user confirms it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceCall {
+    pub domain: String,
+    pub service: String,
+    pub entity_id: String,
+    /// Friendly name shown in the confirmation overlay
+    pub entity_name: String,
+}
+
+impl ServiceCall {
+    /// Builds a call from `light.turn_off` or a bare `turn_off`, which takes the entity's domain
+    #[must_use]
+    pub fn new(service: &str, entity: &EntityState) -> Option<Self> {
+        let service = service.trim();
+        let (domain, service) = match service.split_once('.') {
+            Some((domain, service)) => (domain, service),
+            None => (entity.entity_id.split_once('.')?.0, service),
+        };
+        if domain.is_empty() || service.is_empty() {
+            return None;
+        }
+        Some(Self {
+            domain: domain.to_string(),
+            service: service.to_string(),
+            entity_id: entity.entity_id.clone(),
+            entity_name: entity.friendly_name().to_string(),
+        })
+    }
+
+    /// `light.turn_off → Living room lights`
+    #[must_use]
+    pub fn describe(&self) -> String {
+        format!("{}.{} → {}", self.domain, self.service, self.entity_name)
+    }
+}
+
+// ── Requests ────────────────────────────────────────────────────────────────
+
+fn client() -> Result<Client> {
+    Ok(Client::builder()
+        .connect_timeout(Duration::from_secs(REQUEST_CONNECT_TIMEOUT_SECS))
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent("kimi")
+        .build()?)
+}
+
+fn endpoint(config: &HomeAssistantConfig, path: &str) -> Result<String> {
+    if config.url.trim().is_empty() || config.api_key.trim().is_empty() {
+        return Err(eyre!("Home Assistant not configured. Add its URL and token under Connect"));
+    }
+    Ok(format!("{}/api{}", config.url.trim().trim_end_matches('/'), path))
+}
+
+/// Fetches the current state of every entity
+pub fn fetch_states(config: &HomeAssistantConfig) -> Result<Vec<EntityState>> {
+    let states = client()?
+        .get(endpoint(config, "/states")?)
+        .bearer_auth(config.api_key.trim())
+        .send()?
+        .error_for_status()?
+        .json()?;
+    Ok(states)
+}
+
+/// Calls a service on one entity and returns the states it changed
+pub fn call_service(config: &HomeAssistantConfig, call: &ServiceCall) -> Result<Vec<EntityState>> {
+    let path = format!("/services/{}/{}", call.domain, call.service);
+    let changed = client()?
+        .post(endpoint(config, &path)?)
+        .bearer_auth(config.api_key.trim())
+        .json(&serde_json::json!({ "entity_id": call.entity_id }))
+        .send()?
+        .error_for_status()?
+        .json()?;
+    Ok(changed)
+}
+
+// ── Matching ────────────────────────────────────────────────────────────────
+
+/// Entities matching `query`: an exact entity id, else every word of the query found
+/// in the entity id or friendly name ("balcony door" finds `binary_sensor.balcony_door`)
+#[must_use]
+pub fn find_entities<'a>(states: &'a [EntityState], query: &str) -> Vec<&'a EntityState> {
+    let query = query.trim().to_lowercase();
+    if let Some(exact) = states.iter().find(|state| state.entity_id == query) {
+        return vec![exact];
+    }
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+    states
+        .iter()
+        .filter(|state| {
+            let haystack = format!(
+                "{} {}",
+                state.entity_id.replace(['_', '.'], " "),
+                state.friendly_name()
+            )
+            .to_lowercase();
+            words.iter().all(|word| haystack.contains(word))
+        })
+        .collect()
+}
+
+// ── Formatting ──────────────────────────────────────────────────────────────
+
+/// One entity per line: name, id, state with its unit, device class and when it last changed
+#[must_use]
+pub fn format_states(query: &str, matches: &[&EntityState]) -> String {
+    if matches.is_empty() {
+        return format!("No Home Assistant entity matches '{}'.", query);
+    }
+    let mut output = String::new();
+    if matches.len() > MAX_MATCHES {
+        output.push_str(&format!(
+            "{} entities match '{}'; showing {}. Ask with a more specific name.\n",
+            matches.len(),
+            query,
+            MAX_MATCHES
+        ));
+    }
+    for state in matches.iter().take(MAX_MATCHES) {
+        output.push_str(&format!(
+            "- {} ({}): {}{}",
+            state.friendly_name(),
+            state.entity_id,
+            state.state,
+            state
+                .attribute("unit_of_measurement")
+                .map(|unit| format!(" {}", unit))
+                .unwrap_or_default()
+        ));
+        if let Some(class) = state.attribute("device_class") {
+            output.push_str(&format!(" [{}]", class));
+        }
+        if let Some(changed) = state.last_changed.get(..16) {
+            output.push_str(&format!(", since {}", changed.replace('T', " ")));
+        }
+        output.push('\n');
+    }
+    output.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(entity_id: &str, name: &str, state: &str) -> EntityState {
+        let mut attributes = serde_json::Map::new();
+        attributes.insert("friendly_name".to_string(), name.into());
+        EntityState {
+            entity_id: entity_id.to_string(),
+            state: state.to_string(),
+            attributes,
+            last_changed: "2026-10-17T07:45:12.000000+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_finds_entities_by_id_or_name_words() {
+        let states = vec![
+            entity("binary_sensor.balcony_door", "Balcony Door", "on"),
+            entity("light.living_room", "Living room lights", "on"),
+            entity("light.living_room_lamp", "Reading lamp", "off"),
+        ];
+        let ids = |query: &str| -> Vec<String> {
+            find_entities(&states, query)
+                .iter()
+                .map(|state| state.entity_id.clone())
+                .collect()
+        };
+        assert_eq!(ids("balcony door"), ["binary_sensor.balcony_door"]);
+        assert_eq!(ids("light.living_room"), ["light.living_room"]);
+        assert_eq!(ids("living room"), ["light.living_room", "light.living_room_lamp"]);
+        assert!(ids("kitchen").is_empty());
+        assert!(ids("  ").is_empty());
+    }
+
+    #[test]
+    fn test_service_calls_take_the_entity_domain_when_omitted() {
+        let lights = entity("light.living_room", "Living room lights", "on");
+        let Some(call) = ServiceCall::new("turn_off", &lights) else {
+            panic!("expected a call");
+        };
+        assert_eq!((call.domain.as_str(), call.service.as_str()), ("light", "turn_off"));
+        assert_eq!(call.describe(), "light.turn_off → Living room lights");
+        let Some(call) = ServiceCall::new("homeassistant.toggle", &lights) else {
+            panic!("expected a call");
+        };
+        assert_eq!(call.domain, "homeassistant");
+        assert!(ServiceCall::new(".", &lights).is_none());
+    }
+
+    #[test]
+    fn test_formats_state_with_unit_and_change_time() {
+        let mut sensor = entity("sensor.balcony_temperature", "Balcony temperature", "11.5");
+        sensor.attributes.insert("unit_of_measurement".to_string(), "°C".into());
+        assert_eq!(
+            format_states("balcony", &[&sensor]),
+            "- Balcony temperature (sensor.balcony_temperature): 11.5 °C, since 2026-10-17 07:45"
+        );
+        assert!(format_states("attic", &[]).starts_with("No Home Assistant entity"));
+    }
+}
@@ -510,8 +510,7 @@ fn identity_state_path() -> Result<PathBuf> {
 }
 
 fn project_data_dir() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-    Ok(current_dir.join("data"))
+    crate::profile::data_dir()
 }
 
 fn format_identity_prompt(state: &IdentityState) -> String {
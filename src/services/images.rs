@@ -0,0 +1,188 @@
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use color_eyre::{Result, eyre::eyre};
+use directories::UserDirs;
+use serde::Deserialize;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::agents::retry;
+use crate::config::ImagesConfig;
+
+const VENICE_IMAGE_URL: &str = "https://api.venice.ai/api/v1/image/generate";
+const PICTURES_SUBDIR: &str = "kimi";
+/// Longest prompt fragment kept in a file name
+const SLUG_MAX_CHARS: usize = 40;
+
+#[derive(Debug, Deserialize)]
+struct VeniceImageResponse {
+    images: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIImageResponse {
+    data: Vec<OpenAIImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIImage {
+    b64_json: Option<String>,
+}
+
+/// One terminal row of a preview: each cell is the top and bottom pixel of a '▀'
+pub type PreviewRow = Vec<([u8; 3], [u8; 3])>;
+
+/// Generates a picture for `prompt` and saves it as PNG in the pictures directory
+pub fn generate(config: &ImagesConfig, venice_api_key: &str, prompt: &str) -> Result<PathBuf> {
+    let bytes = match config.provider.trim() {
+        "openai" => request_openai(config, prompt)?,
+        "venice" | "" => request_venice(config, venice_api_key, prompt)?,
+        other => return Err(eyre!("Unknown image provider: {}", other)),
+    };
+    // Re-encode so the file is a PNG whatever format the provider answered with
+    let image = image::load_from_memory(&bytes)?;
+    let directory = pictures_dir(config)?;
+    fs::create_dir_all(&directory)?;
+    let path = directory.join(file_name(&chrono::Local::now(), prompt));
+    image.save_with_format(&path, image::ImageFormat::Png)?;
+    Ok(path)
+}
+
+fn request_venice(config: &ImagesConfig, api_key: &str, prompt: &str) -> Result<Vec<u8>> {
+    if api_key.trim().is_empty() {
+        return Err(eyre!("Set [venice] api_key to generate images"));
+    }
+    let client = retry::build_client(&config.requests)?;
+    let body = json!({
+        "model": config.model,
+        "prompt": prompt,
+        "width": config.width,
+        "height": config.height,
+        "format": "png",
+    });
+    let response = retry::send_with_retry("Venice", &config.requests, None, || {
+        client.post(VENICE_IMAGE_URL).bearer_auth(api_key).json(&body).send()
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        let details = response.text().unwrap_or_default();
+        return Err(eyre!("Venice image API error: {} {}", status, details));
+    }
+    let payload: VeniceImageResponse = response.json()?;
+    let encoded = payload
+        .images
+        .into_iter()
+        .next()
+        .ok_or_else(|| eyre!("Venice returned no image"))?;
+    Ok(STANDARD.decode(encoded)?)
+}
+
+fn request_openai(config: &ImagesConfig, prompt: &str) -> Result<Vec<u8>> {
+    let base = config.url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return Err(eyre!("Set [images] url to use an OpenAI-compatible image server"));
+    }
+    let client = retry::build_client(&config.requests)?;
+    let body = json!({
+        "model": config.model,
+        "prompt": prompt,
+        "n": 1,
+        "size": format!("{}x{}", config.width, config.height),
+        "response_format": "b64_json",
+    });
+    let url = format!("{}/images/generations", base);
+    let response = retry::send_with_retry("Images", &config.requests, None, || {
+        let request = client.post(&url).json(&body);
+        if config.api_key.trim().is_empty() {
+            request.send()
+        } else {
+            request.bearer_auth(config.api_key.trim()).send()
+        }
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        let details = response.text().unwrap_or_default();
+        return Err(eyre!("Image API error: {} {}", status, details));
+    }
+    let payload: OpenAIImageResponse = response.json()?;
+    let encoded = payload
+        .data
+        .into_iter()
+        .find_map(|image| image.b64_json)
+        .ok_or_else(|| eyre!("Image server returned no image"))?;
+    Ok(STANDARD.decode(encoded)?)
+}
+
+/// `[images] directory`, or Pictures/kimi (~/Pictures/kimi without an XDG pictures dir)
+pub fn pictures_dir(config: &ImagesConfig) -> Result<PathBuf> {
+    let directory = config.directory.trim();
+    if !directory.is_empty() {
+        return Ok(crate::platform::expand_home(directory));
+    }
+    let user_dirs = UserDirs::new().ok_or_else(|| eyre!("Could not locate pictures directory"))?;
+    let pictures = user_dirs
+        .picture_dir()
+        .map_or_else(|| user_dirs.home_dir().join("Pictures"), Path::to_path_buf);
+    Ok(pictures.join(PICTURES_SUBDIR))
+}
+
+/// "20261017-142501-a-fox-in-the-snow.png"
+fn file_name(now: &chrono::DateTime<chrono::Local>, prompt: &str) -> String {
+    let slug = slugify(prompt);
+    let stamp = now.format("%Y%m%d-%H%M%S");
+    if slug.is_empty() {
+        format!("{}.png", stamp)
+    } else {
+        format!("{}-{}.png", stamp, slug)
+    }
+}
+
+fn slugify(prompt: &str) -> String {
+    let mut slug = String::new();
+    for character in prompt.chars() {
+        if slug.chars().count() >= SLUG_MAX_CHARS {
+            break;
+        }
+        if character.is_alphanumeric() {
+            slug.extend(character.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Downscales the picture at `path` to at most `columns` cells wide and `rows` tall
+pub fn preview(path: &Path, columns: u32, rows: u32) -> Result<Vec<PreviewRow>> {
+    let image = image::open(path)?;
+    // Every cell shows two pixels stacked, and cells are about twice as tall as wide
+    let thumbnail = image.thumbnail(columns.max(1), rows.max(1) * 2).to_rgb8();
+    let (width, height) = thumbnail.dimensions();
+    let pixel = |x: u32, y: u32| thumbnail.get_pixel_checked(x, y).map_or([0, 0, 0], |pixel| pixel.0);
+    Ok((0..height.div_ceil(2))
+        .map(|row| {
+            (0..width)
+                .map(|x| (pixel(x, row * 2), pixel(x, row * 2 + 1)))
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_file_names_carry_the_prompt() {
+        let Some(now) = chrono::Local.with_ymd_and_hms(2026, 10, 17, 14, 25, 1).single() else {
+            panic!("bad test date");
+        };
+        assert_eq!(
+            file_name(&now, "A fox, in the snow!"),
+            "20261017-142501-a-fox-in-the-snow.png"
+        );
+        assert_eq!(file_name(&now, "!!!"), "20261017-142501.png");
+        assert!(slugify(&"word ".repeat(30)).chars().count() <= SLUG_MAX_CHARS);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::config::JournalConfig;
+use crate::storage::ConversationSummary;
+use chrono::{NaiveDate, NaiveTime};
+use color_eyre::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where today's daily note lives: `<vault>/<folder>/<date>.md`
+#[must_use]
+pub fn daily_note_path(vault_path: &str, config: &JournalConfig, date: NaiveDate) -> PathBuf {
+    let mut path = PathBuf::from(vault_path);
+    if !config.folder.trim().is_empty() {
+        path.push(config.folder.trim());
+    }
+    path.push(format!("{}.md", date.format(&config.date_format)));
+    path
+}
+
+/// Fills `{{date}}`, `{{title}}` and `{{time}}` in a daily-note template
+#[must_use]
+pub fn render_template(template: &str, config: &JournalConfig, date: NaiveDate, time: NaiveTime) -> String {
+    let title = date.format(&config.date_format).to_string();
+    template
+        .replace("{{date}}", &date.format("%Y-%m-%d").to_string())
+        .replace("{{title}}", &title)
+        .replace("{{time}}", &time.format("%H:%M").to_string())
+}
+
+/// A list item stamped with the time; continuation lines are indented under it
+#[must_use]
+pub fn format_entry(time: NaiveTime, text: &str) -> String {
+    let mut lines = text.trim().lines();
+    let first = lines.next().unwrap_or_default();
+    let mut entry = format!("- {} {}", time.format("%H:%M"), first);
+    for line in lines {
+        entry.push_str("\n  ");
+        entry.push_str(line);
+    }
+    entry
+}
+
+/// One entry listing today's saved conversations by title, or None when there were none
+#[must_use]
+pub fn format_conversation_digest(
+    conversations: &[ConversationSummary],
+    date: NaiveDate,
+    time: NaiveTime,
+) -> Option<String> {
+    let day = date.format("%Y-%m-%d").to_string();
+    let titles: Vec<String> = conversations
+        .iter()
+        .filter(|conversation| conversation.created_at.starts_with(&day))
+        .filter_map(|conversation| {
+            let title = conversation.title()?;
+            let started = chrono::DateTime::parse_from_rfc3339(&conversation.created_at)
+                .map_or_else(|_| String::new(), |dt| format!("{} ", dt.format("%H:%M")));
+            Some(format!("{}{}", started, title.trim()))
+        })
+        .collect();
+    if titles.is_empty() {
+        return None;
+    }
+    let mut text = format!("Talked with Kimi about {} thing(s):", titles.len());
+    for title in titles.iter().rev() {
+        text.push('\n');
+        text.push_str("- ");
+        text.push_str(title);
+    }
+    Some(format_entry(time, &text))
+}
+
+/// Adds `entry` at the end of the `heading` section, creating the section when missing
+#[must_use]
+pub fn insert_under_heading(content: &str, heading: &str, entry: &str) -> String {
+    let heading = heading.trim();
+    let level = heading.chars().take_while(|c| *c == '#').count();
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(start) = lines.iter().position(|line| line.trim() == heading) else {
+        let mut updated = content.trim_end().to_string();
+        if !updated.is_empty() {
+            updated.push_str("\n\n");
+        }
+        updated.push_str(heading);
+        updated.push('\n');
+        updated.push_str(entry);
+        updated.push('\n');
+        return updated;
+    };
+
+    // The section ends at the next heading of the same or a higher level
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, line)| {
+            let hashes = line.chars().take_while(|c| *c == '#').count();
+            hashes > 0 && hashes <= level.max(1) && line.chars().nth(hashes) == Some(' ')
+        })
+        .map_or(lines.len(), |(index, _)| index);
+    // Keep the blank lines that separate this section from the next one
+    let mut insert_at = end;
+    while insert_at > start + 1
+        && lines
+            .get(insert_at - 1)
+            .is_some_and(|line| line.trim().is_empty())
+    {
+        insert_at -= 1;
+    }
+
+    let mut updated: Vec<&str> = Vec::with_capacity(lines.len() + 1);
+    updated.extend(lines.iter().take(insert_at));
+    updated.push(entry);
+    updated.extend(lines.iter().skip(insert_at));
+    let mut result = updated.join("\n");
+    result.push('\n');
+    result
+}
+
+/// Appends an entry to the daily note, creating the note from the template when needed.
+/// Returns the note path.
+pub fn append_to_daily_note(
+    vault_path: &str,
+    config: &JournalConfig,
+    entry: &str,
+) -> Result<PathBuf> {
+    if vault_path.trim().is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "Obsidian vault path not configured. Set vault_path in config.toml."
+        ));
+    }
+    let now = chrono::Local::now();
+    let (date, time) = (now.date_naive(), now.time());
+    let path = daily_note_path(vault_path, config, date);
+
+    let content = if path.exists() {
+        fs::read_to_string(&path)?
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        read_template(vault_path, config).map_or_else(
+            || format!("# {}\n", date.format(&config.date_format)),
+            |template| render_template(&template, config, date, time),
+        )
+    };
+
+    fs::write(&path, insert_under_heading(&content, &config.heading, entry))?;
+    Ok(path)
+}
+
+fn read_template(vault_path: &str, config: &JournalConfig) -> Option<String> {
+    let template = config.template.trim();
+    if template.is_empty() {
+        return None;
+    }
+    let mut path = Path::new(vault_path).join(template);
+    if path.extension().is_none() {
+        path.set_extension("md");
+    }
+    fs::read_to_string(path).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_land_at_the_end_of_their_section() {
+        let note = "# 2026-10-17\n\n## Kimi\n- 09:00 first\n\n## Tasks\n- [ ] call mum\n";
+        let updated = insert_under_heading(note, "## Kimi", "- 10:00 second");
+        assert_eq!(
+            updated,
+            "# 2026-10-17\n\n## Kimi\n- 09:00 first\n- 10:00 second\n\n## Tasks\n- [ ] call mum\n"
+        );
+
+        let created = insert_under_heading("# 2026-10-17\n", "## Kimi", "- 10:00 note");
+        assert_eq!(created, "# 2026-10-17\n\n## Kimi\n- 10:00 note\n");
+
+        let Some(time) = NaiveTime::from_hms_opt(21, 5, 0) else {
+            panic!("bad test time");
+        };
+        assert_eq!(format_entry(time, "line one\nline two"), "- 21:05 line one\n  line two");
+    }
+}
@@ -0,0 +1,65 @@
+/// Language codes Kimi can be told to answer in, with the name used in the prompt
+const LANGUAGES: [(&str, &str); 20] = [
+    ("en", "English"),
+    ("cs", "Czech"),
+    ("sk", "Slovak"),
+    ("pl", "Polish"),
+    ("de", "German"),
+    ("fr", "French"),
+    ("es", "Spanish"),
+    ("it", "Italian"),
+    ("pt", "Portuguese"),
+    ("nl", "Dutch"),
+    ("sv", "Swedish"),
+    ("da", "Danish"),
+    ("no", "Norwegian"),
+    ("fi", "Finnish"),
+    ("hu", "Hungarian"),
+    ("uk", "Ukrainian"),
+    ("ru", "Russian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("zh", "Chinese"),
+];
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Normalizes `cs`, `CS` or `czech` to the language code; `None` for unknown languages
+#[must_use]
+pub fn normalize(input: &str) -> Option<&'static str> {
+    let input = input.trim().to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(code, name)| *code == input || name.to_lowercase() == input)
+        .map(|(code, _)| *code)
+}
+
+/// English name of a language code, falling back to the code itself
+#[must_use]
+pub fn name(code: &str) -> &str {
+    LANGUAGES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map_or(code, |(_, name)| *name)
+}
+
+/// Comma-separated list of the supported codes, for usage messages
+#[must_use]
+pub fn supported_codes() -> String {
+    LANGUAGES.iter().map(|(code, _)| *code).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_codes_and_names() {
+        assert_eq!(normalize("cs"), Some("cs"));
+        assert_eq!(normalize(" Czech "), Some("cs"));
+        assert_eq!(normalize("DE"), Some("de"));
+        assert_eq!(normalize("klingon"), None);
+        assert_eq!(name("cs"), "Czech");
+        assert_eq!(name("xx"), "xx");
+    }
+}
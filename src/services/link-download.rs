@@ -4,9 +4,62 @@ use directories::UserDirs;
 use std::io::{BufRead, BufReader, Read as _};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
 
-/// Downloads a video using yt-dlp with real-time progress updates.
-pub fn download_video_with_progress(url: &str, mut on_progress: impl FnMut(u8)) -> Result<()> {
+/// Progress reported while yt-dlp runs
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownloadUpdate {
+    Progress { percent: u8, speed: Option<String> },
+    /// File yt-dlp is writing to; the merged output replaces per-format files
+    Destination(PathBuf),
+}
+
+/// How a download ended
+#[derive(Debug)]
+pub enum DownloadOutcome {
+    Completed(Option<PathBuf>),
+    Cancelled,
+}
+
+/// Final state of a download, as kept in the download history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl DownloadStatus {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Self {
+        match value {
+            "completed" => Self::Completed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Failed,
+        }
+    }
+}
+
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Downloads a video using yt-dlp with real-time progress updates. Setting `cancel`
+/// stops yt-dlp and returns `DownloadOutcome::Cancelled`.
+pub fn download_video_with_progress(
+    url: &str,
+    cancel: &AtomicBool,
+    mut on_update: impl FnMut(DownloadUpdate),
+) -> Result<DownloadOutcome> {
     let download_dir = resolve_download_dir()?;
     let output_template = download_dir.join("%(title)s.%(ext)s");
     let output_path = output_template
@@ -31,20 +84,46 @@ pub fn download_video_with_progress(url: &str, mut on_progress: impl FnMut(u8))
         })
     });
 
-    // Read stdout line-by-line for progress updates
+    // Stdout is read on its own thread so cancellation is noticed between lines
+    let (line_tx, line_rx) = mpsc::channel();
     if let Some(stdout) = child.stdout.take() {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines().map_while(Result::ok) {
-            if let Some(progress) = parse_progress_percent(&line) {
-                on_progress(progress);
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if line_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let mut destination = None;
+    loop {
+        match line_rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+            Ok(line) => {
+                if let Some(update) = parse_download_line(&line) {
+                    if let DownloadUpdate::Destination(path) = &update {
+                        destination = Some(path.clone());
+                    }
+                    on_update(update);
+                }
             }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        if cancel.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(DownloadOutcome::Cancelled);
         }
     }
 
     let status = child.wait()?;
     if status.success() {
-        on_progress(100);
-        Ok(())
+        on_update(DownloadUpdate::Progress {
+            percent: 100,
+            speed: None,
+        });
+        Ok(DownloadOutcome::Completed(destination))
     } else {
         let stderr_output = stderr_thread
             .and_then(|handle| handle.join().ok())
@@ -55,7 +134,7 @@ pub fn download_video_with_progress(url: &str, mut on_progress: impl FnMut(u8))
 }
 
 /// Resolves download directory: Downloads > Desktop > Home
-fn resolve_download_dir() -> Result<PathBuf> {
+pub(crate) fn resolve_download_dir() -> Result<PathBuf> {
     let user_dirs =
         UserDirs::new().ok_or_else(|| eyre!("Could not determine home directory"))?;
 
@@ -73,7 +152,7 @@ fn resolve_download_dir() -> Result<PathBuf> {
 }
 
 /// Extracts meaningful error messages from yt-dlp stderr output.
-fn extract_ytdlp_error(stderr: &str) -> String {
+pub(crate) fn extract_ytdlp_error(stderr: &str) -> String {
     // yt-dlp prefixes errors with "ERROR:"
     let error_lines: Vec<&str> = stderr
         .lines()
@@ -106,3 +185,57 @@ fn parse_progress_percent(line: &str) -> Option<u8> {
     let clamped = value.clamp(0.0, 100.0) as u8;
     Some(clamped)
 }
+
+/// Parses a yt-dlp output line into a progress or destination update.
+/// Progress lines look like: `[download]  45.3% of ~100.00MiB at  1.20MiB/s ETA 01:10`
+fn parse_download_line(line: &str) -> Option<DownloadUpdate> {
+    let line = line.trim();
+    if let Some(path) = line.strip_prefix("[download] Destination: ") {
+        return Some(DownloadUpdate::Destination(PathBuf::from(path.trim())));
+    }
+    if let Some(path) = line
+        .strip_prefix("[Merger] Merging formats into ")
+        .or_else(|| line.strip_prefix("[ExtractAudio] Destination: "))
+    {
+        return Some(DownloadUpdate::Destination(PathBuf::from(path.trim().trim_matches('"'))));
+    }
+    if let Some(path) = line
+        .strip_prefix("[download] ")
+        .and_then(|rest| rest.strip_suffix(" has already been downloaded"))
+    {
+        return Some(DownloadUpdate::Destination(PathBuf::from(path.trim())));
+    }
+    let percent = parse_progress_percent(line)?;
+    let speed = line
+        .split_whitespace()
+        .skip_while(|word| *word != "at")
+        .nth(1)
+        .filter(|speed| speed.ends_with("/s"))
+        .map(str::to_string);
+    Some(DownloadUpdate::Progress { percent, speed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_lines_report_progress_speed_and_destination() {
+        assert_eq!(
+            parse_download_line("[download]  45.3% of ~100.00MiB at  1.20MiB/s ETA 01:10"),
+            Some(DownloadUpdate::Progress {
+                percent: 45,
+                speed: Some("1.20MiB/s".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_download_line("[download] 100% of 10.00MiB in 00:00:05"),
+            Some(DownloadUpdate::Progress { percent: 100, speed: None })
+        );
+        assert_eq!(
+            parse_download_line("[Merger] Merging formats into \"/tmp/Talk.mp4\""),
+            Some(DownloadUpdate::Destination(PathBuf::from("/tmp/Talk.mp4")))
+        );
+        assert_eq!(parse_download_line("[youtube] abc: Downloading webpage"), None);
+    }
+}
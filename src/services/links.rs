@@ -0,0 +1,91 @@
+use color_eyre::{Result, eyre::eyre};
+use std::ffi::OsStr;
+use std::ops::Range;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const URL_SCHEMES: [&str; 2] = ["https://", "http://"];
+
+/// Characters that usually close a sentence or a markdown link rather than belong to the URL
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '}', '"', '\'', '>', '*', '`'];
+
+/// URLs in `text` with their char ranges
+#[must_use]
+pub fn find_urls(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut urls = Vec::new();
+    let mut char_index = 0;
+    for token in text.split_inclusive(char::is_whitespace) {
+        let token_chars = token.chars().count();
+        let word = token.trim_end();
+        let start_in_word = URL_SCHEMES
+            .iter()
+            .filter_map(|scheme| word.find(scheme))
+            .min();
+        if let Some(byte_start) = start_in_word
+            && let Some(candidate) = word.get(byte_start..)
+        {
+            let url = candidate.trim_end_matches(TRAILING_PUNCTUATION);
+            let is_bare_scheme = URL_SCHEMES.iter().any(|scheme| url.len() <= scheme.len());
+            if !is_bare_scheme {
+                let start = char_index + word.get(..byte_start).map_or(0, |prefix| prefix.chars().count());
+                urls.push((start..start + url.chars().count(), url.to_string()));
+            }
+        }
+        char_index += token_chars;
+    }
+    urls
+}
+
+/// The URL covering char `position` of `text`, if any
+#[must_use]
+pub fn url_at(text: &str, position: usize) -> Option<String> {
+    find_urls(text)
+        .into_iter()
+        .find(|(range, _)| range.contains(&position))
+        .map(|(_, url)| url)
+}
+
+/// Opens `url` in the default browser without waiting for it
+pub fn open_url(url: &str) -> Result<()> {
+    if !URL_SCHEMES.iter().any(|scheme| url.starts_with(scheme)) {
+        return Err(eyre!("Not a web URL: {}", url));
+    }
+    spawn_opener(OsStr::new(url))
+}
+
+/// Opens an existing file or directory with the desktop's default application
+pub fn open_path(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(eyre!("No such file: {}", path.display()));
+    }
+    spawn_opener(path.as_os_str())
+}
+
+fn spawn_opener(target: &OsStr) -> Result<()> {
+    let (opener, leading_args) = crate::platform::opener();
+    Command::new(opener)
+        .args(leading_args)
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| eyre!("Failed to run {}: {}", opener, error))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_urls_and_trims_punctuation() {
+        let text = "See (https://example.com/a?b=1). Or ~http://kimi.dev, not https:// alone";
+        let urls: Vec<String> = find_urls(text).into_iter().map(|(_, url)| url).collect();
+        assert_eq!(urls, vec!["https://example.com/a?b=1".to_string(), "http://kimi.dev".to_string()]);
+        assert_eq!(url_at(text, 6), Some("https://example.com/a?b=1".to_string()));
+        assert_eq!(url_at(text, 2), None);
+        assert_eq!(url_at("é https://x.io", 3), Some("https://x.io".to_string()));
+        assert!(open_url("file:///etc/passwd").is_err());
+    }
+}
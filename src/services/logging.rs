@@ -0,0 +1,57 @@
+use color_eyre::{Result, eyre::eyre};
+use directories::ProjectDirs;
+use std::fs;
+use std::path::PathBuf;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::LevelFilter;
+
+const LOG_FILE_PREFIX: &str = "kimi.log";
+/// Set `KIMI_LOG=debug` (or trace/warn/error) to change verbosity
+const LOG_LEVEL_ENV: &str = "KIMI_LOG";
+
+/// Directory of the rolling log files (`~/.local/share/kimi/logs` on Linux)
+pub fn log_dir() -> Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "kimi")
+        .ok_or_else(|| eyre!("Could not determine data directory"))?;
+    Ok(project_dirs.data_dir().join("logs"))
+}
+
+/// Installs a daily rolling file logger. Keep the returned guard alive for the
+/// whole process so buffered lines are flushed on exit.
+pub fn init_logging() -> Option<WorkerGuard> {
+    let directory = log_dir().ok()?;
+    fs::create_dir_all(&directory).ok()?;
+    let appender = tracing_appender::rolling::daily(&directory, LOG_FILE_PREFIX);
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let level = std::env::var(LOG_LEVEL_ENV)
+        .ok()
+        .and_then(|value| value.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::INFO);
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_max_level(level)
+        .try_init()
+        .ok()?;
+    Some(guard)
+}
+
+/// Returns the last `max_lines` lines of the newest log file
+pub fn read_log_tail(max_lines: usize) -> Result<Vec<String>> {
+    let directory = log_dir()?;
+    let newest = fs::read_dir(&directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .max()
+        .ok_or_else(|| eyre!("No log files in {}", directory.display()))?;
+
+    let content = fs::read_to_string(&newest)?;
+    let lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let start = lines.len().saturating_sub(max_lines);
+    Ok(lines.get(start..).map(<[String]>::to_vec).unwrap_or_default())
+}
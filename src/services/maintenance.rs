@@ -0,0 +1,210 @@
+use crate::storage::StorageManager;
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const STATE_FILE_NAME: &str = "maintenance.json";
+/// Keyword queries used to sample BM25 latency
+const SAMPLE_KEYWORD_QUERIES: [&str; 3] = ["project", "tomorrow", "remember"];
+const SAMPLE_RESULT_LIMIT: usize = 10;
+/// Days an ephemeral conversation is kept after it is marked
+pub const EPHEMERAL_DAYS: i64 = 7;
+/// How often the in-app scheduler deletes expired conversations
+pub const RETENTION_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Average query latency over the sample queries
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryLatency {
+    pub keyword_ms: f64,
+    pub vector_ms: Option<f64>,
+}
+
+/// Outcome of a garbage-collection + index rebuild run
+#[derive(Debug, Clone, Default)]
+pub struct ReindexReport {
+    pub orphaned_messages_removed: usize,
+    pub stale_embeddings_cleared: usize,
+    pub before: QueryLatency,
+    pub after: QueryLatency,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MaintenanceState {
+    last_reindex: Option<String>,
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(STATE_FILE_NAME))
+}
+
+fn load_state() -> MaintenanceState {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &MaintenanceState) -> Result<()> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Returns true when the last reindex is older than `interval_days` (0 disables scheduling)
+#[must_use]
+pub fn is_reindex_due(interval_days: u64) -> bool {
+    if interval_days == 0 {
+        return false;
+    }
+    let Some(last_run) = load_state()
+        .last_reindex
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(&value).ok())
+    else {
+        return true;
+    };
+    let elapsed = chrono::Local::now().signed_duration_since(last_run);
+    elapsed.num_days() >= i64::try_from(interval_days).unwrap_or(i64::MAX)
+}
+
+/// Removes orphaned messages and stale embeddings, rebuilds the MTREE and BM25
+/// indexes and measures query latency before and after.
+pub async fn run_reindex(storage: &StorageManager) -> Result<ReindexReport> {
+    let before = measure_query_latency(storage).await;
+    let orphaned_messages_removed = storage.delete_orphaned_messages().await?;
+    let stale_embeddings_cleared = storage.clear_stale_embeddings().await?;
+    storage.rebuild_message_indexes().await?;
+    let after = measure_query_latency(storage).await;
+
+    let mut state = load_state();
+    state.last_reindex = Some(chrono::Local::now().to_rfc3339());
+    save_state(&state)?;
+
+    Ok(ReindexReport {
+        orphaned_messages_removed,
+        stale_embeddings_cleared,
+        before,
+        after,
+    })
+}
+
+/// RFC 3339 timestamp `days` before `now`; conversations last updated earlier get archived
+#[must_use]
+pub fn archive_cutoff(now: chrono::DateTime<chrono::Local>, days: u64) -> String {
+    let days = i64::try_from(days).unwrap_or(i64::MAX);
+    now.checked_sub_signed(chrono::Duration::days(days))
+        .unwrap_or(now)
+        .to_rfc3339()
+}
+
+/// Archives conversations nobody has touched in `days` days (0 archives nothing)
+pub async fn archive_older_than(storage: &StorageManager, days: u64) -> Result<usize> {
+    if days == 0 {
+        return Ok(0);
+    }
+    storage
+        .archive_conversations_before(&archive_cutoff(chrono::Local::now(), days))
+        .await
+}
+
+/// RFC 3339 time an ephemeral conversation marked at `now` is deleted
+#[must_use]
+pub fn ephemeral_expiry(now: chrono::DateTime<chrono::Local>) -> String {
+    now.checked_add_signed(chrono::Duration::days(EPHEMERAL_DAYS))
+        .unwrap_or(now)
+        .to_rfc3339()
+}
+
+/// Whole days left before `expires_at` (0 on the last day); `None` when unparseable
+#[must_use]
+pub fn days_until_expiry(expires_at: &str, now: chrono::DateTime<chrono::Local>) -> Option<i64> {
+    let expires_at = chrono::DateTime::parse_from_rfc3339(expires_at).ok()?;
+    Some(expires_at.signed_duration_since(now).num_days().max(0))
+}
+
+/// Deletes ephemeral conversations past their expiry
+pub async fn expire_conversations(storage: &StorageManager) -> Result<usize> {
+    storage
+        .delete_expired_conversations(&chrono::Local::now().to_rfc3339())
+        .await
+}
+
+async fn measure_query_latency(storage: &StorageManager) -> QueryLatency {
+    let started = Instant::now();
+    for query in SAMPLE_KEYWORD_QUERIES {
+        let _ = storage.search_keyword_messages(query, SAMPLE_RESULT_LIMIT).await;
+    }
+    let keyword_ms = elapsed_ms(started) / SAMPLE_KEYWORD_QUERIES.len() as f64;
+
+    let vector_ms = match storage.load_sample_embedding().await {
+        Ok(Some(embedding)) => {
+            let started = Instant::now();
+            let _ = storage.search_similar_messages(embedding, SAMPLE_RESULT_LIMIT).await;
+            Some(elapsed_ms(started))
+        }
+        Ok(None) | Err(_) => None,
+    };
+
+    QueryLatency {
+        keyword_ms,
+        vector_ms,
+    }
+}
+
+fn elapsed_ms(started: Instant) -> f64 {
+    started.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Human-readable summary printed by `kimi reindex`
+#[must_use]
+pub fn format_report(report: &ReindexReport) -> String {
+    let vector = |latency: &QueryLatency| {
+        latency
+            .vector_ms
+            .map_or_else(|| "n/a".to_string(), |value| format!("{:.1} ms", value))
+    };
+    [
+        format!("Orphaned messages removed: {}", report.orphaned_messages_removed),
+        format!("Stale embeddings cleared:  {}", report.stale_embeddings_cleared),
+        format!(
+            "Keyword (BM25) query:      {:.1} ms -> {:.1} ms",
+            report.before.keyword_ms, report.after.keyword_ms
+        ),
+        format!(
+            "Vector query:              {} -> {}",
+            vector(&report.before),
+            vector(&report.after)
+        ),
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_cutoff_counts_back_whole_days() {
+        let now = chrono::Local::now();
+        let cutoff = archive_cutoff(now, 90);
+        let parsed = chrono::DateTime::parse_from_rfc3339(&cutoff).map(|date| now.signed_duration_since(date));
+        assert_eq!(parsed.map(|elapsed| elapsed.num_days()).ok(), Some(90));
+        assert_eq!(archive_cutoff(now, 0), now.to_rfc3339());
+    }
+
+    #[test]
+    fn test_ephemeral_conversations_count_down_a_week() {
+        let now = chrono::Local::now();
+        let expiry = ephemeral_expiry(now);
+        assert_eq!(days_until_expiry(&expiry, now), Some(EPHEMERAL_DAYS));
+        let later = now + chrono::Duration::days(EPHEMERAL_DAYS) - chrono::Duration::hours(3);
+        assert_eq!(days_until_expiry(&expiry, later), Some(0));
+        assert_eq!(days_until_expiry(&expiry, now + chrono::Duration::days(30)), Some(0));
+        assert_eq!(days_until_expiry("soon", now), None);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod tts;
 pub mod weather;
+pub mod transit;
 pub mod clipboard;
 pub mod personality;
 pub mod identity;
@@ -7,11 +8,64 @@ pub mod obsidian;
 #[path = "link-download.rs"]
 pub mod link_download;
 pub mod convert;
+pub mod transcribe;
 pub mod dates;
 pub mod embeddings;
 pub mod retrieval;
 pub mod fuzzy;
 pub mod projects;
+pub mod calendar;
+pub mod webhook;
+pub mod documents;
+pub mod bench;
+pub mod personality_tuning;
+pub mod personality_share;
+pub mod ocr;
+pub mod maintenance;
+pub mod bundle;
+pub mod archive;
+pub mod backup;
+pub mod images;
+pub mod attachments;
+pub mod personal_data;
+pub mod logging;
+pub mod gpu;
+pub mod secrets;
+pub mod encryption;
+pub mod stdin;
+pub mod check_ins;
+pub mod resume_recap;
+pub mod notifications;
+pub mod chat_import;
+pub mod journal;
+pub mod feeds;
+pub mod email;
+pub mod file_search;
+pub mod database;
+pub mod git_context;
+pub mod homeassistant;
+pub mod health;
+pub mod links;
+pub mod usage;
+pub mod response_cache;
+pub mod entities;
+pub mod languages;
+pub mod calculator;
+pub mod finance;
+pub mod timers;
+pub mod tasks;
+pub mod sentiment;
+pub mod diary;
+pub mod scheduler;
+pub mod capture;
+pub mod editor;
+pub mod settings;
+pub mod tool_policy;
+pub mod tool_outputs;
+pub mod citations;
+pub mod verification;
+pub mod duplicates;
+pub mod dashboard;
 
 pub use tts::TTSService;
 pub use fuzzy::fuzzy_score;
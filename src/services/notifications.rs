@@ -0,0 +1,193 @@
+use crate::services::calendar::CalendarEvent;
+use crate::storage::ProjectTask;
+use chrono::{NaiveDate, NaiveDateTime};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const STATE_FILE_NAME: &str = "notifications.json";
+
+/// What published a notification; shown as a tag in `/inbox`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Reminder,
+    Digest,
+    Embeddings,
+    Download,
+    Budget,
+    Job,
+}
+
+impl NotificationKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reminder => "reminder",
+            Self::Digest => "digest",
+            Self::Embeddings => "embeddings",
+            Self::Download => "download",
+            Self::Budget => "budget",
+            Self::Job => "job",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Self {
+        match value {
+            "reminder" => Self::Reminder,
+            "digest" => Self::Digest,
+            "embeddings" => Self::Embeddings,
+            "download" => Self::Download,
+            "budget" => Self::Budget,
+            _ => Self::Job,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NotificationState {
+    /// Day the last due digest was published (YYYY-MM-DD)
+    last_digest: Option<String>,
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(STATE_FILE_NAME))
+}
+
+fn load_state() -> NotificationState {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &NotificationState) -> Result<()> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// True until today's digest was published
+#[must_use]
+pub fn is_digest_due(today: NaiveDate) -> bool {
+    load_state().last_digest != Some(today.to_string())
+}
+
+pub fn mark_digest_published(today: NaiveDate) -> Result<()> {
+    let mut state = load_state();
+    state.last_digest = Some(today.to_string());
+    save_state(&state)
+}
+
+/// Today's events and open project tasks as one message, or None when nothing is due
+#[must_use]
+pub fn format_due_digest(
+    events: &[CalendarEvent],
+    open_tasks: &[ProjectTask],
+    today: NaiveDate,
+) -> Option<String> {
+    let todays_events: Vec<&CalendarEvent> =
+        events.iter().filter(|event| event.date() == today).collect();
+    if todays_events.is_empty() && open_tasks.is_empty() {
+        return None;
+    }
+    let mut lines = Vec::new();
+    if !todays_events.is_empty() {
+        lines.push(format!("{} event(s) today:", todays_events.len()));
+        lines.extend(
+            todays_events
+                .iter()
+                .map(|event| format!("  {}", crate::services::calendar::format_event_line(event))),
+        );
+    }
+    if !open_tasks.is_empty() {
+        lines.push(format!("{} open project task(s):", open_tasks.len()));
+        lines.extend(
+            open_tasks
+                .iter()
+                .map(|task| format!("  {} ({})", task.title, task.project)),
+        );
+    }
+    Some(lines.join("\n"))
+}
+
+/// Timed events starting within `lead_minutes` of `now` that have not started yet
+#[must_use]
+pub fn upcoming_reminders(
+    events: &[CalendarEvent],
+    now: NaiveDateTime,
+    lead_minutes: i64,
+) -> Vec<&CalendarEvent> {
+    events
+        .iter()
+        .filter(|event| !event.all_day)
+        .filter(|event| {
+            let minutes_until = event.start.signed_duration_since(now).num_minutes();
+            (0..=lead_minutes).contains(&minutes_until)
+        })
+        .collect()
+}
+
+/// Shows a desktop notification through `notify-send`; a missing binary is ignored
+pub fn send_desktop_notification(title: &str, message: &str) {
+    let spawned = Command::new("notify-send")
+        .arg("--app-name=kimi")
+        .arg(title)
+        .arg(message)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = spawned {
+        // Reap the process in the background so it does not linger as a zombie
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(summary: &str, start: &str, all_day: bool) -> CalendarEvent {
+        let Ok(start) = NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M") else {
+            panic!("bad test date");
+        };
+        CalendarEvent {
+            summary: summary.to_string(),
+            start,
+            end: None,
+            all_day,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_reminders_cover_only_timed_events_in_the_lead_window() {
+        let events = [
+            event("Standup", "2026-10-17 09:10", false),
+            event("Lunch", "2026-10-17 12:00", false),
+            event("Holiday", "2026-10-17 00:00", true),
+            event("Earlier", "2026-10-17 08:50", false),
+        ];
+        let Ok(now) = NaiveDateTime::parse_from_str("2026-10-17 09:00", "%Y-%m-%d %H:%M") else {
+            panic!("bad test date");
+        };
+        let due = upcoming_reminders(&events, now, 15);
+        assert_eq!(due.len(), 1);
+        assert!(due.iter().all(|event| event.summary == "Standup"));
+
+        let today = now.date();
+        let Some(digest) = format_due_digest(&events, &[], today) else {
+            panic!("expected a digest");
+        };
+        assert!(digest.starts_with("4 event(s) today:"));
+        assert!(format_due_digest(&[], &[], today).is_none());
+    }
+}
@@ -12,6 +12,8 @@ pub enum NoteType {
 #[derive(Debug, Clone)]
 pub struct NoteSnippet {
     pub title: String,
+    /// Vault path of the note, or the name it was read by
+    pub path: String,
     pub note_type: NoteType,
     pub snippet: String,
 }
@@ -93,6 +95,7 @@ pub fn search_notes(vault_name: &str, query: &str, limit: usize) -> Result<Vec<N
                 .join("\n");
             NoteSnippet {
                 title,
+                path: result.file,
                 note_type,
                 snippet,
             }
@@ -116,15 +119,25 @@ pub fn read_daily_note(vault_name: &str) -> Result<String> {
     Ok(output)
 }
 
-/// Format note snippets into a context block for the LLM
-pub fn format_obsidian_context(label: &str, notes: &[NoteSnippet]) -> Option<String> {
+/// Format note snippets into a context block for the LLM; `number` gives each
+/// note the citation number it is shown with
+pub fn format_obsidian_context(
+    label: &str,
+    notes: &[NoteSnippet],
+    mut number: impl FnMut(&NoteSnippet) -> usize,
+) -> Option<String> {
     if notes.is_empty() {
         return None;
     }
     let mut blocks = Vec::new();
     blocks.push(format!("--- {} ---", label));
     for note in notes {
-        blocks.push(format!("{} ({})", note.title, note_type_label(note.note_type)));
+        blocks.push(format!(
+            "[{}] {} ({})",
+            number(note),
+            note.title,
+            note_type_label(note.note_type)
+        ));
         if !note.snippet.is_empty() {
             blocks.push(note.snippet.clone());
         }
@@ -0,0 +1,100 @@
+use crate::app::ModelSource;
+use crate::config::OcrConfig;
+use color_eyre::{Result, eyre::eyre};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Minimum number of word-like tokens before an image counts as a text screenshot
+const MIN_OCR_WORDS: usize = 5;
+/// Share of non-space characters that must be letters/digits/punctuation
+const MIN_TEXT_RATIO: f32 = 0.7;
+/// Maximum characters of OCR text added to the prompt per image
+const MAX_OCR_CHARS: usize = 8_000;
+
+/// Model name fragments of Ollama models that accept images directly
+const VISION_MODEL_MARKERS: [&str; 9] = [
+    "llava", "vision", "moondream", "minicpm-v", "qwen2.5vl", "qwen2-vl", "gemma3", "llama4",
+    "granite3.2-vision",
+];
+
+/// Returns true when the agent can read attached images itself
+#[must_use]
+pub fn model_supports_images(model_source: &ModelSource, model: &str) -> bool {
+    if *model_source != ModelSource::Ollama {
+        return false;
+    }
+    let model = model.to_lowercase();
+    VISION_MODEL_MARKERS.iter().any(|marker| model.contains(marker))
+}
+
+/// Runs tesseract on image bytes and returns the recognized text
+pub fn extract_image_text(config: &OcrConfig, image_bytes: &[u8]) -> Result<String> {
+    let mut child = Command::new(&config.command)
+        .args(["stdin", "stdout", "-l", &config.language])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| eyre!("{} not available ({})", config.command, error))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(image_bytes)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(eyre!("{} failed", config.command));
+    }
+    Ok(normalize_ocr_text(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Collapses blank-line runs and trailing whitespace, truncated to `MAX_OCR_CHARS`
+fn normalize_ocr_text(raw: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in raw.lines().map(str::trim_end) {
+        if line.trim().is_empty() && lines.last().is_none_or(|last| last.trim().is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    let text = lines.join("\n").trim().to_string();
+    if text.chars().count() > MAX_OCR_CHARS {
+        let truncated: String = text.chars().take(MAX_OCR_CHARS).collect();
+        return format!("{}\n[... truncated]", truncated);
+    }
+    text
+}
+
+/// Heuristic: OCR output of a real text screenshot has enough words and little noise,
+/// whereas photos produce a handful of stray symbols
+#[must_use]
+pub fn looks_like_text(text: &str) -> bool {
+    let words = text
+        .split_whitespace()
+        .filter(|word| word.chars().filter(|character| character.is_alphanumeric()).count() >= 2)
+        .count();
+    if words < MIN_OCR_WORDS {
+        return false;
+    }
+    let visible: Vec<char> = text.chars().filter(|character| !character.is_whitespace()).collect();
+    let clean = visible
+        .iter()
+        .filter(|character| character.is_alphanumeric() || ".,:;!?'\"()-/".contains(**character))
+        .count();
+    clean as f32 / visible.len().max(1) as f32 >= MIN_TEXT_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_text() {
+        assert!(looks_like_text("error[E0425]: cannot find value `x` in this scope"));
+        assert!(!looks_like_text("~ ¦ @@ %"));
+        assert!(!looks_like_text("#$%& *^~| ab {}<> §§ cd ¤¤ ef"));
+    }
+
+    #[test]
+    fn test_normalize_ocr_text_collapses_blank_lines() {
+        assert_eq!(normalize_ocr_text("\n\nfirst  \n\n\n\nsecond\n\x0c"), "first\n\nsecond");
+    }
+}
@@ -0,0 +1,111 @@
+use crate::storage::{PurgeMatch, StorageManager};
+use color_eyre::Result;
+use std::path::Path;
+
+/// Most mentioned topics listed in the report
+const REPORT_TOPIC_LIMIT: usize = 10;
+/// Characters of each match shown in the purge listing
+const MATCH_PREVIEW_CHARS: usize = 80;
+
+/// What `kimi data report` found in the database and the data directory
+#[derive(Debug, Default)]
+pub struct DataReport {
+    pub counts: Vec<(&'static str, usize)>,
+    pub messages_by_role: Vec<(String, usize)>,
+    pub topics: Vec<(String, usize)>,
+    /// Files kept next to the database: identity state, personalities, attachments
+    pub files: Vec<(&'static str, usize)>,
+}
+
+pub async fn build_report(storage: &StorageManager) -> Result<DataReport> {
+    let mut topics = storage.load_frequent_topics(1).await?;
+    topics.truncate(REPORT_TOPIC_LIMIT);
+    let data_dir = crate::profile::data_dir()?;
+    Ok(DataReport {
+        counts: storage.data_counts().await?,
+        messages_by_role: storage.message_counts_by_role().await?,
+        topics,
+        files: vec![
+            ("identity state", usize::from(data_dir.join("identity-state.json").exists())),
+            ("personalities", count_files(&data_dir.join("personalities"))),
+            ("pasted images", count_files(&crate::services::attachments::attachments_dir()?)),
+        ],
+    })
+}
+
+fn count_files(directory: &Path) -> usize {
+    std::fs::read_dir(directory)
+        .map(|entries| entries.flatten().filter(|entry| entry.path().is_file()).count())
+        .unwrap_or(0)
+}
+
+#[must_use]
+pub fn format_report(report: &DataReport) -> String {
+    let mut lines = vec!["Stored personal data".to_string(), String::new()];
+    for (label, count) in &report.counts {
+        lines.push(format!("  {:<22}{}", label, count));
+    }
+    if !report.messages_by_role.is_empty() {
+        lines.push(String::new());
+        lines.push("Messages by author".to_string());
+        for (role, count) in &report.messages_by_role {
+            lines.push(format!("  {:<22}{}", role.to_lowercase(), count));
+        }
+    }
+    if !report.topics.is_empty() {
+        lines.push(String::new());
+        lines.push("Most mentioned topics".to_string());
+        for (topic, count) in &report.topics {
+            lines.push(format!("  {:<22}{}", topic, count));
+        }
+    }
+    lines.push(String::new());
+    lines.push("Files".to_string());
+    for (label, count) in &report.files {
+        lines.push(format!("  {:<22}{}", label, count));
+    }
+    lines.join("\n")
+}
+
+/// One line per match: category and a single-line preview of the matching text
+#[must_use]
+pub fn format_matches(matches: &[PurgeMatch]) -> String {
+    matches
+        .iter()
+        .map(|found| {
+            let flattened = found.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let mut preview: String = flattened.chars().take(MATCH_PREVIEW_CHARS).collect();
+            if flattened.chars().count() > MATCH_PREVIEW_CHARS {
+                preview.push_str("...");
+            }
+            format!("  [{}] {}", found.category.label(), preview)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::PurgeCategory;
+
+    #[test]
+    fn test_matches_list_one_line_each() {
+        let matches = vec![
+            PurgeMatch {
+                id: surrealdb::sql::Thing::from(("message", "a")),
+                category: PurgeCategory::Message,
+                text: "My doctor said\nthe knee needs rest".to_string(),
+            },
+            PurgeMatch {
+                id: surrealdb::sql::Thing::from(("topic_mention", "b")),
+                category: PurgeCategory::Topic,
+                text: "x".repeat(100),
+            },
+        ];
+        let listing = format_matches(&matches);
+        assert_eq!(listing.lines().count(), 2);
+        assert!(listing.starts_with("  [message] My doctor said the knee needs rest"));
+        assert!(listing.ends_with("..."));
+    }
+}
@@ -1,8 +1,10 @@
+use crate::config::EditorConfig;
 use color_eyre::Result;
 use directories::ProjectDirs;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use std::process::Command;
 
 const BASE_PERSONALITY_NAME: &str = "Kimi";
 const DEFAULT_PERSONALITY_NAME: &str = "Casca";
@@ -10,6 +12,23 @@ const MY_PERSONALITY_NAME: &str = "My personality";
 const MEMORIES_ENTRY_NAME: &str = "Memories";
 const PERSONALITY_EXTENSION: &str = "md";
 const LEGACY_PERSONALITY_EXTENSION: &str = "txt";
+/// Sidecar next to a personality file with its recommended model and voice
+const META_EXTENSION: &str = "toml";
+
+/// Settings a personality was written for, kept beside its text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonalityMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice_id: Option<String>,
+}
+
+impl PersonalityMeta {
+    fn is_empty(&self) -> bool {
+        self.recommended_model.is_none() && self.voice_id.is_none()
+    }
+}
 
 pub fn default_personality_name() -> String {
     DEFAULT_PERSONALITY_NAME.to_string()
@@ -116,6 +135,10 @@ pub fn delete_personality(name: &str) -> Result<()> {
     if personality_path.exists() {
         fs::remove_file(personality_path)?;
     }
+    let meta_path = personality_meta_path(name)?;
+    if meta_path.exists() {
+        fs::remove_file(meta_path)?;
+    }
     Ok(())
 }
 
@@ -124,156 +147,74 @@ pub fn read_personality(name: &str) -> Result<String> {
     Ok(fs::read_to_string(personality_path)?)
 }
 
-pub fn read_my_personality() -> Result<String> {
-    let personality_path = ensure_my_personality()?;
-    Ok(fs::read_to_string(personality_path)?)
-}
-
-pub fn open_personality_in_new_terminal(name: &str) -> Result<()> {
+pub fn write_personality(name: &str, text: &str) -> Result<()> {
     let personality_path = ensure_personality(name)?;
-    let personality_str = personality_path.to_string_lossy().to_string();
-
-    let mut attempts: Vec<(String, Vec<String>)> = Vec::new();
-
-    if let Ok(terminal) = std::env::var("TERMINAL") {
-        attempts.push((
-            terminal,
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ));
-    }
-
-    attempts.extend([
-        (
-            "x-terminal-emulator".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "gnome-terminal".to_string(),
-            vec!["--".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "konsole".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "kitty".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "alacritty".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "wezterm".to_string(),
-            vec![
-                "start".to_string(),
-                "--".to_string(),
-                "micro".to_string(),
-                personality_str.clone(),
-            ],
-        ),
-        (
-            "xterm".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-    ]);
-
-    for (program, args) in attempts {
-        if try_spawn_terminal(&program, &args) {
-            return Ok(());
-        }
-    }
-
-    Err(color_eyre::eyre::eyre!(
-        "No supported terminal emulator found"
-    ))
+    fs::write(personality_path, text)?;
+    Ok(())
 }
 
-pub fn open_my_personality_in_new_terminal() -> Result<()> {
-    let personality_path = ensure_my_personality()?;
-    let personality_str = personality_path.to_string_lossy().to_string();
-    let mut attempts: Vec<(String, Vec<String>)> = Vec::new();
+#[must_use]
+pub fn personality_exists(name: &str) -> bool {
+    personality_path(name).is_ok_and(|path| path.exists())
+}
 
-    if let Ok(terminal) = std::env::var("TERMINAL") {
-        attempts.push((
-            terminal,
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ));
+/// Recommended model and voice for a personality (empty when it has no sidecar)
+pub fn read_personality_meta(name: &str) -> Result<PersonalityMeta> {
+    let meta_path = personality_meta_path(name)?;
+    if !meta_path.exists() {
+        return Ok(PersonalityMeta::default());
     }
+    Ok(toml::from_str(&fs::read_to_string(meta_path)?)?)
+}
 
-    attempts.extend([
-        (
-            "x-terminal-emulator".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "gnome-terminal".to_string(),
-            vec!["--".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "konsole".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "kitty".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "alacritty".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-        (
-            "wezterm".to_string(),
-            vec![
-                "start".to_string(),
-                "--".to_string(),
-                "micro".to_string(),
-                personality_str.clone(),
-            ],
-        ),
-        (
-            "xterm".to_string(),
-            vec!["-e".to_string(), "micro".to_string(), personality_str.clone()],
-        ),
-    ]);
-
-    for (program, args) in attempts {
-        if try_spawn_terminal(&program, &args) {
-            return Ok(());
+pub fn write_personality_meta(name: &str, meta: &PersonalityMeta) -> Result<()> {
+    let meta_path = personality_meta_path(name)?;
+    if meta.is_empty() {
+        if meta_path.exists() {
+            fs::remove_file(meta_path)?;
         }
+        return Ok(());
     }
-
-    Err(color_eyre::eyre::eyre!(
-        "No supported terminal emulator found"
-    ))
+    fs::write(meta_path, toml::to_string_pretty(meta)?)?;
+    Ok(())
 }
 
-pub fn open_base_personality_in_new_terminal() -> Result<()> {
-    open_personality_in_new_terminal(BASE_PERSONALITY_NAME)
+pub fn read_my_personality() -> Result<String> {
+    let personality_path = ensure_my_personality()?;
+    Ok(fs::read_to_string(personality_path)?)
 }
 
-pub fn open_personality_in_place(name: &str) -> Result<()> {
+/// Opens a personality file in the configured editor in a new terminal window
+pub fn open_personality_in_new_terminal(name: &str, editor: &EditorConfig) -> Result<()> {
     let personality_path = ensure_personality(name)?;
-    let status = Command::new("micro").arg(personality_path).status()?;
-    if status.success() {
-        Ok(())
-    } else {
-        Err(color_eyre::eyre::eyre!("Micro exited with error"))
-    }
+    crate::services::editor::open_in_new_terminal(&personality_path, editor)
 }
 
-pub fn open_my_personality_in_place() -> Result<()> {
-    let personality_path = ensure_my_personality()?;
-    let status = Command::new("micro").arg(personality_path).status()?;
-    if status.success() {
-        Ok(())
-    } else {
-        Err(color_eyre::eyre::eyre!("Micro exited with error"))
-    }
+/// Runs the configured editor on a personality file in the current terminal
+pub fn open_personality_in_place(name: &str, editor: &EditorConfig) -> Result<()> {
+    let personality_path = ensure_personality(name)?;
+    crate::services::editor::open_in_place(&personality_path, editor)
 }
 
-pub fn open_base_personality_in_place() -> Result<()> {
-    open_personality_in_place(BASE_PERSONALITY_NAME)
+/// Watches the personalities directory; `on_change` runs whenever a personality file
+/// changes (editor swap files are ignored). The watcher stops when the handle is dropped
+pub fn watch_personalities(on_change: impl Fn() + Send + 'static) -> Result<RecommendedWatcher> {
+    let directory = personality_dir()?;
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let touches_personality = event.paths.iter().any(|path| {
+            path.extension().is_some_and(|extension| {
+                extension == PERSONALITY_EXTENSION || extension == META_EXTENSION
+            })
+        });
+        if touches_personality && !event.kind.is_access() {
+            on_change();
+        }
+    })?;
+    watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
 }
 
 pub fn personality_dir() -> Result<PathBuf> {
@@ -295,6 +236,10 @@ fn personality_path(name: &str) -> Result<PathBuf> {
     Ok(personality_dir.join(format!("{}.{}", trimmed, PERSONALITY_EXTENSION)))
 }
 
+fn personality_meta_path(name: &str) -> Result<PathBuf> {
+    Ok(personality_path(name)?.with_extension(META_EXTENSION))
+}
+
 fn legacy_personality_path(name: &str) -> Result<PathBuf> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
@@ -310,8 +255,7 @@ fn legacy_personality_path(name: &str) -> Result<PathBuf> {
 }
 
 fn project_data_dir() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir()?;
-    Ok(current_dir.join("data"))
+    crate::profile::data_dir()
 }
 
 fn legacy_personality_dir() -> Result<PathBuf> {
@@ -322,10 +266,6 @@ fn legacy_personality_dir() -> Result<PathBuf> {
     Ok(config_dir.join("personalities"))
 }
 
-fn try_spawn_terminal(program: &str, args: &[String]) -> bool {
-    Command::new(program).args(args).spawn().is_ok()
-}
-
 fn default_personality_template() -> String {
     [
         "You are a helpful assistant.",
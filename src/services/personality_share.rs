@@ -0,0 +1,145 @@
+use crate::services::personality::{self, PersonalityMeta};
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A personality as a single shareable file (TOML, or JSON by extension)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonalityPackage {
+    pub name: String,
+    /// The personality text used in the system prompt
+    pub system: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice_id: Option<String>,
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+/// Parses a package, choosing the format from the file extension
+pub fn parse_package(text: &str, path: &Path) -> Result<PersonalityPackage> {
+    let package: PersonalityPackage = if is_json(path) {
+        serde_json::from_str(text)?
+    } else {
+        toml::from_str(text)?
+    };
+    let name = package.name.trim();
+    if name.is_empty() {
+        return Err(eyre!("Personality file has no name"));
+    }
+    // The name becomes a file name in the personalities directory
+    if name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(eyre!("Invalid personality name '{}'", name));
+    }
+    let reserved = [
+        personality::base_personality_name(),
+        personality::my_personality_name(),
+    ];
+    if reserved.iter().any(|reserved| reserved.eq_ignore_ascii_case(name)) {
+        return Err(eyre!("'{}' is a built-in entry and cannot be imported", name));
+    }
+    Ok(PersonalityPackage {
+        name: name.to_string(),
+        ..package
+    })
+}
+
+pub fn render_package(package: &PersonalityPackage, path: &Path) -> Result<String> {
+    if is_json(path) {
+        Ok(serde_json::to_string_pretty(package)?)
+    } else {
+        Ok(toml::to_string_pretty(package)?)
+    }
+}
+
+/// Writes a personality and its recommended settings to `target`
+/// (a directory gets `<name>.toml`)
+pub fn export_personality(name: &str, target: &Path) -> Result<PathBuf> {
+    if !personality::personality_exists(name) {
+        return Err(eyre!("Personality '{}' not found", name));
+    }
+    let meta = personality::read_personality_meta(name)?;
+    let package = PersonalityPackage {
+        name: name.to_string(),
+        system: personality::read_personality(name)?,
+        recommended_model: meta.recommended_model,
+        voice_id: meta.voice_id,
+    };
+    let path = if target.is_dir() {
+        target.join(format!("{}.toml", name))
+    } else {
+        target.to_path_buf()
+    };
+    fs::write(&path, render_package(&package, &path)?)?;
+    Ok(path)
+}
+
+/// Installs a personality file; an existing personality with the same name is only
+/// replaced when `overwrite` is set
+pub fn import_personality(source: &Path, overwrite: bool) -> Result<PersonalityPackage> {
+    let package = parse_package(&fs::read_to_string(source)?, source)?;
+    if personality::personality_exists(&package.name) && !overwrite {
+        return Err(eyre!(
+            "Personality '{}' already exists (use --force to replace it)",
+            package.name
+        ));
+    }
+    personality::write_personality(&package.name, &package.system)?;
+    personality::write_personality_meta(
+        &package.name,
+        &PersonalityMeta {
+            recommended_model: package.recommended_model.clone(),
+            voice_id: package.voice_id.clone(),
+        },
+    )?;
+    Ok(package)
+}
+
+/// Copies a personality (text and settings) under the first free "<name> copy" name
+pub fn clone_personality(name: &str) -> Result<String> {
+    let clone_name = (1..)
+        .map(|attempt| {
+            if attempt == 1 {
+                format!("{} copy", name)
+            } else {
+                format!("{} copy {}", name, attempt)
+            }
+        })
+        .find(|candidate| !personality::personality_exists(candidate))
+        .ok_or_else(|| eyre!("No free name for a copy of '{}'", name))?;
+    personality::write_personality(&clone_name, &personality::read_personality(name)?)?;
+    personality::write_personality_meta(&clone_name, &personality::read_personality_meta(name)?)?;
+    Ok(clone_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packages_round_trip_in_both_formats() {
+        let package = PersonalityPackage {
+            name: "Casca".to_string(),
+            system: "Blunt.\nSwears in Japanese.".to_string(),
+            recommended_model: Some("gemma3:12b".to_string()),
+            voice_id: None,
+        };
+        for file in ["casca.toml", "casca.json"] {
+            let path = Path::new(file);
+            let Ok(text) = render_package(&package, path) else {
+                panic!("render {}", file);
+            };
+            let Ok(parsed) = parse_package(&text, path) else {
+                panic!("parse {}", file);
+            };
+            assert_eq!(parsed, package);
+        }
+        assert!(parse_package("name = \"../evil\"\nsystem = \"x\"", Path::new("a.toml")).is_err());
+        assert!(parse_package("name = \"  \"\nsystem = \"x\"", Path::new("a.toml")).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::agents::{Agent, AgentManager, ChatMessage};
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const STATE_FILE_NAME: &str = "personality-suggestion.json";
+
+/// A proposed revision of a personality file, waiting for the user's decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalitySuggestion {
+    pub personality: String,
+    pub original: String,
+    pub proposed: String,
+    pub rationale: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TuningState {
+    conversations_since_suggestion: usize,
+    pending: Option<PersonalitySuggestion>,
+}
+
+/// One line of a line-based diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(STATE_FILE_NAME))
+}
+
+fn load_state() -> TuningState {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &TuningState) -> Result<()> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Returns the suggestion waiting for review, if any
+#[must_use]
+pub fn load_pending_suggestion() -> Option<PersonalitySuggestion> {
+    load_state().pending
+}
+
+/// Drops the pending suggestion (after it was accepted or rejected)
+pub fn clear_pending_suggestion() -> Result<()> {
+    let mut state = load_state();
+    state.pending = None;
+    save_state(&state)
+}
+
+/// Counts a finished conversation. Returns true when enough conversations have
+/// passed since the last suggestion and nothing is pending review.
+pub fn record_conversation(threshold: usize) -> Result<bool> {
+    if threshold == 0 {
+        return Ok(false);
+    }
+    let mut state = load_state();
+    state.conversations_since_suggestion = state.conversations_since_suggestion.saturating_add(1);
+    let is_due = state.pending.is_none() && state.conversations_since_suggestion >= threshold;
+    if is_due {
+        state.conversations_since_suggestion = 0;
+    }
+    save_state(&state)?;
+    Ok(is_due)
+}
+
+/// Asks the model to revise the personality text based on how the user writes
+/// and stores the result as the pending suggestion.
+pub fn generate_suggestion(
+    manager: &AgentManager,
+    agent: &Agent,
+    personality_name: &str,
+    recent_user_messages: &[String],
+) -> Result<PersonalitySuggestion> {
+    let original = crate::services::personality::read_personality(personality_name)?;
+    if recent_user_messages.is_empty() {
+        return Err(eyre!("Not enough conversation history"));
+    }
+
+    let samples = recent_user_messages
+        .iter()
+        .map(|message| format!("- {}", message.chars().take(300).collect::<String>()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = format!(
+        "Below is a personality file that shapes an assistant's tone, followed by recent messages the user wrote.\n\
+Propose small, concrete adjustments so the tone, phrasing and pacing suit this user better \
+(e.g. directness, humor, length, phrases they respond well to). Keep the structure and everything that still fits.\n\n\
+Return exactly this format:\n\
+Rationale: <one sentence>\n\
+---\n\
+<the full revised personality file>\n\n\
+--- Current personality ---\n{}\n\n--- Recent user messages ---\n{}",
+        original.trim(),
+        samples
+    );
+
+    let messages = vec![
+        ChatMessage::system("You tune assistant personality files. Make conservative, specific edits."),
+        ChatMessage::user(prompt),
+    ];
+    let response = manager.chat(agent, &messages)?;
+    let (rationale, proposed) = parse_suggestion_response(&response)
+        .ok_or_else(|| eyre!("Model returned an unusable suggestion"))?;
+    if proposed.trim() == original.trim() {
+        return Err(eyre!("No changes suggested"));
+    }
+
+    let suggestion = PersonalitySuggestion {
+        personality: personality_name.to_string(),
+        original,
+        proposed,
+        rationale,
+        created_at: chrono::Local::now().to_rfc3339(),
+    };
+    let mut state = load_state();
+    state.pending = Some(suggestion.clone());
+    save_state(&state)?;
+    Ok(suggestion)
+}
+
+fn parse_suggestion_response(response: &str) -> Option<(String, String)> {
+    let (head, body) = response.split_once("\n---")?;
+    let rationale = head
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Rationale:"))
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default();
+    let proposed = body.trim_start_matches('-').trim().to_string();
+    if proposed.is_empty() {
+        return None;
+    }
+    Some((rationale, format!("{}\n", proposed)))
+}
+
+/// Line-based diff using the longest common subsequence
+#[must_use]
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let rows = old_lines.len();
+    let columns = new_lines.len();
+
+    // lcs[i][j] = LCS length of old_lines[i..] and new_lines[j..]
+    let mut lcs = vec![vec![0usize; columns + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            let value = if old_lines.get(i) == new_lines.get(j) {
+                lcs_at(&lcs, i + 1, j + 1) + 1
+            } else {
+                lcs_at(&lcs, i + 1, j).max(lcs_at(&lcs, i, j + 1))
+            };
+            if let Some(cell) = lcs.get_mut(i).and_then(|row| row.get_mut(j)) {
+                *cell = value;
+            }
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < rows || j < columns {
+        match (old_lines.get(i), new_lines.get(j)) {
+            (Some(old_line), Some(new_line)) if old_line == new_line => {
+                diff.push(DiffLine::Same((*old_line).to_string()));
+                i += 1;
+                j += 1;
+            }
+            (Some(old_line), Some(_)) if lcs_at(&lcs, i + 1, j) >= lcs_at(&lcs, i, j + 1) => {
+                diff.push(DiffLine::Removed((*old_line).to_string()));
+                i += 1;
+            }
+            (_, Some(new_line)) => {
+                diff.push(DiffLine::Added((*new_line).to_string()));
+                j += 1;
+            }
+            (Some(old_line), None) => {
+                diff.push(DiffLine::Removed((*old_line).to_string()));
+                i += 1;
+            }
+            (None, None) => break,
+        }
+    }
+    diff
+}
+
+fn lcs_at(table: &[Vec<usize>], row: usize, column: usize) -> usize {
+    table
+        .get(row)
+        .and_then(|cells| cells.get(column))
+        .copied()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff_marks_changes() {
+        let diff = line_diff("tone: warm\nlength: long\nend", "tone: warm\nlength: short\nend");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("tone: warm".to_string()),
+                DiffLine::Removed("length: long".to_string()),
+                DiffLine::Added("length: short".to_string()),
+                DiffLine::Same("end".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_suggestion_response() {
+        let parsed = parse_suggestion_response("Rationale: shorter replies\n---\nBe brief.");
+        assert_eq!(
+            parsed,
+            Some(("shorter replies".to_string(), "Be brief.\n".to_string()))
+        );
+        assert_eq!(parse_suggestion_response("no separator"), None);
+    }
+}
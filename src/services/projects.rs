@@ -3,6 +3,9 @@ use color_eyre::{Result, eyre::eyre};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+const ENTRIES_HEADING: &str = "## Entries";
+const CONVERSATIONS_HEADING: &str = "## Conversations";
+
 // ── Data types ──────────────────────────────────────────────────────────────
 
 /// Summary of a project (name + entry count) for list views
@@ -19,6 +22,36 @@ pub struct ProjectFile {
     pub name: String,
     pub description: String,
     pub entries: Vec<String>,
+    /// Conversations attached to the project, one line each
+    pub conversations: Vec<String>,
+}
+
+/// Lifecycle of a project task: the AI proposes, the user confirms, either marks it done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Proposed,
+    Open,
+    Done,
+}
+
+impl TaskStatus {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Proposed => "proposed",
+            Self::Open => "open",
+            Self::Done => "done",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Self {
+        match value {
+            "proposed" => Self::Proposed,
+            "done" => Self::Done,
+            _ => Self::Open,
+        }
+    }
 }
 
 /// Result of LLM entry extraction for one project
@@ -103,7 +136,12 @@ pub fn create_project_file(vault_path: &str, name: &str, description: &str) -> R
         return Err(eyre!("Project '{}' already exists", name));
     }
 
-    let content = format!("# {}\n\n> {}\n\n## Entries\n", name.trim(), description.trim());
+    let content = format!(
+        "# {}\n\n> {}\n\n{}\n",
+        name.trim(),
+        description.trim(),
+        ENTRIES_HEADING
+    );
     fs::write(&path, content)?;
     Ok(())
 }
@@ -118,26 +156,226 @@ pub fn append_project_entries(vault_path: &str, name: &str, entries: &[String])
         return Err(eyre!("Project '{}' does not exist", name));
     }
 
-    // Read current content
     let current = fs::read_to_string(&path)?;
-
-    // Build new entry lines
     let new_lines: Vec<String> = entries
         .iter()
         .map(|entry| format!("- {}", entry.trim()))
         .collect();
+    let updated = insert_into_section(&current, ENTRIES_HEADING, &new_lines);
+    fs::write(&path, updated)?;
+    Ok(())
+}
 
-    // Append after the ## Entries section (or at the end if not found)
-    let updated = if current.contains("## Entries") {
-        format!("{}\n{}\n", current.trim_end(), new_lines.join("\n"))
-    } else {
-        format!("{}\n\n## Entries\n{}\n", current.trim_end(), new_lines.join("\n"))
-    };
+/// Renames a project and/or replaces its description, keeping everything else in the file
+pub fn update_project(vault_path: &str, name: &str, new_name: &str, description: &str) -> Result<()> {
+    let path = project_file_path(vault_path, name)?;
+    if !path.exists() {
+        return Err(eyre!("Project '{}' does not exist", name));
+    }
+    let new_path = project_file_path(vault_path, new_name)?;
+    if new_path != path && new_path.exists() {
+        return Err(eyre!("Project '{}' already exists", new_name));
+    }
 
-    fs::write(&path, updated)?;
+    let current = fs::read_to_string(&path)?;
+    let mut title_done = false;
+    let mut description_done = false;
+    let mut lines: Vec<String> = Vec::new();
+    for line in current.lines() {
+        let trimmed = line.trim();
+        if !title_done && trimmed.starts_with("# ") {
+            lines.push(format!("# {}", new_name.trim()));
+            title_done = true;
+        } else if !description_done && trimmed.starts_with("> ") {
+            lines.push(format!("> {}", description.trim()));
+            description_done = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if !description_done {
+        let position = usize::from(title_done);
+        lines.insert(position, format!("\n> {}", description.trim()));
+    }
+    if !title_done {
+        lines.insert(0, format!("# {}\n", new_name.trim()));
+    }
+
+    fs::write(&new_path, format!("{}\n", lines.join("\n").trim_end()))?;
+    if new_path != path {
+        fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+/// Removes one entry (by its position in the Entries section) from a project file
+pub fn remove_project_entry(vault_path: &str, name: &str, entry_index: usize) -> Result<()> {
+    let path = project_file_path(vault_path, name)?;
+    if !path.exists() {
+        return Err(eyre!("Project '{}' does not exist", name));
+    }
+    let current = fs::read_to_string(&path)?;
+    let mut in_entries_section = false;
+    let mut seen_entries = 0;
+    let mut removed = false;
+    let mut lines: Vec<&str> = Vec::new();
+    for line in current.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("## ") {
+            in_entries_section = trimmed == ENTRIES_HEADING;
+        } else if in_entries_section
+            && trimmed.strip_prefix("- ").is_some_and(|entry| !entry.trim().is_empty())
+        {
+            let is_target = seen_entries == entry_index;
+            seen_entries += 1;
+            if is_target {
+                removed = true;
+                continue;
+            }
+        }
+        lines.push(line);
+    }
+    if !removed {
+        return Err(eyre!("Entry not found in '{}'", name));
+    }
+    fs::write(&path, format!("{}\n", lines.join("\n").trim_end()))?;
     Ok(())
 }
 
+/// Links a conversation to a project as `<date> <title> (<id>)`; returns false when the
+/// conversation is already listed
+pub fn attach_conversation(
+    vault_path: &str,
+    name: &str,
+    conversation_id: &str,
+    title: &str,
+) -> Result<bool> {
+    let path = project_file_path(vault_path, name)?;
+    if !path.exists() {
+        return Err(eyre!("Project '{}' does not exist", name));
+    }
+    let current = fs::read_to_string(&path)?;
+    let project = parse_project_markdown(&current, name)?;
+    let id_marker = format!("({})", conversation_id);
+    if project.conversations.iter().any(|line| line.ends_with(&id_marker)) {
+        return Ok(false);
+    }
+    let line = format!(
+        "- {} {} {}",
+        chrono::Local::now().format("%Y-%m-%d"),
+        title.trim(),
+        id_marker
+    );
+    fs::write(&path, insert_into_section(&current, CONVERSATIONS_HEADING, &[line]))?;
+    Ok(true)
+}
+
+/// Inserts lines at the end of a `## ` section, creating the section at the end of
+/// the file when it is missing. Text outside the section is left untouched
+fn insert_into_section(content: &str, heading: &str, new_lines: &[String]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(heading_index) = lines.iter().position(|line| line.trim() == heading) else {
+        return format!("{}\n\n{}\n{}\n", content.trim_end(), heading, new_lines.join("\n"));
+    };
+    let section_end = lines
+        .iter()
+        .enumerate()
+        .skip(heading_index + 1)
+        .find(|(_, line)| line.trim().starts_with("## "))
+        .map_or(lines.len(), |(index, _)| index);
+    // Insert after the last non-blank line of the section
+    let insert_at = (heading_index + 1..section_end)
+        .rev()
+        .find(|index| lines.get(*index).is_some_and(|line| !line.trim().is_empty()))
+        .map_or(heading_index + 1, |index| index + 1);
+
+    let mut output: Vec<String> = Vec::with_capacity(lines.len() + new_lines.len());
+    output.extend(lines.iter().take(insert_at).map(|line| (*line).to_string()));
+    output.extend(new_lines.iter().cloned());
+    output.extend(lines.iter().skip(insert_at).map(|line| (*line).to_string()));
+    format!("{}\n", output.join("\n").trim_end())
+}
+
+/// Checks whether recent conversation text is about a project: its name, or at least
+/// two distinctive words from its description, appear in the text
+pub fn project_matches_text(project: &ProjectFile, text: &str) -> bool {
+    let text_lower = text.to_lowercase();
+    let text_words: Vec<&str> = text_lower
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let contains_word = |word: &str| text_words.contains(&word);
+
+    let name_lower = project.name.to_lowercase();
+    let name_words: Vec<&str> = name_lower
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .collect();
+    if !name_words.is_empty() && name_words.iter().all(|word| contains_word(word)) {
+        return true;
+    }
+
+    let description_lower = project.description.to_lowercase();
+    let mut description_words: Vec<&str> = description_lower
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 5)
+        .collect();
+    description_words.dedup();
+    description_words.iter().filter(|word| contains_word(word)).count() >= 2
+}
+
+/// Formats a project's knowledge for the system prompt, newest entries first
+pub fn format_project_knowledge(project: &ProjectFile, max_entries: usize) -> String {
+    let mut lines = vec![format!("--- Project knowledge: {} ---", project.name)];
+    if !project.description.is_empty() {
+        lines.push(project.description.clone());
+    }
+    lines.extend(
+        project
+            .entries
+            .iter()
+            .rev()
+            .take(max_entries)
+            .map(|entry| format!("- {}", entry)),
+    );
+    lines.push(
+        "This is what you have learned about the project so far. Use it when relevant \
+and do not contradict it without saying so."
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
+/// Open tasks and pending proposals for the prompt, so the assistant can bring them up
+#[must_use]
+pub fn format_project_tasks(project: &str, tasks: &[crate::storage::ProjectTask]) -> Option<String> {
+    let open: Vec<&str> = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Open)
+        .map(|task| task.title.as_str())
+        .collect();
+    let proposed: Vec<&str> = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Proposed)
+        .map(|task| task.title.as_str())
+        .collect();
+    if open.is_empty() && proposed.is_empty() {
+        return None;
+    }
+    let mut lines = vec![format!("--- Open tasks for project {} ---", project)];
+    lines.extend(open.iter().map(|title| format!("- {}", title)));
+    if !proposed.is_empty() {
+        lines.push("Proposed, not yet confirmed by the user:".to_string());
+        lines.extend(proposed.iter().map(|title| format!("- {}", title)));
+    }
+    lines.push(
+        "Bring up open tasks when they fit the conversation (e.g. \"you still wanted to ...\"). \
+When the user says one is finished, call complete_task. Do not propose a task that is already listed."
+            .to_string(),
+    );
+    Some(lines.join("\n"))
+}
+
 /// Reads and parses a project file into structured data
 pub fn read_project_file(vault_path: &str, name: &str) -> Result<ProjectFile> {
     let path = project_file_path(vault_path, name)?;
@@ -182,6 +420,7 @@ pub fn list_projects(vault_path: &str) -> Result<Vec<ProjectSummary>> {
                     name: file_name.clone(),
                     description: String::new(),
                     entries: Vec::new(),
+                    conversations: Vec::new(),
                 });
             summaries.push(ProjectSummary {
                 name: parsed.name,
@@ -278,7 +517,8 @@ fn parse_project_markdown(content: &str, fallback_name: &str) -> Result<ProjectF
     let mut name = fallback_name.to_string();
     let mut description = String::new();
     let mut entries = Vec::new();
-    let mut in_entries_section = false;
+    let mut conversations = Vec::new();
+    let mut section: Option<&str> = None;
 
     for line in content.lines() {
         let trimmed = line.trim();
@@ -297,25 +537,22 @@ fn parse_project_markdown(content: &str, fallback_name: &str) -> Result<ProjectF
             continue;
         }
 
-        // Detect ## Entries section
-        if trimmed == "## Entries" {
-            in_entries_section = true;
+        // Track which ## section the following list items belong to
+        if trimmed.starts_with("## ") {
+            section = Some(trimmed);
             continue;
         }
 
-        // New section ends entries
-        if trimmed.starts_with("## ") && in_entries_section {
-            in_entries_section = false;
-            continue;
-        }
-
-        // Parse entry lines
-        if in_entries_section
-            && let Some(entry_text) = trimmed.strip_prefix("- ")
-        {
-            let entry = entry_text.trim().to_string();
-            if !entry.is_empty() {
-                entries.push(entry);
+        // Parse list items of the Entries and Conversations sections
+        if let Some(item_text) = trimmed.strip_prefix("- ") {
+            let item = item_text.trim().to_string();
+            if item.is_empty() {
+                continue;
+            }
+            match section {
+                Some(ENTRIES_HEADING) => entries.push(item),
+                Some(CONVERSATIONS_HEADING) => conversations.push(item),
+                _ => {}
             }
         }
     }
@@ -324,6 +561,7 @@ fn parse_project_markdown(content: &str, fallback_name: &str) -> Result<ProjectF
         name,
         description,
         entries,
+        conversations,
     })
 }
 
@@ -335,34 +573,119 @@ fn is_markdown_file(path: &Path) -> bool {
 
 // ── LLM extraction logic ────────────────────────────────────────────────────
 
-/// Extracts 1-3 topic keywords from a conversation (lightweight LLM call)
+/// Topics an extraction model tends to return that never deserve a project
+const TOPIC_STOPLIST: &[&str] = &[
+    "advice", "chat", "chatting", "conversation", "discussion", "emotions", "feelings",
+    "general", "greeting", "greetings", "help", "idea", "ideas", "information", "life",
+    "misc", "miscellaneous", "none", "question", "questions", "small talk", "stuff",
+    "thanks", "thing", "things", "time", "today", "topic", "topics", "work",
+];
+
+/// Cosine similarity above which two topic names are treated as the same topic
+const TOPIC_SYNONYM_SIMILARITY: f32 = 0.85;
+
+/// Extracts 1-3 topic keywords from a conversation (lightweight LLM call). Each agent
+/// is tried in order, so callers pass the summary and routing models first and the chat
+/// model as fallback; the reply is constrained to a `{"topics": [...]}` schema
 pub fn extract_topics(
     conversation_content: &str,
-    agent: &crate::agents::Agent,
+    agents: &[&crate::agents::Agent],
     manager: &AgentManager,
 ) -> Vec<String> {
     let truncated: String = conversation_content.chars().take(1000).collect();
     let prompt = format!(
-        "What are the main topics discussed? Return a JSON array of 1-3 topic names, lowercase. \
+        "What are the main topics discussed? Return JSON {{\"topics\": [...]}} with 1-3 topic \
+names, lowercase, as specific as the conversation allows. \
 Only include substantive topics (not greetings, emotions, small talk, or meta-conversation). \
-Example: [\"gardening\", \"rust programming\"]. If no substantive topics, return [].\n\n\
+Example: {{\"topics\": [\"gardening\", \"rust programming\"]}}. If no substantive topics, \
+return {{\"topics\": []}}.\n\n\
 Conversation:\n{}",
         truncated
     );
 
     let messages = vec![
         AgentChatMessage::system(
-            "You extract topic keywords from conversations. Return only a JSON array of strings.",
+            "You extract topic keywords from conversations. Return only JSON.",
         ),
         AgentChatMessage::user(&prompt),
     ];
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "topics": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": 3
+            }
+        },
+        "required": ["topics"]
+    });
 
-    let response = match manager.chat(agent, &messages) {
-        Ok(text) => text,
-        Err(_) => return Vec::new(),
-    };
+    agents
+        .iter()
+        .find_map(|agent| {
+            manager
+                .chat_structured_cached(agent, &messages, &schema)
+                .ok()
+                .and_then(|response| parse_topic_json(&response))
+        })
+        .map(filter_noise_topics)
+        .unwrap_or_default()
+}
 
-    parse_topic_json(&response)
+/// Drops stoplisted, numeric and too-short topics and repeated names
+fn filter_noise_topics(topics: Vec<String>) -> Vec<String> {
+    let mut kept: Vec<String> = Vec::new();
+    for topic in topics {
+        let topic = topic
+            .trim()
+            .trim_matches(|character: char| !character.is_alphanumeric())
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let is_noise = topic.chars().count() < 3
+            || topic.chars().all(|character| !character.is_alphabetic())
+            || TOPIC_STOPLIST.contains(&topic.as_str());
+        if !is_noise && !kept.contains(&topic) {
+            kept.push(topic);
+        }
+    }
+    kept
+}
+
+/// Maps each topic onto an earlier topic with a near-identical embedding (e.g. "rust
+/// lang" onto "rust"), so mentions of synonyms count together. New topics are
+/// remembered; a topic whose embedding fails is kept as written
+pub async fn canonicalize_topics(
+    storage: &crate::storage::StorageManager,
+    topics: Vec<String>,
+) -> Vec<String> {
+    let mut canonical: Vec<String> = Vec::new();
+    for topic in topics {
+        let resolved = match crate::services::embeddings::generate_embedding(&topic).await {
+            Ok(embedding) => match storage
+                .find_similar_topic(embedding.clone(), TOPIC_SYNONYM_SIMILARITY)
+                .await
+            {
+                Ok(Some(known)) => known,
+                Ok(None) => {
+                    if let Err(error) = storage.save_topic_embedding(&topic, embedding).await {
+                        tracing::warn!(%error, topic, "could not save topic embedding");
+                    }
+                    topic
+                }
+                Err(error) => {
+                    tracing::warn!(%error, topic, "topic similarity lookup failed");
+                    topic
+                }
+            },
+            Err(_) => topic,
+        };
+        if !canonical.contains(&resolved) {
+            canonical.push(resolved);
+        }
+    }
+    canonical
 }
 
 /// Extracts factual entries from a conversation for matching projects
@@ -403,24 +726,35 @@ Conversation:\n{}",
     parse_extraction_json(&response)
 }
 
-fn parse_topic_json(response: &str) -> Vec<String> {
-    let trimmed = response.trim();
-    let start = trimmed.find('[');
-    let end = trimmed.rfind(']');
+/// Reads `{"topics": [...]}`, or a bare array from models that ignore the schema.
+/// `None` when the reply has no JSON at all, so the next model can be tried
+fn parse_topic_json(response: &str) -> Option<Vec<String>> {
+    #[derive(serde::Deserialize)]
+    struct TopicResponse {
+        topics: Vec<String>,
+    }
 
-    if let (Some(start_idx), Some(end_idx)) = (start, end)
-        && start_idx < end_idx
-    {
-        let json_slice = &trimmed[start_idx..=end_idx];
-        if let Ok(topics) = serde_json::from_str::<Vec<String>>(json_slice) {
-            return topics
-                .into_iter()
-                .map(|topic| topic.to_lowercase().trim().to_string())
-                .filter(|topic| !topic.is_empty())
-                .collect();
-        }
+    let trimmed = response.trim();
+    let topics = match (trimmed.find('{'), trimmed.rfind('}')) {
+        (Some(start), Some(end)) if start < end => trimmed
+            .get(start..=end)
+            .and_then(|json| serde_json::from_str::<TopicResponse>(json).ok())
+            .map(|parsed| parsed.topics),
+        _ => None,
     }
-    Vec::new()
+    .or_else(|| match (trimmed.find('['), trimmed.rfind(']')) {
+        (Some(start), Some(end)) if start < end => trimmed
+            .get(start..=end)
+            .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok()),
+        _ => None,
+    })?;
+    Some(
+        topics
+            .into_iter()
+            .map(|topic| topic.to_lowercase().trim().to_string())
+            .filter(|topic| !topic.is_empty())
+            .collect(),
+    )
 }
 
 fn parse_extraction_json(response: &str) -> Vec<ProjectExtractionResult> {
@@ -487,3 +821,73 @@ pub fn format_search_results(results: &[(String, String)], project_names: &[Stri
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_keep_entries_and_conversations_apart() {
+        let content = "# Garden\n\n> Tomatoes and raised beds\n\n## Entries\n- Beds face south\n\n## Conversations\n- 2026-05-01 Planting plan\n";
+        let updated = insert_into_section(content, ENTRIES_HEADING, &["- Uses drip irrigation".to_string()]);
+        let Ok(project) = parse_project_markdown(&updated, "Garden") else {
+            panic!("parse");
+        };
+        assert_eq!(project.entries, vec!["Beds face south", "Uses drip irrigation"]);
+        assert_eq!(project.conversations, vec!["2026-05-01 Planting plan"]);
+
+        let created = insert_into_section("# Garden\n", CONVERSATIONS_HEADING, &["- Chat".to_string()]);
+        assert!(created.ends_with("## Conversations\n- Chat\n"));
+    }
+
+    #[test]
+    fn test_projects_match_by_name_or_description_words() {
+        let project = ProjectFile {
+            name: "Raised Garden".to_string(),
+            description: "Growing tomatoes and peppers in raised beds".to_string(),
+            entries: Vec::new(),
+            conversations: Vec::new(),
+        };
+        assert!(project_matches_text(&project, "How is my raised garden doing?"));
+        assert!(project_matches_text(&project, "Should I water the tomatoes and peppers?"));
+        assert!(!project_matches_text(&project, "What is the weather like?"));
+    }
+
+    #[test]
+    fn test_task_prompt_lists_open_and_proposed_but_not_done() {
+        let task = |title: &str, status: TaskStatus| crate::storage::ProjectTask {
+            id: format!("project_task:{}", title),
+            project: "Parser".to_string(),
+            title: title.to_string(),
+            status,
+            completed_at: None,
+        };
+        assert!(format_project_tasks("Parser", &[task("Ship", TaskStatus::Done)]).is_none());
+
+        let tasks = [
+            task("Benchmark the parser", TaskStatus::Open),
+            task("Write docs", TaskStatus::Proposed),
+            task("Ship", TaskStatus::Done),
+        ];
+        let Some(prompt) = format_project_tasks("Parser", &tasks) else {
+            panic!("expected open tasks");
+        };
+        assert!(prompt.contains("- Benchmark the parser"));
+        assert!(prompt.contains("not yet confirmed by the user:\n- Write docs"));
+        assert!(!prompt.contains("Ship"));
+    }
+
+    #[test]
+    fn test_topic_replies_are_parsed_and_noise_is_dropped() {
+        let Some(topics) = parse_topic_json("{\"topics\": [\"Rust\", \"Small Talk\", \"42\"]}") else {
+            panic!("expected topics");
+        };
+        assert_eq!(filter_noise_topics(topics), vec!["rust"]);
+
+        let Some(bare) = parse_topic_json("Sure: [\"gardening\", \"gardening\", \"stuff\"]") else {
+            panic!("expected bare array");
+        };
+        assert_eq!(filter_noise_topics(bare), vec!["gardening"]);
+        assert!(parse_topic_json("no topics here").is_none());
+    }
+}
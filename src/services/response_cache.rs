@@ -0,0 +1,131 @@
+use crate::agents::ChatMessage;
+use crate::config::CacheConfig;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A reply to a deterministic sub-task (summary, intent, topics)
+struct CacheEntry {
+    /// Model and every message
+    key: u64,
+    /// Model and every message but the last, which is compared by similarity
+    context_key: u64,
+    words: HashSet<String>,
+    response: String,
+    stored_at: Instant,
+}
+
+/// In-memory replies keyed by hashed prompt + model. A miss on the exact key falls back
+/// to the entry whose last message is most similar, when it shares the rest of the prompt
+pub struct ResponseCache {
+    ttl: Duration,
+    similarity: f64,
+    max_entries: usize,
+    entries: Mutex<Vec<CacheEntry>>,
+}
+
+impl ResponseCache {
+    #[must_use]
+    pub fn new(config: &CacheConfig) -> Self {
+        Self {
+            ttl: Duration::from_secs(config.ttl_minutes.saturating_mul(60)),
+            similarity: config.similarity,
+            max_entries: config.max_entries.max(1),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, model: &str, messages: &[ChatMessage]) -> Option<String> {
+        let (key, context_key, words) = cache_keys(model, messages);
+        let mut entries = self.entries.lock().ok()?;
+        entries.retain(|entry| entry.stored_at.elapsed() < self.ttl);
+        if let Some(entry) = entries.iter().find(|entry| entry.key == key) {
+            return Some(entry.response.clone());
+        }
+        entries
+            .iter()
+            .filter(|entry| entry.context_key == context_key)
+            .map(|entry| (word_similarity(&words, &entry.words), entry))
+            .filter(|(score, _)| *score >= self.similarity)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, entry)| entry.response.clone())
+    }
+
+    pub fn insert(&self, model: &str, messages: &[ChatMessage], response: &str) {
+        let (key, context_key, words) = cache_keys(model, messages);
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        entries.retain(|entry| entry.key != key);
+        if entries.len() >= self.max_entries {
+            entries.remove(0);
+        }
+        entries.push(CacheEntry {
+            key,
+            context_key,
+            words,
+            response: response.to_string(),
+            stored_at: Instant::now(),
+        });
+    }
+}
+
+fn cache_keys(model: &str, messages: &[ChatMessage]) -> (u64, u64, HashSet<String>) {
+    let mut hasher = DefaultHasher::new();
+    model.hash(&mut hasher);
+    let (last, context) = messages.split_last().map_or((None, messages), |(last, rest)| (Some(last), rest));
+    for message in context {
+        message.role.hash(&mut hasher);
+        message.content.hash(&mut hasher);
+    }
+    let context_key = hasher.finish();
+    let words = last.map(|message| normalized_words(&message.content)).unwrap_or_default();
+    if let Some(message) = last {
+        message.role.hash(&mut hasher);
+        message.content.hash(&mut hasher);
+    }
+    (hasher.finish(), context_key, words)
+}
+
+fn normalized_words(text: &str) -> HashSet<String> {
+    text.split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Jaccard overlap of two word sets
+fn word_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hits_exact_and_near_identical_prompts_only() {
+        let cache = ResponseCache::new(&CacheConfig::default());
+        let system = ChatMessage::system("Classify the intent. Return JSON.");
+        let asked = [system.clone(), ChatMessage::user("What's the weather in Prague today?")];
+        cache.insert("llama", &asked, "{\"intent\":\"general\"}");
+
+        assert!(cache.get("llama", &asked).is_some());
+        let reworded = [system.clone(), ChatMessage::user("what's the weather in prague today")];
+        assert!(cache.get("llama", &reworded).is_some());
+        let different = [system, ChatMessage::user("Find my notes about the garden")];
+        assert!(cache.get("llama", &different).is_none());
+        assert!(cache.get("qwen", &asked).is_none());
+
+        let expired = ResponseCache::new(&CacheConfig { ttl_minutes: 0, ..CacheConfig::default() });
+        expired.insert("llama", &asked, "stale");
+        assert!(expired.get("llama", &asked).is_none());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::agents::{Agent, AgentManager, ChatMessage as AgentChatMessage};
+use chrono::{DateTime, Local};
+
+/// "earlier today", "yesterday", "5 days ago", "3 weeks ago" for an RFC 3339 save time
+#[must_use]
+pub fn describe_gap(last_saved: &str, now: &DateTime<Local>) -> Option<String> {
+    let saved = DateTime::parse_from_rfc3339(last_saved).ok()?.with_timezone(&Local);
+    let days = (now.date_naive() - saved.date_naive()).num_days().max(0);
+    Some(match days {
+        0 => "earlier today".to_string(),
+        1 => "yesterday".to_string(),
+        2..14 => format!("{} days ago", days),
+        14..60 => format!("{} weeks ago", days / 7),
+        _ => format!("{} months ago", days / 30),
+    })
+}
+
+/// Asks the first agent that answers (summary model first) for a one or two sentence
+/// welcome back, e.g. "We last spoke 5 days ago about the garden; you were going to try
+/// the new fertilizer"
+pub fn generate(summary: &str, gap: &str, agents: &[&Agent], manager: &AgentManager) -> String {
+    let prompt = format!(
+        "The user is reopening a conversation. In one or two sentences, remind them when you \
+last spoke ({}) and what it was about, and mention anything they were going to do. \
+Speak to the user directly, e.g. \"We last spoke 5 days ago about your garden; you were \
+going to try the new fertilizer.\" Return only the recap.\n\nSummary: {}",
+        gap, summary
+    );
+    let messages = vec![
+        AgentChatMessage::system("You write short recaps of earlier conversations."),
+        AgentChatMessage::user(&prompt),
+    ];
+    agents
+        .iter()
+        .find_map(|agent| {
+            manager
+                .chat(agent, &messages)
+                .ok()
+                .map(|recap| recap.trim().to_string())
+                .filter(|recap| !recap.is_empty())
+        })
+        .unwrap_or_else(|| format!("We last spoke {}. {}", gap, summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_describes_how_long_ago_we_spoke() {
+        let Some(now) = Local.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).single() else {
+            panic!("bad test date");
+        };
+        let saved = |days: i64| (now - chrono::Duration::days(days)).to_rfc3339();
+        assert_eq!(describe_gap(&saved(0), &now).as_deref(), Some("earlier today"));
+        assert_eq!(describe_gap(&saved(1), &now).as_deref(), Some("yesterday"));
+        assert_eq!(describe_gap(&saved(5), &now).as_deref(), Some("5 days ago"));
+        assert_eq!(describe_gap(&saved(21), &now).as_deref(), Some("3 weeks ago"));
+        assert_eq!(describe_gap(&saved(90), &now).as_deref(), Some("3 months ago"));
+        assert_eq!(describe_gap("not a date", &now), None);
+    }
+}
@@ -1,20 +1,14 @@
 use color_eyre::Result;
 use std::collections::HashMap;
+use std::time::Instant;
+use chrono::NaiveDateTime;
+use crate::agents::{Agent, AgentManager, ChatMessage as AgentChatMessage};
 use crate::storage::{RetrievedMessage, RetrievalSource, StorageManager};
 
 // Debug logging (disabled in production)
 #[allow(unused)]
-fn debug_log(_msg: &str) {
-    // Uncomment to enable debug logging:
-    // use std::io::Write;
-    // if let Ok(mut file) = std::fs::OpenOptions::new()
-    //     .create(true)
-    //     .append(true)
-    //     .open("/tmp/kimi-retrieval.log")
-    // {
-    //     let now = chrono::Local::now().format("%H:%M:%S%.3f");
-    //     let _ = writeln!(file, "[{}] {}", now, _msg);
-    // }
+fn debug_log(msg: &str) {
+    tracing::debug!(target: "kimi::retrieval", "{}", msg);
 }
 
 const EMBEDDING_BACKFILL_LIMIT: usize = 50;
@@ -28,6 +22,135 @@ const META_RECALL_SUMMARY_LIMIT: usize = 20;
 const MIN_SUBSTANTIVE_LENGTH: usize = 15;
 /// Number of messages missing embeddings that triggers opportunistic backfill
 const BACKFILL_THRESHOLD: usize = 10;
+/// Most rewritten queries searched next to the original
+const MAX_QUERY_EXPANSIONS: usize = 3;
+/// Share of its score the oldest memory keeps, so a strong old match can still surface
+const RECENCY_FLOOR: f32 = 0.3;
+/// Phrases marking a message the user explicitly asked to keep
+const REMEMBER_PHRASES: &[&str] = &["remember that", "remember this", "don't forget", "dont forget", "keep in mind", "note that"];
+/// Words that mark a message written with strong feeling
+const STRONG_EMOTION_WORDS: &[&str] = &[
+    "love", "hate", "afraid", "scared", "furious", "angry", "devastated", "heartbroken",
+    "thrilled", "excited", "terrified", "miserable", "ashamed", "grateful", "proud", "hurt",
+];
+
+/// Recency decay and importance boost applied to fused scores (`[embeddings]` settings)
+#[derive(Debug, Clone, Copy)]
+struct MemoryWeights {
+    /// Days after which a memory counts half; 0 turns decay off
+    half_life_days: f32,
+    /// Extra score for user messages, "remember ..." facts and emotional messages; 0 turns it off
+    importance: f32,
+    now: NaiveDateTime,
+}
+
+impl MemoryWeights {
+    fn from_config(config: &crate::config::EmbeddingsConfig) -> Self {
+        Self {
+            half_life_days: config.recency_half_life_days.max(0.0),
+            importance: config.importance_weight.max(0.0),
+            now: chrono::Local::now().naive_local(),
+        }
+    }
+
+    fn factor(&self, result: &RetrievedMessage) -> f32 {
+        let age_days = NaiveDateTime::parse_from_str(&result.timestamp, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|sent| (self.now - sent).num_hours().max(0) as f32 / 24.0);
+        let recency = match age_days {
+            Some(age_days) if self.half_life_days > 0.0 => {
+                RECENCY_FLOOR + (1.0 - RECENCY_FLOOR) * 0.5_f32.powf(age_days / self.half_life_days)
+            }
+            _ => 1.0,
+        };
+        recency * (1.0 + self.importance * importance_signal(result))
+    }
+}
+
+/// 0.0-1.0: a third each for being the user's own words, an explicit "remember" and strong emotion
+fn importance_signal(result: &RetrievedMessage) -> f32 {
+    let lowered = result.content.to_lowercase();
+    let signals = [
+        result.role.eq_ignore_ascii_case("user"),
+        REMEMBER_PHRASES.iter().any(|phrase| lowered.contains(phrase)),
+        tokenize_query(&lowered)
+            .iter()
+            .any(|token| STRONG_EMOTION_WORDS.contains(&token.as_str())),
+    ];
+    signals.iter().filter(|signal| **signal).count() as f32 / signals.len() as f32
+}
+
+/// Asks the first agent that answers for paraphrases of `query` plus a hypothetical
+/// statement that would answer it, so short questions like "what do i like" also match
+/// what the user actually wrote. Callers pass the routing model first, the chat model last.
+pub fn expand_query(query: &str, agents: &[&Agent], manager: &AgentManager) -> Vec<String> {
+    let started = Instant::now();
+    let prompt = format!(
+        "Rewrite this question for searching a personal chat history. Return JSON \
+{{\"queries\": [...]}} with two short paraphrases and one sentence the user might have \
+written that answers it, in first person.\n\nQuestion: {}",
+        query
+    );
+    let messages = vec![
+        AgentChatMessage::system("You rewrite search queries. Return only JSON."),
+        AgentChatMessage::user(&prompt),
+    ];
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "queries": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": MAX_QUERY_EXPANSIONS
+            }
+        },
+        "required": ["queries"]
+    });
+    let expansions = agents
+        .iter()
+        .find_map(|agent| {
+            manager
+                .chat_structured_cached(agent, &messages, &schema)
+                .ok()
+                .map(|response| parse_query_expansions(&response, query))
+        })
+        .unwrap_or_default();
+    tracing::info!(
+        target: "kimi::retrieval",
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        expansions = expansions.len(),
+        "query rewrite"
+    );
+    expansions
+}
+
+/// Reads `{"queries": [...]}`, dropping blanks, repeats and copies of the original query
+fn parse_query_expansions(raw: &str, query: &str) -> Vec<String> {
+    #[derive(serde::Deserialize)]
+    struct Expansions {
+        queries: Vec<String>,
+    }
+    let Some(json) = raw.find('{').and_then(|start| raw.rfind('}').and_then(|end| raw.get(start..=end)))
+    else {
+        return Vec::new();
+    };
+    let Ok(parsed) = serde_json::from_str::<Expansions>(json) else {
+        return Vec::new();
+    };
+    let mut kept: Vec<String> = Vec::new();
+    for candidate in parsed.queries {
+        let candidate = candidate.trim().to_string();
+        if candidate.is_empty()
+            || candidate.eq_ignore_ascii_case(query.trim())
+            || kept.iter().any(|existing| existing.eq_ignore_ascii_case(&candidate))
+        {
+            continue;
+        }
+        kept.push(candidate);
+    }
+    kept.truncate(MAX_QUERY_EXPANSIONS);
+    kept
+}
 
 /// Retrieves relevant messages from storage based on semantic similarity
 pub async fn retrieve_relevant_messages(
@@ -35,6 +158,18 @@ pub async fn retrieve_relevant_messages(
     query: &str,
     limit: usize,
     similarity_threshold: f32,
+) -> Result<Vec<RetrievedMessage>> {
+    retrieve_with_expansions(storage, query, &[], limit, similarity_threshold).await
+}
+
+/// Like `retrieve_relevant_messages`, also searching the rewrites from `expand_query`
+/// and fusing their dense results with the original query's
+pub async fn retrieve_with_expansions(
+    storage: &StorageManager,
+    query: &str,
+    expansions: &[String],
+    limit: usize,
+    similarity_threshold: f32,
 ) -> Result<Vec<RetrievedMessage>> {
     debug_log(&format!("=== retrieve_relevant_messages called for: '{}' ===", query));
 
@@ -44,6 +179,16 @@ pub async fn retrieve_relevant_messages(
         debug_log("Meta-recall query detected -- using broad retrieval");
         return build_meta_recall_results(storage, limit).await;
     }
+
+    // "What did Anna say?" only looks at messages attributed to that speaker with /as
+    if let Some(speaker) = speaker_in_query(query) {
+        let embedding = crate::services::embeddings::generate_embedding(query).await.ok();
+        let results = storage.search_speaker_messages(&speaker, embedding, limit).await?;
+        debug_log(&format!("Speaker query for '{}': {} results", speaker, results.len()));
+        if !results.is_empty() {
+            return Ok(results);
+        }
+    }
     
     // Debug: check embedding stats
     if let Ok((total, with_embedding)) = storage.get_embedding_stats().await {
@@ -99,6 +244,23 @@ pub async fn retrieve_relevant_messages(
             debug_log(&format!("Retry got {} results", dense_results.len()));
         }
     }
+
+    if query_embedding.is_some() && !expansions.is_empty() {
+        let started = Instant::now();
+        let mut lists = vec![dense_results];
+        let (embeddings, _) = crate::services::embeddings::generate_embeddings(expansions.to_vec()).await?;
+        for embedding in embeddings.into_iter().flatten() {
+            lists.push(storage.search_similar_messages(embedding, limit).await?);
+        }
+        dense_results = fuse_dense_lists(lists, limit);
+        tracing::info!(
+            target: "kimi::retrieval",
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            expansions = expansions.len(),
+            results = dense_results.len(),
+            "expanded dense search"
+        );
+    }
     
     let sparse_results = match build_keyword_query(query) {
         Some(keyword_query) => {
@@ -119,7 +281,12 @@ pub async fn retrieve_relevant_messages(
             Vec::new()
         }
     };
-    let mut fused_results = fuse_results(dense_results, sparse_results, limit);
+    let weights = MemoryWeights::from_config(
+        &crate::config::Config::load()
+            .map(|config| config.embeddings)
+            .unwrap_or_default(),
+    );
+    let mut fused_results = fuse_results(dense_results, sparse_results, limit, &weights);
     debug_log(&format!("Fused: {} results", fused_results.len()));
 
     // For profile queries, ALWAYS check heuristic fallback since vector search
@@ -166,32 +333,42 @@ pub async fn retrieve_relevant_messages(
     }
     
     debug_log(&format!("=== Returning {} results ===", filtered.len()));
+    if let Err(error) = storage.record_retrievals(&filtered).await {
+        tracing::warn!(%error, "could not count retrievals");
+    }
     Ok(filtered)
 }
 
 /// Maximum character length for embeddings (to avoid context length errors)
 const MAX_EMBEDDING_LENGTH: usize = 2000;
 
-/// Generates and returns an embedding for a message
-pub async fn generate_message_embedding(content: &str) -> Result<Option<Vec<f32>>> {
+/// Trims `content` and cuts it to the embedding length limit; `None` for texts too short to embed
+fn prepare_embedding_text(content: &str) -> Option<&str> {
     let trimmed = content.trim();
     
     // Skip embedding for very short messages
     if trimmed.len() < 10 {
-        return Ok(None);
+        return None;
     }
     
     // Truncate if too long to avoid context length errors
-    let embedding_text = if trimmed.len() > MAX_EMBEDDING_LENGTH {
+    if trimmed.len() > MAX_EMBEDDING_LENGTH {
         let truncated = &trimmed[..MAX_EMBEDDING_LENGTH];
         // Try to truncate at word boundary
         if let Some(last_space) = truncated.rfind(' ') {
-            &trimmed[..last_space]
+            Some(&trimmed[..last_space])
         } else {
-            truncated
+            Some(truncated)
         }
     } else {
-        trimmed
+        Some(trimmed)
+    }
+}
+
+/// Generates and returns an embedding for a message
+pub async fn generate_message_embedding(content: &str) -> Result<Option<Vec<f32>>> {
+    let Some(embedding_text) = prepare_embedding_text(content) else {
+        return Ok(None);
     };
     
     match crate::services::embeddings::generate_embedding(embedding_text).await {
@@ -204,13 +381,31 @@ pub async fn generate_message_embedding(content: &str) -> Result<Option<Vec<f32>
     }
 }
 
+/// Batched counterpart of `generate_message_embedding`, one result per input in order
+pub async fn generate_message_embeddings(contents: &[&str]) -> Result<Vec<Option<Vec<f32>>>> {
+    let prepared: Vec<Option<&str>> = contents.iter().map(|content| prepare_embedding_text(content)).collect();
+    let texts: Vec<String> = prepared.iter().flatten().map(|text| (*text).to_string()).collect();
+    if texts.is_empty() {
+        return Ok(vec![None; contents.len()]);
+    }
+    let (embeddings, stats) = crate::services::embeddings::generate_embeddings(texts).await?;
+    debug_log(&format!("Embedded {}", stats.describe()));
+    let mut embeddings = embeddings.into_iter();
+    Ok(prepared
+        .into_iter()
+        .map(|text| text.and_then(|_| embeddings.next().flatten()))
+        .collect())
+}
+
 async fn backfill_missing_embeddings(storage: &StorageManager) -> Result<usize> {
     let candidates = storage
         .load_messages_missing_embeddings(EMBEDDING_BACKFILL_LIMIT)
         .await?;
+    let contents: Vec<&str> = candidates.iter().map(|candidate| candidate.content.as_str()).collect();
+    let embeddings = generate_message_embeddings(&contents).await?;
     let mut updated = 0;
-    for candidate in candidates {
-        if let Some(embedding) = generate_message_embedding(&candidate.content).await? {
+    for (candidate, embedding) in candidates.into_iter().zip(embeddings) {
+        if let Some(embedding) = embedding {
             storage.update_message_embedding_by_id(candidate.id, embedding).await?;
             updated += 1;
         }
@@ -222,6 +417,7 @@ fn fuse_results(
     dense_results: Vec<RetrievedMessage>,
     sparse_results: Vec<RetrievedMessage>,
     limit: usize,
+    weights: &MemoryWeights,
 ) -> Vec<RetrievedMessage> {
     let mut fused: HashMap<String, RetrievedMessage> = HashMap::new();
     let mut dense_ranks: HashMap<String, usize> = HashMap::new();
@@ -250,7 +446,7 @@ fn fuse_results(
             let dense_rank = dense_ranks.get(&key).copied();
             let sparse_rank = sparse_ranks.get(&key).copied();
             let score = rrf_score(dense_rank) + rrf_score(sparse_rank);
-            entry.score = score;
+            entry.score = score * weights.factor(&entry);
             entry
         })
         .collect();
@@ -260,6 +456,33 @@ fn fuse_results(
     results
 }
 
+/// Reciprocal-rank fusion of several dense result lists, keeping each message's best similarity
+fn fuse_dense_lists(lists: Vec<Vec<RetrievedMessage>>, limit: usize) -> Vec<RetrievedMessage> {
+    let mut fused: HashMap<String, RetrievedMessage> = HashMap::new();
+    let mut scores: HashMap<String, f32> = HashMap::new();
+    for list in lists {
+        for (index, result) in list.into_iter().enumerate() {
+            let key = result_key(&result);
+            let similarity = result.similarity;
+            *scores.entry(key.clone()).or_insert(0.0) += rrf_score(Some(index + 1));
+            fused
+                .entry(key)
+                .and_modify(|entry| entry.similarity = entry.similarity.max(similarity))
+                .or_insert(result);
+        }
+    }
+    let mut results: Vec<RetrievedMessage> = fused
+        .into_iter()
+        .map(|(key, mut entry)| {
+            entry.score = scores.get(&key).copied().unwrap_or(0.0);
+            entry
+        })
+        .collect();
+    results.sort_by(|left, right| right.score.total_cmp(&left.score));
+    results.truncate(limit);
+    results
+}
+
 fn rrf_score(rank: Option<usize>) -> f32 {
     rank.map_or(0.0, |value| 1.0 / (RRF_K + value as f32))
 }
@@ -387,6 +610,7 @@ async fn build_profile_fallback(
                 content: message.content,
                 role: message.role,
                 timestamp: message.timestamp,
+                display_name: message.display_name,
                 similarity: 0.0,
                 score: 0.01,
                 source: RetrievalSource::Heuristic,
@@ -411,6 +635,50 @@ fn is_profile_fact_candidate(content: &str) -> bool {
         || lowered.contains("i work ")
 }
 
+/// Name of the person a query asks about, as in "what did Anna say about the trip?",
+/// "Anna said", or "according to Anna"
+pub fn speaker_in_query(query: &str) -> Option<String> {
+    let words: Vec<&str> = query
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-'))
+        .filter(|word| !word.is_empty())
+        .collect();
+    let lowered: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    let is_name = |word: &str| {
+        word.chars().next().is_some_and(char::is_uppercase)
+            && !matches!(word, "I" | "You" | "We" | "They" | "He" | "She")
+    };
+    let speech_verbs = ["say", "mention", "think", "tell", "write", "ask"];
+    let past_speech_verbs = ["said", "mentioned", "thought", "told", "wrote", "asked"];
+
+    for (index, word) in lowered.iter().enumerate() {
+        // "what did Anna say" / "did Anna mention"
+        if word == "did"
+            && let (Some(name), Some(verb)) = (words.get(index + 1), lowered.get(index + 2))
+            && is_name(name)
+            && speech_verbs.contains(&verb.as_str())
+        {
+            return Some((*name).to_string());
+        }
+        // "according to Anna"
+        if word == "according"
+            && lowered.get(index + 1).is_some_and(|next| next == "to")
+            && let Some(name) = words.get(index + 2)
+            && is_name(name)
+        {
+            return Some((*name).to_string());
+        }
+        // "Anna said" / "anything Anna mentioned"
+        if past_speech_verbs.contains(&word.as_str())
+            && let Some(name) = index.checked_sub(1).and_then(|prev| words.get(prev))
+            && is_name(name)
+        {
+            return Some((*name).to_string());
+        }
+    }
+    None
+}
+
 /// Detects broad meta-recall queries where the user asks "what do you remember/know about me?"
 /// These need a broad retrieval strategy since semantic search can't match meta-questions to content.
 pub fn is_meta_recall_query(query: &str) -> bool {
@@ -456,14 +724,21 @@ pub async fn build_meta_recall_results(
             // Prefer detailed_summary, fall back to summary
             let text = summary
                 .detailed_summary
+                .clone()
                 .filter(|value| !value.trim().is_empty())
-                .or(summary.summary.filter(|value| !value.trim().is_empty()));
+                .or_else(|| {
+                    summary
+                        .title()
+                        .filter(|value| !value.trim().is_empty())
+                        .map(str::to_string)
+                });
 
             if let Some(text) = text {
                 results.push(RetrievedMessage {
                     content: format!("[Conversation summary] {}", text),
                     role: "System".to_string(),
                     timestamp: summary.created_at,
+                    display_name: None,
                     similarity: 0.0,
                     score: 0.02,
                     source: RetrievalSource::Heuristic,
@@ -471,6 +746,23 @@ pub async fn build_meta_recall_results(
             }
         }
     }
+    // Parts of long conversations, so details past the overall summary are recalled too
+    if let Ok(chunks) = storage
+        .load_recent_chunk_summaries(META_RECALL_SUMMARY_LIMIT)
+        .await
+    {
+        for (created_at, text) in chunks.into_iter().take(META_RECALL_SUMMARY_LIMIT) {
+            results.push(RetrievedMessage {
+                content: format!("[Conversation part] {}", text),
+                role: "System".to_string(),
+                timestamp: created_at,
+                display_name: None,
+                similarity: 0.0,
+                score: 0.015,
+                source: RetrievalSource::Heuristic,
+            });
+        }
+    }
     debug_log(&format!(
         "Meta-recall: {} conversation summaries loaded",
         results.len()
@@ -487,6 +779,7 @@ pub async fn build_meta_recall_results(
                     content: message.content,
                     role: message.role,
                     timestamp: message.timestamp,
+                    display_name: message.display_name,
                     similarity: 0.0,
                     score: 0.01,
                     source: RetrievalSource::Heuristic,
@@ -529,3 +822,71 @@ fn is_substantive_statement(content: &str) -> bool {
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_the_speaker_a_query_asks_about() {
+        assert_eq!(speaker_in_query("What did Anna say about the trip?").as_deref(), Some("Anna"));
+        assert_eq!(speaker_in_query("what Tomas mentioned yesterday").as_deref(), Some("Tomas"));
+        assert_eq!(speaker_in_query("according to Eva, is it Friday?").as_deref(), Some("Eva"));
+        assert_eq!(speaker_in_query("What did I say about the trip?"), None);
+        assert_eq!(speaker_in_query("what did you think of it"), None);
+        assert_eq!(speaker_in_query("Remind me what we planned"), None);
+    }
+
+    fn dense(content: &str, similarity: f32) -> RetrievedMessage {
+        RetrievedMessage {
+            content: content.to_string(),
+            role: "User".to_string(),
+            timestamp: "2026-10-17 09:00:00".to_string(),
+            display_name: None,
+            similarity,
+            score: similarity,
+            source: RetrievalSource::Dense,
+        }
+    }
+
+    #[test]
+    fn test_fuses_dense_results_of_rewritten_queries() {
+        let original = vec![dense("i like hiking", 0.4), dense("weather is nice", 0.35)];
+        let rewritten = vec![dense("i really enjoy sushi", 0.7), dense("i like hiking", 0.6)];
+        let fused = fuse_dense_lists(vec![original, rewritten], 10);
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused.first().map(|result| result.content.as_str()), Some("i like hiking"));
+        assert_eq!(fused.first().map(|result| result.similarity), Some(0.6));
+    }
+
+    #[test]
+    fn test_parses_query_rewrites() {
+        let raw = "Sure: {\"queries\": [\"What are my hobbies\", \"what do i like\", \"\", \"what are my hobbies\", \"I like hiking and sushi\"]}";
+        assert_eq!(
+            parse_query_expansions(raw, "what do i like"),
+            vec!["What are my hobbies".to_string(), "I like hiking and sushi".to_string()]
+        );
+        assert!(parse_query_expansions("no json here", "what do i like").is_empty());
+    }
+
+    #[test]
+    fn test_newer_and_important_memories_score_higher() {
+        let Ok(now) = NaiveDateTime::parse_from_str("2026-10-17 09:00:00", "%Y-%m-%d %H:%M:%S") else {
+            panic!("bad test date");
+        };
+        let weights = MemoryWeights { half_life_days: 30.0, importance: 0.5, now };
+        let mut recent = dense("we talked about the garden", 0.5);
+        recent.role = "Assistant".to_string();
+        let mut old = recent.clone();
+        old.timestamp = "2025-10-17 09:00:00".to_string();
+        assert!(weights.factor(&recent) > weights.factor(&old));
+        assert!(weights.factor(&old) >= RECENCY_FLOOR);
+
+        let remembered = dense("Remember that I love the garden", 0.5);
+        assert!((importance_signal(&remembered) - 1.0).abs() < f32::EPSILON);
+        assert!(weights.factor(&remembered) > weights.factor(&recent));
+
+        let flat = MemoryWeights { half_life_days: 0.0, importance: 0.0, now };
+        assert!((flat.factor(&old) - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::config::ScheduledJobConfig;
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+use color_eyre::{Result, eyre::eyre};
+
+/// A run missed while Kimi was closed still happens within this window, not days later
+const MISSED_RUN_GRACE_HOURS: i64 = 6;
+const ALL_DAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Data gathered for a job before the model writes its result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Weather,
+    Agenda,
+    News,
+    Tasks,
+}
+
+impl Source {
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "weather" | "forecast" => Some(Self::Weather),
+            "agenda" | "calendar" | "events" | "schedule" => Some(Self::Agenda),
+            "news" | "feeds" | "headlines" => Some(Self::News),
+            "tasks" | "todo" | "todos" | "reminders" => Some(Self::Tasks),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Weather => "weather",
+            Self::Agenda => "agenda",
+            Self::News => "news",
+            Self::Tasks => "tasks",
+        }
+    }
+}
+
+/// A recurring job from `[[schedule]]` or `/schedule`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    /// Stable id its runs are recorded under: "config:<name>" or the stored record id
+    pub key: String,
+    pub name: String,
+    pub days: Vec<Weekday>,
+    pub at: NaiveTime,
+    pub sources: Vec<Source>,
+    /// What to do with the gathered data ("prepare a briefing")
+    pub prompt: String,
+}
+
+impl Job {
+    pub fn from_config(config: &ScheduledJobConfig) -> Result<Self> {
+        let name = config.name.trim();
+        if name.is_empty() {
+            return Err(eyre!("[[schedule]] entry without a name"));
+        }
+        let at = parse_time(&config.at).ok_or_else(|| eyre!("{}: cannot read time \"{}\"", name, config.at))?;
+        let days = parse_days(&config.days).ok_or_else(|| eyre!("{}: cannot read days \"{}\"", name, config.days))?;
+        let mut sources = Vec::new();
+        for source in &config.sources {
+            let source = Source::from_name(source).ok_or_else(|| eyre!("{}: unknown source \"{}\"", name, source))?;
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        Ok(Self {
+            key: format!("config:{}", name),
+            name: name.to_string(),
+            days,
+            at,
+            sources,
+            prompt: config.prompt.trim().to_string(),
+        })
+    }
+
+    /// "weekdays at 08:00: weather, agenda, news. Prepare a briefing"
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut text = format!("{} at {}", describe_days(&self.days), self.at.format("%H:%M"));
+        if !self.sources.is_empty() {
+            let sources: Vec<&str> = self.sources.iter().map(|source| source.label()).collect();
+            text.push_str(&format!(": {}", sources.join(", ")));
+        }
+        if !self.prompt.is_empty() {
+            text.push_str(&format!(". {}", self.prompt));
+        }
+        text
+    }
+
+    /// The latest scheduled time at or before `now`, looking back a week
+    #[must_use]
+    pub fn last_slot(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        (0..7)
+            .map(|days_back| now.date() - Duration::days(days_back))
+            .filter(|date| self.days.contains(&date.weekday()))
+            .map(|date| date.and_time(self.at))
+            .find(|slot| *slot <= now)
+    }
+
+    /// Due when its latest slot has not run yet and is recent enough to still be useful
+    #[must_use]
+    pub fn is_due(&self, last_run: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+        let Some(slot) = self.last_slot(now) else {
+            return false;
+        };
+        let recent = now - slot < Duration::hours(MISSED_RUN_GRACE_HOURS);
+        recent && last_run.is_none_or(|last_run| last_run < slot)
+    }
+}
+
+/// "08:00", "8", "8:30", "8am", "6:15 pm"
+#[must_use]
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    let value = value.trim().to_lowercase().replace(' ', "");
+    let (digits, offset) = if let Some(digits) = value.strip_suffix("am") {
+        (digits, 0)
+    } else if let Some(digits) = value.strip_suffix("pm") {
+        (digits, 12)
+    } else {
+        (value.as_str(), 0)
+    };
+    let (hours, minutes) = digits.split_once(':').unwrap_or((digits, "0"));
+    let mut hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    if offset > 0 || value.ends_with("am") {
+        if !(1..=12).contains(&hours) {
+            return None;
+        }
+        hours = hours % 12 + offset;
+    }
+    NaiveTime::from_hms_opt(hours, minutes, 0)
+}
+
+/// "daily", "weekdays", "weekends" or day names like "mon,wed,fri"
+#[must_use]
+pub fn parse_days(value: &str) -> Option<Vec<Weekday>> {
+    let value = value.trim().to_lowercase();
+    match value.as_str() {
+        "" | "daily" | "every day" | "everyday" => return Some(ALL_DAYS.to_vec()),
+        "weekdays" | "weekday" | "workdays" => return Some(ALL_DAYS.iter().copied().take(5).collect()),
+        "weekends" | "weekend" => return Some(vec![Weekday::Sat, Weekday::Sun]),
+        _ => {}
+    }
+    let mut days: Vec<Weekday> = Vec::new();
+    for name in value.split([',', ' ', '/']).filter(|name| !name.is_empty() && *name != "and") {
+        let day = weekday_from_name(name)?;
+        if !days.contains(&day) {
+            days.push(day);
+        }
+    }
+    days.sort_by_key(Weekday::num_days_from_monday);
+    (!days.is_empty()).then_some(days)
+}
+
+fn weekday_from_name(name: &str) -> Option<Weekday> {
+    let name = name.trim_end_matches('s');
+    ALL_DAYS.iter().copied().find(|day| {
+        let full = match day {
+            Weekday::Mon => "monday",
+            Weekday::Tue => "tuesday",
+            Weekday::Wed => "wednesday",
+            Weekday::Thu => "thursday",
+            Weekday::Fri => "friday",
+            Weekday::Sat => "saturday",
+            Weekday::Sun => "sunday",
+        };
+        name.len() >= 3 && full.starts_with(name)
+    })
+}
+
+#[must_use]
+pub fn describe_days(days: &[Weekday]) -> String {
+    if days.len() == 7 {
+        return "daily".to_string();
+    }
+    if days == [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri] {
+        return "weekdays".to_string();
+    }
+    if days == [Weekday::Sat, Weekday::Sun] {
+        return "weekends".to_string();
+    }
+    days.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// Reads "every morning at 8: fetch weather + agenda + top news and prepare a briefing".
+/// The schedule comes before the colon, the instruction after; sources are the data words
+/// the instruction mentions
+pub fn parse_request(request: &str) -> Result<ScheduledJobConfig> {
+    let (when, instruction) = request
+        .split_once(':')
+        .filter(|(when, _)| !when.chars().all(|c| c.is_ascii_digit() || c.is_whitespace()))
+        .map(|(when, instruction)| {
+            // "at 8:30: ..." splits at the minutes; put them back
+            match instruction.split_once(':') {
+                Some((minutes, rest)) if minutes.trim().chars().all(|c| c.is_ascii_digit()) && !minutes.trim().is_empty() => {
+                    (format!("{}:{}", when, minutes.trim()), rest.to_string())
+                }
+                _ => (when.to_string(), instruction.to_string()),
+            }
+        })
+        .ok_or_else(|| eyre!("Usage: schedule <when>: <what>, e.g. schedule every morning at 8: weather + agenda + news briefing"))?;
+    let when = when.to_lowercase();
+    let instruction = instruction.trim();
+    if instruction.is_empty() {
+        return Err(eyre!("Say what to do after the colon"));
+    }
+
+    let words: Vec<&str> = when.split_whitespace().collect();
+    let at = words
+        .iter()
+        .position(|word| *word == "at")
+        .and_then(|index| words.get(index + 1..))
+        .map(|rest| rest.iter().take(2).copied().collect::<Vec<_>>().join(""))
+        .and_then(|time| parse_time(&time).or_else(|| parse_time(time.trim_end_matches(|c: char| c.is_alphabetic()))))
+        .or_else(|| {
+            words.iter().find_map(|word| match *word {
+                "morning" => NaiveTime::from_hms_opt(8, 0, 0),
+                "noon" | "lunchtime" => NaiveTime::from_hms_opt(12, 0, 0),
+                "afternoon" => NaiveTime::from_hms_opt(15, 0, 0),
+                "evening" => NaiveTime::from_hms_opt(19, 0, 0),
+                "night" => NaiveTime::from_hms_opt(22, 0, 0),
+                _ => None,
+            })
+        })
+        .ok_or_else(|| eyre!("When should it run? Add a time like \"at 8\" or \"at 18:30\""))?;
+    let day_words: Vec<&str> = words
+        .iter()
+        .copied()
+        .take_while(|word| *word != "at")
+        .filter(|word| !matches!(*word, "every" | "each" | "on" | "morning" | "noon" | "lunchtime" | "afternoon" | "evening" | "night" | "day"))
+        .collect();
+    let days = if day_words.is_empty() { "daily".to_string() } else { day_words.join(",") };
+    parse_days(&days).ok_or_else(|| eyre!("Cannot read the days in \"{}\"", when))?;
+
+    let mut sources: Vec<String> = Vec::new();
+    for word in instruction.split(|c: char| !c.is_alphanumeric()) {
+        if let Some(source) = Source::from_name(word)
+            && !sources.iter().any(|known| known == source.label())
+        {
+            sources.push(source.label().to_string());
+        }
+    }
+    let name: String = instruction.chars().take(40).collect();
+    Ok(ScheduledJobConfig {
+        name: name.trim().to_string(),
+        at: at.format("%H:%M").to_string(),
+        days,
+        sources,
+        prompt: instruction.to_string(),
+        enabled: true,
+    })
+}
+
+/// The model's instructions for one run: what was gathered, then what to do with it
+#[must_use]
+pub fn format_job_prompt(job: &Job, gathered: &[(Source, String)], now: NaiveDateTime) -> String {
+    let mut prompt = format!(
+        "It is {}. This is your scheduled job \"{}\", running without the user present; \
+they will read the result later in their inbox.\n",
+        now.format("%A %B %-d, %H:%M"),
+        job.name
+    );
+    for (source, text) in gathered {
+        prompt.push_str(&format!("\n## {}\n{}\n", source.label(), text.trim()));
+    }
+    let task = if job.prompt.is_empty() { "Summarize the above for the user." } else { job.prompt.as_str() };
+    prompt.push_str(&format!("\nTask: {}\nWrite only the result, concise and ready to read.", task));
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M")
+            .unwrap_or_else(|error| panic!("bad test time: {error}"))
+    }
+
+    #[test]
+    fn test_reads_times_and_days() {
+        assert_eq!(parse_time("8"), NaiveTime::from_hms_opt(8, 0, 0));
+        assert_eq!(parse_time("6:15 pm"), NaiveTime::from_hms_opt(18, 15, 0));
+        assert_eq!(parse_time("12am"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time("25:00"), None);
+        assert_eq!(parse_days("weekdays").map(|days| days.len()), Some(5));
+        assert_eq!(parse_days("wed, mon"), Some(vec![Weekday::Mon, Weekday::Wed]));
+        assert_eq!(parse_days("mondays"), Some(vec![Weekday::Mon]));
+        assert_eq!(parse_days("someday"), None);
+    }
+
+    #[test]
+    fn test_parses_natural_requests() {
+        let Ok(job) = parse_request("every morning at 8: fetch weather + agenda + top news and prepare a briefing") else {
+            panic!("request should parse");
+        };
+        assert_eq!(job.at, "08:00");
+        assert_eq!(job.days, "daily");
+        assert_eq!(job.sources, vec!["weather", "agenda", "news"]);
+
+        let Ok(job) = parse_request("weekdays at 18:30: list my tasks") else {
+            panic!("request should parse");
+        };
+        assert_eq!((job.at.as_str(), job.days.as_str()), ("18:30", "weekdays"));
+        assert_eq!(job.prompt, "list my tasks");
+        assert!(parse_request("prepare a briefing").is_err());
+    }
+
+    #[test]
+    fn test_runs_once_per_slot_and_skips_stale_ones() {
+        let Ok(job) = Job::from_config(&ScheduledJobConfig {
+            name: "Briefing".to_string(),
+            at: "08:00".to_string(),
+            days: "weekdays".to_string(),
+            sources: vec!["weather".to_string()],
+            prompt: String::new(),
+            enabled: true,
+        }) else {
+            panic!("job should load");
+        };
+        // 2026-10-16 is a Friday
+        assert!(job.is_due(None, at("2026-10-16 08:01")));
+        assert!(!job.is_due(Some(at("2026-10-16 08:01")), at("2026-10-16 09:00")));
+        assert!(!job.is_due(None, at("2026-10-16 07:59")));
+        assert!(!job.is_due(None, at("2026-10-16 20:00")));
+        // Saturday: Friday's slot is long gone
+        assert!(!job.is_due(Some(at("2026-10-15 08:00")), at("2026-10-17 08:30")));
+        assert_eq!(job.describe(), "weekdays at 08:00: weather");
+    }
+}
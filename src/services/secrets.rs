@@ -0,0 +1,338 @@
+use argon2::Argon2;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use color_eyre::{Result, eyre::eyre};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const SERVICE_NAME: &str = "kimi";
+/// Config sections whose `api_key` lives in the secrets backend
+pub const API_KEY_NAMES: [&str; 8] = [
+    "elevenlabs",
+    "venice",
+    "gab",
+    "brave",
+    "github",
+    "homeassistant",
+    "transit",
+    "images",
+];
+/// Set to `file` to skip the OS keyring, or `keyring` to require it
+const BACKEND_ENV: &str = "KIMI_SECRETS_BACKEND";
+/// Passphrase for the encrypted-file backend (prompted on the terminal when unset)
+const PASSPHRASE_ENV: &str = "KIMI_SECRETS_PASSPHRASE";
+const SECRETS_FILE_NAME: &str = "secrets.enc";
+const SECRETS_FILE_VERSION: u32 = 1;
+const PLACEHOLDER_API_KEY: &str = "your_api_key_here";
+const SALT_LENGTH: usize = 16;
+
+/// Where API keys are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretsBackend {
+    Keyring,
+    EncryptedFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+struct SecretStore {
+    backend: SecretsBackend,
+    values: HashMap<String, String>,
+    /// Derived key and salt for the encrypted file, once unlocked
+    file_key: Option<([u8; 32], [u8; SALT_LENGTH])>,
+}
+
+/// Secrets are read once at startup and cached, so lookups never block on the keyring
+static STORE: Mutex<Option<SecretStore>> = Mutex::new(None);
+
+fn secrets_file_path() -> Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "kimi")
+        .ok_or_else(|| eyre!("Could not determine data directory"))?;
+    Ok(project_dirs.data_dir().join(SECRETS_FILE_NAME))
+}
+
+fn keyring_entry(name: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE_NAME, name).map_err(|error| eyre!("Keyring unavailable: {}", error))
+}
+
+fn keyring_available() -> bool {
+    let Ok(entry) = keyring_entry("probe") else {
+        return false;
+    };
+    matches!(entry.get_password(), Ok(_) | Err(keyring::Error::NoEntry))
+}
+
+fn choose_backend() -> Result<SecretsBackend> {
+    match std::env::var(BACKEND_ENV).ok().as_deref() {
+        Some("file") => Ok(SecretsBackend::EncryptedFile),
+        Some("keyring") if keyring_available() => Ok(SecretsBackend::Keyring),
+        Some("keyring") => Err(eyre!("{}=keyring but no OS keyring is reachable", BACKEND_ENV)),
+        _ if keyring_available() => Ok(SecretsBackend::Keyring),
+        _ => Ok(SecretsBackend::EncryptedFile),
+    }
+}
+
+/// Picks a backend (OS keyring, falling back to an encrypted file) and loads all
+/// stored API keys. Must run before the TUI starts since it may prompt for a passphrase.
+pub fn init() -> Result<SecretsBackend> {
+    let backend = choose_backend()?;
+    let mut store = SecretStore {
+        backend,
+        values: HashMap::new(),
+        file_key: None,
+    };
+
+    match backend {
+        SecretsBackend::Keyring => {
+            for name in API_KEY_NAMES {
+                if let Ok(value) = keyring_entry(name)?.get_password() {
+                    store.values.insert(name.to_string(), value);
+                }
+            }
+        }
+        SecretsBackend::EncryptedFile => {
+            let path = secrets_file_path()?;
+            if path.exists() {
+                let file: EncryptedFile = serde_json::from_str(&fs::read_to_string(&path)?)?;
+                let salt: [u8; SALT_LENGTH] = BASE64
+                    .decode(&file.salt)?
+                    .try_into()
+                    .map_err(|_| eyre!("Corrupt secrets file (salt)"))?;
+                let passphrase = read_passphrase("Passphrase for kimi secrets: ")?;
+                let key = derive_key(&passphrase, &salt)?;
+                store.values = decrypt_values(&key, &file)?;
+                store.file_key = Some((key, salt));
+            }
+        }
+    }
+
+    let mut guard = STORE.lock().map_err(|_| eyre!("Secrets store poisoned"))?;
+    *guard = Some(store);
+    Ok(backend)
+}
+
+/// Returns the active backend, if `init` succeeded
+#[must_use]
+pub fn backend() -> Option<SecretsBackend> {
+    STORE.lock().ok()?.as_ref().map(|store| store.backend)
+}
+
+/// Returns a cached secret (empty values are treated as missing)
+#[must_use]
+pub fn get_secret(name: &str) -> Option<String> {
+    STORE
+        .lock()
+        .ok()?
+        .as_ref()?
+        .values
+        .get(name)
+        .filter(|value| !value.trim().is_empty())
+        .cloned()
+}
+
+/// Stores a secret in the active backend; an empty value deletes it
+pub fn set_secret(name: &str, value: &str) -> Result<()> {
+    let mut guard = STORE.lock().map_err(|_| eyre!("Secrets store poisoned"))?;
+    let store = guard
+        .as_mut()
+        .ok_or_else(|| eyre!("Secrets backend not initialized"))?;
+    let value = value.trim();
+
+    match store.backend {
+        SecretsBackend::Keyring => {
+            let entry = keyring_entry(name)?;
+            if value.is_empty() {
+                match entry.delete_credential() {
+                    Ok(()) | Err(keyring::Error::NoEntry) => {}
+                    Err(error) => return Err(eyre!("Keyring delete failed: {}", error)),
+                }
+            } else {
+                entry
+                    .set_password(value)
+                    .map_err(|error| eyre!("Keyring write failed: {}", error))?;
+            }
+            update_cached_value(store, name, value);
+        }
+        SecretsBackend::EncryptedFile => {
+            let (key, salt) = match store.file_key {
+                Some(file_key) => file_key,
+                None => {
+                    let passphrase = read_passphrase("New passphrase for kimi secrets: ")?;
+                    let mut salt = [0u8; SALT_LENGTH];
+                    OsRng.fill_bytes(&mut salt);
+                    let file_key = (derive_key(&passphrase, &salt)?, salt);
+                    store.file_key = Some(file_key);
+                    file_key
+                }
+            };
+            update_cached_value(store, name, value);
+            write_encrypted_file(&key, &salt, &store.values)?;
+        }
+    }
+    Ok(())
+}
+
+fn update_cached_value(store: &mut SecretStore, name: &str, value: &str) {
+    if value.is_empty() {
+        store.values.remove(name);
+    } else {
+        store.values.insert(name.to_string(), value.to_string());
+    }
+}
+
+fn read_passphrase(prompt: &str) -> Result<String> {
+    prompt_passphrase(PASSPHRASE_ENV, prompt)
+}
+
+/// Passphrase from `env_name`, or a no-echo terminal prompt before the TUI starts
+pub fn prompt_passphrase(env_name: &str, prompt: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(env_name)
+        && !passphrase.is_empty()
+    {
+        return Ok(passphrase);
+    }
+    if !std::io::stdin().is_terminal() || crossterm::terminal::is_raw_mode_enabled().unwrap_or(false) {
+        return Err(eyre!(
+            "Passphrase required; set {} or restart kimi to enter it",
+            env_name
+        ));
+    }
+    let passphrase = rpassword::prompt_password(prompt)?;
+    if passphrase.is_empty() {
+        return Err(eyre!("Empty passphrase"));
+    }
+    Ok(passphrase)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|error| eyre!("Key derivation failed: {}", error))?;
+    Ok(key)
+}
+
+fn encrypt_values(
+    key: &[u8; 32],
+    salt: &[u8],
+    values: &HashMap<String, String>,
+) -> Result<EncryptedFile> {
+    let cipher = ChaCha20Poly1305::new(key.into());
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let plaintext = serde_json::to_vec(values)?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| eyre!("Encrypting secrets failed"))?;
+    Ok(EncryptedFile {
+        version: SECRETS_FILE_VERSION,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn decrypt_values(key: &[u8; 32], file: &EncryptedFile) -> Result<HashMap<String, String>> {
+    if file.version > SECRETS_FILE_VERSION {
+        return Err(eyre!("Secrets file version {} is not supported", file.version));
+    }
+    let cipher = ChaCha20Poly1305::new(key.into());
+    let nonce_bytes = BASE64.decode(&file.nonce)?;
+    if nonce_bytes.len() != 12 {
+        return Err(eyre!("Corrupt secrets file (nonce)"));
+    }
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), BASE64.decode(&file.ciphertext)?.as_slice())
+        .map_err(|_| eyre!("Wrong passphrase or corrupt secrets file"))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+fn write_encrypted_file(
+    key: &[u8; 32],
+    salt: &[u8],
+    values: &HashMap<String, String>,
+) -> Result<()> {
+    let path = secrets_file_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = encrypt_values(key, salt, values)?;
+    fs::write(&path, serde_json::to_string_pretty(&file)?)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+/// Moves plaintext `api_key` values out of config.local.toml / config.toml into the
+/// secrets backend and strips them from those files. Returns how many keys moved.
+pub fn migrate_plaintext_keys() -> Result<usize> {
+    let mut migrated = 0;
+    let project_config_path = crate::config::Config::project_config_path()?;
+    for path in [crate::config::Config::local_config_path()?, project_config_path.clone()] {
+        if !path.exists() {
+            continue;
+        }
+        let mut document: toml::Table = toml::from_str(&fs::read_to_string(&path)?)?;
+        let mut changed = false;
+        for name in API_KEY_NAMES {
+            let Some(section) = document.get_mut(name).and_then(toml::Value::as_table_mut) else {
+                continue;
+            };
+            let Some(api_key) = section.get("api_key").and_then(toml::Value::as_str) else {
+                continue;
+            };
+            let api_key = api_key.trim().to_string();
+            if api_key.is_empty() || api_key == PLACEHOLDER_API_KEY {
+                continue;
+            }
+            if get_secret(name).is_none() {
+                set_secret(name, &api_key)?;
+                migrated += 1;
+            }
+            section.insert("api_key".to_string(), toml::Value::String(String::new()));
+            changed = true;
+        }
+        if changed && path == project_config_path {
+            // Config::save always writes config.toml with keys redacted
+            crate::config::Config::load()?.save()?;
+        } else if changed {
+            fs::write(&path, toml::to_string_pretty(&document)?)?;
+        }
+    }
+    Ok(migrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_file_round_trips_and_rejects_wrong_key() {
+        let salt = [7u8; SALT_LENGTH];
+        let key = derive_key("correct horse", &salt).unwrap_or_default();
+        let values = HashMap::from([("venice".to_string(), "sk-test".to_string())]);
+
+        let file = encrypt_values(&key, &salt, &values).ok();
+        let decrypted = file.as_ref().and_then(|file| decrypt_values(&key, file).ok());
+        assert_eq!(decrypted, Some(values));
+
+        let wrong_key = derive_key("battery staple", &salt).unwrap_or_default();
+        assert!(file.is_some_and(|file| decrypt_values(&wrong_key, &file).is_err()));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::agents::{AgentManager, ChatMessage as AgentChatMessage};
+use crate::storage::SentimentEntry;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::HashMap;
+
+/// Characters of a conversation handed to the model; the tone shows early
+const MAX_ANALYSIS_CHARS: usize = 4000;
+const MAX_EMOTIONS: usize = 3;
+/// Kimi's own emotions below this intensity are left out of the snapshot
+const MIN_IDENTITY_INTENSITY: f32 = 0.3;
+
+/// Overall tone of one conversation, scored when it is saved
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversationSentiment {
+    /// -1 (very negative) to 1 (very positive)
+    pub valence: f32,
+    /// The user's dominant emotions, strongest first
+    pub emotions: Vec<String>,
+}
+
+/// Asks the model how the user felt over a conversation
+pub fn analyze(
+    conversation_content: &str,
+    agents: &[&crate::agents::Agent],
+    manager: &AgentManager,
+) -> Option<ConversationSentiment> {
+    let truncated: String = conversation_content.chars().take(MAX_ANALYSIS_CHARS).collect();
+    let prompt = format!(
+        "How did the user feel over this conversation? Return JSON \
+{{\"valence\": 0.0, \"emotions\": [...]}} where valence runs from -1 (very negative) through 0 \
+(neutral) to 1 (very positive), and emotions lists up to {} of the user's dominant emotions, \
+single lowercase words, strongest first. Judge the user, not the assistant. \
+Example: {{\"valence\": -0.4, \"emotions\": [\"frustration\", \"worry\"]}}\n\n\
+Conversation:\n{}",
+        MAX_EMOTIONS, truncated
+    );
+
+    let messages = vec![
+        AgentChatMessage::system("You rate the emotional tone of conversations. Return only JSON."),
+        AgentChatMessage::user(&prompt),
+    ];
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "valence": { "type": "number", "minimum": -1, "maximum": 1 },
+            "emotions": {
+                "type": "array",
+                "items": { "type": "string" },
+                "maxItems": MAX_EMOTIONS
+            }
+        },
+        "required": ["valence", "emotions"]
+    });
+
+    agents.iter().find_map(|agent| {
+        manager
+            .chat_structured_cached(agent, &messages, &schema)
+            .ok()
+            .and_then(|response| parse_sentiment_json(&response))
+    })
+}
+
+fn parse_sentiment_json(response: &str) -> Option<ConversationSentiment> {
+    #[derive(serde::Deserialize)]
+    struct SentimentResponse {
+        valence: f32,
+        #[serde(default)]
+        emotions: Vec<String>,
+    }
+
+    let trimmed = response.trim();
+    let parsed = match (trimmed.find('{'), trimmed.rfind('}')) {
+        (Some(start), Some(end)) if start < end => trimmed
+            .get(start..=end)
+            .and_then(|json| serde_json::from_str::<SentimentResponse>(json).ok())?,
+        _ => return None,
+    };
+    if !parsed.valence.is_finite() {
+        return None;
+    }
+    let mut emotions: Vec<String> = Vec::new();
+    for emotion in parsed.emotions {
+        let emotion = emotion.trim().to_lowercase();
+        if !emotion.is_empty() && !emotions.contains(&emotion) {
+            emotions.push(emotion);
+        }
+    }
+    emotions.truncate(MAX_EMOTIONS);
+    Some(ConversationSentiment {
+        valence: parsed.valence.clamp(-1.0, 1.0),
+        emotions,
+    })
+}
+
+/// Kimi's strongest current emotions from the identity state, stored next to the user's
+#[must_use]
+pub fn identity_emotions(state: &crate::services::identity::IdentityState) -> Vec<String> {
+    let mut emotions: Vec<&crate::services::identity::EmotionEntry> = state
+        .emotions
+        .iter()
+        .filter(|emotion| emotion.intensity >= MIN_IDENTITY_INTENSITY && !emotion.name.trim().is_empty())
+        .collect();
+    emotions.sort_by(|a, b| b.intensity.total_cmp(&a.intensity));
+    emotions
+        .into_iter()
+        .take(MAX_EMOTIONS)
+        .map(|emotion| emotion.name.trim().to_lowercase())
+        .collect()
+}
+
+/// Average valence per week (Monday to Sunday) for the last `weeks` weeks up to `today`,
+/// oldest first; `None` for weeks without scored conversations
+#[must_use]
+pub fn weekly_valence(entries: &[SentimentEntry], today: NaiveDate, weeks: usize) -> Vec<(NaiveDate, Option<f32>)> {
+    let current_week = week_start(today);
+    let weeks_back = i64::try_from(weeks.saturating_sub(1)).unwrap_or(0);
+    let first_week = current_week - Duration::weeks(weeks_back);
+    let mut sums = vec![(0.0_f32, 0_u32); weeks];
+    for entry in entries {
+        let Some(date) = entry_date(entry) else {
+            continue;
+        };
+        let week = week_start(date);
+        if week < first_week {
+            continue;
+        }
+        let index = usize::try_from((week - first_week).num_weeks()).unwrap_or(usize::MAX);
+        if let Some((sum, count)) = sums.get_mut(index) {
+            *sum += entry.valence;
+            *count += 1;
+        }
+    }
+    sums.into_iter()
+        .zip(0_i64..)
+        .map(|((sum, count), index)| {
+            let average = (count > 0).then(|| sum / count as f32);
+            (first_week + Duration::weeks(index), average)
+        })
+        .collect()
+}
+
+/// How often each emotion was recorded, most frequent first; `kimi` counts Kimi's own
+#[must_use]
+pub fn emotion_counts(entries: &[SentimentEntry], kimi: bool) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in entries {
+        let emotions = if kimi { &entry.kimi_emotions } else { &entry.emotions };
+        for emotion in emotions {
+            *counts.entry(emotion.as_str()).or_default() += 1;
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(emotion, count)| (emotion.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// "positive", "slightly negative", ...
+#[must_use]
+pub fn describe_valence(valence: f32) -> &'static str {
+    match valence {
+        v if v >= 0.5 => "positive",
+        v if v >= 0.15 => "slightly positive",
+        v if v > -0.15 => "neutral",
+        v if v > -0.5 => "slightly negative",
+        _ => "negative",
+    }
+}
+
+/// Local day the conversation started
+#[must_use]
+pub fn entry_date(entry: &SentimentEntry) -> Option<NaiveDate> {
+    chrono::DateTime::parse_from_rfc3339(&entry.created_at)
+        .ok()
+        .map(|created| created.with_timezone(&chrono::Local).date_naive())
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(created_at: &str, valence: f32, emotions: &[&str]) -> SentimentEntry {
+        SentimentEntry {
+            title: String::new(),
+            created_at: created_at.to_string(),
+            valence,
+            emotions: emotions.iter().map(|emotion| (*emotion).to_string()).collect(),
+            kimi_emotions: vec!["curiosity".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_parses_and_clamps_sentiment() {
+        let Some(sentiment) = parse_sentiment_json(r#"Here: {"valence": 1.7, "emotions": ["Joy", "joy", " relief "]}"#)
+        else {
+            panic!("sentiment should parse");
+        };
+        assert_eq!(
+            sentiment,
+            ConversationSentiment {
+                valence: 1.0,
+                emotions: vec!["joy".to_string(), "relief".to_string()],
+            }
+        );
+        assert!(parse_sentiment_json("no idea").is_none());
+        assert_eq!(describe_valence(-0.3), "slightly negative");
+        assert_eq!(describe_valence(0.0), "neutral");
+    }
+
+    #[test]
+    fn test_averages_valence_per_week() {
+        let Some(today) = NaiveDate::from_ymd_opt(2026, 10, 17) else {
+            panic!("bad test date");
+        };
+        let entries = [
+            entry("2026-10-06T12:00:00+02:00", -0.5, &["worry"]),
+            entry("2026-10-13T12:00:00+02:00", 0.2, &["joy"]),
+            entry("2026-10-15T12:00:00+02:00", 0.6, &["joy", "relief"]),
+            entry("2025-01-01T12:00:00+02:00", 1.0, &[]),
+        ];
+        let weeks = weekly_valence(&entries, today, 3);
+        let averages: Vec<Option<f32>> = weeks.iter().map(|(_, average)| *average).collect();
+        assert_eq!(averages.len(), 3);
+        assert_eq!(averages.first().copied().flatten(), None);
+        assert_eq!(averages.get(1).copied().flatten(), Some(-0.5));
+        let Some(Some(latest)) = averages.get(2).copied() else {
+            panic!("last week should have an average");
+        };
+        assert!((latest - 0.4).abs() < 1e-6);
+
+        assert_eq!(
+            emotion_counts(&entries, false),
+            vec![("joy".to_string(), 2), ("relief".to_string(), 1), ("worry".to_string(), 1)]
+        );
+        assert_eq!(emotion_counts(&entries, true), vec![("curiosity".to_string(), 4)]);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::config::Config;
+use color_eyre::{Result, eyre::eyre};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+// ── Registry ────────────────────────────────────────────────────────────────
+
+/// What a setting accepts; values are checked before anything is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingKind {
+    Text,
+    /// http:// or https:// URL
+    Url,
+    Integer { min: i64, max: i64 },
+    Decimal { min: f64, max: f64 },
+    /// Enter flips it
+    Toggle,
+    /// Enter cycles through the options
+    Choice(&'static [&'static str]),
+}
+
+/// One editable config value, addressed by its dotted TOML path
+#[derive(Debug, Clone, Copy)]
+pub struct SettingField {
+    /// Heading the field is listed under
+    pub section: &'static str,
+    pub path: &'static str,
+    pub label: &'static str,
+    pub kind: SettingKind,
+    /// Read once at startup (clients, caches); the change is saved but needs a restart
+    pub needs_restart: bool,
+}
+
+const fn field(
+    section: &'static str,
+    path: &'static str,
+    label: &'static str,
+    kind: SettingKind,
+    needs_restart: bool,
+) -> SettingField {
+    SettingField {
+        section,
+        path,
+        label,
+        kind,
+        needs_restart,
+    }
+}
+
+/// Everything `/settings` shows, in display order
+pub const SETTINGS: &[SettingField] = &[
+    field("Ollama", "ollama.url", "Server URL", SettingKind::Url, true),
+    field("Ollama", "ollama.warm_up", "Preload models", SettingKind::Toggle, true),
+    field("Ollama", "ollama.requests.timeout_secs", "Request timeout (s)", SettingKind::Integer { min: 5, max: 3600 }, true),
+    field("Ollama", "ollama.requests.retries", "Retries", SettingKind::Integer { min: 0, max: 10 }, true),
+    field("Models", "agents.chat.model", "Chat model", SettingKind::Text, false),
+    field("Models", "agents.translate.model", "Translate model", SettingKind::Text, false),
+    field("Embeddings", "embeddings.model", "Embedding model", SettingKind::Text, true),
+    field("Embeddings", "embeddings.ollama_url", "Embeddings URL", SettingKind::Url, true),
+    field("Embeddings", "embeddings.similarity_threshold", "Similarity threshold", SettingKind::Decimal { min: 0.0, max: 1.0 }, true),
+    field("Embeddings", "embeddings.max_retrieved_messages", "Recalled messages", SettingKind::Integer { min: 1, max: 200 }, true),
+    field("Embeddings", "embeddings.batch_size", "Batch size", SettingKind::Integer { min: 1, max: 512 }, false),
+    field("Embeddings", "embeddings.concurrency", "Parallel requests", SettingKind::Integer { min: 1, max: 16 }, false),
+    field("Embeddings", "embeddings.query_rewrite", "Rewrite memory queries", SettingKind::Toggle, false),
+    field("Embeddings", "embeddings.recency_half_life_days", "Recency half-life (days)", SettingKind::Decimal { min: 0.0, max: 3650.0 }, false),
+    field("Embeddings", "embeddings.importance_weight", "Importance boost", SettingKind::Decimal { min: 0.0, max: 5.0 }, false),
+    field("Conversation", "conversation.language", "Reply language", SettingKind::Text, false),
+    field("Conversation", "conversation.carry_over", "Carry over last session", SettingKind::Toggle, false),
+    field("Conversation", "conversation.carry_over_window_minutes", "Carry-over window (min)", SettingKind::Integer { min: 0, max: 10080 }, false),
+    field("Conversation", "conversation.autosave_every_messages", "Autosave every N messages", SettingKind::Integer { min: 1, max: 100 }, false),
+    field("Conversation", "conversation.autosave_interval_seconds", "Autosave interval (s)", SettingKind::Integer { min: 0, max: 3600 }, false),
+    field("Conversation", "conversation.resume_last", "Resume last conversation", SettingKind::Choice(&["ask", "always", "never"]), false),
+    field("Conversation", "conversation.resume_recap", "Recap on resume", SettingKind::Toggle, false),
+    field("Conversation", "conversation.extract_tasks", "Extract dated tasks", SettingKind::Toggle, false),
+    field("Conversation", "conversation.track_sentiment", "Track sentiment", SettingKind::Toggle, false),
+    field("Conversation", "conversation.check_ins", "Check-ins", SettingKind::Toggle, false),
+    field("Conversation", "conversation.check_in_every_days", "Check-in every (days)", SettingKind::Integer { min: 0, max: 365 }, false),
+    field("Conversation", "conversation.show_message_stats", "Show reply stats", SettingKind::Toggle, false),
+    field("Conversation", "conversation.duplicate_question_similarity", "Repeat question similarity", SettingKind::Decimal { min: 0.0, max: 1.0 }, false),
+    field("Personality", "personality.suggest_after_conversations", "Suggest after (conversations)", SettingKind::Integer { min: 0, max: 1000 }, false),
+    field("Diary", "diary.enabled", "Write diary", SettingKind::Toggle, false),
+    field("Diary", "diary.export_to_obsidian", "Export to Obsidian", SettingKind::Toggle, false),
+    field("Diary", "diary.folder", "Vault folder", SettingKind::Text, false),
+    field("Calendar", "calendar.agenda_days", "Agenda days", SettingKind::Integer { min: 1, max: 60 }, false),
+    field("Weather", "weather.provider", "Weather provider", SettingKind::Choice(&["open-meteo", "wttr"]), false),
+    field("Weather", "weather.location", "Location name", SettingKind::Text, false),
+    field("Weather", "weather.latitude", "Latitude", SettingKind::Decimal { min: -90.0, max: 90.0 }, false),
+    field("Weather", "weather.longitude", "Longitude", SettingKind::Decimal { min: -180.0, max: 180.0 }, false),
+    field("Weather", "weather.units", "Units", SettingKind::Choice(&["metric", "imperial"]), false),
+    field("Weather", "weather.cache_minutes", "Reuse report for (min)", SettingKind::Integer { min: 0, max: 1440 }, false),
+    field("Transit", "transit.home_stop", "Home stop", SettingKind::Text, false),
+    field("Transit", "transit.work_stop", "Work stop", SettingKind::Text, false),
+    field("Transit", "transit.departures", "Departures listed", SettingKind::Integer { min: 1, max: 20 }, false),
+    field("Transit", "transit.minutes_ahead", "Look ahead (min)", SettingKind::Integer { min: 5, max: 240 }, false),
+    field("Notifications", "notifications.desktop", "Desktop popups", SettingKind::Toggle, false),
+    field("Notifications", "notifications.webhook_format", "Webhook format", SettingKind::Choice(&["ntfy", "json"]), false),
+    field("Notifications", "notifications.reminder_lead_minutes", "Reminder lead (min)", SettingKind::Integer { min: 0, max: 1440 }, false),
+    field("Usage", "usage.enabled", "Track usage", SettingKind::Toggle, false),
+    field("Usage", "usage.warn_at", "Warn at budget fraction", SettingKind::Decimal { min: 0.0, max: 1.0 }, false),
+    field("Usage", "usage.cutoff", "Stop at budget", SettingKind::Toggle, false),
+    field("Cache", "cache.enabled", "Response cache", SettingKind::Toggle, true),
+    field("Cache", "cache.ttl_minutes", "Cache lifetime (min)", SettingKind::Integer { min: 1, max: 10080 }, true),
+    field("Cache", "cache.similarity", "Cache match similarity", SettingKind::Decimal { min: 0.0, max: 1.0 }, true),
+    field("Tools", "calculator.enabled", "Offline calculator", SettingKind::Toggle, false),
+    field("Tools", "finance.live_rates", "Live exchange rates", SettingKind::Toggle, false),
+    field("Tools", "finance.rates_url", "Exchange rates URL", SettingKind::Url, false),
+    field("Tools", "finance.quote_cache_minutes", "Reuse quotes for (min)", SettingKind::Integer { min: 0, max: 1440 }, false),
+    field("Tools", "timers.announce", "Announce timers", SettingKind::Toggle, false),
+    field("Tools", "git.enabled", "Git repository context", SettingKind::Toggle, false),
+    field("Tools", "email.command", "Email command", SettingKind::Text, false),
+    field("Tools", "tools.dry_run", "Dry-run tools that change things", SettingKind::Toggle, false),
+    field("Tools", "tools.audit", "Audit tool calls", SettingKind::Toggle, false),
+    field("Tools", "tools.summarize_over_chars", "Summarize results over (chars)", SettingKind::Integer { min: 0, max: 1_000_000 }, false),
+    field("Capture", "capture.memory", "Save to memory", SettingKind::Toggle, false),
+    field("Capture", "capture.obsidian", "Append to Obsidian inbox", SettingKind::Toggle, false),
+    field("Capture", "capture.inbox_note", "Inbox note", SettingKind::Text, false),
+    field("OCR", "ocr.enabled", "Screenshot OCR", SettingKind::Toggle, false),
+    field("OCR", "ocr.command", "Tesseract command", SettingKind::Text, false),
+    field("OCR", "ocr.language", "OCR language", SettingKind::Text, false),
+    field("Editor", "editor.mode", "Editor mode", SettingKind::Choice(&["auto", "terminal", "internal"]), false),
+    field("Editor", "editor.command", "Editor command", SettingKind::Text, false),
+    field("Editor", "editor.terminal", "Terminal emulator", SettingKind::Text, false),
+    field("Maintenance", "maintenance.reindex_interval_days", "Reindex every (days)", SettingKind::Integer { min: 0, max: 365 }, true),
+    field("Maintenance", "maintenance.archive_after_days", "Archive after (days)", SettingKind::Integer { min: 0, max: 3650 }, true),
+    field("Display", "display.compatibility", "Compatibility mode", SettingKind::Choice(&["auto", "on", "off"]), true),
+    field("Images", "images.provider", "Image provider", SettingKind::Choice(&["venice", "openai"]), false),
+    field("Images", "images.url", "Image server URL", SettingKind::Url, false),
+    field("Images", "images.model", "Image model", SettingKind::Text, false),
+    field("Images", "images.width", "Image width", SettingKind::Integer { min: 256, max: 2048 }, false),
+    field("Images", "images.height", "Image height", SettingKind::Integer { min: 256, max: 2048 }, false),
+    field("Images", "images.directory", "Pictures directory", SettingKind::Text, false),
+    field("Backup", "backup.enabled", "Scheduled backups", SettingKind::Toggle, true),
+    field("Backup", "backup.directory", "Backup directory", SettingKind::Text, true),
+    field("Backup", "backup.interval_hours", "Backup every (hours)", SettingKind::Integer { min: 1, max: 720 }, true),
+    field("Backup", "backup.keep", "Backups kept", SettingKind::Integer { min: 1, max: 365 }, true),
+];
+
+// ── Reading and writing ─────────────────────────────────────────────────────
+
+fn lookup<'a>(root: &'a toml::Value, path: &str) -> Option<&'a toml::Value> {
+    path.split('.').try_fold(root, |value, key| value.get(key))
+}
+
+fn lookup_mut<'a>(root: &'a mut toml::Value, path: &str) -> Option<&'a mut toml::Value> {
+    path.split('.').try_fold(root, |value, key| value.get_mut(key))
+}
+
+/// Current value as shown in the form; empty when the path is not in the config
+#[must_use]
+pub fn current_value(config: &Config, field: &SettingField) -> String {
+    let Ok(root) = toml::Value::try_from(config) else {
+        return String::new();
+    };
+    match lookup(&root, field.path) {
+        Some(toml::Value::String(text)) => text.clone(),
+        // f32 fields widen to noisy f64s (0.30000001192092896)
+        Some(toml::Value::Float(number)) => ((number * 1e6).round() / 1e6).to_string(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Checks `input` against the field's kind and converts it to the TOML value to store
+pub fn parse_input(field: &SettingField, input: &str) -> Result<toml::Value> {
+    let input = input.trim();
+    match field.kind {
+        SettingKind::Text => Ok(toml::Value::String(input.to_string())),
+        SettingKind::Url => {
+            if input.starts_with("http://") || input.starts_with("https://") {
+                Ok(toml::Value::String(input.trim_end_matches('/').to_string()))
+            } else {
+                Err(eyre!("{} must start with http:// or https://", field.label))
+            }
+        }
+        SettingKind::Integer { min, max } => {
+            let number: i64 = input
+                .parse()
+                .map_err(|_| eyre!("{} must be a whole number", field.label))?;
+            if !(min..=max).contains(&number) {
+                return Err(eyre!("{} must be between {} and {}", field.label, min, max));
+            }
+            Ok(toml::Value::Integer(number))
+        }
+        SettingKind::Decimal { min, max } => {
+            let number: f64 = input
+                .parse()
+                .map_err(|_| eyre!("{} must be a number", field.label))?;
+            if !(min..=max).contains(&number) {
+                return Err(eyre!("{} must be between {} and {}", field.label, min, max));
+            }
+            Ok(toml::Value::Float(number))
+        }
+        SettingKind::Toggle => match input {
+            "true" | "on" | "yes" => Ok(toml::Value::Boolean(true)),
+            "false" | "off" | "no" => Ok(toml::Value::Boolean(false)),
+            _ => Err(eyre!("{} is either on or off", field.label)),
+        },
+        SettingKind::Choice(options) => options
+            .iter()
+            .find(|option| **option == input)
+            .map(|option| toml::Value::String((*option).to_string()))
+            .ok_or_else(|| eyre!("{} is one of: {}", field.label, options.join(", "))),
+    }
+}
+
+/// Value Enter switches a toggle or choice to, without typing
+#[must_use]
+pub fn next_value(field: &SettingField, current: &str) -> Option<String> {
+    match field.kind {
+        SettingKind::Toggle => Some((current != "true").to_string()),
+        SettingKind::Choice(options) => {
+            let position = options.iter().position(|option| *option == current);
+            let next = position.map_or(0, |index| (index + 1) % options.len());
+            options.get(next).map(|option| (*option).to_string())
+        }
+        SettingKind::Text | SettingKind::Url | SettingKind::Integer { .. } | SettingKind::Decimal { .. } => None,
+    }
+}
+
+/// Returns a copy of `config` with the field set to `input`. The result is read back into
+/// `Config`, so anything serde would reject at startup is rejected here.
+pub fn apply(config: &Config, field: &SettingField, input: &str) -> Result<Config> {
+    let value = parse_input(field, input)?;
+    let mut root = toml::Value::try_from(config)?;
+    let slot = lookup_mut(&mut root, field.path)
+        .ok_or_else(|| eyre!("{} is not in this config ({})", field.label, field.path))?;
+    *slot = value;
+    root.try_into()
+        .map_err(|error| eyre!("{} was not accepted: {}", field.label, error))
+}
+
+// ── Hot reload ──────────────────────────────────────────────────────────────
+
+/// Sections only read when clients and caches are built at startup
+const RESTART_SECTIONS: &[&str] =
+    &["ollama", "elevenlabs", "embeddings", "cache", "maintenance", "backup", "display", "storage"];
+
+/// What differs between two loads of the config, by section
+#[derive(Debug, Default, PartialEq)]
+pub struct ConfigChanges {
+    /// Sections (and agent models) that take effect right away
+    pub applied: Vec<String>,
+    /// Sections that were saved but are only read at startup
+    pub needs_restart: Vec<String>,
+}
+
+impl ConfigChanges {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.needs_restart.is_empty()
+    }
+}
+
+/// Compares two configs section by section. Agent models are applied on the fly;
+/// any other agent setting (prompt, provider) is only read at startup.
+#[must_use]
+pub fn changed_sections(previous: &Config, current: &Config) -> ConfigChanges {
+    let mut changes = ConfigChanges::default();
+    let (Ok(toml::Value::Table(previous)), Ok(toml::Value::Table(current))) =
+        (toml::Value::try_from(previous), toml::Value::try_from(current))
+    else {
+        return changes;
+    };
+    let mut sections: Vec<&String> = previous.keys().chain(current.keys()).collect();
+    sections.sort();
+    sections.dedup();
+    for section in sections {
+        if section == "agents" {
+            compare_agents(previous.get(section), current.get(section), &mut changes);
+            continue;
+        }
+        if previous.get(section) == current.get(section) {
+            continue;
+        }
+        if RESTART_SECTIONS.contains(&section.as_str()) {
+            changes.needs_restart.push(section.clone());
+        } else {
+            changes.applied.push(section.clone());
+        }
+    }
+    changes
+}
+
+fn compare_agents(
+    previous: Option<&toml::Value>,
+    current: Option<&toml::Value>,
+    changes: &mut ConfigChanges,
+) {
+    let empty = toml::map::Map::new();
+    let previous = previous.and_then(toml::Value::as_table).unwrap_or(&empty);
+    let current = current.and_then(toml::Value::as_table).unwrap_or(&empty);
+    let mut names: Vec<&String> = previous.keys().chain(current.keys()).collect();
+    names.sort();
+    names.dedup();
+    for name in names {
+        let mut before = previous.get(name).cloned().unwrap_or(toml::Value::Table(empty.clone()));
+        let mut after = current.get(name).cloned().unwrap_or(toml::Value::Table(empty.clone()));
+        if before.get("model") != after.get("model") {
+            changes.applied.push(format!("{} model", name));
+        }
+        for agent in [&mut before, &mut after] {
+            if let Some(table) = agent.as_table_mut() {
+                table.remove("model");
+            }
+        }
+        if before != after {
+            changes.needs_restart.push(format!("agents.{}", name));
+        }
+    }
+}
+
+/// Watches the profile's config.toml and config.local.toml; `on_change` runs on every
+/// write. The watcher stops when the handle is dropped
+pub fn watch_config(on_change: impl Fn() + Send + 'static) -> Result<RecommendedWatcher> {
+    let config_path = Config::project_config_path()?;
+    let local_path = Config::local_config_path()?;
+    let directory = config_path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .ok_or_else(|| eyre!("Config file has no parent directory"))?;
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let touches_config = event.paths.iter().any(|path| {
+            path.file_name() == config_path.file_name() || path.file_name() == local_path.file_name()
+        });
+        if touches_config && !event.kind.is_access() {
+            on_change();
+        }
+    })?;
+    // The directory rather than the files, so editors that replace the file on save still count
+    watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(path: &str) -> &'static SettingField {
+        let Some(field) = SETTINGS.iter().find(|field| field.path == path) else {
+            panic!("no setting {}", path);
+        };
+        field
+    }
+
+    #[test]
+    fn test_every_setting_exists_in_the_default_config() {
+        let config = Config::default();
+        let Ok(root) = toml::Value::try_from(&config) else {
+            panic!("config should serialize");
+        };
+        for field in SETTINGS {
+            assert!(lookup(&root, field.path).is_some(), "{} missing", field.path);
+        }
+    }
+
+    #[test]
+    fn test_inputs_are_validated_against_the_kind() {
+        let window = find("conversation.carry_over_window_minutes");
+        assert!(parse_input(window, "45").is_ok());
+        assert!(parse_input(window, "-1").is_err());
+        assert!(parse_input(window, "soon").is_err());
+        assert!(parse_input(find("ollama.url"), "localhost:11434").is_err());
+        assert!(parse_input(find("embeddings.similarity_threshold"), "1.5").is_err());
+        assert!(parse_input(find("editor.mode"), "vim").is_err());
+    }
+
+    #[test]
+    fn test_apply_updates_the_field_and_round_trips() {
+        let config = Config::default();
+        let field = find("conversation.autosave_every_messages");
+        let Ok(updated) = apply(&config, field, "12") else {
+            panic!("expected the value to apply");
+        };
+        assert_eq!(updated.conversation.autosave_every_messages, 12);
+        assert_eq!(current_value(&updated, field), "12");
+
+        let stats = find("conversation.show_message_stats");
+        let current = current_value(&updated, stats);
+        assert_eq!(next_value(stats, &current).as_deref(), Some("true"));
+        assert_eq!(next_value(find("editor.mode"), "internal").as_deref(), Some("auto"));
+    }
+
+    #[test]
+    fn test_changes_are_split_into_applied_and_restart() {
+        let previous = Config::default();
+        let mut current = previous.clone();
+        current.conversation.show_message_stats = !current.conversation.show_message_stats;
+        current.cache.enabled = !current.cache.enabled;
+        if let Some(chat) = current.agents.get_mut("chat") {
+            chat.model = "another-model".to_string();
+        }
+        let changes = changed_sections(&previous, &current);
+        assert!(changes.applied.contains(&"conversation".to_string()));
+        assert!(changes.needs_restart.contains(&"cache".to_string()));
+        assert!(changes.applied.contains(&"chat model".to_string()));
+        assert!(!changes.needs_restart.iter().any(|section| section.starts_with("agents")));
+        assert!(changed_sections(&previous, &previous.clone()).is_empty());
+    }
+}
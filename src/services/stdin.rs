@@ -0,0 +1,82 @@
+use color_eyre::Result;
+use std::io::{IsTerminal, Read};
+
+/// Largest piped input attached to a message; the rest is dropped and reported
+pub const MAX_PIPED_BYTES: usize = 64 * 1024;
+
+/// Text piped into kimi (`git diff | kimi ask "review this"`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipedInput {
+    pub text: String,
+    /// Size of the input before truncation
+    pub total_bytes: usize,
+}
+
+impl PipedInput {
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        self.text.len() < self.total_bytes
+    }
+
+    /// Human-readable truncation note, `None` when everything was kept
+    #[must_use]
+    pub fn truncation_note(&self) -> Option<String> {
+        self.is_truncated().then(|| {
+            format!(
+                "stdin truncated: kept {} of {} bytes",
+                self.text.len(),
+                self.total_bytes
+            )
+        })
+    }
+}
+
+/// Reads stdin when it is a pipe or file rather than a terminal.
+/// Returns `None` for a terminal or empty input.
+pub fn read_piped_stdin() -> Result<Option<PipedInput>> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Ok(None);
+    }
+    let mut bytes = Vec::new();
+    stdin.read_to_end(&mut bytes)?;
+    Ok(limit_piped_input(&String::from_utf8_lossy(&bytes), MAX_PIPED_BYTES))
+}
+
+/// Cuts `raw` to at most `max_bytes` on a char boundary
+fn limit_piped_input(raw: &str, max_bytes: usize) -> Option<PipedInput> {
+    if raw.trim().is_empty() {
+        return None;
+    }
+    let mut end = raw.len().min(max_bytes);
+    while !raw.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(PipedInput {
+        text: raw.get(..end).unwrap_or_default().to_string(),
+        total_bytes: raw.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_small_input_and_truncates_on_char_boundary() {
+        let Some(small) = limit_piped_input("diff --git a b\n", 100) else {
+            panic!("input dropped");
+        };
+        assert!(!small.is_truncated());
+        assert_eq!(small.truncation_note(), None);
+
+        let Some(cut) = limit_piped_input("ééé", 3) else {
+            panic!("input dropped");
+        };
+        assert_eq!(cut.text, "é");
+        assert_eq!(cut.total_bytes, 6);
+        assert!(cut.is_truncated());
+
+        assert_eq!(limit_piped_input("  \n", 100), None);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::agents::{AgentManager, ChatMessage as AgentChatMessage};
+use crate::services::dates;
+use chrono::{Duration, NaiveDate};
+
+/// Characters of a conversation handed to the extractor; commitments tend to be stated plainly
+const MAX_EXTRACTION_CHARS: usize = 4000;
+const MAX_TASKS_PER_CONVERSATION: usize = 5;
+
+/// A commitment with a date, as found in a conversation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedTask {
+    pub text: String,
+    pub due: NaiveDate,
+}
+
+/// Finds things the user committed to doing by a date ("renew my passport by June").
+/// Undated intentions and dates already past are left out.
+pub fn extract_commitments(
+    conversation_content: &str,
+    today: NaiveDate,
+    agents: &[&crate::agents::Agent],
+    manager: &AgentManager,
+) -> Vec<ExtractedTask> {
+    let truncated: String = conversation_content.chars().take(MAX_EXTRACTION_CHARS).collect();
+    let prompt = format!(
+        "Today is {} ({}). List things the user said they need or plan to do by a specific \
+date or deadline. Return JSON {{\"tasks\": [{{\"task\": \"...\", \"due\": \"YYYY-MM-DD\"}}]}}. \
+Write each task as a short imperative (\"Renew passport\"). For a month or week without a day, \
+use its last day. Skip anything without a date, anything the assistant suggested but the user \
+did not commit to, and anything already done. If there are none, return {{\"tasks\": []}}.\n\n\
+Conversation:\n{}",
+        today.format("%Y-%m-%d"),
+        today.format("%A"),
+        truncated
+    );
+
+    let messages = vec![
+        AgentChatMessage::system(
+            "You extract dated commitments from conversations. Return only JSON.",
+        ),
+        AgentChatMessage::user(&prompt),
+    ];
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "tasks": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "task": { "type": "string" },
+                        "due": { "type": "string" }
+                    },
+                    "required": ["task", "due"]
+                },
+                "maxItems": MAX_TASKS_PER_CONVERSATION
+            }
+        },
+        "required": ["tasks"]
+    });
+
+    agents
+        .iter()
+        .find_map(|agent| {
+            manager
+                .chat_structured_cached(agent, &messages, &schema)
+                .ok()
+                .and_then(|response| parse_task_json(&response, today))
+        })
+        .unwrap_or_default()
+}
+
+fn parse_task_json(response: &str, today: NaiveDate) -> Option<Vec<ExtractedTask>> {
+    #[derive(serde::Deserialize)]
+    struct TaskResponse {
+        tasks: Vec<TaskItem>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct TaskItem {
+        task: String,
+        #[serde(default)]
+        due: String,
+    }
+
+    let trimmed = response.trim();
+    let parsed = match (trimmed.find('{'), trimmed.rfind('}')) {
+        (Some(start), Some(end)) if start < end => trimmed
+            .get(start..=end)
+            .and_then(|json| serde_json::from_str::<TaskResponse>(json).ok())?,
+        _ => return None,
+    };
+
+    let mut tasks: Vec<ExtractedTask> = Vec::new();
+    for item in parsed.tasks {
+        let text = item.task.split_whitespace().collect::<Vec<_>>().join(" ");
+        let Some(due) = parse_due(&item.due) else {
+            continue;
+        };
+        let duplicate = tasks
+            .iter()
+            .any(|task| task.due == due && task.text.eq_ignore_ascii_case(&text));
+        if text.is_empty() || due < today || duplicate {
+            continue;
+        }
+        tasks.push(ExtractedTask { text, due });
+    }
+    tasks.truncate(MAX_TASKS_PER_CONVERSATION);
+    Some(tasks)
+}
+
+/// ISO dates as asked for, or a phrase like "next friday" when the model ignores the format;
+/// a range ("june") is due on its last day
+fn parse_due(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| dates::parse_date_reference(value)?.as_range().map(|range| range.end))
+}
+
+/// New due date for a snoozed task; an overdue task is pushed out from today
+#[must_use]
+pub fn snooze(due: NaiveDate, today: NaiveDate, days: i64) -> NaiveDate {
+    due.max(today) + Duration::days(days)
+}
+
+/// "today", "tomorrow", "in 5 days", "overdue by 2 days" or the date itself
+#[must_use]
+pub fn describe_due(due: NaiveDate, today: NaiveDate) -> String {
+    let days = (due - today).num_days();
+    match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "overdue by 1 day".to_string(),
+        2..=13 => format!("in {} days", days),
+        _ if days < 0 => format!("overdue by {} days", -days),
+        _ => due.format("%a %d %b %Y").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap_or_else(|error| panic!("bad test date: {error}"))
+    }
+
+    #[test]
+    fn test_parses_dated_commitments() {
+        let today = date("2026-05-10");
+        let response = r#"Sure: {"tasks": [
+            {"task": "Renew  passport", "due": "2026-06-30"},
+            {"task": "renew passport", "due": "2026-06-30"},
+            {"task": "File taxes", "due": "2026-04-01"},
+            {"task": "Call mom", "due": "sometime"}
+        ]}"#;
+        let Some(tasks) = parse_task_json(response, today) else {
+            panic!("response should parse");
+        };
+        assert_eq!(
+            tasks,
+            vec![ExtractedTask {
+                text: "Renew passport".to_string(),
+                due: date("2026-06-30"),
+            }]
+        );
+        assert!(parse_task_json("no tasks here", today).is_none());
+    }
+
+    #[test]
+    fn test_snoozing_starts_from_today_when_overdue() {
+        let today = date("2026-05-10");
+        assert_eq!(snooze(date("2026-05-12"), today, 1), date("2026-05-13"));
+        assert_eq!(snooze(date("2026-05-01"), today, 7), date("2026-05-17"));
+    }
+
+    #[test]
+    fn test_describes_due_dates_relative_to_today() {
+        let today = date("2026-05-10");
+        assert_eq!(describe_due(today, today), "today");
+        assert_eq!(describe_due(date("2026-05-11"), today), "tomorrow");
+        assert_eq!(describe_due(date("2026-05-15"), today), "in 5 days");
+        assert_eq!(describe_due(date("2026-05-07"), today), "overdue by 3 days");
+        assert_eq!(describe_due(date("2026-06-30"), today), "Tue 30 Jun 2026");
+    }
+}
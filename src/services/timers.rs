@@ -0,0 +1,233 @@
+use std::time::{Duration, Instant};
+
+/// Longest message still read as a timer request rather than a question about timers
+const MAX_REQUEST_WORDS: usize = 14;
+
+/// Words between the duration and the label: "timer for tea", "called pasta"
+const LABEL_FILLERS: [&str; 7] = ["timer", "for", "to", "called", "named", "labelled", "labeled"];
+
+/// A countdown the app loop checks every tick
+#[derive(Debug, Clone)]
+pub struct Timer {
+    pub label: Option<String>,
+    pub duration: Duration,
+    pub ends_at: Instant,
+}
+
+impl Timer {
+    #[must_use]
+    pub fn start(request: TimerRequest) -> Self {
+        Self {
+            label: request.label,
+            duration: request.duration,
+            ends_at: Instant::now() + request.duration,
+        }
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.ends_at.saturating_duration_since(Instant::now())
+    }
+
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        Instant::now() >= self.ends_at
+    }
+
+    /// "tea" or "10m timer" when unlabelled
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.label
+            .clone()
+            .unwrap_or_else(|| format!("{} timer", format_duration(self.duration)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerRequest {
+    pub duration: Duration,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopwatchCommand {
+    Start,
+    Stop,
+}
+
+/// Arguments of `/timer`: "10m tea", "1h30m", "90 seconds pasta"
+#[must_use]
+pub fn parse_timer_command(arguments: &str) -> Option<TimerRequest> {
+    let words: Vec<String> = arguments.split_whitespace().map(str::to_lowercase).collect();
+    let (duration, used) = duration_at(&words, 0)?;
+    let label = label_from(arguments.split_whitespace().skip(used));
+    Some(TimerRequest { duration, label })
+}
+
+/// "set a timer for 10 minutes for tea", "start a 5 minute timer", "timer 10m pasta"
+#[must_use]
+pub fn parse_timer_request(message: &str) -> Option<TimerRequest> {
+    let original: Vec<&str> = message.split_whitespace().collect();
+    let words: Vec<String> = original
+        .iter()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .collect();
+    if words.len() > MAX_REQUEST_WORDS || !words.iter().any(|word| word == "timer") || message.contains('?') {
+        return None;
+    }
+    let starts_like_request = words
+        .first()
+        .is_some_and(|first| ["set", "start", "timer", "make", "create", "add", "please", "kimi"].contains(&first.as_str()));
+    if !starts_like_request {
+        return None;
+    }
+    let (index, (duration, used)) = (0..words.len()).find_map(|index| duration_at(&words, index).map(|found| (index, found)))?;
+    let label = label_from(original.into_iter().skip(index + used).map(|word| word.trim_end_matches(['.', '!'])));
+    Some(TimerRequest { duration, label })
+}
+
+/// "start a stopwatch", "stop the stopwatch"
+#[must_use]
+pub fn parse_stopwatch_request(message: &str) -> Option<StopwatchCommand> {
+    let lowered = message.trim().trim_end_matches(['.', '!']).to_lowercase();
+    if !lowered.ends_with("stopwatch") || lowered.split_whitespace().count() > 4 {
+        return None;
+    }
+    if lowered.starts_with("start") {
+        return Some(StopwatchCommand::Start);
+    }
+    if lowered.starts_with("stop") {
+        return Some(StopwatchCommand::Stop);
+    }
+    None
+}
+
+/// "4:32", "1:02:03"
+#[must_use]
+pub fn format_remaining(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// "1h30m", "10m", "45s"
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    let mut parts = String::new();
+    if hours > 0 {
+        parts.push_str(&format!("{}h", hours));
+    }
+    if minutes > 0 {
+        parts.push_str(&format!("{}m", minutes));
+    }
+    if seconds > 0 || parts.is_empty() {
+        parts.push_str(&format!("{}s", seconds));
+    }
+    parts
+}
+
+/// A duration starting at word `index` and the number of words it spans:
+/// "10m", "1h30m", "10 minutes", "10-minute", "1 hour and 30 minutes"
+fn duration_at(words: &[String], index: usize) -> Option<(Duration, usize)> {
+    let mut total = 0.0;
+    let mut used = 0;
+    loop {
+        let position = index + used;
+        let Some(word) = words.get(position) else {
+            break;
+        };
+        if used > 0 && word == "and" && words.get(position + 1).is_some_and(|next| next.starts_with(|c: char| c.is_ascii_digit())) {
+            used += 1;
+            continue;
+        }
+        if let Some(seconds) = compact_duration(word) {
+            total += seconds;
+            used += 1;
+            continue;
+        }
+        // "10 minutes", "10-minute"
+        let (number, unit, span) = match word.split_once('-') {
+            Some((number, unit)) => (number.to_string(), Some(unit.to_string()), 1),
+            None => (word.clone(), words.get(position + 1).cloned(), 2),
+        };
+        let (Ok(number), Some(unit_seconds)) = (number.parse::<f64>(), unit.as_deref().and_then(unit_seconds)) else {
+            break;
+        };
+        total += number * unit_seconds;
+        used += span;
+    }
+    (used > 0 && total >= 1.0).then(|| (Duration::from_secs_f64(total), used))
+}
+
+/// "10m", "1h30m", "90s"
+fn compact_duration(word: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut number = String::new();
+    let mut found = false;
+    for c in word.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let seconds = match c {
+            'h' => 3600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => return None,
+        };
+        total += number.parse::<f64>().ok()? * seconds;
+        number.clear();
+        found = true;
+    }
+    (found && number.is_empty()).then_some(total)
+}
+
+fn unit_seconds(unit: &str) -> Option<f64> {
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1.0),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60.0),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(3600.0),
+        _ => None,
+    }
+}
+
+/// What follows the duration, without "timer for" and similar filler
+fn label_from<'a>(words: impl Iterator<Item = &'a str>) -> Option<String> {
+    let label: Vec<&str> = words
+        .skip_while(|word| LABEL_FILLERS.contains(&word.to_lowercase().as_str()))
+        .collect();
+    let label = label.join(" ");
+    (!label.is_empty()).then_some(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_commands_and_requests() {
+        let tea = TimerRequest { duration: Duration::from_secs(600), label: Some("tea".to_string()) };
+        assert_eq!(parse_timer_command("10m tea"), Some(tea.clone()));
+        assert_eq!(parse_timer_request("Set a timer for 10 minutes for tea"), Some(tea.clone()));
+        assert_eq!(parse_timer_request("start a 10-minute timer for tea."), Some(tea));
+        assert_eq!(
+            parse_timer_command("1h30m"),
+            Some(TimerRequest { duration: Duration::from_secs(5400), label: None })
+        );
+        assert_eq!(
+            parse_timer_request("set a timer for 1 hour and 30 minutes"),
+            Some(TimerRequest { duration: Duration::from_secs(5400), label: None })
+        );
+        assert_eq!(parse_timer_request("how do I set a timer for 10 minutes?"), None);
+        assert_eq!(parse_timer_request("the timer broke after 10 minutes"), None);
+        assert_eq!(parse_timer_command("tea"), None);
+        assert_eq!(parse_stopwatch_request("Start the stopwatch"), Some(StopwatchCommand::Start));
+        assert_eq!(format_remaining(Duration::from_secs(3723)), "1:02:03");
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+    }
+}
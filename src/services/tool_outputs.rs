@@ -0,0 +1,96 @@
+use crate::agents::{Agent, AgentManager, ChatMessage as AgentChatMessage};
+
+/// Characters of a tool output the summarizer reads; the rest only goes to storage
+const SUMMARY_INPUT_CHARS: usize = 24_000;
+
+/// Whether an output is long enough to be summarized before it reaches the prompt.
+/// A limit of 0 turns summarizing off
+#[must_use]
+pub fn needs_summary(output: &str, limit: usize) -> bool {
+    limit > 0 && output.chars().count() > limit
+}
+
+/// Key the full output is stored under and cited by, unique within a reply
+#[must_use]
+pub fn output_key(index: usize) -> String {
+    format!("{}-{}", chrono::Local::now().format("%Y%m%d%H%M%S%3f"), index)
+}
+
+fn summary_messages(tool: &str, arguments: &str, output: &str) -> Vec<AgentChatMessage> {
+    let truncated: String = output.chars().take(SUMMARY_INPUT_CHARS).collect();
+    let prompt = format!(
+        "Summarize this {} result called with {} for another assistant that will answer \
+the user with it. Keep every concrete fact, number, name, date, URL and file path that \
+could matter; drop boilerplate, navigation and repetition. Use short bullet points.\n\n\
+Result:\n{}",
+        tool, arguments, truncated
+    );
+    vec![
+        AgentChatMessage::system(
+            "You compress tool results without losing facts. Reply with the summary only.",
+        ),
+        AgentChatMessage::user(&prompt),
+    ]
+}
+
+/// Summarizes a long output with the first agent that answers (summary model first)
+pub fn summarize(
+    tool: &str,
+    arguments: &str,
+    output: &str,
+    agents: &[&Agent],
+    manager: &AgentManager,
+) -> Option<String> {
+    let messages = summary_messages(tool, arguments, output);
+    agents.iter().find_map(|agent| {
+        manager
+            .chat(agent, &messages)
+            .inspect_err(|error| {
+                tracing::warn!(%error, agent = %agent.name, "tool output summary failed");
+            })
+            .ok()
+            .map(|summary| summary.trim().to_string())
+            .filter(|summary| !summary.is_empty())
+    })
+}
+
+/// What the model gets instead of the full output
+#[must_use]
+pub fn summarized_result(summary: &str, output: &str, key: &str) -> String {
+    format!(
+        "Summary of a {}-character result (full text stored as tool output {}):\n{}",
+        output.chars().count(),
+        key,
+        summary
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_long_outputs_are_summarized() {
+        assert!(needs_summary(&"a".repeat(101), 100));
+        assert!(!needs_summary(&"a".repeat(100), 100));
+        assert!(!needs_summary(&"a".repeat(10_000), 0));
+    }
+
+    #[test]
+    fn test_summarized_result_names_the_stored_output() {
+        let result = summarized_result("- fact", "abcdef", "20261017-0");
+        assert!(result.starts_with("Summary of a 6-character result"));
+        assert!(result.contains("tool output 20261017-0"));
+        assert!(result.ends_with("- fact"));
+    }
+
+    #[test]
+    fn test_summary_prompt_is_bounded() {
+        let output = "x".repeat(SUMMARY_INPUT_CHARS * 2);
+        let messages = summary_messages("search_web", "{}", &output);
+        let Some(prompt) = messages.last() else {
+            panic!("expected a user message");
+        };
+        assert!(prompt.content.chars().count() < SUMMARY_INPUT_CHARS + 1000);
+    }
+}
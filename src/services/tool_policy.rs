@@ -0,0 +1,119 @@
+use crate::config::ToolsConfig;
+
+/// Characters of a tool result kept in the audit log
+const AUDIT_RESULT_CHARS: usize = 4000;
+
+/// What the policy lets a tool call do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolDecision {
+    Run,
+    /// Described to the model instead of carried out
+    DryRun,
+    Denied,
+}
+
+impl ToolDecision {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Run => "ran",
+            Self::DryRun => "dry run",
+            Self::Denied => "denied",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Self {
+        match value {
+            "ran" => Self::Run,
+            "dry run" => Self::DryRun,
+            _ => Self::Denied,
+        }
+    }
+}
+
+/// Whether the allow/deny lists let the agent call `tool` at all
+#[must_use]
+pub fn is_allowed(config: &ToolsConfig, tool: &str) -> bool {
+    let listed = |names: &[String]| names.iter().any(|name| name.trim() == tool);
+    !listed(&config.deny) && (config.allow.is_empty() || listed(&config.allow))
+}
+
+/// Decides one call. Dry-run only holds back tools that change something; lookups still run
+#[must_use]
+pub fn decide(config: &ToolsConfig, tool: &str, changes_something: bool) -> ToolDecision {
+    if !is_allowed(config, tool) {
+        ToolDecision::Denied
+    } else if config.dry_run && changes_something {
+        ToolDecision::DryRun
+    } else {
+        ToolDecision::Run
+    }
+}
+
+/// Tool result handed to the model for a call the policy held back
+#[must_use]
+pub fn held_back_result(decision: ToolDecision, tool: &str, arguments: &str) -> String {
+    match decision {
+        ToolDecision::Denied => format!(
+            "The {} tool is turned off in the settings. Tell the user you cannot do this.",
+            tool
+        ),
+        ToolDecision::DryRun => format!(
+            "Dry run: {} was not carried out. It would have run with {}. Tell the user what would happen.",
+            tool, arguments
+        ),
+        ToolDecision::Run => String::new(),
+    }
+}
+
+/// The part of a result stored in the audit log
+#[must_use]
+pub fn audit_excerpt(result: &str) -> String {
+    match result.char_indices().nth(AUDIT_RESULT_CHARS) {
+        Some((end, _)) => format!("{}…", &result[..end]),
+        None => result.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(deny: &[&str], allow: &[&str], dry_run: bool) -> ToolsConfig {
+        ToolsConfig {
+            deny: deny.iter().map(|name| (*name).to_string()).collect(),
+            allow: allow.iter().map(|name| (*name).to_string()).collect(),
+            dry_run,
+            audit: true,
+            summarize_over_chars: 0,
+        }
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let policy = config(&["query_database"], &["query_database", "search_web"], false);
+        assert_eq!(decide(&policy, "query_database", false), ToolDecision::Denied);
+        assert_eq!(decide(&policy, "search_web", false), ToolDecision::Run);
+        assert_eq!(decide(&policy, "search_notes", false), ToolDecision::Denied);
+        assert!(is_allowed(&config(&[], &[], false), "search_notes"));
+    }
+
+    #[test]
+    fn test_dry_run_only_holds_back_changes() {
+        let policy = config(&[], &[], true);
+        assert_eq!(decide(&policy, "delete_project", true), ToolDecision::DryRun);
+        assert_eq!(decide(&policy, "search_web", false), ToolDecision::Run);
+        assert!(held_back_result(ToolDecision::DryRun, "delete_project", "{}").starts_with("Dry run"));
+    }
+
+    #[test]
+    fn test_decisions_round_trip_and_excerpts_are_bounded() {
+        for decision in [ToolDecision::Run, ToolDecision::DryRun, ToolDecision::Denied] {
+            assert_eq!(ToolDecision::from_stored(decision.as_str()), decision);
+        }
+        let long = "é".repeat(AUDIT_RESULT_CHARS + 10);
+        assert_eq!(audit_excerpt(&long).chars().count(), AUDIT_RESULT_CHARS + 1);
+        assert_eq!(audit_excerpt("short"), "short");
+    }
+}
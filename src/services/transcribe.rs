@@ -0,0 +1,147 @@
+use color_eyre::eyre::{eyre, WrapErr};
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Whisper model used when `KIMI_WHISPER_MODEL` is not set
+const DEFAULT_WHISPER_MODEL: &str = "base";
+const TRANSCRIPT_SUFFIX: &str = "transcript.txt";
+
+/// A finished transcription saved next to the other downloads
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+impl Transcript {
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+}
+
+#[must_use]
+pub fn is_url(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// Transcribes a URL (audio is fetched with yt-dlp) or a local media file with the
+/// whisper CLI, and saves the text as `<name>.transcript.txt` in the download directory
+pub fn transcribe(source: &str) -> Result<Transcript> {
+    let download_dir = crate::services::link_download::resolve_download_dir()?;
+    let media_path = if is_url(source) {
+        download_audio(source, &download_dir)?
+    } else {
+        let path = PathBuf::from(source);
+        if !path.is_file() {
+            return Err(eyre!("File not found: {}", source));
+        }
+        path
+    };
+
+    let work_dir = std::env::temp_dir().join(format!("kimi-transcribe-{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir)?;
+    let result = transcribe_media(&media_path, &work_dir, &download_dir);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result
+}
+
+fn transcribe_media(media_path: &Path, work_dir: &Path, download_dir: &Path) -> Result<Transcript> {
+    let stem = media_path
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| eyre!("Invalid media file name"))?;
+
+    // Whisper works on 16 kHz mono audio; converting first also handles video files
+    let audio_path = work_dir.join("audio.wav");
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-i"])
+        .arg(media_path)
+        .args(["-vn", "-ac", "1", "-ar", "16000"])
+        .arg(&audio_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .wrap_err("Failed to start ffmpeg — is it installed and in your PATH?")?;
+    if !status.success() {
+        return Err(eyre!("ffmpeg could not extract audio from {}", media_path.display()));
+    }
+
+    let model = std::env::var("KIMI_WHISPER_MODEL")
+        .unwrap_or_else(|_| DEFAULT_WHISPER_MODEL.to_string());
+    let output = Command::new("whisper")
+        .arg(&audio_path)
+        .args(["--model", &model, "--output_format", "txt", "--output_dir"])
+        .arg(work_dir)
+        .stdin(Stdio::null())
+        .output()
+        .wrap_err("Failed to start whisper — is it installed and in your PATH?")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+        return Err(eyre!("whisper failed: {}", reason.unwrap_or("unknown error").trim()));
+    }
+
+    let text = clean_transcript(&std::fs::read_to_string(work_dir.join("audio.txt"))?);
+    if text.is_empty() {
+        return Err(eyre!("No speech found in {}", media_path.display()));
+    }
+    let path = download_dir.join(format!("{}.{}", stem, TRANSCRIPT_SUFFIX));
+    std::fs::write(&path, &text)?;
+    Ok(Transcript { path, text })
+}
+
+/// Downloads only the audio track and returns the path yt-dlp wrote it to
+fn download_audio(url: &str, download_dir: &Path) -> Result<PathBuf> {
+    let output_template = download_dir.join("%(title)s.%(ext)s");
+    let output_path = output_template
+        .to_str()
+        .ok_or_else(|| eyre!("Download path contains invalid characters"))?;
+    let output = Command::new("yt-dlp")
+        .args(["--no-playlist", "--quiet", "-x", "--audio-format", "mp3"])
+        .args(["--print", "after_move:filepath"])
+        .args(["-o", output_path])
+        .arg(url)
+        .stdin(Stdio::null())
+        .output()
+        .wrap_err("Failed to start yt-dlp — is it installed and in your PATH?")?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "{}",
+            crate::services::link_download::extract_ytdlp_error(&String::from_utf8_lossy(
+                &output.stderr
+            ))
+        ));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| eyre!("yt-dlp did not report the downloaded file"))
+}
+
+/// Joins whisper's per-segment lines into paragraphs, keeping blank-line breaks
+#[must_use]
+pub fn clean_transcript(raw: &str) -> String {
+    raw.split("\n\n")
+        .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_lines_are_joined_into_paragraphs() {
+        let raw = " Hello there.\n How are you?\n\n\n Fine.\n";
+        assert_eq!(clean_transcript(raw), "Hello there. How are you?\n\nFine.");
+        assert!(clean_transcript("\n \n").is_empty());
+        assert!(is_url("https://youtu.be/x"));
+        assert!(!is_url("/home/me/talk.mp4"));
+    }
+}
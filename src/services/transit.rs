@@ -0,0 +1,324 @@
+use crate::config::TransitConfig;
+use chrono::{DateTime, FixedOffset};
+use color_eyre::{Result, eyre::eyre};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const GOLEMIO_DEPARTURES_URL: &str = "https://api.golemio.cz/v2/pid/departureboards";
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+/// Departures fetched per requested one when filtering by mode, so a busy bus stop
+/// still yields enough trams
+const MODE_FILTER_OVERFETCH: usize = 4;
+
+/// Kind of vehicle, from the GTFS route type Golemio reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Tram,
+    Metro,
+    Train,
+    Bus,
+    Ferry,
+    Funicular,
+}
+
+impl Mode {
+    #[must_use]
+    pub fn from_route_type(route_type: u32) -> Option<Self> {
+        match route_type {
+            0 => Some(Self::Tram),
+            1 => Some(Self::Metro),
+            2 => Some(Self::Train),
+            3 | 11 => Some(Self::Bus),
+            4 => Some(Self::Ferry),
+            7 => Some(Self::Funicular),
+            _ => None,
+        }
+    }
+
+    /// "tram", "trams", "subway" and the like; `None` for anything else
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "tram" | "trams" | "tramvaj" => Some(Self::Tram),
+            "metro" | "subway" | "underground" => Some(Self::Metro),
+            "train" | "trains" | "vlak" => Some(Self::Train),
+            "bus" | "buses" | "autobus" => Some(Self::Bus),
+            "ferry" | "ferries" | "boat" => Some(Self::Ferry),
+            "funicular" | "lanovka" => Some(Self::Funicular),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tram => "tram",
+            Self::Metro => "metro",
+            Self::Train => "train",
+            Self::Bus => "bus",
+            Self::Ferry => "ferry",
+            Self::Funicular => "funicular",
+        }
+    }
+}
+
+/// One departure from a departure board
+#[derive(Debug, Clone, PartialEq)]
+pub struct Departure {
+    pub line: String,
+    pub mode: Option<Mode>,
+    pub headsign: String,
+    pub platform: Option<String>,
+    /// Predicted time when there is live data, otherwise the timetable
+    pub departs_at: DateTime<FixedOffset>,
+    pub delay_minutes: Option<i64>,
+}
+
+// ── Golemio response ────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct BoardResponse {
+    #[serde(default)]
+    departures: Vec<BoardDeparture>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardDeparture {
+    departure_timestamp: BoardTimestamp,
+    #[serde(default)]
+    delay: Option<BoardDelay>,
+    route: BoardRoute,
+    #[serde(default)]
+    stop: Option<BoardStop>,
+    trip: BoardTrip,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardTimestamp {
+    #[serde(default)]
+    predicted: Option<String>,
+    scheduled: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardDelay {
+    #[serde(default)]
+    is_available: bool,
+    #[serde(default)]
+    minutes: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardRoute {
+    #[serde(default)]
+    short_name: Option<String>,
+    #[serde(rename = "type", default)]
+    route_type: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardStop {
+    #[serde(default)]
+    platform_code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BoardTrip {
+    #[serde(default)]
+    headsign: String,
+}
+
+fn parse_board(body: &str) -> Result<Vec<Departure>> {
+    let board: BoardResponse = serde_json::from_str(body)?;
+    Ok(board
+        .departures
+        .into_iter()
+        .filter_map(|departure| {
+            let timestamp = departure
+                .departure_timestamp
+                .predicted
+                .as_deref()
+                .unwrap_or(&departure.departure_timestamp.scheduled);
+            Some(Departure {
+                line: departure.route.short_name.unwrap_or_default(),
+                mode: departure.route.route_type.and_then(Mode::from_route_type),
+                headsign: departure.trip.headsign,
+                platform: departure
+                    .stop
+                    .and_then(|stop| stop.platform_code)
+                    .filter(|platform| !platform.trim().is_empty()),
+                departs_at: DateTime::parse_from_rfc3339(timestamp).ok()?,
+                delay_minutes: departure
+                    .delay
+                    .filter(|delay| delay.is_available)
+                    .and_then(|delay| delay.minutes),
+            })
+        })
+        .collect())
+}
+
+// ── Requests ────────────────────────────────────────────────────────────────
+
+/// Stop to look up: "home" (or nothing) and "work" map to the configured stops
+pub fn resolve_stop<'a>(config: &'a TransitConfig, stop: &'a str) -> Result<&'a str> {
+    let (configured, key) = match stop.trim().to_lowercase().as_str() {
+        "" | "home" => (config.home_stop.trim(), "home_stop"),
+        "work" | "office" => (config.work_stop.trim(), "work_stop"),
+        _ => return Ok(stop.trim()),
+    };
+    if configured.is_empty() {
+        return Err(eyre!("No stop given and [transit] {} is not set", key));
+    }
+    Ok(configured)
+}
+
+/// Next departures from `stop`, optionally only one kind of vehicle
+pub fn fetch_departures(config: &TransitConfig, stop: &str, mode: Option<Mode>) -> Result<Vec<Departure>> {
+    if config.api_key.trim().is_empty() {
+        return Err(eyre!("Transit not configured. Set [transit] api_key to a Golemio token"));
+    }
+    let wanted = config.departures.max(1);
+    let limit = if mode.is_some() { wanted * MODE_FILTER_OVERFETCH } else { wanted };
+    let client = Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent("kimi")
+        .build()?;
+    let body = client
+        .get(GOLEMIO_DEPARTURES_URL)
+        .header("X-Access-Token", config.api_key.trim())
+        .query(&[
+            ("names", stop.to_string()),
+            ("limit", limit.to_string()),
+            ("minutesAfter", config.minutes_ahead.to_string()),
+            ("order", "real".to_string()),
+        ])
+        .send()?
+        .error_for_status()?
+        .text()?;
+    let mut departures = parse_board(&body)?;
+    if let Some(mode) = mode {
+        departures.retain(|departure| departure.mode == Some(mode));
+    }
+    departures.truncate(wanted);
+    Ok(departures)
+}
+
+// ── Formatting ──────────────────────────────────────────────────────────────
+
+/// One line per departure: "tram 9 → Spojovací (platform A) in 3 min, 09:03, 1 min late"
+#[must_use]
+pub fn format_departures(stop: &str, departures: &[Departure], now: DateTime<FixedOffset>) -> String {
+    if departures.is_empty() {
+        return format!("No upcoming departures found for {}.", stop);
+    }
+    let mut lines = vec![format!("Departures from {}:", stop)];
+    for departure in departures {
+        let mut line = format!("- {}", departure.mode.map_or("line", Mode::label));
+        if !departure.line.is_empty() {
+            line.push_str(&format!(" {}", departure.line));
+        }
+        line.push_str(&format!(" → {}", departure.headsign));
+        if let Some(platform) = &departure.platform {
+            line.push_str(&format!(" (platform {})", platform));
+        }
+        let minutes = (departure.departs_at - now).num_minutes().max(0);
+        if minutes == 0 {
+            line.push_str(" now");
+        } else {
+            line.push_str(&format!(" in {} min", minutes));
+        }
+        line.push_str(&format!(", {}", departure.departs_at.format("%H:%M")));
+        if let Some(delay) = departure.delay_minutes.filter(|delay| *delay > 0) {
+            line.push_str(&format!(", {} min late", delay));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// "when's the next tram", "bus departures from home"; keeps these away from web search
+#[must_use]
+pub fn looks_like_transit_question(lowered: &str) -> bool {
+    let words: Vec<&str> = lowered
+        .split_whitespace()
+        .map(|word| word.trim_matches(|character: char| !character.is_alphanumeric()))
+        .collect();
+    if words.iter().any(|word| word.starts_with("departure")) {
+        return true;
+    }
+    let mentions_vehicle = words.iter().any(|word| Mode::from_name(word).is_some());
+    let asks_when = words
+        .iter()
+        .any(|word| matches!(*word, "next" | "when" | "leave" | "leaves" | "from"));
+    mentions_vehicle && asks_when
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"{
+        "stops": [{"stop_name": "Anděl"}],
+        "departures": [
+            {
+                "departure_timestamp": {"predicted": "2026-10-17T09:04:00+02:00", "scheduled": "2026-10-17T09:03:00+02:00"},
+                "delay": {"is_available": true, "minutes": 1},
+                "route": {"short_name": "9", "type": 0},
+                "stop": {"platform_code": "A"},
+                "trip": {"headsign": "Spojovací"}
+            },
+            {
+                "departure_timestamp": {"predicted": null, "scheduled": "2026-10-17T09:00:30+02:00"},
+                "delay": {"is_available": false, "minutes": null},
+                "route": {"short_name": "B", "type": 1},
+                "stop": {"platform_code": ""},
+                "trip": {"headsign": "Černý Most"}
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parses_and_formats_a_board() {
+        let Ok(departures) = parse_board(BOARD) else {
+            panic!("board should parse");
+        };
+        assert_eq!(departures.len(), 2);
+        let Some(tram) = departures.first() else {
+            panic!("missing departure");
+        };
+        assert_eq!(tram.mode, Some(Mode::Tram));
+        assert_eq!(tram.delay_minutes, Some(1));
+        let Ok(now) = DateTime::parse_from_rfc3339("2026-10-17T09:00:00+02:00") else {
+            panic!("bad test date");
+        };
+        assert_eq!(
+            format_departures("Anděl", &departures, now),
+            "Departures from Anděl:\n\
+             - tram 9 → Spojovací (platform A) in 4 min, 09:04, 1 min late\n\
+             - metro B → Černý Most now, 09:00"
+        );
+    }
+
+    #[test]
+    fn test_home_and_work_map_to_configured_stops() {
+        let config = TransitConfig {
+            home_stop: "Anděl".to_string(),
+            ..TransitConfig::default()
+        };
+        assert_eq!(resolve_stop(&config, "home").ok(), Some("Anděl"));
+        assert_eq!(resolve_stop(&config, "").ok(), Some("Anděl"));
+        assert_eq!(resolve_stop(&config, "Karlovo náměstí").ok(), Some("Karlovo náměstí"));
+        assert!(resolve_stop(&config, "work").is_err());
+    }
+
+    #[test]
+    fn test_recognizes_transit_questions() {
+        assert!(looks_like_transit_question("when's the next tram from anděl?"));
+        assert!(looks_like_transit_question("bus departures from home"));
+        assert!(!looks_like_transit_question("what's the weather like"));
+        assert!(!looks_like_transit_question("how do i train a puppy"));
+        assert_eq!(Mode::from_name("Trams"), Some(Mode::Tram));
+        assert_eq!(Mode::from_name("bus"), Some(Mode::Bus));
+    }
+}
@@ -1,8 +1,67 @@
 use color_eyre::Result;
 use reqwest::blocking::Client;
-use rodio::{Decoder, OutputStream, Sink};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use std::collections::VecDeque;
 use std::io::Cursor;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// The first chunk is kept short so playback starts quickly
+const FIRST_CHUNK_MAX_CHARS: usize = 160;
+/// Later chunks are fetched while the previous one plays, so they can be longer
+const CHUNK_MAX_CHARS: usize = 600;
+const PLAYBACK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const PREVIEW_CHARS: usize = 40;
+/// Volume of the message being read while an alert plays over it
+const DUCKED_VOLUME: f32 = 0.25;
+
+/// What is being spoken right now, for the footer indicator
+#[derive(Debug, Clone)]
+pub struct SpeechStatus {
+    /// Start of the message being spoken
+    pub preview: String,
+    pub elapsed: Duration,
+    pub paused: bool,
+    /// Messages waiting after the current one
+    pub queued: usize,
+}
+
+struct CurrentSpeech {
+    preview: String,
+    started_at: Instant,
+    paused_at: Option<Instant>,
+    paused_total: Duration,
+}
+
+impl CurrentSpeech {
+    fn elapsed(&self) -> Duration {
+        let paused_now = self.paused_at.map_or(Duration::ZERO, |paused_at| paused_at.elapsed());
+        self.started_at
+            .elapsed()
+            .saturating_sub(self.paused_total + paused_now)
+    }
+}
+
+/// A queued message and the voice to read it with (the configured one when `None`)
+struct Utterance {
+    text: String,
+    voice_id: Option<String>,
+}
+
+/// Playback state shared between the service handle and its worker thread. Messages
+/// play one after another; alerts (timers) play right away with the message ducked
+#[derive(Default)]
+struct Playback {
+    queue: VecDeque<Utterance>,
+    alerts: VecDeque<String>,
+    current: Option<CurrentSpeech>,
+    sink: Option<Arc<Sink>>,
+    alert_sink: Option<Arc<Sink>>,
+    /// Bumped by skip/stop so the worker drops chunks of the interrupted message
+    generation: u64,
+    worker_running: bool,
+    last_error: Option<String>,
+}
 
 /// Text-to-speech service using ElevenLabs API
 #[derive(Clone)]
@@ -11,7 +70,7 @@ pub struct TTSService {
     voice_id: String,
     model: String,
     client: Client,
-    current_sink: Arc<Mutex<Option<Arc<Sink>>>>,
+    playback: Arc<Mutex<Playback>>,
 }
 
 impl TTSService {
@@ -22,88 +81,352 @@ impl TTSService {
             voice_id,
             model,
             client: Client::new(),
-            current_sink: Arc::new(Mutex::new(None)),
+            playback: Arc::new(Mutex::new(Playback::default())),
         }
     }
 
-    /// Converts text to speech and plays it
+    /// Queues text to be spoken after anything already playing; audio is fetched
+    /// sentence by sentence in the background so speech starts early
     pub fn speak_text(&self, text: &str) -> Result<()> {
-        let body = serde_json::json!({
-            "text": text,
-            "model_id": self.model,
-            "voice_settings": {
-                "stability": 0.5,
-                "similarity_boost": 0.5
-            }
-        });
+        self.speak_text_with_voice(text, None)
+    }
 
-        let response = self
-            .client
-            .post(format!(
-                "https://api.elevenlabs.io/v1/text-to-speech/{}",
-                self.voice_id
-            ))
-            .header("xi-api-key", &self.api_key)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()?
-            .error_for_status()?;
+    /// Like `speak_text`, reading this message with `voice_id` instead of the configured voice
+    pub fn speak_text_with_voice(&self, text: &str, voice_id: Option<&str>) -> Result<()> {
+        if !self.is_configured() {
+            return Err(color_eyre::eyre::eyre!("ElevenLabs API key not configured"));
+        }
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+        let mut playback = self.lock();
+        playback.queue.push_back(Utterance {
+            text: text.to_string(),
+            voice_id: voice_id.map(str::to_string),
+        });
+        self.ensure_worker(&mut playback);
+        Ok(())
+    }
 
-        let audio_data = response.bytes()?.to_vec();
-        self.play_audio(audio_data)?;
+    /// Speaks a short alert without waiting for the queue: the message being read is
+    /// turned down while the alert plays and continues afterwards
+    pub fn speak_alert(&self, text: &str) -> Result<()> {
+        if !self.is_configured() {
+            return Err(color_eyre::eyre::eyre!("ElevenLabs API key not configured"));
+        }
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+        let mut playback = self.lock();
+        playback.alerts.push_back(text.to_string());
+        self.ensure_worker(&mut playback);
         Ok(())
     }
 
+    fn ensure_worker(&self, playback: &mut Playback) {
+        if !playback.worker_running {
+            playback.worker_running = true;
+            let service = self.clone();
+            std::thread::spawn(move || service.run_worker());
+        }
+    }
+
     /// Checks if TTS is configured with valid credentials
     #[must_use]
     pub fn is_configured(&self) -> bool {
         !self.api_key.is_empty() && self.api_key != "your_api_key_here"
     }
 
-    /// Checks if audio is currently playing
+    /// Checks if a message is being spoken or waiting in the queue
     #[must_use]
     pub fn is_playing(&self) -> bool {
-        if let Ok(sink_guard) = self.current_sink.lock()
-            && let Some(sink) = sink_guard.as_ref()
-        {
-            return !sink.empty();
+        let playback = self.lock();
+        playback.current.is_some() || !playback.queue.is_empty()
+    }
+
+    /// Checks if anything at all is audible or waiting, alerts included
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        let playback = self.lock();
+        playback.current.is_some()
+            || !playback.queue.is_empty()
+            || !playback.alerts.is_empty()
+            || playback.alert_sink.is_some()
+    }
+
+    #[must_use]
+    pub fn status(&self) -> Option<SpeechStatus> {
+        let playback = self.lock();
+        playback.current.as_ref().map(|current| SpeechStatus {
+            preview: current.preview.clone(),
+            elapsed: current.elapsed(),
+            paused: current.paused_at.is_some(),
+            queued: playback.queue.len(),
+        })
+    }
+
+    /// Pauses or resumes the current message; returns true when now paused
+    pub fn toggle_pause(&self) -> bool {
+        let mut playback = self.lock();
+        let sink = playback.sink.clone();
+        let Some(current) = playback.current.as_mut() else {
+            return false;
+        };
+        match current.paused_at.take() {
+            Some(paused_at) => {
+                current.paused_total += paused_at.elapsed();
+                if let Some(sink) = sink {
+                    sink.play();
+                }
+                false
+            }
+            None => {
+                current.paused_at = Some(Instant::now());
+                if let Some(sink) = sink {
+                    sink.pause();
+                }
+                true
+            }
+        }
+    }
+
+    /// Drops the rest of the current message and moves on to the next queued one
+    pub fn skip(&self) {
+        let mut playback = self.lock();
+        playback.generation += 1;
+        if let Some(sink) = &playback.sink {
+            sink.clear();
         }
-        false
     }
 
-    /// Stops currently playing audio
+    /// Stops playback and empties the queue; alerts keep playing
     pub fn stop(&self) {
-        if let Ok(mut sink_guard) = self.current_sink.lock()
-            && let Some(sink) = sink_guard.take()
-        {
-            sink.stop();
+        let mut playback = self.lock();
+        playback.queue.clear();
+        playback.generation += 1;
+        if let Some(sink) = &playback.sink {
+            sink.clear();
         }
     }
 
-    fn play_audio(&self, audio_data: Vec<u8>) -> Result<()> {
+    /// Silences everything: the queue, the message being read and any alert
+    pub fn stop_all(&self) {
         self.stop();
+        let mut playback = self.lock();
+        playback.alerts.clear();
+        if let Some(alert_sink) = &playback.alert_sink {
+            alert_sink.stop();
+        }
+    }
 
-        let current_sink = Arc::clone(&self.current_sink);
+    /// Error from the background worker since the last call, if any
+    pub fn take_error(&self) -> Option<String> {
+        self.lock().last_error.take()
+    }
 
-        std::thread::spawn(move || {
-            let (_stream, stream_handle) = OutputStream::try_default().ok()?;
-            let sink = Arc::new(Sink::try_new(&stream_handle).ok()?);
+    fn lock(&self) -> MutexGuard<'_, Playback> {
+        self.playback
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
 
-            if let Ok(mut sink_guard) = current_sink.lock() {
-                *sink_guard = Some(Arc::clone(&sink));
+    /// Speaks queued messages until the queue is empty. The output stream lives on
+    /// this thread because it cannot be moved between threads
+    fn run_worker(&self) {
+        let opened = OutputStream::try_default()
+            .map_err(|error| error.to_string())
+            .and_then(|(stream, handle)| {
+                Sink::try_new(&handle)
+                    .map(|sink| (stream, handle, Arc::new(sink)))
+                    .map_err(|error| error.to_string())
+            });
+        let (_stream, handle, sink) = match opened {
+            Ok(opened) => opened,
+            Err(error) => {
+                let mut playback = self.lock();
+                playback.queue.clear();
+                playback.alerts.clear();
+                playback.worker_running = false;
+                playback.last_error = Some(format!("No audio output: {}", error));
+                return;
             }
+        };
+        self.lock().sink = Some(Arc::clone(&sink));
 
-            if let Ok(source) = Decoder::new(Cursor::new(audio_data)) {
-                sink.append(source);
-                sink.sleep_until_end();
+        loop {
+            self.play_alerts(&handle, &sink);
+            let (utterance, generation) = {
+                let mut playback = self.lock();
+                if !playback.alerts.is_empty() {
+                    continue;
+                }
+                let Some(utterance) = playback.queue.pop_front() else {
+                    playback.current = None;
+                    playback.sink = None;
+                    playback.worker_running = false;
+                    return;
+                };
+                playback.current = Some(CurrentSpeech {
+                    preview: utterance
+                        .text
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .chars()
+                        .take(PREVIEW_CHARS)
+                        .collect(),
+                    started_at: Instant::now(),
+                    paused_at: None,
+                    paused_total: Duration::ZERO,
+                });
+                (utterance, playback.generation)
+            };
+            sink.play();
+            if let Err(error) = self.play_message(&handle, &sink, &utterance, generation) {
+                tracing::warn!(%error, "tts playback failed");
+                self.lock().last_error = Some(error.to_string());
             }
+            self.lock().current = None;
+        }
+    }
 
-            if let Ok(mut sink_guard) = current_sink.lock() {
-                *sink_guard = None;
+    fn play_message(
+        &self,
+        handle: &OutputStreamHandle,
+        sink: &Sink,
+        utterance: &Utterance,
+        generation: u64,
+    ) -> Result<()> {
+        let interrupted = || self.lock().generation != generation;
+        let voice_id = utterance.voice_id.as_deref().unwrap_or(&self.voice_id);
+        for chunk in split_into_speech_chunks(&utterance.text) {
+            if interrupted() {
+                return Ok(());
             }
-            Some(())
-        });
+            let audio = self.fetch_audio(&chunk, voice_id)?;
+            if interrupted() {
+                return Ok(());
+            }
+            sink.append(Decoder::new(Cursor::new(audio))?);
+            self.play_alerts(handle, sink);
+        }
+        while !sink.empty() && !interrupted() {
+            self.play_alerts(handle, sink);
+            std::thread::sleep(PLAYBACK_POLL_INTERVAL);
+        }
+        Ok(())
+    }
 
+    /// Plays waiting alerts on their own sink with `sink` ducked underneath
+    fn play_alerts(&self, handle: &OutputStreamHandle, sink: &Sink) {
+        loop {
+            let Some(text) = self.lock().alerts.pop_front() else {
+                return;
+            };
+            sink.set_volume(DUCKED_VOLUME);
+            if let Err(error) = self.play_alert(handle, &text) {
+                tracing::warn!(%error, "tts alert failed");
+                self.lock().last_error = Some(error.to_string());
+            }
+            self.lock().alert_sink = None;
+            sink.set_volume(1.0);
+        }
+    }
+
+    fn play_alert(&self, handle: &OutputStreamHandle, text: &str) -> Result<()> {
+        let audio = self.fetch_audio(text, &self.voice_id)?;
+        let alert_sink = Arc::new(Sink::try_new(handle)?);
+        alert_sink.append(Decoder::new(Cursor::new(audio))?);
+        self.lock().alert_sink = Some(Arc::clone(&alert_sink));
+        while !alert_sink.empty() {
+            std::thread::sleep(PLAYBACK_POLL_INTERVAL);
+        }
         Ok(())
     }
+
+    fn fetch_audio(&self, text: &str, voice_id: &str) -> Result<Vec<u8>> {
+        let body = serde_json::json!({
+            "text": text,
+            "model_id": self.model,
+            "voice_settings": {
+                "stability": 0.5,
+                "similarity_boost": 0.5
+            }
+        });
+
+        let response = self
+            .client
+            .post(format!(
+                "https://api.elevenlabs.io/v1/text-to-speech/{}",
+                voice_id
+            ))
+            .header("xi-api-key", &self.api_key)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()?
+            .error_for_status()?;
+
+        Ok(response.bytes()?.to_vec())
+    }
+}
+
+/// Splits text at sentence ends into chunks for separate TTS requests: a short
+/// first chunk, then sentences grouped up to `CHUNK_MAX_CHARS`
+#[must_use]
+pub fn split_into_speech_chunks(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut sentence = String::new();
+    let mut characters = text.chars().peekable();
+    while let Some(character) = characters.next() {
+        sentence.push(character);
+        let at_boundary = matches!(character, '.' | '!' | '?' | '\n')
+            && characters.peek().is_none_or(|next| next.is_whitespace());
+        if at_boundary {
+            let trimmed = sentence.trim();
+            if !trimmed.is_empty() {
+                sentences.push(trimmed.to_string());
+            }
+            sentence.clear();
+        }
+    }
+    if !sentence.trim().is_empty() {
+        sentences.push(sentence.trim().to_string());
+    }
+
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for sentence in sentences {
+        let limit = if chunks.is_empty() {
+            FIRST_CHUNK_MAX_CHARS
+        } else {
+            CHUNK_MAX_CHARS
+        };
+        if !current.is_empty() && current.chars().count() + sentence.chars().count() + 1 > limit {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&sentence);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_start_short_and_keep_sentences_whole() {
+        let long_sentence = format!("{}end.", "word ".repeat(40));
+        let text = format!("Hi there! {} Version 1.5 is out. Done?", long_sentence);
+        let chunks = split_into_speech_chunks(&text);
+        assert_eq!(chunks.first().map(String::as_str), Some("Hi there!"));
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.last().is_some_and(|chunk| chunk.ends_with("Version 1.5 is out. Done?")));
+        assert!(split_into_speech_chunks("  \n ").is_empty());
+    }
 }
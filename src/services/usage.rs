@@ -0,0 +1,228 @@
+use crate::agents::openai_compat::TokenUsage;
+use crate::config::{ModelPrice, UsageConfig};
+use crate::storage::{StorageManager, UsageRecord};
+use color_eyre::{Result, eyre::eyre};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Receives `(title, message)` of budget warnings so they land in the inbox
+pub type BudgetNotifier = Arc<dyn Fn(String, String) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetState {
+    Under,
+    Warning,
+    Exceeded,
+}
+
+/// Today's spend against one configured budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetLine {
+    /// "venice" or "overall"
+    pub scope: String,
+    pub spent: f64,
+    pub budget: f64,
+    pub state: BudgetState,
+}
+
+impl BudgetLine {
+    #[must_use]
+    pub fn describe(&self) -> String {
+        format!("${:.2} of the ${:.2} daily {} budget spent", self.spent, self.budget, self.scope)
+    }
+}
+
+/// Local date the ledger files requests under
+#[must_use]
+pub fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// USD for one request; models without a configured price cost nothing
+#[must_use]
+pub fn estimate_cost(prices: &HashMap<String, ModelPrice>, provider: &str, model: &str, usage: TokenUsage) -> f64 {
+    let price = prices
+        .get(&format!("{}/{}", provider, model))
+        .or_else(|| prices.get(model))
+        .copied()
+        .unwrap_or_default();
+    (usage.prompt_tokens as f64 * price.input + usage.completion_tokens as f64 * price.output) / 1_000_000.0
+}
+
+#[must_use]
+pub fn budget_state(spent: f64, budget: f64, warn_at: f64) -> BudgetState {
+    if spent >= budget {
+        BudgetState::Exceeded
+    } else if spent >= budget * warn_at {
+        BudgetState::Warning
+    } else {
+        BudgetState::Under
+    }
+}
+
+/// Every configured budget (per provider, then overall) with today's spend from `today_rows`
+#[must_use]
+pub fn budget_lines(config: &UsageConfig, today_rows: &[UsageRecord]) -> Vec<BudgetLine> {
+    let mut providers: Vec<(&String, &f64)> = config.budgets.iter().collect();
+    providers.sort_by(|a, b| a.0.cmp(b.0));
+    let mut budgets: Vec<(String, f64, f64)> = providers
+        .into_iter()
+        .map(|(provider, budget)| {
+            let spent = today_rows
+                .iter()
+                .filter(|row| &row.provider == provider)
+                .map(|row| row.cost_usd)
+                .sum();
+            (provider.clone(), spent, *budget)
+        })
+        .collect();
+    if let Some(budget) = config.daily_budget_usd {
+        budgets.push(("overall".to_string(), today_rows.iter().map(|row| row.cost_usd).sum(), budget));
+    }
+    budgets
+        .into_iter()
+        .map(|(scope, spent, budget)| BudgetLine {
+            scope,
+            spent,
+            budget,
+            state: budget_state(spent, budget, config.warn_at),
+        })
+        .collect()
+}
+
+/// Records tokens and cost of hosted requests and enforces the daily budgets
+pub struct UsageLedger {
+    storage: StorageManager,
+    runtime: tokio::runtime::Runtime,
+    config: UsageConfig,
+    notifier: BudgetNotifier,
+    /// `day|scope|state` of warnings already published, so each goes out once
+    warned: Mutex<HashSet<String>>,
+    /// Set while the open chat is incognito; budgets are still enforced
+    paused: AtomicBool,
+}
+
+impl UsageLedger {
+    pub fn new(storage: StorageManager, config: UsageConfig, notifier: BudgetNotifier) -> Result<Self> {
+        Ok(Self {
+            storage,
+            runtime: tokio::runtime::Runtime::new()?,
+            config,
+            notifier,
+            warned: Mutex::new(HashSet::new()),
+            paused: AtomicBool::new(false),
+        })
+    }
+
+    /// Stops or resumes recording requests (for incognito chats)
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Fails when `cutoff` is on and a budget covering `provider` is spent for today
+    pub fn check_budget(&self, provider: &str) -> Result<()> {
+        let exceeded = match self.evaluate(provider) {
+            Ok(exceeded) => exceeded,
+            Err(error) => {
+                tracing::warn!(%error, "could not read today's usage");
+                return Ok(());
+            }
+        };
+        match exceeded {
+            Some(line) if self.config.cutoff => Err(eyre!(
+                "Daily budget reached: {}. Raise it under [usage] in config.toml or wait until tomorrow",
+                line.describe()
+            )),
+            Some(_) | None => Ok(()),
+        }
+    }
+
+    /// Adds one request to today's row for `provider`/`model`
+    pub fn record(&self, provider: &str, model: &str, usage: TokenUsage) {
+        if self.paused.load(Ordering::Relaxed) {
+            return;
+        }
+        let cost = estimate_cost(&self.config.prices, provider, model, usage);
+        let saved = self.block_on(self.storage.add_usage(
+            &today(),
+            provider,
+            model,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+            cost,
+        ));
+        if let Err(error) = saved.and_then(|()| self.evaluate(provider).map(|_| ())) {
+            tracing::warn!(%error, provider, model, "could not record usage");
+        }
+    }
+
+    /// Publishes warnings for budgets covering `provider` and returns the first one spent
+    fn evaluate(&self, provider: &str) -> Result<Option<BudgetLine>> {
+        if self.config.budgets.is_empty() && self.config.daily_budget_usd.is_none() {
+            return Ok(None);
+        }
+        let day = today();
+        let rows = self.block_on(self.storage.load_usage(&day))?;
+        let mut exceeded = None;
+        for line in budget_lines(&self.config, &rows) {
+            if line.state == BudgetState::Under || (line.scope != provider && line.scope != "overall") {
+                continue;
+            }
+            self.warn_once(&day, &line);
+            if line.state == BudgetState::Exceeded && exceeded.is_none() {
+                exceeded = Some(line);
+            }
+        }
+        Ok(exceeded)
+    }
+
+    fn warn_once(&self, day: &str, line: &BudgetLine) {
+        let key = format!("{}|{}|{:?}", day, line.scope, line.state);
+        let is_new = self.warned.lock().is_ok_and(|mut warned| warned.insert(key));
+        if !is_new {
+            return;
+        }
+        let title = match line.state {
+            BudgetState::Exceeded if self.config.cutoff => "Daily budget reached, requests paused",
+            BudgetState::Exceeded => "Daily budget exceeded",
+            BudgetState::Warning | BudgetState::Under => "Daily budget almost spent",
+        };
+        (self.notifier)(title.to_string(), line.describe());
+    }
+
+    /// The ledger is called from chat threads; inside an async context it would panic
+    fn block_on<T>(&self, future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(eyre!("usage ledger called from an async context"));
+        }
+        self.runtime.block_on(future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prices_tokens_and_flags_budgets() {
+        let mut config = UsageConfig::default();
+        config.prices.insert("venice/big".to_string(), ModelPrice { input: 1.0, output: 4.0 });
+        config.prices.insert("small".to_string(), ModelPrice { input: 0.5, output: 0.5 });
+        let usage = TokenUsage { prompt_tokens: 500_000, completion_tokens: 250_000 };
+        assert!((estimate_cost(&config.prices, "venice", "big", usage) - 1.5).abs() < 1e-9);
+        assert!((estimate_cost(&config.prices, "gab", "small", usage) - 0.375).abs() < 1e-9);
+        assert!(estimate_cost(&config.prices, "gab", "unknown", usage).abs() < 1e-9);
+
+        config.budgets.insert("venice".to_string(), 2.0);
+        config.daily_budget_usd = Some(10.0);
+        let rows = vec![
+            UsageRecord { provider: "venice".to_string(), cost_usd: 1.7, ..UsageRecord::default() },
+            UsageRecord { provider: "gab".to_string(), cost_usd: 0.4, ..UsageRecord::default() },
+        ];
+        let lines = budget_lines(&config, &rows);
+        let states: Vec<(&str, BudgetState)> = lines.iter().map(|line| (line.scope.as_str(), line.state)).collect();
+        assert_eq!(states, vec![("venice", BudgetState::Warning), ("overall", BudgetState::Under)]);
+        assert_eq!(budget_state(2.0, 2.0, 0.8), BudgetState::Exceeded);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::agents::{Agent, AgentManager, ChatMessage as AgentChatMessage};
+use crate::config::Verification;
+use serde::Deserialize;
+
+/// Characters of the system prompt (notes, memories, search results) the checker reads
+const CONTEXT_CHARS: usize = 12_000;
+/// Issues kept from one check
+const MAX_ISSUES: usize = 5;
+
+/// The checker's overall judgement of an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Outcome of checking one answer; the answer itself is never changed
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AnswerCheck {
+    pub verdict: Verdict,
+    #[serde(default)]
+    pub issues: Vec<String>,
+}
+
+impl AnswerCheck {
+    /// Badge next to the reply: "✓ checked", "⚠ 2 issues", "✗ likely wrong"
+    #[must_use]
+    pub fn badge(&self) -> String {
+        match self.verdict {
+            Verdict::Pass => "✓ checked".to_string(),
+            Verdict::Warn if self.issues.len() == 1 => "⚠ 1 issue".to_string(),
+            Verdict::Warn => format!("⚠ {} issues", self.issues.len()),
+            Verdict::Fail => "✗ likely wrong".to_string(),
+        }
+    }
+}
+
+fn check_messages(
+    strictness: Verification,
+    context: &str,
+    question: &str,
+    answer: &str,
+) -> Vec<AgentChatMessage> {
+    let rules = match strictness {
+        Verification::Strict => {
+            "Flag factual errors, contradictions with the context, claims neither the \
+context nor well-known facts support, missing caveats and parts of the question left unanswered."
+        }
+        Verification::Lenient | Verification::Off => {
+            "Only flag clear factual errors, contradictions with the context and answers \
+that miss the question. Style, tone and brevity are never issues."
+        }
+    };
+    let context: String = context.chars().take(CONTEXT_CHARS).collect();
+    let prompt = format!(
+        "Check the assistant's answer. {}\n\n\
+Reply with JSON only: {{\"verdict\": \"pass\" | \"warn\" | \"fail\", \"issues\": [\"short description\", ...]}}. \
+Use \"pass\" with no issues when the answer holds up, \"warn\" for minor problems and \"fail\" \
+when the answer is wrong. Do not rewrite the answer.\n\n\
+Context the assistant had:\n{}\n\nQuestion:\n{}\n\nAnswer:\n{}",
+        rules, context, question, answer
+    );
+    vec![
+        AgentChatMessage::system("You review answers for accuracy and report problems as JSON."),
+        AgentChatMessage::user(&prompt),
+    ]
+}
+
+/// Reads the checker's JSON, tolerating text around it
+#[must_use]
+pub fn parse_check(reply: &str) -> Option<AnswerCheck> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    if end <= start {
+        return None;
+    }
+    let mut check: AnswerCheck = serde_json::from_str(&reply[start..=end]).ok()?;
+    check.issues.retain(|issue| !issue.trim().is_empty());
+    check.issues.truncate(MAX_ISSUES);
+    if check.verdict == Verdict::Pass {
+        check.issues.clear();
+    }
+    Some(check)
+}
+
+/// Asks `agent` to check an answer; `None` when checking is off or the reply is not usable
+pub fn check_answer(
+    strictness: Verification,
+    context: &str,
+    question: &str,
+    answer: &str,
+    agent: &Agent,
+    manager: &AgentManager,
+) -> Option<AnswerCheck> {
+    if strictness.is_off() {
+        return None;
+    }
+    let messages = check_messages(strictness, context, question, answer);
+    let reply = manager
+        .chat(agent, &messages)
+        .inspect_err(|error| tracing::warn!(%error, agent = %agent.name, "answer check failed"))
+        .ok()?;
+    let check = parse_check(&reply);
+    if check.is_none() {
+        tracing::warn!(agent = %agent.name, "answer check returned no usable JSON");
+    }
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_json_surrounded_by_text() {
+        let reply = "Sure:\n{\"verdict\": \"warn\", \"issues\": [\"date is off by a day\", \" \"]}\nDone";
+        let Some(check) = parse_check(reply) else {
+            panic!("expected a check");
+        };
+        assert_eq!(check.verdict, Verdict::Warn);
+        assert_eq!(check.issues, vec!["date is off by a day".to_string()]);
+        assert_eq!(check.badge(), "⚠ 1 issue");
+    }
+
+    #[test]
+    fn test_pass_drops_issues_and_bad_replies_are_ignored() {
+        let Some(check) = parse_check("{\"verdict\": \"pass\", \"issues\": [\"nit\"]}") else {
+            panic!("expected a check");
+        };
+        assert!(check.issues.is_empty());
+        assert_eq!(check.badge(), "✓ checked");
+        assert!(parse_check("The answer looks right.").is_none());
+        assert!(parse_check("{\"verdict\": \"maybe\"}").is_none());
+    }
+
+    #[test]
+    fn test_strict_checks_ask_for_more() {
+        let lenient = check_messages(Verification::Lenient, "", "q", "a");
+        let strict = check_messages(Verification::Strict, "", "q", "a");
+        let prompt = |messages: &[AgentChatMessage]| messages.last().map(|m| m.content.clone()).unwrap_or_default();
+        assert!(prompt(&strict).contains("missing caveats"));
+        assert!(!prompt(&lenient).contains("missing caveats"));
+    }
+}
@@ -1,62 +1,288 @@
-use color_eyre::Result;
+use color_eyre::{Result, eyre::eyre};
 use reqwest::blocking::Client;
 use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::{WeatherConfig, WeatherUnits};
 
-#[allow(dead_code)]
 const OPEN_METEO_URL: &str = "https://api.open-meteo.com/v1/forecast";
-#[allow(dead_code)]
-const PRAGUE_LAT: f32 = 50.0755;
-#[allow(dead_code)]
-const PRAGUE_LON: f32 = 14.4378;
+const WTTR_URL: &str = "https://wttr.in";
+/// A slow provider should not hold up the fast path; the next one is asked instead
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// Current conditions, always metric; converted only when shown
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherReport {
+    pub location: String,
+    pub time: String,
+    pub temperature_c: f32,
+    pub wind_kph: f32,
+    /// "light rain", when the provider says
+    pub description: Option<String>,
+    pub provider: &'static str,
+}
 
-#[allow(dead_code)]
-pub struct WeatherService {
-    client: Client,
+/// A source of current conditions for the configured location
+pub trait WeatherProvider {
+    fn name(&self) -> &'static str;
+    fn current(&self, client: &Client, config: &WeatherConfig) -> Result<WeatherReport>;
 }
 
-#[allow(dead_code)]
+pub struct OpenMeteo;
+
 #[derive(Debug, Deserialize)]
-struct WeatherResponse {
-    current_weather: CurrentWeather,
+struct OpenMeteoResponse {
+    current_weather: OpenMeteoCurrent,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
-struct CurrentWeather {
+struct OpenMeteoCurrent {
     temperature: f32,
     windspeed: f32,
     weathercode: i32,
     time: String,
 }
 
-#[allow(dead_code)]
-impl WeatherService {
-    pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-        }
+impl WeatherProvider for OpenMeteo {
+    fn name(&self) -> &'static str {
+        "open-meteo"
     }
 
-    pub fn fetch_current_weather_json(&self) -> Result<String> {
+    fn current(&self, client: &Client, config: &WeatherConfig) -> Result<WeatherReport> {
         let url = format!(
-            "{OPEN_METEO_URL}?latitude={PRAGUE_LAT}&longitude={PRAGUE_LON}&current_weather=true"
+            "{}?latitude={}&longitude={}&current_weather=true",
+            OPEN_METEO_URL, config.latitude, config.longitude
         );
-        let response = self.client.get(url).send()?.error_for_status()?;
-        let payload: WeatherResponse = response.json()?;
+        let payload: OpenMeteoResponse = client.get(url).send()?.error_for_status()?.json()?;
+        let current = payload.current_weather;
+        Ok(WeatherReport {
+            location: config.location.clone(),
+            time: current.time,
+            temperature_c: current.temperature,
+            wind_kph: current.windspeed,
+            description: describe_weather_code(current.weathercode).map(str::to_string),
+            provider: self.name(),
+        })
+    }
+}
+
+pub struct Wttr;
+
+#[derive(Debug, Deserialize)]
+struct WttrResponse {
+    current_condition: Vec<WttrCondition>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WttrCondition {
+    #[serde(rename = "temp_C")]
+    temp_c: String,
+    windspeed_kmph: String,
+    #[serde(default)]
+    weather_desc: Vec<WttrValue>,
+    #[serde(default)]
+    local_obs_date_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WttrValue {
+    value: String,
+}
+
+impl WeatherProvider for Wttr {
+    fn name(&self) -> &'static str {
+        "wttr"
+    }
+
+    fn current(&self, client: &Client, config: &WeatherConfig) -> Result<WeatherReport> {
+        let url = format!("{}/{}?format=j1", WTTR_URL, config.location.trim().replace(' ', "+"));
+        let payload: WttrResponse = client.get(url).send()?.error_for_status()?.json()?;
+        parse_wttr(payload, &config.location)
+    }
+}
+
+fn parse_wttr(payload: WttrResponse, location: &str) -> Result<WeatherReport> {
+    let condition = payload
+        .current_condition
+        .into_iter()
+        .next()
+        .ok_or_else(|| eyre!("wttr.in returned no current conditions"))?;
+    Ok(WeatherReport {
+        location: location.to_string(),
+        time: condition.local_obs_date_time,
+        temperature_c: condition.temp_c.trim().parse()?,
+        wind_kph: condition.windspeed_kmph.trim().parse()?,
+        description: condition
+            .weather_desc
+            .into_iter()
+            .map(|desc| desc.value.trim().to_lowercase())
+            .find(|desc| !desc.is_empty()),
+        provider: Wttr.name(),
+    })
+}
+
+/// WMO weather interpretation codes as used by Open-Meteo
+fn describe_weather_code(code: i32) -> Option<&'static str> {
+    Some(match code {
+        0 => "clear sky",
+        1 => "mainly clear",
+        2 => "partly cloudy",
+        3 => "overcast",
+        45 | 48 => "fog",
+        51..=57 => "drizzle",
+        61 | 63 | 80 | 81 => "rain",
+        65 | 82 => "heavy rain",
+        66 | 67 => "freezing rain",
+        71 | 73 | 77 | 85 => "snow",
+        75 | 86 => "heavy snow",
+        95..=99 => "thunderstorm",
+        _ => return None,
+    })
+}
 
-        let summary = serde_json::json!({
-            "location": "Prague",
-            "time": payload.current_weather.time,
-            "temperature_c": payload.current_weather.temperature,
-            "wind_kph": payload.current_weather.windspeed,
-            "weather_code": payload.current_weather.weathercode
-        });
+/// The configured provider first, then the other one as a fallback
+fn providers(config: &WeatherConfig) -> Vec<Box<dyn WeatherProvider>> {
+    match config.provider.trim() {
+        "wttr" | "wttr.in" => vec![Box::new(Wttr), Box::new(OpenMeteo)],
+        _ => vec![Box::new(OpenMeteo), Box::new(Wttr)],
+    }
+}
+
+struct CachedReport {
+    key: String,
+    fetched_at: Instant,
+    report: WeatherReport,
+}
+
+static CACHE: Mutex<Option<CachedReport>> = Mutex::new(None);
+
+/// Reports are only reused for the same place
+fn cache_key(config: &WeatherConfig) -> String {
+    format!("{}|{}|{}", config.location.trim().to_lowercase(), config.latitude, config.longitude)
+}
+
+/// Current conditions for `[weather]`: a report younger than `cache_minutes` is reused,
+/// otherwise each provider is asked in turn. When all of them fail, an older report for
+/// the same place is better than nothing.
+pub fn current(config: &WeatherConfig) -> Result<WeatherReport> {
+    let key = cache_key(config);
+    let max_age = Duration::from_secs(config.cache_minutes.saturating_mul(60));
+    if let Some(report) = cached(&key, Some(max_age)) {
+        return Ok(report);
+    }
+    let client = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+    let mut errors = Vec::new();
+    for provider in providers(config) {
+        match provider.current(&client, config) {
+            Ok(report) => {
+                if let Ok(mut cache) = CACHE.lock() {
+                    *cache = Some(CachedReport {
+                        key,
+                        fetched_at: Instant::now(),
+                        report: report.clone(),
+                    });
+                }
+                return Ok(report);
+            }
+            Err(error) => {
+                tracing::warn!(%error, provider = provider.name(), "weather provider failed");
+                errors.push(format!("{}: {}", provider.name(), error));
+            }
+        }
+    }
+    cached(&key, None).ok_or_else(|| eyre!("No weather provider answered ({})", errors.join("; ")))
+}
+
+fn cached(key: &str, max_age: Option<Duration>) -> Option<WeatherReport> {
+    let cache = CACHE.lock().ok()?;
+    let entry = cache.as_ref().filter(|entry| entry.key == key)?;
+    if max_age.is_some_and(|max_age| entry.fetched_at.elapsed() >= max_age) {
+        return None;
+    }
+    Some(entry.report.clone())
+}
+
+/// "Current weather in Prague: 12.5°C, light rain, wind 14 km/h (as of 2026-10-17T09:00)."
+#[must_use]
+pub fn format_report(report: &WeatherReport, units: WeatherUnits) -> String {
+    let (temperature, wind) = match units {
+        WeatherUnits::Metric => (
+            format!("{:.1}°C", report.temperature_c),
+            format!("{:.0} km/h", report.wind_kph),
+        ),
+        WeatherUnits::Imperial => (
+            format!("{:.1}°F", report.temperature_c * 9.0 / 5.0 + 32.0),
+            format!("{:.0} mph", report.wind_kph / 1.609_344),
+        ),
+    };
+    let conditions = report
+        .description
+        .as_deref()
+        .map(|description| format!("{}, ", description))
+        .unwrap_or_default();
+    format!(
+        "Current weather in {}: {}, {}wind {} (as of {}).",
+        report.location, temperature, conditions, wind, report.time
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> WeatherReport {
+        WeatherReport {
+            location: "Prague".to_string(),
+            time: "2026-10-17T09:00".to_string(),
+            temperature_c: 10.0,
+            wind_kph: 16.0934,
+            description: Some("light rain".to_string()),
+            provider: "open-meteo",
+        }
+    }
+
+    #[test]
+    fn test_reports_follow_unit_preference() {
+        assert_eq!(
+            format_report(&report(), WeatherUnits::Metric),
+            "Current weather in Prague: 10.0°C, light rain, wind 16 km/h (as of 2026-10-17T09:00)."
+        );
+        let without_description = WeatherReport {
+            description: None,
+            ..report()
+        };
+        assert_eq!(
+            format_report(&without_description, WeatherUnits::Imperial),
+            "Current weather in Prague: 50.0°F, wind 10 mph (as of 2026-10-17T09:00)."
+        );
+    }
 
-        Ok(summary.to_string())
+    #[test]
+    fn test_configured_provider_is_asked_first() {
+        let mut config = WeatherConfig::default();
+        let names = |config: &WeatherConfig| {
+            providers(config).iter().map(|provider| provider.name()).collect::<Vec<_>>()
+        };
+        assert_eq!(names(&config), vec!["open-meteo", "wttr"]);
+        config.provider = "wttr".to_string();
+        assert_eq!(names(&config), vec!["wttr", "open-meteo"]);
     }
 
-    pub fn weather_system_prompt(&self) -> Result<String> {
-        let weather_json = self.fetch_current_weather_json()?;
-        Ok(format!("Current weather (Prague): {}", weather_json))
+    #[test]
+    fn test_parses_wttr_conditions() {
+        let payload: WttrResponse = serde_json::from_str(
+            r#"{"current_condition":[{"temp_C":"7","windspeedKmph":"11",
+                "weatherDesc":[{"value":"Patchy rain nearby"}],
+                "localObsDateTime":"2026-10-17 09:00 AM"}]}"#,
+        )
+        .unwrap_or_else(|error| panic!("bad test payload: {error}"));
+        let Ok(report) = parse_wttr(payload, "Prague") else {
+            panic!("wttr payload should parse");
+        };
+        assert!((report.temperature_c - 7.0).abs() < f32::EPSILON);
+        assert_eq!(report.description.as_deref(), Some("patchy rain nearby"));
+        assert_eq!(report.provider, "wttr");
     }
 }
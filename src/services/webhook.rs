@@ -0,0 +1,45 @@
+use crate::config::NotificationsConfig;
+use color_eyre::Result;
+use reqwest::blocking::Client;
+use std::time::Duration;
+
+/// Sends a notification to the configured webhook.
+/// `ntfy` posts the message as plain text with a `Title` header;
+/// `json` posts `{"title", "message", "text"}` which suits Matrix/Slack hooks and the Telegram bot API.
+pub fn send_notification(config: &NotificationsConfig, title: &str, message: &str) -> Result<()> {
+    let url = config.webhook_url.trim();
+    if url.is_empty() {
+        return Ok(());
+    }
+
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let request = if config.webhook_format.eq_ignore_ascii_case("json") {
+        client.post(url).json(&serde_json::json!({
+            "title": title,
+            "message": message,
+            "text": format!("{}: {}", title, message),
+        }))
+    } else {
+        client
+            .post(url)
+            .header("Title", title)
+            .header("Tags", "kimi")
+            .body(message.to_string())
+    };
+
+    request.send()?.error_for_status()?;
+    Ok(())
+}
+
+/// Fires a notification on a background thread; failures are ignored so jobs never block on the webhook
+pub fn notify_in_background(config: &NotificationsConfig, title: &str, message: &str) {
+    if config.webhook_url.trim().is_empty() {
+        return;
+    }
+    let config = config.clone();
+    let title = title.to_string();
+    let message = message.to_string();
+    std::thread::spawn(move || {
+        let _ = send_notification(&config, &title, &message);
+    });
+}
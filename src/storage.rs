@@ -1,9 +1,73 @@
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use surrealdb::engine::local::{Db, RocksDb};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use surrealdb::engine::any::Any;
+use surrealdb::opt::auth;
 use surrealdb::sql::Thing;
 use surrealdb::Surreal;
+use crate::config::StorageConfig;
+use crate::services::encryption;
+use crate::services::link_download::DownloadStatus;
+use crate::services::notifications::NotificationKind;
+use crate::services::tool_policy::ToolDecision;
+use crate::services::projects::TaskStatus;
+
+/// Version of the last entry in `MIGRATIONS`; archives record it so a restore can tell
+/// whether it understands the dump
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// One ordered schema change, applied once per database and recorded in `schema_migration`
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub statements: &'static str,
+}
+
+/// Schema changes on top of the tables `init_db` defines, oldest first. `init_db` only ever
+/// adds tables and fields; changing a field type, an index or existing rows goes here.
+/// Append new steps with the next version and never edit one that has shipped.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        statements: "",
+    },
+    Migration {
+        version: 2,
+        name: "index messages by conversation",
+        statements: "DEFINE INDEX IF NOT EXISTS message_conversation ON message FIELDS conversation;",
+    },
+];
+
+/// Server password, preferred over `[storage] password`
+const PASSWORD_ENV: &str = "KIMI_SURREAL_PASSWORD";
+
+/// Written next to the embedded database while a kimi process has it open
+const PID_FILE_NAME: &str = "kimi.pid";
+
+/// `[storage]` settings, set once at startup; defaults to the embedded database
+static SETTINGS: OnceLock<StorageConfig> = OnceLock::new();
+/// Set when the user chose read-only after being told the database is in use
+static OPEN_SNAPSHOT: AtomicBool = AtomicBool::new(false);
+/// Read-only snapshots opened by this process, by live database path. Each is copied once
+/// and shared by every later `new()`; RocksDB cannot open the same copy twice.
+static SNAPSHOTS: tokio::sync::Mutex<Vec<(PathBuf, Surreal<Any>)>> = tokio::sync::Mutex::const_new(Vec::new());
+
+/// Keys written per batch when copying the database into a read-only snapshot
+const SNAPSHOT_BATCH_SIZE: usize = 10_000;
+
+/// Dimension of message embeddings (must match the MTREE index definition)
+const EMBEDDING_DIMENSION: usize = 1024;
+
+/// Characters of a recalled memory kept for the `/dashboard` list
+const RETRIEVAL_PREVIEW_CHARS: usize = 120;
+
+/// Nearest messages the MTREE index returns for a repeat-question lookup; role and
+/// conversation filters run on these, so it is larger than the handful checked for a reply
+const ANSWERED_QUESTION_NEIGHBOURS: usize = 20;
 
 /// Summary of a saved conversation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,7 +77,141 @@ pub struct ConversationSummary {
     pub summary: Option<String>,
     #[allow(dead_code)]
     pub detailed_summary: Option<String>,
+    /// User-set title; takes precedence over the generated summary
+    pub custom_title: Option<String>,
     pub created_at: String,
+    /// Hidden from the default history list; still searchable and retrievable
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub retention: Retention,
+    /// When an ephemeral conversation is deleted (RFC 3339)
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+/// Tables counted by `kimi data report`, with the label shown for each
+const DATA_REPORT_TABLES: &[(&str, &str)] = &[
+    ("conversation", "conversations"),
+    ("message", "messages"),
+    ("topic_mention", "topic mentions"),
+    ("snippet", "snippets"),
+    ("project_task", "project tasks"),
+    ("reminder", "reminders"),
+    ("due_task", "dated tasks"),
+    ("diary_entry", "diary entries"),
+    ("scheduled_job", "scheduled jobs"),
+    ("notification", "notifications"),
+    ("download", "downloads"),
+    ("tool_audit", "audited tool calls"),
+    ("tool_output", "saved tool outputs"),
+    ("feed_item", "feed items seen"),
+    ("usage", "usage rows"),
+    ("retrieval_stat", "recall statistics"),
+];
+
+/// Text fields searched by `kimi data purge`, and what a match there is called
+const PURGE_FIELDS: &[(&str, &str, PurgeCategory)] = &[
+    ("message", "content", PurgeCategory::Message),
+    ("message", "quoted_text", PurgeCategory::Message),
+    ("conversation", "summary", PurgeCategory::Summary),
+    ("conversation", "detailed_summary", PurgeCategory::Summary),
+    ("conversation", "custom_title", PurgeCategory::Summary),
+    ("conversation", "chunk_summaries", PurgeCategory::Summary),
+    ("topic_mention", "topic", PurgeCategory::Topic),
+    ("snippet", "title", PurgeCategory::Snippet),
+    ("snippet", "content", PurgeCategory::Snippet),
+    ("reminder", "text", PurgeCategory::Reminder),
+    ("due_task", "text", PurgeCategory::Reminder),
+    ("diary_entry", "content", PurgeCategory::Diary),
+    ("tool_audit", "arguments", PurgeCategory::ToolCall),
+    ("tool_audit", "result", PurgeCategory::ToolCall),
+    ("tool_output", "arguments", PurgeCategory::ToolCall),
+    ("tool_output", "content", PurgeCategory::ToolCall),
+    ("retrieval_stat", "preview", PurgeCategory::RecallStat),
+];
+
+/// What kind of record a purge match is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurgeCategory {
+    Message,
+    /// Conversation title and summaries; cleared instead of deleting the conversation
+    Summary,
+    Topic,
+    Snippet,
+    Reminder,
+    Diary,
+    ToolCall,
+    RecallStat,
+}
+
+impl PurgeCategory {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            PurgeCategory::Message => "message",
+            PurgeCategory::Summary => "summary",
+            PurgeCategory::Topic => "topic",
+            PurgeCategory::Snippet => "snippet",
+            PurgeCategory::Reminder => "reminder",
+            PurgeCategory::Diary => "diary entry",
+            PurgeCategory::ToolCall => "tool call",
+            PurgeCategory::RecallStat => "recall stat",
+        }
+    }
+}
+
+/// A stored record mentioning a purge keyword, listed before anything is deleted
+#[derive(Debug, Clone)]
+pub struct PurgeMatch {
+    pub id: Thing,
+    pub category: PurgeCategory,
+    /// The matching text, decrypted
+    pub text: String,
+}
+
+/// How long a conversation is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Retention {
+    /// Kept, and archived like any other after `[maintenance] archive_after_days`
+    #[default]
+    Normal,
+    /// Deleted by the maintenance task once `expires_at` passes
+    Ephemeral,
+    /// Never archived or expired
+    Forever,
+}
+
+impl Retention {
+    /// Value of the `retention` field; `None` for the default
+    #[must_use]
+    pub fn as_stored(self) -> Option<&'static str> {
+        match self {
+            Retention::Normal => None,
+            Retention::Ephemeral => Some("ephemeral"),
+            Retention::Forever => Some("forever"),
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: Option<&str>) -> Self {
+        match value {
+            Some("ephemeral") => Retention::Ephemeral,
+            Some("forever") => Retention::Forever,
+            _ => Retention::Normal,
+        }
+    }
+}
+
+impl ConversationSummary {
+    /// Custom title if set, otherwise the generated short summary
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.custom_title
+            .as_deref()
+            .filter(|title| !title.trim().is_empty())
+            .or(self.summary.as_deref())
+    }
 }
 
 /// A stored message from conversation history
@@ -23,6 +221,67 @@ pub struct StoredMessage {
     pub content: String,
     pub timestamp: String,
     pub display_name: Option<String>,
+    /// Role of the message this one replies to
+    #[serde(default)]
+    pub quoted_role: Option<String>,
+    /// Content of the message this one replies to
+    #[serde(default)]
+    pub quoted_text: Option<String>,
+    /// Model that generated an assistant reply
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub prompt_tokens: Option<u64>,
+    #[serde(default)]
+    pub completion_tokens: Option<u64>,
+    /// Pictures shown with the message (paths of generated or attached images)
+    #[serde(default)]
+    pub images: Vec<String>,
+    /// Paths of documents attached to the message
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+impl StoredMessage {
+    /// Generation metadata of an assistant reply, when it was recorded
+    #[must_use]
+    pub fn stats(&self) -> Option<MessageStats> {
+        Some(MessageStats {
+            model: self.model.clone()?,
+            duration_ms: self.duration_ms.unwrap_or_default(),
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+        })
+    }
+}
+
+/// How an assistant reply was generated: model, wall time and provider token counts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageStats {
+    pub model: String,
+    /// From sending the request to the final reply, tool rounds included
+    pub duration_ms: u64,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+}
+
+impl MessageStats {
+    /// "llama3.1 · 2.4s · 812→164 tok"
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut parts = vec![
+            self.model.clone(),
+            format!("{:.1}s", self.duration_ms as f64 / 1000.0),
+        ];
+        match (self.prompt_tokens, self.completion_tokens) {
+            (Some(prompt), Some(completion)) => parts.push(format!("{}→{} tok", prompt, completion)),
+            (None, Some(completion)) => parts.push(format!("{} tok", completion)),
+            (Some(_) | None, None) => {}
+        }
+        parts.join(" · ")
+    }
 }
 
 /// Message data for persistence
@@ -32,6 +291,11 @@ pub struct ConversationMessage {
     pub content: String,
     pub timestamp: String,
     pub display_name: Option<String>,
+    pub quoted_role: Option<String>,
+    pub quoted_text: Option<String>,
+    pub stats: Option<MessageStats>,
+    pub images: Vec<String>,
+    pub files: Vec<String>,
 }
 
 /// A conversation with its messages, used for date-range recall
@@ -46,6 +310,10 @@ pub struct ConversationData<'a> {
     pub agent_name: &'a str,
     pub summary: Option<&'a str>,
     pub detailed_summary: Option<&'a str>,
+    /// When the conversation took place (RFC 3339); defaults to now
+    pub created_at: Option<&'a str>,
+    /// Language code set with `/language`; `None` keeps the configured default
+    pub language: Option<&'a str>,
     pub messages: &'a [ConversationMessage],
 }
 
@@ -56,6 +324,8 @@ impl<'a> ConversationData<'a> {
             agent_name,
             summary: None,
             detailed_summary: None,
+            created_at: None,
+            language: None,
             messages,
         }
     }
@@ -70,6 +340,174 @@ impl<'a> ConversationData<'a> {
         self.detailed_summary = Some(summary);
         self
     }
+
+    pub fn with_language(mut self, language: &'a str) -> Self {
+        self.language = Some(language);
+        self
+    }
+}
+
+/// A saved code snippet
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    pub id: String,
+    pub title: String,
+    pub language: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+/// A task or milestone within a project
+#[derive(Debug, Clone)]
+pub struct ProjectTask {
+    pub id: String,
+    pub project: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub completed_at: Option<String>,
+}
+
+/// A commitment with a date found in a conversation, listed in `/tasks`
+#[derive(Debug, Clone)]
+pub struct DueTask {
+    pub id: String,
+    pub text: String,
+    /// YYYY-MM-DD, local time
+    pub due: String,
+    /// Proposed until the user accepts it in `/tasks`
+    pub status: TaskStatus,
+}
+
+/// An entry of the `/inbox` notification queue
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: String,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub message: String,
+    pub created_at: String,
+    pub read: bool,
+}
+
+/// A finished download shown in the `/downloads` history
+#[derive(Debug, Clone)]
+pub struct DownloadRecord {
+    pub id: String,
+    pub url: String,
+    pub status: DownloadStatus,
+    pub destination: Option<String>,
+    pub error: Option<String>,
+    pub finished_at: String,
+}
+
+/// One tool call the agent made, shown in `/audit`
+#[derive(Debug, Clone)]
+pub struct ToolAuditRecord {
+    pub tool: String,
+    /// Arguments as compact JSON
+    pub arguments: String,
+    pub decision: ToolDecision,
+    /// What the model was given back, shortened
+    pub result: String,
+    pub created_at: String,
+}
+
+/// Full text of a tool result the agent only saw summarized
+#[derive(Debug, Clone)]
+pub struct ToolOutputRecord {
+    /// Key the summary cites, e.g. "20261017143005123-0"
+    pub key: String,
+    pub tool: String,
+    pub arguments: String,
+    pub content: String,
+    pub summary: String,
+    /// Embedding of the summary, when the embeddings model was reachable
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// Tokens and estimated cost of one provider/model on one day, for the `/usage` report
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct UsageRecord {
+    /// YYYY-MM-DD, local time
+    pub day: String,
+    pub provider: String,
+    pub model: String,
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Message counts of one conversation, bucketed by week in `/dashboard`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConversationActivity {
+    /// RFC 3339
+    pub created_at: String,
+    pub messages: usize,
+    pub embedded: usize,
+}
+
+/// One day of Kimi's diary
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiaryEntry {
+    /// YYYY-MM-DD, local time
+    pub date: String,
+    pub content: String,
+}
+
+/// Tone of one conversation for the `/sentiment` timeline
+#[derive(Debug, Clone)]
+pub struct SentimentEntry {
+    pub title: String,
+    /// RFC 3339
+    pub created_at: String,
+    /// -1 (very negative) to 1 (very positive)
+    pub valence: f32,
+    /// The user's dominant emotions
+    pub emotions: Vec<String>,
+    /// Kimi's identity emotions when the conversation was saved
+    pub kimi_emotions: Vec<String>,
+}
+
+/// A memory and how often recall has put it into a prompt
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetrievalCount {
+    pub preview: String,
+    pub count: u64,
+    pub last_retrieved_at: String,
+}
+
+/// A reminder added from a date in a chat message
+#[derive(Debug, Clone, Deserialize)]
+pub struct Reminder {
+    /// YYYY-MM-DD, local time
+    pub due: String,
+    pub text: String,
+}
+
+/// Full-text search hit together with the conversation it belongs to
+#[derive(Debug, Clone)]
+pub struct MessageSearchHit {
+    pub conversation_id: String,
+    /// Custom title, or the generated summary
+    pub conversation_title: Option<String>,
+    pub conversation_created_at: String,
+    pub agent_name: String,
+    pub role: String,
+    pub content: String,
+    pub timestamp: String,
+    pub score: f32,
+}
+
+/// A past question and the reply it got, offered before asking again
+#[derive(Debug, Clone)]
+pub struct AnsweredQuestion {
+    pub conversation_id: String,
+    /// When the conversation it was asked in started (RFC 3339)
+    pub asked_at: String,
+    pub question: String,
+    pub answer: String,
+    pub similarity: f32,
 }
 
 /// Retrieved message with fused relevance score
@@ -78,11 +516,21 @@ pub struct RetrievedMessage {
     pub content: String,
     pub role: String,
     pub timestamp: String,
+    /// Who wrote it, when a speaker was set with `/as`
+    pub display_name: Option<String>,
     pub similarity: f32,
     pub score: f32,
     pub source: RetrievalSource,
 }
 
+impl RetrievedMessage {
+    /// Label for prompts: the speaker's name, or the role
+    #[must_use]
+    pub fn speaker(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.role)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RetrievalSource {
     Dense,
@@ -111,6 +559,38 @@ struct MessageRecord {
     embedding: Option<Vec<f32>>,
     timestamp: String,
     display_name: Option<String>,
+    quoted_role: Option<String>,
+    quoted_text: Option<String>,
+    model: Option<String>,
+    duration_ms: Option<u64>,
+    prompt_tokens: Option<u64>,
+    completion_tokens: Option<u64>,
+    images: Option<Vec<String>>,
+    files: Option<Vec<String>>,
+}
+
+impl MessageRecord {
+    /// A new row for `message` of `conversation`, sealed when encryption is on
+    fn new(conversation: Thing, message: &ConversationMessage) -> Self {
+        let stats = message.stats.as_ref();
+        Self {
+            id: None,
+            conversation,
+            role: message.role.clone(),
+            content: encryption::seal(&message.content),
+            embedding: None,
+            timestamp: message.timestamp.clone(),
+            display_name: message.display_name.clone(),
+            quoted_role: message.quoted_role.clone(),
+            quoted_text: message.quoted_text.as_deref().map(encryption::seal),
+            model: stats.map(|stats| stats.model.clone()),
+            duration_ms: stats.map(|stats| stats.duration_ms),
+            prompt_tokens: stats.and_then(|stats| stats.prompt_tokens),
+            completion_tokens: stats.and_then(|stats| stats.completion_tokens),
+            images: (!message.images.is_empty()).then(|| message.images.clone()),
+            files: (!message.files.is_empty()).then(|| message.files.clone()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -126,6 +606,19 @@ struct ConversationRecord {
     agent_name: String,
     summary: Option<String>,
     detailed_summary: Option<String>,
+    #[serde(default)]
+    custom_title: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    archived: Option<bool>,
+    #[serde(default)]
+    retention: Option<String>,
+    #[serde(default)]
+    expires_at: Option<String>,
+    /// Summaries of the parts of a long conversation, one per line
+    #[serde(default)]
+    chunk_summaries: Option<String>,
     created_at: String,
     updated_at: String,
 }
@@ -133,23 +626,193 @@ struct ConversationRecord {
 /// Manages persistent storage of conversations using SurrealDB
 #[derive(Clone)]
 pub struct StorageManager {
-    db: Surreal<Db>,
+    db: Surreal<Any>,
+    /// Opened from a snapshot because another process holds the embedded database;
+    /// writes land in the snapshot and are dropped on exit
+    read_only: bool,
 }
 
 impl StorageManager {
-    /// Creates a new storage manager and initializes the database
+    /// Chooses the embedded database or a SurrealDB server for every later `new()`
+    pub fn configure(settings: &StorageConfig) {
+        let _ = SETTINGS.set(settings.clone());
+    }
+
+    /// Creates a new storage manager, initializes the database and applies pending migrations
     pub async fn new() -> Result<Self> {
+        let manager = Self::open_unmigrated().await?;
+        for migration in manager.run_migrations(false).await? {
+            tracing::info!(version = migration.version, name = migration.name, "applied schema migration");
+        }
+        Ok(manager)
+    }
+
+    /// Opens the database with the baseline tables only, so `kimi migrate --dry-run`
+    /// can list what would change
+    pub async fn open_unmigrated() -> Result<Self> {
+        let settings = SETTINGS.get().cloned().unwrap_or_default();
+        let (db, read_only) = if settings.url.trim().is_empty() {
+            Self::connect_embedded(&settings).await?
+        } else {
+            (Self::connect_remote(&settings).await?, false)
+        };
+        db.use_ns(&settings.namespace).use_db(crate::profile::database_name()).await?;
+
+        let manager = Self { db, read_only };
+        manager.init_db().await?;
+
+        Ok(manager)
+    }
+
+    /// True when opening the embedded database fails because another process holds its
+    /// RocksDB lock. The database is closed again before this returns.
+    #[must_use]
+    pub fn is_locked_elsewhere() -> bool {
+        let settings = SETTINGS.get().cloned().unwrap_or_default();
+        if !settings.url.trim().is_empty() {
+            return false;
+        }
+        let Ok(db_path) = Self::project_data_dir().map(|dir| dir.join("kimi.db")) else {
+            return false;
+        };
+        if !db_path.exists() {
+            return false;
+        }
+        let Ok(runtime) = tokio::runtime::Runtime::new() else {
+            return false;
+        };
+        // Dropping the runtime with the connection shuts the engine down and releases the lock
+        runtime.block_on(async {
+            match surrealdb::engine::any::connect(format!("rocksdb://{}", db_path.display())).await {
+                Ok(_) => false,
+                Err(error) => is_lock_error(&error),
+            }
+        })
+    }
+
+    /// PID of another running kimi that has the embedded database open, from its pid file.
+    /// Only a hint for messages: the file can be stale or belong to a reused PID.
+    #[must_use]
+    pub fn lock_holder() -> Option<u32> {
+        let settings = SETTINGS.get().cloned().unwrap_or_default();
+        if !settings.url.trim().is_empty() {
+            return None;
+        }
+        let path = Self::project_data_dir().ok()?.join(PID_FILE_NAME);
+        let pid: u32 = std::fs::read_to_string(path).ok()?.trim().parse().ok()?;
+        (pid != std::process::id() && process_alive(pid)).then_some(pid)
+    }
+
+    /// Makes every later `new()` open a read-only snapshot instead of the live database
+    pub fn open_read_only() {
+        OPEN_SNAPSHOT.store(true, Ordering::Relaxed);
+    }
+
+    /// Removes this process's pid file and read-only snapshot on exit
+    pub fn release_lock_file() {
+        let _ = std::fs::remove_dir_all(snapshot_dir());
+        let Ok(path) = Self::project_data_dir().map(|dir| dir.join(PID_FILE_NAME)) else {
+            return;
+        };
+        let ours = std::fs::read_to_string(&path)
+            .is_ok_and(|content| content.trim() == std::process::id().to_string());
+        if ours {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// True when this is a throwaway snapshot of a database another kimi has open
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    async fn connect_embedded(settings: &StorageConfig) -> Result<(Surreal<Any>, bool)> {
         let project_data_dir = Self::project_data_dir()?;
         std::fs::create_dir_all(&project_data_dir)?;
         let db_path = project_data_dir.join("kimi.db");
 
-        let db = Surreal::new::<RocksDb>(db_path).await?;
-        db.use_ns("kimi").use_db("main").await?;
+        if OPEN_SNAPSHOT.load(Ordering::Relaxed) {
+            return Ok((Self::connect_snapshot(&db_path).await?, true));
+        }
+        match surrealdb::engine::any::connect(format!("rocksdb://{}", db_path.display())).await {
+            Ok(db) => {
+                let _ = std::fs::write(project_data_dir.join(PID_FILE_NAME), std::process::id().to_string());
+                Ok((db, false))
+            }
+            Err(error) if is_lock_error(&error) => {
+                if settings.on_locked.trim() == "read-only" {
+                    tracing::warn!(%error, "database locked by another process, opening a read-only snapshot");
+                    return Ok((Self::connect_snapshot(&db_path).await?, true));
+                }
+                let holder = Self::lock_holder()
+                    .map_or_else(|| "another process".to_string(), |pid| format!("kimi (PID {})", pid));
+                Err(color_eyre::eyre::eyre!(
+                    "The database is in use by {}. Close it, or set [storage] on_locked = \"read-only\" to open a snapshot.",
+                    holder
+                ))
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
 
-        let manager = Self { db };
-        manager.init_db().await?;
+    async fn connect_snapshot(db_path: &Path) -> Result<Surreal<Any>> {
+        let mut snapshots = SNAPSHOTS.lock().await;
+        if let Some((_, db)) = snapshots.iter().find(|(path, _)| path == db_path) {
+            return Ok(db.clone());
+        }
+        // One copy per profile, so switching profiles never deletes a snapshot still in use
+        let snapshot = snapshot_dir().join(crate::profile::database_name());
+        let _ = std::fs::remove_dir_all(&snapshot);
+        snapshot_database(db_path, &snapshot)?;
+        let db = surrealdb::engine::any::connect(format!("rocksdb://{}", snapshot.display())).await?;
+        snapshots.push((db_path.to_path_buf(), db.clone()));
+        Ok(db)
+    }
 
-        Ok(manager)
+    async fn connect_remote(settings: &StorageConfig) -> Result<Surreal<Any>> {
+        let db = surrealdb::engine::any::connect(settings.url.trim()).await.map_err(|error| {
+            color_eyre::eyre::eyre!("Could not reach SurrealDB at {}: {}", settings.url.trim(), error)
+        })?;
+        let username = settings.username.trim();
+        if username.is_empty() {
+            return Ok(db);
+        }
+        let password = std::env::var(PASSWORD_ENV).unwrap_or_else(|_| settings.password.clone());
+        let database = crate::profile::database_name();
+        match settings.auth_level.trim() {
+            "namespace" => {
+                db.signin(auth::Namespace {
+                    namespace: &settings.namespace,
+                    username,
+                    password: &password,
+                })
+                .await?;
+            }
+            "database" => {
+                db.signin(auth::Database {
+                    namespace: &settings.namespace,
+                    database: &database,
+                    username,
+                    password: &password,
+                })
+                .await?;
+            }
+            "root" | "" => {
+                db.signin(auth::Root {
+                    username,
+                    password: &password,
+                })
+                .await?;
+            }
+            other => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Unknown [storage] auth_level '{}' (root, namespace or database)",
+                    other
+                ));
+            }
+        }
+        Ok(db)
     }
 
     async fn init_db(&self) -> Result<()> {
@@ -159,6 +822,15 @@ impl StorageManager {
             DEFINE FIELD agent_name ON conversation TYPE string;
             DEFINE FIELD summary ON conversation TYPE option<string>;
             DEFINE FIELD detailed_summary ON conversation TYPE option<string>;
+            DEFINE FIELD custom_title ON conversation TYPE option<string>;
+            DEFINE FIELD language ON conversation TYPE option<string>;
+            DEFINE FIELD archived ON conversation TYPE option<bool>;
+            DEFINE FIELD retention ON conversation TYPE option<string>;
+            DEFINE FIELD expires_at ON conversation TYPE option<string>;
+            DEFINE FIELD chunk_summaries ON conversation TYPE option<string>;
+            DEFINE FIELD sentiment ON conversation TYPE option<float>;
+            DEFINE FIELD emotions ON conversation TYPE option<array<string>>;
+            DEFINE FIELD kimi_emotions ON conversation TYPE option<array<string>>;
             DEFINE FIELD created_at ON conversation TYPE string;
             DEFINE FIELD updated_at ON conversation TYPE string;
         ").await?;
@@ -174,6 +846,14 @@ impl StorageManager {
             DEFINE FIELD embedding ON message TYPE option<array<float>>;
             DEFINE FIELD timestamp ON message TYPE string;
             DEFINE FIELD display_name ON message TYPE option<string>;
+            DEFINE FIELD quoted_role ON message TYPE option<string>;
+            DEFINE FIELD quoted_text ON message TYPE option<string>;
+            DEFINE FIELD model ON message TYPE option<string>;
+            DEFINE FIELD duration_ms ON message TYPE option<int>;
+            DEFINE FIELD prompt_tokens ON message TYPE option<int>;
+            DEFINE FIELD completion_tokens ON message TYPE option<int>;
+            DEFINE FIELD images ON message TYPE option<array<string>>;
+            DEFINE FIELD files ON message TYPE option<array<string>>;
 
             DEFINE INDEX IF NOT EXISTS idx_msg_embedding ON message
                 FIELDS embedding MTREE DIMENSION 1024 DIST COSINE;
@@ -189,12 +869,239 @@ impl StorageManager {
             DEFINE FIELD created_at ON topic_mention TYPE string;
         ").await?;
 
+        // Define topic_embedding table so synonymous topics share one name
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS topic_embedding SCHEMAFULL;
+            DEFINE FIELD topic ON topic_embedding TYPE string;
+            DEFINE FIELD embedding ON topic_embedding TYPE array<float>;
+            DEFINE INDEX IF NOT EXISTS topic_embedding_topic ON topic_embedding FIELDS topic UNIQUE;
+        ").await?;
+
+        // Define snippet table for saved code blocks
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS snippet SCHEMAFULL;
+            DEFINE FIELD title ON snippet TYPE string;
+            DEFINE FIELD language ON snippet TYPE string;
+            DEFINE FIELD content ON snippet TYPE string;
+            DEFINE FIELD created_at ON snippet TYPE string;
+        ").await?;
+
+        // Define project_task table for project goals and milestones
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS project_task SCHEMAFULL;
+            DEFINE FIELD project ON project_task TYPE string;
+            DEFINE FIELD title ON project_task TYPE string;
+            DEFINE FIELD status ON project_task TYPE string;
+            DEFINE FIELD created_at ON project_task TYPE string;
+            DEFINE FIELD completed_at ON project_task TYPE option<string>;
+            DEFINE INDEX IF NOT EXISTS project_task_project ON project_task FIELDS project;
+        ").await?;
+
+        // Define feed_item table so briefings skip items already seen
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS feed_item SCHEMAFULL;
+            DEFINE FIELD key ON feed_item TYPE string;
+            DEFINE FIELD feed ON feed_item TYPE string;
+            DEFINE FIELD title ON feed_item TYPE string;
+            DEFINE FIELD seen_at ON feed_item TYPE string;
+            DEFINE INDEX IF NOT EXISTS feed_item_key ON feed_item FIELDS key UNIQUE;
+        ").await?;
+
+        // Define notification table for the inbox
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS notification SCHEMAFULL;
+            DEFINE FIELD kind ON notification TYPE string;
+            DEFINE FIELD title ON notification TYPE string;
+            DEFINE FIELD message ON notification TYPE string;
+            DEFINE FIELD created_at ON notification TYPE string;
+            DEFINE FIELD read ON notification TYPE bool;
+        ").await?;
+
+        // Define download table for the download manager history
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS download SCHEMAFULL;
+            DEFINE FIELD url ON download TYPE string;
+            DEFINE FIELD status ON download TYPE string;
+            DEFINE FIELD destination ON download TYPE option<string>;
+            DEFINE FIELD error ON download TYPE option<string>;
+            DEFINE FIELD finished_at ON download TYPE string;
+        ").await?;
+
+        // Define usage table: one row per day, provider and model
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS usage SCHEMAFULL;
+            DEFINE FIELD day ON usage TYPE string;
+            DEFINE FIELD provider ON usage TYPE string;
+            DEFINE FIELD model ON usage TYPE string;
+            DEFINE FIELD requests ON usage TYPE int;
+            DEFINE FIELD prompt_tokens ON usage TYPE int;
+            DEFINE FIELD completion_tokens ON usage TYPE int;
+            DEFINE FIELD cost_usd ON usage TYPE float;
+            DEFINE INDEX IF NOT EXISTS usage_day ON usage FIELDS day;
+        ").await?;
+
+        // Define retrieval_stat table: how often each memory was recalled
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS retrieval_stat SCHEMAFULL;
+            DEFINE FIELD preview ON retrieval_stat TYPE string;
+            DEFINE FIELD count ON retrieval_stat TYPE int;
+            DEFINE FIELD last_retrieved_at ON retrieval_stat TYPE string;
+        ").await?;
+
+        // Define schema_migration table: one row per applied migration
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS schema_migration SCHEMAFULL;
+            DEFINE FIELD version ON schema_migration TYPE int;
+            DEFINE FIELD name ON schema_migration TYPE string;
+            DEFINE FIELD applied_at ON schema_migration TYPE string;
+        ").await?;
+
+        // Define tool_audit table: every tool call the agent made
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS tool_audit SCHEMAFULL;
+            DEFINE FIELD tool ON tool_audit TYPE string;
+            DEFINE FIELD arguments ON tool_audit TYPE string;
+            DEFINE FIELD decision ON tool_audit TYPE string;
+            DEFINE FIELD result ON tool_audit TYPE string;
+            DEFINE FIELD created_at ON tool_audit TYPE string;
+            DEFINE INDEX IF NOT EXISTS tool_audit_created ON tool_audit FIELDS created_at;
+        ").await?;
+
+        // Define tool_output table: full text of long tool results the agent saw summarized
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS tool_output SCHEMAFULL;
+            DEFINE FIELD tool ON tool_output TYPE string;
+            DEFINE FIELD arguments ON tool_output TYPE string;
+            DEFINE FIELD content ON tool_output TYPE string;
+            DEFINE FIELD summary ON tool_output TYPE string;
+            DEFINE FIELD embedding ON tool_output TYPE option<array<float>>;
+            DEFINE FIELD created_at ON tool_output TYPE string;
+        ").await?;
+
+        // Define reminder table for dates picked from chat messages
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS reminder SCHEMAFULL;
+            DEFINE FIELD due ON reminder TYPE string;
+            DEFINE FIELD text ON reminder TYPE string;
+            DEFINE FIELD created_at ON reminder TYPE string;
+            DEFINE FIELD delivered ON reminder TYPE bool;
+            DEFINE INDEX IF NOT EXISTS reminder_due ON reminder FIELDS due;
+        ").await?;
+
+        // Define due_task table for dated commitments found in saved conversations
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS due_task SCHEMAFULL;
+            DEFINE FIELD text ON due_task TYPE string;
+            DEFINE FIELD due ON due_task TYPE string;
+            DEFINE FIELD status ON due_task TYPE string;
+            DEFINE FIELD conversation_id ON due_task TYPE option<string>;
+            DEFINE FIELD created_at ON due_task TYPE string;
+            DEFINE FIELD completed_at ON due_task TYPE option<string>;
+            DEFINE FIELD notified ON due_task TYPE bool;
+            DEFINE INDEX IF NOT EXISTS due_task_due ON due_task FIELDS due;
+        ").await?;
+
+        // Define diary_entry table for Kimi's daily diary, one record per day
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS diary_entry SCHEMAFULL;
+            DEFINE FIELD date ON diary_entry TYPE string;
+            DEFINE FIELD content ON diary_entry TYPE string;
+            DEFINE FIELD updated_at ON diary_entry TYPE string;
+            DEFINE INDEX IF NOT EXISTS diary_entry_date ON diary_entry FIELDS date UNIQUE;
+        ").await?;
+
+        // Define scheduled_job table for jobs added with `/schedule`, and job_run for when
+        // each job (from config or `/schedule`) last ran
+        self.db.query("
+            DEFINE TABLE IF NOT EXISTS scheduled_job SCHEMAFULL;
+            DEFINE FIELD name ON scheduled_job TYPE string;
+            DEFINE FIELD at ON scheduled_job TYPE string;
+            DEFINE FIELD days ON scheduled_job TYPE string;
+            DEFINE FIELD sources ON scheduled_job TYPE array<string>;
+            DEFINE FIELD prompt ON scheduled_job TYPE string;
+            DEFINE FIELD created_at ON scheduled_job TYPE string;
+
+            DEFINE TABLE IF NOT EXISTS job_run SCHEMAFULL;
+            DEFINE FIELD job ON job_run TYPE string;
+            DEFINE FIELD ran_at ON job_run TYPE string;
+            DEFINE INDEX IF NOT EXISTS job_run_job ON job_run FIELDS job UNIQUE;
+        ").await?;
+
+        Ok(())
+    }
+
+    /// Dumps this profile's tables and records as SurrealQL
+    pub async fn export_database(&self, path: &Path) -> Result<()> {
+        self.db.export(path.to_path_buf()).await?;
+        Ok(())
+    }
+
+    /// Drops this profile's database and rebuilds it from an `export_database` dump;
+    /// tables added since the dump was made are defined again afterwards
+    pub async fn replace_database(&self, path: &Path) -> Result<()> {
+        let name = crate::profile::database_name();
+        self.db.query(format!("REMOVE DATABASE IF EXISTS `{}`", name)).await?.check()?;
+        self.db.use_db(&name).await?;
+        self.db.import(path).await?;
+        self.init_db().await?;
+        self.run_migrations(false).await?;
         Ok(())
     }
 
+    /// Highest migration recorded for this database, 0 before any ran
+    pub async fn schema_version(&self) -> Result<u32> {
+        #[derive(Debug, Deserialize)]
+        struct VersionRow {
+            version: u32,
+        }
+
+        let mut response = self.db
+            .query("SELECT version FROM schema_migration ORDER BY version DESC LIMIT 1")
+            .await?;
+        let rows: Vec<VersionRow> = response.take(0)?;
+        Ok(rows.first().map_or(0, |row| row.version))
+    }
+
+    /// Applies migrations newer than `schema_version` in order, each in its own transaction,
+    /// and returns them; with `dry_run` only returns what would run
+    pub async fn run_migrations(&self, dry_run: bool) -> Result<Vec<&'static Migration>> {
+        let current = self.schema_version().await?;
+        let pending: Vec<&'static Migration> = MIGRATIONS
+            .iter()
+            .filter(|migration| migration.version > current)
+            .collect();
+        if dry_run {
+            return Ok(pending);
+        }
+        for migration in &pending {
+            self.db
+                .query(format!(
+                    "BEGIN TRANSACTION;
+                    {}
+                    CREATE $id SET version = $version, name = $name, applied_at = $now;
+                    COMMIT TRANSACTION;",
+                    migration.statements
+                ))
+                .bind(("id", Thing::from(("schema_migration", migration.version.to_string().as_str()))))
+                .bind(("version", migration.version))
+                .bind(("name", migration.name))
+                .bind(("now", chrono::Local::now().to_rfc3339()))
+                .await?
+                .check()
+                .map_err(|error| {
+                    color_eyre::eyre::eyre!(
+                        "Schema migration {} ({}) failed: {}",
+                        migration.version,
+                        migration.name,
+                        error
+                    )
+                })?;
+        }
+        Ok(pending)
+    }
+
     fn project_data_dir() -> Result<PathBuf> {
-        let current_dir = std::env::current_dir()?;
-        Ok(current_dir.join("data"))
+        crate::profile::data_dir()
     }
 
     fn normalize_conversation_id(id: &str) -> &str {
@@ -215,9 +1122,15 @@ impl StorageManager {
             .content(ConversationRecord {
                 id: None,
                 agent_name: data.agent_name.to_string(),
-                summary: data.summary.map(str::to_string),
-                detailed_summary: data.detailed_summary.map(str::to_string),
-                created_at: now.clone(),
+                summary: data.summary.map(encryption::seal),
+                detailed_summary: data.detailed_summary.map(encryption::seal),
+                custom_title: None,
+                language: data.language.map(str::to_string),
+                archived: None,
+                retention: None,
+                expires_at: None,
+                chunk_summaries: None,
+                created_at: data.created_at.map_or_else(|| now.clone(), str::to_string),
                 updated_at: now,
             })
             .await?;
@@ -231,15 +1144,7 @@ impl StorageManager {
         for message in data.messages {
             let _: Option<MessageRecord> = self.db
                 .create("message")
-                .content(MessageRecord {
-                    id: None,
-                    conversation: conversation_ref.clone(),
-                    role: message.role.clone(),
-                    content: message.content.clone(),
-                    embedding: None,
-                    timestamp: message.timestamp.clone(),
-                    display_name: message.display_name.clone(),
-                })
+                .content(MessageRecord::new(conversation_ref.clone(), message))
                 .await?;
         }
 
@@ -256,7 +1161,7 @@ impl StorageManager {
         };
         let conversation_ref = Self::conversation_ref(update.conversation_id);
         let role = update.role.to_string();
-        let content = update.content.to_string();
+        let content = encryption::seal(update.content);
         let timestamp = update.timestamp.to_string();
 
         // Use IS NULL check for display_name since NULL = NULL returns NULL, not TRUE
@@ -321,7 +1226,13 @@ impl StorageManager {
         .await?;
 
         let results: Vec<MessageEmbeddingCandidate> = response.take(0)?;
-        Ok(results)
+        Ok(results
+            .into_iter()
+            .map(|candidate| MessageEmbeddingCandidate {
+                content: encryption::open(candidate.content),
+                ..candidate
+            })
+            .collect())
     }
 
     /// Returns count of messages missing embeddings (for opportunistic backfill)
@@ -364,6 +1275,66 @@ impl StorageManager {
         Ok((total, with_embedding))
     }
 
+    /// Deletes messages whose conversation record no longer exists
+    pub async fn delete_orphaned_messages(&self) -> Result<usize> {
+        #[derive(Debug, Deserialize)]
+        struct DeletedRow {
+            #[allow(dead_code)]
+            id: Thing,
+        }
+
+        let mut response = self.db.query("
+            DELETE FROM message
+            WHERE conversation.created_at IS NONE
+            RETURN BEFORE
+        ").await?;
+        let deleted: Vec<DeletedRow> = response.take(0)?;
+        Ok(deleted.len())
+    }
+
+    /// Clears embeddings whose dimension no longer matches the index so backfill regenerates them
+    pub async fn clear_stale_embeddings(&self) -> Result<usize> {
+        #[derive(Debug, Deserialize)]
+        struct UpdatedRow {
+            #[allow(dead_code)]
+            id: Thing,
+        }
+
+        let mut response = self.db.query("
+            UPDATE message
+            SET embedding = NONE
+            WHERE embedding IS NOT NONE AND array::len(embedding) != $dimension
+            RETURN BEFORE
+        ")
+        .bind(("dimension", EMBEDDING_DIMENSION))
+        .await?;
+        let updated: Vec<UpdatedRow> = response.take(0)?;
+        Ok(updated.len())
+    }
+
+    /// Rebuilds the MTREE (embedding) and BM25 (content) indexes from scratch
+    pub async fn rebuild_message_indexes(&self) -> Result<()> {
+        self.db.query("
+            REBUILD INDEX IF EXISTS idx_msg_embedding ON message;
+            REBUILD INDEX IF EXISTS idx_msg_content ON message;
+        ").await?.check()?;
+        Ok(())
+    }
+
+    /// Returns one stored embedding, used to sample vector query latency
+    pub async fn load_sample_embedding(&self) -> Result<Option<Vec<f32>>> {
+        #[derive(Debug, Deserialize)]
+        struct EmbeddingRow {
+            embedding: Vec<f32>,
+        }
+
+        let mut response = self.db.query("
+            SELECT embedding FROM message WHERE embedding IS NOT NONE LIMIT 1
+        ").await?;
+        let rows: Vec<EmbeddingRow> = response.take(0)?;
+        Ok(rows.into_iter().next().map(|row| row.embedding))
+    }
+
     /// Searches for similar messages using vector similarity
     pub async fn search_similar_messages(
         &self,
@@ -375,6 +1346,7 @@ impl StorageManager {
             content: String,
             role: String,
             timestamp: String,
+            display_name: Option<String>,
             similarity: f32,
         }
 
@@ -383,6 +1355,7 @@ impl StorageManager {
                 content,
                 role,
                 timestamp,
+                display_name,
                 vector::similarity::cosine(embedding, $query_embedding) AS similarity
             FROM message
             WHERE embedding IS NOT NONE
@@ -398,9 +1371,10 @@ impl StorageManager {
         Ok(results
             .into_iter()
             .map(|r| RetrievedMessage {
-                content: r.content,
+                content: encryption::open(r.content),
                 role: r.role,
                 timestamp: r.timestamp,
+                display_name: r.display_name,
                 similarity: r.similarity,
                 score: r.similarity,
                 source: RetrievalSource::Dense,
@@ -408,16 +1382,90 @@ impl StorageManager {
             .collect())
     }
 
-    pub async fn search_keyword_messages(
+    /// The most similar question asked in another conversation that got an answer,
+    /// when it reaches `min_similarity`
+    pub async fn find_answered_question(
         &self,
-        query: &str,
-        limit: usize,
-    ) -> Result<Vec<RetrievedMessage>> {
+        query_embedding: Vec<f32>,
+        exclude_conversation: Option<&str>,
+        min_similarity: f32,
+    ) -> Result<Option<AnsweredQuestion>> {
+        #[derive(Debug, Deserialize)]
+        struct QuestionRow {
+            conversation: Thing,
+            created_at: Option<String>,
+            content: String,
+            timestamp: String,
+            similarity: f32,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct ReplyRow {
+            role: String,
+            content: String,
+        }
+
+        // `<|k|>` is answered by the MTREE index instead of scoring every message
+        let mut response = self.db.query(format!("
+            SELECT
+                conversation,
+                conversation.created_at AS created_at,
+                content,
+                timestamp,
+                vector::similarity::cosine(embedding, $query_embedding) AS similarity
+            FROM message
+            WHERE role = \"User\" AND conversation != $exclude
+                AND embedding <|{}|> $query_embedding
+            ORDER BY similarity DESC
+            LIMIT 5
+        ", ANSWERED_QUESTION_NEIGHBOURS))
+        .bind(("query_embedding", query_embedding))
+        .bind(("exclude", exclude_conversation.map(Self::conversation_ref)))
+        .await?;
+        let questions: Vec<QuestionRow> = response.take(0)?;
+
+        for question in questions {
+            if question.similarity < min_similarity {
+                break;
+            }
+            // Only reuse the reply that directly followed the question
+            let mut response = self.db.query("
+                SELECT role, content, timestamp
+                FROM message
+                WHERE conversation = $conversation AND timestamp > $timestamp
+                ORDER BY timestamp ASC
+                LIMIT 1
+            ")
+            .bind(("conversation", question.conversation.clone()))
+            .bind(("timestamp", question.timestamp.clone()))
+            .await?;
+            let replies: Vec<ReplyRow> = response.take(0)?;
+            if let Some(reply) = replies.into_iter().next()
+                && reply.role == "Assistant"
+            {
+                return Ok(Some(AnsweredQuestion {
+                    conversation_id: question.conversation.to_string(),
+                    asked_at: question.created_at.unwrap_or_default(),
+                    question: encryption::open(question.content),
+                    answer: encryption::open(reply.content),
+                    similarity: question.similarity,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn search_keyword_messages(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RetrievedMessage>> {
         #[derive(Debug, Deserialize)]
         struct SearchResult {
             content: String,
             role: String,
             timestamp: String,
+            display_name: Option<String>,
             score: f32,
         }
 
@@ -427,6 +1475,7 @@ impl StorageManager {
                 content,
                 role,
                 timestamp,
+                display_name,
                 search::score(1) AS score
             FROM message
             WHERE content @@ $query
@@ -442,9 +1491,10 @@ impl StorageManager {
         Ok(results
             .into_iter()
             .map(|r| RetrievedMessage {
-                content: r.content,
+                content: encryption::open(r.content),
                 role: r.role,
                 timestamp: r.timestamp,
+                display_name: r.display_name,
                 similarity: 0.0,
                 score: r.score,
                 source: RetrievalSource::Sparse,
@@ -452,12 +1502,125 @@ impl StorageManager {
             .collect())
     }
 
+    /// Messages written under a speaker name (case-insensitive), most similar to
+    /// `query_embedding` first, or newest first without one
+    pub async fn search_speaker_messages(
+        &self,
+        speaker: &str,
+        query_embedding: Option<Vec<f32>>,
+        limit: usize,
+    ) -> Result<Vec<RetrievedMessage>> {
+        #[derive(Debug, Deserialize)]
+        struct SearchResult {
+            content: String,
+            role: String,
+            timestamp: String,
+            display_name: Option<String>,
+            similarity: f32,
+        }
+
+        let query = if query_embedding.is_some() {
+            "SELECT content, role, timestamp, display_name,
+                vector::similarity::cosine(embedding, $query_embedding) AS similarity
+            FROM message
+            WHERE display_name != NONE AND string::lowercase(display_name) = $speaker
+                AND embedding IS NOT NONE
+            ORDER BY similarity DESC
+            LIMIT $limit"
+        } else {
+            "SELECT content, role, timestamp, display_name, 0.0 AS similarity
+            FROM message
+            WHERE display_name != NONE AND string::lowercase(display_name) = $speaker
+            ORDER BY timestamp DESC
+            LIMIT $limit"
+        };
+        let mut response = self.db.query(query)
+            .bind(("speaker", speaker.to_lowercase()))
+            .bind(("query_embedding", query_embedding.unwrap_or_default()))
+            .bind(("limit", limit))
+            .await?;
+
+        let results: Vec<SearchResult> = response.take(0)?;
+        Ok(results
+            .into_iter()
+            .map(|r| RetrievedMessage {
+                content: encryption::open(r.content),
+                role: r.role,
+                timestamp: r.timestamp,
+                display_name: r.display_name,
+                similarity: r.similarity,
+                score: r.similarity,
+                source: RetrievalSource::Dense,
+            })
+            .collect())
+    }
+
+    /// Ranks messages across all conversations with the BM25 content index
+    pub async fn search_messages(&self, query: &str, limit: usize) -> Result<Vec<MessageSearchHit>> {
+        if encryption::is_active() {
+            return Err(color_eyre::eyre::eyre!(
+                "Full-text search is unavailable while database encryption is active"
+            ));
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct HitRow {
+            conversation: Thing,
+            custom_title: Option<String>,
+            summary: Option<String>,
+            created_at: Option<String>,
+            agent_name: Option<String>,
+            role: String,
+            content: String,
+            timestamp: String,
+            score: f32,
+        }
+
+        let mut response = self.db.query("
+            SELECT
+                conversation,
+                conversation.custom_title AS custom_title,
+                conversation.summary AS summary,
+                conversation.created_at AS created_at,
+                conversation.agent_name AS agent_name,
+                role,
+                content,
+                timestamp,
+                search::score(1) AS score
+            FROM message
+            WHERE content @@ $query
+            ORDER BY score DESC
+            LIMIT $limit
+        ")
+        .bind(("query", query.to_string()))
+        .bind(("limit", limit))
+        .await?;
+
+        let rows: Vec<HitRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| MessageSearchHit {
+                conversation_id: row.conversation.to_string(),
+                conversation_title: row
+                    .custom_title
+                    .filter(|title| !title.trim().is_empty())
+                    .or(row.summary),
+                conversation_created_at: row.created_at.unwrap_or_default(),
+                agent_name: row.agent_name.unwrap_or_default(),
+                role: row.role,
+                content: row.content,
+                timestamp: row.timestamp,
+                score: row.score,
+            })
+            .collect())
+    }
+
     pub async fn load_recent_user_messages(
         &self,
         limit: usize,
     ) -> Result<Vec<StoredMessage>> {
         let mut response = self.db.query("
-            SELECT role, content, timestamp, display_name
+            SELECT role, content, timestamp, display_name, quoted_role, quoted_text, model, duration_ms, prompt_tokens, completion_tokens, images, files
             FROM message
             WHERE role = \"User\"
             ORDER BY timestamp DESC
@@ -467,7 +1630,7 @@ impl StorageManager {
         .await?;
 
         let messages: Vec<StoredMessage> = response.take(0)?;
-        Ok(messages)
+        Ok(open_messages(messages))
     }
 
     #[allow(dead_code, unused_variables)]
@@ -496,14 +1659,28 @@ impl StorageManager {
         self.load_conversations_with_limit(20).await
     }
 
+    /// Newest conversations that are not archived
     pub async fn load_conversations_with_limit(&self, limit: usize) -> Result<Vec<ConversationSummary>> {
+        self.query_conversation_list("WHERE archived != true", limit).await
+    }
+
+    /// Newest archived conversations, for the `/archive` view of history
+    pub async fn load_archived_conversations(&self, limit: usize) -> Result<Vec<ConversationSummary>> {
+        self.query_conversation_list("WHERE archived = true", limit).await
+    }
+
+    async fn query_conversation_list(&self, condition: &str, limit: usize) -> Result<Vec<ConversationSummary>> {
         #[derive(Debug, Deserialize)]
         struct ConvRow {
             id: surrealdb::sql::Thing,
             agent_name: String,
             summary: Option<String>,
             detailed_summary: Option<String>,
+            custom_title: Option<String>,
             created_at: String,
+            archived: Option<bool>,
+            retention: Option<String>,
+            expires_at: Option<String>,
         }
 
         let query = format!("
@@ -512,11 +1689,16 @@ impl StorageManager {
                 agent_name,
                 summary,
                 detailed_summary,
-                created_at
+                custom_title,
+                created_at,
+                archived,
+                retention,
+                expires_at
             FROM conversation
+            {}
             ORDER BY created_at DESC
             LIMIT {}
-        ", limit);
+        ", condition, limit);
 
         let mut response = self.db.query(query).await?;
         let results: Vec<ConvRow> = response.take(0)?;
@@ -525,9 +1707,13 @@ impl StorageManager {
             ConversationSummary {
                 id: row.id.to_string(),
                 agent_name: row.agent_name,
-                summary: row.summary,
-                detailed_summary: row.detailed_summary,
+                summary: row.summary.map(encryption::open),
+                detailed_summary: row.detailed_summary.map(encryption::open),
+                custom_title: row.custom_title,
                 created_at: row.created_at,
+                archived: row.archived.unwrap_or(false),
+                retention: Retention::from_stored(row.retention.as_deref()),
+                expires_at: row.expires_at,
             }
         }).collect();
 
@@ -549,7 +1735,7 @@ impl StorageManager {
 
         let conversation_ref = Self::conversation_ref(normalized_id);
         let mut response = self.db.query("
-            SELECT role, content, timestamp, display_name
+            SELECT role, content, timestamp, display_name, quoted_role, quoted_text, model, duration_ms, prompt_tokens, completion_tokens, images, files
             FROM message
             WHERE conversation = $conv_id
             ORDER BY timestamp ASC
@@ -559,7 +1745,7 @@ impl StorageManager {
 
         let messages: Vec<StoredMessage> = response.take(0)?;
 
-        Ok((agent_name, messages))
+        Ok((agent_name, open_messages(messages)))
     }
 
     /// Loads messages from all conversations within a date range (RFC 3339 strings).
@@ -594,7 +1780,7 @@ impl StorageManager {
         for row in conv_rows {
             let conversation_ref = Thing::from(("conversation", row.id.id.to_string().as_str()));
             let mut msg_response = self.db.query("
-                SELECT role, content, timestamp, display_name
+                SELECT role, content, timestamp, display_name, quoted_role, quoted_text, model, duration_ms, prompt_tokens, completion_tokens, images, files
                 FROM message
                 WHERE conversation = $conv_id AND role != 'System'
                 ORDER BY timestamp ASC
@@ -602,7 +1788,7 @@ impl StorageManager {
             .bind(("conv_id", conversation_ref))
             .await?;
 
-            let messages: Vec<StoredMessage> = msg_response.take(0)?;
+            let messages = open_messages(msg_response.take(0)?);
             if messages.is_empty() {
                 continue;
             }
@@ -654,6 +1840,90 @@ impl StorageManager {
         Ok(())
     }
 
+    /// Record counts per kind of stored data, for `kimi data report`
+    pub async fn data_counts(&self) -> Result<Vec<(&'static str, usize)>> {
+        #[derive(Debug, Deserialize)]
+        struct CountRow {
+            count: usize,
+        }
+
+        let mut counts = Vec::new();
+        for (table, label) in DATA_REPORT_TABLES {
+            let mut response = self.db.query(format!("SELECT count() AS count FROM {} GROUP ALL", table)).await?;
+            let rows: Vec<CountRow> = response.take(0)?;
+            counts.push((*label, rows.into_iter().next().map_or(0, |row| row.count)));
+        }
+        Ok(counts)
+    }
+
+    /// Message counts per role ("User", "Assistant", "System")
+    pub async fn message_counts_by_role(&self) -> Result<Vec<(String, usize)>> {
+        #[derive(Debug, Deserialize)]
+        struct RoleCount {
+            role: String,
+            count: usize,
+        }
+
+        let mut response = self.db.query("
+            SELECT role, count() AS count FROM message GROUP BY role
+        ").await?;
+        let rows: Vec<RoleCount> = response.take(0)?;
+        Ok(rows.into_iter().map(|row| (row.role, row.count)).collect())
+    }
+
+    /// Every record whose text mentions `keyword` (case-insensitive). Matching happens
+    /// after decryption, so it also works with `[storage] encrypt_content`
+    pub async fn find_purge_matches(&self, keyword: &str) -> Result<Vec<PurgeMatch>> {
+        #[derive(Debug, Deserialize)]
+        struct TextRow {
+            id: Thing,
+            text: Option<String>,
+        }
+
+        let needle = keyword.trim().to_lowercase();
+        if needle.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut matches: Vec<PurgeMatch> = Vec::new();
+        for (table, field, category) in PURGE_FIELDS {
+            let mut response = self.db
+                .query(format!("SELECT id, {} AS text FROM {} WHERE {} IS NOT NONE", field, table, field))
+                .await?;
+            let rows: Vec<TextRow> = response.take(0)?;
+            for row in rows {
+                let Some(text) = row.text.map(encryption::open) else {
+                    continue;
+                };
+                if !text.to_lowercase().contains(&needle) || matches.iter().any(|found| found.id == row.id) {
+                    continue;
+                }
+                matches.push(PurgeMatch {
+                    id: row.id,
+                    category: *category,
+                    text,
+                });
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Deletes the matched records; conversation summaries are cleared instead, so the
+    /// rest of the conversation stays. Returns how many records were changed
+    pub async fn purge_matches(&self, matches: &[PurgeMatch]) -> Result<usize> {
+        for found in matches {
+            if found.category == PurgeCategory::Summary {
+                self.db.query("UPDATE $record SET summary = NONE, detailed_summary = NONE, custom_title = NONE, chunk_summaries = NONE")
+                    .bind(("record", found.id.clone()))
+                    .await?;
+            } else {
+                self.db.query("DELETE $record")
+                    .bind(("record", found.id.clone()))
+                    .await?;
+            }
+        }
+        Ok(matches.len())
+    }
+
     /// Updates summary and messages for an existing conversation
     pub async fn update_conversation(
         &self,
@@ -670,8 +1940,8 @@ impl StorageManager {
         let _: Option<ConversationRecord> = self.db
             .update(("conversation", normalized_id))
             .merge(serde_json::json!({
-                "summary": summary,
-                "detailed_summary": detailed_summary,
+                "summary": encryption::seal(summary),
+                "detailed_summary": encryption::seal(detailed_summary),
                 "updated_at": now,
             }))
             .await?;
@@ -687,15 +1957,7 @@ impl StorageManager {
         for message in messages {
             let _: Option<MessageRecord> = self.db
                 .create("message")
-                .content(MessageRecord {
-                    id: None,
-                    conversation: conversation_ref.clone(),
-                    role: message.role.clone(),
-                    content: message.content.clone(),
-                    embedding: None,
-                    timestamp: message.timestamp.clone(),
-                    display_name: message.display_name.clone(),
-                })
+                .content(MessageRecord::new(conversation_ref.clone(), message))
                 .await?;
         }
 
@@ -704,13 +1966,21 @@ impl StorageManager {
 
     /// Filters conversations by summary, agent name, or message content
     pub async fn filter_conversations(&self, filter: &str) -> Result<Vec<ConversationSummary>> {
+        if encryption::is_active() {
+            return self.filter_conversations_decrypted(filter).await;
+        }
+
         #[derive(Debug, Deserialize)]
         struct ConvRow {
             id: surrealdb::sql::Thing,
             agent_name: String,
             summary: Option<String>,
             detailed_summary: Option<String>,
+            custom_title: Option<String>,
             created_at: String,
+            archived: Option<bool>,
+            retention: Option<String>,
+            expires_at: Option<String>,
         }
 
         let filter_str = filter.to_string();
@@ -720,10 +1990,15 @@ impl StorageManager {
                 agent_name,
                 summary,
                 detailed_summary,
-                created_at
+                custom_title,
+                created_at,
+                archived,
+                retention,
+                expires_at
             FROM conversation
             WHERE
                 string::contains(string::lowercase(summary), string::lowercase($filter))
+                OR string::contains(string::lowercase(custom_title), string::lowercase($filter))
                 OR string::contains(string::lowercase(agent_name), string::lowercase($filter))
                 OR id IN (
                     SELECT conversation FROM message
@@ -740,107 +2015,416 @@ impl StorageManager {
             ConversationSummary {
                 id: row.id.to_string(),
                 agent_name: row.agent_name,
-                summary: row.summary,
-                detailed_summary: row.detailed_summary,
+                summary: row.summary.map(encryption::open),
+                detailed_summary: row.detailed_summary.map(encryption::open),
+                custom_title: row.custom_title,
                 created_at: row.created_at,
+                archived: row.archived.unwrap_or(false),
+                retention: Retention::from_stored(row.retention.as_deref()),
+                expires_at: row.expires_at,
             }
         }).collect();
         Ok(summaries)
     }
 
-    /// Updates only conversation messages (keeps existing summaries)
-    pub async fn update_conversation_messages(
+    /// Encrypted rows can't be matched in the database, so filter summaries in memory
+    /// (message content is not searched while encryption is active)
+    async fn filter_conversations_decrypted(&self, filter: &str) -> Result<Vec<ConversationSummary>> {
+        let needle = filter.to_lowercase();
+        let matches = |value: Option<&str>| {
+            value.is_some_and(|text| text.to_lowercase().contains(&needle))
+        };
+        Ok(self
+            .query_conversation_list("", usize::MAX)
+            .await?
+            .into_iter()
+            .filter(|conversation| {
+                matches(conversation.summary.as_deref())
+                    || matches(conversation.detailed_summary.as_deref())
+                    || matches(conversation.custom_title.as_deref())
+                    || matches(Some(&conversation.agent_name))
+            })
+            .collect())
+    }
+
+    /// Sets or clears (empty title) the user-defined conversation title
+    pub async fn rename_conversation(&self, id: &str, title: &str) -> Result<()> {
+        let normalized_id = Self::normalize_conversation_id(id);
+        let custom_title = Some(title.trim()).filter(|value| !value.is_empty());
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "custom_title": custom_title,
+            }))
+            .await?;
+        Ok(())
+    }
+
+    /// Hides a conversation from the default history list, or brings it back
+    pub async fn set_conversation_archived(&self, id: &str, archived: bool) -> Result<()> {
+        let normalized_id = Self::normalize_conversation_id(id);
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "archived": archived,
+            }))
+            .await?;
+        Ok(())
+    }
+
+    /// Archives every conversation last updated before `cutoff` (RFC 3339); returns how many
+    pub async fn archive_conversations_before(&self, cutoff: &str) -> Result<usize> {
+        #[derive(Debug, Deserialize)]
+        struct UpdatedRow {
+            #[allow(dead_code)]
+            id: Thing,
+        }
+
+        let mut response = self.db.query("
+            UPDATE conversation
+            SET archived = true
+            WHERE archived != true AND retention != 'forever' AND updated_at < $cutoff
+            RETURN BEFORE
+        ")
+        .bind(("cutoff", cutoff.to_string()))
+        .await?;
+        let updated: Vec<UpdatedRow> = response.take(0)?;
+        Ok(updated.len())
+    }
+
+    /// Marks a conversation ephemeral (deleted after `expires_at`), kept forever or normal
+    pub async fn set_conversation_retention(
         &self,
         id: &str,
-        messages: &[ConversationMessage],
+        retention: Retention,
+        expires_at: Option<&str>,
     ) -> Result<()> {
         let normalized_id = Self::normalize_conversation_id(id);
-        let conversation_ref = Self::conversation_ref(normalized_id);
-        let now = chrono::Local::now().to_rfc3339();
-
         let _: Option<ConversationRecord> = self.db
             .update(("conversation", normalized_id))
             .merge(serde_json::json!({
-                "updated_at": now,
+                "retention": retention.as_stored(),
+                "expires_at": expires_at,
             }))
             .await?;
+        Ok(())
+    }
 
-        self.db.query("
-            DELETE FROM message WHERE conversation = $conv_id
+    /// Deletes ephemeral conversations whose `expires_at` is before `now`; returns how many
+    pub async fn delete_expired_conversations(&self, now: &str) -> Result<usize> {
+        #[derive(Debug, Deserialize)]
+        struct ExpiredRow {
+            id: Thing,
+        }
+
+        let mut response = self.db.query("
+            SELECT id FROM conversation
+            WHERE retention = 'ephemeral' AND expires_at < $now
         ")
-        .bind(("conv_id", conversation_ref.clone()))
+        .bind(("now", now.to_string()))
         .await?;
-
-        for message in messages {
-            let _: Option<MessageRecord> = self.db
-                .create("message")
-                .content(MessageRecord {
-                    id: None,
-                    conversation: conversation_ref.clone(),
-                    role: message.role.clone(),
-                    content: message.content.clone(),
-                    embedding: None,
-                    timestamp: message.timestamp.clone(),
-                    display_name: message.display_name.clone(),
-                })
-                .await?;
+        let expired: Vec<ExpiredRow> = response.take(0)?;
+        for row in &expired {
+            self.delete_conversation(&row.id.to_string()).await?;
         }
+        Ok(expired.len())
+    }
 
+    /// Sets the language replies in this conversation are written in
+    pub async fn set_conversation_language(&self, id: &str, language: &str) -> Result<()> {
+        let normalized_id = Self::normalize_conversation_id(id);
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "language": language,
+            }))
+            .await?;
         Ok(())
     }
 
-    // ── Topic tracking for project suggestions ──────────────────────────────
+    /// The language saved with a conversation, if `/language` was used in it
+    pub async fn load_conversation_language(&self, id: &str) -> Result<Option<String>> {
+        #[derive(Debug, Deserialize)]
+        struct ConvLanguage {
+            #[serde(default)]
+            language: Option<String>,
+        }
 
-    /// Records topic mentions for a conversation (batch insert)
-    pub async fn record_topic_mentions(
+        let normalized_id = Self::normalize_conversation_id(id);
+        let conv: Option<ConvLanguage> = self.db.select(("conversation", normalized_id)).await?;
+        Ok(conv.and_then(|conv| conv.language))
+    }
+
+    /// Stores the part summaries of a long conversation; an empty list clears them
+    pub async fn set_conversation_chunk_summaries(&self, id: &str, chunk_summaries: &[String]) -> Result<()> {
+        let normalized_id = Self::normalize_conversation_id(id);
+        let value = if chunk_summaries.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::Value::String(encryption::seal(&chunk_summaries.join("\n")))
+        };
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "chunk_summaries": value,
+            }))
+            .await?;
+        Ok(())
+    }
+
+    /// Stores the tone the routing model gave a conversation, with Kimi's emotions at that time
+    pub async fn set_conversation_sentiment(
         &self,
-        topics: &[String],
-        conversation_id: &str,
+        id: &str,
+        valence: f32,
+        emotions: &[String],
+        kimi_emotions: &[String],
     ) -> Result<()> {
-        let now = chrono::Local::now().to_rfc3339();
-        for topic in topics {
-            let normalized = topic.to_lowercase().trim().to_string();
-            if normalized.is_empty() {
-                continue;
-            }
-            self.db.query(
-                "CREATE topic_mention SET topic = $topic, conversation_id = $conv_id, created_at = $now"
-            )
-            .bind(("topic", normalized))
-            .bind(("conv_id", conversation_id.to_string()))
-            .bind(("now", now.clone()))
+        let normalized_id = Self::normalize_conversation_id(id);
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "sentiment": valence,
+                "emotions": emotions,
+                "kimi_emotions": kimi_emotions,
+            }))
             .await?;
-        }
         Ok(())
     }
 
-    /// Loads topics that have >= threshold mentions and don't yet have a project file.
-    /// Returns (topic_name, mention_count) pairs.
-    pub async fn load_frequent_topics(
-        &self,
-        threshold: usize,
-    ) -> Result<Vec<(String, usize)>> {
+    /// Every scored conversation, oldest first
+    pub async fn load_sentiment_timeline(&self) -> Result<Vec<SentimentEntry>> {
         #[derive(Debug, Deserialize)]
-        struct TopicCount {
-            topic: String,
-            count: usize,
+        struct SentimentRow {
+            summary: Option<String>,
+            custom_title: Option<String>,
+            created_at: String,
+            sentiment: f32,
+            #[serde(default)]
+            emotions: Option<Vec<String>>,
+            #[serde(default)]
+            kimi_emotions: Option<Vec<String>>,
         }
 
-        let mut response = self.db.query("
-            SELECT topic, count() AS count
-            FROM topic_mention
-            GROUP BY topic
-            ORDER BY count DESC
-        ").await?;
-
-        let results: Vec<TopicCount> = response.take(0)?;
-        Ok(results
+        let mut response = self.db
+            .query("
+                SELECT summary, custom_title, created_at, sentiment, emotions, kimi_emotions FROM conversation
+                WHERE sentiment IS NOT NONE
+                ORDER BY created_at ASC
+            ")
+            .await?;
+        let rows: Vec<SentimentRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| SentimentEntry {
+                title: row
+                    .custom_title
+                    .or_else(|| row.summary.map(encryption::open))
+                    .unwrap_or_else(|| "Untitled".to_string()),
+                created_at: row.created_at,
+                valence: row.sentiment,
+                emotions: row.emotions.unwrap_or_default(),
+                kimi_emotions: row.kimi_emotions.unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Part summaries of the newest long conversations, with each conversation's creation time
+    pub async fn load_recent_chunk_summaries(&self, limit: usize) -> Result<Vec<(String, String)>> {
+        #[derive(Debug, Deserialize)]
+        struct ChunkRow {
+            created_at: String,
+            chunk_summaries: Option<String>,
+        }
+
+        let mut response = self.db
+            .query("
+                SELECT created_at, chunk_summaries FROM conversation
+                WHERE chunk_summaries IS NOT NONE
+                ORDER BY created_at DESC
+                LIMIT $limit
+            ")
+            .bind(("limit", limit))
+            .await?;
+        let rows: Vec<ChunkRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.chunk_summaries.map(|text| (row.created_at, encryption::open(text))))
+            .flat_map(|(created_at, text)| {
+                text.lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| (created_at.clone(), line.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
+
+    /// The detailed summary of a conversation and when it was last saved (RFC 3339)
+    pub async fn load_conversation_recap(&self, id: &str) -> Result<Option<(Option<String>, String)>> {
+        #[derive(Debug, Deserialize)]
+        struct ConvRecap {
+            detailed_summary: Option<String>,
+            updated_at: String,
+        }
+
+        let normalized_id = Self::normalize_conversation_id(id);
+        let conv: Option<ConvRecap> = self.db.select(("conversation", normalized_id)).await?;
+        Ok(conv.map(|conv| (conv.detailed_summary.map(encryption::open), conv.updated_at)))
+    }
+
+    /// Appends new messages to an existing conversation (autosave)
+    pub async fn append_conversation_messages(
+        &self,
+        id: &str,
+        messages: &[ConversationMessage],
+    ) -> Result<()> {
+        let normalized_id = Self::normalize_conversation_id(id);
+        let conversation_ref = Self::conversation_ref(normalized_id);
+        let now = chrono::Local::now().to_rfc3339();
+
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "updated_at": now,
+            }))
+            .await?;
+
+        for message in messages {
+            let _: Option<MessageRecord> = self.db
+                .create("message")
+                .content(MessageRecord::new(conversation_ref.clone(), message))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates only conversation messages (keeps existing summaries)
+    pub async fn update_conversation_messages(
+        &self,
+        id: &str,
+        messages: &[ConversationMessage],
+    ) -> Result<()> {
+        let normalized_id = Self::normalize_conversation_id(id);
+        let conversation_ref = Self::conversation_ref(normalized_id);
+        let now = chrono::Local::now().to_rfc3339();
+
+        let _: Option<ConversationRecord> = self.db
+            .update(("conversation", normalized_id))
+            .merge(serde_json::json!({
+                "updated_at": now,
+            }))
+            .await?;
+
+        self.db.query("
+            DELETE FROM message WHERE conversation = $conv_id
+        ")
+        .bind(("conv_id", conversation_ref.clone()))
+        .await?;
+
+        for message in messages {
+            let _: Option<MessageRecord> = self.db
+                .create("message")
+                .content(MessageRecord::new(conversation_ref.clone(), message))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // ── Topic tracking for project suggestions ──────────────────────────────
+
+    /// Records topic mentions for a conversation (batch insert)
+    pub async fn record_topic_mentions(
+        &self,
+        topics: &[String],
+        conversation_id: &str,
+    ) -> Result<()> {
+        let now = chrono::Local::now().to_rfc3339();
+        for topic in topics {
+            let normalized = topic.to_lowercase().trim().to_string();
+            if normalized.is_empty() {
+                continue;
+            }
+            self.db.query(
+                "CREATE topic_mention SET topic = $topic, conversation_id = $conv_id, created_at = $now"
+            )
+            .bind(("topic", normalized))
+            .bind(("conv_id", conversation_id.to_string()))
+            .bind(("now", now.clone()))
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Loads topics that have >= threshold mentions and don't yet have a project file.
+    /// Returns (topic_name, mention_count) pairs.
+    pub async fn load_frequent_topics(
+        &self,
+        threshold: usize,
+    ) -> Result<Vec<(String, usize)>> {
+        #[derive(Debug, Deserialize)]
+        struct TopicCount {
+            topic: String,
+            count: usize,
+        }
+
+        let mut response = self.db.query("
+            SELECT topic, count() AS count
+            FROM topic_mention
+            GROUP BY topic
+            ORDER BY count DESC
+        ").await?;
+
+        let results: Vec<TopicCount> = response.take(0)?;
+        Ok(results
             .into_iter()
             .filter(|entry| entry.count >= threshold)
             .map(|entry| (entry.topic, entry.count))
             .collect())
     }
 
+    /// Returns the known topic closest to `embedding` when it is at least `min_similarity` alike
+    pub async fn find_similar_topic(
+        &self,
+        embedding: Vec<f32>,
+        min_similarity: f32,
+    ) -> Result<Option<String>> {
+        #[derive(Debug, Deserialize)]
+        struct TopicMatch {
+            topic: String,
+            similarity: f32,
+        }
+
+        let mut response = self.db.query("
+            SELECT topic, vector::similarity::cosine(embedding, $embedding) AS similarity
+            FROM topic_embedding
+            ORDER BY similarity DESC
+            LIMIT 1
+        ")
+        .bind(("embedding", embedding))
+        .await?;
+
+        let matches: Vec<TopicMatch> = response.take(0)?;
+        Ok(matches
+            .into_iter()
+            .find(|candidate| candidate.similarity >= min_similarity)
+            .map(|candidate| candidate.topic))
+    }
+
+    /// Remembers a topic's embedding so later synonyms resolve to it
+    pub async fn save_topic_embedding(&self, topic: &str, embedding: Vec<f32>) -> Result<()> {
+        self.db.query("
+            IF (SELECT VALUE id FROM topic_embedding WHERE topic = $topic LIMIT 1) = [] {
+                CREATE topic_embedding SET topic = $topic, embedding = $embedding
+            }
+        ")
+        .bind(("topic", topic.to_string()))
+        .bind(("embedding", embedding))
+        .await?;
+        Ok(())
+    }
+
     /// Clears all topic mentions for a given topic (after project creation or archival)
     pub async fn clear_topic_mentions(&self, topic: &str) -> Result<()> {
         let normalized = topic.to_lowercase().trim().to_string();
@@ -851,4 +2435,917 @@ impl StorageManager {
         .await?;
         Ok(())
     }
+
+    // ── Snippets ────────────────────────────────────────────────────────────
+
+    /// Keys from `keys` that were already included in an earlier briefing
+    pub async fn load_seen_feed_keys(&self, keys: Vec<String>) -> Result<HashSet<String>> {
+        #[derive(Debug, Deserialize)]
+        struct Row {
+            key: String,
+        }
+
+        let mut response = self.db
+            .query("SELECT key FROM feed_item WHERE key IN $keys")
+            .bind(("keys", keys))
+            .await?;
+        let rows: Vec<Row> = response.take(0)?;
+        Ok(rows.into_iter().map(|row| row.key).collect())
+    }
+
+    /// Remembers a feed item so later briefings leave it out
+    pub async fn mark_feed_item_seen(&self, key: &str, feed: &str, title: &str) -> Result<()> {
+        let _ = self.db
+            .query("CREATE feed_item SET key = $key, feed = $feed, title = $title, seen_at = $now")
+            .bind(("key", key.to_string()))
+            .bind(("feed", feed.to_string()))
+            .bind(("title", title.to_string()))
+            .bind(("now", chrono::Local::now().to_rfc3339()))
+            .await?;
+        Ok(())
+    }
+
+    /// Saves a code snippet and returns its id
+    pub async fn save_snippet(&self, title: &str, language: &str, content: &str) -> Result<String> {
+        #[derive(Debug, Deserialize)]
+        struct CreatedRow {
+            id: Thing,
+        }
+
+        let mut response = self.db.query(
+            "CREATE snippet SET title = $title, language = $language, content = $content, created_at = $now"
+        )
+        .bind(("title", title.to_string()))
+        .bind(("language", language.to_string()))
+        .bind(("content", content.to_string()))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+
+        let created: Vec<CreatedRow> = response.take(0)?;
+        created
+            .into_iter()
+            .next()
+            .map(|row| row.id.to_string())
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to create snippet"))
+    }
+
+    /// Loads all snippets, newest first
+    pub async fn load_snippets(&self) -> Result<Vec<Snippet>> {
+        #[derive(Debug, Deserialize)]
+        struct SnippetRow {
+            id: Thing,
+            title: String,
+            language: String,
+            content: String,
+            created_at: String,
+        }
+
+        let mut response = self.db.query("
+            SELECT id, title, language, content, created_at
+            FROM snippet
+            ORDER BY created_at DESC
+        ").await?;
+
+        let rows: Vec<SnippetRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Snippet {
+                id: row.id.to_string(),
+                title: row.title,
+                language: row.language,
+                content: row.content,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    /// Deletes a snippet by id
+    pub async fn delete_snippet(&self, id: &str) -> Result<()> {
+        let normalized_id = id.strip_prefix("snippet:").unwrap_or(id);
+        self.db.query("DELETE $snippet")
+            .bind(("snippet", Thing::from(("snippet", normalized_id))))
+            .await?;
+        Ok(())
+    }
+
+    // ── Project tasks ───────────────────────────────────────────────────────
+
+    /// Adds a task to a project and returns its id
+    pub async fn save_project_task(
+        &self,
+        project: &str,
+        title: &str,
+        status: TaskStatus,
+    ) -> Result<String> {
+        #[derive(Debug, Deserialize)]
+        struct CreatedRow {
+            id: Thing,
+        }
+
+        let mut response = self.db.query(
+            "CREATE project_task SET project = $project, title = $title, status = $status, created_at = $now, completed_at = NONE"
+        )
+        .bind(("project", project.to_string()))
+        .bind(("title", title.trim().to_string()))
+        .bind(("status", status.as_str()))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+
+        let created: Vec<CreatedRow> = response.take(0)?;
+        created
+            .into_iter()
+            .next()
+            .map(|row| row.id.to_string())
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to create task"))
+    }
+
+    /// Loads a project's tasks, oldest first
+    pub async fn load_project_tasks(&self, project: &str) -> Result<Vec<ProjectTask>> {
+        #[derive(Debug, Deserialize)]
+        struct TaskRow {
+            id: Thing,
+            project: String,
+            title: String,
+            status: String,
+            completed_at: Option<String>,
+        }
+
+        let mut response = self.db.query("
+            SELECT id, project, title, status, created_at, completed_at
+            FROM project_task
+            WHERE project = $project
+            ORDER BY created_at ASC
+        ")
+        .bind(("project", project.to_string()))
+        .await?;
+
+        let rows: Vec<TaskRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ProjectTask {
+                id: row.id.to_string(),
+                project: row.project,
+                title: row.title,
+                status: TaskStatus::from_stored(&row.status),
+                completed_at: row.completed_at,
+            })
+            .collect())
+    }
+
+    /// Open tasks across all projects, oldest first
+    pub async fn load_open_project_tasks(&self) -> Result<Vec<ProjectTask>> {
+        #[derive(Debug, Deserialize)]
+        struct TaskRow {
+            id: Thing,
+            project: String,
+            title: String,
+        }
+
+        let mut response = self.db.query("
+            SELECT id, project, title, created_at
+            FROM project_task
+            WHERE status = 'open'
+            ORDER BY created_at ASC
+        ").await?;
+
+        let rows: Vec<TaskRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ProjectTask {
+                id: row.id.to_string(),
+                project: row.project,
+                title: row.title,
+                status: TaskStatus::Open,
+                completed_at: None,
+            })
+            .collect())
+    }
+
+    /// Changes a task's status; `completed_at` is set only while it is done
+    pub async fn set_project_task_status(&self, id: &str, status: TaskStatus) -> Result<()> {
+        let normalized_id = id.strip_prefix("project_task:").unwrap_or(id);
+        let completed_at = (status == TaskStatus::Done).then(|| chrono::Local::now().to_rfc3339());
+        self.db.query("UPDATE $task SET status = $status, completed_at = $completed_at")
+            .bind(("task", Thing::from(("project_task", normalized_id))))
+            .bind(("status", status.as_str()))
+            .bind(("completed_at", completed_at))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_project_task(&self, id: &str) -> Result<()> {
+        let normalized_id = id.strip_prefix("project_task:").unwrap_or(id);
+        self.db.query("DELETE $task")
+            .bind(("task", Thing::from(("project_task", normalized_id))))
+            .await?;
+        Ok(())
+    }
+
+    /// Moves tasks along when a project is renamed
+    pub async fn rename_project_tasks(&self, old_name: &str, new_name: &str) -> Result<()> {
+        self.db.query("UPDATE project_task SET project = $new_name WHERE project = $old_name")
+            .bind(("old_name", old_name.to_string()))
+            .bind(("new_name", new_name.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    // ── Downloads ───────────────────────────────────────────────────────────
+
+    /// Records a finished, failed or cancelled download
+    pub async fn save_download_record(
+        &self,
+        url: &str,
+        status: DownloadStatus,
+        destination: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.db.query(
+            "CREATE download SET url = $url, status = $status, destination = $destination, error = $error, finished_at = $now"
+        )
+        .bind(("url", url.to_string()))
+        .bind(("status", status.as_str()))
+        .bind(("destination", destination.map(str::to_string)))
+        .bind(("error", error.map(str::to_string)))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+        Ok(())
+    }
+
+    /// Loads the most recent download records, newest first
+    pub async fn load_download_records(&self, limit: usize) -> Result<Vec<DownloadRecord>> {
+        #[derive(Debug, Deserialize)]
+        struct DownloadRow {
+            id: Thing,
+            url: String,
+            status: String,
+            destination: Option<String>,
+            error: Option<String>,
+            finished_at: String,
+        }
+
+        let mut response = self.db.query("
+            SELECT id, url, status, destination, error, finished_at
+            FROM download
+            ORDER BY finished_at DESC
+            LIMIT $limit
+        ")
+        .bind(("limit", limit))
+        .await?;
+
+        let rows: Vec<DownloadRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| DownloadRecord {
+                id: row.id.to_string(),
+                url: row.url,
+                status: DownloadStatus::from_stored(&row.status),
+                destination: row.destination,
+                error: row.error,
+                finished_at: row.finished_at,
+            })
+            .collect())
+    }
+
+    /// Deletes a download record by id
+    pub async fn delete_download_record(&self, id: &str) -> Result<()> {
+        let normalized_id = id.strip_prefix("download:").unwrap_or(id);
+        self.db.query("DELETE $download")
+            .bind(("download", Thing::from(("download", normalized_id))))
+            .await?;
+        Ok(())
+    }
+
+    // ── Tool audit ──────────────────────────────────────────────────────────
+
+    /// Records a tool call; arguments and result are encrypted like message content
+    pub async fn save_tool_audit(
+        &self,
+        tool: &str,
+        arguments: &str,
+        decision: ToolDecision,
+        result: &str,
+    ) -> Result<()> {
+        self.db.query(
+            "CREATE tool_audit SET tool = $tool, arguments = $arguments, decision = $decision, result = $result, created_at = $now"
+        )
+        .bind(("tool", tool.to_string()))
+        .bind(("arguments", encryption::seal(arguments)))
+        .bind(("decision", decision.as_str()))
+        .bind(("result", encryption::seal(result)))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+        Ok(())
+    }
+
+    /// Loads the most recent tool calls, newest first
+    pub async fn load_tool_audit(&self, limit: usize) -> Result<Vec<ToolAuditRecord>> {
+        #[derive(Debug, Deserialize)]
+        struct ToolAuditRow {
+            tool: String,
+            arguments: String,
+            decision: String,
+            result: String,
+            created_at: String,
+        }
+
+        let mut response = self.db.query("
+            SELECT tool, arguments, decision, result, created_at
+            FROM tool_audit
+            ORDER BY created_at DESC
+            LIMIT $limit
+        ")
+        .bind(("limit", limit))
+        .await?;
+
+        let rows: Vec<ToolAuditRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ToolAuditRecord {
+                tool: row.tool,
+                arguments: encryption::open(row.arguments),
+                decision: ToolDecision::from_stored(&row.decision),
+                result: encryption::open(row.result),
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    /// Stores the full text behind a summarized tool result under its key
+    pub async fn save_tool_output(&self, output: ToolOutputRecord) -> Result<()> {
+        self.db.query(
+            "UPSERT $id SET tool = $tool, arguments = $arguments, content = $content, summary = $summary, embedding = $embedding, created_at = $now"
+        )
+        .bind(("id", Thing::from(("tool_output", output.key.as_str()))))
+        .bind(("tool", output.tool))
+        .bind(("arguments", encryption::seal(&output.arguments)))
+        .bind(("content", encryption::seal(&output.content)))
+        .bind(("summary", encryption::seal(&output.summary)))
+        .bind(("embedding", output.embedding))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+        Ok(())
+    }
+
+    // ── Usage ledger ────────────────────────────────────────────────────────
+
+    /// Adds one request's tokens and cost to the day's row for `provider`/`model`
+    pub async fn add_usage(
+        &self,
+        day: &str,
+        provider: &str,
+        model: &str,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        cost_usd: f64,
+    ) -> Result<()> {
+        let id = Thing::from(("usage", format!("{}|{}|{}", day, provider, model).as_str()));
+        let mut response = self.db
+            .query("SELECT day, provider, model, requests, prompt_tokens, completion_tokens, cost_usd FROM $id")
+            .bind(("id", id.clone()))
+            .await?;
+        let existing: Option<UsageRecord> = response.take(0)?;
+        let current = existing.unwrap_or_default();
+        self.db.query(
+            "UPSERT $id SET day = $day, provider = $provider, model = $model, requests = $requests, \
+prompt_tokens = $prompt_tokens, completion_tokens = $completion_tokens, cost_usd = $cost_usd"
+        )
+        .bind(("id", id))
+        .bind(("day", day.to_string()))
+        .bind(("provider", provider.to_string()))
+        .bind(("model", model.to_string()))
+        .bind(("requests", current.requests.saturating_add(1)))
+        .bind(("prompt_tokens", current.prompt_tokens.saturating_add(prompt_tokens)))
+        .bind(("completion_tokens", current.completion_tokens.saturating_add(completion_tokens)))
+        .bind(("cost_usd", current.cost_usd + cost_usd))
+        .await?;
+        Ok(())
+    }
+
+    /// Loads usage rows from `since_day` (YYYY-MM-DD) on, newest day first
+    pub async fn load_usage(&self, since_day: &str) -> Result<Vec<UsageRecord>> {
+        let mut response = self.db.query("
+            SELECT day, provider, model, requests, prompt_tokens, completion_tokens, cost_usd
+            FROM usage
+            WHERE day >= $since
+            ORDER BY day DESC, cost_usd DESC
+        ")
+        .bind(("since", since_day.to_string()))
+        .await?;
+        Ok(response.take(0)?)
+    }
+
+    // ── Dashboard ───────────────────────────────────────────────────────────
+
+    /// Every conversation's creation time with its message and embedded message counts
+    pub async fn load_conversation_activity(&self) -> Result<Vec<ConversationActivity>> {
+        #[derive(Debug, Deserialize)]
+        struct MessageCounts {
+            conversation: Thing,
+            messages: usize,
+            embedded: usize,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Created {
+            id: Thing,
+            created_at: String,
+        }
+
+        let mut response = self.db.query("
+            SELECT conversation, count() AS messages, count(embedding IS NOT NONE) AS embedded
+            FROM message
+            GROUP BY conversation;
+            SELECT id, created_at FROM conversation;
+        ").await?;
+        let counts: Vec<MessageCounts> = response.take(0)?;
+        let conversations: Vec<Created> = response.take(1)?;
+        let counts: std::collections::HashMap<String, (usize, usize)> = counts
+            .into_iter()
+            .map(|entry| (entry.conversation.to_string(), (entry.messages, entry.embedded)))
+            .collect();
+        Ok(conversations
+            .into_iter()
+            .map(|conversation| {
+                let (messages, embedded) = counts.get(&conversation.id.to_string()).copied().unwrap_or_default();
+                ConversationActivity { created_at: conversation.created_at, messages, embedded }
+            })
+            .collect())
+    }
+
+    /// Counts one more recall of each memory; keyed by content, so edits count as new memories
+    pub async fn record_retrievals(&self, messages: &[RetrievedMessage]) -> Result<()> {
+        let now = chrono::Local::now().to_rfc3339();
+        for message in messages {
+            let id = Thing::from(("retrieval_stat", content_key(&message.content).as_str()));
+            let mut response = self.db
+                .query("SELECT preview, count, last_retrieved_at FROM $id")
+                .bind(("id", id.clone()))
+                .await?;
+            let existing: Option<RetrievalCount> = response.take(0)?;
+            let preview: String = message.content.chars().take(RETRIEVAL_PREVIEW_CHARS).collect();
+            self.db
+                .query("UPSERT $id SET preview = $preview, count = $count, last_retrieved_at = $now")
+                .bind(("id", id))
+                .bind(("preview", encryption::seal(&preview)))
+                .bind(("count", existing.map_or(0, |entry| entry.count).saturating_add(1)))
+                .bind(("now", now.clone()))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// The most often recalled memories, most first
+    pub async fn load_top_retrievals(&self, limit: usize) -> Result<Vec<RetrievalCount>> {
+        let mut response = self.db.query("
+            SELECT preview, count, last_retrieved_at FROM retrieval_stat ORDER BY count DESC LIMIT $limit
+        ")
+        .bind(("limit", limit))
+        .await?;
+        let rows: Vec<RetrievalCount> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| RetrievalCount { preview: encryption::open(row.preview), ..row })
+            .collect())
+    }
+
+    // ── Reminders ───────────────────────────────────────────────────────────
+
+    /// Stores a reminder published to the inbox on `due` (YYYY-MM-DD)
+    pub async fn add_reminder(&self, due: &str, text: &str) -> Result<()> {
+        self.db.query(
+            "CREATE reminder SET due = $due, text = $text, created_at = $now, delivered = false"
+        )
+        .bind(("due", due.to_string()))
+        .bind(("text", text.to_string()))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+        Ok(())
+    }
+
+    /// Marks every undelivered reminder due on or before `today` as delivered and returns them
+    pub async fn take_due_reminders(&self, today: &str) -> Result<Vec<Reminder>> {
+        let mut response = self.db.query("
+            SELECT due, text FROM reminder WHERE delivered = false AND due <= $today ORDER BY due;
+            UPDATE reminder SET delivered = true WHERE delivered = false AND due <= $today;
+        ")
+        .bind(("today", today.to_string()))
+        .await?;
+        Ok(response.take(0)?)
+    }
+
+    // ── Dated tasks ─────────────────────────────────────────────────────────
+
+    /// Proposes tasks found in a conversation, skipping ones already listed with the
+    /// same text and date. Returns how many were added
+    pub async fn propose_due_tasks(
+        &self,
+        tasks: &[(String, String)],
+        conversation_id: &str,
+    ) -> Result<usize> {
+        let existing = self.load_due_tasks(true).await?;
+        let mut added = 0;
+        for (text, due) in tasks {
+            let duplicate = existing
+                .iter()
+                .any(|task| task.due == *due && task.text.eq_ignore_ascii_case(text.trim()));
+            if duplicate {
+                continue;
+            }
+            self.db.query(
+                "CREATE due_task SET text = $text, due = $due, status = $status, conversation_id = $conversation_id, created_at = $now, completed_at = NONE, notified = false"
+            )
+            .bind(("text", text.trim().to_string()))
+            .bind(("due", due.clone()))
+            .bind(("status", TaskStatus::Proposed.as_str()))
+            .bind(("conversation_id", conversation_id.to_string()))
+            .bind(("now", chrono::Local::now().to_rfc3339()))
+            .await?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Dated tasks, soonest first; done ones only with `include_done`
+    pub async fn load_due_tasks(&self, include_done: bool) -> Result<Vec<DueTask>> {
+        #[derive(Debug, Deserialize)]
+        struct TaskRow {
+            id: Thing,
+            text: String,
+            due: String,
+            status: String,
+        }
+
+        let filter = if include_done { "" } else { "WHERE status != 'done'" };
+        let mut response = self.db
+            .query(format!("SELECT id, text, due, status FROM due_task {} ORDER BY due ASC", filter))
+            .await?;
+        let rows: Vec<TaskRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| DueTask {
+                id: row.id.to_string(),
+                text: row.text,
+                due: row.due,
+                status: TaskStatus::from_stored(&row.status),
+            })
+            .collect())
+    }
+
+    /// Accepts, completes or reopens a dated task
+    pub async fn set_due_task_status(&self, id: &str, status: TaskStatus) -> Result<()> {
+        let normalized_id = id.strip_prefix("due_task:").unwrap_or(id);
+        let completed_at = (status == TaskStatus::Done).then(|| chrono::Local::now().to_rfc3339());
+        self.db.query("UPDATE $task SET status = $status, completed_at = $completed_at")
+            .bind(("task", Thing::from(("due_task", normalized_id))))
+            .bind(("status", status.as_str()))
+            .bind(("completed_at", completed_at))
+            .await?;
+        Ok(())
+    }
+
+    /// Moves a task to `due` (YYYY-MM-DD); it is announced again on that day
+    pub async fn snooze_due_task(&self, id: &str, due: &str) -> Result<()> {
+        let normalized_id = id.strip_prefix("due_task:").unwrap_or(id);
+        self.db.query("UPDATE $task SET due = $due, notified = false")
+            .bind(("task", Thing::from(("due_task", normalized_id))))
+            .bind(("due", due.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_due_task(&self, id: &str) -> Result<()> {
+        let normalized_id = id.strip_prefix("due_task:").unwrap_or(id);
+        self.db.query("DELETE $task")
+            .bind(("task", Thing::from(("due_task", normalized_id))))
+            .await?;
+        Ok(())
+    }
+
+    /// Marks accepted tasks due on or before `today` as announced and returns them,
+    /// shaped like reminders for the inbox
+    pub async fn take_due_tasks(&self, today: &str) -> Result<Vec<Reminder>> {
+        let mut response = self.db.query("
+            SELECT due, text FROM due_task WHERE status = 'open' AND notified = false AND due <= $today ORDER BY due;
+            UPDATE due_task SET notified = true WHERE status = 'open' AND notified = false AND due <= $today;
+        ")
+        .bind(("today", today.to_string()))
+        .await?;
+        Ok(response.take(0)?)
+    }
+
+    // ── Diary ───────────────────────────────────────────────────────────────
+
+    /// Kimi's entry for `date` (YYYY-MM-DD), if one was written
+    pub async fn load_diary_entry(&self, date: &str) -> Result<Option<String>> {
+        let entry: Option<DiaryEntry> = self.db
+            .select(("diary_entry", date))
+            .await?;
+        Ok(entry.map(|entry| encryption::open(entry.content)))
+    }
+
+    /// Writes or replaces the entry for `date`
+    pub async fn save_diary_entry(&self, date: &str, content: &str) -> Result<()> {
+        let _: Option<DiaryEntry> = self.db
+            .upsert(("diary_entry", date))
+            .content(serde_json::json!({
+                "date": date,
+                "content": encryption::seal(content),
+                "updated_at": chrono::Local::now().to_rfc3339(),
+            }))
+            .await?;
+        Ok(())
+    }
+
+    /// The newest `limit` entries, newest first
+    pub async fn load_diary_entries(&self, limit: usize) -> Result<Vec<DiaryEntry>> {
+        let mut response = self.db
+            .query("SELECT date, content FROM diary_entry ORDER BY date DESC LIMIT $limit")
+            .bind(("limit", limit))
+            .await?;
+        let entries: Vec<DiaryEntry> = response.take(0)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| DiaryEntry {
+                date: entry.date,
+                content: encryption::open(entry.content),
+            })
+            .collect())
+    }
+
+    pub async fn delete_diary_entry(&self, date: &str) -> Result<()> {
+        let _: Option<DiaryEntry> = self.db.delete(("diary_entry", date)).await?;
+        Ok(())
+    }
+
+    // ── Scheduled jobs ──────────────────────────────────────────────────────
+
+    /// Keeps a job added with `/schedule`; returns its key
+    pub async fn add_scheduled_job(&self, job: &crate::config::ScheduledJobConfig) -> Result<String> {
+        #[derive(Debug, Deserialize)]
+        struct Created {
+            id: Thing,
+        }
+
+        let mut response = self.db.query(
+            "CREATE scheduled_job SET name = $name, at = $at, days = $days, sources = $sources, prompt = $prompt, created_at = $now"
+        )
+        .bind(("name", job.name.clone()))
+        .bind(("at", job.at.clone()))
+        .bind(("days", job.days.clone()))
+        .bind(("sources", job.sources.clone()))
+        .bind(("prompt", job.prompt.clone()))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+        let created: Vec<Created> = response.take(0)?;
+        created
+            .into_iter()
+            .next()
+            .map(|created| created.id.to_string())
+            .ok_or_else(|| color_eyre::eyre::eyre!("Scheduled job was not saved"))
+    }
+
+    /// Jobs added with `/schedule`, oldest first, each with its key
+    pub async fn load_scheduled_jobs(&self) -> Result<Vec<(String, crate::config::ScheduledJobConfig)>> {
+        #[derive(Debug, Deserialize)]
+        struct JobRow {
+            id: Thing,
+            name: String,
+            at: String,
+            days: String,
+            sources: Vec<String>,
+            prompt: String,
+        }
+
+        let mut response = self.db
+            .query("SELECT id, name, at, days, sources, prompt, created_at FROM scheduled_job ORDER BY created_at ASC")
+            .await?;
+        let rows: Vec<JobRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let job = crate::config::ScheduledJobConfig {
+                    name: row.name,
+                    at: row.at,
+                    days: row.days,
+                    sources: row.sources,
+                    prompt: row.prompt,
+                    enabled: true,
+                };
+                (row.id.to_string(), job)
+            })
+            .collect())
+    }
+
+    pub async fn delete_scheduled_job(&self, key: &str) -> Result<()> {
+        let normalized_id = key.strip_prefix("scheduled_job:").unwrap_or(key);
+        self.db.query("DELETE $job; DELETE job_run WHERE job = $key")
+            .bind(("job", Thing::from(("scheduled_job", normalized_id))))
+            .bind(("key", key.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    /// When each job last ran (RFC 3339), by job key
+    pub async fn load_job_runs(&self) -> Result<std::collections::HashMap<String, String>> {
+        #[derive(Debug, Deserialize)]
+        struct RunRow {
+            job: String,
+            ran_at: String,
+        }
+
+        let mut response = self.db.query("SELECT job, ran_at FROM job_run").await?;
+        let rows: Vec<RunRow> = response.take(0)?;
+        Ok(rows.into_iter().map(|row| (row.job, row.ran_at)).collect())
+    }
+
+    pub async fn record_job_run(&self, key: &str, ran_at: &str) -> Result<()> {
+        self.db.query("
+            DELETE job_run WHERE job = $key;
+            CREATE job_run SET job = $key, ran_at = $ran_at;
+        ")
+        .bind(("key", key.to_string()))
+        .bind(("ran_at", ran_at.to_string()))
+        .await?;
+        Ok(())
+    }
+
+    // ── Notifications ───────────────────────────────────────────────────────
+
+    /// Adds an unread notification to the inbox
+    pub async fn save_notification(
+        &self,
+        kind: NotificationKind,
+        title: &str,
+        message: &str,
+    ) -> Result<()> {
+        self.db.query(
+            "CREATE notification SET kind = $kind, title = $title, message = $message, created_at = $now, read = false"
+        )
+        .bind(("kind", kind.as_str()))
+        .bind(("title", title.to_string()))
+        .bind(("message", message.to_string()))
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+        Ok(())
+    }
+
+    /// Loads the most recent notifications, newest first
+    pub async fn load_notifications(&self, limit: usize) -> Result<Vec<Notification>> {
+        #[derive(Debug, Deserialize)]
+        struct NotificationRow {
+            id: Thing,
+            kind: String,
+            title: String,
+            message: String,
+            created_at: String,
+            read: bool,
+        }
+
+        let mut response = self.db.query("
+            SELECT id, kind, title, message, created_at, read
+            FROM notification
+            ORDER BY created_at DESC
+            LIMIT $limit
+        ")
+        .bind(("limit", limit))
+        .await?;
+
+        let rows: Vec<NotificationRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Notification {
+                id: row.id.to_string(),
+                kind: NotificationKind::from_stored(&row.kind),
+                title: row.title,
+                message: row.message,
+                created_at: row.created_at,
+                read: row.read,
+            })
+            .collect())
+    }
+
+    pub async fn count_unread_notifications(&self) -> Result<usize> {
+        #[derive(Debug, Deserialize)]
+        struct CountRow {
+            count: usize,
+        }
+
+        let mut response = self.db.query(
+            "SELECT count() AS count FROM notification WHERE read = false GROUP ALL"
+        ).await?;
+        let rows: Vec<CountRow> = response.take(0)?;
+        Ok(rows.into_iter().next().map_or(0, |row| row.count))
+    }
+
+    pub async fn set_notification_read(&self, id: &str, read: bool) -> Result<()> {
+        let normalized_id = id.strip_prefix("notification:").unwrap_or(id);
+        self.db.query("UPDATE $notification SET read = $read")
+            .bind(("notification", Thing::from(("notification", normalized_id))))
+            .bind(("read", read))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn mark_all_notifications_read(&self) -> Result<()> {
+        self.db.query("UPDATE notification SET read = true WHERE read = false").await?;
+        Ok(())
+    }
+
+    pub async fn delete_notification(&self, id: &str) -> Result<()> {
+        let normalized_id = id.strip_prefix("notification:").unwrap_or(id);
+        self.db.query("DELETE $notification")
+            .bind(("notification", Thing::from(("notification", normalized_id))))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Decrypts message content loaded from the database
+fn open_messages(messages: Vec<StoredMessage>) -> Vec<StoredMessage> {
+    messages
+        .into_iter()
+        .map(|message| StoredMessage {
+            content: encryption::open(message.content),
+            quoted_text: message.quoted_text.map(encryption::open),
+            ..message
+        })
+        .collect()
+}
+
+/// RocksDB's "While lock file: ..." means another process; a second open in this
+/// process reports "lock hold by current process" and is a real error
+fn is_lock_error(error: &surrealdb::Error) -> bool {
+    error.to_string().contains("While lock file")
+}
+
+fn snapshot_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("kimi-snapshot-{}", std::process::id()))
+}
+
+fn process_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        return Path::new("/proc").join(pid.to_string()).exists();
+    }
+    if cfg!(windows) {
+        return std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()));
+    }
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Copies the embedded database into a fresh RocksDB at `target` while another process
+/// has it open. Copying the files one by one is not safe: compaction deletes SSTs and
+/// MANIFEST, CURRENT and the WAL can come from different moments. A secondary instance
+/// needs no lock, replays the owner's MANIFEST and WAL, and its iterator reads a single
+/// consistent version; keeping every SST open means files the owner compacts away
+/// mid-copy stay readable.
+fn snapshot_database(source: &Path, target: &Path) -> Result<()> {
+    let mut options = rocksdb::Options::default();
+    options.set_max_open_files(-1);
+    let secondary_dir = target.with_extension("secondary");
+    let secondary = rocksdb::DB::open_as_secondary(&options, source, secondary_dir.as_path())?;
+    secondary.try_catch_up_with_primary()?;
+
+    let mut copy_options = rocksdb::Options::default();
+    copy_options.create_if_missing(true);
+    let copy = rocksdb::DB::open(&copy_options, target)?;
+    let mut batch = rocksdb::WriteBatch::default();
+    for entry in secondary.iterator(rocksdb::IteratorMode::Start) {
+        let (key, value) = entry?;
+        batch.put(key, value);
+        if batch.len() >= SNAPSHOT_BATCH_SIZE {
+            copy.write(std::mem::take(&mut batch))?;
+        }
+    }
+    copy.write(batch)?;
+    copy.flush()?;
+    drop(copy);
+    drop(secondary);
+    let _ = std::fs::remove_dir_all(secondary_dir);
+    Ok(())
+}
+
+/// Stable FNV-1a hash of a memory's text, used as its `retrieval_stat` key
+fn content_key(content: &str) -> String {
+    let hash = content.trim().bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered_and_end_at_schema_version() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|migration| migration.version).collect();
+        let expected: Vec<u32> = (1..=SCHEMA_VERSION).collect();
+        assert_eq!(versions, expected);
+    }
 }
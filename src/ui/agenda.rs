@@ -0,0 +1,119 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::services::calendar;
+use crate::ui::components;
+
+// ── Agenda View ─────────────────────────────────────────────────────────────
+
+pub fn render_agenda_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Event list
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    if let [header, content, footer] = &chunks[..] {
+        render_agenda_header(frame, app, *header);
+        render_agenda_content(frame, app, *content);
+        render_agenda_footer(frame, *footer);
+    }
+}
+
+fn render_agenda_header(frame: &mut Frame, app: &App, area: Rect) {
+    let count = app.agenda_events.len();
+    let extra = vec![
+        Span::styled(" ", Style::default()),
+        Span::styled(
+            format!("({} events, next {} days)", count, app.agenda_days),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    components::render_view_header_with_extra(frame, area, "Agenda", extra);
+}
+
+fn render_agenda_content(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    if app.agenda_events.is_empty() {
+        let message = Paragraph::new("Nothing scheduled. Enjoy the free time.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = 0;
+    let mut current_date = None;
+
+    for (index, event) in app.agenda_events.iter().enumerate() {
+        if current_date != Some(event.date()) {
+            current_date = Some(event.date());
+            if !items.is_empty() {
+                items.push(ListItem::new(Line::from("")));
+            }
+            items.push(ListItem::new(Line::from(Span::styled(
+                calendar::day_heading(event.date(), today),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ))));
+        }
+
+        let is_selected = index == app.agenda_selected_index;
+        if is_selected {
+            selected_row = items.len();
+        }
+        let summary_style = if is_selected {
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let prefix = if is_selected { "> " } else { "  " };
+
+        let mut spans = vec![
+            Span::raw(prefix),
+            Span::styled(
+                format!("{:<13}", event.time_label()),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(event.summary.as_str(), summary_style),
+        ];
+        if let Some(location) = &event.location {
+            spans.push(Span::styled(
+                format!(" @ {}", location),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        items.push(ListItem::new(Line::from(spans)));
+    }
+
+    let mut state = ListState::default();
+    state.select(Some(selected_row));
+    let list = List::new(items).block(block);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_agenda_footer(frame: &mut Frame, area: Rect) {
+    components::render_navigation_footer(
+        frame,
+        area,
+        "AGENDA",
+        &[("↑↓", "navigate"), ("r", "refresh"), ("Esc", "back")],
+        &[],
+    );
+}
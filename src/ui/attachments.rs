@@ -0,0 +1,68 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::documents::format_size;
+use crate::ui::components;
+
+/// Renders the attachment manager overlay on top of the chat view
+pub fn render_attachment_panel(f: &mut Frame, app: &App) {
+    let title = format!("Attachments ({})", app.chat_attachments.len());
+    let area = components::render_modal_frame(f, f.area(), 70, 50, &title);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([list_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let items: Vec<ListItem> = app
+        .chat_attachments
+        .iter()
+        .enumerate()
+        .map(|(index, attachment)| {
+            let is_selected = index == app.attachment_selected_index;
+            ListItem::new(Line::from(vec![
+                Span::raw(components::selection_prefix(is_selected)),
+                Span::styled(
+                    format!("{}. {}", index + 1, attachment.label()),
+                    components::selected_name_style(is_selected),
+                ),
+                Span::styled(
+                    format!("  {}", format_size(attachment.size_bytes())),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    format!("  {}", attachment.preview()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.attachment_selected_index));
+    f.render_stateful_widget(List::new(items), *list_area, &mut state);
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" select  ", Style::default().fg(Color::White)),
+        Span::styled(" Shift+↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" reorder  ", Style::default().fg(Color::White)),
+        Span::styled(" d ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" remove  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
@@ -0,0 +1,112 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::services::tool_policy::ToolDecision;
+use crate::storage::ToolAuditRecord;
+use crate::ui::components;
+
+/// Renders `/audit`: recent tool calls, with the selected call's arguments and result below
+pub fn render_audit(f: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(f, f.area(), 90, 80, "Tool audit");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Percentage(45),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    let Some([list_area, detail_area, help_area]) =
+        chunks.get(0..3).and_then(|s| <&[_; 3]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    if app.audit_records.is_empty() {
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No tool calls recorded yet. Every tool the agent uses is listed here.",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            *list_area,
+        );
+    } else {
+        let items: Vec<ListItem> = app
+            .audit_records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| audit_item(record, index == app.audit_selected_index))
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(app.audit_selected_index));
+        f.render_stateful_widget(List::new(items), *list_area, &mut state);
+    }
+
+    if let Some(record) = app.audit_records.get(app.audit_selected_index) {
+        let label = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Arguments: ", label),
+                Span::styled(record.arguments.clone(), Style::default().fg(Color::White)),
+            ]),
+            Line::from(""),
+        ];
+        lines.extend(
+            record
+                .result
+                .lines()
+                .map(|line| Line::from(Span::styled(line.to_string(), Style::default().fg(Color::Gray)))),
+        );
+        f.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .title(" Result ")
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+            *detail_area,
+        );
+    }
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" select  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn audit_item(record: &ToolAuditRecord, is_selected: bool) -> ListItem<'static> {
+    let color = match record.decision {
+        ToolDecision::Run => Color::Green,
+        ToolDecision::DryRun => Color::Yellow,
+        ToolDecision::Denied => Color::Red,
+    };
+    let created_at = chrono::DateTime::parse_from_rfc3339(&record.created_at).map_or_else(
+        |_| record.created_at.clone(),
+        |dt| dt.format("%b %d, %H:%M:%S").to_string(),
+    );
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            components::selection_prefix(is_selected),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(format!("{:<8}", record.decision.as_str()), Style::default().fg(color)),
+        Span::styled(format!("{}  ", created_at), Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{:<22}", record.tool),
+            components::selected_name_style(is_selected),
+        ),
+        Span::styled(record.arguments.clone(), Style::default().fg(Color::DarkGray)),
+    ]))
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+/// Quick capture: one input, and what was saved this session below it
+pub fn render_capture_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(3), // Input
+            Constraint::Min(0),    // Captured so far
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let [header, input, body, footer] = &chunks[..] else {
+        return;
+    };
+
+    let mut destinations = Vec::new();
+    if app.capture.memory {
+        destinations.push("memory".to_string());
+    }
+    if app.capture.obsidian {
+        destinations.push(app.capture.inbox_note.clone());
+    }
+    let extra = vec![Span::styled(
+        format!(" · saves to {}", destinations.join(" + ")),
+        Style::default().fg(Color::DarkGray),
+    )];
+    components::render_view_header_with_extra(frame, *header, "Capture", extra);
+
+    let config = components::TextInputConfig::new(app.capture_input.content(), " Thought ")
+        .with_placeholder("Type and press Enter — nothing is sent to the model")
+        .with_cursor_position(app.capture_input.cursor_position());
+    components::render_text_input(frame, *input, config);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Captured ")
+        .border_style(Style::default().fg(Color::DarkGray));
+    let lines: Vec<Line> = if app.captures.is_empty() {
+        vec![Line::from(Span::styled(
+            "Nothing captured yet this session",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        // Newest first
+        app.captures
+            .iter()
+            .rev()
+            .map(|(time, text, saved_to)| {
+                let saved_style = if saved_to.starts_with("not saved") {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Line::from(vec![
+                    Span::styled(format!("{} ", time), Style::default().fg(Color::Cyan)),
+                    Span::styled(text.clone(), Style::default().fg(Color::White)),
+                    Span::styled(format!("  → {}", saved_to), saved_style),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), *body);
+
+    components::render_navigation_footer(
+        frame,
+        *footer,
+        "CAPTURE",
+        &[("Enter", "save"), ("Esc", "back")],
+        &[],
+    );
+    if let Some(message) = app.status_toast_message() {
+        let inner = footer.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        components::render_status_toast(frame, inner, message);
+    }
+}
@@ -5,18 +5,38 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
+use std::ops::Range;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::components;
 
 use crate::app::{App, MessageRole};
+use crate::services::verification::Verdict;
 
 /// Primary chat view with header, messages, input, and footer
 pub fn render_chat_view(f: &mut Frame, app: &App) {
-    let has_suggestions = !app.follow_up_suggestions.is_empty() && !app.is_loading;
+    let has_suggestions = has_follow_up_suggestions(app);
     let suggestion_height = if has_suggestions { 3 } else { 0 };
 
-    let chunks = Layout::default()
+    let chunks = chat_layout(f.area(), suggestion_height);
+
+    if let [header, history, suggestions, input, footer] = &chunks[..] {
+        render_chat_header(f, app, *header);
+        render_chat_history(f, app, *history);
+        if has_suggestions {
+            render_follow_up_suggestions(f, app, *suggestions);
+        }
+        render_chat_input(f, app, *input);
+        render_chat_footer(f, app, *footer);
+    }
+}
+
+fn has_follow_up_suggestions(app: &App) -> bool {
+    !app.follow_up_suggestions.is_empty() && !app.is_loading
+}
+
+fn chat_layout(area: Rect, suggestion_height: u16) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),                     // Header
@@ -25,17 +45,87 @@ pub fn render_chat_view(f: &mut Frame, app: &App) {
             Constraint::Length(3),                      // Input
             Constraint::Length(3),                      // Footer
         ])
-        .split(f.area());
+        .split(area)
+}
 
-    if let [header, history, suggestions, input, footer] = &chunks[..] {
-        render_chat_header(f, app, *header);
-        render_chat_history(f, app, *history);
-        if has_suggestions {
-            render_follow_up_suggestions(f, app, *suggestions);
+/// Scroll offset (lines from the bottom) that brings message `index` to the top of
+/// the conversation pane for a terminal of size `area`
+pub fn scroll_offset_for_message(app: &App, area: Rect, index: usize) -> Option<usize> {
+    let suggestion_height = if has_follow_up_suggestions(app) { 3 } else { 0 };
+    let chunks = chat_layout(area, suggestion_height);
+    let history = chunks.get(1)?;
+    let (lines, message_lines) = build_history_lines(app, *history);
+    let visible_height = history.height.saturating_sub(2) as usize;
+    let max_scroll_offset = lines.len().saturating_sub(visible_height);
+    let start = message_lines.get(index)?.start;
+    Some(max_scroll_offset.saturating_sub(start.saturating_sub(1)))
+}
+
+/// First message at least partly visible in the conversation pane, for a terminal of size `area`
+pub fn first_visible_message(app: &App, area: Rect) -> Option<usize> {
+    let suggestion_height = if has_follow_up_suggestions(app) { 3 } else { 0 };
+    let chunks = chat_layout(area, suggestion_height);
+    let history = chunks.get(1)?;
+    let (lines, message_lines) = build_history_lines(app, *history);
+    let visible_height = history.height.saturating_sub(2) as usize;
+    let (scroll_from_top, _) = calculate_scroll_position(
+        lines.len(),
+        visible_height,
+        app.chat_scroll_offset,
+        app.chat_auto_scroll,
+    );
+    message_lines.iter().position(|range| range.end > scroll_from_top)
+}
+
+/// What a click at (`column`, `row`) landed on in the conversation pane
+pub struct ChatHit {
+    pub message_index: usize,
+    /// URL under the pointer, when the click was on one
+    pub url: Option<String>,
+}
+
+/// Maps a mouse position to the chat message drawn there, for a terminal of size `area`
+pub fn chat_hit_at(app: &App, area: Rect, column: u16, row: u16) -> Option<ChatHit> {
+    let suggestion_height = if has_follow_up_suggestions(app) { 3 } else { 0 };
+    let chunks = chat_layout(area, suggestion_height);
+    let history = chunks.get(1)?;
+    // Inside the border
+    if column <= history.x
+        || column >= history.x + history.width.saturating_sub(1)
+        || row <= history.y
+        || row >= history.y + history.height.saturating_sub(1)
+    {
+        return None;
+    }
+    let (lines, message_lines) = build_history_lines(app, *history);
+    let visible_height = history.height.saturating_sub(2) as usize;
+    let (scroll_from_top, _) = calculate_scroll_position(
+        lines.len(),
+        visible_height,
+        app.chat_scroll_offset,
+        app.chat_auto_scroll,
+    );
+    let line_index = scroll_from_top + usize::from(row - history.y - 1);
+    let message_index = message_lines.iter().position(|range| range.contains(&line_index))?;
+    let text: String = lines
+        .get(line_index)
+        .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+        .unwrap_or_default();
+    let url = char_index_at_column(&text, usize::from(column - history.x - 1))
+        .and_then(|position| crate::services::links::url_at(&text, position));
+    Some(ChatHit { message_index, url })
+}
+
+/// Char index of the character drawn at display column `column`
+fn char_index_at_column(text: &str, column: usize) -> Option<usize> {
+    let mut width = 0;
+    for (index, character) in text.chars().enumerate() {
+        width += character.width().unwrap_or(0);
+        if width > column {
+            return Some(index);
         }
-        render_chat_input(f, app, *input);
-        render_chat_footer(f, app, *footer);
     }
+    None
 }
 
 fn render_chat_header(f: &mut Frame, app: &App, area: Rect) {
@@ -51,7 +141,7 @@ fn render_chat_header(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let version_text = format!("v{}", env!("CARGO_PKG_VERSION"));
-    let title_spans = vec![
+    let mut title_spans = vec![
         Span::raw(" "),
         Span::styled(
             "Kimi",
@@ -64,6 +154,16 @@ fn render_chat_header(f: &mut Frame, app: &App, area: Rect) {
         Span::styled(" ", Style::default().fg(Color::DarkGray)),
         Span::styled(version_text, Style::default().fg(Color::DarkGray)),
     ];
+    if app.incognito {
+        title_spans.push(Span::raw("  "));
+        title_spans.push(Span::styled(
+            " INCOGNITO ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
 
     let model_name = app
         .current_agent
@@ -122,10 +222,10 @@ struct MessageStyles {
 
 impl MessageStyles {
     /// Returns appropriate styles based on message role
-    fn for_role(role: &MessageRole, assistant_name: Option<&str>) -> Self {
+    fn for_role(role: &MessageRole, display_name: Option<&str>) -> Self {
         match role {
             MessageRole::User => Self {
-                prefix: "You".to_string(),
+                prefix: display_name.unwrap_or("You").to_string(),
                 prefix_style: Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
@@ -133,7 +233,7 @@ impl MessageStyles {
                 role_indicator: ">",
             },
             MessageRole::Assistant => Self {
-                prefix: assistant_name.unwrap_or("Kimi").to_string(),
+                prefix: display_name.unwrap_or("Kimi").to_string(),
                 prefix_style: Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
@@ -205,11 +305,12 @@ fn render_regular_message(
     message: &crate::app::ChatMessage,
     styles: &MessageStyles,
     max_content_width: usize,
+    show_stats: bool,
 ) -> Vec<Line<'static>> {
     let mut message_lines = Vec::new();
 
     // Message header with role indicator
-    let header_spans = vec![
+    let mut header_spans = vec![
         Span::styled(
             format!(" {} ", styles.role_indicator),
             Style::default().fg(Color::DarkGray),
@@ -220,21 +321,152 @@ fn render_regular_message(
             Style::default().fg(Color::DarkGray),
         ),
     ];
+    if let Some(usage) = &message.context_usage
+        && !usage.skipped.is_empty()
+    {
+        let skipped: Vec<String> = usage
+            .skipped
+            .iter()
+            .map(|section| section.label().to_lowercase())
+            .collect();
+        header_spans.push(Span::styled(
+            format!("  without {}", skipped.join(", ")),
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        ));
+    }
+    if let Some(check) = &message.check {
+        let color = match check.verdict {
+            Verdict::Pass => Color::DarkGray,
+            Verdict::Warn => Color::Yellow,
+            Verdict::Fail => Color::Red,
+        };
+        header_spans.push(Span::styled(
+            format!("  {}", check.badge()),
+            Style::default().fg(color).add_modifier(Modifier::DIM),
+        ));
+    }
+    if show_stats && let Some(stats) = &message.stats {
+        header_spans.push(Span::styled(
+            format!("  {}", stats.describe()),
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+        ));
+    }
     // Context usage info removed - cleaner UI
     message_lines.push(Line::from(header_spans));
 
     // Message content with proper indentation
     let max_empty_lines = 1;
     let wrapped_content = wrap_text(&message.content, max_content_width, max_empty_lines);
+    let underline_entities = message.role == MessageRole::Assistant;
     for content_line in wrapped_content {
+        let mut spans = vec![Span::raw("   ")];
+        if underline_entities {
+            spans.extend(entity_spans(content_line, styles.content_style));
+        } else {
+            spans.push(Span::styled(content_line, styles.content_style));
+        }
+        message_lines.push(Line::from(spans));
+    }
+    if let Some(usage) = &message.context_usage
+        && !usage.sources.is_empty()
+    {
+        let cited = crate::services::citations::cited_numbers(&message.content, usage.sources.len());
+        let listed = if cited.is_empty() {
+            format!("{} sources", usage.sources.len())
+        } else {
+            cited
+                .iter()
+                .filter_map(|number| {
+                    let source = usage.sources.get(number - 1)?;
+                    Some(format!("[{}] {}", number, source.title))
+                })
+                .collect::<Vec<_>>()
+                .join(" · ")
+        };
+        let footer: String = format!("{} · /sources", listed)
+            .chars()
+            .take(max_content_width)
+            .collect();
         message_lines.push(Line::from(vec![
             Span::raw("   "),
-            Span::styled(content_line, styles.content_style),
+            Span::styled(
+                footer,
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+            ),
         ]));
     }
     message_lines
 }
 
+/// Half-block thumbnails of a message's pictures with their tokens, then attached files
+fn render_message_images(message: &crate::app::ChatMessage, max_content_width: usize) -> Vec<Line<'static>> {
+    let token_style = Style::default().fg(Color::Cyan);
+    let path_style = Style::default().fg(Color::DarkGray).add_modifier(Modifier::UNDERLINED);
+    let caption = |token: String, path: &std::path::Path| {
+        let path: String = path
+            .display()
+            .to_string()
+            .chars()
+            .take(max_content_width.saturating_sub(token.chars().count() + 1))
+            .collect();
+        Line::from(vec![
+            Span::raw("   "),
+            Span::styled(token, token_style),
+            Span::raw(" "),
+            Span::styled(path, path_style),
+        ])
+    };
+    let mut image_lines = Vec::new();
+    for image in &message.images {
+        for row in &image.preview {
+            let mut spans = vec![Span::raw("   ")];
+            spans.extend(row.iter().take(max_content_width).map(|(top, bottom)| {
+                Span::styled(
+                    "▀",
+                    Style::default()
+                        .fg(Color::Rgb(top[0], top[1], top[2]))
+                        .bg(Color::Rgb(bottom[0], bottom[1], bottom[2])),
+                )
+            }));
+            image_lines.push(Line::from(spans));
+        }
+        image_lines.push(caption(image.token(), &image.path));
+    }
+    for path in &message.files {
+        let name = path
+            .file_name()
+            .map_or_else(|| "file".to_string(), |name| name.to_string_lossy().into_owned());
+        image_lines.push(caption(format!("[[file:{}]]", name), path));
+    }
+    image_lines
+}
+
+/// Splits a wrapped line so its URLs, paths and dates are underlined
+fn entity_spans(line: String, style: Style) -> Vec<Span<'static>> {
+    let entities = crate::services::entities::find_entities(&line);
+    if entities.is_empty() {
+        return vec![Span::styled(line, style)];
+    }
+    let underlined = style.add_modifier(Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut chars = line.chars();
+    let mut position = 0;
+    for entity in entities {
+        let before: String = chars.by_ref().take(entity.range.start.saturating_sub(position)).collect();
+        if !before.is_empty() {
+            spans.push(Span::styled(before, style));
+        }
+        let text: String = chars.by_ref().take(entity.range.len()).collect();
+        spans.push(Span::styled(text, underlined));
+        position = entity.range.end;
+    }
+    let rest: String = chars.collect();
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest, style));
+    }
+    spans
+}
+
 /// Adds loading indicator animation
 fn add_loading_indicator(
     lines: &mut Vec<Line>,
@@ -243,9 +475,7 @@ fn add_loading_indicator(
     frame: u8,
     suffix: Option<String>,
 ) {
-    let dots_frames = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
-    let frame_index = (frame as usize) % dots_frames.len();
-    let dots = dots_frames.get(frame_index).copied().unwrap_or("⣷").to_string();
+    let dots = super::compat::spinner(frame);
     let assistant_name = if app.personality_enabled {
         app.personality_name.as_deref().unwrap_or("Kimi")
     } else {
@@ -324,8 +554,10 @@ fn calculate_scroll_position(
     (scroll_from_top, actual_scroll_offset)
 }
 
-fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
+/// All conversation lines plus the lines each chat message occupies
+fn build_history_lines(app: &App, area: Rect) -> (Vec<Line<'_>>, Vec<Range<usize>>) {
     let mut lines: Vec<Line> = Vec::new();
+    let mut message_lines = Vec::with_capacity(app.chat_history.len());
     let content_width = area.width.saturating_sub(2) as usize;
     let max_content_width = content_width.saturating_sub(6).max(1);
     let max_system_width = content_width.saturating_sub(4).max(1);
@@ -346,14 +578,14 @@ fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
 
     // Build all message lines
     for message in &app.chat_history {
-        let assistant_name = message.display_name.as_deref();
-        let styles = MessageStyles::for_role(&message.role, assistant_name);
+        let styles = MessageStyles::for_role(&message.role, message.display_name.as_deref());
 
         add_spacing(&mut lines, 1);
 
         if message.role == MessageRole::User {
             add_spacing(&mut lines, 1);
         }
+        let message_start = lines.len();
 
         if message.role == MessageRole::System {
             lines.extend(render_system_message(
@@ -366,8 +598,26 @@ fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
                 message,
                 &styles,
                 max_content_width,
+                app.show_message_stats,
             ));
         }
+        lines.extend(render_message_images(message, max_content_width));
+
+        let is_selected =
+            app.message_selection_active && message_lines.len() == app.selected_message_index;
+        if is_selected || message.excluded_from_context {
+            let mut style = Style::default();
+            if is_selected {
+                style = style.bg(Color::Rgb(40, 40, 60));
+            }
+            if message.excluded_from_context {
+                style = style.add_modifier(Modifier::DIM | Modifier::CROSSED_OUT);
+            }
+            for line in lines.iter_mut().skip(message_start) {
+                *line = std::mem::take(line).patch_style(style);
+            }
+        }
+        message_lines.push(message_start..lines.len());
     }
 
     // Add loading indicator if processing
@@ -389,7 +639,10 @@ fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
     // Display each active download
     for download in &app.active_downloads {
         add_spacing(&mut lines, 1);
-        let progress = download.progress.map(|value| format!("{}%", value));
+        let progress = download.progress.map(|value| match &download.speed {
+            Some(speed) => format!("{}% · {}", value, speed),
+            None => format!("{}%", value),
+        });
 
         // Truncate URL for display if too long (char-safe to avoid UTF-8 panic)
         let display_url = if download.url.chars().count() > 50 {
@@ -405,7 +658,24 @@ fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
 
     if app.conversion_active {
         add_spacing(&mut lines, 1);
-        add_loading_indicator(&mut lines, app, "converting", app.conversion_frame, None);
+        let progress = (app.conversion_total > 1)
+            .then(|| format!("{}/{}", app.conversion_done, app.conversion_total));
+        add_loading_indicator(&mut lines, app, "converting", app.conversion_frame, progress);
+    }
+
+    if app.active_transcriptions > 0 {
+        add_spacing(&mut lines, 1);
+        let label = if app.active_transcriptions == 1 {
+            "transcribing".to_string()
+        } else {
+            format!("transcribing {} files", app.active_transcriptions)
+        };
+        add_loading_indicator(&mut lines, app, &label, app.transcription_frame, None);
+    }
+
+    if app.active_image_generations > 0 {
+        add_spacing(&mut lines, 1);
+        add_loading_indicator(&mut lines, app, "imagining", app.image_frame, None);
     }
 
     if app.summary_active {
@@ -415,6 +685,12 @@ fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
 
     // Bottom padding
     add_spacing(&mut lines, 1);
+    (lines, message_lines)
+}
+
+fn render_chat_history(frame: &mut Frame, app: &App, area: Rect) {
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let (lines, _) = build_history_lines(app, area);
 
     // Calculate viewport and scroll position
     let total_lines = lines.len();
@@ -619,10 +895,16 @@ fn render_follow_up_suggestions(frame: &mut Frame, app: &App, area: Rect) {
         }
     }
 
+    let hint = if app.suggestion_mode_active {
+        " ←→/Tab choose · Enter send · Esc cancel "
+    } else {
+        " ↑ follow-ups "
+    };
     let paragraph = Paragraph::new(Line::from(spans))
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .title(Span::styled(hint, Style::default().fg(Color::DarkGray)))
                 .border_style(Style::default().fg(Color::DarkGray)),
         )
         .alignment(Alignment::Left);
@@ -667,7 +949,27 @@ fn render_chat_input(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_chat_footer(f: &mut Frame, app: &App, area: Rect) {
-    let keybindings = [("/", "menu"), ("Tab", "switch"), ("^R", "speak"), ("Esc", "history")];
+    let (mode, keybindings): (&str, &[(&str, &str)]) = if app.message_selection_active {
+        (
+            "SELECT",
+            &[
+                ("↑↓", "move"),
+                ("c", "copy"),
+                ("s", "speak"),
+                ("d", "context"),
+                ("q", "quote"),
+                ("t", "translate"),
+                ("n", "snippet"),
+                ("e", "actions"),
+                ("Esc", "done"),
+            ],
+        )
+    } else {
+        (
+            "CHAT",
+            &[("/", "menu"), ("Tab", "switch"), ("^K", "select"), ("^R", "speak"), ("Esc", "history")],
+        )
+    };
 
     let border_block = ratatui::widgets::Block::default()
         .borders(ratatui::widgets::Borders::ALL)
@@ -696,8 +998,58 @@ fn render_chat_footer(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let menu_enabled = app.chat_input.is_empty();
-    let keybinding_spans =
-        build_footer_spans("CHAT", &keybindings, app.personality_enabled, menu_enabled);
+    let speech = app.tts_service.as_ref().and_then(crate::services::TTSService::status);
+    let mut keybinding_spans =
+        build_footer_spans(mode, keybindings, app.personality_enabled, menu_enabled);
+    if let Some(speech) = speech {
+        keybinding_spans.splice(2..2, speech_indicator_spans(&speech));
+    }
+    if app.unread_notifications > 0 {
+        keybinding_spans.splice(
+            2..2,
+            [
+                Span::raw(" "),
+                Span::styled(
+                    format!(" ✉ {} ", app.unread_notifications),
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ],
+        );
+    }
+    if !app.health.is_empty() {
+        let mut dots = vec![Span::raw(" ")];
+        for service in &app.health {
+            let initial = service.name.chars().next().unwrap_or('?');
+            dots.push(Span::styled(format!(" {}", initial), Style::default().fg(Color::DarkGray)));
+            dots.push(Span::styled(
+                "●",
+                Style::default().fg(super::status::level_color(service.level)),
+            ));
+        }
+        keybinding_spans.splice(2..2, dots);
+    }
+    let countdowns = timer_spans(app);
+    if !countdowns.is_empty() {
+        keybinding_spans.splice(2..2, countdowns);
+    }
+    if let Some(speaker) = &app.speaker {
+        keybinding_spans.splice(
+            2..2,
+            [
+                Span::raw(" "),
+                Span::styled(
+                    format!(" as {} ", speaker),
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ],
+        );
+    }
     f.render_widget(
         Paragraph::new(Line::from(keybinding_spans)),
         left_area,
@@ -714,6 +1066,66 @@ fn render_chat_footer(f: &mut Frame, app: &App, area: Rect) {
     }
 }
 
+/// Remaining time of the soonest timers and the running stopwatch
+fn timer_spans(app: &App) -> Vec<Span<'static>> {
+    let badge = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    for timer in app.timers.iter().take(3) {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!(
+                " ⏱ {} {} ",
+                timer.label.as_deref().unwrap_or("timer"),
+                crate::services::timers::format_remaining(timer.remaining())
+            ),
+            badge,
+        ));
+    }
+    if app.timers.len() > 3 {
+        spans.push(Span::styled(format!(" +{}", app.timers.len() - 3), Style::default().fg(Color::DarkGray)));
+    }
+    if let Some(started) = app.stopwatch_started {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!(" ⏲ {} ", crate::services::timers::format_remaining(started.elapsed())),
+            badge,
+        ));
+    }
+    spans
+}
+
+/// "Now speaking" badge with elapsed time and how many messages are queued
+fn speech_indicator_spans(speech: &crate::services::tts::SpeechStatus) -> Vec<Span<'static>> {
+    let seconds = speech.elapsed.as_secs();
+    let (icon, background) = if speech.paused {
+        ("⏸", Color::DarkGray)
+    } else {
+        ("▶", Color::Green)
+    };
+    let queued = if speech.queued > 0 {
+        format!(" +{}", speech.queued)
+    } else {
+        String::new()
+    };
+    vec![
+        Span::raw(" "),
+        Span::styled(
+            format!(" {} {}:{:02}{} ", icon, seconds / 60, seconds % 60, queued),
+            Style::default()
+                .fg(Color::Black)
+                .bg(background)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {}… ^O pause ^N skip", speech.preview),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]
+}
+
 fn build_footer_spans(
     mode: &str,
     keybindings: &[(&str, &str)],
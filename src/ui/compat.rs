@@ -0,0 +1,174 @@
+use crate::config::Compatibility;
+use ratatui::{buffer::Buffer, style::Color};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once at startup from `[display] compatibility`
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `TERM` values whose fonts lack box drawing, braille or 256 colors (besides `vt*`)
+const LIMITED_TERMS: &[&str] = &["linux", "screen", "dumb", "ansi", "cons25"];
+
+const SPINNER: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+const ASCII_SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// Decides once whether frames are drawn in compatibility mode
+pub fn configure(compatibility: Compatibility) {
+    let enabled = match compatibility {
+        Compatibility::On => true,
+        Compatibility::Off => false,
+        Compatibility::Auto => is_limited_terminal(
+            std::env::var("TERM").ok().as_deref(),
+            locale().as_deref(),
+        ),
+    };
+    if enabled {
+        tracing::info!("terminal compatibility mode on");
+    }
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The first of LC_ALL, LC_CTYPE and LANG that is set, like the C library picks it
+fn locale() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+fn is_limited_terminal(term: Option<&str>, locale: Option<&str>) -> bool {
+    let limited_term = term.is_some_and(|term| LIMITED_TERMS.contains(&term) || term.starts_with("vt"));
+    let ascii_locale = locale.is_some_and(|locale| {
+        let locale = locale.to_lowercase();
+        !locale.contains("utf-8") && !locale.contains("utf8")
+    });
+    limited_term || ascii_locale
+}
+
+/// Frame `frame` of the loading spinner: braille dots, or `| / - \` in compatibility mode
+#[must_use]
+pub fn spinner(frame: u8) -> &'static str {
+    let frames: &[&'static str] = if enabled() { &ASCII_SPINNER } else { &SPINNER };
+    frames.get(usize::from(frame) % frames.len()).copied().unwrap_or("*")
+}
+
+/// Rewrites a drawn frame for a limited terminal: ASCII for glyphs the console font
+/// lacks and the nearest of the 16 basic colors for RGB and 256-color styles
+pub fn downgrade(buffer: &mut Buffer) {
+    if !enabled() {
+        return;
+    }
+    for cell in &mut buffer.content {
+        if let Some(replacement) = ascii_symbol(cell.symbol()) {
+            cell.set_symbol(replacement);
+        }
+        cell.fg = basic_color(cell.fg);
+        cell.bg = basic_color(cell.bg);
+    }
+}
+
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let character = chars.next()?;
+    if chars.next().is_some() || character.is_ascii() {
+        return None;
+    }
+    let replacement = match character {
+        '─' | '━' | '═' | '—' | '–' => "-",
+        '│' | '┃' | '║' => "|",
+        '┌' | '┐' | '└' | '┘' | '╭' | '╮' | '╰' | '╯' | '┏' | '┓' | '┗' | '┛' | '╔' | '╗' | '╚'
+        | '╝' | '├' | '┤' | '┬' | '┴' | '┼' => "+",
+        '●' | '◆' | '★' => "*",
+        '○' | '◇' => "o",
+        '█' | '▓' | '▒' => "#",
+        '░' => ".",
+        '▶' | '▸' | '›' | '→' => ">",
+        '◀' | '‹' | '←' => "<",
+        '↑' => "^",
+        '↓' => "v",
+        '⏸' => "=",
+        '…' => ".",
+        '“' | '”' => "\"",
+        '‘' | '’' => "'",
+        '✓' | '✔' => "v",
+        '✗' | '✘' => "x",
+        '✉' => "@",
+        '\u{2800}'..='\u{28ff}' => "*",
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+fn basic_color(color: Color) -> Color {
+    match color {
+        Color::Rgb(red, green, blue) => nearest_basic(red, green, blue),
+        Color::Indexed(index) => indexed_color(index),
+        other => other,
+    }
+}
+
+/// The 256-color palette: 16 basic colors, a 6×6×6 cube, then 24 grays
+fn indexed_color(index: u8) -> Color {
+    const BASIC: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    let level = |step: u8| if step == 0 { 0 } else { 55 + step * 40 };
+    match index {
+        0..=15 => BASIC.get(usize::from(index)).copied().unwrap_or(Color::Reset),
+        16..=231 => {
+            let cube = index - 16;
+            nearest_basic(level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            nearest_basic(gray, gray, gray)
+        }
+    }
+}
+
+fn nearest_basic(red: u8, green: u8, blue: u8) -> Color {
+    let brightest = red.max(green).max(blue);
+    if brightest < 64 {
+        // Dim tints (selection backgrounds) read best as the terminal's dark gray
+        return if brightest < 24 { Color::Black } else { Color::DarkGray };
+    }
+    let half = brightest / 2;
+    let channels = (red > half, green > half, blue > half);
+    let bright = brightest > 191;
+    match (channels, bright) {
+        ((true, true, true), true) => Color::White,
+        ((true, true, true), false) => Color::Gray,
+        ((true, false, false), false) => Color::Red,
+        ((true, false, false), true) => Color::LightRed,
+        ((false, true, false), false) => Color::Green,
+        ((false, true, false), true) => Color::LightGreen,
+        ((true, true, false), false) => Color::Yellow,
+        ((true, true, false), true) => Color::LightYellow,
+        ((false, false, true), false) => Color::Blue,
+        ((false, false, true), true) => Color::LightBlue,
+        ((true, false, true), false) => Color::Magenta,
+        ((true, false, true), true) => Color::LightMagenta,
+        ((false, true, true), false) => Color::Cyan,
+        ((false, true, true), true) => Color::LightCyan,
+        ((false, false, false), _) => Color::DarkGray,
+    }
+}
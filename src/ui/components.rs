@@ -174,7 +174,11 @@ fn slice_by_chars(value: &str, start: usize, end: usize) -> String {
 fn build_input_spans(content: &str) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
     let mut index = 0;
-    while let Some(start_offset) = content[index..].find("[[image:") {
+    while let Some((start_offset, prefix)) = ["[[image:", "[[file:"]
+        .iter()
+        .filter_map(|prefix| content[index..].find(prefix).map(|offset| (offset, *prefix)))
+        .min_by_key(|(offset, _)| *offset)
+    {
         let start_index = index + start_offset;
         if start_index > index {
             spans.push(Span::styled(
@@ -184,13 +188,14 @@ fn build_input_spans(content: &str) -> Vec<Span<'static>> {
         }
         if let Some(end_offset) = content[start_index..].find("]]") {
             let end_index = start_index + end_offset + 2;
-            let label = content[start_index + 8..start_index + end_offset].trim();
+            let label = content[start_index + prefix.len()..start_index + end_offset].trim();
             let chip_text = format!(" {} ", label);
+            let chip_color = if prefix == "[[file:" { Color::Cyan } else { Color::Yellow };
             spans.push(Span::styled(
                 chip_text,
                 Style::default()
                     .fg(Color::Black)
-                    .bg(Color::Yellow)
+                    .bg(chip_color)
                     .add_modifier(Modifier::BOLD),
             ));
             index = end_index;
@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::services::secrets::SecretsBackend;
 use crate::ui::components;
 
 /// Render full-screen connect view with header, provider list, and footer
@@ -28,7 +29,17 @@ pub fn render_connect_view(frame: &mut Frame, app: &App) {
 }
 
 fn render_connect_header(frame: &mut Frame, area: Rect) {
-    components::render_view_header(frame, area, "Connect");
+    let storage = match crate::services::secrets::backend() {
+        Some(SecretsBackend::Keyring) => " · keys in OS keyring",
+        Some(SecretsBackend::EncryptedFile) => " · keys in encrypted file",
+        None => " · keys not persisted (secrets backend unavailable)",
+    };
+    components::render_view_header_with_extra(
+        frame,
+        area,
+        "Connect",
+        vec![Span::styled(storage, Style::default().fg(Color::DarkGray))],
+    );
 }
 
 fn render_provider_list(frame: &mut Frame, app: &App, area: Rect) {
@@ -69,22 +80,11 @@ fn render_provider_list(frame: &mut Frame, app: &App, area: Rect) {
 /// Returns (status_text, status_style, icon) for a given provider
 fn provider_status<'a>(app: &App, provider: &str) -> (&'a str, Style, &'a str) {
     match provider {
-        "ElevenLabs" if !app.connect_elevenlabs_key.is_empty() => {
-            ("configured", Style::default().fg(Color::Green), "●")
-        }
-        "Venice AI" if !app.connect_venice_key.is_empty() => {
-            ("configured", Style::default().fg(Color::Green), "●")
-        }
-        "Gab AI" if !app.connect_gab_key.is_empty() => {
-            ("configured", Style::default().fg(Color::Green), "●")
-        }
-        "Brave Search" if !app.connect_brave_key.is_empty() => {
-            ("configured", Style::default().fg(Color::Green), "●")
-        }
-        "Obsidian" if !app.connect_obsidian_vault.trim().is_empty() => {
+        _ if app.is_provider_configured(provider) => {
             ("configured", Style::default().fg(Color::Green), "●")
         }
-        "ElevenLabs" | "Venice AI" | "Gab AI" | "Brave Search" | "Obsidian" => {
+        "ElevenLabs" | "Venice AI" | "Gab AI" | "Brave Search" | "GitHub" | "Home Assistant"
+        | "Obsidian" => {
             ("not configured", Style::default().fg(Color::DarkGray), "○")
         }
         _ => ("unknown", Style::default().fg(Color::Red), "?"),
@@ -127,6 +127,12 @@ pub fn render_api_key_input(f: &mut Frame, app: &App) {
             " Vault Name ".to_string(),
             "Obsidian vault name...",
         )
+    } else if provider_name == "Home Assistant" && app.connect_entering_url {
+        (
+            input_value.to_string(),
+            " Server URL ".to_string(),
+            "http://homeassistant.local:8123",
+        )
     } else {
         let masked = if key_len == 0 {
             String::new()
@@ -230,6 +236,55 @@ pub fn render_api_key_input(f: &mut Frame, app: &App) {
                 ),
             ]),
         ],
+        "GitHub" => vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  ● ", Style::default().fg(Color::Green)),
+                Span::styled(
+                    "GitHub",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    " - Your PRs, review requests and issues",
+                    Style::default().fg(Color::White),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("    Create a token (repo read access): ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    "https://github.com/settings/tokens",
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+            ]),
+        ],
+        "Home Assistant" => vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  ● ", Style::default().fg(Color::Green)),
+                Span::styled(
+                    "Home Assistant",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    " - Read sensors and control devices (asks before acting)",
+                    Style::default().fg(Color::White),
+                ),
+            ]),
+            Line::from(vec![Span::styled(
+                if app.connect_entering_url {
+                    "    Step 1/2: server URL, then Enter for the access token"
+                } else {
+                    "    Step 2/2: long-lived access token from your Home Assistant profile"
+                },
+                Style::default().fg(Color::DarkGray),
+            )]),
+        ],
         "Obsidian" => vec![
             Line::from(""),
             Line::from(vec![
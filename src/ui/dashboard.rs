@@ -0,0 +1,188 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::dashboard::{self, DashboardData};
+use crate::ui::components;
+
+/// `/dashboard`: totals, conversations per week, memory growth, top topics and recalled memories
+pub fn render_dashboard_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),      // Header
+            Constraint::Length(1),      // Totals
+            Constraint::Percentage(50), // Charts
+            Constraint::Min(0),         // Lists
+            Constraint::Length(3),      // Footer
+        ])
+        .split(frame.area());
+    let [header, totals, charts, lists, footer] = &chunks[..] else {
+        return;
+    };
+
+    components::render_view_header(frame, *header, "Dashboard");
+    components::render_navigation_footer(frame, *footer, "DASHBOARD", &[("Esc", "back")], &[]);
+    let Some(data) = &app.dashboard else {
+        return;
+    };
+
+    let embedded_percent = (data.embedded * 100).checked_div(data.messages).unwrap_or(0);
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Conversations ", label),
+            Span::styled(data.conversations.to_string(), value),
+            Span::styled("   Messages ", label),
+            Span::styled(data.messages.to_string(), value),
+            Span::styled("   Embedded ", label),
+            Span::styled(format!("{} ({}%)", data.embedded, embedded_percent), value),
+            Span::styled("   Database ", label),
+            Span::styled(dashboard::format_bytes(data.database_bytes), value),
+        ])),
+        *totals,
+    );
+
+    let chart_columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(*charts);
+    if let [per_week, growth] = &chart_columns[..] {
+        render_conversations_per_week(frame, data, *per_week);
+        render_memory_growth(frame, data, *growth);
+    }
+
+    let list_columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(*lists);
+    if let [topics, recalled] = &list_columns[..] {
+        render_top_topics(frame, data, *topics);
+        render_top_retrievals(frame, data, *recalled);
+    }
+}
+
+fn panel(title: &str) -> Block<'_> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", title))
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
+fn render_conversations_per_week(frame: &mut Frame, data: &DashboardData, area: Rect) {
+    let labels: Vec<String> = data.weeks.iter().map(dashboard::WeekBucket::label).collect();
+    let bars: Vec<Bar> = data
+        .weeks
+        .iter()
+        .zip(&labels)
+        .map(|(week, label)| {
+            Bar::default()
+                .value(week.conversations)
+                .label(Line::from(label.clone()))
+                .style(Style::default().fg(Color::Magenta))
+                .value_style(Style::default().fg(Color::Black).bg(Color::Magenta))
+        })
+        .collect();
+    // Fit every week into the panel
+    let weeks = u16::try_from(data.weeks.len().max(1)).unwrap_or(u16::MAX);
+    let bar_width = (area.width.saturating_sub(2) / weeks).saturating_sub(1).clamp(1, 6);
+    frame.render_widget(
+        BarChart::default()
+            .block(panel("Conversations per week"))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(1),
+        area,
+    );
+}
+
+fn render_memory_growth(frame: &mut Frame, data: &DashboardData, area: Rect) {
+    let points = |value: fn(&dashboard::WeekBucket) -> u64| -> Vec<(f64, f64)> {
+        data.weeks
+            .iter()
+            .enumerate()
+            .map(|(index, week)| (index as f64, value(week) as f64))
+            .collect()
+    };
+    let messages = points(|week| week.total_messages);
+    let embedded = points(|week| week.total_embedded);
+    let top = data.weeks.last().map_or(0, |week| week.total_messages).max(1) as f64;
+    let last_index = data.weeks.len().saturating_sub(1) as f64;
+    let first_label = data.weeks.first().map(dashboard::WeekBucket::label).unwrap_or_default();
+    let last_label = data.weeks.last().map(dashboard::WeekBucket::label).unwrap_or_default();
+
+    let datasets = vec![
+        Dataset::default()
+            .name("messages")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&messages),
+        Dataset::default()
+            .name("embedded")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&embedded),
+    ];
+    let chart = Chart::new(datasets)
+        .block(panel("Memory growth"))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, last_index.max(1.0)])
+                .labels([first_label, last_label]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, top])
+                .labels(["0".to_string(), format!("{:.0}", top)]),
+        );
+    frame.render_widget(chart, area);
+}
+
+fn render_top_topics(frame: &mut Frame, data: &DashboardData, area: Rect) {
+    let lines: Vec<Line> = if data.top_topics.is_empty() {
+        vec![Line::from(Span::styled("No topics tracked yet", Style::default().fg(Color::DarkGray)))]
+    } else {
+        data.top_topics
+            .iter()
+            .map(|(topic, count)| {
+                Line::from(vec![
+                    Span::styled(format!("{:>4} ", count), Style::default().fg(Color::Yellow)),
+                    Span::styled(topic.clone(), Style::default().fg(Color::White)),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).block(panel("Top topics")), area);
+}
+
+fn render_top_retrievals(frame: &mut Frame, data: &DashboardData, area: Rect) {
+    let lines: Vec<Line> = if data.top_retrievals.is_empty() {
+        vec![Line::from(Span::styled("No memories recalled yet", Style::default().fg(Color::DarkGray)))]
+    } else {
+        data.top_retrievals
+            .iter()
+            .map(|memory| {
+                Line::from(vec![
+                    Span::styled(format!("{:>4}× ", memory.count), Style::default().fg(Color::Yellow)),
+                    Span::styled(memory.preview.replace('\n', " "), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("  last {}", memory.last_retrieved_at.get(..10).unwrap_or_default()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).block(panel("Most recalled memories")), area);
+}
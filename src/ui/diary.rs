@@ -0,0 +1,96 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+/// `/diary`: days on the left, the selected day's entry on the right
+pub fn render_diary_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Days and entry
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+    let [header, body, footer] = &chunks[..] else {
+        return;
+    };
+
+    components::render_view_header(frame, *header, "Kimi's Diary");
+    components::render_navigation_footer(
+        frame,
+        *footer,
+        "DIARY",
+        &[("↑↓", "day"), ("E", "export to Obsidian"), ("D", "delete"), ("Esc", "back")],
+        &[("EXPORT", app.diary.export_to_obsidian)],
+    );
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(22), Constraint::Min(0)])
+        .split(*body);
+    let [days_area, entry_area] = &columns[..] else {
+        return;
+    };
+    let panel = |title: &'static str| {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::DarkGray))
+    };
+
+    if app.diary_entries.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No entries yet. Kimi writes one after each saved conversation of the day.",
+                Style::default().fg(Color::DarkGray),
+            )))
+            .wrap(Wrap { trim: true })
+            .block(panel(" Entry ")),
+            *entry_area,
+        );
+        frame.render_widget(panel(" Days "), *days_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .diary_entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let is_selected = index == app.diary_selected_index;
+            let day = chrono::NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d")
+                .map_or_else(|_| entry.date.clone(), |date| date.format("%a %b %d %Y").to_string());
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    components::selection_prefix(is_selected),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(day, components::selected_name_style(is_selected)),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(app.diary_selected_index));
+    frame.render_stateful_widget(List::new(items).block(panel(" Days ")), *days_area, &mut state);
+
+    let content = app
+        .diary_entries
+        .get(app.diary_selected_index)
+        .map(|entry| entry.content.clone())
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(content)
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: false })
+            .block(panel(" Entry ")),
+        *entry_area,
+    );
+}
@@ -0,0 +1,141 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::{App, DownloadItem};
+use crate::services::link_download::DownloadStatus;
+use crate::storage::DownloadRecord;
+use crate::ui::components;
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Renders the `/downloads` overlay: active downloads first, then the history
+pub fn render_downloads(f: &mut Frame, app: &App) {
+    let title = format!(
+        "Downloads ({} active, {} finished)",
+        app.active_downloads.len(),
+        app.download_history.len()
+    );
+    let area = components::render_modal_frame(f, f.area(), 90, 75, &title);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([list_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let mut items: Vec<ListItem> = app
+        .active_downloads
+        .iter()
+        .enumerate()
+        .map(|(index, item)| active_download_item(item, index == app.download_selected_index))
+        .collect();
+    let offset = items.len();
+    items.extend(app.download_history.iter().enumerate().map(|(index, record)| {
+        download_record_item(record, index + offset == app.download_selected_index)
+    }));
+
+    if items.is_empty() {
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No downloads yet. Type `download <url>` in chat to start one.",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            *list_area,
+        );
+    } else {
+        let mut state = ListState::default();
+        state.select(Some(app.download_selected_index));
+        f.render_stateful_widget(List::new(items), *list_area, &mut state);
+    }
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" select  ", Style::default().fg(Color::White)),
+        Span::styled(" C ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" cancel  ", Style::default().fg(Color::White)),
+        Span::styled(" R ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" retry  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn progress_bar(percent: u8) -> String {
+    let filled = usize::from(percent.min(100)) * PROGRESS_BAR_WIDTH / 100;
+    format!(
+        "[{}{}]",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+fn active_download_item(item: &DownloadItem, is_selected: bool) -> ListItem<'static> {
+    let percent = item.progress.unwrap_or(0);
+    let mut first = vec![
+        Span::styled(
+            components::selection_prefix(is_selected),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(progress_bar(percent), Style::default().fg(Color::Cyan)),
+        Span::styled(format!(" {:>3}% ", percent), Style::default().fg(Color::White)),
+    ];
+    if let Some(speed) = &item.speed {
+        first.push(Span::styled(format!("{} ", speed), Style::default().fg(Color::Yellow)));
+    }
+    first.push(Span::styled(
+        item.url.clone(),
+        components::selected_name_style(is_selected),
+    ));
+    let destination = item.destination.as_ref().map_or_else(
+        || "waiting for yt-dlp...".to_string(),
+        |path| path.display().to_string(),
+    );
+    ListItem::new(vec![
+        Line::from(first),
+        Line::from(Span::styled(
+            format!("     → {}", destination),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+}
+
+fn download_record_item(record: &DownloadRecord, is_selected: bool) -> ListItem<'static> {
+    let (label, color) = match record.status {
+        DownloadStatus::Completed => ("done     ", Color::Green),
+        DownloadStatus::Failed => ("failed   ", Color::Red),
+        DownloadStatus::Cancelled => ("cancelled", Color::DarkGray),
+    };
+    let finished_at = chrono::DateTime::parse_from_rfc3339(&record.finished_at)
+        .map_or_else(|_| record.finished_at.clone(), |dt| dt.format("%b %d, %H:%M").to_string());
+    let detail = match (&record.destination, &record.error) {
+        (_, Some(error)) => Span::styled(format!("     {}", error), Style::default().fg(Color::Red)),
+        (Some(destination), None) => Span::styled(
+            format!("     → {}", destination),
+            Style::default().fg(Color::DarkGray),
+        ),
+        (None, None) => Span::raw(""),
+    };
+    ListItem::new(vec![
+        Line::from(vec![
+            Span::styled(
+                components::selection_prefix(is_selected),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(label, Style::default().fg(color)),
+            Span::styled(format!(" {}  ", finished_at), Style::default().fg(Color::DarkGray)),
+            Span::styled(record.url.clone(), components::selected_name_style(is_selected)),
+        ]),
+        Line::from(detail),
+    ])
+}
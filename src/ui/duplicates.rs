@@ -0,0 +1,65 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::services::duplicates;
+use crate::ui::components;
+
+/// Renders the offer to reuse the answer a past conversation gave to the same question
+pub fn render_duplicate_question(f: &mut Frame, app: &App) {
+    let Some(offer) = &app.duplicate_offer else {
+        return;
+    };
+    let area = components::render_modal_frame(f, f.area(), 70, 60, "Asked before");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    let Some([title_area, answer_area, help_area]) =
+        chunks.get(0..3).and_then(|s| <&[_; 3]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let title = vec![
+        Line::from(Span::styled(
+            duplicates::offer_prompt(&offer.earlier.asked_at, today),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("“{}”", offer.earlier.question.trim()),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    f.render_widget(Paragraph::new(title), *title_area);
+
+    f.render_widget(
+        Paragraph::new(offer.earlier.answer.trim().to_string())
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: false }),
+        *answer_area,
+    );
+
+    let help = Line::from(vec![
+        Span::styled(" Enter/Y ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" reuse answer  ", Style::default().fg(Color::White)),
+        Span::styled(" N ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" ask again  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" edit", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
@@ -0,0 +1,83 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+/// Built-in editor for personality files when no external editor can open
+pub fn render_editor_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Text
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+    let [header, body, footer] = &chunks[..] else {
+        return;
+    };
+    let Some(session) = &app.editor_session else {
+        return;
+    };
+
+    let marker = if session.dirty { " · modified" } else { "" };
+    let extra = vec![Span::styled(
+        format!(" · {}{}", session.path.display(), marker),
+        Style::default().fg(Color::DarkGray),
+    )];
+    components::render_view_header_with_extra(frame, *header, &session.title, extra);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let width = usize::from(body.width.saturating_sub(2)).max(1);
+    let height = usize::from(body.height.saturating_sub(2));
+    let (cursor_row, cursor_column) = session.buffer.cursor();
+    let lines: Vec<Line> = session
+        .buffer
+        .lines()
+        .iter()
+        .enumerate()
+        .skip(session.scroll)
+        .take(height)
+        .map(|(row, text)| {
+            if row != cursor_row {
+                return Line::from(Span::raw(text.chars().take(width).collect::<String>()));
+            }
+            // Scroll the cursor line sideways so the cursor stays visible
+            let start = cursor_column.saturating_sub(width.saturating_sub(1));
+            let visible: Vec<char> = text.chars().skip(start).take(width).collect();
+            let relative = cursor_column - start;
+            let before: String = visible.iter().take(relative).collect();
+            let under = visible.get(relative).map_or(" ".to_string(), char::to_string);
+            let after: String = visible.iter().skip(relative + 1).collect();
+            Line::from(vec![
+                Span::raw(before),
+                Span::styled(under, Style::default().add_modifier(Modifier::REVERSED)),
+                Span::raw(after),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), *body);
+
+    components::render_navigation_footer(
+        frame,
+        *footer,
+        "EDITOR",
+        &[("Ctrl+S", "save"), ("Esc", "close")],
+        &[],
+    );
+    if let Some(message) = app.status_toast_message() {
+        let inner = footer.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        components::render_status_toast(frame, inner, message);
+    }
+}
@@ -0,0 +1,66 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+/// Renders the review overlay for an email the agent drafted
+pub fn render_email_preview(f: &mut Frame, app: &App) {
+    let Some(draft) = &app.email_draft else {
+        return;
+    };
+    let area = components::render_modal_frame(f, f.area(), 80, 70, "Send this email?");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([header_area, body_area, help_area]) =
+        chunks.get(0..3).and_then(|s| <&[_; 3]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let header = vec![
+        Line::from(vec![Span::styled("To:      ", label), Span::styled(draft.to.clone(), value)]),
+        Line::from(vec![
+            Span::styled("Subject: ", label),
+            Span::styled(draft.subject.clone(), value),
+        ]),
+        Line::from(vec![
+            Span::styled("Via:     ", label),
+            Span::styled(
+                app.email_command.split_whitespace().next().unwrap_or("(not configured)").to_string(),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(header), *header_area);
+
+    f.render_widget(
+        Paragraph::new(draft.body.clone())
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: false })
+            .scroll((app.email_preview_scroll, 0)),
+        *body_area,
+    );
+
+    let help = Line::from(vec![
+        Span::styled(" Enter/Y ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" send  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc/N ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" discard  ", Style::default().fg(Color::White)),
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" scroll", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::app::App;
+use crate::services::entities::EntityKind;
+use crate::ui::components;
+
+/// Renders the quick-action popup for the selected message's URLs, paths and dates
+pub fn render_entity_actions(f: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(f, f.area(), 60, 50, "Quick actions");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([body_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    let mut current_entity = None;
+    for (index, (entity, action)) in app.entity_actions.iter().enumerate() {
+        if current_entity != Some(entity) {
+            current_entity = Some(entity);
+            let (label, color) = match entity.kind {
+                EntityKind::Url => ("link", Color::Cyan),
+                EntityKind::Path => ("path", Color::Green),
+                EntityKind::Date(_) => ("date", Color::Magenta),
+            };
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            let mut header = vec![
+                Span::styled(format!("{:<5}", label), Style::default().fg(color)),
+                Span::styled(
+                    entity.text.clone(),
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if let EntityKind::Date(date) = entity.kind {
+                header.push(Span::styled(
+                    format!("  {}", date.format("%A %Y-%m-%d")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(header));
+        }
+        let is_selected = index == app.entity_action_index;
+        if is_selected {
+            selected_line = lines.len();
+        }
+        let style = if is_selected {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        lines.push(Line::from(vec![
+            Span::raw("     "),
+            Span::styled(format!(" {} ", action.label()), style),
+        ]));
+    }
+    // Keep the selected action in view
+    let visible = usize::from(body_area.height);
+    let scroll = selected_line.saturating_sub(visible.saturating_sub(1));
+    f.render_widget(
+        Paragraph::new(lines).scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0)),
+        *body_area,
+    );
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" move  ", Style::default().fg(Color::White)),
+        Span::styled(" Enter ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" run  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
@@ -6,21 +6,27 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+use crate::app::App;
 use crate::ui::components;
 
-pub fn render_help_view(f: &mut Frame) {
+/// Width of the key column; longer keys push their description along
+const KEY_COLUMN_WIDTH: usize = 16;
+
+pub fn render_help_view(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header
+            Constraint::Length(3), // Filter
             Constraint::Min(0),    // Body
             Constraint::Length(3), // Footer
         ])
         .split(f.area());
 
-    if let [header, body, footer] = &chunks[..] {
+    if let [header, filter, body, footer] = &chunks[..] {
         render_help_header(f, *header);
-        render_help_body(f, *body);
+        render_help_filter(f, app, *filter);
+        render_help_body(f, app, *body);
         render_help_footer(f, *footer);
     }
 }
@@ -29,51 +35,48 @@ fn render_help_header(f: &mut Frame, area: Rect) {
     components::render_view_header(f, area, "Help");
 }
 
-fn render_help_body(f: &mut Frame, area: Rect) {
-    let lines = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  Global shortcuts", Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  Ctrl+C", Style::default().fg(Color::Yellow)),
-            Span::styled("  Quit", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("  /", Style::default().fg(Color::Yellow)),
-            Span::styled("       Command menu", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Tab", Style::default().fg(Color::Yellow)),
-            Span::styled("     Rotate agent", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+R", Style::default().fg(Color::Yellow)),
-            Span::styled("  Speak last response", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+T", Style::default().fg(Color::Yellow)),
-            Span::styled("  Toggle auto-TTS", Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+P", Style::default().fg(Color::Yellow)),
-            Span::styled("  Toggle personality", Style::default().fg(Color::White)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  Esc", Style::default().fg(Color::Yellow)),
-            Span::styled("     Back/close", Style::default().fg(Color::White)),
-        ]),
-    ];
+fn render_help_filter(f: &mut Frame, app: &App, area: Rect) {
+    let config = components::TextInputConfig::new(app.help_filter.content(), " Filter ")
+        .with_placeholder("Type to filter keys, commands and providers...")
+        .with_cursor_position(app.help_filter.cursor_position());
+    components::render_text_input(f, area, config);
+}
+
+fn render_help_body(f: &mut Frame, app: &App, area: Rect) {
+    let sections = app.help_sections();
+    let mut lines = Vec::new();
+    for section in &sections {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", section.title),
+            Style::default().fg(Color::Cyan),
+        )));
+        for entry in &section.entries {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}", entry.key, width = KEY_COLUMN_WIDTH),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(format!("  {}", entry.description), Style::default().fg(Color::White)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    if sections.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Nothing matches the filter",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
 
     f.render_widget(
-        Paragraph::new(lines).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Shortcuts ")
-                .border_style(Style::default().fg(Color::DarkGray)),
-        ),
+        Paragraph::new(lines)
+            .scroll((app.help_scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Shortcuts & commands ")
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
         area,
     );
 }
@@ -83,7 +86,7 @@ fn render_help_footer(f: &mut Frame, area: Rect) {
         f,
         area,
         "HELP",
-        &[("Esc", "back")],
+        &[("type", "filter"), ("↑↓ PgUp PgDn", "scroll"), ("Esc", "clear/back")],
         &[],
     );
 }
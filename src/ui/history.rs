@@ -8,16 +8,21 @@ use ratatui::{
 
 use crate::app::App;
 use crate::app::PENDING_SUMMARY_LABEL;
+use crate::storage::Retention;
 use crate::ui::components;
-pub fn render_history_view(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+fn history_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header
             Constraint::Min(0),    // History list
             Constraint::Length(3), // Footer
         ])
-        .split(f.area());
+        .split(area)
+}
+
+pub fn render_history_view(f: &mut Frame, app: &App) {
+    let chunks = history_layout(f.area());
 
     if let [header, list, footer] = &chunks[..] {
         render_history_header(f, app, *header);
@@ -27,6 +32,9 @@ pub fn render_history_view(f: &mut Frame, app: &App) {
     if app.history_delete_all_active {
         render_history_delete_all_modal(f, app);
     }
+    if app.history_rename_active {
+        render_history_rename_modal(f, app);
+    }
 }
 
 fn render_history_header(f: &mut Frame, app: &App, area: Rect) {
@@ -40,29 +48,43 @@ fn render_history_header(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let extra = vec![Span::styled(count_text, Style::default().fg(Color::DarkGray))];
-    components::render_view_header_with_extra(f, area, "History", extra);
+    let title = if app.history_show_archived { "Archive" } else { "History" };
+    components::render_view_header_with_extra(f, area, title, extra);
 }
 
-fn render_history_list(f: &mut Frame, app: &App, area: Rect) {
+/// List rows of the history pane, the row of the selected conversation, and which
+/// conversation (if any) each row shows
+fn build_history_items(app: &App, width: u16) -> (Vec<ListItem<'_>>, Option<usize>, Vec<Option<usize>>) {
     let mut items = Vec::new();
-    let mut selectable_item_count = 0;
     let mut selected_item_index: Option<usize> = None;
 
     items.extend(build_filter_bar(app));
 
-    if app.history_conversations.is_empty() {
-        items.extend(build_empty_state());
-    } else {
-        for (index, conv) in app.history_conversations.iter().enumerate() {
-            let is_selected = index == app.history_selected_index;
-            items.push(build_conversation_item(app, conv, is_selected, area.width));
-            if is_selected {
-                selected_item_index = Some(items.len().saturating_sub(1));
-            }
-            items.push(ListItem::new(Line::from("")));
-            selectable_item_count += 1;
+    if app.history_conversations.is_empty() && app.history_loading {
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(Line::from(Span::styled(
+            "  Loading conversations...",
+            Style::default().fg(Color::DarkGray),
+        ))));
+    } else if app.history_conversations.is_empty() {
+        items.extend(build_empty_state(app.history_show_archived));
+    }
+    let mut conversation_rows = vec![None; items.len()];
+    for (index, conv) in app.history_conversations.iter().enumerate() {
+        let is_selected = index == app.history_selected_index;
+        items.push(build_conversation_item(app, conv, is_selected, width));
+        conversation_rows.push(Some(index));
+        if is_selected {
+            selected_item_index = Some(items.len().saturating_sub(1));
         }
+        items.push(ListItem::new(Line::from("")));
+        conversation_rows.push(None);
     }
+    (items, selected_item_index, conversation_rows)
+}
+
+fn render_history_list(f: &mut Frame, app: &App, area: Rect) {
+    let (items, selected_item_index, _) = build_history_items(app, area.width);
 
     let list = List::new(items)
         .block(
@@ -74,13 +96,51 @@ fn render_history_list(f: &mut Frame, app: &App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     let mut list_state = ListState::default();
-    if selectable_item_count > 0
-        && let Some(item_index) = selected_item_index
+    list_state.select(selected_item_index);
+
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Conversation drawn at (`column`, `row`) of the history view, for a terminal of size `area`
+pub fn history_item_at(app: &App, area: Rect, column: u16, row: u16) -> Option<usize> {
+    let chunks = history_layout(area);
+    let list = chunks.get(1)?;
+    if column <= list.x
+        || column >= list.x + list.width.saturating_sub(1)
+        || row <= list.y
+        || row >= list.y + list.height.saturating_sub(1)
     {
-        list_state.select(Some(item_index));
+        return None;
+    }
+    let (items, selected_item_index, conversation_rows) = build_history_items(app, list.width);
+    let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
+    let first_visible = first_visible_item(&heights, selected_item_index, usize::from(list.height.saturating_sub(2)));
+    // Walk the rows from the first visible item down to the click
+    let mut remaining = usize::from(row - list.y - 1);
+    for (index, height) in heights.iter().enumerate().skip(first_visible) {
+        if remaining < *height {
+            return conversation_rows.get(index).copied().flatten();
+        }
+        remaining -= height;
     }
+    None
+}
 
-    f.render_stateful_widget(list, area, &mut list_state);
+/// Mirrors how `List` scrolls a fresh `ListState` so the selected item is the last one shown
+fn first_visible_item(heights: &[usize], selected: Option<usize>, max_height: usize) -> usize {
+    let Some(selected) = selected else {
+        return 0;
+    };
+    let mut first = 0;
+    let mut used = 0;
+    for (index, height) in heights.iter().enumerate().take(selected + 1) {
+        used += height;
+        while used > max_height && first < index {
+            used -= heights.get(first).copied().unwrap_or(0);
+            first += 1;
+        }
+    }
+    first
 }
 
 fn build_filter_bar(app: &App) -> Vec<ListItem<'static>> {
@@ -116,12 +176,17 @@ fn build_filter_bar(app: &App) -> Vec<ListItem<'static>> {
     ]
 }
 
-fn build_empty_state() -> Vec<ListItem<'static>> {
+fn build_empty_state(archived: bool) -> Vec<ListItem<'static>> {
+    let message = if archived {
+        "No archived conversations"
+    } else {
+        "No conversations yet"
+    };
     vec![
         ListItem::new(Line::from("")),
         ListItem::new(Line::from(vec![
             Span::styled("  ", Style::default()),
-            Span::styled("No conversations yet", Style::default().fg(Color::DarkGray)),
+            Span::styled(message, Style::default().fg(Color::DarkGray)),
         ])),
         ListItem::new(Line::from("")),
         ListItem::new(Line::from(vec![
@@ -148,9 +213,9 @@ fn build_conversation_item<'a>(
     let summary_text = if is_generating {
         "Generating summary...".to_string()
     } else {
-        conv.summary
-            .clone()
-            .unwrap_or_else(|| "Untitled conversation".to_string())
+        conv.title()
+            .unwrap_or("Untitled conversation")
+            .to_string()
     };
 
     let prefix = components::selection_prefix(is_selected);
@@ -186,6 +251,31 @@ fn build_conversation_item<'a>(
             Style::default().fg(Color::Yellow),
         ));
     }
+    if conv.archived && !app.history_show_archived {
+        meta_spans.push(Span::styled(" · ", meta_style));
+        meta_spans.push(Span::styled("archived", Style::default().fg(Color::Magenta)));
+    }
+    match conv.retention {
+        Retention::Normal => {}
+        Retention::Ephemeral => {
+            let days = conv
+                .expires_at
+                .as_deref()
+                .and_then(|expires_at| crate::services::maintenance::days_until_expiry(expires_at, chrono::Local::now()));
+            let badge = match days {
+                Some(0) => "expires today".to_string(),
+                Some(1) => "expires in 1 day".to_string(),
+                Some(days) => format!("expires in {} days", days),
+                None => "ephemeral".to_string(),
+            };
+            meta_spans.push(Span::styled(" · ", meta_style));
+            meta_spans.push(Span::styled(badge, Style::default().fg(Color::Red)));
+        }
+        Retention::Forever => {
+            meta_spans.push(Span::styled(" · ", meta_style));
+            meta_spans.push(Span::styled("kept forever", Style::default().fg(Color::Green)));
+        }
+    }
 
     let mut item_lines = vec![summary_line];
     for line in summary_lines.iter().skip(1) {
@@ -200,7 +290,7 @@ fn build_conversation_item<'a>(
 }
 
 fn is_pending_summary(app: &App, conv: &crate::storage::ConversationSummary) -> bool {
-    if conv.summary.as_deref() != Some(PENDING_SUMMARY_LABEL) {
+    if conv.custom_title.is_some() || conv.summary.as_deref() != Some(PENDING_SUMMARY_LABEL) {
         return false;
     }
     if !app.is_generating_summary {
@@ -255,13 +345,28 @@ fn wrap_summary_text(text: &str, max_width: usize, max_lines: usize) -> Vec<Stri
 }
 
 fn render_history_footer(f: &mut Frame, app: &App, area: Rect) {
-    let keybindings: &[(&str, &str)] = if app.history_filter_active {
+    let keybindings: &[(&str, &str)] = if app.history_rename_active {
+        &[("Enter", "save"), ("Esc", "cancel")]
+    } else if app.history_filter_active {
         &[("Type", "filter"), ("Esc", "done")]
     } else if app.history_delete_all_active {
         &[("Enter", "confirm"), ("Esc", "cancel"), ("←/→", "choose")]
+    } else if app.history_show_archived {
+        &[
+            ("Enter", "load"),
+            ("^R", "rename"),
+            ("^A", "unarchive"),
+            ("^K", "retention"),
+            ("Del", "delete"),
+            ("/", "menu"),
+            ("Esc", "new chat"),
+        ]
     } else {
         &[
             ("Enter", "load"),
+            ("^R", "rename"),
+            ("^A", "archive"),
+            ("^K", "retention"),
             ("Del", "delete"),
             ("/", "menu"),
             ("Esc", "new chat"),
@@ -270,6 +375,8 @@ fn render_history_footer(f: &mut Frame, app: &App, area: Rect) {
 
     let status: &[(&str, bool)] = if app.history_filter_active {
         &[("FILTERING", true)]
+    } else if app.history_show_archived {
+        &[("ARCHIVE", true)]
     } else {
         &[]
     };
@@ -277,6 +384,35 @@ fn render_history_footer(f: &mut Frame, app: &App, area: Rect) {
     components::render_navigation_footer(f, area, "HISTORY", keybindings, status);
 }
 
+fn render_history_rename_modal(f: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(f, f.area(), 60, 30, "Rename conversation");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let Some([input_area, hint_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let config = components::TextInputConfig::new(app.history_rename_input.content(), " Title ")
+        .with_placeholder("Type a title...")
+        .with_cursor_position(app.history_rename_input.cursor_position());
+    components::render_text_input(f, *input_area, config);
+
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            "Leave empty to use the generated summary",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .alignment(Alignment::Center),
+        *hint_area,
+    );
+}
+
 fn render_history_delete_all_modal(f: &mut Frame, app: &App) {
     let area = components::render_modal_frame(f, f.area(), 45, 30, "Delete all history?");
 
@@ -327,3 +463,53 @@ fn render_history_delete_all_modal(f: &mut Frame, app: &App) {
 
     f.render_widget(Paragraph::new(buttons).alignment(Alignment::Center), *buttons_area);
 }
+
+/// Startup prompt offering to reopen the most recent conversation
+pub fn render_resume_prompt(f: &mut Frame, app: &App) {
+    let Some(conversation) = &app.resume_prompt else {
+        return;
+    };
+    let area = components::render_modal_frame(f, f.area(), 55, 30, "Resume last conversation?");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([content_area, hint_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let date_display = chrono::DateTime::parse_from_rfc3339(&conversation.created_at)
+        .map_or_else(|_| conversation.created_at.clone(), |dt| dt.format("%b %d, %H:%M").to_string());
+    let title = conversation.title().unwrap_or("Untitled conversation");
+    let lines = vec![
+        Line::from(Span::styled(
+            title.to_string(),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("{} · {}", date_display, conversation.agent_name),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true }),
+        *content_area,
+    );
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Enter/y", Style::default().fg(Color::Cyan)),
+            Span::styled(" resume   ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc/n", Style::default().fg(Color::Cyan)),
+            Span::styled(" new chat", Style::default().fg(Color::DarkGray)),
+        ]))
+        .alignment(Alignment::Center),
+        *hint_area,
+    );
+}
@@ -0,0 +1,56 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+/// Renders the confirmation overlay for a Home Assistant service call
+pub fn render_home_assistant_confirm(f: &mut Frame, app: &App) {
+    let Some(call) = &app.home_assistant_call else {
+        return;
+    };
+    let area = components::render_modal_frame(f, f.area(), 60, 30, "Run this action?");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([details_area, help_area]) =
+        chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let details = vec![
+        Line::from(vec![
+            Span::styled("Device:  ", label),
+            Span::styled(call.entity_name.clone(), value),
+        ]),
+        Line::from(vec![
+            Span::styled("Entity:  ", label),
+            Span::styled(call.entity_id.clone(), Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![
+            Span::styled("Service: ", label),
+            Span::styled(format!("{}.{}", call.domain, call.service), value),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(details), *details_area);
+
+    let help = Line::from(vec![
+        Span::styled(" Enter/Y ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" run  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc/N ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" cancel", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
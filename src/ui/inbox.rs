@@ -0,0 +1,106 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::notifications::NotificationKind;
+use crate::storage::Notification;
+use crate::ui::components;
+
+/// Renders the `/inbox` overlay, newest notifications first
+pub fn render_inbox(f: &mut Frame, app: &App) {
+    let title = format!("Inbox ({} unread)", app.unread_notifications);
+    let area = components::render_modal_frame(f, f.area(), 90, 75, &title);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([list_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    if app.inbox.is_empty() {
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Nothing here yet. Reminders, digests and finished jobs show up in this inbox.",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            *list_area,
+        );
+    } else {
+        let items: Vec<ListItem> = app
+            .inbox
+            .iter()
+            .enumerate()
+            .map(|(index, notification)| {
+                notification_item(notification, index == app.inbox_selected_index)
+            })
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(app.inbox_selected_index));
+        f.render_stateful_widget(List::new(items), *list_area, &mut state);
+    }
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" select  ", Style::default().fg(Color::White)),
+        Span::styled(" Enter ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" read/unread  ", Style::default().fg(Color::White)),
+        Span::styled(" A ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" all read  ", Style::default().fg(Color::White)),
+        Span::styled(" D ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" delete  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn notification_item(notification: &Notification, is_selected: bool) -> ListItem<'static> {
+    let (label, color) = match notification.kind {
+        NotificationKind::Reminder => ("reminder  ", Color::Yellow),
+        NotificationKind::Digest => ("digest    ", Color::Cyan),
+        NotificationKind::Embeddings => ("embeddings", Color::Blue),
+        NotificationKind::Download => ("download  ", Color::Green),
+        NotificationKind::Budget => ("budget    ", Color::Red),
+        NotificationKind::Job => ("job       ", Color::Magenta),
+    };
+    let created_at = chrono::DateTime::parse_from_rfc3339(&notification.created_at).map_or_else(
+        |_| notification.created_at.clone(),
+        |dt| dt.format("%b %d, %H:%M").to_string(),
+    );
+    let title_style = if notification.read {
+        components::selected_name_style(is_selected)
+    } else {
+        components::selected_name_style(is_selected).add_modifier(Modifier::BOLD)
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            components::selection_prefix(is_selected),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            if notification.read { "  " } else { "● " },
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(label, Style::default().fg(color)),
+        Span::styled(format!(" {}  ", created_at), Style::default().fg(Color::DarkGray)),
+        Span::styled(notification.title.clone(), title_style),
+    ])];
+    lines.extend(notification.message.lines().map(|line| {
+        Line::from(Span::styled(
+            format!("       {}", line),
+            Style::default().fg(Color::DarkGray),
+        ))
+    }));
+    ListItem::new(lines)
+}
@@ -0,0 +1,67 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+/// Renders the `/logs` overlay tailing the current log file
+pub fn render_log_viewer(f: &mut Frame, app: &App) {
+    let title = if app.log_scroll == 0 {
+        "Logs (following)".to_string()
+    } else {
+        format!("Logs (-{} lines)", app.log_scroll)
+    };
+    let area = components::render_modal_frame(f, f.area(), 90, 80, &title);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([log_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let visible_rows = log_area.height as usize;
+    let end = app.log_lines.len().saturating_sub(app.log_scroll);
+    let start = end.saturating_sub(visible_rows);
+    let lines: Vec<Line> = app
+        .log_lines
+        .get(start..end)
+        .unwrap_or_default()
+        .iter()
+        .map(|line| Line::from(Span::styled(line.as_str(), log_line_style(line))))
+        .collect();
+    f.render_widget(Paragraph::new(lines), *log_area);
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" scroll  ", Style::default().fg(Color::White)),
+        Span::styled(" PgUp/PgDn ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" page  ", Style::default().fg(Color::White)),
+        Span::styled(" End ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" follow  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn log_line_style(line: &str) -> Style {
+    if line.contains(" ERROR ") {
+        Style::default().fg(Color::Red)
+    } else if line.contains(" WARN ") {
+        Style::default().fg(Color::Yellow)
+    } else if line.contains(" DEBUG ") || line.contains(" TRACE ") {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default().fg(Color::White)
+    }
+}
@@ -15,14 +15,7 @@ pub fn render_command_menu(frame: &mut Frame, app: &App) {
     let area = frame.area();
     frame.render_widget(Clear, area);
 
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Length(3), // Search
-            Constraint::Min(0),    // List
-        ])
-        .split(area);
+    let chunks = menu_layout(area);
 
     if let [header_area, search_area, list_area] = &chunks[..] {
         render_command_header(frame, *header_area);
@@ -36,6 +29,32 @@ pub fn render_command_menu(frame: &mut Frame, app: &App) {
     }
 }
 
+fn menu_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(3), // Search
+            Constraint::Min(0),    // List
+        ])
+        .split(area)
+}
+
+/// Index into `filtered_items()` of the command drawn at (`column`, `row`)
+pub fn command_item_at(app: &App, area: Rect, column: u16, row: u16) -> Option<usize> {
+    let chunks = menu_layout(area);
+    let list = chunks.get(2)?;
+    if column <= list.x
+        || column >= list.x + list.width.saturating_sub(1)
+        || row <= list.y
+        || row >= list.y + list.height.saturating_sub(1)
+    {
+        return None;
+    }
+    let index = usize::from(row - list.y - 1);
+    (index < app.filtered_items().len()).then_some(index)
+}
+
 fn render_command_header(frame: &mut Frame, area: Rect) {
     components::render_view_header(frame, area, "Commands");
 }
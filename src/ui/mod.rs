@@ -1,18 +1,74 @@
+mod agenda;
+mod attachments;
+mod audit;
+mod capture;
 mod chat;
+mod compat;
 mod components;
 mod connect;
+mod dashboard;
+mod diary;
 mod help;
 mod history;
 mod menu;
 mod models;
 mod personality;
+mod profiles;
+mod prompt_inspector;
 mod identity;
+mod logs;
+mod downloads;
+mod editor;
+mod email;
+mod duplicates;
+mod entities;
+mod homeassistant;
+mod inbox;
 mod projects;
+mod search;
+mod sentiment;
+mod settings;
+mod snippets;
+mod sources;
+mod status;
+mod tasks;
+mod usage;
 mod utils;
 
 use crate::app::{App, AppMode};
 use ratatui::Frame;
 
+/// Applies `[display] compatibility` before the first frame is drawn
+pub fn configure_display(display: &crate::config::DisplayConfig) {
+    compat::configure(display.compatibility);
+}
+
+/// Chat scroll offset that shows message `index` at the top, for a terminal of size `area`
+#[must_use]
+pub fn chat_scroll_offset_for_message(app: &App, area: ratatui::layout::Rect, index: usize) -> Option<usize> {
+    chat::scroll_offset_for_message(app, area, index)
+}
+
+#[must_use]
+pub fn chat_first_visible_message(app: &App, area: ratatui::layout::Rect) -> Option<usize> {
+    chat::first_visible_message(app, area)
+}
+
+#[must_use]
+pub fn chat_hit_at(app: &App, area: ratatui::layout::Rect, column: u16, row: u16) -> Option<chat::ChatHit> {
+    chat::chat_hit_at(app, area, column, row)
+}
+
+#[must_use]
+pub fn command_item_at(app: &App, area: ratatui::layout::Rect, column: u16, row: u16) -> Option<usize> {
+    menu::command_item_at(app, area, column, row)
+}
+
+#[must_use]
+pub fn history_item_at(app: &App, area: ratatui::layout::Rect, column: u16, row: u16) -> Option<usize> {
+    history::history_item_at(app, area, column, row)
+}
+
 pub fn render(f: &mut Frame, app: &App) {
     match app.mode {
         AppMode::Chat => chat::render_chat_view(f, app),
@@ -24,19 +80,103 @@ pub fn render(f: &mut Frame, app: &App) {
             connect::render_api_key_input(f, app);
         }
         AppMode::History => history::render_history_view(f, app),
-        AppMode::Help => help::render_help_view(f),
+        AppMode::Help => help::render_help_view(f, app),
         AppMode::PersonalitySelection => personality::render_personality_view(f, app),
         AppMode::PersonalityCreate => {
             personality::render_personality_view(f, app);
             personality::render_personality_create(f, app);
         }
+        AppMode::PersonalitySuggestion => personality::render_personality_suggestion(f, app),
         AppMode::IdentityView => identity::render_identity_view(f, app),
         AppMode::ProjectList => projects::render_project_list(f, app),
         AppMode::ProjectDetail => projects::render_project_detail(f, app),
+        AppMode::ProjectForm => {
+            if matches!(
+                app.project_form,
+                Some(crate::app::ProjectForm::Entry { .. } | crate::app::ProjectForm::Task { .. })
+            ) {
+                projects::render_project_detail(f, app);
+            } else {
+                projects::render_project_list(f, app);
+            }
+            projects::render_project_form(f, app);
+        }
+        AppMode::Agenda => agenda::render_agenda_view(f, app),
+        AppMode::SnippetList => snippets::render_snippet_view(f, app),
+        AppMode::LogViewer => {
+            chat::render_chat_view(f, app);
+            logs::render_log_viewer(f, app);
+        }
+        AppMode::ProfileSelection => profiles::render_profile_view(f, app),
+        AppMode::Search => search::render_search_view(f, app),
+        AppMode::Capture => capture::render_capture_view(f, app),
+        AppMode::Dashboard => dashboard::render_dashboard_view(f, app),
+        AppMode::Sentiment => sentiment::render_sentiment_view(f, app),
+        AppMode::Diary => diary::render_diary_view(f, app),
+        AppMode::Editor => editor::render_editor_view(f, app),
+        AppMode::Settings => settings::render_settings_view(f, app),
+        AppMode::PromptInspector => {
+            chat::render_chat_view(f, app);
+            prompt_inspector::render_prompt_inspector(f, app);
+        }
+        AppMode::Downloads => {
+            chat::render_chat_view(f, app);
+            downloads::render_downloads(f, app);
+        }
+        AppMode::Inbox => {
+            chat::render_chat_view(f, app);
+            inbox::render_inbox(f, app);
+        }
+        AppMode::Tasks => {
+            chat::render_chat_view(f, app);
+            tasks::render_tasks(f, app);
+        }
+        AppMode::EmailPreview => {
+            chat::render_chat_view(f, app);
+            email::render_email_preview(f, app);
+        }
+        AppMode::HomeAssistantConfirm => {
+            chat::render_chat_view(f, app);
+            homeassistant::render_home_assistant_confirm(f, app);
+        }
+        AppMode::DuplicateQuestion => {
+            chat::render_chat_view(f, app);
+            duplicates::render_duplicate_question(f, app);
+        }
+        AppMode::Status => {
+            chat::render_chat_view(f, app);
+            status::render_status(f, app);
+        }
+        AppMode::Audit => {
+            chat::render_chat_view(f, app);
+            audit::render_audit(f, app);
+        }
+        AppMode::Sources => {
+            chat::render_chat_view(f, app);
+            sources::render_sources(f, app);
+        }
+        AppMode::Usage => {
+            chat::render_chat_view(f, app);
+            usage::render_usage(f, app);
+        }
+        AppMode::EntityActions => {
+            chat::render_chat_view(f, app);
+            entities::render_entity_actions(f, app);
+        }
     }
 
     // Overlay command menu if active
     if app.mode == AppMode::CommandMenu {
         menu::render_command_menu(f, app);
     }
+
+    if app.mode == AppMode::Chat && app.attachment_panel_active {
+        attachments::render_attachment_panel(f, app);
+    }
+
+    if app.mode == AppMode::Chat && app.resume_prompt.is_some() {
+        history::render_resume_prompt(f, app);
+    }
+
+    compat::downgrade(f.buffer_mut());
 }
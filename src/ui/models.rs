@@ -6,7 +6,8 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState},
 };
 
-use crate::app::{App, ModelSource};
+use crate::app::{App, AvailableModel, ModelSource};
+use crate::services::gpu::VramFit;
 use crate::ui::components;
 
 pub fn render_model_selection(f: &mut Frame, app: &App) {
@@ -20,14 +21,61 @@ pub fn render_model_selection(f: &mut Frame, app: &App) {
         .split(f.area());
 
     if let [header, list, footer] = &chunks[..] {
-        render_model_header(f, *header);
+        render_model_header(f, app, *header);
         render_model_list(f, app, *list);
         render_model_footer(f, *footer);
     }
 }
 
-fn render_model_header(f: &mut Frame, area: Rect) {
-    components::render_view_header(f, area, "Model Selection");
+fn render_model_header(f: &mut Frame, app: &App, area: Rect) {
+    let vram = app.gpu_vram_bytes.map_or_else(
+        || " · VRAM unknown".to_string(),
+        |bytes| format!(" · {} VRAM", crate::services::gpu::format_gigabytes(bytes)),
+    );
+    components::render_view_header_with_extra(
+        f,
+        area,
+        "Model Selection",
+        vec![Span::styled(vram, Style::default().fg(Color::DarkGray))],
+    );
+}
+
+/// "8B Q4_K_M 4.9 GB" plus a fits / too large hint when VRAM is known
+fn model_detail_spans(app: &App, model: &AvailableModel, is_current: bool) -> Vec<Span<'static>> {
+    let Some(details) = &model.details else {
+        return Vec::new();
+    };
+    let parameters = details.parameter_size.clone().or_else(|| {
+        details
+            .parameter_count
+            .map(|count| format!("{:.1}B", count as f64 / 1_000_000_000.0))
+    });
+    let info: Vec<String> = [
+        parameters,
+        details.quantization.clone(),
+        details.size_bytes.map(crate::services::gpu::format_gigabytes),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let info_style = if is_current {
+        components::selected_secondary_style(true, Style::default())
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let mut spans = Vec::new();
+    if !info.is_empty() {
+        spans.push(Span::styled(format!("  {}", info.join(" ")), info_style));
+    }
+    if let (Some(size), Some(vram)) = (details.size_bytes, app.gpu_vram_bytes) {
+        let (label, color) = match crate::services::gpu::estimate_fit(size, vram) {
+            VramFit::Fits => ("fits", Color::Green),
+            VramFit::TooLarge => ("too large", Color::Red),
+        };
+        spans.push(Span::styled(format!("  {}", label), Style::default().fg(color)));
+    }
+    spans
 }
 
 fn render_model_list(f: &mut Frame, app: &App, area: Rect) {
@@ -109,17 +157,19 @@ fn render_model_list(f: &mut Frame, app: &App, area: Rect) {
                         Style::default().fg(Color::DarkGray)
                     };
 
-                    items.push(ListItem::new(Line::from(vec![
+                    let mut spans = vec![
                         Span::styled(
                             components::selection_prefix(is_current),
                             Style::default().fg(Color::Cyan),
                         ),
                         Span::styled(checkbox, checkbox_style),
                         Span::raw("  "),
-                        Span::styled(&model.name, name_style),
+                        Span::styled(model.name.as_str(), name_style),
                         Span::styled("  ", name_style),
                         Span::styled(source_text, source_style),
-                    ])));
+                    ];
+                    spans.extend(model_detail_spans(app, model, is_current));
+                    items.push(ListItem::new(Line::from(spans)));
                     if is_current {
                         selected_list_index = Some(items.len().saturating_sub(1));
                     }
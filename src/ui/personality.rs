@@ -3,28 +3,184 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use crate::app::App;
+use crate::services::personality_tuning::{DiffLine, line_diff};
 use crate::ui::components;
 pub fn render_personality_view(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header
-            Constraint::Min(0),    // List
+            Constraint::Min(0),    // List + preview
             Constraint::Length(3), // Footer
         ])
         .split(f.area());
 
-    if let [header, list, footer] = &chunks[..] {
-        render_personality_header(f, *header);
-        render_personality_list(f, app, *list);
+    if let [header, body, footer] = &chunks[..] {
+        render_personality_header(f, app, *header);
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(*body);
+        if let [list, preview] = &panes[..] {
+            render_personality_list(f, app, *list);
+            render_personality_preview(f, app, *preview);
+        }
         render_personality_footer(f, *footer);
     }
 }
 
+/// Selected personality's text, with the sample reply below it once generated
+fn render_personality_preview(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines: Vec<Line> = app
+        .personality_preview
+        .lines()
+        .map(|line| Line::from(Span::styled(line.to_string(), Style::default().fg(Color::White))))
+        .collect();
+    let meta = &app.personality_preview_meta;
+    let recommendations: Vec<String> = [
+        meta.recommended_model.as_ref().map(|model| format!("model {}", model)),
+        meta.voice_id.as_ref().map(|voice| format!("voice {}", voice)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !recommendations.is_empty() {
+        lines.insert(0, Line::from(""));
+        lines.insert(
+            0,
+            Line::from(Span::styled(
+                format!("Recommended: {}", recommendations.join(" · ")),
+                Style::default().fg(Color::Cyan),
+            )),
+        );
+    }
+
+    let title = match &app.personality_sample {
+        Some(sample) => {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("── Test message to {} ──", sample.personality),
+                Style::default().fg(Color::Cyan),
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("> {}", crate::app::personality::SAMPLE_TEST_MESSAGE),
+                Style::default().fg(Color::DarkGray),
+            )));
+            match &sample.reply {
+                Some(reply) => lines.extend(reply.lines().map(|line| {
+                    Line::from(Span::styled(line.to_string(), Style::default().fg(Color::Yellow)))
+                })),
+                None => lines.push(Line::from(Span::styled(
+                    "Generating sample reply...",
+                    Style::default().fg(Color::DarkGray),
+                ))),
+            }
+            " Preview · sample reply at the end "
+        }
+        None => " Preview ",
+    };
+
+    // Keep the sample reply in view when the personality text is long
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let scroll = if app.personality_sample.is_some() {
+        lines.len().saturating_sub(inner_height)
+    } else {
+        0
+    };
+    f.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+        area,
+    );
+}
+
+/// Shows a pending personality suggestion as a line diff against the current file
+pub fn render_personality_suggestion(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Diff
+            Constraint::Length(3), // Footer
+        ])
+        .split(f.area());
+
+    let [header, body, footer] = &chunks[..] else {
+        return;
+    };
+    let Some(suggestion) = &app.personality_suggestion else {
+        return;
+    };
+
+    components::render_view_header_with_extra(
+        f,
+        *header,
+        "Personality suggestion",
+        vec![Span::styled(
+            format!(" · {}", suggestion.personality),
+            Style::default().fg(Color::DarkGray),
+        )],
+    );
+
+    let mut lines = Vec::new();
+    if !suggestion.rationale.is_empty() {
+        lines.push(Line::from(Span::styled(
+            suggestion.rationale.clone(),
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(""));
+    }
+    for diff_line in line_diff(&suggestion.original, &suggestion.proposed) {
+        lines.push(match diff_line {
+            DiffLine::Same(text) => Line::from(Span::styled(
+                format!("  {}", text),
+                Style::default().fg(Color::DarkGray),
+            )),
+            DiffLine::Added(text) => {
+                Line::from(Span::styled(format!("+ {}", text), Style::default().fg(Color::Green)))
+            }
+            DiffLine::Removed(text) => {
+                Line::from(Span::styled(format!("- {}", text), Style::default().fg(Color::Red)))
+            }
+        });
+    }
+
+    let diff = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Proposed changes ")
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((app.personality_suggestion_scroll, 0));
+    f.render_widget(diff, *body);
+
+    components::render_navigation_footer(
+        f,
+        *footer,
+        "SUGGESTION",
+        &[
+            ("A", "accept"),
+            ("R", "reject"),
+            ("↑↓", "scroll"),
+            ("Esc", "back"),
+        ],
+        &[],
+    );
+}
+
 pub fn render_personality_create(f: &mut Frame, app: &App) {
     let area = components::render_modal_frame(f, f.area(), 60, 30, "New Personality");
 
@@ -47,8 +203,16 @@ pub fn render_personality_create(f: &mut Frame, app: &App) {
 
 }
 
-fn render_personality_header(f: &mut Frame, area: Rect) {
-    components::render_view_header(f, area, "Personalities");
+fn render_personality_header(f: &mut Frame, app: &App, area: Rect) {
+    let extra = if app.personality_suggestion.is_some() {
+        vec![Span::styled(
+            " · suggestion pending (S to review)",
+            Style::default().fg(Color::Yellow),
+        )]
+    } else {
+        Vec::new()
+    };
+    components::render_view_header_with_extra(f, area, "Personalities", extra);
 }
 
 fn render_personality_list(f: &mut Frame, app: &App, area: Rect) {
@@ -144,6 +308,9 @@ fn render_personality_footer(f: &mut Frame, area: Rect) {
             ("Enter", "open"),
             ("N", "new"),
             ("E", "edit"),
+            ("T", "test message"),
+            ("C", "clone"),
+            ("S", "suggestion"),
             ("Del", "delete"),
             ("Esc", "back"),
         ],
@@ -0,0 +1,112 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::ui::components;
+
+pub fn render_profile_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Profile list
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    if let [header, list, footer] = &chunks[..] {
+        let extra = vec![Span::styled(
+            format!(" · active: {}", crate::profile::active_name()),
+            Style::default().fg(Color::DarkGray),
+        )];
+        components::render_view_header_with_extra(frame, *header, "Profiles", extra);
+        render_profile_list(frame, app, *list);
+        components::render_navigation_footer(
+            frame,
+            *footer,
+            "PROFILES",
+            &[
+                ("↑↓", "navigate"),
+                ("Enter", "switch"),
+                ("n", "new"),
+                ("Esc", "back"),
+            ],
+            &[],
+        );
+        if let Some(message) = app.status_toast_message() {
+            let inner = footer.inner(Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+            components::render_status_toast(frame, inner, message);
+        }
+    }
+
+    if app.profile_create_active {
+        render_profile_create_modal(frame, app);
+    }
+}
+
+fn render_profile_list(frame: &mut Frame, app: &App, area: Rect) {
+    let active = crate::profile::active_name();
+    let items: Vec<ListItem> = app
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let is_selected = index == app.profile_selected_index;
+            let mut spans = vec![
+                Span::raw(components::selection_prefix(is_selected)),
+                Span::styled(name.as_str(), components::selected_name_style(is_selected)),
+            ];
+            if *name == active {
+                spans.push(Span::styled("  (active)", Style::default().fg(Color::Green)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.profile_selected_index));
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Memories, config and personalities are kept per profile ")
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_profile_create_modal(frame: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(frame, frame.area(), 60, 30, "New profile");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let Some([input_area, hint_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let config = components::TextInputConfig::new(app.profile_create_input.content(), " Name ")
+        .with_placeholder("e.g. work")
+        .with_cursor_position(app.profile_create_input.cursor_position());
+    components::render_text_input(frame, *input_area, config);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            "Starts from the default profile's config and personalities",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .alignment(Alignment::Center),
+        *hint_area,
+    );
+}
@@ -6,7 +6,8 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
 
-use crate::app::App;
+use crate::app::{App, ProjectDetailFocus, ProjectForm};
+use crate::services::projects::TaskStatus;
 use crate::ui::components;
 
 // ── Project List View ───────────────────────────────────────────────────────
@@ -42,7 +43,7 @@ fn render_list_header(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_list_content(frame: &mut Frame, app: &App, area: Rect) {
     if app.projects.is_empty() {
-        let message = Paragraph::new("No projects yet. The AI will suggest creating one when you discuss a topic frequently.")
+        let message = Paragraph::new("No projects yet. Press N to create one, or the AI will suggest one when you discuss a topic frequently.")
             .style(Style::default().fg(Color::DarkGray))
             .block(
                 Block::default()
@@ -77,8 +78,13 @@ fn render_list_content(frame: &mut Frame, app: &App, area: Rect) {
                 "entries"
             };
 
+            let is_active = app.active_project.as_deref() == Some(project.name.as_str());
             let mut spans = vec![
                 Span::raw(prefix),
+                Span::styled(
+                    if is_active { "[x] " } else { "[ ] " },
+                    Style::default().fg(if is_active { Color::Green } else { Color::DarkGray }),
+                ),
                 Span::styled(&project.name, name_style),
                 Span::styled(
                     format!(" ({} {})", project.entry_count, entry_label),
@@ -110,7 +116,15 @@ fn render_list_footer(frame: &mut Frame, area: Rect) {
         frame,
         area,
         "PROJECTS",
-        &[("↑↓", "navigate"), ("Enter", "view"), ("Esc", "back")],
+        &[
+            ("↑↓", "navigate"),
+            ("Enter", "view"),
+            ("Space", "active"),
+            ("N", "new"),
+            ("E", "edit"),
+            ("Del", "archive"),
+            ("Esc", "back"),
+        ],
         &[],
     );
 }
@@ -131,8 +145,22 @@ pub fn render_project_detail(frame: &mut Frame, app: &App) {
     if let [header, description, content, footer] = &chunks[..] {
         render_detail_header(frame, app, *header);
         render_detail_description(frame, app, *description);
-        render_detail_entries(frame, app, *content);
-        render_detail_footer(frame, *footer);
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(*content);
+        if let [entries, side] = &panes[..] {
+            render_detail_entries(frame, app, *entries);
+            let side_panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(*side);
+            if let [tasks, conversations] = &side_panes[..] {
+                render_detail_tasks(frame, app, *tasks);
+                render_detail_conversations(frame, app, *conversations);
+            }
+        }
+        render_detail_footer(frame, app.project_detail_focus, *footer);
     }
 }
 
@@ -177,7 +205,17 @@ fn render_detail_description(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Border of a detail pane; the one receiving keys is highlighted
+fn pane_border_style(is_focused: bool) -> Style {
+    if is_focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
+}
+
 fn render_detail_entries(frame: &mut Frame, app: &App, area: Rect) {
+    let border_style = pane_border_style(app.project_detail_focus == ProjectDetailFocus::Entries);
     if app.project_entries.is_empty() {
         let message = Paragraph::new("No entries yet.")
             .style(Style::default().fg(Color::DarkGray))
@@ -188,7 +226,7 @@ fn render_detail_entries(frame: &mut Frame, app: &App, area: Rect) {
                         " Entries ",
                         Style::default().fg(Color::White),
                     )]))
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(border_style),
             );
         frame.render_widget(message, area);
         return;
@@ -220,19 +258,222 @@ fn render_detail_entries(frame: &mut Frame, app: &App, area: Rect) {
                 " Entries ",
                 Style::default().fg(Color::White),
             )]))
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(border_style),
     );
     frame.render_widget(list, area);
 }
 
-fn render_detail_footer(frame: &mut Frame, area: Rect) {
-    components::render_navigation_footer(
-        frame,
-        area,
-        "PROJECT",
-        &[("↑↓", "navigate"), ("Esc", "back")],
-        &[],
-    );
+fn render_detail_tasks(frame: &mut Frame, app: &App, area: Rect) {
+    let is_focused = app.project_detail_focus == ProjectDetailFocus::Tasks;
+    let open_count = app
+        .project_tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Open)
+        .count();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(vec![Span::styled(
+            format!(" Tasks ({} open) ", open_count),
+            Style::default().fg(Color::White),
+        )]))
+        .border_style(pane_border_style(is_focused));
+
+    if app.project_tasks.is_empty() {
+        let message = Paragraph::new("No tasks yet. Tab here and press A to add one.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .project_tasks
+        .iter()
+        .enumerate()
+        .map(|(index, task)| {
+            let is_selected = is_focused && index == app.project_task_selected_index;
+            let (marker, marker_color) = match task.status {
+                TaskStatus::Proposed => ("[?] ", Color::Yellow),
+                TaskStatus::Open => ("[ ] ", Color::White),
+                TaskStatus::Done => ("[x] ", Color::Green),
+            };
+            let title_style = match (is_selected, task.status) {
+                (true, _) => Style::default().fg(Color::Magenta),
+                (false, TaskStatus::Done) => Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT),
+                (false, TaskStatus::Proposed) => Style::default().fg(Color::Yellow),
+                (false, TaskStatus::Open) => Style::default().fg(Color::White),
+            };
+            let mut spans = vec![
+                Span::raw(if is_selected { "> " } else { "  " }),
+                Span::styled(marker, Style::default().fg(marker_color)),
+                Span::styled(task.title.clone(), title_style),
+            ];
+            if let Some(completed) = task
+                .completed_at
+                .as_deref()
+                .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+            {
+                spans.push(Span::styled(
+                    format!("  {}", completed.format("%b %d")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn render_detail_conversations(frame: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = if app.project_conversations.is_empty() {
+        vec![Line::from(Span::styled(
+            "None attached yet.",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.project_conversations
+            .iter()
+            .rev()
+            .map(|conversation| {
+                Line::from(Span::styled(
+                    conversation.clone(),
+                    Style::default().fg(Color::White),
+                ))
+            })
+            .collect()
+    };
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(vec![Span::styled(
+                    " Conversations ",
+                    Style::default().fg(Color::White),
+                )]))
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+/// Modal for creating or editing a project, or adding an entry
+pub fn render_project_form(frame: &mut Frame, app: &App) {
+    let Some(form) = &app.project_form else {
+        return;
+    };
+    match form {
+        ProjectForm::Project {
+            original,
+            name,
+            description,
+            editing_description,
+        } => {
+            let title = if original.is_some() { "Edit Project" } else { "New Project" };
+            let area = components::render_modal_frame(frame, frame.area(), 60, 40, title);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                    Constraint::Length(1),
+                ])
+                .split(area);
+            let Some([name_area, description_area, help_area]) =
+                chunks.get(0..3).and_then(|s| <&[_; 3]>::try_from(s).ok())
+            else {
+                return;
+            };
+            let name_config = components::TextInputConfig::new(name.content(), " Name ")
+                .with_cursor_visible(!editing_description)
+                .with_cursor_position(name.cursor_position());
+            components::render_text_input(frame, *name_area, name_config);
+            let description_config =
+                components::TextInputConfig::new(description.content(), " Description ")
+                    .with_placeholder("What the project keeps track of")
+                    .with_cursor_visible(*editing_description)
+                    .with_cursor_position(description.cursor_position());
+            components::render_text_input(frame, *description_area, description_config);
+            render_form_help(frame, *help_area, true);
+        }
+        ProjectForm::Entry { project, text } => {
+            let title = format!("New entry for {}", project);
+            let area = components::render_modal_frame(frame, frame.area(), 60, 25, &title);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([Constraint::Length(3), Constraint::Length(1)])
+                .split(area);
+            let Some([input_area, help_area]) =
+                chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+            else {
+                return;
+            };
+            let config = components::TextInputConfig::new(text.content(), " Entry ")
+                .with_placeholder("A fact worth remembering")
+                .with_cursor_position(text.cursor_position());
+            components::render_text_input(frame, *input_area, config);
+            render_form_help(frame, *help_area, false);
+        }
+        ProjectForm::Task { project, text } => {
+            let title = format!("New task for {}", project);
+            let area = components::render_modal_frame(frame, frame.area(), 60, 25, &title);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([Constraint::Length(3), Constraint::Length(1)])
+                .split(area);
+            let Some([input_area, help_area]) =
+                chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+            else {
+                return;
+            };
+            let config = components::TextInputConfig::new(text.content(), " Task ")
+                .with_placeholder("Something to get done, e.g. benchmark the parser")
+                .with_cursor_position(text.cursor_position());
+            components::render_text_input(frame, *input_area, config);
+            render_form_help(frame, *help_area, false);
+        }
+    }
+}
+
+fn render_form_help(frame: &mut Frame, area: Rect, has_fields: bool) {
+    let mut spans = vec![
+        Span::styled(" Enter ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" save  ", Style::default().fg(Color::White)),
+    ];
+    if has_fields {
+        spans.push(Span::styled(" Tab ", Style::default().fg(Color::Black).bg(Color::Yellow)));
+        spans.push(Span::styled(" switch field  ", Style::default().fg(Color::White)));
+    }
+    spans.push(Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)));
+    spans.push(Span::styled(" cancel", Style::default().fg(Color::White)));
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn render_detail_footer(frame: &mut Frame, focus: ProjectDetailFocus, area: Rect) {
+    let keys: &[(&str, &str)] = match focus {
+        ProjectDetailFocus::Entries => &[
+            ("↑↓", "navigate"),
+            ("A", "add entry"),
+            ("Del", "remove entry"),
+            ("C", "attach this chat"),
+            ("Tab", "tasks"),
+            ("Esc", "back"),
+        ],
+        ProjectDetailFocus::Tasks => &[
+            ("↑↓", "navigate"),
+            ("A", "add task"),
+            ("Space", "confirm / done"),
+            ("Del", "remove / reject"),
+            ("Tab", "entries"),
+            ("Esc", "back"),
+        ],
+    };
+    components::render_navigation_footer(frame, area, "PROJECT", keys, &[]);
 }
 
 // ── Helpers ─────────────────────────────────────────────────────────────────
@@ -0,0 +1,140 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::{App, PromptSection};
+use crate::ui::components;
+
+/// Renders the `/prompt` overlay: prompt sections with token counts and the selected section's text
+pub fn render_prompt_inspector(f: &mut Frame, app: &App) {
+    let title = match &app.prompt_preview {
+        Some(sections) => {
+            let total: usize = sections
+                .iter()
+                .filter(|part| app.is_prompt_section_enabled(part.section))
+                .map(crate::app::PromptSectionText::estimated_tokens)
+                .sum();
+            format!("System prompt (~{} tokens)", total)
+        }
+        None => "System prompt (assembling...)".to_string(),
+    };
+    let area = components::render_modal_frame(f, f.area(), 90, 80, &title);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    let Some([body, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(36), Constraint::Min(0)])
+        .split(*body);
+    if let [list_area, text_area] = &panes[..] {
+        render_section_list(f, app, *list_area);
+        render_section_text(f, app, *text_area);
+    }
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" section  ", Style::default().fg(Color::White)),
+        Span::styled(" PgUp/PgDn ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" scroll  ", Style::default().fg(Color::White)),
+        Span::styled(" Space ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" on/off for session  ", Style::default().fg(Color::White)),
+        Span::styled(" r ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" rebuild  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn render_section_list(f: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = PromptSection::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, section)| {
+            let is_selected = index == app.prompt_inspector_index;
+            let enabled = app.is_prompt_section_enabled(*section);
+            let tokens = app
+                .prompt_section_text(*section)
+                .map_or(0, crate::app::PromptSectionText::estimated_tokens);
+            let name_style = if !enabled {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else if tokens == 0 {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let count = if enabled {
+                format!("{:>6}", tokens)
+            } else {
+                format!("{:>6}", "off")
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(components::selection_prefix(is_selected)),
+                Span::styled(format!("{:<22}", section.label()), name_style),
+                Span::styled(count, Style::default().fg(Color::Cyan)),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.prompt_inspector_index));
+    f.render_stateful_widget(
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Sections · tokens ")
+                .border_style(Style::default().fg(Color::DarkGray)),
+        ),
+        area,
+        &mut state,
+    );
+}
+
+fn render_section_text(f: &mut Frame, app: &App, area: Rect) {
+    let Some(section) = app.selected_prompt_section() else {
+        return;
+    };
+    let (text, style) = match (app.prompt_preview.as_ref(), app.prompt_section_text(section)) {
+        (None, _) => ("Assembling prompt...".to_string(), Style::default().fg(Color::DarkGray)),
+        (Some(_), Some(part)) => (part.text.clone(), Style::default().fg(Color::White)),
+        (Some(_), None) if !app.is_prompt_section_enabled(section) => (
+            "Disabled for this session.".to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+        (Some(_), None) => (
+            "Not part of the prompt for this message.".to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    let title = if app.is_prompt_section_enabled(section) {
+        format!(" {} ", section.label())
+    } else {
+        format!(" {} (off — not sent) ", section.label())
+    };
+    f.render_widget(
+        Paragraph::new(Text::styled(text, style))
+            .wrap(Wrap { trim: false })
+            .scroll((u16::try_from(app.prompt_inspector_scroll).unwrap_or(u16::MAX), 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+        area,
+    );
+}
@@ -0,0 +1,235 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use std::ops::Range;
+
+use crate::app::App;
+use crate::services::fuzzy::match_ranges;
+use crate::ui::components;
+
+/// Characters of context shown before the first match in a result line
+const SNIPPET_LEAD_CHARS: usize = 24;
+
+pub fn render_search_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(3), // Query
+            Constraint::Min(0),    // Results + preview
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    if let [header, query, body, footer] = &chunks[..] {
+        let summary = if app.search_last_query.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " · {} matches in {} conversations",
+                app.search_hit_count(),
+                app.search_groups.len()
+            )
+        };
+        let extra = vec![Span::styled(summary, Style::default().fg(Color::DarkGray))];
+        components::render_view_header_with_extra(frame, *header, "Search", extra);
+
+        let config = components::TextInputConfig::new(app.search_input.content(), " Query ")
+            .with_placeholder("Words to find in any conversation...")
+            .with_cursor_position(app.search_input.cursor_position());
+        components::render_text_input(frame, *query, config);
+
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(*body);
+        if let [results, preview] = &panes[..] {
+            render_search_results(frame, app, *results);
+            render_search_preview(frame, app, *preview);
+        }
+
+        components::render_navigation_footer(
+            frame,
+            *footer,
+            "SEARCH",
+            &[("Enter", "search / open"), ("↑↓", "navigate"), ("Esc", "back")],
+            &[],
+        );
+        if let Some(message) = app.status_toast_message() {
+            let inner = footer.inner(Margin {
+                vertical: 1,
+                horizontal: 1,
+            });
+            components::render_status_toast(frame, inner, message);
+        }
+    }
+}
+
+fn render_search_results(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Results ")
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let empty_message = if let Some(error) = &app.search_error {
+        Some(error.clone())
+    } else if app.search_last_query.is_empty() {
+        Some("Type a query and press Enter".to_string())
+    } else if app.search_groups.is_empty() {
+        Some(format!("No messages match \"{}\"", app.search_last_query))
+    } else {
+        None
+    };
+    if let Some(message) = empty_message {
+        frame.render_widget(
+            Paragraph::new(Span::styled(message, Style::default().fg(Color::DarkGray)))
+                .wrap(Wrap { trim: true })
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let snippet_width = area.width.saturating_sub(14) as usize;
+    let mut items = Vec::new();
+    let mut selected_item = 0;
+    let mut hit_index = 0;
+    for group in &app.search_groups {
+        let date = chrono::DateTime::parse_from_rfc3339(&group.created_at)
+            .map_or_else(|_| group.created_at.clone(), |dt| dt.format("%b %d").to_string());
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(
+                group.title.clone(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  {}", date), Style::default().fg(Color::DarkGray)),
+        ])));
+
+        for hit in &group.hits {
+            let is_selected = hit_index == app.search_selected_index;
+            if is_selected {
+                selected_item = items.len();
+            }
+            let mut spans = vec![
+                Span::raw(components::selection_prefix(is_selected)),
+                Span::styled(
+                    format!("{:<10}", hit.role.to_lowercase()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            spans.extend(snippet_spans(&hit.content, &app.search_last_query, snippet_width));
+            items.push(ListItem::new(Line::from(spans)));
+            hit_index += 1;
+        }
+    }
+
+    let mut state = ListState::default();
+    state.select(Some(selected_item));
+    frame.render_stateful_widget(List::new(items).block(block), area, &mut state);
+}
+
+fn render_search_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((group, hit)) = app.selected_search_hit() else {
+        frame.render_widget(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Preview ")
+                .border_style(Style::default().fg(Color::DarkGray)),
+            area,
+        );
+        return;
+    };
+
+    let mut first_match_line = None;
+    let lines: Vec<Line> = hit
+        .content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let ranges = match_ranges(line, &app.search_last_query);
+            if first_match_line.is_none() && !ranges.is_empty() {
+                first_match_line = Some(index);
+            }
+            Line::from(highlighted_spans(line, &ranges, 0..line.len()))
+        })
+        .collect();
+
+    let title = format!(
+        " {} · {} · {} · score {:.2} ",
+        hit.role, hit.timestamp, group.agent_name, hit.score
+    );
+    let scroll = first_match_line.unwrap_or(0).saturating_sub(2);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+        area,
+    );
+}
+
+/// One-line excerpt of `content` starting shortly before the first match
+fn snippet_spans(content: &str, query: &str, max_chars: usize) -> Vec<Span<'static>> {
+    let flat = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    let ranges = match_ranges(&flat, query);
+    let first_match = ranges.first().map_or(0, |range| range.start);
+    let lead_start = flat
+        .get(..first_match)
+        .map_or(0, |before| {
+            before
+                .char_indices()
+                .rev()
+                .nth(SNIPPET_LEAD_CHARS)
+                .map_or(0, |(index, _)| index)
+        });
+    let window_end = flat
+        .get(lead_start..)
+        .and_then(|rest| rest.char_indices().nth(max_chars))
+        .map_or(flat.len(), |(offset, _)| lead_start + offset);
+
+    let mut spans = Vec::new();
+    if lead_start > 0 {
+        spans.push(Span::styled("…", Style::default().fg(Color::DarkGray)));
+    }
+    spans.extend(highlighted_spans(&flat, &ranges, lead_start..window_end));
+    spans
+}
+
+/// Spans for `text[window]` with the parts inside `ranges` highlighted
+fn highlighted_spans(text: &str, ranges: &[Range<usize>], window: Range<usize>) -> Vec<Span<'static>> {
+    let plain = Style::default().fg(Color::White);
+    let highlight = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let mut spans = Vec::new();
+    let mut position = window.start;
+    for range in ranges {
+        let start = range.start.max(window.start);
+        let end = range.end.min(window.end);
+        if start >= end {
+            continue;
+        }
+        if let Some(before) = text.get(position..start) {
+            spans.push(Span::styled(before.to_string(), plain));
+        }
+        if let Some(matched) = text.get(start..end) {
+            spans.push(Span::styled(matched.to_string(), highlight));
+        }
+        position = end;
+    }
+    if let Some(rest) = text.get(position..window.end) {
+        spans.push(Span::styled(rest.to_string(), plain));
+    }
+    spans
+}
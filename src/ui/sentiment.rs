@@ -0,0 +1,198 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::sentiment;
+use crate::storage::SentimentEntry;
+use crate::ui::components;
+
+/// Weeks averaged in the trend line
+const TREND_WEEKS: usize = 12;
+/// Rows in the emotion frequency lists
+const TOP_EMOTIONS: usize = 8;
+
+/// `/sentiment`: each conversation's tone over time, the weekly trend and the most
+/// frequent emotions of the user and Kimi
+pub fn render_sentiment_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),      // Header
+            Constraint::Length(1),      // Totals
+            Constraint::Percentage(45), // Chart
+            Constraint::Min(0),         // Lists
+            Constraint::Length(3),      // Footer
+        ])
+        .split(frame.area());
+    let [header, totals, chart, lists, footer] = &chunks[..] else {
+        return;
+    };
+
+    components::render_view_header(frame, *header, "Sentiment");
+    components::render_navigation_footer(frame, *footer, "SENTIMENT", &[("↑↓", "scroll"), ("Esc", "back")], &[]);
+
+    let entries = &app.sentiment_timeline;
+    let label = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let average = if entries.is_empty() {
+        0.0
+    } else {
+        entries.iter().map(|entry| entry.valence).sum::<f32>() / entries.len() as f32
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Scored conversations ", label),
+            Span::styled(entries.len().to_string(), value),
+            Span::styled("   Average ", label),
+            Span::styled(
+                format!("{:+.2} ({})", average, sentiment::describe_valence(average)),
+                Style::default().fg(valence_color(average)).add_modifier(Modifier::BOLD),
+            ),
+        ])),
+        *totals,
+    );
+
+    render_timeline(frame, entries, *chart);
+
+    let list_columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(20), Constraint::Percentage(20)])
+        .split(*lists);
+    if let [conversations, user_emotions, kimi_emotions] = &list_columns[..] {
+        render_conversations(frame, entries, app.sentiment_scroll, *conversations);
+        render_emotions(frame, &sentiment::emotion_counts(entries, false), "Your emotions", *user_emotions);
+        render_emotions(frame, &sentiment::emotion_counts(entries, true), "Kimi's emotions", *kimi_emotions);
+    }
+}
+
+fn panel(title: &str) -> Block<'_> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", title))
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
+fn valence_color(valence: f32) -> Color {
+    if valence >= 0.15 {
+        Color::Green
+    } else if valence > -0.15 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+/// Conversations as dots by day, with the weekly average as a line over the last weeks
+fn render_timeline(frame: &mut Frame, entries: &[SentimentEntry], area: Rect) {
+    let today = chrono::Local::now().date_naive();
+    let weeks = sentiment::weekly_valence(entries, today, TREND_WEEKS);
+    let Some(first_day) = weeks.first().map(|(week, _)| *week) else {
+        return;
+    };
+    let day_offset = |date: chrono::NaiveDate| (date - first_day).num_days() as f64;
+
+    let conversations: Vec<(f64, f64)> = entries
+        .iter()
+        .filter_map(|entry| Some((sentiment::entry_date(entry)?, entry.valence)))
+        .filter(|(date, _)| *date >= first_day)
+        .map(|(date, valence)| (day_offset(date), f64::from(valence)))
+        .collect();
+    // Weekly averages sit in the middle of their week
+    let trend: Vec<(f64, f64)> = weeks
+        .iter()
+        .filter_map(|(week, average)| Some((day_offset(*week) + 3.0, f64::from((*average)?))))
+        .collect();
+    let last_day = day_offset(today).max(1.0);
+
+    let datasets = vec![
+        Dataset::default()
+            .name("conversation")
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&conversations),
+        Dataset::default()
+            .name("weekly average")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&trend),
+    ];
+    let chart = Chart::new(datasets)
+        .block(panel("Sentiment over time"))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, last_day])
+                .labels([first_day.format("%b %-d").to_string(), today.format("%b %-d").to_string()]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([-1.0, 1.0])
+                .labels(["-1".to_string(), "0".to_string(), "+1".to_string()]),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// Newest first, each with its score and emotions
+fn render_conversations(frame: &mut Frame, entries: &[SentimentEntry], scroll: usize, area: Rect) {
+    let lines: Vec<Line> = if entries.is_empty() {
+        vec![Line::from(Span::styled(
+            "No conversations scored yet; tone is recorded when a conversation is saved",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        entries
+            .iter()
+            .rev()
+            .skip(scroll)
+            .map(|entry| {
+                let date = sentiment::entry_date(entry)
+                    .map(|date| date.format("%b %d").to_string())
+                    .unwrap_or_default();
+                let title: String = entry.title.lines().next().unwrap_or_default().chars().take(48).collect();
+                let mut spans = vec![
+                    Span::styled(format!("{:<7}", date), Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        format!("{:+.2} ", entry.valence),
+                        Style::default().fg(valence_color(entry.valence)),
+                    ),
+                    Span::styled(title, Style::default().fg(Color::White)),
+                ];
+                if !entry.emotions.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", entry.emotions.join(", ")),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).block(panel("Conversations")), area);
+}
+
+fn render_emotions(frame: &mut Frame, counts: &[(String, usize)], title: &str, area: Rect) {
+    let lines: Vec<Line> = if counts.is_empty() {
+        vec![Line::from(Span::styled("None recorded yet", Style::default().fg(Color::DarkGray)))]
+    } else {
+        counts
+            .iter()
+            .take(TOP_EMOTIONS)
+            .map(|(emotion, count)| {
+                Line::from(vec![
+                    Span::styled(format!("{:>4} ", count), Style::default().fg(Color::Yellow)),
+                    Span::styled(emotion.clone(), Style::default().fg(Color::White)),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).block(panel(title)), area);
+}
@@ -0,0 +1,160 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::settings::{self, SETTINGS, SettingKind};
+use crate::ui::components;
+
+/// `/settings`: config values grouped by section, edited in place
+pub fn render_settings_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Fields
+            Constraint::Length(3), // Input or error
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+    let [header, list, input, footer] = &chunks[..] else {
+        return;
+    };
+
+    let path = crate::config::Config::project_config_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    components::render_view_header_with_extra(
+        frame,
+        *header,
+        "Settings",
+        vec![Span::styled(format!(" · {}", path), Style::default().fg(Color::DarkGray))],
+    );
+    render_settings_list(frame, app, *list);
+    render_settings_input(frame, app, *input);
+    render_settings_footer(frame, app, *footer);
+}
+
+fn render_settings_list(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(config) = &app.settings_config else {
+        return;
+    };
+    let mut items = Vec::new();
+    let mut selected_row = 0;
+    let mut previous_section = "";
+    for (index, field) in SETTINGS.iter().enumerate() {
+        if field.section != previous_section {
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!(" {}", field.section),
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            ))));
+            previous_section = field.section;
+        }
+        let is_selected = index == app.settings_selected_index;
+        if is_selected {
+            selected_row = items.len();
+        }
+        let value = settings::current_value(config, field);
+        let value_span = match field.kind {
+            SettingKind::Toggle => {
+                let enabled = value == "true";
+                Span::styled(
+                    if enabled { "● on" } else { "○ off" },
+                    Style::default().fg(if enabled { Color::Green } else { Color::DarkGray }),
+                )
+            }
+            SettingKind::Text
+            | SettingKind::Url
+            | SettingKind::Integer { .. }
+            | SettingKind::Decimal { .. }
+            | SettingKind::Choice(_) => Span::styled(
+                if value.is_empty() { "(empty)".to_string() } else { value },
+                Style::default().fg(Color::White),
+            ),
+        };
+        let mut spans = vec![
+            Span::styled(components::selection_prefix(is_selected), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:<30}", field.label), components::selected_name_style(is_selected)),
+            value_span,
+        ];
+        if field.needs_restart {
+            spans.push(Span::styled("  (restart)", Style::default().fg(Color::DarkGray)));
+        }
+        items.push(ListItem::new(Line::from(spans)));
+    }
+
+    let mut state = ListState::default();
+    state.select(Some(selected_row));
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_settings_input(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(input) = &app.settings_input {
+        let title = app
+            .selected_setting()
+            .map(|field| format!(" {} ", field.label))
+            .unwrap_or_default();
+        let config = components::TextInputConfig::new(input.content(), &title)
+            .with_cursor_position(input.cursor_position())
+            .with_title_style(Style::default().fg(Color::White));
+        components::render_text_input(frame, area, config);
+        if let Some(error) = &app.settings_error {
+            // Shown on the input's bottom border so the value stays visible
+            let error_area = Rect::new(
+                area.x + 2,
+                area.y + area.height.saturating_sub(1),
+                area.width.saturating_sub(4),
+                1,
+            );
+            frame.render_widget(
+                Paragraph::new(Span::styled(format!(" {} ", error), Style::default().fg(Color::Red))),
+                error_area,
+            );
+        }
+        return;
+    }
+    let line = match (&app.settings_error, app.selected_setting()) {
+        (Some(error), _) => Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))),
+        (None, Some(field)) => Line::from(Span::styled(
+            format!("[{}]", field.path),
+            Style::default().fg(Color::DarkGray),
+        )),
+        (None, None) => Line::from(""),
+    };
+    frame.render_widget(
+        Paragraph::new(line).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        ),
+        area,
+    );
+}
+
+fn render_settings_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let keys: &[(&str, &str)] = if app.settings_input.is_some() {
+        &[("Enter", "save"), ("Esc", "cancel")]
+    } else if app.selected_setting_is_typed() {
+        &[("↑↓", "navigate"), ("Enter", "edit"), ("Esc", "back")]
+    } else {
+        &[("↑↓", "navigate"), ("Enter", "change"), ("Esc", "back")]
+    };
+    components::render_navigation_footer(frame, area, "SETTINGS", keys, &[]);
+
+    if let Some(message) = app.status_toast_message() {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        components::render_status_toast(frame, inner, message);
+    }
+}
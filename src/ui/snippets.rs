@@ -0,0 +1,195 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::services::clipboard::entry_title;
+use crate::ui::components;
+
+// ── Snippet List View ───────────────────────────────────────────────────────
+
+pub fn render_snippet_view(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Snippet list + preview
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    if let [header, content, footer] = &chunks[..] {
+        render_snippet_header(frame, app, *header);
+        render_snippet_content(frame, app, *content);
+        render_snippet_footer(frame, app, *footer);
+    }
+}
+
+fn render_snippet_header(frame: &mut Frame, app: &App, area: Rect) {
+    let (title, count) = if app.snippets_show_clipboard {
+        ("Clipboard", format!("({} copied this session)", app.snippet_entry_count()))
+    } else {
+        ("Snippets", format!("({} saved)", app.snippet_entry_count()))
+    };
+    let extra = vec![
+        Span::styled(" ", Style::default()),
+        Span::styled(count, Style::default().fg(Color::DarkGray)),
+    ];
+    components::render_view_header_with_extra(frame, area, title, extra);
+}
+
+fn render_snippet_content(frame: &mut Frame, app: &App, area: Rect) {
+    if app.snippet_entry_count() == 0 {
+        let empty = if app.snippets_show_clipboard {
+            "Nothing copied yet. Texts you copy from Kimi show up here until you quit."
+        } else {
+            "No snippets yet. Press Ctrl+S in chat to save code blocks from the last response."
+        };
+        let message = Paragraph::new(empty)
+            .style(Style::default().fg(Color::DarkGray))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    if let [list_area, preview_area] = &columns[..] {
+        render_snippet_list(frame, app, *list_area);
+        render_snippet_preview(frame, app, *preview_area);
+    }
+}
+
+fn render_snippet_list(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = if app.snippets_show_clipboard {
+        app.clipboard_service
+            .history()
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let is_selected = index == app.snippet_selected_index;
+                ListItem::new(Line::from(vec![
+                    Span::raw(components::selection_prefix(is_selected)),
+                    Span::styled(entry_title(text), components::selected_name_style(is_selected)),
+                ]))
+            })
+            .collect()
+    } else {
+        app.snippets
+            .iter()
+            .enumerate()
+            .map(|(index, snippet)| {
+                let is_selected = index == app.snippet_selected_index;
+                let mut spans = vec![
+                    Span::raw(components::selection_prefix(is_selected)),
+                    Span::styled(snippet.title.as_str(), components::selected_name_style(is_selected)),
+                ];
+                if !snippet.language.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" [{}]", snippet.language),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    state.select(Some(app.snippet_selected_index));
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_snippet_preview(frame: &mut Frame, app: &App, area: Rect) {
+    if app.snippets_show_clipboard {
+        let content = app
+            .clipboard_service
+            .history()
+            .get(app.snippet_selected_index)
+            .map_or("", String::as_str);
+        let preview = Paragraph::new(content)
+            .style(Style::default().fg(Color::White))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Preview ")
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        frame.render_widget(preview, area);
+        return;
+    }
+    let selected = app.snippets.get(app.snippet_selected_index);
+    let content = selected.map_or("", |snippet| snippet.content.as_str());
+    let title = selected
+        .and_then(|snippet| snippet.created_at.get(..10))
+        .map_or_else(|| " Preview ".to_string(), |date| format!(" Preview · saved {} ", date));
+    let preview = Paragraph::new(content)
+        .style(Style::default().fg(Color::White))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+    frame.render_widget(preview, area);
+}
+
+fn render_snippet_footer(frame: &mut Frame, app: &App, area: Rect) {
+    if app.snippets_show_clipboard {
+        components::render_navigation_footer(
+            frame,
+            area,
+            "CLIPBOARD",
+            &[
+                ("↑↓", "navigate"),
+                ("Enter", "insert"),
+                ("c", "copy"),
+                ("s", "save as snippet"),
+                ("d", "remove"),
+                ("Tab", "snippets"),
+                ("Esc", "back"),
+            ],
+            &[],
+        );
+    } else {
+        components::render_navigation_footer(
+            frame,
+            area,
+            "SNIPPETS",
+            &[
+                ("↑↓", "navigate"),
+                ("Enter", "insert"),
+                ("c", "copy"),
+                ("d", "delete"),
+                ("Tab", "clipboard"),
+                ("Esc", "back"),
+            ],
+            &[],
+        );
+    }
+
+    if let Some(message) = app.status_toast_message() {
+        let inner = area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+        components::render_status_toast(frame, inner, message);
+    }
+}
@@ -0,0 +1,97 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::citations::{Source, SourceKind};
+use crate::ui::components;
+
+/// Renders `/sources`: the numbered sources of the last reply, cited ones marked
+pub fn render_sources(f: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(f, f.area(), 80, 70, "Sources of the last reply");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([list_area, help_area]) =
+        chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let cited = app.last_reply_cited();
+    let items: Vec<ListItem> = app
+        .last_reply_sources()
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+            source_item(
+                source,
+                index + 1,
+                cited.contains(&(index + 1)),
+                index == app.sources_selected_index,
+            )
+        })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(app.sources_selected_index));
+    f.render_stateful_widget(List::new(items), *list_area, &mut state);
+
+    if let Some(message) = app.status_toast_message() {
+        components::render_status_toast(f, *help_area, message);
+        return;
+    }
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" select  ", Style::default().fg(Color::White)),
+        Span::styled(" Enter ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" open link  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close  ", Style::default().fg(Color::White)),
+        Span::styled("● cited in the reply", Style::default().fg(Color::Green)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn source_item(source: &Source, number: usize, is_cited: bool, is_selected: bool) -> ListItem<'static> {
+    let color = match source.kind {
+        SourceKind::Memory => Color::Magenta,
+        SourceKind::Note => Color::Cyan,
+        SourceKind::Web => Color::Blue,
+        SourceKind::ToolOutput => Color::Yellow,
+    };
+    let location = match source.kind {
+        SourceKind::Memory => format!("conversation of {}", source.location),
+        SourceKind::ToolOutput => format!("stored as tool output {}", source.location),
+        SourceKind::Note | SourceKind::Web => source.location.clone(),
+    };
+    ListItem::new(vec![
+        Line::from(vec![
+            Span::styled(
+                components::selection_prefix(is_selected),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                if is_cited { "● " } else { "  " },
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                format!("[{}] ", number),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{:<12}", source.kind.label()), Style::default().fg(color)),
+            Span::styled(source.title.clone(), components::selected_name_style(is_selected)),
+        ]),
+        Line::from(Span::styled(
+            format!("          {}", location),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+}
@@ -0,0 +1,120 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::services::{backup, embeddings};
+use crate::services::health::HealthLevel;
+use crate::ui::components;
+
+#[must_use]
+pub fn level_color(level: HealthLevel) -> Color {
+    match level {
+        HealthLevel::Ok => Color::Green,
+        HealthLevel::Degraded => Color::Yellow,
+        HealthLevel::Down => Color::Red,
+    }
+}
+
+/// Renders the `/status` overlay: one entry per backend with what failed and how to fix it
+pub fn render_status(f: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(f, f.area(), 70, 60, "Status");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([body_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let mut lines = Vec::new();
+    if app.health.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Checking connections...",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for service in &app.health {
+        lines.push(Line::from(vec![
+            Span::styled("● ", Style::default().fg(level_color(service.level))),
+            Span::styled(
+                service.name,
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  {}", service.detail), Style::default().fg(Color::Gray)),
+        ]));
+        if let Some(fix) = &service.fix {
+            lines.push(Line::from(vec![
+                Span::styled("  fix: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(fix.clone(), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.push(backup_line(app));
+    lines.push(embeddings_line());
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), *body_area);
+
+    let checked = if app.health_checking {
+        "checking…".to_string()
+    } else {
+        app.health_checked_at
+            .map(|at| format!("checked {}s ago", at.elapsed().as_secs()))
+            .unwrap_or_default()
+    };
+    let help = Line::from(vec![
+        Span::styled(" R ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" re-check  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close  ", Style::default().fg(Color::White)),
+        Span::styled(checked, Style::default().fg(Color::DarkGray)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+/// Age of the newest snapshot; yellow once a scheduled backup is overdue
+fn backup_line(app: &App) -> Line<'static> {
+    let age = app.last_backup.and_then(|at| at.elapsed().ok());
+    let overdue = age.is_none_or(|age| age.as_secs() > app.backup.interval_hours.max(1) * 2 * 3600);
+    let (color, detail) = match age {
+        Some(age) if app.backup.enabled => (
+            if overdue { Color::Yellow } else { Color::Green },
+            format!(
+                "last {} · every {} h, keeping {}",
+                backup::format_age(age),
+                app.backup.interval_hours,
+                app.backup.keep
+            ),
+        ),
+        Some(age) => (Color::DarkGray, format!("last {} · schedule off ([backup] enabled)", backup::format_age(age))),
+        None if app.backup.enabled => (Color::Yellow, "none yet".to_string()),
+        None => (Color::DarkGray, "none yet · schedule off ([backup] enabled)".to_string()),
+    };
+    Line::from(vec![
+        Span::styled("● ", Style::default().fg(color)),
+        Span::styled("Backups", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  {}", detail), Style::default().fg(Color::Gray)),
+    ])
+}
+
+/// Throughput of the last batched embedding run, so slow backfills are visible
+fn embeddings_line() -> Line<'static> {
+    let (color, detail) = match embeddings::last_stats() {
+        Some(stats) if stats.failed_batches > 0 => (Color::Yellow, stats.describe()),
+        Some(stats) => (Color::Green, stats.describe()),
+        None => (Color::DarkGray, "no batch run yet".to_string()),
+    };
+    Line::from(vec![
+        Span::styled("● ", Style::default().fg(color)),
+        Span::styled("Embeddings", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  {}", detail), Style::default().fg(Color::Gray)),
+    ])
+}
@@ -0,0 +1,96 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::services::projects::TaskStatus;
+use crate::storage::DueTask;
+use crate::ui::components;
+
+/// Renders the `/tasks` overlay, soonest due first
+pub fn render_tasks(f: &mut Frame, app: &App) {
+    let proposed = app
+        .due_tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Proposed)
+        .count();
+    let title = format!("Tasks ({} proposed)", proposed);
+    let area = components::render_modal_frame(f, f.area(), 90, 75, &title);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([list_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    if app.due_tasks.is_empty() {
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No tasks yet. Things you plan to do by a date are proposed here after a conversation is saved.",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            *list_area,
+        );
+    } else {
+        let today = chrono::Local::now().date_naive();
+        let items: Vec<ListItem> = app
+            .due_tasks
+            .iter()
+            .enumerate()
+            .map(|(index, task)| task_item(task, index == app.due_tasks_selected_index, today))
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(app.due_tasks_selected_index));
+        f.render_stateful_widget(List::new(items), *list_area, &mut state);
+    }
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" select  ", Style::default().fg(Color::White)),
+        Span::styled(" Enter ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" accept  ", Style::default().fg(Color::White)),
+        Span::styled(" C ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" complete  ", Style::default().fg(Color::White)),
+        Span::styled(" S ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" snooze day  ", Style::default().fg(Color::White)),
+        Span::styled(" W ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" snooze week  ", Style::default().fg(Color::White)),
+        Span::styled(" D ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" delete  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close", Style::default().fg(Color::White)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
+
+fn task_item(task: &DueTask, is_selected: bool, today: chrono::NaiveDate) -> ListItem<'static> {
+    let (label, label_color) = match task.status {
+        TaskStatus::Proposed => ("proposed", Color::Magenta),
+        TaskStatus::Open => ("open    ", Color::Green),
+        TaskStatus::Done => ("done    ", Color::DarkGray),
+    };
+    let (due, due_color) = match chrono::NaiveDate::parse_from_str(&task.due, "%Y-%m-%d") {
+        Ok(date) if date < today => (crate::services::tasks::describe_due(date, today), Color::Red),
+        Ok(date) => (crate::services::tasks::describe_due(date, today), Color::Yellow),
+        Err(_) => (task.due.clone(), Color::DarkGray),
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            components::selection_prefix(is_selected),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(label, Style::default().fg(label_color)),
+        Span::styled(format!(" {:<16} ", due), Style::default().fg(due_color)),
+        Span::styled(task.text.clone(), components::selected_name_style(is_selected)),
+    ]))
+}
@@ -0,0 +1,97 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::app::App;
+use crate::services::usage::{self, BudgetState};
+use crate::ui::components;
+
+/// Renders the `/usage` overlay: today's budgets, then tokens and cost per day and model
+pub fn render_usage(f: &mut Frame, app: &App) {
+    let area = components::render_modal_frame(f, f.area(), 80, 70, "Usage");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let Some([body_area, help_area]) = chunks.get(0..2).and_then(|s| <&[_; 2]>::try_from(s).ok())
+    else {
+        return;
+    };
+
+    let today = usage::today();
+    let today_rows: Vec<_> = app.usage_rows.iter().filter(|row| row.day == today).cloned().collect();
+    let mut lines = Vec::new();
+    let budgets = usage::budget_lines(&app.usage, &today_rows);
+    if budgets.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No daily budget set (see [usage] in config.toml)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for budget in &budgets {
+        let color = match budget.state {
+            BudgetState::Under => Color::Green,
+            BudgetState::Warning => Color::Yellow,
+            BudgetState::Exceeded => Color::Red,
+        };
+        lines.push(Line::from(vec![
+            Span::styled("● ", Style::default().fg(color)),
+            Span::styled(budget.describe(), Style::default().fg(Color::White)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    if app.usage_rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No Venice or Gab requests in the last seven days",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let mut current_day = None;
+    for row in &app.usage_rows {
+        if current_day != Some(&row.day) {
+            current_day = Some(&row.day);
+            let day_cost: f64 = app
+                .usage_rows
+                .iter()
+                .filter(|other| other.day == row.day)
+                .map(|other| other.cost_usd)
+                .sum();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    row.day.clone(),
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  ${:.4}", day_cost), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<7}", row.provider), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:<36}", row.model), Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!(
+                    "{:>4} req  {:>9} in  {:>8} out  ${:.4}",
+                    row.requests, row.prompt_tokens, row.completion_tokens, row.cost_usd
+                ),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+    }
+    f.render_widget(Paragraph::new(lines).scroll((app.usage_scroll, 0)), *body_area);
+
+    let help = Line::from(vec![
+        Span::styled(" ↑↓ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" scroll  ", Style::default().fg(Color::White)),
+        Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(" close  ", Style::default().fg(Color::White)),
+        Span::styled("costs are estimates from [usage.prices]", Style::default().fg(Color::DarkGray)),
+    ]);
+    f.render_widget(Paragraph::new(help), *help_area);
+}
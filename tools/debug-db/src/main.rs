@@ -119,7 +119,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
     
-    let db: Surreal<Db> = Surreal::new::<RocksDb>(db_path).await?;
+    let db: Surreal<Db> = match Surreal::new::<RocksDb>(db_path.clone()).await {
+        Ok(db) => db,
+        Err(error) if error.to_string().contains("While lock file") => {
+            // kimi writes its PID next to the database while it has it open
+            let pid = db_path
+                .parent()
+                .and_then(|dir| std::fs::read_to_string(dir.join("kimi.pid")).ok())
+                .map_or_else(|| "unknown PID".to_string(), |pid| format!("PID {}", pid.trim()));
+            println!("   ERROR: Database is in use by a running kimi ({}); close it and retry", pid);
+            return Ok(());
+        }
+        Err(error) => return Err(error.into()),
+    };
     db.use_ns("kimi").use_db("main").await?;
     println!("   SUCCESS: Connected to database");
 